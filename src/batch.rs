@@ -0,0 +1,166 @@
+//! Batch processing of many saves
+//!
+//! [`process_dir`] parses every save in a directory on a pool of threads and collects the result
+//! of a callback, or the error that stopped the file from being read, for each of them. Only one
+//! file per thread is held in memory at a time, so thousands of saves can be validated without
+//! keeping them all around.
+//!
+//! [`process_dir`]: crate::batch::process_dir
+//!
+//! ```
+//! use gvas::batch::{process_dir, BatchOptions};
+//!
+//! let options = BatchOptions {
+//!     extension: Some(String::from("sav")),
+//!     ..Default::default()
+//! };
+//! let results = process_dir("resources/test", &options, |_path, file| file.properties.len())?;
+//! for result in &results {
+//!     match &result.result {
+//!         Ok(count) => println!("{}: {count} properties", result.path.display()),
+//!         Err(e) => println!("{}: {e}", result.path.display()),
+//!     }
+//! }
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+
+use std::{
+    fs, io,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use crate::{
+    error::Error, game_version::GameVersion, hints::Hints, options::ReadOptions, GvasFile,
+};
+
+/// Options used by [`process_dir`].
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    /// Number of threads, `0` uses the available parallelism.
+    pub threads: usize,
+    /// Only process files with this extension, compared case-insensitively.
+    pub extension: Option<String>,
+    /// Also process the files in subdirectories.
+    pub recursive: bool,
+    /// Game version used for every file.
+    pub game_version: GameVersion,
+    /// Hints used for every file.
+    pub hints: Hints,
+    /// Read options used for every file.
+    pub read_options: ReadOptions,
+}
+
+impl Default for BatchOptions {
+    #[inline]
+    fn default() -> Self {
+        BatchOptions {
+            threads: 0,
+            extension: None,
+            recursive: false,
+            game_version: GameVersion::Auto,
+            hints: Hints::new(),
+            read_options: ReadOptions::default(),
+        }
+    }
+}
+
+/// The outcome of processing a single file.
+#[derive(Debug)]
+pub struct FileResult<T> {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Value returned by the callback, or the error that stopped the file from being read.
+    pub result: Result<T, Error>,
+}
+
+/// Read every save in `dir` and call `f` with each of them.
+///
+/// Results are returned in the order of the file paths. A panic in `f` is propagated once all
+/// threads have stopped.
+///
+/// # Errors
+///
+/// If the directory can't be listed this function returns [`Error`]. Errors reading a single
+/// file are stored in its [`FileResult`] instead.
+pub fn process_dir<T, F>(
+    dir: impl AsRef<Path>,
+    options: &BatchOptions,
+    f: F,
+) -> Result<Vec<FileResult<T>>, Error>
+where
+    T: Send,
+    F: Fn(&Path, GvasFile) -> T + Sync,
+{
+    let mut paths = Vec::new();
+    list_files(dir.as_ref(), options, &mut paths)?;
+    paths.sort();
+
+    let threads = match options.threads {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        threads => threads,
+    }
+    .min(paths.len())
+    .max(1);
+
+    let next = AtomicUsize::new(0);
+    let process = || {
+        let mut results = Vec::new();
+        loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some(path) = paths.get(index) else {
+                break;
+            };
+            let result = GvasFile::open(
+                path,
+                options.game_version,
+                &options.hints,
+                &options.read_options,
+            )
+            .map(|file| f(path, file));
+            results.push((index, result));
+        }
+        results
+    };
+
+    let mut results = thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|_| scope.spawn(process))
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .flat_map(|worker| match worker.join() {
+                Ok(results) => results,
+                Err(panic) => std::panic::resume_unwind(panic),
+            })
+            .collect::<Vec<_>>()
+    });
+    results.sort_by_key(|(index, _)| *index);
+
+    Ok(paths
+        .into_iter()
+        .zip(results)
+        .map(|(path, (_, result))| FileResult { path, result })
+        .collect())
+}
+
+fn list_files(dir: &Path, options: &BatchOptions, paths: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        if file_type.is_dir() {
+            if options.recursive {
+                list_files(&path, options, paths)?;
+            }
+        } else if options.extension.as_ref().is_none_or(|extension| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+        }) {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,703 @@
+//! Borrowed views of saves
+//!
+//! [`GvasFileRef`] parses uncompressed GVAS data from a byte slice for read-only analysis.
+//! Property names, type names and string values borrow from the slice instead of being copied
+//! into a new `String` each, and byte arrays and binary structs like `Vector` are kept as
+//! slices of the input. Only strings stored as UTF-16 are decoded into owned strings.
+//!
+//! Saves in a container, like Palworld saves, are decompressed first with
+//! [`GvasFile::decompress_raw`]. Values with a type this view doesn't model, like
+//! `TextProperty`, are kept as the raw bytes of their body.
+//!
+//! [`GvasFile::decompress_raw`]: crate::GvasFile::decompress_raw
+//!
+//! ```
+//! use gvas::{borrowed::GvasFileRef, error::Error};
+//!
+//! let data = std::fs::read("resources/test/Slot1.sav")?;
+//! let file = GvasFileRef::parse(&data)?;
+//!
+//! assert_eq!(file.properties["int32_test"].value.as_i64(), Some(-2147483647));
+//! assert_eq!(file.properties["str_property"].value.as_str(), Some("Hello world"));
+//!
+//! let field = file.properties["struct_property"].value.field("test_field");
+//! assert_eq!(field.and_then(|field| field.value.as_i64()), Some(12345));
+//! # Ok::<(), Error>(())
+//! ```
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    io::{self, Cursor, ErrorKind},
+    str,
+};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use indexmap::IndexMap;
+
+use crate::{
+    cursor_ext::{ReadExt, DEFAULT_MAX_STRING_LEN},
+    error::{DeserializeError, Error},
+    hints::{HintValue, Hints},
+    object_version::{EUnrealEngineObjectUE4Version, PackageVersion},
+    options::{ReadOptions, WriteOptions},
+    properties::{
+        complete_type_names_unsupported,
+        property_kind::PropertyKind,
+        struct_property::StructProperty,
+        type_names::{
+            DATE_TIME, GUID, INT64_VECTOR, INT_POINT, INT_VECTOR, INT_VECTOR4, LINEAR_COLOR, QUAT,
+            ROTATOR, TIMESPAN, UINT_VECTOR, VECTOR, VECTOR2D,
+        },
+        Property, PropertyOptions,
+    },
+    read_trailing_padding,
+    types::{map::HashableIndexMap, Guid},
+    GvasHeader,
+};
+
+/// Struct types stored as binary data instead of tagged properties.
+const BINARY_STRUCTS: [&str; 13] = [
+    DATE_TIME,
+    GUID,
+    INT64_VECTOR,
+    INT_POINT,
+    INT_VECTOR,
+    INT_VECTOR4,
+    LINEAR_COLOR,
+    QUAT,
+    ROTATOR,
+    TIMESPAN,
+    UINT_VECTOR,
+    VECTOR,
+    VECTOR2D,
+];
+
+/// A save parsed from a byte slice, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GvasFileRef<'a> {
+    /// GVAS file header.
+    pub header: GvasHeader,
+    /// GVAS properties.
+    pub properties: IndexMap<Cow<'a, str>, PropertyRef<'a>>,
+    /// The padding after the `None` terminator of the properties, `None` if the file ends
+    /// without it.
+    pub trailing_padding: Option<i32>,
+    /// The bytes after the `None` terminator when the file ends before a full padding value.
+    pub trailing_bytes: Vec<u8>,
+}
+
+/// A property with a tag, a root property or a struct field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyRef<'a> {
+    /// Type of the property, e.g. `IntProperty`.
+    pub property_type: Cow<'a, str>,
+    /// Index of the property when it's an element of a static array.
+    pub array_index: u32,
+    /// Value of the property.
+    pub value: ValueRef<'a>,
+}
+
+/// A value borrowed from the data of a [`GvasFileRef`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueRef<'a> {
+    /// A `BoolProperty` value.
+    Bool(bool),
+    /// An `Int8Property`, `Int16Property`, `IntProperty` or `Int64Property` value.
+    Int(i64),
+    /// A `UInt16Property`, `UInt32Property` or `UInt64Property` value.
+    UInt(u64),
+    /// A `FloatProperty` value.
+    Float(f32),
+    /// A `DoubleProperty` value.
+    Double(f64),
+    /// A `ByteProperty` holding a byte.
+    Byte(u8),
+    /// The values of an array or set of `ByteProperty` bytes.
+    Bytes(&'a [u8]),
+    /// A `StrProperty`, `NameProperty` or `ObjectProperty` value, `None` for a null string.
+    Str(Option<Cow<'a, str>>),
+    /// An `EnumProperty` value, or a `ByteProperty` holding an enum value.
+    Enum {
+        /// Enum type, only stored in property tags.
+        enum_type: Option<Cow<'a, str>>,
+        /// Enum value.
+        value: Cow<'a, str>,
+    },
+    /// A struct made of tagged properties.
+    Struct {
+        /// Struct type.
+        type_name: Cow<'a, str>,
+        /// Fields in the order they are stored.
+        fields: Vec<(Cow<'a, str>, PropertyRef<'a>)>,
+    },
+    /// A struct stored as binary data, like `Vector` or `Guid`.
+    BinaryStruct {
+        /// Struct type.
+        type_name: Cow<'a, str>,
+        /// Serialized struct.
+        data: &'a [u8],
+    },
+    /// The elements of an `ArrayProperty` or `SetProperty`.
+    Array {
+        /// Type of the elements.
+        element_type: Cow<'a, str>,
+        /// Elements in the order they are stored.
+        elements: Vec<ValueRef<'a>>,
+    },
+    /// The entries of a `MapProperty`.
+    Map {
+        /// Type of the keys.
+        key_type: Cow<'a, str>,
+        /// Type of the values.
+        value_type: Cow<'a, str>,
+        /// Entries in the order they are stored.
+        entries: Vec<(ValueRef<'a>, ValueRef<'a>)>,
+    },
+    /// The serialized body of a value of another type.
+    Raw(&'a [u8]),
+}
+
+impl<'a> GvasFileRef<'a> {
+    /// Parse uncompressed GVAS data
+    ///
+    /// # Errors
+    ///
+    /// If the data is invalid this function returns [`Error`]
+    #[inline]
+    pub fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        Self::parse_with_hints(data, Hints::new())
+    }
+
+    /// Parse uncompressed GVAS data using hints for values stored without a type, see
+    /// [`hints`](crate::hints)
+    ///
+    /// # Errors
+    ///
+    /// If the data is invalid, or a struct stored without a type has no hint, this function
+    /// returns [`Error`]
+    pub fn parse_with_hints(data: &'a [u8], hints: impl Into<Hints>) -> Result<Self, Error> {
+        let mut cursor = Cursor::new(data);
+        let header = GvasHeader::read(&mut cursor)?;
+
+        let mut parser = Parser {
+            cursor,
+            hints: hints.into().into_inner(),
+            properties_stack: Vec::new(),
+            custom_versions: header.get_custom_versions(),
+            package_version: header.get_package_version(),
+            read_options: ReadOptions::default(),
+            write_options: WriteOptions::default(),
+        };
+        let mut properties = IndexMap::new();
+        loop {
+            let name = parser.read_string()?;
+            if name == "None" {
+                break;
+            }
+            let property_type = parser.read_string()?;
+            parser.push(&name);
+            let property = parser.read_tagged(property_type)?;
+            parser.pop();
+            properties.insert(name, property);
+        }
+        let (trailing_padding, trailing_bytes) = read_trailing_padding(&mut parser.cursor)?;
+
+        Ok(GvasFileRef {
+            header,
+            properties,
+            trailing_padding,
+            trailing_bytes,
+        })
+    }
+
+    /// The root property named `name`.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&PropertyRef<'a>> {
+        self.properties.get(name)
+    }
+}
+
+impl<'a> ValueRef<'a> {
+    /// The first field of a struct named `name`.
+    pub fn field(&self, name: &str) -> Option<&PropertyRef<'a>> {
+        match self {
+            ValueRef::Struct { fields, .. } => fields
+                .iter()
+                .find(|(field_name, _)| field_name == name)
+                .map(|(_, property)| property),
+            _ => None,
+        }
+    }
+
+    /// The value of a string or enum.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ValueRef::Str(value) => value.as_deref(),
+            ValueRef::Enum { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The value of a signed or unsigned integer or byte, if it fits in an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            ValueRef::Int(value) => Some(value),
+            ValueRef::UInt(value) => i64::try_from(value).ok(),
+            ValueRef::Byte(value) => Some(i64::from(value)),
+            _ => None,
+        }
+    }
+
+    /// The value of a float or double.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            ValueRef::Float(value) => Some(f64::from(value)),
+            ValueRef::Double(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Parses the properties of a [`GvasFileRef`].
+struct Parser<'a, 'h> {
+    cursor: Cursor<&'a [u8]>,
+    hints: HashMap<String, HintValue>,
+    properties_stack: Vec<String>,
+    custom_versions: &'h HashableIndexMap<Guid, u32>,
+    package_version: PackageVersion,
+    read_options: ReadOptions,
+    write_options: WriteOptions,
+}
+
+impl<'a> Parser<'a, '_> {
+    /// Pushes `entry` onto the properties stack, which is only needed to look up hints.
+    #[inline]
+    fn push(&mut self, entry: &str) {
+        if !self.hints.is_empty() {
+            self.properties_stack.push(entry.to_string());
+        }
+    }
+
+    #[inline]
+    fn pop(&mut self) {
+        if !self.hints.is_empty() {
+            let _ = self.properties_stack.pop();
+        }
+    }
+
+    #[inline]
+    fn supports_package_version(&self, required: EUnrealEngineObjectUE4Version) -> bool {
+        self.package_version.ue4 >= required as u32
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let data: &'a [u8] = self.cursor.get_ref();
+        let start = self.cursor.position() as usize;
+        let end = start
+            .checked_add(len)
+            .filter(|end| *end <= data.len())
+            .ok_or_else(|| io::Error::from(ErrorKind::UnexpectedEof))?;
+        self.cursor.set_position(end as u64);
+        Ok(&data[start..end])
+    }
+
+    fn read_fstring(&mut self) -> Result<Option<Cow<'a, str>>, Error> {
+        let start = self.cursor.position();
+        let len = self.cursor.read_i32::<LittleEndian>()?;
+        if len > 0 && len.unsigned_abs() <= DEFAULT_MAX_STRING_LEN {
+            let bytes = self.read_bytes(len as usize)?;
+            if let Some((0, string)) = bytes.split_last() {
+                if let Ok(string) = str::from_utf8(string) {
+                    return Ok(Some(Cow::Borrowed(string)));
+                }
+            }
+        }
+
+        // UTF-16 strings are decoded, and invalid strings rejected, by the owned reader
+        self.cursor.set_position(start);
+        Ok(self.cursor.read_fstring()?.map(Cow::Owned))
+    }
+
+    fn read_string(&mut self) -> Result<Cow<'a, str>, Error> {
+        match self.read_fstring()? {
+            Some(string) => Ok(string),
+            None => Err(DeserializeError::InvalidString(0, self.cursor.position()))?,
+        }
+    }
+
+    /// Fails if property tags use complete type names, see
+    /// [`PackageVersion::has_complete_type_names`].
+    fn check_tag_layout(&mut self) -> Result<(), Error> {
+        if self.package_version.has_complete_type_names() {
+            Err(Error::unsupported(DeserializeError::invalid_property(
+                complete_type_names_unsupported(self.package_version),
+                &mut self.cursor,
+            )))?
+        }
+        Ok(())
+    }
+
+    fn read_property_guid_flag(&mut self) -> Result<(), Error> {
+        if self.supports_package_version(EUnrealEngineObjectUE4Version::PropertyGuidInPropertyTag) {
+            let terminator = self.cursor.read_u8()?;
+            if terminator != 0 {
+                let position = self.cursor.position() - 1;
+                Err(DeserializeError::InvalidSeparator(terminator, position))?
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a property after its name and type.
+    fn read_tagged(&mut self, property_type: Cow<'a, str>) -> Result<PropertyRef<'a>, Error> {
+        self.check_tag_layout()?;
+        let length = self.cursor.read_u32::<LittleEndian>()?;
+        let array_index = self.cursor.read_u32::<LittleEndian>()?;
+        let kind = PropertyKind::from_type_name(&property_type);
+
+        if kind == Some(PropertyKind::BoolProperty) {
+            let value = self.cursor.read_bool()?;
+            self.read_property_guid_flag()?;
+            return Ok(PropertyRef {
+                property_type,
+                array_index,
+                value: ValueRef::Bool(value),
+            });
+        }
+
+        let value = match kind {
+            Some(PropertyKind::StructProperty) => {
+                let type_name = self.read_string()?;
+                if self.supports_package_version(
+                    EUnrealEngineObjectUE4Version::StructGuidInPropertyTag,
+                ) {
+                    self.cursor.read_guid()?;
+                }
+                self.read_body(&property_type, length, |parser| {
+                    parser.read_struct(type_name)
+                })?
+            }
+            Some(PropertyKind::ByteProperty) => {
+                let enum_type = self.read_string()?;
+                self.read_body(&property_type, length, |parser| match length {
+                    0..=2 => Ok(ValueRef::Byte(parser.cursor.read_u8()?)),
+                    _ => Ok(ValueRef::Enum {
+                        enum_type: Some(enum_type),
+                        value: parser.read_string()?,
+                    }),
+                })?
+            }
+            Some(PropertyKind::EnumProperty) => {
+                let enum_type = self.read_string()?;
+                self.read_body(&property_type, length, |parser| {
+                    Ok(ValueRef::Enum {
+                        enum_type: Some(enum_type),
+                        value: parser.read_string()?,
+                    })
+                })?
+            }
+            Some(PropertyKind::ArrayProperty) => {
+                let element_type = self.read_string()?;
+                self.read_body(&property_type, length, |parser| {
+                    parser.read_array(length, element_type)
+                })?
+            }
+            Some(PropertyKind::SetProperty) => {
+                let element_type = self.read_string()?;
+                self.read_body(&property_type, length, |parser| {
+                    parser.read_set(length, element_type)
+                })?
+            }
+            Some(PropertyKind::MapProperty) => {
+                let key_type = self.read_string()?;
+                let value_type = self.read_string()?;
+                self.read_body(&property_type, length, |parser| {
+                    parser.read_map(key_type, value_type)
+                })?
+            }
+            Some(kind) if is_scalar(kind) => self.read_body(&property_type, length, |parser| {
+                parser.read_scalar(kind, Some(length))
+            })?,
+            _ => self.read_body(&property_type, length, |parser| {
+                Ok(ValueRef::Raw(parser.read_bytes(length as usize)?))
+            })?,
+        };
+
+        Ok(PropertyRef {
+            property_type,
+            array_index,
+            value,
+        })
+    }
+
+    /// Reads the property GUID flag ending a tag and the body of `length` bytes following it.
+    fn read_body(
+        &mut self,
+        property_type: &str,
+        length: u32,
+        read: impl FnOnce(&mut Self) -> Result<ValueRef<'a>, Error>,
+    ) -> Result<ValueRef<'a>, Error> {
+        self.read_property_guid_flag()?;
+        if length == 0 {
+            return Ok(ValueRef::Raw(&[]));
+        }
+
+        self.push(property_type);
+        let start = self.cursor.position();
+        let value = read(self)?;
+        let end = self.cursor.position();
+        self.pop();
+
+        if end - start != u64::from(length) {
+            Err(DeserializeError::LengthMismatch {
+                expected: u64::from(length),
+                actual: end - start,
+                position: start,
+            })?
+        }
+        Ok(value)
+    }
+
+    /// Reads the body of a struct of type `type_name`.
+    fn read_struct(&mut self, type_name: Cow<'a, str>) -> Result<ValueRef<'a>, Error> {
+        if BINARY_STRUCTS.contains(&type_name.as_ref()) {
+            let data = self.read_measured(|cursor, options| {
+                StructProperty::read_body(cursor, &type_name, options).map(drop)
+            })?;
+            return Ok(ValueRef::BinaryStruct { type_name, data });
+        }
+
+        let mut fields = Vec::new();
+        loop {
+            let name = self.read_string()?;
+            if name == "None" {
+                break;
+            }
+            let property_type = self.read_string()?;
+            self.push(&name);
+            let property = self.read_tagged(property_type)?;
+            self.pop();
+            fields.push((name, property));
+        }
+        Ok(ValueRef::Struct { type_name, fields })
+    }
+
+    fn read_array(
+        &mut self,
+        length: u32,
+        element_type: Cow<'a, str>,
+    ) -> Result<ValueRef<'a>, Error> {
+        let count = self.cursor.read_u32::<LittleEndian>()?;
+        let suggested_length = match (count, length) {
+            (1.., 4..) => Some((length - 4) / count),
+            _ => None,
+        };
+
+        let elements = match PropertyKind::from_type_name(&element_type) {
+            Some(PropertyKind::StructProperty) => {
+                let _field_name = self.read_string()?;
+                let inner_type = self.read_string()?;
+                if inner_type != "StructProperty" {
+                    Err(DeserializeError::invalid_property(
+                        format!("Expected StructProperty array elements, got {inner_type}"),
+                        &mut self.cursor,
+                    ))?
+                }
+                let _size = self.cursor.read_u64::<LittleEndian>()?;
+                let type_name = self.read_string()?;
+                if self.supports_package_version(
+                    EUnrealEngineObjectUE4Version::StructGuidInPropertyTag,
+                ) {
+                    self.cursor.read_guid()?;
+                }
+                self.read_property_guid_flag()?;
+                (0..count)
+                    .map(|_| self.read_struct(type_name.clone()))
+                    .collect::<Result<_, _>>()?
+            }
+            Some(PropertyKind::ByteProperty) if suggested_length.unwrap_or(1) <= 2 => {
+                return Ok(ValueRef::Bytes(self.read_bytes(count as usize)?));
+            }
+            Some(PropertyKind::BoolProperty)
+                if count > 0 && u64::from(length) == 4 + 4 * u64::from(count) =>
+            {
+                (0..count)
+                    .map(|_| Ok(ValueRef::Bool(self.cursor.read_b32()?)))
+                    .collect::<Result<_, Error>>()?
+            }
+            _ => (0..count)
+                .map(|_| self.read_element(&element_type, suggested_length))
+                .collect::<Result<_, _>>()?,
+        };
+        Ok(ValueRef::Array {
+            element_type,
+            elements,
+        })
+    }
+
+    fn read_set(&mut self, length: u32, element_type: Cow<'a, str>) -> Result<ValueRef<'a>, Error> {
+        let _allocation_flags = self.cursor.read_u32::<LittleEndian>()?;
+        let count = self.cursor.read_u32::<LittleEndian>()?;
+
+        let elements = match PropertyKind::from_type_name(&element_type) {
+            Some(PropertyKind::ByteProperty) if length.saturating_sub(8) == count => {
+                return Ok(ValueRef::Bytes(self.read_bytes(count as usize)?));
+            }
+            Some(PropertyKind::ByteProperty) => (0..count)
+                .map(|_| {
+                    Ok(ValueRef::Enum {
+                        enum_type: None,
+                        value: self.read_string()?,
+                    })
+                })
+                .collect::<Result<_, Error>>()?,
+            _ => {
+                let suggested_length = length.saturating_sub(8).checked_div(count);
+                (0..count)
+                    .map(|_| self.read_element(&element_type, suggested_length))
+                    .collect::<Result<_, _>>()?
+            }
+        };
+        Ok(ValueRef::Array {
+            element_type,
+            elements,
+        })
+    }
+
+    fn read_map(
+        &mut self,
+        key_type: Cow<'a, str>,
+        value_type: Cow<'a, str>,
+    ) -> Result<ValueRef<'a>, Error> {
+        let _allocation_flags = self.cursor.read_u32::<LittleEndian>()?;
+        let count = self.cursor.read_u32::<LittleEndian>()?;
+
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            self.push("Key");
+            let key = self.read_element(&key_type, None)?;
+            self.pop();
+            self.push("Value");
+            let value = self.read_element(&value_type, None)?;
+            self.pop();
+            entries.push((key, value));
+        }
+        Ok(ValueRef::Map {
+            key_type,
+            value_type,
+            entries,
+        })
+    }
+
+    /// Reads a value stored without a tag, like an array element.
+    fn read_element(
+        &mut self,
+        element_type: &str,
+        suggested_length: Option<u32>,
+    ) -> Result<ValueRef<'a>, Error> {
+        let hint = match self.hints.is_empty() {
+            true => None,
+            false => {
+                let path = self.properties_stack.join(".");
+                self.hints.get(&format!("{path}.{element_type}"))
+            }
+        };
+        match (PropertyKind::from_type_name(element_type), hint) {
+            (Some(PropertyKind::StructProperty), Some(HintValue::Struct(type_name))) => {
+                let type_name = Cow::Owned(type_name.clone());
+                self.push(element_type);
+                let value = self.read_struct(type_name)?;
+                self.pop();
+                Ok(value)
+            }
+            (Some(kind), None) if is_scalar(kind) => self.read_scalar(kind, suggested_length),
+            // Other values are sized by the owned reader, which also reports missing hints
+            _ => {
+                let data = self.read_measured(|cursor, options| {
+                    Property::new(cursor, element_type, false, options, suggested_length).map(drop)
+                })?;
+                Ok(ValueRef::Raw(data))
+            }
+        }
+    }
+
+    fn read_scalar(
+        &mut self,
+        kind: PropertyKind,
+        suggested_length: Option<u32>,
+    ) -> Result<ValueRef<'a>, Error> {
+        let cursor = &mut self.cursor;
+        Ok(match kind {
+            PropertyKind::BoolProperty => ValueRef::Bool(cursor.read_bool()?),
+            PropertyKind::Int8Property => ValueRef::Int(i64::from(cursor.read_i8()?)),
+            PropertyKind::Int16Property => {
+                ValueRef::Int(i64::from(cursor.read_i16::<LittleEndian>()?))
+            }
+            PropertyKind::IntProperty => {
+                ValueRef::Int(i64::from(cursor.read_i32::<LittleEndian>()?))
+            }
+            PropertyKind::Int64Property => ValueRef::Int(cursor.read_i64::<LittleEndian>()?),
+            PropertyKind::UInt16Property => {
+                ValueRef::UInt(u64::from(cursor.read_u16::<LittleEndian>()?))
+            }
+            PropertyKind::UInt32Property => {
+                ValueRef::UInt(u64::from(cursor.read_u32::<LittleEndian>()?))
+            }
+            PropertyKind::UInt64Property => ValueRef::UInt(cursor.read_u64::<LittleEndian>()?),
+            PropertyKind::FloatProperty => ValueRef::Float(cursor.read_f32::<LittleEndian>()?),
+            PropertyKind::DoubleProperty => ValueRef::Double(cursor.read_f64::<LittleEndian>()?),
+            PropertyKind::ByteProperty if suggested_length.unwrap_or(1) <= 2 => {
+                ValueRef::Byte(cursor.read_u8()?)
+            }
+            PropertyKind::ByteProperty | PropertyKind::EnumProperty => ValueRef::Enum {
+                enum_type: None,
+                value: self.read_string()?,
+            },
+            _ => ValueRef::Str(self.read_fstring()?),
+        })
+    }
+
+    /// Reads a value with the owned reader, returning the bytes it read.
+    fn read_measured(
+        &mut self,
+        read: impl FnOnce(&mut Cursor<&'a [u8]>, &mut PropertyOptions) -> Result<(), Error>,
+    ) -> Result<&'a [u8], Error> {
+        let start = self.cursor.position();
+        let mut options = PropertyOptions {
+            hints: &self.hints,
+            properties_stack: &mut self.properties_stack,
+            custom_versions: self.custom_versions,
+            package_version: self.package_version,
+            read_options: &self.read_options,
+            write_options: &self.write_options,
+        };
+        read(&mut self.cursor, &mut options)?;
+        let end = self.cursor.position();
+        self.cursor.set_position(start);
+        self.read_bytes((end - start) as usize)
+    }
+}
+
+/// Returns true for the property types read by [`Parser::read_scalar`].
+#[inline]
+fn is_scalar(kind: PropertyKind) -> bool {
+    matches!(
+        kind,
+        PropertyKind::BoolProperty
+            | PropertyKind::Int8Property
+            | PropertyKind::Int16Property
+            | PropertyKind::IntProperty
+            | PropertyKind::Int64Property
+            | PropertyKind::UInt16Property
+            | PropertyKind::UInt32Property
+            | PropertyKind::UInt64Property
+            | PropertyKind::FloatProperty
+            | PropertyKind::DoubleProperty
+            | PropertyKind::ByteProperty
+            | PropertyKind::EnumProperty
+            | PropertyKind::StrProperty
+            | PropertyKind::NameProperty
+            | PropertyKind::ObjectProperty
+    )
+}
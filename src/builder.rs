@@ -0,0 +1,295 @@
+//! Building saves from scratch
+//!
+//! [`GvasHeaderBuilder`] starts from the versions written by an engine release, see
+//! [`GvasHeader::new_ue5_3`], and [`GvasFileBuilder`] adds the root properties. Both check the
+//! result in `build`, so a file that builds can be written and read back.
+//!
+//! [`GvasHeaderBuilder`]: crate::builder::GvasHeaderBuilder
+//! [`GvasFileBuilder`]: crate::builder::GvasFileBuilder
+//! [`GvasHeader::new_ue5_3`]: crate::GvasHeader::new_ue5_3
+//!
+//! ```
+//! use gvas::{
+//!     game_version::GameVersion,
+//!     properties::{int_property::IntProperty, str_property::StrProperty},
+//!     GvasFile, GvasHeader,
+//! };
+//! use std::io::Cursor;
+//!
+//! let file = GvasFile::builder(GvasHeader::new_ue5_3())
+//!     .save_game_class_name("/Script/MyGame.MySaveGame")
+//!     .property("Level", IntProperty::new(3))
+//!     .property("PlayerName", StrProperty::from("Player"))
+//!     .build()?;
+//!
+//! let mut writer = Cursor::new(Vec::new());
+//! file.write(&mut writer)?;
+//! writer.set_position(0);
+//! assert_eq!(GvasFile::read(&mut writer, GameVersion::Default)?, file);
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+
+use std::io;
+
+use crate::{
+    custom_version::{self, CustomVersionFormat, CustomVersionTrait},
+    engine_version::FEngineVersion,
+    error::{Error, SerializeError},
+    game_version::DeserializedGameVersion,
+    object_version::PackageVersion,
+    properties::Property,
+    savegame_version::SaveGameVersion,
+    types::{map::HashableIndexMap, Guid},
+    GvasFile, GvasHeader,
+};
+
+/// Builds a [`GvasHeader`], see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GvasHeaderBuilder {
+    package_version: PackageVersion,
+    engine_version: FEngineVersion,
+    custom_version_format: CustomVersionFormat,
+    custom_versions: HashableIndexMap<Guid, u32>,
+    save_game_class_name: String,
+}
+
+impl GvasHeaderBuilder {
+    /// Creates a new `GvasHeaderBuilder` instance without custom versions.
+    ///
+    /// A `Version3` header is built when `package_version` has a UE5 version.
+    #[inline]
+    pub fn new(package_version: PackageVersion, engine_version: FEngineVersion) -> Self {
+        GvasHeaderBuilder {
+            package_version,
+            engine_version,
+            custom_version_format: CustomVersionFormat::Optimized,
+            custom_versions: HashableIndexMap::new(),
+            save_game_class_name: String::new(),
+        }
+    }
+
+    /// Set the package file versions.
+    #[inline]
+    pub fn package_version(mut self, package_version: PackageVersion) -> Self {
+        self.package_version = package_version;
+        self
+    }
+
+    /// Set the engine version.
+    #[inline]
+    pub fn engine_version(mut self, engine_version: FEngineVersion) -> Self {
+        self.engine_version = engine_version;
+        self
+    }
+
+    /// Set the format of the custom version container.
+    #[inline]
+    pub fn custom_version_format(mut self, format: CustomVersionFormat) -> Self {
+        self.custom_version_format = format;
+        self
+    }
+
+    /// Set the version of the custom version `key`, replacing the current one.
+    #[inline]
+    pub fn custom_version(mut self, key: Guid, version: u32) -> Self {
+        self.custom_versions.insert(key, version);
+        self
+    }
+
+    /// Set custom version `T` to `version`, replacing the current one.
+    ///
+    /// ```
+    /// use gvas::{custom_version::FEditorObjectVersion, GvasHeader};
+    ///
+    /// let header = GvasHeader::new_ue4_27()
+    ///     .supported_version(FEditorObjectVersion::BeforeCustomVersionWasAdded)
+    ///     .save_game_class_name("/Script/MyGame.MySaveGame")
+    ///     .build()?;
+    /// let version = FEditorObjectVersion::CultureInvariantTextSerializationKeyStability;
+    /// assert!(!header.supports_version(version));
+    /// # Ok::<(), gvas::error::Error>(())
+    /// ```
+    #[inline]
+    pub fn supported_version<T>(self, version: T) -> Self
+    where
+        T: CustomVersionTrait + Into<u32>,
+    {
+        self.custom_version(T::GUID, version.into())
+    }
+
+    /// Replace all custom versions.
+    #[inline]
+    pub fn custom_versions<I>(mut self, custom_versions: I) -> Self
+    where
+        I: IntoIterator<Item = (Guid, u32)>,
+    {
+        self.custom_versions = HashableIndexMap(custom_versions.into_iter().collect());
+        self
+    }
+
+    /// Set the save game class name, e.g. `/Script/MyGame.MySaveGame`.
+    #[inline]
+    pub fn save_game_class_name(mut self, save_game_class_name: impl Into<String>) -> Self {
+        self.save_game_class_name = save_game_class_name.into();
+        self
+    }
+
+    /// Build the header.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SerializeError::InvalidValue`] if the save game class name is empty, if a
+    /// package file version can't be read back, or if the custom versions can't be written in
+    /// the chosen format.
+    pub fn build(self) -> Result<GvasHeader, Error> {
+        let GvasHeaderBuilder {
+            package_version,
+            engine_version,
+            custom_version_format,
+            custom_versions,
+            save_game_class_name,
+        } = self;
+
+        if save_game_class_name.is_empty() {
+            Err(SerializeError::invalid_value(
+                "Save game class name is empty",
+            ))?
+        }
+        if let Some(message) = PackageVersion::unsupported_ue4(package_version.ue4) {
+            Err(SerializeError::invalid_value(message))?
+        }
+        if package_version.ue5 != 0 {
+            if let Some(message) = PackageVersion::unsupported_ue5(package_version.ue5) {
+                Err(SerializeError::invalid_value(message))?
+            }
+        }
+        custom_version::write_container(&mut io::sink(), custom_version_format, &custom_versions)?;
+
+        let custom_version_format = u32::from(custom_version_format);
+        Ok(match package_version.ue5 {
+            0 => GvasHeader::Version2 {
+                package_file_version: package_version.ue4,
+                engine_version,
+                custom_version_format,
+                custom_versions,
+                custom_version_names: HashableIndexMap::new(),
+                save_game_class_name,
+            },
+            package_file_version_ue5 => GvasHeader::Version3 {
+                save_game_file_version: SaveGameVersion::PackageFileSummaryVersionChange as u32,
+                package_file_version: package_version.ue4,
+                package_file_version_ue5,
+                engine_version,
+                custom_version_format,
+                custom_versions,
+                custom_version_names: HashableIndexMap::new(),
+                save_game_class_name,
+            },
+        })
+    }
+}
+
+/// Builds a [`GvasFile`], see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GvasFileBuilder {
+    header: GvasHeaderBuilder,
+    deserialized_game_version: DeserializedGameVersion,
+    prefix: Vec<u8>,
+    properties: HashableIndexMap<String, Property>,
+}
+
+impl GvasFileBuilder {
+    /// Creates a new `GvasFileBuilder` instance without properties.
+    #[inline]
+    pub fn new(header: GvasHeaderBuilder) -> Self {
+        GvasFileBuilder {
+            header,
+            deserialized_game_version: DeserializedGameVersion::Default,
+            prefix: Vec::new(),
+            properties: HashableIndexMap::new(),
+        }
+    }
+
+    /// Set the save game class name, see [`GvasHeaderBuilder::save_game_class_name`].
+    #[inline]
+    pub fn save_game_class_name(mut self, save_game_class_name: impl Into<String>) -> Self {
+        self.header = self.header.save_game_class_name(save_game_class_name);
+        self
+    }
+
+    /// Set the version of a custom version, see [`GvasHeaderBuilder::custom_version`].
+    #[inline]
+    pub fn custom_version(mut self, key: Guid, version: u32) -> Self {
+        self.header = self.header.custom_version(key, version);
+        self
+    }
+
+    /// Set the game the file is written for.
+    #[inline]
+    pub fn game_version(mut self, deserialized_game_version: DeserializedGameVersion) -> Self {
+        self.deserialized_game_version = deserialized_game_version;
+        self
+    }
+
+    /// Set the data written before the GVAS header.
+    #[inline]
+    pub fn prefix(mut self, prefix: impl Into<Vec<u8>>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Add a root property, replacing a property with the same name.
+    #[inline]
+    pub fn property(mut self, name: impl Into<String>, property: impl Into<Property>) -> Self {
+        self.properties.insert(name.into(), property.into());
+        self
+    }
+
+    /// Add root properties, replacing properties with the same names.
+    #[inline]
+    pub fn properties<I, K, V>(mut self, properties: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<Property>,
+    {
+        for (name, property) in properties {
+            self.properties.insert(name.into(), property.into());
+        }
+        self
+    }
+
+    /// Build the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SerializeError::InvalidValue`] if the header is invalid, see
+    /// [`GvasHeaderBuilder::build`], or if a property name is empty or `None`, which would end
+    /// the properties when the file is read.
+    pub fn build(self) -> Result<GvasFile, Error> {
+        let header = self.header.build()?;
+        for name in self.properties.keys() {
+            if name.is_empty() || name == "None" {
+                Err(SerializeError::invalid_value(format!(
+                    "Invalid property name {name:?}"
+                )))?
+            }
+        }
+        Ok(GvasFile {
+            deserialized_game_version: self.deserialized_game_version,
+            prefix: self.prefix,
+            header,
+            custom_version_extra: Vec::new(),
+            properties: self.properties,
+            trailing_padding: Some(0),
+            trailing_bytes: Vec::new(),
+        })
+    }
+}
+
+impl From<GvasHeaderBuilder> for GvasFileBuilder {
+    #[inline]
+    fn from(header: GvasHeaderBuilder) -> Self {
+        GvasFileBuilder::new(header)
+    }
+}
@@ -0,0 +1,117 @@
+//! Chunked zlib container
+//!
+//! This is the layout produced by Unreal Engine's `FArchive::SerializeCompressed`: one or more
+//! chunks, each made of a header followed by zlib compressed blocks.
+//!
+//! ```text
+//! u64 package file tag (0x9E2A83C1)
+//! u64 maximum uncompressed block size
+//! u64 compressed size, u64 uncompressed size (summary)
+//! u64 compressed size, u64 uncompressed size (per block)
+//! compressed blocks
+//! ```
+
+use std::io::{ErrorKind, Read, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+
+use crate::error::{DeserializeError, Error};
+
+/// Tag at the start of every chunk.
+pub const PACKAGE_FILE_TAG: u64 = 0x9E2A83C1;
+
+/// Block size used by Unreal Engine.
+pub const DEFAULT_BLOCK_SIZE: u64 = 0x20000;
+
+/// Decompresses all chunks until the end of the reader.
+///
+/// Returns the decompressed data and the block size of the first chunk.
+///
+/// # Errors
+///
+/// If the data isn't a valid chunked container this function returns [`Error`]
+pub fn decompress<R: Read>(reader: &mut R) -> Result<(Vec<u8>, u64), Error> {
+    let mut data = Vec::new();
+    let mut first_block_size = None;
+    loop {
+        let tag = match reader.read_u64::<LittleEndian>() {
+            Ok(tag) => tag,
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof && first_block_size.is_some() => break,
+            Err(e) => Err(e)?,
+        };
+        if tag != PACKAGE_FILE_TAG {
+            Err(DeserializeError::InvalidHeader(
+                format!("Invalid chunk tag {tag:#x}").into_boxed_str(),
+            ))?
+        }
+        let block_size = reader.read_u64::<LittleEndian>()?;
+        if block_size == 0 {
+            Err(DeserializeError::InvalidHeader(
+                "Invalid chunk block size 0".into(),
+            ))?
+        }
+        first_block_size.get_or_insert(block_size);
+
+        let _compressed_size = reader.read_u64::<LittleEndian>()?;
+        let uncompressed_size = reader.read_u64::<LittleEndian>()?;
+
+        let block_count = uncompressed_size.div_ceil(block_size);
+        let mut blocks = Vec::new();
+        for _ in 0..block_count {
+            let compressed = reader.read_u64::<LittleEndian>()?;
+            let uncompressed = reader.read_u64::<LittleEndian>()?;
+            blocks.push((compressed, uncompressed));
+        }
+
+        let start = data.len();
+        for (compressed, uncompressed) in blocks {
+            let block_start = data.len();
+            ZlibDecoder::new(reader.by_ref().take(compressed)).read_to_end(&mut data)?;
+            let actual = (data.len() - block_start) as u64;
+            if actual != uncompressed {
+                Err(DeserializeError::DecompressedLengthMismatch(
+                    uncompressed,
+                    actual,
+                ))?
+            }
+        }
+        let actual = (data.len() - start) as u64;
+        if actual != uncompressed_size {
+            Err(DeserializeError::DecompressedLengthMismatch(
+                uncompressed_size,
+                actual,
+            ))?
+        }
+    }
+    Ok((data, first_block_size.unwrap_or(DEFAULT_BLOCK_SIZE)))
+}
+
+/// Compresses `data` as a single chunk split in blocks of `block_size`.
+///
+/// # Errors
+///
+/// If writing fails this function returns [`Error`]
+pub fn compress<W: Write>(writer: &mut W, data: &[u8], block_size: u64) -> Result<(), Error> {
+    let block_size = block_size.max(1);
+    let mut blocks = Vec::new();
+    for block in data.chunks(block_size as usize) {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(block)?;
+        blocks.push((encoder.finish()?, block.len() as u64));
+    }
+    let compressed_size = blocks.iter().map(|(block, _)| block.len() as u64).sum();
+
+    writer.write_u64::<LittleEndian>(PACKAGE_FILE_TAG)?;
+    writer.write_u64::<LittleEndian>(block_size)?;
+    writer.write_u64::<LittleEndian>(compressed_size)?;
+    writer.write_u64::<LittleEndian>(data.len() as u64)?;
+    for (compressed, uncompressed) in &blocks {
+        writer.write_u64::<LittleEndian>(compressed.len() as u64)?;
+        writer.write_u64::<LittleEndian>(*uncompressed)?;
+    }
+    for (compressed, _) in &blocks {
+        writer.write_all(compressed)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! Grounded container
+//!
+//! Grounded stores its saves as a small header followed by a single zlib stream.
+//!
+//! ```text
+//! u32 decompressed size
+//! u32 compressed size
+//! zlib compressed GVAS data
+//! ```
+
+use std::io::{Read, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+
+use crate::error::{DeserializeError, Error};
+
+/// Size of the header before the zlib stream.
+pub const HEADER_LEN: usize = 8;
+
+/// Returns true if `magic` starts with a Grounded header followed by a zlib stream header.
+pub(crate) fn is_grounded(magic: &[u8]) -> bool {
+    match magic.get(HEADER_LEN..HEADER_LEN + 2) {
+        Some(&[cmf, flg]) => cmf == 0x78 && (u16::from(cmf) << 8 | u16::from(flg)) % 31 == 0,
+        _ => false,
+    }
+}
+
+/// Decompresses a Grounded save.
+///
+/// # Errors
+///
+/// If the data isn't a valid Grounded container, or doesn't decompress to the declared size,
+/// this function returns [`Error`]
+pub fn decompress<R: Read>(reader: &mut R) -> Result<Vec<u8>, Error> {
+    let decompressed_size = reader.read_u32::<LittleEndian>()?;
+    let compressed_size = reader.read_u32::<LittleEndian>()?;
+
+    let mut data = Vec::new();
+    ZlibDecoder::new(reader.take(u64::from(compressed_size))).read_to_end(&mut data)?;
+    if data.len() as u64 != u64::from(decompressed_size) {
+        Err(DeserializeError::DecompressedLengthMismatch(
+            u64::from(decompressed_size),
+            data.len() as u64,
+        ))?
+    }
+    Ok(data)
+}
+
+/// Compresses `data` as a Grounded save.
+///
+/// # Errors
+///
+/// If writing fails this function returns [`Error`]
+pub fn compress<W: Write>(writer: &mut W, data: &[u8]) -> Result<(), Error> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    let compressed = encoder.finish()?;
+
+    writer.write_u32::<LittleEndian>(data.len() as u32)?;
+    writer.write_u32::<LittleEndian>(compressed.len() as u32)?;
+    writer.write_all(&compressed)?;
+    Ok(())
+}
@@ -0,0 +1,357 @@
+//! Containers wrapping GVAS data
+//!
+//! Some games don't store the GVAS data directly but wrap it in an outer container.
+//! The containers in this module are selected through [`GameVersion`], or detected from the
+//! leading bytes of a file with [`GameVersion::Auto`]. Each file keeps the container it was read
+//! from in [`GvasFile::deserialized_game_version`], which implements [`ContainerFormat`] to wrap
+//! the file again when it is written. Other containers can be supported by implementing
+//! [`ContainerFormat`] and reading saves with [`GvasFile::read_with_container`] or
+//! [`ReadOptions::container`].
+//!
+//! [`GvasFile::deserialized_game_version`]: crate::GvasFile::deserialized_game_version
+//! [`GvasFile::read_with_container`]: crate::GvasFile::read_with_container
+//! [`ReadOptions::container`]: crate::options::ReadOptions::container
+
+use std::{
+    fmt::Debug,
+    io::{Read, Seek, SeekFrom, Write},
+    sync::Arc,
+};
+
+use crate::{
+    error::Error,
+    game_version::{DeserializedGameVersion, GameVersion, PLZ_MAGIC},
+    FILE_TYPE_GVAS,
+};
+
+/// Module for chunked zlib containers.
+pub mod chunked;
+/// Module for Grounded saves.
+pub mod grounded;
+/// Module for Palworld saves.
+pub mod palworld;
+/// Module for zip archives containing a single save.
+pub mod zip;
+
+/// An outer container wrapping GVAS data.
+///
+/// Used with [`GvasFile::read_with_container`] and [`ReadOptions::container`], which parse the
+/// GVAS data unwrapped by the container. The file keeps the container as
+/// [`DeserializedGameVersion::Custom`], so writing or saving it wraps it again.
+///
+/// [`GvasFile::read_with_container`]: crate::GvasFile::read_with_container
+/// [`ReadOptions::container`]: crate::options::ReadOptions::container
+///
+/// # Examples
+///
+/// A container storing the length of the GVAS data before it:
+///
+/// ```
+/// use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+/// use gvas::{
+///     container::ContainerFormat,
+///     error::Error,
+///     game_version::GameVersion,
+///     hints::Hints,
+///     options::{ReadOptions, WriteOptions},
+///     GvasFile,
+/// };
+/// use std::{
+///     fs::File,
+///     io::{Cursor, Read, Write},
+/// };
+///
+/// struct LengthPrefixed;
+///
+/// impl ContainerFormat for LengthPrefixed {
+///     fn read(&self, reader: &mut dyn Read) -> Result<Vec<u8>, Error> {
+///         let length = reader.read_u32::<LittleEndian>()?;
+///         let mut data = vec![0; length as usize];
+///         reader.read_exact(&mut data)?;
+///         Ok(data)
+///     }
+///
+///     fn write(&self, writer: &mut dyn Write, data: &[u8]) -> Result<(), Error> {
+///         writer.write_u32::<LittleEndian>(data.len() as u32)?;
+///         writer.write_all(data)?;
+///         Ok(())
+///     }
+/// }
+///
+/// let mut file = File::open("resources/test/Slot1.sav")?;
+/// let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+///
+/// let mut writer = Cursor::new(Vec::new());
+/// gvas_file.write_with_container(&mut writer, &LengthPrefixed, &WriteOptions::default())?;
+/// let data = writer.into_inner();
+/// let read = GvasFile::read_with_container(
+///     &mut Cursor::new(&data),
+///     LengthPrefixed,
+///     Hints::new(),
+///     &ReadOptions::default(),
+/// )?;
+/// assert_eq!(read.properties, gvas_file.properties);
+///
+/// // The file is written back in the same container
+/// let mut writer = Cursor::new(Vec::new());
+/// read.write(&mut writer)?;
+/// assert_eq!(writer.into_inner(), data);
+/// # Ok::<(), Error>(())
+/// ```
+pub trait ContainerFormat {
+    /// Reads the container from `reader` and returns the GVAS data it wraps.
+    ///
+    /// The data may start with a prefix, which is kept in [`GvasFile::prefix`].
+    ///
+    /// [`GvasFile::prefix`]: crate::GvasFile::prefix
+    ///
+    /// # Errors
+    ///
+    /// If the container is invalid this function returns [`Error`]
+    fn read(&self, reader: &mut dyn Read) -> Result<Vec<u8>, Error>;
+
+    /// Writes `data` wrapped in the container to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails this function returns [`Error`]
+    fn write(&self, writer: &mut dyn Write, data: &[u8]) -> Result<(), Error>;
+}
+
+/// A shared [`ContainerFormat`] implemented outside of this crate, see
+/// [`DeserializedGameVersion::Custom`].
+///
+/// Containers are compared by identity, two clones of the same container are equal.
+#[derive(Clone)]
+pub struct CustomContainer(Arc<dyn ContainerFormat + Send + Sync>);
+
+impl CustomContainer {
+    /// Creates a new `CustomContainer` instance.
+    #[inline]
+    pub fn new(container: impl ContainerFormat + Send + Sync + 'static) -> Self {
+        CustomContainer(Arc::new(container))
+    }
+}
+
+impl ContainerFormat for CustomContainer {
+    #[inline]
+    fn read(&self, reader: &mut dyn Read) -> Result<Vec<u8>, Error> {
+        self.0.read(reader)
+    }
+
+    #[inline]
+    fn write(&self, writer: &mut dyn Write, data: &[u8]) -> Result<(), Error> {
+        self.0.write(writer, data)
+    }
+}
+
+impl Debug for CustomContainer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CustomContainer")
+    }
+}
+
+impl PartialEq for CustomContainer {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CustomContainer {}
+
+impl ContainerFormat for DeserializedGameVersion {
+    /// Reads the container and returns the GVAS data it wraps.
+    ///
+    /// The lengths and names stored in the container are not checked against this value, use
+    /// [`GvasFile::decompress_raw`] to read them.
+    ///
+    /// [`GvasFile::decompress_raw`]: crate::GvasFile::decompress_raw
+    fn read(&self, mut reader: &mut dyn Read) -> Result<Vec<u8>, Error> {
+        match self {
+            DeserializedGameVersion::Default => {
+                let mut data = Vec::new();
+                reader.read_to_end(&mut data)?;
+                Ok(data)
+            }
+            DeserializedGameVersion::Palworld(_, _) => Ok(palworld::decompress(&mut reader)?.0),
+            DeserializedGameVersion::Chunked(_) => Ok(chunked::decompress(&mut reader)?.0),
+            DeserializedGameVersion::Zip(_) => {
+                // Zip archives are read from their central directory at the end
+                let mut archive = Vec::new();
+                reader.read_to_end(&mut archive)?;
+                Ok(zip::extract(std::io::Cursor::new(archive))?.1)
+            }
+            DeserializedGameVersion::Grounded => grounded::decompress(&mut reader),
+            DeserializedGameVersion::Custom(container) => container.read(reader),
+        }
+    }
+
+    fn write(&self, mut writer: &mut dyn Write, data: &[u8]) -> Result<(), Error> {
+        match self {
+            DeserializedGameVersion::Default => writer.write_all(data)?,
+            DeserializedGameVersion::Chunked(block_size) => {
+                chunked::compress(&mut writer, data, *block_size)?
+            }
+            DeserializedGameVersion::Zip(entry_name) => {
+                let mut archive = std::io::Cursor::new(Vec::new());
+                zip::write(&mut archive, entry_name, data)?;
+                writer.write_all(archive.get_ref())?
+            }
+            DeserializedGameVersion::Grounded => grounded::compress(&mut writer, data)?,
+            &DeserializedGameVersion::Palworld(compression_type, _) => {
+                palworld::compress(&mut writer, data, compression_type)?
+            }
+            DeserializedGameVersion::Custom(container) => container.write(writer, data)?,
+        }
+        Ok(())
+    }
+}
+
+/// Number of leading bytes needed by [`detect_magic`].
+pub const MAGIC_LEN: usize = 12;
+
+/// Detects the container of a save from its leading bytes.
+///
+/// Data which doesn't match any known container is assumed to be [`GameVersion::Default`].
+///
+/// # Examples
+///
+/// ```
+/// use gvas::{container::detect_magic, game_version::GameVersion};
+///
+/// assert_eq!(detect_magic(b"GVAS\x03\x00\x00\x00"), GameVersion::Default);
+/// assert_eq!(detect_magic(b"\xa7\x1e\x00\x00\x49\x0e\x00\x00PlZ1"), GameVersion::Palworld);
+/// ```
+pub fn detect_magic(magic: &[u8]) -> GameVersion {
+    if magic.starts_with(&FILE_TYPE_GVAS.to_le_bytes()) {
+        return GameVersion::Default;
+    }
+    if magic.get(8..11) == Some(PLZ_MAGIC.as_slice()) {
+        return GameVersion::Palworld;
+    }
+    if magic.starts_with(&chunked::PACKAGE_FILE_TAG.to_le_bytes()) {
+        return GameVersion::Chunked;
+    }
+    if magic.starts_with(zip::ZIP_MAGIC) {
+        return GameVersion::Zip;
+    }
+    if grounded::is_grounded(magic) {
+        return GameVersion::Grounded;
+    }
+    GameVersion::Default
+}
+
+/// The game version to suggest for a save starting at `start` which couldn't be read with
+/// `requested`, if its leading bytes match another container.
+pub(crate) fn suggest<R: Read + Seek>(
+    reader: &mut R,
+    start: u64,
+    requested: GameVersion,
+) -> Option<GameVersion> {
+    reader.seek(SeekFrom::Start(start)).ok()?;
+    let mut magic = Vec::with_capacity(MAGIC_LEN);
+    reader
+        .by_ref()
+        .take(MAGIC_LEN as u64)
+        .read_to_end(&mut magic)
+        .ok()?;
+    let detected = detect_magic(&magic);
+
+    // Data which doesn't match any container is detected as Default, only suggest it for GVAS
+    if detected == GameVersion::Default && !magic.starts_with(&FILE_TYPE_GVAS.to_le_bytes()) {
+        return None;
+    }
+    match (requested, detected) {
+        (GameVersion::Auto, _) | (GameVersion::DeepRockGalactic, GameVersion::Default) => None,
+        (requested, detected) => (requested != detected).then_some(detected),
+    }
+}
+
+/// The GVAS data inside the container of a save, see [`contents`].
+pub(crate) enum Contents<'a, R> {
+    /// The reader holds GVAS data starting with the magic, without a container or a prefix.
+    Plain(&'a mut R),
+    /// The decompressed data, which may start with a prefix.
+    Buffered(Vec<u8>),
+    /// A reader decompressing the data while it's read, which may start with a prefix.
+    Streamed {
+        /// The decompressing reader.
+        reader: Box<dyn Read + 'a>,
+        /// The decompressed length declared by the container.
+        length: u64,
+    },
+}
+
+/// Removes the container of `game_version` from a save, detecting it first for
+/// [`GameVersion::Auto`].
+///
+/// Returns the data inside the container and the container to wrap it in again.
+pub(crate) fn contents<R: Read + Seek>(
+    reader: &mut R,
+    game_version: GameVersion,
+) -> Result<(Contents<'_, R>, DeserializedGameVersion), Error> {
+    let game_version = match game_version {
+        GameVersion::Auto => detect(reader)?,
+        game_version => game_version,
+    };
+    Ok(match game_version {
+        GameVersion::Default | GameVersion::Auto | GameVersion::DeepRockGalactic => {
+            let start = reader.stream_position()?;
+            let mut magic = Vec::with_capacity(4);
+            reader.by_ref().take(4).read_to_end(&mut magic)?;
+            reader.seek(SeekFrom::Start(start))?;
+
+            let contents = match magic == FILE_TYPE_GVAS.to_le_bytes() {
+                true => Contents::Plain(reader),
+                false => {
+                    let mut data = Vec::new();
+                    reader.read_to_end(&mut data)?;
+                    Contents::Buffered(data)
+                }
+            };
+            (contents, DeserializedGameVersion::Default)
+        }
+        GameVersion::Chunked => {
+            let (data, block_size) = chunked::decompress(reader)?;
+            (
+                Contents::Buffered(data),
+                DeserializedGameVersion::Chunked(block_size),
+            )
+        }
+        GameVersion::Zip => {
+            let (entry_name, data) = zip::extract(reader)?;
+            (
+                Contents::Buffered(data),
+                DeserializedGameVersion::Zip(entry_name),
+            )
+        }
+        GameVersion::Grounded => {
+            let data = grounded::decompress(reader)?;
+            (Contents::Buffered(data), DeserializedGameVersion::Grounded)
+        }
+        GameVersion::Palworld => {
+            let (compression_type, lengths, decoder) = palworld::decoder(reader)?;
+            (
+                Contents::Streamed {
+                    reader: decoder,
+                    length: u64::from(lengths.decompressed),
+                },
+                DeserializedGameVersion::Palworld(compression_type, lengths),
+            )
+        }
+    })
+}
+
+/// Detects the container of a save, leaving the reader at its original position.
+///
+/// # Errors
+///
+/// If the reader fails this function returns [`Error`]
+pub fn detect<R: Read + Seek>(reader: &mut R) -> Result<GameVersion, Error> {
+    let start = reader.stream_position()?;
+    let mut magic = Vec::with_capacity(MAGIC_LEN);
+    reader.take(MAGIC_LEN as u64).read_to_end(&mut magic)?;
+    reader.seek(SeekFrom::Start(start))?;
+    Ok(detect_magic(&magic))
+}
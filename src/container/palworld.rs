@@ -0,0 +1,219 @@
+//! Palworld container
+//!
+//! Palworld stores its saves as a small header followed by the GVAS data, compressed with zlib
+//! once or twice depending on the compression type.
+//!
+//! ```text
+//! u32 decompressed size
+//! u32 compressed size
+//! "PlZ" magic
+//! u8 compression type
+//! compressed GVAS data
+//! ```
+//!
+//! [`GvasFile::read`] decompresses while parsing. [`decompress`] and [`compress`] only handle
+//! the container, so callers can decompress and compress saves on their own threads, for
+//! example decompressing the next save while parsing the current one.
+//!
+//! [`GvasFile::read`]: crate::GvasFile::read
+//!
+//! ```
+//! use gvas::{container::palworld, game_version::GameVersion, GvasFile};
+//! use std::{fs::File, io::Cursor};
+//!
+//! let mut file = File::open("resources/test/palworld_zlib.sav")?;
+//! let (data, compression_type, _) = palworld::decompress(&mut file)?;
+//! let gvas_file = GvasFile::read(&mut Cursor::new(&data), GameVersion::Default)?;
+//!
+//! let mut data = Vec::new();
+//! gvas_file.write(&mut Cursor::new(&mut data))?;
+//! let mut writer = Vec::new();
+//! palworld::compress(&mut writer, &data, compression_type)?;
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+
+use std::io::{Read, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+
+use crate::{
+    cursor_ext::WriteExt,
+    error::{DeserializeError, Error, SerializeError},
+    game_version::{PalworldCompressionType, PalworldLengths, PLZ_MAGIC},
+    options::WriteOptions,
+};
+
+/// Size of the header before the compressed data.
+pub const HEADER_LEN: usize = 12;
+
+/// Zlib compression level used by [`compress`].
+pub const DEFAULT_COMPRESSION_LEVEL: u32 = 6;
+
+/// Decompresses a Palworld save.
+///
+/// Returns the GVAS data with the compression type and lengths declared in the header.
+///
+/// # Errors
+///
+/// If the data isn't a valid Palworld container, or doesn't decompress to the declared size,
+/// this function returns [`Error`]
+pub fn decompress<R: Read>(
+    reader: &mut R,
+) -> Result<(Vec<u8>, PalworldCompressionType, PalworldLengths), Error> {
+    let mut data = Vec::new();
+    let (compression_type, lengths) = decompress_into(reader, &mut data)?;
+    Ok((data, compression_type, lengths))
+}
+
+/// Decompresses a Palworld save into `data`, reusing its allocation.
+///
+/// `data` is cleared first. Returns the compression type and lengths declared in the header.
+///
+/// # Errors
+///
+/// If the data isn't a valid Palworld container, or doesn't decompress to the declared size,
+/// this function returns [`Error`]
+pub fn decompress_into<R: Read>(
+    reader: &mut R,
+    data: &mut Vec<u8>,
+) -> Result<(PalworldCompressionType, PalworldLengths), Error> {
+    let (compression_type, lengths, mut decoder) = decoder(reader)?;
+    data.clear();
+    data.reserve(lengths.decompressed as usize);
+    decoder.read_to_end(data)?;
+
+    let declared = u64::from(lengths.decompressed);
+    let actual = data.len() as u64;
+    if actual != declared {
+        Err(DeserializeError::DecompressedLengthMismatch(
+            declared, actual,
+        ))?
+    }
+    Ok((compression_type, lengths))
+}
+
+/// Compresses `data` as a Palworld save with `compression_type`.
+///
+/// # Errors
+///
+/// If the compression type is unknown, or writing fails, this function returns [`Error`]
+#[inline]
+pub fn compress<W: Write>(
+    writer: &mut W,
+    data: &[u8],
+    compression_type: PalworldCompressionType,
+) -> Result<(), Error> {
+    compress_with_level(writer, data, compression_type, DEFAULT_COMPRESSION_LEVEL)
+}
+
+/// Compresses `data` as a Palworld save with `compression_type` and a zlib compression
+/// `level` from 0 to 9.
+///
+/// # Errors
+///
+/// If the compression type is unknown, the level is out of range, or writing fails, this
+/// function returns [`Error`]
+#[inline]
+pub fn compress_with_level<W: Write>(
+    writer: &mut W,
+    data: &[u8],
+    compression_type: PalworldCompressionType,
+    level: u32,
+) -> Result<(), Error> {
+    let options = WriteOptions {
+        palworld_compression_level: Some(level),
+        ..Default::default()
+    };
+    compress_with_options(writer, data, compression_type, &options)
+}
+
+/// Compresses `data` as a Palworld save with the compression type, level and buffer size of
+/// `options`, or with `compression_type` if `options` doesn't set a compression type.
+///
+/// # Errors
+///
+/// If the compression type is unknown, the level is out of range, or writing fails, this
+/// function returns [`Error`]
+pub fn compress_with_options<W: Write>(
+    writer: &mut W,
+    data: &[u8],
+    compression_type: PalworldCompressionType,
+    options: &WriteOptions,
+) -> Result<(), Error> {
+    let compression_type = options.palworld_compression.unwrap_or(compression_type);
+    let level = options
+        .palworld_compression_level
+        .unwrap_or(DEFAULT_COMPRESSION_LEVEL);
+    if level > Compression::best().level() {
+        Err(SerializeError::InvalidValue(
+            format!("Invalid compression level {level}").into_boxed_str(),
+        ))?
+    }
+    let level = Compression::new(level);
+    let buffer = Vec::with_capacity(options.palworld_buffer_size.unwrap_or_default());
+
+    let compressed = match compression_type {
+        PalworldCompressionType::None => data.to_vec(),
+        PalworldCompressionType::Zlib => {
+            let mut encoder = ZlibEncoder::new(buffer, level);
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        PalworldCompressionType::ZlibTwice => {
+            let encoder = ZlibEncoder::new(buffer, level);
+            let mut encoder = ZlibEncoder::new(encoder, level);
+            encoder.write_all(data)?;
+            encoder.finish()?.finish()?
+        }
+        PalworldCompressionType::Other(compression_type) => {
+            Err(Error::unsupported(SerializeError::InvalidValue(
+                format!("Unsupported Palworld compression type {compression_type:#x}")
+                    .into_boxed_str(),
+            )))?
+        }
+    };
+
+    // Compressed in memory first, so the length can be written without seeking back
+    writer.write_u32::<LittleEndian>(data.len() as u32)?;
+    writer.write_u32::<LittleEndian>(compressed.len() as u32)?;
+    writer.write_all(PLZ_MAGIC)?;
+    writer.write_enum(compression_type)?;
+    writer.write_all(&compressed)?;
+    Ok(())
+}
+
+/// Reads the Palworld container header and returns a reader decompressing the GVAS data.
+pub(crate) fn decoder<R: Read>(
+    reader: &mut R,
+) -> Result<(PalworldCompressionType, PalworldLengths, Box<dyn Read + '_>), Error> {
+    let lengths = PalworldLengths {
+        decompressed: reader.read_u32::<LittleEndian>()?,
+        compressed: reader.read_u32::<LittleEndian>()?,
+    };
+
+    let mut magic = [0u8; 3];
+    reader.read_exact(&mut magic)?;
+    if &magic != PLZ_MAGIC {
+        Err(DeserializeError::InvalidHeader(
+            format!("Invalid PlZ magic {magic:?}").into_boxed_str(),
+        ))?
+    }
+
+    let compression_type = PalworldCompressionType::from(reader.read_i8()?);
+
+    // Decompress while parsing instead of buffering the whole decompressed save
+    let decoder: Box<dyn Read + '_> = match compression_type {
+        PalworldCompressionType::None => Box::new(reader.take(u64::from(lengths.decompressed))),
+        PalworldCompressionType::Zlib => Box::new(ZlibDecoder::new(reader)),
+        PalworldCompressionType::ZlibTwice => Box::new(ZlibDecoder::new(ZlibDecoder::new(reader))),
+        PalworldCompressionType::Other(compression_type) => {
+            Err(Error::unsupported(DeserializeError::InvalidHeader(
+                format!("Unsupported Palworld compression type {compression_type:#x}")
+                    .into_boxed_str(),
+            )))?
+        }
+    };
+
+    Ok((compression_type, lengths, decoder))
+}
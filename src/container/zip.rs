@@ -0,0 +1,90 @@
+//! Zip archives containing a single save
+//!
+//! Reading and writing zip archives needs the `zip` feature, without it [`extract`] and
+//! [`write`] return [`Error::Unsupported`].
+
+use std::io::{Read, Seek, Write};
+
+#[cfg(feature = "zip")]
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+#[cfg(not(feature = "zip"))]
+use crate::error::SerializeError;
+use crate::error::{DeserializeError, Error};
+
+/// Magic at the start of a zip archive.
+pub const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// Extracts the save from a zip archive.
+///
+/// If the archive contains more than one file, the first file with a `.sav` extension is used.
+///
+/// Returns the entry name and its contents.
+///
+/// # Errors
+///
+/// If the archive is invalid or doesn't contain a save this function returns [`Error`]
+#[cfg(feature = "zip")]
+pub fn extract<R: Read + Seek>(reader: R) -> Result<(String, Vec<u8>), Error> {
+    let mut archive = ZipArchive::new(reader)?;
+
+    let mut files = (0..archive.len())
+        .filter_map(|index| archive.name_for_index(index).map(|name| (index, name)))
+        .filter(|(_, name)| !name.ends_with('/'));
+    let index = match archive.len() {
+        1 => files.next().map(|(index, _)| index),
+        _ => files
+            .find(|(_, name)| name.to_ascii_lowercase().ends_with(".sav"))
+            .map(|(index, _)| index),
+    };
+    let Some(index) = index else {
+        Err(DeserializeError::InvalidHeader(
+            "Zip archive doesn't contain a save".into(),
+        ))?
+    };
+
+    let mut file = archive.by_index(index)?;
+    let name = file.name().to_string();
+    let mut data = Vec::with_capacity(file.size() as usize);
+    file.read_to_end(&mut data)?;
+    Ok((name, data))
+}
+
+/// Extracts the save from a zip archive.
+///
+/// # Errors
+///
+/// Without the `zip` feature this function always returns [`Error::Unsupported`]
+#[cfg(not(feature = "zip"))]
+pub fn extract<R: Read + Seek>(_reader: R) -> Result<(String, Vec<u8>), Error> {
+    Err(Error::unsupported(DeserializeError::InvalidHeader(
+        "Reading zip archives needs the zip feature".into(),
+    )))
+}
+
+/// Writes `data` to a new zip archive as `entry_name`.
+///
+/// # Errors
+///
+/// If writing fails this function returns [`Error`]
+#[cfg(feature = "zip")]
+pub fn write<W: Write + Seek>(writer: W, entry_name: &str, data: &[u8]) -> Result<(), Error> {
+    let mut zip = ZipWriter::new(writer);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.start_file(entry_name, options)?;
+    zip.write_all(data)?;
+    zip.finish()?;
+    Ok(())
+}
+
+/// Writes `data` to a new zip archive as `entry_name`.
+///
+/// # Errors
+///
+/// Without the `zip` feature this function always returns [`Error::Unsupported`]
+#[cfg(not(feature = "zip"))]
+pub fn write<W: Write + Seek>(_writer: W, _entry_name: &str, _data: &[u8]) -> Result<(), Error> {
+    Err(Error::unsupported(SerializeError::InvalidValue(
+        "Writing zip archives needs the zip feature".into(),
+    )))
+}
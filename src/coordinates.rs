@@ -0,0 +1,176 @@
+//! Coordinate space transformations
+//!
+//! Map migration tools need to move everything stored in a save when a level is re-origined
+//! between game versions. A [`CoordinateTransform`] applies a scale, a rotation and a translation
+//! to every vector and `Transform` struct below a property.
+//!
+//! [`CoordinateTransform`]: crate::coordinates::CoordinateTransform
+//!
+//! ```
+//! use glam::DVec3;
+//! use gvas::{
+//!     coordinates::CoordinateTransform,
+//!     properties::{
+//!         struct_property::{StructProperty, StructPropertyValue},
+//!         struct_types::VectorD,
+//!         Property,
+//!     },
+//!     types::Guid,
+//! };
+//!
+//! let mut property = Property::from(StructProperty::new(
+//!     Guid::default(),
+//!     String::from("Vector"),
+//!     StructPropertyValue::VectorD(VectorD::new(1.0, 2.0, 3.0)),
+//! ));
+//!
+//! let transform = CoordinateTransform::from_translation(DVec3::new(100.0, 0.0, 0.0));
+//! assert_eq!(transform.apply(&mut property), 1);
+//! assert_eq!(
+//!     property.get_struct().and_then(|property| property.value.get_dvec3()),
+//!     Some(DVec3::new(101.0, 2.0, 3.0))
+//! );
+//! ```
+
+use glam::{DQuat, DVec3};
+
+use crate::{
+    properties::{struct_property::StructPropertyValue, Property},
+    types::map::HashableIndexMap,
+    visitor::{walk_mut, VisitorMut},
+};
+
+/// A scale, followed by a rotation and a translation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoordinateTransform {
+    /// Scale applied first.
+    pub scale: DVec3,
+    /// Rotation applied after scaling.
+    pub rotation: DQuat,
+    /// Translation applied last.
+    pub translation: DVec3,
+}
+
+impl Default for CoordinateTransform {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl CoordinateTransform {
+    /// A transform that doesn't change anything.
+    pub const IDENTITY: Self = CoordinateTransform {
+        scale: DVec3::ONE,
+        rotation: DQuat::IDENTITY,
+        translation: DVec3::ZERO,
+    };
+
+    /// Creates a new `CoordinateTransform` instance.
+    #[inline]
+    pub fn new(scale: DVec3, rotation: DQuat, translation: DVec3) -> Self {
+        CoordinateTransform {
+            scale,
+            rotation,
+            translation,
+        }
+    }
+
+    /// Creates a transform that only translates.
+    #[inline]
+    pub fn from_translation(translation: DVec3) -> Self {
+        CoordinateTransform {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Creates a transform that only rotates.
+    #[inline]
+    pub fn from_rotation(rotation: DQuat) -> Self {
+        CoordinateTransform {
+            rotation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Creates a transform that only scales.
+    #[inline]
+    pub fn from_scale(scale: DVec3) -> Self {
+        CoordinateTransform {
+            scale,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Transform a position.
+    #[inline]
+    pub fn transform_point(&self, point: DVec3) -> DVec3 {
+        self.rotation * (self.scale * point) + self.translation
+    }
+
+    /// Transform every vector and `Transform` struct in `property`.
+    ///
+    /// The translation of a `Transform` is moved like any other vector, its rotation is rotated
+    /// and its scale is multiplied. Returns the number of values that were changed.
+    pub fn apply(&self, property: &mut Property) -> usize {
+        let mut visitor = TransformVisitor {
+            transform: self,
+            changed: 0,
+        };
+        walk_mut(&mut visitor, "", property);
+        visitor.changed
+    }
+}
+
+struct TransformVisitor<'a> {
+    transform: &'a CoordinateTransform,
+    changed: usize,
+}
+
+impl VisitorMut for TransformVisitor<'_> {
+    fn visit_struct(&mut self, _path: &str, value: &mut StructPropertyValue) -> bool {
+        if let Some(point) = value.get_dvec3() {
+            value.set_dvec3(self.transform.transform_point(point));
+            self.changed += 1;
+            return true;
+        }
+
+        let Some(fields) = value.get_custom_struct_mut() else {
+            return true;
+        };
+        if !["Rotation", "Translation", "Scale3D"]
+            .iter()
+            .all(|name| field_value(fields, name).is_some())
+        {
+            return true;
+        }
+
+        if let Some(rotation) = field_value(fields, "Rotation") {
+            let quat = rotation.get_dquat().unwrap_or_default();
+            rotation.set_dquat(self.transform.rotation * quat);
+        }
+        if let Some(translation) = field_value(fields, "Translation") {
+            let point = translation.get_dvec3().unwrap_or_default();
+            translation.set_dvec3(self.transform.transform_point(point));
+        }
+        if let Some(scale) = field_value(fields, "Scale3D") {
+            let factor = scale.get_dvec3().unwrap_or_default();
+            scale.set_dvec3(self.transform.scale * factor);
+        }
+        self.changed += 1;
+        false
+    }
+}
+
+/// The struct value of a field, if it is a struct.
+fn field_value<'a>(
+    fields: &'a mut HashableIndexMap<String, Vec<Property>>,
+    name: &str,
+) -> Option<&'a mut StructPropertyValue> {
+    match fields.get_mut(name)?.first_mut()? {
+        Property::StructProperty(property) => Some(&mut property.value),
+        Property::StructPropertyValue(value) => Some(value),
+        _ => None,
+    }
+}
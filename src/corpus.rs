@@ -0,0 +1,196 @@
+//! Round trip checks over a directory of saves
+//!
+//! [`verify_roundtrip`] reads every save in a directory, writes it back and reports the files
+//! that weren't reproduced, so a save library can be checked against a new version of the crate
+//! before relying on it.
+//!
+//! Compressed containers are compared by their contents, since the compressor used by a game
+//! rarely produces the same bytes as the one used here.
+//!
+//! [`verify_roundtrip`]: crate::corpus::verify_roundtrip
+//!
+//! ```no_run
+//! use gvas::corpus::{verify_roundtrip, CorpusOptions};
+//!
+//! let results = verify_roundtrip("saves", &CorpusOptions::default())?;
+//! for result in &results {
+//!     if let Some(mismatch) = &result.mismatch {
+//!         println!("{}: {mismatch}", result.path.display());
+//!     }
+//! }
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+
+use std::{
+    fmt::Display,
+    fs,
+    io::Cursor,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    batch::{process_dir, BatchOptions},
+    error::Error,
+    game_version::{DeserializedGameVersion, GameVersion},
+    hints::Hints,
+    options::{ReadOptions, WriteOptions},
+    testutil::describe_differences,
+    GvasFile,
+};
+
+/// Options used by [`verify_roundtrip`].
+#[derive(Debug, Clone)]
+pub struct CorpusOptions {
+    /// Only check files with this extension, compared case-insensitively.
+    pub extension: Option<String>,
+    /// Also check the files in subdirectories.
+    pub recursive: bool,
+    /// Game version used for every file.
+    pub game_version: GameVersion,
+    /// Hints used for every file.
+    pub hints: Hints,
+    /// Read options used for every file.
+    pub read_options: ReadOptions,
+    /// Write options used for every file.
+    pub write_options: WriteOptions,
+}
+
+impl Default for CorpusOptions {
+    #[inline]
+    fn default() -> Self {
+        CorpusOptions {
+            extension: Some(String::from("sav")),
+            recursive: true,
+            game_version: GameVersion::Auto,
+            hints: Hints::new(),
+            read_options: ReadOptions::default(),
+            write_options: WriteOptions::default(),
+        }
+    }
+}
+
+/// The outcome of checking a single file.
+#[derive(Debug)]
+pub struct CorpusResult {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Why the file didn't round trip, `None` if it did.
+    pub mismatch: Option<Mismatch>,
+}
+
+/// Why a file didn't round trip.
+#[derive(Debug)]
+pub enum Mismatch {
+    /// The file couldn't be read.
+    Read(Error),
+    /// The file couldn't be written back.
+    Write(Error),
+    /// The written file differs from the original, starting at `offset`.
+    Bytes {
+        /// Offset of the first differing byte.
+        offset: usize,
+        /// Length of the original file.
+        original_len: usize,
+        /// Length of the written file.
+        written_len: usize,
+    },
+    /// The contents of a compressed file changed, described by
+    /// [`describe_differences`].
+    ///
+    /// [`describe_differences`]: crate::testutil::describe_differences
+    Contents(String),
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mismatch::Read(e) => write!(f, "failed to read: {e}"),
+            Mismatch::Write(e) => write!(f, "failed to write: {e}"),
+            Mismatch::Bytes {
+                offset,
+                original_len,
+                written_len,
+            } => write!(
+                f,
+                "bytes differ at {offset:#x}, read {original_len} bytes and wrote {written_len}"
+            ),
+            Mismatch::Contents(differences) => write!(f, "contents differ\n{differences}"),
+        }
+    }
+}
+
+/// Read every save in `dir`, write it back and compare the result with the original file.
+///
+/// Files are checked in parallel, and results are returned in the order of the file paths.
+///
+/// # Errors
+///
+/// If the directory can't be listed this function returns [`Error`]. Errors reading or writing
+/// a single file are stored in its [`CorpusResult`] instead.
+pub fn verify_roundtrip(
+    dir: impl AsRef<Path>,
+    options: &CorpusOptions,
+) -> Result<Vec<CorpusResult>, Error> {
+    let batch_options = BatchOptions {
+        threads: 0,
+        extension: options.extension.clone(),
+        recursive: options.recursive,
+        game_version: options.game_version,
+        hints: options.hints.clone(),
+        read_options: options.read_options.clone(),
+    };
+    let results = process_dir(dir, &batch_options, |path, file| {
+        compare(path, &file, options)
+    })?;
+
+    Ok(results
+        .into_iter()
+        .map(|result| CorpusResult {
+            path: result.path,
+            mismatch: match result.result {
+                Ok(mismatch) => mismatch,
+                Err(e) => Some(Mismatch::Read(e)),
+            },
+        })
+        .collect())
+}
+
+fn compare(path: &Path, file: &GvasFile, options: &CorpusOptions) -> Option<Mismatch> {
+    let original = match fs::read(path) {
+        Ok(original) => original,
+        Err(e) => return Some(Mismatch::Read(e.into())),
+    };
+    let mut writer = Cursor::new(Vec::new());
+    if let Err(e) = file.write_with_options(&mut writer, &options.write_options) {
+        return Some(Mismatch::Write(e));
+    }
+    let written = writer.into_inner();
+    if written == original {
+        return None;
+    }
+
+    if file.deserialized_game_version != DeserializedGameVersion::Default {
+        let reread = GvasFile::read_with_options(
+            &mut Cursor::new(written),
+            options.game_version,
+            &options.hints,
+            &options.read_options,
+        );
+        return match reread {
+            Ok(reread) if reread == *file => None,
+            Ok(reread) => Some(Mismatch::Contents(describe_differences(file, &reread))),
+            Err(e) => Some(Mismatch::Write(e)),
+        };
+    }
+
+    let offset = original
+        .iter()
+        .zip(&written)
+        .position(|(a, b)| a != b)
+        .unwrap_or(original.len().min(written.len()));
+    Some(Mismatch::Bytes {
+        offset,
+        original_len: original.len(),
+        written_len: written.len(),
+    })
+}
@@ -1,18 +1,49 @@
-use std::io::{Read, Seek, Write};
+use std::{
+    borrow::Cow,
+    io::{Read, Seek, SeekFrom, Write},
+};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
 use crate::{
     error::{DeserializeError, Error},
+    options::Codepage,
+    properties::type_names::{self, MAX_KNOWN_TYPE_NAME_LEN},
     types::Guid,
 };
 
+/// Maximum length in characters of strings read without [`ReadOptions::max_string_len`],
+/// including the terminator.
+///
+/// [`ReadOptions::max_string_len`]: crate::options::ReadOptions::max_string_len
+pub const DEFAULT_MAX_STRING_LEN: u32 = 131072;
+
 /// Extensions for `Read`.
 pub trait ReadExt {
     /// Reads a GVAS string.
     fn read_string(&mut self) -> Result<String, Error>;
     /// Reads a GVAS string.
     fn read_fstring(&mut self) -> Result<Option<String>, Error>;
+    /// Reads a GVAS string, decoding single-byte strings with `codepage`.
+    fn read_fstring_in(&mut self, codepage: Codepage) -> Result<Option<String>, Error>;
+    /// Reads a GVAS string, decoding single-byte strings with `codepage`.
+    ///
+    /// Strings longer than `max_len` characters, including the terminator, fail with
+    /// [`DeserializeError::StringTooLong`] before anything is allocated.
+    fn read_fstring_limited(
+        &mut self,
+        codepage: Codepage,
+        max_len: u32,
+    ) -> Result<Option<String>, Error>;
+    /// Reads a GVAS string holding a type name.
+    ///
+    /// Well-known type names are returned as `&'static str` without allocating.
+    fn read_type_name(&mut self) -> Result<Cow<'static, str>, Error>;
+    /// Reads a GVAS string holding a type name, see [`ReadExt::read_type_name`].
+    ///
+    /// Other type names longer than `max_len` characters, including the terminator, fail with
+    /// [`DeserializeError::StringTooLong`] before anything is allocated.
+    fn read_type_name_limited(&mut self, max_len: u32) -> Result<Cow<'static, str>, Error>;
     /// Reads a GUID.
     fn read_guid(&mut self) -> Result<Guid, Error>;
     /// Reads an 8bit boolean value.
@@ -31,6 +62,8 @@ pub trait WriteExt {
     fn write_string<T: AsRef<str>>(&mut self, v: T) -> Result<usize, Error>;
     /// Writes a GVAS string.
     fn write_fstring(&mut self, v: Option<&str>) -> Result<usize, Error>;
+    /// Writes a GVAS string, encoding it as single bytes if `codepage` can represent it.
+    fn write_fstring_in(&mut self, v: Option<&str>, codepage: Codepage) -> Result<usize, Error>;
     /// Writes a GUID.
     fn write_guid(&mut self, v: &Guid) -> Result<(), Error>;
     /// Writes an 8bit boolean value.
@@ -52,49 +85,61 @@ impl<R: Read + Seek> ReadExt for R {
         }
     }
 
+    #[inline]
     fn read_fstring(&mut self) -> Result<Option<String>, Error> {
+        self.read_fstring_in(Codepage::Utf8)
+    }
+
+    #[inline]
+    fn read_fstring_in(&mut self, codepage: Codepage) -> Result<Option<String>, Error> {
+        self.read_fstring_limited(codepage, DEFAULT_MAX_STRING_LEN)
+    }
+
+    fn read_fstring_limited(
+        &mut self,
+        codepage: Codepage,
+        max_len: u32,
+    ) -> Result<Option<String>, Error> {
         let start_position = self.stream_position()?;
         let len = self.read_i32::<LittleEndian>()?;
+        read_fstring_body(self, len, start_position, codepage, max_len)
+    }
 
-        if !(-131072..=131072).contains(&len) {
-            Err(DeserializeError::InvalidString(
-                len,
-                self.stream_position()?,
-            ))?
-        } else if len == 0 {
-            Ok(None)
-        } else if len < 0 {
-            let mut buf = vec![0u16; -len as usize - 1];
-            self.read_u16_into::<LittleEndian>(&mut buf)?;
-
-            let terminator = self.read_u16::<LittleEndian>()?;
-            if terminator != 0 {
-                Err(DeserializeError::InvalidStringTerminator(
-                    terminator,
-                    self.stream_position()?,
-                ))?
-            }
+    #[inline]
+    fn read_type_name(&mut self) -> Result<Cow<'static, str>, Error> {
+        self.read_type_name_limited(DEFAULT_MAX_STRING_LEN)
+    }
 
-            let string = String::from_utf16(&buf[..])
-                .map_err(|e| DeserializeError::FromUtf16Error(e, start_position))?;
+    fn read_type_name_limited(&mut self, max_len: u32) -> Result<Cow<'static, str>, Error> {
+        let start_position = self.stream_position()?;
+        let len = self.read_i32::<LittleEndian>()?;
 
-            Ok(Some(string))
-        } else {
-            let mut buf = vec![0u8; len as usize - 1];
-            self.read_exact(&mut buf)?;
+        if len > 0 && len as usize <= MAX_KNOWN_TYPE_NAME_LEN {
+            let mut buf = [0u8; MAX_KNOWN_TYPE_NAME_LEN];
+            let buf = &mut buf[..len as usize];
+            self.read_exact(buf)?;
 
-            let terminator = self.read_u8()?;
-            if terminator != 0 {
+            let (terminator, name) = buf.split_last().unwrap_or((&0, &[]));
+            if *terminator != 0 {
                 Err(DeserializeError::InvalidStringTerminator(
-                    terminator as u16,
+                    *terminator as u16,
                     self.stream_position()?,
                 ))?
             }
 
-            let string = String::from_utf8(buf)
-                .map_err(|e| DeserializeError::FromUtf8Error(e, start_position))?;
+            return match type_names::intern_bytes(name) {
+                Some(name) => Ok(Cow::Borrowed(name)),
+                None => {
+                    let string = String::from_utf8(name.to_vec())
+                        .map_err(|e| DeserializeError::FromUtf8Error(e, start_position))?;
+                    Ok(Cow::Owned(string))
+                }
+            };
+        }
 
-            Ok(Some(string))
+        match read_fstring_body(self, len, start_position, Codepage::Utf8, max_len)? {
+            Some(str) => Ok(Cow::Owned(str)),
+            None => Err(DeserializeError::InvalidString(0, self.stream_position()?))?,
         }
     }
 
@@ -143,6 +188,87 @@ impl<R: Read + Seek> ReadExt for R {
     }
 }
 
+fn read_fstring_body<R: Read + Seek>(
+    reader: &mut R,
+    len: i32,
+    start_position: u64,
+    codepage: Codepage,
+    max_len: u32,
+) -> Result<Option<String>, Error> {
+    if len.unsigned_abs() > max_len {
+        Err(DeserializeError::StringTooLong(
+            len.unsigned_abs(),
+            max_len,
+            start_position,
+        ))?
+    } else if len == 0 {
+        Ok(None)
+    } else if len < 0 {
+        let mut buf = vec![0u16; -len as usize - 1];
+        reader.read_u16_into::<LittleEndian>(&mut buf)?;
+
+        let terminator = reader.read_u16::<LittleEndian>()?;
+        if terminator != 0 {
+            Err(DeserializeError::InvalidStringTerminator(
+                terminator,
+                reader.stream_position()?,
+            ))?
+        }
+
+        let string = String::from_utf16(&buf[..])
+            .map_err(|e| DeserializeError::FromUtf16Error(e, start_position))?;
+
+        Ok(Some(string))
+    } else {
+        let mut buf = vec![0u8; len as usize - 1];
+        reader.read_exact(&mut buf)?;
+
+        let terminator = reader.read_u8()?;
+        if terminator != 0 {
+            Err(DeserializeError::InvalidStringTerminator(
+                terminator as u16,
+                reader.stream_position()?,
+            ))?
+        }
+
+        let string = match codepage {
+            Codepage::Utf8 => String::from_utf8(buf)
+                .map_err(|e| DeserializeError::FromUtf8Error(e, start_position))?,
+            Codepage::Windows1252 => buf.into_iter().map(decode_windows_1252).collect(),
+        };
+
+        Ok(Some(string))
+    }
+}
+
+/// Characters of Windows-1252 between `0x80` and `0x9f`, the other bytes map to the code point
+/// of the same value. Unassigned bytes map to the C1 control characters.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20ac}', '\u{81}', '\u{201a}', '\u{192}', '\u{201e}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2c6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8d}', '\u{17d}', '\u{8f}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201c}', '\u{201d}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2dc}', '\u{2122}', '\u{161}', '\u{203a}', '\u{153}', '\u{9d}', '\u{17e}', '\u{178}',
+];
+
+#[inline]
+fn decode_windows_1252(byte: u8) -> char {
+    match byte {
+        0x80..=0x9f => WINDOWS_1252_HIGH[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
+
+#[inline]
+fn encode_windows_1252(c: char) -> Option<u8> {
+    match c as u32 {
+        0..=0x7f | 0xa0..=0xff => Some(c as u8),
+        _ => WINDOWS_1252_HIGH
+            .iter()
+            .position(|&high| high == c)
+            .map(|index| 0x80 + index as u8),
+    }
+}
+
 impl<W: Write> WriteExt for W {
     #[inline]
     fn write_string<T: AsRef<str>>(&mut self, v: T) -> Result<usize, Error> {
@@ -153,7 +279,7 @@ impl<W: Write> WriteExt for W {
             self.write_i32::<LittleEndian>(len as i32)?;
             let _ = self.write(v.as_bytes())?;
             let _ = self.write(&[0u8; 1])?;
-            Ok(len * 2 + 4)
+            Ok(len + 4)
         } else {
             // Perform UTF-16 encoding when non-ASCII characters are detected
             let words: Vec<u16> = v.encode_utf16().collect();
@@ -167,10 +293,29 @@ impl<W: Write> WriteExt for W {
         }
     }
 
+    #[inline]
     fn write_fstring(&mut self, v: Option<&str>) -> Result<usize, Error> {
-        match v {
-            Some(str) => self.write_string(str),
-            None => {
+        self.write_fstring_in(v, Codepage::Utf8)
+    }
+
+    fn write_fstring_in(&mut self, v: Option<&str>, codepage: Codepage) -> Result<usize, Error> {
+        let bytes = match (v, codepage) {
+            (Some(str), Codepage::Windows1252) if !str.is_ascii() => str
+                .chars()
+                .map(encode_windows_1252)
+                .collect::<Option<Vec<u8>>>(),
+            _ => None,
+        };
+        match (v, bytes) {
+            (_, Some(bytes)) => {
+                let len = bytes.len() + 1;
+                self.write_i32::<LittleEndian>(len as i32)?;
+                self.write_all(&bytes)?;
+                self.write_u8(0)?;
+                Ok(len + 4)
+            }
+            (Some(str), None) => self.write_string(str),
+            (None, None) => {
                 self.write_i32::<LittleEndian>(0)?;
                 Ok(4)
             }
@@ -200,3 +345,146 @@ impl<W: Write> WriteExt for W {
         Ok(self.write_i8(v.into())?)
     }
 }
+
+/// Hash algorithms supported by [`HashingReader`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    /// CRC-32, as used by zip and zlib.
+    #[default]
+    Crc32,
+    /// SHA-256, requires the `sha2` feature.
+    #[cfg(feature = "sha2")]
+    Sha256,
+}
+
+/// Digest of the bytes read through a [`HashingReader`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Digest {
+    /// CRC-32 checksum.
+    Crc32(u32),
+    /// SHA-256 hash.
+    #[cfg(feature = "sha2")]
+    Sha256([u8; 32]),
+}
+
+#[derive(Clone)]
+enum Hasher {
+    Crc32(crc32fast::Hasher),
+    #[cfg(feature = "sha2")]
+    Sha256(sha2::Sha256),
+}
+
+impl Hasher {
+    fn new(algorithm: DigestAlgorithm) -> Self {
+        match algorithm {
+            DigestAlgorithm::Crc32 => Hasher::Crc32(crc32fast::Hasher::new()),
+            #[cfg(feature = "sha2")]
+            DigestAlgorithm::Sha256 => Hasher::Sha256(sha2::Digest::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Crc32(hasher) => hasher.update(data),
+            #[cfg(feature = "sha2")]
+            Hasher::Sha256(hasher) => sha2::Digest::update(hasher, data),
+        }
+    }
+
+    fn finalize(self) -> Digest {
+        match self {
+            Hasher::Crc32(hasher) => Digest::Crc32(hasher.finalize()),
+            #[cfg(feature = "sha2")]
+            Hasher::Sha256(hasher) => Digest::Sha256(sha2::Digest::finalize(hasher).into()),
+        }
+    }
+}
+
+/// Hashes the bytes read from a reader while passing them through.
+///
+/// Every byte is hashed once, in the order it was first read. Bytes that are read again after
+/// seeking backwards aren't hashed twice, and bytes skipped by seeking forwards aren't hashed at
+/// all, so the digest covers exactly the bytes that were parsed.
+///
+/// ```
+/// use gvas::cursor_ext::{Digest, DigestAlgorithm, HashingReader, ReadExt};
+/// use std::io::Cursor;
+///
+/// let mut reader = HashingReader::new(Cursor::new(b"\x05\0\0\0None\0"), DigestAlgorithm::Crc32)?;
+/// assert_eq!(reader.read_string()?, "None");
+/// assert_eq!(reader.position(), 9);
+/// assert_eq!(reader.digest(), Digest::Crc32(crc32fast::hash(b"\x05\0\0\0None\0")));
+/// # Ok::<(), gvas::error::Error>(())
+/// ```
+pub struct HashingReader<R> {
+    inner: R,
+    hasher: Hasher,
+    position: u64,
+    hashed: u64,
+}
+
+impl<R: Seek> HashingReader<R> {
+    /// Creates a new `HashingReader` instance, hashing from the current position of `inner`.
+    pub fn new(mut inner: R, algorithm: DigestAlgorithm) -> std::io::Result<Self> {
+        let position = inner.stream_position()?;
+        Ok(HashingReader {
+            inner,
+            hasher: Hasher::new(algorithm),
+            position,
+            hashed: position,
+        })
+    }
+}
+
+impl<R> HashingReader<R> {
+    /// Hash bytes that were parsed before they reached this reader, such as a prefix.
+    #[inline]
+    pub fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    /// Current position of the inner reader, for reporting progress.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Digest of the bytes hashed so far.
+    #[inline]
+    pub fn digest(&self) -> Digest {
+        self.hasher.clone().finalize()
+    }
+
+    /// Unwraps this `HashingReader`, returning the inner reader.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        let end = self.position + read as u64;
+        if end > self.hashed {
+            let skip = self.hashed.saturating_sub(self.position) as usize;
+            self.hasher.update(&buf[skip..read]);
+            self.hashed = end;
+        }
+        self.position = end;
+        Ok(read)
+    }
+}
+
+impl<R: Seek> Seek for HashingReader<R> {
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.position = self.inner.seek(pos)?;
+        Ok(self.position)
+    }
+
+    #[inline]
+    fn stream_position(&mut self) -> std::io::Result<u64> {
+        Ok(self.position)
+    }
+}
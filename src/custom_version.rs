@@ -2,10 +2,10 @@
 
 use crate::cursor_ext::{ReadExt, WriteExt};
 use crate::engine_version::EngineVersion;
-use crate::error::Error;
-use crate::types::Guid;
+use crate::error::{Error, SerializeError};
+use crate::types::{map::HashableIndexMap, Guid};
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use num_enum::IntoPrimitive;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
 use std::io::{Read, Seek, Write};
 
 /// Stores CustomVersions serialized by UE4
@@ -43,6 +43,127 @@ impl FCustomVersion {
     }
 }
 
+/// Serialization formats of a custom version container, `ECustomVersionSerializationFormat`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[repr(u32)]
+pub enum CustomVersionFormat {
+    /// Guid, version and friendly name entries
+    Guids = 1,
+    /// Enum tag and version entries, the tag is stored as `Guid(0, 0, 0, tag)`
+    Enums = 2,
+    /// Guid and version entries, used by every save game since UE 4.12
+    Optimized = 3,
+}
+
+/// Friendly names of custom versions by key, stored by [`CustomVersionFormat::Guids`]
+pub type CustomVersionNames = HashableIndexMap<Guid, String>;
+
+/// Read a custom version container in the given format
+///
+/// Friendly names stored by [`CustomVersionFormat::Guids`] are skipped, see
+/// [`read_container_with_names`] to keep them.
+///
+/// # Examples
+/// ```
+/// use gvas::custom_version::{read_container, CustomVersionFormat};
+/// use std::io::Cursor;
+///
+/// let data = [1, 0, 0, 0, 7, 0, 0, 0, 3, 0, 0, 0];
+/// let versions = read_container(&mut Cursor::new(data), CustomVersionFormat::Enums)?;
+/// assert_eq!(versions.values().copied().collect::<Vec<_>>(), vec![3]);
+/// # Ok::<(), gvas::error::Error>(())
+/// ```
+#[inline]
+pub fn read_container<R: Read + Seek>(
+    reader: &mut R,
+    format: CustomVersionFormat,
+) -> Result<HashableIndexMap<Guid, u32>, Error> {
+    Ok(read_container_with_names(reader, format)?.0)
+}
+
+/// Read a custom version container in the given format, with the friendly names stored by
+/// [`CustomVersionFormat::Guids`]
+///
+/// Empty names are left out, and the names are always empty for the other formats.
+pub fn read_container_with_names<R: Read + Seek>(
+    reader: &mut R,
+    format: CustomVersionFormat,
+) -> Result<(HashableIndexMap<Guid, u32>, CustomVersionNames), Error> {
+    let len = reader.read_u32::<LittleEndian>()?;
+    let mut versions = HashableIndexMap::with_capacity(len as usize);
+    let mut names = HashableIndexMap::new();
+    for _ in 0..len {
+        let FCustomVersion { key, version } = match format {
+            CustomVersionFormat::Guids => {
+                let custom_version = FCustomVersion::read(reader)?;
+                let name = reader.read_string()?;
+                if !name.is_empty() {
+                    names.insert(custom_version.key, name);
+                }
+                custom_version
+            }
+            CustomVersionFormat::Enums => {
+                let tag = reader.read_u32::<LittleEndian>()?;
+                let version = reader.read_u32::<LittleEndian>()?;
+                FCustomVersion::new(Guid::from_u32([0, 0, 0, tag]), version)
+            }
+            CustomVersionFormat::Optimized => FCustomVersion::read(reader)?,
+        };
+        versions.insert(key, version);
+    }
+    Ok((versions, names))
+}
+
+/// Write a custom version container in the given format
+///
+/// [`CustomVersionFormat::Guids`] entries are written with an empty friendly name, see
+/// [`write_container_with_names`] to write them, and [`CustomVersionFormat::Enums`] only
+/// accepts keys created from an enum tag.
+#[inline]
+pub fn write_container<W: Write>(
+    writer: &mut W,
+    format: CustomVersionFormat,
+    versions: &HashableIndexMap<Guid, u32>,
+) -> Result<usize, Error> {
+    write_container_with_names(writer, format, versions, &CustomVersionNames::new())
+}
+
+/// Write a custom version container in the given format, with the friendly `names` of
+/// [`CustomVersionFormat::Guids`] entries
+///
+/// Entries without a name are written with an empty one, and the names are ignored by the
+/// other formats.
+pub fn write_container_with_names<W: Write>(
+    writer: &mut W,
+    format: CustomVersionFormat,
+    versions: &HashableIndexMap<Guid, u32>,
+    names: &CustomVersionNames,
+) -> Result<usize, Error> {
+    writer.write_u32::<LittleEndian>(versions.len() as u32)?;
+    let mut len = 4;
+    for (&key, &version) in versions {
+        len += match format {
+            CustomVersionFormat::Guids => {
+                let name = names.get(&key).map_or("", String::as_str);
+                FCustomVersion::new(key, version).write(writer)? + writer.write_string(name)?
+            }
+            CustomVersionFormat::Enums => {
+                let [a, b, c, tag] = key.to_u32();
+                if (a, b, c) != (0, 0, 0) {
+                    Err(SerializeError::InvalidValue(
+                        format!("Custom version {key} has no enum tag").into_boxed_str(),
+                    ))?
+                }
+                writer.write_u32::<LittleEndian>(tag)?;
+                writer.write_u32::<LittleEndian>(version)?;
+                8
+            }
+            CustomVersionFormat::Optimized => FCustomVersion::new(key, version).write(writer)?,
+        };
+    }
+    Ok(len)
+}
+
 /// Used for predefining custom versions for nicer checking when parsing
 pub trait CustomVersionTrait {
     /// Mappings from engine version to version number of this custom version
@@ -434,3 +555,13 @@ impl_custom_version_trait!(
     "FUE5ReleaseStreamObjectVersion",
     Guid::from_u32([0xD89B5E42, 0x24BD4D46, 0x8412ACA8, 0xDF641779]),
 );
+
+/// Custom versions that only exist in UE5 engines.
+///
+/// These are `FUE5ReleaseStreamObjectVersion`, `FUE5MainStreamObjectVersion` and
+/// `FUE5PrivateFrostyStreamObjectVersion`.
+pub const UE5_CUSTOM_VERSIONS: [Guid; 3] = [
+    FUE5ReleaseStreamObjectVersion::GUID,
+    Guid::from_u32([0x697DD581, 0xE64F41AB, 0xAA4A51EC, 0xBEB7B628]),
+    Guid::from_u32([0x59DA5D52, 0x12324948, 0xB8785978, 0x70B8E98B]),
+];
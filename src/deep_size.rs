@@ -0,0 +1,491 @@
+//! Memory footprint estimation
+//!
+//! The estimates count the inline size of a value plus everything it owns on the heap, using
+//! allocation capacities rather than lengths. Allocator overhead is not included, so the numbers
+//! are a lower bound suitable for enforcing cache budgets rather than exact accounting.
+
+use std::{mem::size_of, sync::Arc};
+
+use indexmap::IndexMap;
+use ordered_float::OrderedFloat;
+
+use crate::{
+    engine_version::FEngineVersion,
+    game_version::{DeserializedGameVersion, PalworldCompressionType, PalworldLengths},
+    properties::{
+        array_property::ArrayProperty,
+        delegate_property::{
+            Delegate, DelegateProperty, MulticastInlineDelegateProperty, MulticastScriptDelegate,
+            MulticastSparseDelegateProperty,
+        },
+        enum_property::EnumProperty,
+        field_path_property::{FieldPath, FieldPathProperty},
+        int_property::{
+            BoolProperty, ByteProperty, BytePropertyValue, DoubleProperty, FloatProperty,
+            Int16Property, Int64Property, Int8Property, IntProperty, UInt16Property,
+            UInt32Property, UInt64Property,
+        },
+        map_property::{MapDuplicate, MapProperty},
+        name_property::NameProperty,
+        object_property::ObjectProperty,
+        set_property::SetProperty,
+        str_property::StrProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        struct_types::{
+            DateTime, Int64Vector, IntPoint, IntVector, IntVector4, LinearColor, QuatD, QuatF,
+            RotatorD, RotatorF, Timespan, UintVector, Vector2D, Vector2F, VectorD, VectorF,
+        },
+        text_property::{
+            DateTimeStyle, FText, FTextHistory, FormatArgumentValue, NumberFormattingOptions,
+            TextProperty, TransformType,
+        },
+        Property,
+    },
+    types::{map::HashableIndexMap, Guid},
+    GvasFile, GvasHeader,
+};
+
+/// Estimate the memory used by a value, including the heap allocations it owns.
+///
+/// ```
+/// use gvas::{deep_size::DeepSizeOf, properties::{str_property::StrProperty, Property}};
+///
+/// let property = Property::from(StrProperty::from("Hello"));
+/// assert!(property.deep_size_of() >= std::mem::size_of::<Property>() + 5);
+/// ```
+pub trait DeepSizeOf {
+    /// Number of bytes owned on the heap, not including the value itself.
+    fn heap_size_of(&self) -> usize;
+
+    /// Number of bytes used by the value and everything it owns.
+    #[inline]
+    fn deep_size_of(&self) -> usize
+    where
+        Self: Sized,
+    {
+        size_of::<Self>() + self.heap_size_of()
+    }
+}
+
+macro_rules! impl_inline {
+    ($($type:ty),+ $(,)?) => {
+        $(
+            impl DeepSizeOf for $type {
+                #[inline]
+                fn heap_size_of(&self) -> usize {
+                    0
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_fields {
+    ($($type:ty { $($field:ident),+ }),+ $(,)?) => {
+        $(
+            impl DeepSizeOf for $type {
+                #[inline]
+                fn heap_size_of(&self) -> usize {
+                    0 $(+ self.$field.heap_size_of())+
+                }
+            }
+        )+
+    };
+}
+
+impl_inline!(
+    bool,
+    u8,
+    i8,
+    u16,
+    i16,
+    u32,
+    i32,
+    u64,
+    i64,
+    f32,
+    f64,
+    OrderedFloat<f32>,
+    OrderedFloat<f64>,
+    Guid,
+    DateTimeStyle,
+    TransformType,
+    NumberFormattingOptions,
+    Vector2F,
+    Vector2D,
+    VectorF,
+    VectorD,
+    RotatorF,
+    RotatorD,
+    QuatF,
+    QuatD,
+    DateTime,
+    Timespan,
+    LinearColor,
+    IntPoint,
+    IntVector,
+    IntVector4,
+    UintVector,
+    Int64Vector,
+    PalworldCompressionType,
+    PalworldLengths,
+    Int8Property,
+    Int16Property,
+    IntProperty,
+    Int64Property,
+    UInt16Property,
+    UInt32Property,
+    UInt64Property,
+    FloatProperty,
+    DoubleProperty,
+    BoolProperty,
+);
+
+impl DeepSizeOf for String {
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        self.capacity()
+    }
+}
+
+/// Names shared through a [`NameTable`](crate::name_table::NameTable) are counted once per
+/// reference.
+impl DeepSizeOf for Arc<str> {
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: DeepSizeOf> DeepSizeOf for Option<T> {
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        self.as_ref().map_or(0, T::heap_size_of)
+    }
+}
+
+impl<T: DeepSizeOf> DeepSizeOf for Box<T> {
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        size_of::<T>() + T::heap_size_of(self)
+    }
+}
+
+impl<A: DeepSizeOf, B: DeepSizeOf> DeepSizeOf for (A, B) {
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        self.0.heap_size_of() + self.1.heap_size_of()
+    }
+}
+
+impl<T: DeepSizeOf> DeepSizeOf for Vec<T> {
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(T::heap_size_of).sum::<usize>()
+    }
+}
+
+// Shared values are counted in full by every owner.
+impl<T: DeepSizeOf> DeepSizeOf for Arc<[T]> {
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        self.len() * size_of::<T>() + self.iter().map(T::heap_size_of).sum::<usize>()
+    }
+}
+
+impl<K: DeepSizeOf, V: DeepSizeOf> DeepSizeOf for IndexMap<K, V> {
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        // Entries store the key, the value and the hash, the index table stores one usize each.
+        self.capacity() * (size_of::<K>() + size_of::<V>() + 2 * size_of::<usize>())
+            + self
+                .iter()
+                .map(|(key, value)| key.heap_size_of() + value.heap_size_of())
+                .sum::<usize>()
+    }
+}
+
+impl<K, V> DeepSizeOf for HashableIndexMap<K, V>
+where
+    K: std::hash::Hash + Eq + DeepSizeOf,
+    V: std::hash::Hash + DeepSizeOf,
+{
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        self.0.heap_size_of()
+    }
+}
+
+impl_fields!(
+    FEngineVersion { branch },
+    Delegate {
+        object,
+        function_name
+    },
+    DelegateProperty { value },
+    MulticastScriptDelegate { delegates },
+    MulticastInlineDelegateProperty { value },
+    MulticastSparseDelegateProperty { value },
+    EnumProperty { enum_type, value },
+    FieldPath {
+        path,
+        resolved_owner
+    },
+    FieldPathProperty { value },
+    MapDuplicate { key, value },
+    ByteProperty { name, value },
+    NameProperty { value },
+    ObjectProperty { value },
+    SetProperty {
+        property_type,
+        properties
+    },
+    StrProperty { value },
+    StructProperty { type_name, value },
+    TextProperty { value },
+    FText { history },
+);
+
+impl DeepSizeOf for BytePropertyValue {
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        match self {
+            BytePropertyValue::Byte(_) => 0,
+            BytePropertyValue::Namespaced(value) => value.heap_size_of(),
+        }
+    }
+}
+
+impl DeepSizeOf for ArrayProperty {
+    fn heap_size_of(&self) -> usize {
+        match self {
+            ArrayProperty::Bools { bools } => bools.heap_size_of(),
+            ArrayProperty::WideBools { wide_bools } => wide_bools.heap_size_of(),
+            ArrayProperty::Bytes { bytes } => bytes.heap_size_of(),
+            ArrayProperty::Enums { enums } => enums.heap_size_of(),
+            ArrayProperty::Floats { floats } => floats.heap_size_of(),
+            ArrayProperty::Ints { ints } => ints.heap_size_of(),
+            ArrayProperty::Names { names } => names.heap_size_of(),
+            ArrayProperty::Strings { strings } => strings.heap_size_of(),
+            ArrayProperty::Structs {
+                field_name,
+                type_name,
+                guid: _,
+                structs,
+            } => field_name.heap_size_of() + type_name.heap_size_of() + structs.heap_size_of(),
+            ArrayProperty::SharedStructs {
+                field_name,
+                type_name,
+                guid: _,
+                fields,
+                values,
+            } => {
+                field_name.heap_size_of()
+                    + type_name.heap_size_of()
+                    + fields.heap_size_of()
+                    + values.heap_size_of()
+            }
+            ArrayProperty::Properties {
+                property_type,
+                properties,
+            } => property_type.heap_size_of() + properties.heap_size_of(),
+        }
+    }
+}
+
+impl DeepSizeOf for MapProperty {
+    fn heap_size_of(&self) -> usize {
+        match self {
+            MapProperty::EnumBool { enum_bools } => enum_bools.heap_size_of(),
+            MapProperty::EnumInt { enum_ints } => enum_ints.heap_size_of(),
+            MapProperty::EnumProperty {
+                value_type,
+                enum_props,
+            } => value_type.heap_size_of() + enum_props.heap_size_of(),
+            MapProperty::NameBool { name_bools } => name_bools.heap_size_of(),
+            MapProperty::NameInt { name_ints } => name_ints.heap_size_of(),
+            MapProperty::NameProperty {
+                value_type,
+                name_props,
+            } => value_type.heap_size_of() + name_props.heap_size_of(),
+            MapProperty::Properties {
+                key_type,
+                value_type,
+                allocation_flags: _,
+                value,
+                duplicates,
+            } => {
+                key_type.heap_size_of()
+                    + value_type.heap_size_of()
+                    + value.heap_size_of()
+                    + duplicates.heap_size_of()
+            }
+            MapProperty::StrBool { str_bools } => str_bools.heap_size_of(),
+            MapProperty::StrInt { str_ints } => str_ints.heap_size_of(),
+            MapProperty::StrProperty {
+                value_type,
+                str_props,
+            } => value_type.heap_size_of() + str_props.heap_size_of(),
+            MapProperty::StrStr { str_strs } => str_strs.heap_size_of(),
+        }
+    }
+}
+
+impl DeepSizeOf for StructPropertyValue {
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        match self {
+            StructPropertyValue::CustomStruct(properties) => properties.heap_size_of(),
+            _ => 0,
+        }
+    }
+}
+
+impl DeepSizeOf for FormatArgumentValue {
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        match self {
+            FormatArgumentValue::Text(text) => text.heap_size_of(),
+            _ => 0,
+        }
+    }
+}
+
+impl DeepSizeOf for FTextHistory {
+    fn heap_size_of(&self) -> usize {
+        match self {
+            FTextHistory::Empty {} => 0,
+            FTextHistory::None {
+                culture_invariant_string,
+            } => culture_invariant_string.heap_size_of(),
+            FTextHistory::Base {
+                namespace,
+                key,
+                source_string,
+            } => namespace.heap_size_of() + key.heap_size_of() + source_string.heap_size_of(),
+            FTextHistory::NamedFormat {
+                source_format,
+                arguments,
+            }
+            | FTextHistory::ArgumentFormat {
+                source_format,
+                arguments,
+            } => source_format.heap_size_of() + arguments.heap_size_of(),
+            FTextHistory::OrderedFormat {
+                source_format,
+                arguments,
+            } => source_format.heap_size_of() + arguments.heap_size_of(),
+            FTextHistory::AsNumber {
+                source_value,
+                format_options: _,
+                target_culture,
+            }
+            | FTextHistory::AsPercent {
+                source_value,
+                format_options: _,
+                target_culture,
+            } => source_value.heap_size_of() + target_culture.heap_size_of(),
+            FTextHistory::AsCurrency {
+                currency_code,
+                source_value,
+                format_options: _,
+                target_culture,
+            } => {
+                currency_code.heap_size_of()
+                    + source_value.heap_size_of()
+                    + target_culture.heap_size_of()
+            }
+            FTextHistory::AsDate { target_culture, .. } => target_culture.heap_size_of(),
+            FTextHistory::AsTime {
+                time_zone,
+                target_culture,
+                ..
+            }
+            | FTextHistory::AsDateTime {
+                time_zone,
+                target_culture,
+                ..
+            } => time_zone.heap_size_of() + target_culture.heap_size_of(),
+            FTextHistory::Transform { source_text, .. } => source_text.heap_size_of(),
+            FTextHistory::StringTableEntry {
+                table_id,
+                key,
+                display_string,
+            } => table_id.heap_size_of() + key.heap_size_of() + display_string.0.heap_size_of(),
+        }
+    }
+}
+
+impl DeepSizeOf for Property {
+    fn heap_size_of(&self) -> usize {
+        match self {
+            Property::ArrayProperty(property) => property.heap_size_of(),
+            Property::BoolProperty(property) => property.heap_size_of(),
+            Property::ByteProperty(property) => property.heap_size_of(),
+            Property::DoubleProperty(property) => property.heap_size_of(),
+            Property::EnumProperty(property) => property.heap_size_of(),
+            Property::FieldPathProperty(property) => property.heap_size_of(),
+            Property::FloatProperty(property) => property.heap_size_of(),
+            Property::Int16Property(property) => property.heap_size_of(),
+            Property::Int64Property(property) => property.heap_size_of(),
+            Property::Int8Property(property) => property.heap_size_of(),
+            Property::IntProperty(property) => property.heap_size_of(),
+            Property::MapProperty(property) => property.heap_size_of(),
+            Property::NameProperty(property) => property.heap_size_of(),
+            Property::ObjectProperty(property) => property.heap_size_of(),
+            Property::SetProperty(property) => property.heap_size_of(),
+            Property::StrProperty(property) => property.heap_size_of(),
+            Property::StructProperty(property) => property.heap_size_of(),
+            Property::StructPropertyValue(property) => property.heap_size_of(),
+            Property::TextProperty(property) => property.heap_size_of(),
+            Property::UInt16Property(property) => property.heap_size_of(),
+            Property::UInt32Property(property) => property.heap_size_of(),
+            Property::UInt64Property(property) => property.heap_size_of(),
+            Property::UnknownProperty(property) => property.heap_size_of(),
+            Property::DelegateProperty(property) => property.heap_size_of(),
+            Property::MulticastInlineDelegateProperty(property) => property.heap_size_of(),
+            Property::MulticastSparseDelegateProperty(property) => property.heap_size_of(),
+        }
+    }
+}
+
+impl DeepSizeOf for DeserializedGameVersion {
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        match self {
+            DeserializedGameVersion::Zip(entry_name) => entry_name.heap_size_of(),
+            _ => 0,
+        }
+    }
+}
+
+impl DeepSizeOf for GvasHeader {
+    fn heap_size_of(&self) -> usize {
+        match self {
+            GvasHeader::Version2 {
+                engine_version,
+                custom_versions,
+                save_game_class_name,
+                ..
+            }
+            | GvasHeader::Version3 {
+                engine_version,
+                custom_versions,
+                save_game_class_name,
+                ..
+            } => {
+                engine_version.heap_size_of()
+                    + custom_versions.heap_size_of()
+                    + save_game_class_name.heap_size_of()
+            }
+        }
+    }
+}
+
+impl_fields!(GvasFile {
+    deserialized_game_version,
+    prefix,
+    header,
+    custom_version_extra,
+    properties
+});
@@ -0,0 +1,181 @@
+//! Differences between saves
+//!
+//! [`diff`] compares the properties of two files and reports every property that was added,
+//! removed or changed, by path. Paths use the format described in [`visitor`](crate::visitor).
+//!
+//! Values that change every time a game is played, like timestamps or session counters, can be
+//! left out with [`DiffOptions::ignore`] patterns. A pattern matches a path and everything below
+//! it, and `*` matches any run of characters, e.g. `*.LastPlayed` or `Stats.Sessions[*]`.
+//!
+//! [`diff`]: crate::diff::diff
+//! [`DiffOptions::ignore`]: crate::diff::DiffOptions::ignore
+//!
+//! ```
+//! use gvas::{
+//!     diff::{diff, Change, DiffOptions},
+//!     game_version::GameVersion,
+//!     properties::{int_property::IntProperty, Property},
+//!     GvasFile,
+//! };
+//! use std::fs::File;
+//!
+//! let mut file = File::open("resources/test/Slot1.sav")?;
+//! let old = GvasFile::read(&mut file, GameVersion::Default)?;
+//! let mut new = old.clone();
+//! new.properties
+//!     .insert(String::from("int32_test"), Property::from(IntProperty::new(5)));
+//!
+//! let options = DiffOptions::default().ignore("date_time_property");
+//! let differences = diff(&old, &new, &options);
+//! assert_eq!(differences.len(), 1);
+//! assert_eq!(differences[0].path, "int32_test");
+//! assert!(matches!(differences[0].change, Change::Changed(_, _)));
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+
+use indexmap::IndexMap;
+
+use crate::{properties::Property, query::glob, visitor, GvasFile};
+
+/// Options used by [`diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffOptions {
+    /// Patterns of paths that are left out of the comparison.
+    pub ignore: Vec<String>,
+}
+
+impl DiffOptions {
+    /// Leave out paths matching `pattern`.
+    #[inline]
+    pub fn ignore<S: Into<String>>(mut self, pattern: S) -> Self {
+        self.ignore.push(pattern.into());
+        self
+    }
+
+    /// Check if `path` is left out of the comparison.
+    pub fn is_ignored(&self, path: &str) -> bool {
+        self.ignore
+            .iter()
+            .any(|pattern| matches_pattern(pattern, path))
+    }
+}
+
+/// How a property differs between two files.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change<'a> {
+    /// The property only exists in the new file.
+    Added(&'a Property),
+    /// The property only exists in the old file.
+    Removed(&'a Property),
+    /// The property has a different value, old first.
+    Changed(&'a Property, &'a Property),
+}
+
+/// A property that differs between two files.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difference<'a> {
+    /// Path of the property.
+    pub path: String,
+    /// How the property differs.
+    pub change: Change<'a>,
+}
+
+/// Compare the properties of `old` and `new`.
+///
+/// Properties are compared at the deepest level the [`visitor`] reaches, so a
+/// changed field of a struct is reported by itself, while a changed element of an
+/// [`ArrayProperty::Ints`](crate::properties::array_property::ArrayProperty::Ints) reports the
+/// whole array. Differences are ordered like the properties of `old`, followed by the properties
+/// that were added.
+pub fn diff<'a>(
+    old: &'a GvasFile,
+    new: &'a GvasFile,
+    options: &DiffOptions,
+) -> Vec<Difference<'a>> {
+    let old_nodes = nodes(old);
+    let new_nodes = nodes(new);
+
+    let mut differences = Vec::new();
+    for (path, old_property) in terminal(&old_nodes) {
+        if options.is_ignored(path) {
+            continue;
+        }
+        let change = match new_nodes.get(path) {
+            None => Change::Removed(old_property),
+            Some(new_property) if new_property != &old_property => {
+                Change::Changed(old_property, new_property)
+            }
+            Some(_) => continue,
+        };
+        differences.push(Difference {
+            path: path.clone(),
+            change,
+        });
+    }
+    for (path, new_property) in terminal(&new_nodes) {
+        if options.is_ignored(path) {
+            continue;
+        }
+        match old_nodes.get(path) {
+            None => differences.push(Difference {
+                path: path.clone(),
+                change: Change::Added(new_property),
+            }),
+            // Reported above, unless the old property has children here
+            Some(old_property)
+                if old_property != &new_property && !is_terminal(&old_nodes, path) =>
+            {
+                differences.push(Difference {
+                    path: path.clone(),
+                    change: Change::Changed(old_property, new_property),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    differences
+}
+
+/// Every property of `file` by path, parents before their children.
+fn nodes(file: &GvasFile) -> IndexMap<String, &Property> {
+    file.properties
+        .iter()
+        .flat_map(|(name, property)| visitor::nodes(name, property))
+        .collect()
+}
+
+/// Check if the property at `path` has no children.
+fn is_terminal(nodes: &IndexMap<String, &Property>, path: &str) -> bool {
+    let Some(index) = nodes.get_index_of(path) else {
+        return false;
+    };
+    nodes
+        .get_index(index + 1)
+        .is_none_or(|(next, _)| !is_child(path, next))
+}
+
+/// The properties without children.
+fn terminal<'n, 'a>(
+    nodes: &'n IndexMap<String, &'a Property>,
+) -> impl Iterator<Item = (&'n String, &'a Property)> {
+    let next = nodes.keys().skip(1).map(Some).chain([None]);
+    nodes
+        .iter()
+        .zip(next)
+        .filter(|((path, _), next)| next.is_none_or(|next| !is_child(path, next)))
+        .map(|((path, property), _)| (path, *property))
+}
+
+#[inline]
+fn is_child(parent: &str, path: &str) -> bool {
+    path.strip_prefix(parent)
+        .is_some_and(|rest| rest.starts_with(['.', '[']))
+}
+
+/// Check if `pattern` matches `path` or one of its parents.
+fn matches_pattern(pattern: &str, path: &str) -> bool {
+    path.match_indices(['.', '['])
+        .map(|(index, _)| &path[..index])
+        .chain([path])
+        .any(|path| glob(pattern, path))
+}
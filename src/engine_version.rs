@@ -3,6 +3,7 @@
 use crate::cursor_ext::{ReadExt, WriteExt};
 use crate::error::Error;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::cmp::Ordering;
 use std::fmt::Display;
 use std::io::{Read, Seek, Write};
 
@@ -74,6 +75,85 @@ impl FEngineVersion {
         Ok(len)
     }
 
+    /// Get the parsed [`EngineBranch`]
+    #[inline]
+    pub fn get_branch(&self) -> EngineBranch {
+        EngineBranch::parse(&self.branch)
+    }
+
+    /// Major, minor and patch version numbers
+    #[inline]
+    pub fn version(&self) -> (u16, u16, u16) {
+        (self.major, self.minor, self.patch)
+    }
+
+    /// Compare the major, minor and patch version numbers, ignoring the change list and branch
+    ///
+    /// ```
+    /// use gvas::engine_version::FEngineVersion;
+    /// use std::cmp::Ordering;
+    ///
+    /// let release = FEngineVersion::new(5, 3, 2, 29314046, String::from("++UE5+Release-5.3"));
+    /// let licensee = FEngineVersion::new(5, 3, 2, 0, String::from("++Game+Main"));
+    /// assert_eq!(release.cmp_version(&licensee), Ordering::Equal);
+    /// assert!(release.same_version(&licensee));
+    /// assert_ne!(release, licensee);
+    /// ```
+    #[inline]
+    pub fn cmp_version(&self, other: &Self) -> Ordering {
+        self.version().cmp(&other.version())
+    }
+
+    /// Returns true if both have the same major, minor and patch version numbers
+    #[inline]
+    pub fn same_version(&self, other: &Self) -> bool {
+        self.cmp_version(other) == Ordering::Equal
+    }
+
+    /// Returns true if this is at least version `major.minor`
+    #[inline]
+    pub fn is_at_least(&self, major: u16, minor: u16) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+
+    /// A copy with the change list set to 0
+    ///
+    /// Licensee and editor builds often save a change list of 0, normalizing it lets the same
+    /// engine version from different builds compare equal.
+    #[inline]
+    pub fn normalized(&self) -> Self {
+        FEngineVersion {
+            change_list: 0,
+            ..self.clone()
+        }
+    }
+
+    /// Get the [`EngineFamily`]
+    ///
+    /// Uses the major version number, or the depot of the branch when the major version is 0.
+    ///
+    /// ```
+    /// use gvas::engine_version::{EngineFamily, FEngineVersion};
+    ///
+    /// let version = FEngineVersion::new(4, 27, 2, 0, String::from("++UE4+Release-4.27"));
+    /// assert_eq!(version.family(), EngineFamily::UE4);
+    ///
+    /// let version = FEngineVersion::new(0, 0, 0, 0, String::from("++UE5+Release-5.1"));
+    /// assert_eq!(version.family(), EngineFamily::UE5);
+    /// ```
+    pub fn family(&self) -> EngineFamily {
+        match self.major {
+            0 => match self.get_branch().depot.as_str() {
+                "UE4" => EngineFamily::UE4,
+                "UE5" => EngineFamily::UE5,
+                _ => EngineFamily::Other(0),
+            },
+            4 => EngineFamily::UE4,
+            5 => EngineFamily::UE5,
+            major => EngineFamily::Other(major),
+        }
+    }
+
     /// Get [`EngineVersion`]
     pub fn get_version(&self) -> EngineVersion {
         match (self.major, self.minor) {
@@ -113,6 +193,86 @@ impl FEngineVersion {
     }
 }
 
+/// Major engine release, see [`FEngineVersion::family`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EngineFamily {
+    /// Unreal Engine 4
+    UE4,
+    /// Unreal Engine 5
+    UE5,
+    /// Any other major version, 0 if unknown
+    Other(u16),
+}
+
+/// Branch an engine was built from, parsed from [`FEngineVersion::branch`]
+///
+/// Branch strings are depot paths with `/` replaced by `+`, such as `++UE5+Release-5.3` for a
+/// release build or `++Fortnite+Main` for a licensee stream.
+///
+/// ```
+/// use gvas::engine_version::EngineBranch;
+///
+/// let branch = EngineBranch::parse("++UE5+Release-5.3");
+/// assert_eq!(branch.depot, "UE5");
+/// assert_eq!(branch.stream, "Release-5.3");
+/// assert!(branch.is_release());
+/// assert!(!branch.is_custom());
+/// assert_eq!(branch.release_version(), Some((5, 3)));
+/// assert_eq!(branch.to_string(), "++UE5+Release-5.3");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EngineBranch {
+    /// Depot name, `UE5` for `++UE5+Release-5.3`
+    pub depot: String,
+    /// Stream name within the depot, `Release-5.3` for `++UE5+Release-5.3`
+    pub stream: String,
+}
+
+impl EngineBranch {
+    /// Parse a branch string, accepting `+` or `/` as separators
+    pub fn parse(branch: &str) -> Self {
+        let branch = branch.replace('/', "+");
+        let branch = branch.trim_matches('+');
+        let (depot, stream) = branch.split_once('+').unwrap_or((branch, ""));
+        EngineBranch {
+            depot: depot.to_string(),
+            stream: stream.trim_start_matches('+').to_string(),
+        }
+    }
+
+    /// Returns true if the engine was built from a `Release-*` stream
+    #[inline]
+    pub fn is_release(&self) -> bool {
+        self.stream.starts_with("Release-")
+    }
+
+    /// Returns true if the engine was not built from an Epic `UE4` or `UE5` release stream
+    #[inline]
+    pub fn is_custom(&self) -> bool {
+        !(matches!(self.depot.as_str(), "UE4" | "UE5") && self.is_release())
+    }
+
+    /// Major and minor version of a release stream, `(5, 3)` for `Release-5.3`
+    pub fn release_version(&self) -> Option<(u16, u16)> {
+        let version = self.stream.strip_prefix("Release-")?;
+        let (major, minor) = version.split_once('.')?;
+        let minor_len = minor
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(minor.len());
+        Some((major.parse().ok()?, minor[..minor_len].parse().ok()?))
+    }
+}
+
+impl Display for EngineBranch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.stream.is_empty() {
+            write!(f, "++{}", self.depot)
+        } else {
+            write!(f, "++{}+{}", self.depot, self.stream)
+        }
+    }
+}
+
 /// UE4 Engine version enum
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[allow(non_camel_case_types)]
@@ -0,0 +1,186 @@
+//! Values of enums used in a save
+//!
+//! Saves store enum values by name, in `EnumProperty` values, namespaced `ByteProperty` values,
+//! arrays of enums and the keys of maps from enums. [`collect`] tallies every value below a
+//! property into an [`EnumUsage`], grouped by enum type, with the paths of the properties
+//! holding each value. This lists the values a game uses without consulting its source.
+//!
+//! The enum type is the type stored with the value. Values stored without a type, like the
+//! elements of enum arrays, are grouped by the namespace before `::` in the value, e.g.
+//! `EFreightType` for `EFreightType::Lumber`, or under an empty type when the value has none.
+//!
+//! Paths use the format described in [`visitor`](crate::visitor). Map keys have the path of
+//! their entry.
+//!
+//! [`collect`]: crate::enum_usage::collect
+//! [`EnumUsage`]: crate::enum_usage::EnumUsage
+//!
+//! ```
+//! use gvas::{
+//!     enum_usage,
+//!     properties::{array_property::ArrayProperty, Property},
+//! };
+//!
+//! let property = Property::from(ArrayProperty::Enums {
+//!     enums: vec![
+//!         String::from("EFreightType::Lumber"),
+//!         String::from("EFreightType::None"),
+//!         String::from("EFreightType::Lumber"),
+//!     ],
+//! });
+//!
+//! let usage = enum_usage::collect("Freight", &property);
+//! assert_eq!(usage.enum_types().collect::<Vec<_>>(), ["EFreightType"]);
+//! assert_eq!(usage.count("EFreightType", "EFreightType::Lumber"), 2);
+//! assert_eq!(
+//!     usage.paths("EFreightType", "EFreightType::Lumber"),
+//!     ["Freight[0]", "Freight[2]"]
+//! );
+//! ```
+
+use indexmap::IndexMap;
+
+use crate::{
+    properties::{
+        array_property::ArrayProperty,
+        int_property::{ByteProperty, BytePropertyValue},
+        map_property::MapProperty,
+        Property,
+    },
+    visitor::{walk, Visitor},
+};
+
+/// The enum values used in a property tree, see the [module documentation](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnumUsage {
+    /// Every enum type with its values and the paths of the properties holding each value, in
+    /// the order they were found.
+    pub enums: IndexMap<String, IndexMap<String, Vec<String>>>,
+}
+
+impl EnumUsage {
+    /// Creates a new, empty `EnumUsage` instance.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the values of `other` to this usage.
+    pub fn extend(&mut self, other: EnumUsage) {
+        for (enum_type, values) in other.enums {
+            let entry = self.enums.entry(enum_type).or_default();
+            for (value, paths) in values {
+                entry.entry(value).or_default().extend(paths);
+            }
+        }
+    }
+
+    /// Returns true if no enum value was found.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.enums.is_empty()
+    }
+
+    /// The enum types, in the order they were found.
+    pub fn enum_types(&self) -> impl Iterator<Item = &str> {
+        self.enums.keys().map(String::as_str)
+    }
+
+    /// Every value of `enum_type` with the number of times it's used, in the order they were
+    /// found.
+    pub fn values<'s>(&'s self, enum_type: &str) -> impl Iterator<Item = (&'s str, usize)> {
+        self.enums
+            .get(enum_type)
+            .into_iter()
+            .flatten()
+            .map(|(value, paths)| (value.as_str(), paths.len()))
+    }
+
+    /// The number of times `value` of `enum_type` is used.
+    pub fn count(&self, enum_type: &str, value: &str) -> usize {
+        self.paths(enum_type, value).len()
+    }
+
+    /// The paths of the properties holding `value` of `enum_type`.
+    pub fn paths(&self, enum_type: &str, value: &str) -> &[String] {
+        self.enums
+            .get(enum_type)
+            .and_then(|values| values.get(value))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn push(&mut self, enum_type: Option<&str>, value: &str, path: String) {
+        let enum_type = match enum_type {
+            Some(enum_type) if !enum_type.is_empty() && enum_type != "None" => enum_type,
+            _ => value
+                .split_once("::")
+                .map_or("", |(namespace, _)| namespace),
+        };
+        let values = match self.enums.get_mut(enum_type) {
+            Some(values) => values,
+            None => self.enums.entry(enum_type.to_string()).or_default(),
+        };
+        match values.get_mut(value) {
+            Some(paths) => paths.push(path),
+            None => {
+                values.insert(value.to_string(), vec![path]);
+            }
+        }
+    }
+
+    fn push_keys<'k>(&mut self, keys: impl Iterator<Item = &'k String>, path: &str) {
+        for (index, key) in keys.enumerate() {
+            self.push(None, key, format!("{path}[{index}]"));
+        }
+    }
+}
+
+/// Find every enum value below `property`, with `path` as the path of `property`.
+pub fn collect(path: &str, property: &Property) -> EnumUsage {
+    let mut visitor = EnumVisitor {
+        usage: EnumUsage::new(),
+    };
+    walk(&mut visitor, path, property);
+    visitor.usage
+}
+
+/// Collects the enum values of a property tree, see [`collect`].
+struct EnumVisitor {
+    usage: EnumUsage,
+}
+
+impl Visitor for EnumVisitor {
+    fn visit_property(&mut self, path: &str, property: &Property) -> bool {
+        match property {
+            Property::EnumProperty(property) => {
+                self.usage.push(
+                    property.enum_type.as_deref(),
+                    &property.value,
+                    path.to_string(),
+                );
+            }
+            Property::ByteProperty(ByteProperty {
+                name,
+                value: BytePropertyValue::Namespaced(value),
+            }) => {
+                self.usage.push(name.as_deref(), value, path.to_string());
+            }
+            Property::ArrayProperty(ArrayProperty::Enums { enums }) => {
+                for (index, value) in enums.iter().enumerate() {
+                    self.usage.push(None, value, format!("{path}[{index}]"));
+                }
+            }
+            Property::MapProperty(MapProperty::EnumBool { enum_bools }) => {
+                self.usage.push_keys(enum_bools.keys(), path);
+            }
+            Property::MapProperty(MapProperty::EnumInt { enum_ints }) => {
+                self.usage.push_keys(enum_ints.keys(), path);
+            }
+            Property::MapProperty(MapProperty::EnumProperty { enum_props, .. }) => {
+                self.usage.push_keys(enum_props.keys(), path);
+            }
+            _ => {}
+        }
+        true
+    }
+}
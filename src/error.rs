@@ -1,12 +1,16 @@
 use std::{
+    fmt::Display,
     io,
     string::{FromUtf16Error, FromUtf8Error},
 };
 
 use thiserror::Error;
 
+use crate::game_version::GameVersion;
+
 /// Gets thrown when there is a deserialization error
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum DeserializeError {
     /// If the GVAS header is not valid
     #[error("Invalid header: {0}")]
@@ -17,6 +21,11 @@ pub enum DeserializeError {
     /// If a string has invalid size
     #[error("Invalid string size {0} at position {1:#x}")]
     InvalidString(i32, u64),
+    /// If a string is longer than [`ReadOptions::max_string_len`]
+    ///
+    /// [`ReadOptions::max_string_len`]: crate::options::ReadOptions::max_string_len
+    #[error("String of {0} characters is longer than the limit of {1} at position {2:#x}")]
+    StringTooLong(u32, u32, u64),
     /// Invalid string terminator
     #[error("Invalid string terminator {0} at position {1:#x}")]
     InvalidStringTerminator(u16, u64),
@@ -41,6 +50,43 @@ pub enum DeserializeError {
     /// Invalid terminator
     #[error("Unexpected terminator value {0} at position {1:#x}")]
     InvalidTerminator(u8, u64),
+    /// If the byte separating a property tag from its body isn't zero
+    #[error("Unexpected separator value {0} at position {1:#x}")]
+    InvalidSeparator(u8, u64),
+    /// If a property body doesn't have the length declared in its tag
+    #[error("Property body has length {actual}, expected {expected} at position {position:#x}")]
+    LengthMismatch {
+        /// Length declared in the property tag
+        expected: u64,
+        /// Length of the body read
+        actual: u64,
+        /// Position of the body
+        position: u64,
+    },
+    /// If the decompressed data length doesn't match the length declared in the header
+    #[error("Declared decompressed length {0} doesn't match actual length {1}")]
+    DecompressedLengthMismatch(u64, u64),
+    /// If a struct array element doesn't have the size implied by the array header
+    #[error("Array element {0} has size {2}, expected {1} at position {3:#x}")]
+    MisalignedArrayElement(u32, u64, u64, u64),
+    /// If an element count doesn't fit in the remaining bytes of a property
+    #[error("Element count {0} doesn't fit in the remaining {1} bytes at position {2:#x}")]
+    InvalidElementCount(u32, u64, u64),
+    /// If a delegate list has more entries than allowed by [`DelegateLimit::Fail`]
+    ///
+    /// [`DelegateLimit::Fail`]: crate::options::DelegateLimit::Fail
+    #[error("Delegate list has {0} entries, more than the limit of {1} at position {2:#x}")]
+    TooManyDelegates(u32, u32, u64),
+    /// If a map contains the same key more than once
+    #[error("Duplicate map key {0} at path {1} at position {2:#x}")]
+    DuplicateMapKey(Box<str>, Box<str>, u64),
+    /// If a save can't be read with the requested [`GameVersion`] but starts like a save of the
+    /// suggested one
+    #[error("Save can't be read as GameVersion::{0:?}, it looks like GameVersion::{1:?}")]
+    WrongGameVersion(GameVersion, GameVersion),
+    /// If the signature of a save is missing or doesn't match its contents
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(Box<str>),
     /// If a string has invalid UTF-16 formatting
     #[error("Invalid UTF-16 string at position {1:#x}")]
     FromUtf16Error(#[source] FromUtf16Error, u64),
@@ -86,6 +132,7 @@ impl DeserializeError {
 
 /// Gets thrown when there is a serialization error
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum SerializeError {
     /// A value was invalid
     #[error("Invalid value {0}")]
@@ -93,6 +140,9 @@ pub enum SerializeError {
     /// Struct is missing a field, e.g. struct with type_name `Vector` doesn't have an `X` property
     #[error("Struct {0} missing field {1}")]
     StructMissingField(Box<str>, Box<str>),
+    /// If a property path doesn't exist
+    #[error("Property {0} not found")]
+    PropertyNotFound(Box<str>),
 }
 
 impl SerializeError {
@@ -114,16 +164,181 @@ impl SerializeError {
     }
 }
 
-/// A wrapper for the various error types this crate can emit
+/// What went wrong while reading or writing, see [`Error::kind`]
 #[derive(Error, Debug)]
-pub enum Error {
+#[non_exhaustive]
+pub enum ErrorKind {
     /// A `DeserializeError` occurred
     #[error(transparent)]
     Deserialize(#[from] DeserializeError),
     /// A `SerializeError` occurred
     #[error(transparent)]
     Serialize(#[from] SerializeError),
+}
+
+/// A wrapper for the various error types this crate can emit
+///
+/// Errors are grouped by how a caller may recover from them, the [`DeserializeError`] or
+/// [`SerializeError`] inside describes the exact cause.
+///
+/// The message of an error includes the message of its cause, so [`std::error::Error::source`]
+/// skips the cause and returns the error behind it, if any.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
     /// An `std::io::Error` occured
-    #[error(transparent)]
-    Io(#[from] io::Error),
+    Io(io::Error),
+    /// The header, or the container around it, is invalid
+    Header(DeserializeError),
+    /// A property couldn't be read or written
+    Property {
+        /// Path of the property, if known
+        path: Option<Box<str>>,
+        /// What went wrong
+        kind: ErrorKind,
+    },
+    /// A value exceeded a limit, e.g. a length that doesn't match the data
+    Limit(DeserializeError),
+    /// The data is valid but uses a format or version this crate doesn't support
+    Unsupported(ErrorKind),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => Display::fmt(e, f),
+            Self::Header(e) | Self::Limit(e) => Display::fmt(e, f),
+            Self::Property {
+                path: Some(path),
+                kind,
+            } => write!(f, "{kind} in property {path}"),
+            Self::Property { path: None, kind } | Self::Unsupported(kind) => Display::fmt(kind, f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => e.source(),
+            Self::Header(e) | Self::Limit(e) => e.source(),
+            Self::Property { kind, .. } | Self::Unsupported(kind) => kind.source(),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    #[inline]
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl Error {
+    /// A helper for creating `Unsupported` errors
+    #[inline]
+    pub fn unsupported<K: Into<ErrorKind>>(kind: K) -> Self {
+        Self::Unsupported(kind.into())
+    }
+
+    /// The property path of a `Property` error, if known
+    #[inline]
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            Self::Property { path, .. } => path.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The cause of a `Property` or `Unsupported` error, `None` for the other variants
+    ///
+    /// The cause of `Header` and `Limit` errors is returned by [`Error::as_deserialize`].
+    #[inline]
+    pub fn kind(&self) -> Option<&ErrorKind> {
+        match self {
+            Self::Property { kind, .. } | Self::Unsupported(kind) => Some(kind),
+            Self::Io(_) | Self::Header(_) | Self::Limit(_) => None,
+        }
+    }
+
+    /// The `DeserializeError` causing this error, if any
+    #[inline]
+    pub fn as_deserialize(&self) -> Option<&DeserializeError> {
+        match self {
+            Self::Header(e) | Self::Limit(e) => Some(e),
+            Self::Property {
+                kind: ErrorKind::Deserialize(e),
+                ..
+            }
+            | Self::Unsupported(ErrorKind::Deserialize(e)) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// The `SerializeError` causing this error, if any
+    #[inline]
+    pub fn as_serialize(&self) -> Option<&SerializeError> {
+        match self {
+            Self::Property {
+                kind: ErrorKind::Serialize(e),
+                ..
+            }
+            | Self::Unsupported(ErrorKind::Serialize(e)) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Sets the path of a `Property` error that doesn't have one yet
+    pub(crate) fn in_property<S: AsRef<str>>(self, stack: &[S]) -> Self {
+        match self {
+            Self::Property { path: None, kind } => Self::Property {
+                path: Some(
+                    stack
+                        .iter()
+                        .map(AsRef::as_ref)
+                        .collect::<Vec<_>>()
+                        .join(".")
+                        .into_boxed_str(),
+                ),
+                kind,
+            },
+            error => error,
+        }
+    }
+}
+
+impl From<DeserializeError> for Error {
+    fn from(e: DeserializeError) -> Self {
+        match e {
+            DeserializeError::InvalidHeader(_) | DeserializeError::WrongGameVersion(..) => {
+                Error::Header(e)
+            }
+            DeserializeError::DecompressedLengthMismatch(..)
+            | DeserializeError::TooManyDelegates(..)
+            | DeserializeError::StringTooLong(..) => Error::Limit(e),
+            e => Error::Property {
+                path: None,
+                kind: e.into(),
+            },
+        }
+    }
+}
+
+impl From<SerializeError> for Error {
+    fn from(e: SerializeError) -> Self {
+        Error::Property {
+            path: None,
+            kind: e.into(),
+        }
+    }
+}
+
+#[cfg(feature = "zip")]
+impl From<zip::result::ZipError> for Error {
+    fn from(e: zip::result::ZipError) -> Self {
+        match e {
+            zip::result::ZipError::Io(e) => Error::Io(e),
+            e => DeserializeError::InvalidHeader(format!("Invalid zip archive: {e}").into()).into(),
+        }
+    }
 }
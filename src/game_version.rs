@@ -1,43 +1,150 @@
 //! Game version enumeration
 
-use num_enum::{IntoPrimitive, TryFromPrimitive};
+use crate::container::CustomContainer;
 
 /// Game version enumeration
 ///
 /// Used for specifying game versions if a game has custom serialization
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum GameVersion {
     /// Default GVAS serialization
     Default,
     /// Palworld serialization
     Palworld,
+    /// Deep Rock Galactic, default serialization read with the hints of
+    /// [`Hints::deep_rock_galactic`] added
+    ///
+    /// Files are read with [`DeserializedGameVersion::Default`].
+    ///
+    /// [`Hints::deep_rock_galactic`]: crate::hints::Hints::deep_rock_galactic
+    DeepRockGalactic,
+    /// Chunked zlib container, see [`container::chunked`]
+    ///
+    /// [`container::chunked`]: crate::container::chunked
+    Chunked,
+    /// Zip archive containing a single save, see [`container::zip`]
+    ///
+    /// Reading and writing zip archives needs the `zip` feature.
+    ///
+    /// [`container::zip`]: crate::container::zip
+    Zip,
+    /// Grounded zlib container, see [`container::grounded`]
+    ///
+    /// [`container::grounded`]: crate::container::grounded
+    Grounded,
+    /// Detect the container from the leading bytes, see [`container::detect`]
+    ///
+    /// [`container::detect`]: crate::container::detect
+    Auto,
 }
 
 /// Palworld compression type
-#[derive(Debug, Copy, Clone, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[repr(i8)]
 pub enum PalworldCompressionType {
     /// None
-    None = 0x30,
+    None,
     /// Zlib
-    Zlib = 0x31,
+    Zlib,
     /// Zlib twice
-    ZlibTwice = 0x32,
+    ZlibTwice,
+    /// A compression type unknown to this version of the crate
+    Other(u8),
+}
+
+impl From<i8> for PalworldCompressionType {
+    #[inline]
+    fn from(value: i8) -> Self {
+        match value {
+            0x30 => PalworldCompressionType::None,
+            0x31 => PalworldCompressionType::Zlib,
+            0x32 => PalworldCompressionType::ZlibTwice,
+            _ => PalworldCompressionType::Other(value as u8),
+        }
+    }
+}
+
+impl From<PalworldCompressionType> for i8 {
+    #[inline]
+    fn from(value: PalworldCompressionType) -> Self {
+        match value {
+            PalworldCompressionType::None => 0x30,
+            PalworldCompressionType::Zlib => 0x31,
+            PalworldCompressionType::ZlibTwice => 0x32,
+            PalworldCompressionType::Other(value) => value as i8,
+        }
+    }
+}
+
+/// Lengths declared in a Palworld save header
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct PalworldLengths {
+    /// Declared length of the decompressed GVAS data
+    pub decompressed: u32,
+    /// Declared length of the compressed data
+    pub compressed: u32,
 }
 
 /// Deserialized game version
 ///
 /// Used for storing additional deserialized information about custom serialization
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///
+/// Declared lengths only describe the file that was read, they are recalculated on write and
+/// are ignored when comparing.
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DeserializedGameVersion {
     /// Default GVAS serialization
     Default,
     /// Palworld serialization
-    Palworld(PalworldCompressionType),
+    Palworld(
+        PalworldCompressionType,
+        #[cfg_attr(feature = "serde", serde(skip))] PalworldLengths,
+    ),
+    /// Chunked zlib container with the given block size
+    Chunked(u64),
+    /// Zip archive with the given entry name, written with the `zip` feature
+    Zip(String),
+    /// Grounded zlib container
+    Grounded,
+    /// Container implemented outside of this crate, see [`GvasFile::read_with_container`]
+    ///
+    /// Custom containers can't be serialized.
+    ///
+    /// [`GvasFile::read_with_container`]: crate::GvasFile::read_with_container
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(CustomContainer),
 }
 
+impl PartialEq for DeserializedGameVersion {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DeserializedGameVersion::Default, DeserializedGameVersion::Default) => true,
+            (
+                DeserializedGameVersion::Palworld(compression_type, _),
+                DeserializedGameVersion::Palworld(other_compression_type, _),
+            ) => compression_type == other_compression_type,
+            (
+                DeserializedGameVersion::Chunked(block_size),
+                DeserializedGameVersion::Chunked(other_block_size),
+            ) => block_size == other_block_size,
+            (
+                DeserializedGameVersion::Zip(entry_name),
+                DeserializedGameVersion::Zip(other_entry_name),
+            ) => entry_name == other_entry_name,
+            (DeserializedGameVersion::Grounded, DeserializedGameVersion::Grounded) => true,
+            (
+                DeserializedGameVersion::Custom(container),
+                DeserializedGameVersion::Custom(other_container),
+            ) => container == other_container,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for DeserializedGameVersion {}
+
 impl Default for DeserializedGameVersion {
     #[inline]
     fn default() -> Self {
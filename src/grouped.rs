@@ -0,0 +1,150 @@
+//! Grouped views over top-level properties
+//!
+//! Some saves store several sub-objects side by side in the root property list, e.g.
+//! `SplineTrackTypeArray`, `SplineTrackLocationArray`, `IndustryTypeArray`, ...
+//! These views group the properties by a user-provided key without changing the file.
+
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+
+use crate::{properties::Property, types::map::HashableIndexMap};
+
+/// A read-only view of properties grouped by key, see [`GvasFile::group_by`].
+///
+/// [`GvasFile::group_by`]: crate::GvasFile::group_by
+#[derive(Debug)]
+pub struct GroupedView<'a, K> {
+    groups: IndexMap<K, IndexMap<&'a str, &'a Property>>,
+}
+
+impl<'a, K: Hash + Eq> GroupedView<'a, K> {
+    pub(crate) fn new<F>(properties: &'a HashableIndexMap<String, Property>, mut key: F) -> Self
+    where
+        F: FnMut(&str) -> K,
+    {
+        let mut groups: IndexMap<K, IndexMap<&'a str, &'a Property>> = IndexMap::new();
+        for (name, property) in properties.iter() {
+            groups
+                .entry(key(name))
+                .or_default()
+                .insert(name.as_str(), property);
+        }
+        GroupedView { groups }
+    }
+
+    /// Get the properties of a group.
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<&IndexMap<&'a str, &'a Property>> {
+        self.groups.get(key)
+    }
+
+    /// Get a property by group and name.
+    #[inline]
+    pub fn get_property(&self, key: &K, name: &str) -> Option<&'a Property> {
+        self.groups.get(key)?.get(name).copied()
+    }
+
+    /// Iterate over the groups in the order they first appear in the file.
+    #[inline]
+    pub fn iter(&self) -> indexmap::map::Iter<'_, K, IndexMap<&'a str, &'a Property>> {
+        self.groups.iter()
+    }
+
+    /// Iterate over the group keys.
+    #[inline]
+    pub fn keys(&self) -> indexmap::map::Keys<'_, K, IndexMap<&'a str, &'a Property>> {
+        self.groups.keys()
+    }
+
+    /// Number of groups.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Returns true if there are no groups.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
+
+/// A mutable view of properties grouped by key, see [`GvasFile::group_by_mut`].
+///
+/// Changes made through the view are written directly to the file.
+///
+/// [`GvasFile::group_by_mut`]: crate::GvasFile::group_by_mut
+#[derive(Debug)]
+pub struct GroupedViewMut<'a, K> {
+    groups: IndexMap<K, IndexMap<&'a str, &'a mut Property>>,
+}
+
+impl<'a, K: Hash + Eq> GroupedViewMut<'a, K> {
+    pub(crate) fn new<F>(properties: &'a mut HashableIndexMap<String, Property>, mut key: F) -> Self
+    where
+        F: FnMut(&str) -> K,
+    {
+        let mut groups: IndexMap<K, IndexMap<&'a str, &'a mut Property>> = IndexMap::new();
+        for (name, property) in properties.iter_mut() {
+            groups
+                .entry(key(name))
+                .or_default()
+                .insert(name.as_str(), property);
+        }
+        GroupedViewMut { groups }
+    }
+
+    /// Get the properties of a group.
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<&IndexMap<&'a str, &'a mut Property>> {
+        self.groups.get(key)
+    }
+
+    /// Get the properties of a group mutably.
+    #[inline]
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut IndexMap<&'a str, &'a mut Property>> {
+        self.groups.get_mut(key)
+    }
+
+    /// Get a property by group and name.
+    #[inline]
+    pub fn get_property(&self, key: &K, name: &str) -> Option<&Property> {
+        self.groups.get(key)?.get(name).map(|property| &**property)
+    }
+
+    /// Get a property by group and name mutably.
+    #[inline]
+    pub fn get_property_mut(&mut self, key: &K, name: &str) -> Option<&mut Property> {
+        self.groups
+            .get_mut(key)?
+            .get_mut(name)
+            .map(|property| &mut **property)
+    }
+
+    /// Iterate over the groups in the order they first appear in the file.
+    #[inline]
+    pub fn iter_mut(
+        &mut self,
+    ) -> indexmap::map::IterMut<'_, K, IndexMap<&'a str, &'a mut Property>> {
+        self.groups.iter_mut()
+    }
+
+    /// Iterate over the group keys.
+    #[inline]
+    pub fn keys(&self) -> indexmap::map::Keys<'_, K, IndexMap<&'a str, &'a mut Property>> {
+        self.groups.keys()
+    }
+
+    /// Number of groups.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Returns true if there are no groups.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
@@ -0,0 +1,214 @@
+//! Typed hints
+//!
+//! Values stored without a property tag, like the keys of a map or the elements of a set, don't
+//! record their type. Hints are keyed by the property path of such a value and tell the parser
+//! how to read it: as a struct of a given type, with a fixed width, or as raw bytes.
+//!
+//! String hints from older versions still work, each of them names a struct type.
+//!
+//! Hints for popular games ship with the crate in [`presets`], keyed by save game class name.
+//! [`ReadOptions::preset_hints`] decides which of them are added to the hints passed in, by
+//! default the preset of a game version like [`GameVersion::DeepRockGalactic`], and with the
+//! `bundled-hints` feature the preset for the class name of every file. Hints passed in take
+//! precedence.
+//!
+//! [`GameVersion::DeepRockGalactic`]: crate::game_version::GameVersion::DeepRockGalactic
+//! [`ReadOptions::preset_hints`]: crate::options::ReadOptions::preset_hints
+//!
+//! ```
+//! use gvas::hints::{HintValue, Hints};
+//! use std::collections::HashMap;
+//!
+//! let mut hints = Hints::from(HashMap::from([(
+//!     String::from("Missions.MapProperty.Key.StructProperty"),
+//!     String::from("Guid"),
+//! )]));
+//! hints.insert(
+//!     String::from("Blobs.MapProperty.Value.StructProperty"),
+//!     HintValue::Raw(Some(32)),
+//! );
+//!
+//! assert_eq!(
+//!     hints.get("Missions.MapProperty.Key.StructProperty"),
+//!     Some(&HintValue::Struct(String::from("Guid")))
+//! );
+//! ```
+
+pub mod presets;
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    game_version::GameVersion,
+    options::{PresetHints, ReadOptions},
+};
+use presets::Preset;
+
+/// How to read a value stored without a property tag.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HintValue {
+    /// Read a struct as this struct type, e.g. `Guid` or `Vector`.
+    Struct(String),
+    /// Read the value with this width in bytes, instead of the width guessed from the container
+    /// it is stored in.
+    ElementWidth(u32),
+    /// Keep the value as raw bytes in an [`UnknownProperty`], either with the given width or with
+    /// the width guessed from the container it is stored in.
+    ///
+    /// [`UnknownProperty`]: crate::properties::unknown_property::UnknownProperty
+    Raw(Option<u32>),
+}
+
+impl From<String> for HintValue {
+    #[inline]
+    fn from(value: String) -> Self {
+        HintValue::Struct(value)
+    }
+}
+
+impl From<&str> for HintValue {
+    #[inline]
+    fn from(value: &str) -> Self {
+        HintValue::Struct(value.to_string())
+    }
+}
+
+/// Hints keyed by property path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Hints(HashMap<String, HintValue>);
+
+impl Hints {
+    /// Creates a new empty `Hints` instance.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Consumes the hints, returning the underlying map.
+    #[inline]
+    pub fn into_inner(self) -> HashMap<String, HintValue> {
+        self.0
+    }
+
+    /// Hints for Deep Rock Galactic saves, added when reading with
+    /// [`GameVersion::DeepRockGalactic`].
+    ///
+    /// ```
+    /// use gvas::hints::{HintValue, Hints};
+    ///
+    /// let hints = Hints::deep_rock_galactic();
+    /// assert_eq!(
+    ///     hints.get("UnLockedMissionParameters.MapProperty.Key.StructProperty"),
+    ///     Some(&HintValue::Struct(String::from("Guid")))
+    /// );
+    /// ```
+    pub fn deep_rock_galactic() -> Hints {
+        presets::for_game_version(GameVersion::DeepRockGalactic)
+            .map(Preset::to_hints)
+            .unwrap_or_default()
+    }
+
+    /// These hints with the preset of `game_version` added, unless `read_options` turn presets
+    /// off. The hints already present take precedence.
+    pub(crate) fn for_game_version(
+        self,
+        game_version: GameVersion,
+        read_options: &ReadOptions,
+    ) -> Hints {
+        let preset = presets::for_game_version(game_version)
+            .filter(|_| read_options.preset_hints != PresetHints::Disabled);
+        match preset {
+            Some(preset) => {
+                let mut hints = preset.to_hints();
+                hints.extend(self.0);
+                hints
+            }
+            None => self,
+        }
+    }
+
+    /// These hints with the preset for `save_game_class_name` added, if `read_options` ask for
+    /// presets by class name.
+    pub(crate) fn with_presets(
+        &self,
+        save_game_class_name: &str,
+        read_options: &ReadOptions,
+    ) -> Cow<'_, Hints> {
+        let enabled = match read_options.preset_hints {
+            PresetHints::Default => cfg!(feature = "bundled-hints"),
+            PresetHints::Enabled => true,
+            PresetHints::Disabled => false,
+        };
+        match presets::find(save_game_class_name).filter(|_| enabled) {
+            Some(preset) => {
+                let mut hints = preset.to_hints();
+                hints.extend(self.iter().map(|(path, hint)| (path.clone(), hint.clone())));
+                Cow::Owned(hints)
+            }
+            None => Cow::Borrowed(self),
+        }
+    }
+}
+
+impl Deref for Hints {
+    type Target = HashMap<String, HintValue>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Hints {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<HashMap<String, HintValue>> for Hints {
+    #[inline]
+    fn from(hints: HashMap<String, HintValue>) -> Self {
+        Hints(hints)
+    }
+}
+
+impl From<HashMap<String, String>> for Hints {
+    #[inline]
+    fn from(hints: HashMap<String, String>) -> Self {
+        hints.into_iter().collect()
+    }
+}
+
+impl From<&HashMap<String, String>> for Hints {
+    #[inline]
+    fn from(hints: &HashMap<String, String>) -> Self {
+        hints
+            .iter()
+            .map(|(path, struct_type)| (path.clone(), struct_type.as_str()))
+            .collect()
+    }
+}
+
+impl From<&Hints> for Hints {
+    #[inline]
+    fn from(hints: &Hints) -> Self {
+        hints.clone()
+    }
+}
+
+impl<K: Into<String>, V: Into<HintValue>> FromIterator<(K, V)> for Hints {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Hints(
+            iter.into_iter()
+                .map(|(path, hint)| (path.into(), hint.into()))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,268 @@
+//! Hint presets for known games
+//!
+//! A registry mapping the save game class name of a game, as stored in the header of its
+//! saves, to the hints for the values its saves store without a property tag. New games are
+//! added by appending to [`PRESETS`].
+//!
+//! Which presets are added while reading is decided by [`ReadOptions::preset_hints`]. By
+//! default the preset of a game version like [`GameVersion::DeepRockGalactic`] is added, and
+//! with the `bundled-hints` feature the preset for the class name of every file.
+//! [`GvasFile::read_with_preset_hints`] always adds the preset for the class name.
+//!
+//! [`ReadOptions::preset_hints`]: crate::options::ReadOptions::preset_hints
+//! [`GvasFile::read_with_preset_hints`]: crate::GvasFile::read_with_preset_hints
+//!
+//! ```
+//! use gvas::hints::{presets, HintValue};
+//!
+//! let hints = presets::get("/Script/FSD.FSDSaveGame").expect("Deep Rock Galactic preset");
+//! assert_eq!(
+//!     hints.get("UnLockedMissionParameters.MapProperty.Key.StructProperty"),
+//!     Some(&HintValue::Struct(String::from("Guid")))
+//! );
+//! assert!(presets::get("/Script/Game.SaveGame").is_none());
+//! ```
+
+use std::collections::HashMap;
+
+use super::{HintValue, Hints};
+use crate::game_version::GameVersion;
+
+/// Hints for the saves of a game, see the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Preset {
+    /// Save game class name of the saves of the game.
+    pub class_name: &'static str,
+    /// Game version adding this preset, whatever the class name of the file.
+    pub game_version: Option<GameVersion>,
+    /// Struct types keyed by property path.
+    pub hints: &'static [(&'static str, &'static str)],
+}
+
+impl Preset {
+    /// The hints of this preset.
+    #[inline]
+    pub fn to_hints(&self) -> Hints {
+        self.hints.iter().copied().collect()
+    }
+}
+
+/// Hint presets keyed by save game class name.
+pub const PRESETS: &[Preset] = &[
+    Preset {
+        class_name: "/Script/FSD.FSDSaveGame",
+        game_version: Some(GameVersion::DeepRockGalactic),
+        hints: DEEP_ROCK_GALACTIC,
+    },
+    Preset {
+        class_name: "/Script/Pal.PalWorldSaveGame",
+        game_version: None,
+        hints: PALWORLD,
+    },
+];
+
+/// The preset hints for saves of `save_game_class_name`.
+pub fn get(save_game_class_name: &str) -> Option<Hints> {
+    find(save_game_class_name).map(Preset::to_hints)
+}
+
+/// The preset for saves of `save_game_class_name`.
+pub fn find(save_game_class_name: &str) -> Option<&'static Preset> {
+    PRESETS
+        .iter()
+        .find(|preset| preset.class_name == save_game_class_name)
+}
+
+/// The preset added when reading with `game_version`.
+pub fn for_game_version(game_version: GameVersion) -> Option<&'static Preset> {
+    PRESETS
+        .iter()
+        .find(|preset| preset.game_version == Some(game_version))
+}
+
+/// Save game class names which have a preset.
+pub fn class_names() -> impl Iterator<Item = &'static str> {
+    PRESETS.iter().map(|preset| preset.class_name)
+}
+
+/// The preset hints applied when reading a save of `save_game_class_name` with `hints` and
+/// presets enabled, leaving out the ones overridden by `hints`.
+pub fn applied(save_game_class_name: &str, hints: &HashMap<String, HintValue>) -> Hints {
+    let mut preset = get(save_game_class_name).unwrap_or_default();
+    preset.retain(|path, _| !hints.contains_key(path));
+    preset
+}
+
+const DEEP_ROCK_GALACTIC: &[(&str, &str)] = &[
+    (
+        "SeasonSave.StructProperty.Seasons.MapProperty.Key.StructProperty",
+        "Guid",
+    ),
+    (
+        "SeasonSave.StructProperty.Seasons.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "SeasonSave.StructProperty.Seasons.MapProperty.Value.StructProperty.CompletedSpecialChallenges.MapProperty.Key.StructProperty",
+        "Guid",
+    ),
+    (
+        "UnLockedMissionParameters.MapProperty.Key.StructProperty",
+        "Guid",
+    ),
+    (
+        "UnLockedMissionParameters.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "ItemUpgradeSelections.MapProperty.Key.StructProperty",
+        "Guid",
+    ),
+    (
+        "ItemUpgradeSelections.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "ItemUpgradeLoadouts.ArrayProperty.Loadout.MapProperty.Key.StructProperty",
+        "Guid",
+    ),
+    (
+        "ItemUpgradeLoadouts.ArrayProperty.Loadout.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "EnemiesKilled.MapProperty.Key.StructProperty",
+        "Guid",
+    ),
+    (
+        "UnlockedItemSkins.MapProperty.Key.StructProperty",
+        "Guid",
+    ),
+    (
+        "UnlockedItemSkins.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "Resources.StructProperty.OwnedResources.MapProperty.Key.StructProperty",
+        "Guid",
+    ),
+    (
+        "FSDEventRewardsSave.StructProperty.EventsSeen.SetProperty.StructProperty",
+        "Guid",
+    ),
+    (
+        "GameDLCSave.StructProperty.AnnouncedIDs.SetProperty.StructProperty",
+        "Guid",
+    ),
+    (
+        "Drinks.StructProperty.UnlockedDrinks.SetProperty.StructProperty",
+        "Guid",
+    ),
+    (
+        "UnlockedItemSkins.MapProperty.Value.StructProperty.Skins.SetProperty.StructProperty",
+        "Guid",
+    ),
+    (
+        "UnlockedPickaxeParts.SetProperty.StructProperty",
+        "Guid",
+    ),
+    (
+        "MinersManualKnownObjects.SetProperty.StructProperty",
+        "Guid",
+    ),
+];
+
+const PALWORLD: &[(&str, &str)] = &[
+    (
+        "worldSaveData.StructProperty.CharacterSaveParameterMap.MapProperty.Key.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.CharacterSaveParameterMap.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.MapObjectSaveData.ArrayProperty.ConcreteModel.StructProperty.ModuleMap.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.FoliageGridSaveDataMap.MapProperty.Key.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.FoliageGridSaveDataMap.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.FoliageGridSaveDataMap.MapProperty.Value.StructProperty.ModelMap.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.FoliageGridSaveDataMap.MapProperty.Value.StructProperty.ModelMap.MapProperty.Value.StructProperty.InstanceDataMap.MapProperty.Key.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.FoliageGridSaveDataMap.MapProperty.Value.StructProperty.ModelMap.MapProperty.Value.StructProperty.InstanceDataMap.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.MapObjectSpawnerInStageSaveData.MapProperty.Key.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.MapObjectSpawnerInStageSaveData.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.MapObjectSpawnerInStageSaveData.MapProperty.Value.StructProperty.SpawnerDataMapByLevelObjectInstanceId.MapProperty.Key.StructProperty",
+        "Guid",
+    ),
+    (
+        "worldSaveData.StructProperty.MapObjectSpawnerInStageSaveData.MapProperty.Value.StructProperty.SpawnerDataMapByLevelObjectInstanceId.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.MapObjectSpawnerInStageSaveData.MapProperty.Value.StructProperty.SpawnerDataMapByLevelObjectInstanceId.MapProperty.Value.StructProperty.ItemMap.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.BaseCampSaveData.MapProperty.Key.StructProperty",
+        "Guid",
+    ),
+    (
+        "worldSaveData.StructProperty.BaseCampSaveData.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.BaseCampSaveData.MapProperty.Value.StructProperty.ModuleMap.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.ItemContainerSaveData.MapProperty.Key.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.ItemContainerSaveData.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.CharacterContainerSaveData.MapProperty.Key.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.CharacterContainerSaveData.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.GroupSaveDataMap.MapProperty.Key.StructProperty",
+        "Guid",
+    ),
+    (
+        "worldSaveData.StructProperty.GroupSaveDataMap.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+    (
+        "worldSaveData.StructProperty.EnemyCampSaveData.StructProperty.EnemyCampStatusMap.MapProperty.Value.StructProperty",
+        "Struct",
+    ),
+];
@@ -0,0 +1,515 @@
+//! JSON previews of large saves
+//!
+//! [`to_value`] converts anything serializable, like a [`GvasFile`] or a single [`Property`],
+//! to JSON while keeping the output browsable. Arrays and objects with more than
+//! [`PreviewOptions::max_elements`] entries are cut short with a summary like `"... 12000 more"`,
+//! and containers nested deeper than [`PreviewOptions::max_depth`] are replaced with a summary
+//! like `"[3 elements]"`. Entries are left out while serializing, so previews of huge saves only
+//! use the memory of the preview.
+//!
+//! [`to_value`]: crate::json::to_value
+//! [`GvasFile`]: crate::GvasFile
+//! [`Property`]: crate::properties::Property
+//! [`PreviewOptions::max_elements`]: crate::json::PreviewOptions::max_elements
+//! [`PreviewOptions::max_depth`]: crate::json::PreviewOptions::max_depth
+//!
+//! ```
+//! use gvas::{
+//!     game_version::GameVersion,
+//!     json::{to_value, PreviewOptions},
+//!     GvasFile,
+//! };
+//! use std::fs::File;
+//!
+//! let mut file = File::open("resources/test/Slot1.sav")?;
+//! let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+//!
+//! let options = PreviewOptions {
+//!     max_depth: Some(4),
+//!     max_elements: Some(2),
+//!     ..Default::default()
+//! };
+//! let preview = to_value(&gvas_file.properties, &options)?;
+//! let more = gvas_file.properties.len() - 2;
+//! assert_eq!(preview["..."], format!("{more} more"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use serde::{
+    ser::{
+        Error as _, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+        SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+    },
+    Serialize, Serializer,
+};
+use serde_json::{json, Map, Value};
+
+use crate::properties::name_property;
+
+/// Options used by [`to_value`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreviewOptions {
+    /// Deepest level at which arrays and objects are written, the top level is `0`.
+    pub max_depth: Option<usize>,
+    /// Number of entries written for each array and object.
+    pub max_elements: Option<usize>,
+    /// Write the names of name properties and name arrays as `{"base": "Chest", "number": 12}`
+    /// instead of `"Chest_12"`, see [`split_name`].
+    ///
+    /// [`split_name`]: crate::properties::name_property::split_name
+    pub split_names: bool,
+}
+
+/// Convert `value` to JSON within the limits of `options`.
+///
+/// # Errors
+///
+/// If `value` can't be represented as JSON this function returns [`serde_json::Error`].
+pub fn to_value<T: Serialize + ?Sized>(
+    value: &T,
+    options: &PreviewOptions,
+) -> serde_json::Result<Value> {
+    let mut value = value.serialize(Preview { options, depth: 0 })?;
+    if options.split_names {
+        split_names(&mut value);
+    }
+    Ok(value)
+}
+
+/// Convert `value` to a pretty printed JSON string within the limits of `options`.
+///
+/// # Errors
+///
+/// If `value` can't be represented as JSON this function returns [`serde_json::Error`].
+pub fn to_string_pretty<T: Serialize + ?Sized>(
+    value: &T,
+    options: &PreviewOptions,
+) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&to_value(value, options)?)
+}
+
+/// Serializes to a [`Value`] at `depth`, leaving out what doesn't fit in `options`.
+#[derive(Clone, Copy)]
+struct Preview<'a> {
+    options: &'a PreviewOptions,
+    depth: usize,
+}
+
+impl<'a> Preview<'a> {
+    #[inline]
+    fn too_deep(&self) -> bool {
+        self.options
+            .max_depth
+            .is_some_and(|max_depth| self.depth > max_depth)
+    }
+
+    #[inline]
+    fn nested(&self) -> Self {
+        Preview {
+            options: self.options,
+            depth: self.depth + 1,
+        }
+    }
+
+    #[inline]
+    fn keeps(&self, kept: usize) -> bool {
+        !self.too_deep()
+            && self
+                .options
+                .max_elements
+                .is_none_or(|max_elements| kept < max_elements)
+    }
+
+    fn seq(self) -> SeqPreview<'a> {
+        SeqPreview {
+            preview: self,
+            elements: Vec::new(),
+            len: 0,
+        }
+    }
+
+    fn map(self) -> MapPreview<'a> {
+        MapPreview {
+            preview: self,
+            fields: Map::new(),
+            len: 0,
+            key: None,
+        }
+    }
+
+    fn variant_of<T>(self, variant: &'static str, content: T) -> VariantPreview<T> {
+        VariantPreview {
+            too_deep: self.too_deep(),
+            variant,
+            content,
+        }
+    }
+}
+
+macro_rules! serialize_scalars {
+    ($($method:ident($ty:ty)),+ $(,)?) => {
+        $(
+            #[inline]
+            fn $method(self, v: $ty) -> serde_json::Result<Value> {
+                serde_json::value::Serializer.$method(v)
+            }
+        )+
+    };
+}
+
+impl<'a> Serializer for Preview<'a> {
+    type Ok = Value;
+    type Error = serde_json::Error;
+    type SerializeSeq = SeqPreview<'a>;
+    type SerializeTuple = SeqPreview<'a>;
+    type SerializeTupleStruct = SeqPreview<'a>;
+    type SerializeTupleVariant = VariantPreview<SeqPreview<'a>>;
+    type SerializeMap = MapPreview<'a>;
+    type SerializeStruct = MapPreview<'a>;
+    type SerializeStructVariant = VariantPreview<MapPreview<'a>>;
+
+    serialize_scalars!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+    );
+
+    fn serialize_bytes(self, v: &[u8]) -> serde_json::Result<Value> {
+        let mut seq = self.seq();
+        for byte in v {
+            SerializeSeq::serialize_element(&mut seq, byte)?;
+        }
+        SerializeSeq::end(seq)
+    }
+
+    #[inline]
+    fn serialize_none(self) -> serde_json::Result<Value> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> serde_json::Result<Value> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> serde_json::Result<Value> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> serde_json::Result<Value> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> serde_json::Result<Value> {
+        Ok(Value::String(variant.to_string()))
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> serde_json::Result<Value> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> serde_json::Result<Value> {
+        let too_deep = self.too_deep();
+        let content = match too_deep {
+            true => Value::Null,
+            false => value.serialize(self.nested())?,
+        };
+        Ok(self::variant(too_deep, variant, content))
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> serde_json::Result<SeqPreview<'a>> {
+        Ok(self.seq())
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _len: usize) -> serde_json::Result<SeqPreview<'a>> {
+        Ok(self.seq())
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> serde_json::Result<SeqPreview<'a>> {
+        Ok(self.seq())
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> serde_json::Result<VariantPreview<SeqPreview<'a>>> {
+        Ok(self.variant_of(variant, self.nested().seq()))
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> serde_json::Result<MapPreview<'a>> {
+        Ok(self.map())
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> serde_json::Result<MapPreview<'a>> {
+        Ok(self.map())
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> serde_json::Result<VariantPreview<MapPreview<'a>>> {
+        Ok(self.variant_of(variant, self.nested().map()))
+    }
+}
+
+/// An array being previewed, counting the elements left out.
+struct SeqPreview<'a> {
+    preview: Preview<'a>,
+    elements: Vec<Value>,
+    len: usize,
+}
+
+impl SerializeSeq for SeqPreview<'_> {
+    type Ok = Value;
+    type Error = serde_json::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> serde_json::Result<()> {
+        if self.preview.keeps(self.elements.len()) {
+            self.elements.push(value.serialize(self.preview.nested())?);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    fn end(mut self) -> serde_json::Result<Value> {
+        if self.preview.too_deep() {
+            return Ok(Value::String(format!("[{} elements]", self.len)));
+        }
+        let more = self.len - self.elements.len();
+        if more > 0 {
+            self.elements
+                .push(Value::String(format!("... {more} more")));
+        }
+        Ok(Value::Array(self.elements))
+    }
+}
+
+impl SerializeTuple for SeqPreview<'_> {
+    type Ok = Value;
+    type Error = serde_json::Error;
+
+    #[inline]
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> serde_json::Result<()> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> serde_json::Result<Value> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeTupleStruct for SeqPreview<'_> {
+    type Ok = Value;
+    type Error = serde_json::Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> serde_json::Result<()> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> serde_json::Result<Value> {
+        SerializeSeq::end(self)
+    }
+}
+
+/// An object being previewed, counting the fields left out.
+struct MapPreview<'a> {
+    preview: Preview<'a>,
+    fields: Map<String, Value>,
+    len: usize,
+    key: Option<String>,
+}
+
+impl MapPreview<'_> {
+    fn field<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> serde_json::Result<()> {
+        if self.preview.keeps(self.fields.len()) {
+            let value = value.serialize(self.preview.nested())?;
+            self.fields.insert(key, value);
+        }
+        self.len += 1;
+        Ok(())
+    }
+}
+
+impl SerializeMap for MapPreview<'_> {
+    type Ok = Value;
+    type Error = serde_json::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> serde_json::Result<()> {
+        let key = match serde_json::to_value(key)? {
+            Value::String(key) => key,
+            key @ (Value::Bool(_) | Value::Number(_)) => key.to_string(),
+            _ => Err(serde_json::Error::custom("key must be a string"))?,
+        };
+        self.key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> serde_json::Result<()> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| serde_json::Error::custom("value serialized before its key"))?;
+        self.field(key, value)
+    }
+
+    fn end(mut self) -> serde_json::Result<Value> {
+        if self.preview.too_deep() {
+            return Ok(Value::String(format!("{{{} fields}}", self.len)));
+        }
+        let more = self.len - self.fields.len();
+        if more > 0 {
+            let more = Value::String(format!("{more} more"));
+            self.fields.insert(String::from("..."), more);
+        }
+        Ok(Value::Object(self.fields))
+    }
+}
+
+impl SerializeStruct for MapPreview<'_> {
+    type Ok = Value;
+    type Error = serde_json::Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> serde_json::Result<()> {
+        self.field(key.to_string(), value)
+    }
+
+    #[inline]
+    fn end(self) -> serde_json::Result<Value> {
+        SerializeMap::end(self)
+    }
+}
+
+/// An enum variant written as `{"Variant": content}`.
+struct VariantPreview<T> {
+    too_deep: bool,
+    variant: &'static str,
+    content: T,
+}
+
+/// `{"variant": content}`, or a summary if the variant is nested too deep.
+fn variant(too_deep: bool, variant: &str, content: Value) -> Value {
+    match too_deep {
+        true => Value::String(String::from("{1 fields}")),
+        false => json!({ variant: content }),
+    }
+}
+
+impl SerializeTupleVariant for VariantPreview<SeqPreview<'_>> {
+    type Ok = Value;
+    type Error = serde_json::Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> serde_json::Result<()> {
+        SerializeSeq::serialize_element(&mut self.content, value)
+    }
+
+    fn end(self) -> serde_json::Result<Value> {
+        let content = SerializeSeq::end(self.content)?;
+        Ok(variant(self.too_deep, self.variant, content))
+    }
+}
+
+impl SerializeStructVariant for VariantPreview<MapPreview<'_>> {
+    type Ok = Value;
+    type Error = serde_json::Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> serde_json::Result<()> {
+        SerializeStruct::serialize_field(&mut self.content, key, value)
+    }
+
+    fn end(self) -> serde_json::Result<Value> {
+        let content = SerializeMap::end(self.content)?;
+        Ok(variant(self.too_deep, self.variant, content))
+    }
+}
+
+fn split_names(value: &mut Value) {
+    match value {
+        Value::Array(elements) => elements.iter_mut().for_each(split_names),
+        Value::Object(fields) => {
+            let names = match fields.get("type").and_then(Value::as_str) {
+                Some("NameProperty") => fields.get_mut("value"),
+                Some("ArrayProperty") => fields.get_mut("names"),
+                _ => None,
+            };
+            match names {
+                Some(Value::Array(names)) => names.iter_mut().for_each(split_name),
+                Some(name) => split_name(name),
+                None => fields.values_mut().for_each(split_names),
+            }
+        }
+        _ => {}
+    }
+}
+
+fn split_name(name: &mut Value) {
+    if let Value::String(value) = name {
+        let (base, number) = name_property::split_name(value);
+        *name = json!({ "base": base, "number": number });
+    }
+}
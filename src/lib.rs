@@ -59,59 +59,174 @@
 //! println!("{:#?}", gvas_file);
 //! # Ok::<(), Error>(())
 //! ```
+//!
+//! Besides struct types, [`HintValue`] can override the width of a value or keep it as raw bytes.
+//!
+//! [`HintValue`]: crate::hints::HintValue
 
+/// Batch processing of many saves.
+pub mod batch;
+/// Borrowed views of saves.
+pub mod borrowed;
+/// Building saves from scratch.
+pub mod builder;
+/// Containers wrapping GVAS data.
+pub mod container;
+/// Coordinate space transformations.
+#[cfg(feature = "glam")]
+pub mod coordinates;
+/// Round trip checks over a directory of saves.
+#[cfg(feature = "testutil")]
+pub mod corpus;
 /// Extensions for `Cursor`.
 pub mod cursor_ext;
 /// Custom version information.
 pub mod custom_version;
+/// Memory footprint estimation.
+pub mod deep_size;
+/// Differences between saves.
+pub mod diff;
 /// Engine version information.
 pub mod engine_version;
+/// Values of enums used in a save.
+pub mod enum_usage;
 /// Error types.
 pub mod error;
 /// Game version enumeration.
 pub mod game_version;
+/// Grouped views over top-level properties.
+pub mod grouped;
+/// Typed hints.
+pub mod hints;
+/// JSON previews of large saves.
+#[cfg(feature = "json")]
+pub mod json;
+/// Interning of repeated names.
+pub mod name_table;
 /// Object version information.
 pub mod object_version;
+/// Read and write options.
+pub mod options;
 /// Extensions for `Ord`.
 mod ord_ext;
+/// Palworld character container helpers.
+#[cfg(feature = "palworld")]
+pub mod palworld;
+/// Reports of data read without being understood.
+pub mod parse_report;
+/// Position tracking for readers which can't seek.
+mod position_reader;
 /// Property types.
 pub mod properties;
+/// Standalone property files.
+pub mod property_file;
+/// Reading the properties of a save one at a time.
+pub mod property_iter;
+/// Queries over property paths.
+pub mod query;
+/// Open, edit and save a file in one call.
+pub mod quick;
+/// Graphs of object references.
+pub mod references;
 /// Savegame version information.
 pub mod savegame_version;
 pub(crate) mod scoped_stack_entry;
+/// Seeking over readers which can't seek.
+pub mod seek_adapter;
+/// HMAC signatures of saves.
+#[cfg(feature = "hmac")]
+pub mod signature;
+/// Snapshots for undo and redo.
+pub mod snapshot;
+/// SQLite export.
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+/// Hashes that stay the same across crate versions.
+pub mod stable_hash;
+/// Statistics kept in maps of integers.
+pub mod statistics;
+/// Flat tables of numeric data.
+pub mod table;
+/// Test helpers.
+#[cfg(feature = "testutil")]
+pub mod testutil;
 /// Various types.
 pub mod types;
+/// Conversion to and from the JSON format of uesave.
+#[cfg(feature = "uesave")]
+pub mod uesave;
+/// Visitors over property trees.
+pub mod visitor;
 
-use std::io::{Cursor, SeekFrom};
+use std::io::{self, Cursor, ErrorKind, SeekFrom};
 use std::{
     collections::HashMap,
+    ffi::OsString,
     fmt::Debug,
-    io::{Read, Seek, Write},
+    fs::{self, File},
+    hash::Hash,
+    io::{BufReader, BufWriter, Read, Seek, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use flate2::read::ZlibDecoder;
-use flate2::write::ZlibEncoder;
-use flate2::Compression;
+use ordered_float::OrderedFloat;
 
 use crate::{
-    cursor_ext::{ReadExt, WriteExt},
-    custom_version::FCustomVersion,
-    engine_version::FEngineVersion,
-    error::{DeserializeError, Error},
-    game_version::{DeserializedGameVersion, GameVersion, PalworldCompressionType, PLZ_MAGIC},
-    object_version::EUnrealEngineObjectUE5Version,
-    ord_ext::OrdExt,
-    properties::{Property, PropertyOptions, PropertyTrait},
+    builder::{GvasFileBuilder, GvasHeaderBuilder},
+    container::{ContainerFormat, Contents, CustomContainer},
+    cursor_ext::{Digest, DigestAlgorithm, HashingReader, ReadExt, WriteExt},
+    custom_version::{
+        CustomVersionFormat, CustomVersionTrait, FEditorObjectVersion,
+        FUE5ReleaseStreamObjectVersion,
+    },
+    diff::{DiffOptions, Difference},
+    engine_version::{EngineFamily, FEngineVersion},
+    enum_usage::EnumUsage,
+    error::{DeserializeError, Error, SerializeError},
+    game_version::{DeserializedGameVersion, GameVersion},
+    grouped::{GroupedView, GroupedViewMut},
+    hints::{HintValue, Hints},
+    object_version::{
+        EUnrealEngineObjectUE4Version, EUnrealEngineObjectUE5Version, PackageVersion,
+    },
+    options::{Codepage, PresetHints, ReadOptions, TrailingPadding, WriteOptions},
+    parse_report::{ParseReport, ReportCollector, ReportEntry, ReportReason},
+    position_reader::PositionReader,
+    properties::{
+        array_property::ArrayProperty, struct_property::StructPropertyValue,
+        text_property::FTextHistory, unknown_property::UnknownProperty, Property, PropertyOptions,
+        PropertyTrait,
+    },
+    property_file::{properties_stack, struct_fields, struct_fields_mut, PropertyFile},
+    property_iter::PropertyIter,
+    query::ParseQueryError,
+    references::ReferenceGraph,
     savegame_version::SaveGameVersion,
-    types::{map::HashableIndexMap, Guid},
+    seek_adapter::BufferedSeekAdapter,
+    snapshot::Snapshot,
+    statistics::Statistics,
+    table::TableRow,
+    types::{
+        map::{HashableIndexMap, KeyComparison},
+        Guid,
+    },
+    visitor::{Visitor, VisitorMut},
 };
 
 /// The four bytes 'GVAS' appear at the beginning of every GVAS file.
 pub const FILE_TYPE_GVAS: u32 = u32::from_le_bytes([b'G', b'V', b'A', b'S']);
 
+/// Longest extension of the custom version container searched for by
+/// [`ReadOptions::preserve_custom_version_extra`].
+const MAX_CUSTOM_VERSION_EXTRA: usize = 4096;
+/// Longest save game class name accepted after an extended custom version container.
+const MAX_CLASS_NAME_LEN: i32 = 1024;
+
 /// Stores information about GVAS file, engine version, etc.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type"))]
 pub enum GvasHeader {
@@ -125,11 +240,27 @@ pub enum GvasHeader {
         custom_version_format: u32,
         /// Custom versions.
         custom_versions: HashableIndexMap<Guid, u32>,
+        /// Friendly names of the custom versions, stored by the `Guids` custom version format.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "is_empty_names")
+        )]
+        custom_version_names: HashableIndexMap<Guid, String>,
         /// Save game class name.
         save_game_class_name: String,
     },
     /// Version 3
     Version3 {
+        /// Save game file version, 3 unless a later version was accepted with
+        /// [`ReadOptions::save_game_versions`].
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                default = "default_save_game_file_version",
+                skip_serializing_if = "is_default_save_game_file_version"
+            )
+        )]
+        save_game_file_version: u32,
         /// File format version (UE4).
         package_file_version: u32,
         /// File format version (UE5).
@@ -140,6 +271,12 @@ pub enum GvasHeader {
         custom_version_format: u32,
         /// Custom versions.
         custom_versions: HashableIndexMap<Guid, u32>,
+        /// Friendly names of the custom versions, stored by the `Guids` custom version format.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "is_empty_names")
+        )]
+        custom_version_names: HashableIndexMap<Guid, String>,
         /// Save game class name.
         save_game_class_name: String,
     },
@@ -168,6 +305,30 @@ impl GvasHeader {
     /// # Ok::<(), Error>(())
     /// ```
     pub fn read<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
+        Self::read_with_options(cursor, &ReadOptions::default())
+    }
+
+    /// Read GvasHeader from a binary file with the save game file versions accepted by
+    /// `read_options`
+    ///
+    /// # Errors
+    ///
+    /// If this function reads an invalid header it returns [`Error`]
+    ///
+    /// With [`ReadOptions::preserve_custom_version_extra`], data after the custom versions is
+    /// skipped. [`GvasFile`] keeps it in [`GvasFile::custom_version_extra`].
+    pub fn read_with_options<R: Read + Seek>(
+        cursor: &mut R,
+        read_options: &ReadOptions,
+    ) -> Result<Self, Error> {
+        Ok(Self::read_with_extra(cursor, read_options)?.0)
+    }
+
+    /// Read GvasHeader and the data between the custom versions and the save game class name.
+    pub(crate) fn read_with_extra<R: Read + Seek>(
+        cursor: &mut R,
+        read_options: &ReadOptions,
+    ) -> Result<(Self, Vec<u8>), Error> {
         let file_type_tag = cursor.read_u32::<LittleEndian>()?;
         if file_type_tag != FILE_TYPE_GVAS {
             Err(DeserializeError::InvalidHeader(
@@ -176,76 +337,98 @@ impl GvasHeader {
         }
 
         let save_game_file_version = cursor.read_u32::<LittleEndian>()?;
-        if !save_game_file_version.between(
-            SaveGameVersion::AddedCustomVersions as u32,
-            SaveGameVersion::PackageFileSummaryVersionChange as u32,
-        ) {
-            Err(DeserializeError::InvalidHeader(
+        let supported = read_options
+            .save_game_versions
+            .as_ref()
+            .unwrap_or(&SaveGameVersion::SUPPORTED);
+        if save_game_file_version < SaveGameVersion::AddedCustomVersions as u32
+            || !supported.contains(&save_game_file_version)
+        {
+            Err(Error::unsupported(DeserializeError::InvalidHeader(
                 format!("GVAS version {save_game_file_version} not supported").into_boxed_str(),
-            ))?
+            )))?
         }
 
         let package_file_version = cursor.read_u32::<LittleEndian>()?;
-        if !package_file_version.between(0x205, 0x20D) {
-            Err(DeserializeError::InvalidHeader(
-                format!("Package file version {package_file_version} not supported")
-                    .into_boxed_str(),
-            ))?
+        if let Some(message) = PackageVersion::unsupported_ue4(package_file_version) {
+            Err(Error::unsupported(DeserializeError::InvalidHeader(
+                message.into_boxed_str(),
+            )))?
         }
 
         // This field is only present in the v3 header
-        let package_file_version_ue5 = if save_game_file_version
-            >= SaveGameVersion::PackageFileSummaryVersionChange as u32
-        {
-            let version = cursor.read_u32::<LittleEndian>()?;
-            if !version.between(
-                EUnrealEngineObjectUE5Version::InitialVersion as u32,
-                EUnrealEngineObjectUE5Version::DataResources as u32,
-            ) {
-                Err(DeserializeError::InvalidHeader(
-                    format!("UE5 Package file version {version} is not supported").into_boxed_str(),
-                ))?
-            }
-            Some(version)
-        } else {
-            None
-        };
+        let package_file_version_ue5 =
+            if save_game_file_version >= SaveGameVersion::PackageFileSummaryVersionChange as u32 {
+                let version = cursor.read_u32::<LittleEndian>()?;
+                if let Some(message) = PackageVersion::unsupported_ue5(version) {
+                    Err(Error::unsupported(DeserializeError::InvalidHeader(
+                        message.into_boxed_str(),
+                    )))?
+                }
+                Some(version)
+            } else {
+                None
+            };
 
         let engine_version = FEngineVersion::read(cursor)?;
         let custom_version_format = cursor.read_u32::<LittleEndian>()?;
-        if custom_version_format != 3 {
-            Err(DeserializeError::InvalidHeader(
+        let Ok(format) = CustomVersionFormat::try_from(custom_version_format) else {
+            Err(Error::unsupported(DeserializeError::InvalidHeader(
                 format!("Custom version format {custom_version_format} not supported")
                     .into_boxed_str(),
-            ))?
-        }
-
-        let custom_versions_len = cursor.read_u32::<LittleEndian>()?;
-        let mut custom_versions = HashableIndexMap::with_capacity(custom_versions_len as usize);
-        for _ in 0..custom_versions_len {
-            let FCustomVersion { key, version } = FCustomVersion::read(cursor)?;
-            custom_versions.insert(key, version);
-        }
-
-        let save_game_class_name = cursor.read_string()?;
+            )))?
+        };
+        let (custom_versions, custom_version_names) =
+            custom_version::read_container_with_names(cursor, format)?;
+        let (custom_version_extra, save_game_class_name) =
+            match read_options.preserve_custom_version_extra {
+                true => {
+                    let offset = cursor.stream_position()?;
+                    let (extra, save_game_class_name) =
+                        read_custom_version_extra(cursor, read_options.string_len_limit())?;
+                    if let (Some(report), false) = (&read_options.report, extra.is_empty()) {
+                        report.push(ReportEntry {
+                            path: String::new(),
+                            offset,
+                            length: extra.len() as u64,
+                            reason: ReportReason::CustomVersionExtra,
+                        });
+                    }
+                    (extra, save_game_class_name)
+                }
+                false => {
+                    let max_len = read_options.string_len_limit();
+                    match cursor.read_fstring_limited(Codepage::Utf8, max_len)? {
+                        Some(save_game_class_name) => (Vec::new(), save_game_class_name),
+                        None => Err(DeserializeError::InvalidString(
+                            0,
+                            cursor.stream_position()?,
+                        ))?,
+                    }
+                }
+            };
 
-        Ok(match package_file_version_ue5 {
+        let header = match package_file_version_ue5 {
             None => GvasHeader::Version2 {
                 package_file_version,
                 engine_version,
                 custom_version_format,
                 custom_versions,
+                custom_version_names,
                 save_game_class_name,
             },
             Some(package_file_version_ue5) => GvasHeader::Version3 {
+                save_game_file_version,
                 package_file_version,
                 package_file_version_ue5,
                 engine_version,
                 custom_version_format,
                 custom_versions,
+                custom_version_names,
                 save_game_class_name,
             },
-        })
+        };
+        Ok((header, custom_version_extra))
     }
 
     /// Write GvasHeader to a binary file
@@ -267,6 +450,16 @@ impl GvasHeader {
     /// # Ok::<(), Error>(())
     /// ```
     pub fn write<W: Write>(&self, cursor: &mut W) -> Result<usize, Error> {
+        self.write_with_extra(cursor, &[])
+    }
+
+    /// Write GvasHeader with `custom_version_extra` between the custom versions and the save
+    /// game class name.
+    pub(crate) fn write_with_extra<W: Write>(
+        &self,
+        cursor: &mut W,
+        custom_version_extra: &[u8],
+    ) -> Result<usize, Error> {
         cursor.write_u32::<LittleEndian>(FILE_TYPE_GVAS)?;
         match self {
             GvasHeader::Version2 {
@@ -274,45 +467,130 @@ impl GvasHeader {
                 engine_version,
                 custom_version_format,
                 custom_versions,
+                custom_version_names,
                 save_game_class_name,
             } => {
-                let mut len = 20;
+                let mut len = 16;
                 cursor.write_u32::<LittleEndian>(2)?;
                 cursor.write_u32::<LittleEndian>(*package_file_version)?;
                 len += engine_version.write(cursor)?;
-                cursor.write_u32::<LittleEndian>(*custom_version_format)?;
-                cursor.write_u32::<LittleEndian>(custom_versions.len() as u32)?;
-                for (&key, &version) in custom_versions {
-                    len += FCustomVersion::new(key, version).write(cursor)?;
-                }
+                len += write_custom_versions(
+                    cursor,
+                    *custom_version_format,
+                    custom_versions,
+                    custom_version_names,
+                )?;
+                cursor.write_all(custom_version_extra)?;
+                len += custom_version_extra.len();
                 len += cursor.write_string(save_game_class_name)?;
                 Ok(len)
             }
 
             GvasHeader::Version3 {
+                save_game_file_version,
                 package_file_version,
                 package_file_version_ue5,
                 engine_version,
                 custom_version_format,
                 custom_versions,
+                custom_version_names,
                 save_game_class_name,
             } => {
-                let mut len = 24;
-                cursor.write_u32::<LittleEndian>(3)?;
+                let mut len = 20;
+                cursor.write_u32::<LittleEndian>(*save_game_file_version)?;
                 cursor.write_u32::<LittleEndian>(*package_file_version)?;
                 cursor.write_u32::<LittleEndian>(*package_file_version_ue5)?;
                 len += engine_version.write(cursor)?;
-                cursor.write_u32::<LittleEndian>(*custom_version_format)?;
-                cursor.write_u32::<LittleEndian>(custom_versions.len() as u32)?;
-                for (&key, &version) in custom_versions {
-                    len += FCustomVersion::new(key, version).write(cursor)?
-                }
+                len += write_custom_versions(
+                    cursor,
+                    *custom_version_format,
+                    custom_versions,
+                    custom_version_names,
+                )?;
+                cursor.write_all(custom_version_extra)?;
+                len += custom_version_extra.len();
                 len += cursor.write_string(save_game_class_name)?;
                 Ok(len)
             }
         }
     }
 
+    /// Convert this header to a `Version2` header for UE4 games.
+    ///
+    /// Removes the UE5 package file version and the custom versions listed in
+    /// [`UE5_CUSTOM_VERSIONS`], so that properties are written in their UE4 format. Values that
+    /// only exist in UE5, such as large world coordinates, are rejected when the file is written.
+    ///
+    /// [`UE5_CUSTOM_VERSIONS`]: custom_version::UE5_CUSTOM_VERSIONS
+    pub fn strip_ue5_fields(&mut self) {
+        if let GvasHeader::Version3 {
+            package_file_version,
+            engine_version,
+            custom_version_format,
+            custom_versions,
+            custom_version_names,
+            save_game_class_name,
+            ..
+        } = self
+        {
+            let mut custom_versions = custom_versions.clone();
+            custom_versions.retain(|key, _| !custom_version::UE5_CUSTOM_VERSIONS.contains(key));
+            let mut custom_version_names = custom_version_names.clone();
+            custom_version_names.retain(|key, _| custom_versions.contains_key(key));
+            *self = GvasHeader::Version2 {
+                package_file_version: *package_file_version,
+                engine_version: engine_version.clone(),
+                custom_version_format: *custom_version_format,
+                custom_versions,
+                custom_version_names,
+                save_game_class_name: save_game_class_name.clone(),
+            };
+        }
+    }
+
+    /// Get package file versions from this header
+    pub fn get_package_version(&self) -> PackageVersion {
+        match self {
+            GvasHeader::Version2 {
+                package_file_version,
+                ..
+            } => PackageVersion {
+                ue4: *package_file_version,
+                ue5: 0,
+            },
+            GvasHeader::Version3 {
+                package_file_version,
+                package_file_version_ue5,
+                ..
+            } => PackageVersion {
+                ue4: *package_file_version,
+                ue5: *package_file_version_ue5,
+            },
+        }
+    }
+
+    /// Get engine version from this header
+    pub fn get_engine_version(&self) -> &FEngineVersion {
+        match self {
+            GvasHeader::Version2 { engine_version, .. } => engine_version,
+            GvasHeader::Version3 { engine_version, .. } => engine_version,
+        }
+    }
+
+    /// Get save game class name from this header
+    pub fn get_save_game_class_name(&self) -> &str {
+        match self {
+            GvasHeader::Version2 {
+                save_game_class_name,
+                ..
+            } => save_game_class_name,
+            GvasHeader::Version3 {
+                save_game_class_name,
+                ..
+            } => save_game_class_name,
+        }
+    }
+
     /// Get custom versions from this header
     pub fn get_custom_versions(&self) -> &HashableIndexMap<Guid, u32> {
         match self {
@@ -324,10 +602,68 @@ impl GvasHeader {
             } => custom_versions,
         }
     }
+
+    /// Check for custom version support
+    pub fn supports_version<T>(&self, required: T) -> bool
+    where
+        T: CustomVersionTrait + Into<u32>,
+    {
+        let version = self.get_custom_versions().get(&T::GUID).copied();
+        version.unwrap_or(0) >= required.into()
+    }
+
+    /// Start building a header with the versions written by UE 4.27.
+    ///
+    /// Only the custom versions that change how properties are serialized are set.
+    pub fn new_ue4_27() -> GvasHeaderBuilder {
+        GvasHeaderBuilder::new(
+            PackageVersion {
+                ue4: EUnrealEngineObjectUE4Version::CorrectLicenseeFlag as u32,
+                ue5: 0,
+            },
+            FEngineVersion::new(4, 27, 2, 0, String::from("++UE4+Release-4.27")),
+        )
+        .supported_version(FEditorObjectVersion::SkeletalMeshSourceDataSupport16bitOfMaterialNumber)
+    }
+
+    /// Start building a header with the versions written by UE 5.0.
+    ///
+    /// Only the custom versions that change how properties are serialized are set.
+    pub fn new_ue5_0() -> GvasHeaderBuilder {
+        GvasHeaderBuilder::new(
+            PackageVersion {
+                ue4: EUnrealEngineObjectUE4Version::CorrectLicenseeFlag as u32,
+                ue5: EUnrealEngineObjectUE5Version::LargeWorldCoordinates as u32,
+            },
+            FEngineVersion::new(5, 0, 3, 0, String::from("++UE5+Release-5.0")),
+        )
+        .supported_version(FEditorObjectVersion::SkeletalMeshSourceDataSupport16bitOfMaterialNumber)
+        .supported_version(
+            FUE5ReleaseStreamObjectVersion::SerializeFloatPinDefaultValuesAsSinglePrecision,
+        )
+    }
+
+    /// Start building a header with the versions written by UE 5.3.
+    ///
+    /// Only the custom versions that change how properties are serialized are set.
+    pub fn new_ue5_3() -> GvasHeaderBuilder {
+        GvasHeaderBuilder::new(
+            PackageVersion {
+                ue4: EUnrealEngineObjectUE4Version::CorrectLicenseeFlag as u32,
+                ue5: EUnrealEngineObjectUE5Version::DataResources as u32,
+            },
+            FEngineVersion::new(5, 3, 2, 0, String::from("++UE5+Release-5.3")),
+        )
+        .supported_version(FEditorObjectVersion::SkeletalMeshSourceDataSupport16bitOfMaterialNumber)
+        .supported_version(
+            FUE5ReleaseStreamObjectVersion::OpenColorIODisabledDisplayConfigurationDefault,
+        )
+    }
 }
 
 /// Main UE4 save file struct
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GvasFile {
     /// Game version
@@ -336,13 +672,81 @@ pub struct GvasFile {
         serde(default, skip_serializing_if = "DeserializedGameVersion::is_default")
     )]
     pub deserialized_game_version: DeserializedGameVersion,
+    /// Data preceding the GVAS header, such as a thumbnail or a metadata block.
+    #[cfg_attr(
+        feature = "serde",
+        serde_as(as = "serde_with::hex::Hex"),
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub prefix: Vec<u8>,
     /// GVAS file header.
     pub header: GvasHeader,
+    /// Data between the custom versions and the save game class name in the header, kept with
+    /// [`ReadOptions::preserve_custom_version_extra`].
+    #[cfg_attr(
+        feature = "serde",
+        serde_as(as = "serde_with::hex::Hex"),
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub custom_version_extra: Vec<u8>,
     /// GVAS properties.
     pub properties: HashableIndexMap<String, Property>,
+    /// Padding read after the `None` terminator, `None` if the file ended without it.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default = "default_trailing_padding",
+            skip_serializing_if = "is_default_trailing_padding"
+        )
+    )]
+    pub trailing_padding: Option<i32>,
+    /// The 1 to 3 bytes after the `None` terminator when the file ends before a full padding
+    /// value, written back as is when the padding is preserved.
+    #[cfg_attr(
+        feature = "serde",
+        serde_as(as = "serde_with::hex::Hex"),
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub trailing_bytes: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+#[inline]
+fn default_trailing_padding() -> Option<i32> {
+    Some(0)
+}
+
+#[cfg(feature = "serde")]
+#[inline]
+fn default_save_game_file_version() -> u32 {
+    SaveGameVersion::PackageFileSummaryVersionChange as u32
+}
+
+#[cfg(feature = "serde")]
+#[inline]
+fn is_default_save_game_file_version(version: &u32) -> bool {
+    *version == default_save_game_file_version()
+}
+
+#[cfg(feature = "serde")]
+#[inline]
+fn is_empty_names(names: &HashableIndexMap<Guid, String>) -> bool {
+    names.is_empty()
+}
+
+#[cfg(feature = "serde")]
+#[inline]
+fn is_default_trailing_padding(padding: &Option<i32>) -> bool {
+    *padding == Some(0)
 }
 
 impl GvasFile {
+    /// Start building a file with `header`, see [`builder`].
+    #[inline]
+    pub fn builder(header: GvasHeaderBuilder) -> GvasFileBuilder {
+        GvasFileBuilder::new(header)
+    }
+
     /// Read GvasFile from a binary file
     ///
     /// # Errors
@@ -367,8 +771,7 @@ impl GvasFile {
     /// # Ok::<(), Error>(())
     /// ```
     pub fn read<R: Read + Seek>(cursor: &mut R, game_version: GameVersion) -> Result<Self, Error> {
-        let hints = HashMap::new();
-        Self::read_with_hints(cursor, game_version, &hints)
+        Self::read_with_hints(cursor, game_version, Hints::new())
     }
 
     /// Read GvasFile from a binary file
@@ -404,168 +807,2270 @@ impl GvasFile {
     pub fn read_with_hints<R: Read + Seek>(
         cursor: &mut R,
         game_version: GameVersion,
-        hints: &HashMap<String, String>,
+        hints: impl Into<Hints>,
     ) -> Result<Self, Error> {
-        let deserialized_game_version: DeserializedGameVersion;
-        let mut cursor = match game_version {
-            GameVersion::Default => {
-                deserialized_game_version = DeserializedGameVersion::Default;
-                let mut data = Vec::new();
-                cursor.read_to_end(&mut data)?;
-                Cursor::new(data)
-            }
-            GameVersion::Palworld => {
-                let decompresed_length = cursor.read_u32::<LittleEndian>()?;
-                let _compressed_length = cursor.read_u32::<LittleEndian>()?;
-
-                let mut magic = [0u8; 3];
-                cursor.read_exact(&mut magic)?;
-                if &magic != PLZ_MAGIC {
-                    Err(DeserializeError::InvalidHeader(
-                        format!("Invalid PlZ magic {magic:?}").into_boxed_str(),
-                    ))?
-                }
-
-                let compression_type = cursor.read_enum()?;
-
-                deserialized_game_version = DeserializedGameVersion::Palworld(compression_type);
-
-                match compression_type {
-                    PalworldCompressionType::None => {
-                        let mut data = vec![0u8; decompresed_length as usize];
+        Self::read_with_options(cursor, game_version, hints, &ReadOptions::default())
+    }
 
-                        cursor.read_exact(&mut data)?;
-                        Cursor::new(data)
-                    }
-                    PalworldCompressionType::Zlib => {
-                        let mut zlib_data = vec![0u8; decompresed_length as usize];
+    /// Read GvasFile from a binary file, adding the [preset](hints::presets) hints for its save
+    /// game class to `hints`
+    ///
+    /// Hints passed in take precedence over the preset.
+    ///
+    /// # Errors
+    ///
+    /// If this function reads an invalid file it returns [`Error`]
+    ///
+    /// If this function reads a file which needs a hint that is missing it returns [`DeserializeError::MissingHint`]
+    ///
+    /// [`DeserializeError::MissingHint`]: error/enum.DeserializeError.html#variant.MissingHint
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gvas::{error::Error, game_version::GameVersion, hints::Hints, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("resources/test/features_01.bin")?;
+    /// let gvas_file =
+    ///     GvasFile::read_with_preset_hints(&mut file, GameVersion::Default, Hints::new())?;
+    ///
+    /// assert!(gvas_file.properties.contains_key("UnLockedMissionParameters"));
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn read_with_preset_hints<R: Read + Seek>(
+        cursor: &mut R,
+        game_version: GameVersion,
+        hints: impl Into<Hints>,
+    ) -> Result<Self, Error> {
+        let read_options = ReadOptions {
+            preset_hints: PresetHints::Enabled,
+            ..Default::default()
+        };
+        Self::read_with_options(cursor, game_version, hints, &read_options)
+    }
 
-                        let mut decoder = ZlibDecoder::new(cursor);
-                        decoder.read_exact(&mut zlib_data)?;
+    /// Read GvasFile from a binary file using the given [`ReadOptions`]
+    ///
+    /// # Errors
+    ///
+    /// If this function reads an invalid file it returns [`Error`]
+    ///
+    /// If this function reads a file which needs a hint that is missing it returns [`DeserializeError::MissingHint`]
+    ///
+    /// If the file can't be read with `game_version` but starts like a save of another
+    /// [`GameVersion`], this function returns [`DeserializeError::WrongGameVersion`] naming it
+    ///
+    /// [`DeserializeError::MissingHint`]: error/enum.DeserializeError.html#variant.MissingHint
+    /// [`DeserializeError::WrongGameVersion`]: error/enum.DeserializeError.html#variant.WrongGameVersion
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{
+    ///     error::Error,
+    ///     game_version::GameVersion,
+    ///     hints::Hints,
+    ///     options::{DuplicateKeyPolicy, ReadOptions},
+    ///     GvasFile,
+    /// };
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("save.sav")?;
+    ///
+    /// let options = ReadOptions {
+    ///     duplicate_map_keys: DuplicateKeyPolicy::Strict,
+    ///     ..Default::default()
+    /// };
+    /// let gvas_file =
+    ///     GvasFile::read_with_options(&mut file, GameVersion::Default, Hints::new(), &options);
+    ///
+    /// println!("{:#?}", gvas_file);
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn read_with_options<R: Read + Seek>(
+        cursor: &mut R,
+        game_version: GameVersion,
+        hints: impl Into<Hints>,
+        read_options: &ReadOptions,
+    ) -> Result<Self, Error> {
+        let hints = &hints.into().for_game_version(game_version, read_options);
+        let start = cursor.stream_position()?;
+        Self::read_game_version(cursor, game_version, hints, read_options, NoHash)
+            .map(|(file, _)| file)
+            .map_err(|error| wrong_game_version(cursor, start, game_version, error))
+    }
 
-                        Cursor::new(zlib_data)
-                    }
-                    PalworldCompressionType::ZlibTwice => {
-                        let decoder = ZlibDecoder::new(cursor);
-                        let mut decoder = ZlibDecoder::new(decoder);
+    /// Read GvasFile and hash the bytes parsed while reading it
+    ///
+    /// The digest covers the GVAS data including any prefix, after the container of
+    /// `game_version` is removed, so saves can be deduplicated or checked for external
+    /// modifications without hashing them again.
+    ///
+    /// # Errors
+    ///
+    /// If this function reads an invalid file, or `algorithm` isn't available, it returns
+    /// [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{
+    ///     cursor_ext::DigestAlgorithm, error::Error, game_version::GameVersion, hints::Hints,
+    ///     options::ReadOptions, GvasFile,
+    /// };
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("save.sav")?;
+    /// let (gvas_file, digest) = GvasFile::read_with_digest(
+    ///     &mut file,
+    ///     GameVersion::Default,
+    ///     Hints::new(),
+    ///     &ReadOptions::default(),
+    ///     DigestAlgorithm::Crc32,
+    /// )?;
+    /// println!("{digest:?}");
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn read_with_digest<R: Read + Seek>(
+        cursor: &mut R,
+        game_version: GameVersion,
+        hints: impl Into<Hints>,
+        read_options: &ReadOptions,
+        algorithm: DigestAlgorithm,
+    ) -> Result<(Self, Digest), Error> {
+        let hints = &hints.into().for_game_version(game_version, read_options);
+        let start = cursor.stream_position()?;
+        Self::read_game_version(cursor, game_version, hints, read_options, algorithm)
+            .map_err(|error| wrong_game_version(cursor, start, game_version, error))
+    }
 
-                        let mut zlib_data = Vec::new();
-                        decoder.read_to_end(&mut zlib_data)?;
+    /// Reads the container of `game_version` and the save inside it, hashing it with `hash`.
+    fn read_game_version<H: SaveHash, R: Read + Seek>(
+        cursor: &mut R,
+        game_version: GameVersion,
+        hints: &Hints,
+        read_options: &ReadOptions,
+        hash: H,
+    ) -> Result<(Self, H::Digest), Error> {
+        if let Some(container) = &read_options.container {
+            return Self::read_custom_container(
+                cursor,
+                container.clone(),
+                hints,
+                read_options,
+                hash,
+            );
+        }
+        let (contents, deserialized_game_version) = container::contents(cursor, game_version)?;
+        match contents {
+            // Saves without a prefix are parsed while reading instead of buffering them
+            Contents::Plain(cursor) => {
+                let start = cursor.stream_position()?;
+                let mut reader = PositionReader::with_position(BufReader::new(cursor), start);
+                let file = Self::read_body(
+                    &mut reader,
+                    deserialized_game_version,
+                    Vec::new(),
+                    hints,
+                    read_options,
+                    hash,
+                )?;
 
-                        Cursor::new(zlib_data)
-                    }
+                // Leave the reader after the save instead of after the buffered data
+                let reader = reader.into_inner();
+                let buffered = reader.buffer().len() as i64;
+                match reader.into_inner().seek(SeekFrom::Current(-buffered)) {
+                    Err(e) if e.kind() != ErrorKind::Unsupported => Err(e)?,
+                    _ => {}
                 }
+                Ok(file)
             }
-        };
-
-        let header = GvasHeader::read(&mut cursor)?;
-
-        let mut options = PropertyOptions {
-            hints,
-            properties_stack: &mut vec![],
-            custom_versions: header.get_custom_versions(),
-        };
-
-        let mut properties = HashableIndexMap::new();
-        loop {
-            let property_name = cursor.read_string()?;
-            if property_name == "None" {
-                break;
+            Contents::Buffered(data) => {
+                Self::read_buffered(data, deserialized_game_version, hints, read_options, hash)
             }
+            Contents::Streamed {
+                reader,
+                length: declared,
+            } => {
+                let (mut reader, prefix) = stream_prefix(reader)?;
+                let file = Self::read_body(
+                    &mut reader,
+                    deserialized_game_version,
+                    prefix,
+                    hints,
+                    read_options,
+                    hash,
+                )?;
 
-            let property_type = cursor.read_string()?;
-
-            options.properties_stack.push(property_name.clone());
-
-            let property = Property::new(&mut cursor, &property_type, true, &mut options, None)?;
-            properties.insert(property_name, property);
+                io::copy(&mut reader, &mut io::sink())?;
+                let actual = reader.position();
+                if actual != declared && !read_options.tolerate_length_mismatch {
+                    Err(DeserializeError::DecompressedLengthMismatch(
+                        declared, actual,
+                    ))?
+                }
+                if let (Some(report), true) = (&read_options.report, actual != declared) {
+                    report.push(ReportEntry {
+                        path: String::new(),
+                        offset: actual.min(declared),
+                        length: actual.abs_diff(declared),
+                        reason: ReportReason::LengthMismatch { declared },
+                    });
+                }
 
-            let _ = options.properties_stack.pop();
+                Ok(file)
+            }
         }
-
-        Ok(GvasFile {
-            deserialized_game_version,
-            header,
-            properties,
-        })
     }
 
-    /// Write GvasFile to a binary file
+    /// Read the GVAS data of a save without parsing it
+    ///
+    /// The container of `game_version` is removed and the data inside it is returned as is,
+    /// including any prefix before the `GVAS` magic, together with the container to pass to
+    /// [`GvasFile::compress_raw`] when writing the data back. The data can be parsed with
+    /// [`GvasFile::read`] and [`GameVersion::Default`].
     ///
     /// # Errors
     ///
-    /// If the file was modified in a way that makes it invalid this function returns [`Error`]
+    /// If the container is invalid, or the reader fails, this function returns [`Error`]
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use gvas::{error::Error, GvasFile};
-    /// use std::{
-    ///     fs::File,
-    ///     io::Cursor,
-    /// };
-    /// use gvas::game_version::GameVersion;
+    /// use gvas::{error::Error, game_version::GameVersion, GvasFile};
+    /// use std::fs::File;
     ///
     /// let mut file = File::open("save.sav")?;
-    /// let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
-    ///
-    /// let mut writer = Cursor::new(Vec::new());
-    /// gvas_file.write(&mut writer)?;
-    /// println!("{:#?}", writer.get_ref());
+    /// let (data, _) = GvasFile::decompress_raw(&mut file, GameVersion::Auto)?;
+    /// std::fs::write("save.gvas", data)?;
     /// # Ok::<(), Error>(())
     /// ```
-    pub fn write<W: Write + Seek>(&self, cursor: &mut W) -> Result<(), Error> {
-        let mut writing_cursor = Cursor::new(Vec::new());
-
-        self.header.write(&mut writing_cursor)?;
+    pub fn decompress_raw<R: Read + Seek>(
+        cursor: &mut R,
+        game_version: GameVersion,
+    ) -> Result<(Vec<u8>, DeserializedGameVersion), Error> {
+        let start = cursor.stream_position()?;
+        Self::decompress_game_version(cursor, game_version)
+            .map_err(|error| wrong_game_version(cursor, start, game_version, error))
+    }
 
-        let mut options = PropertyOptions {
-            hints: &HashMap::new(),
-            properties_stack: &mut vec![],
-            custom_versions: self.header.get_custom_versions(),
+    /// Removes the container of `game_version`, see [`GvasFile::decompress_raw`].
+    fn decompress_game_version<R: Read + Seek>(
+        cursor: &mut R,
+        game_version: GameVersion,
+    ) -> Result<(Vec<u8>, DeserializedGameVersion), Error> {
+        let (contents, deserialized_game_version) = container::contents(cursor, game_version)?;
+        let data = match contents {
+            Contents::Plain(cursor) => {
+                let mut data = Vec::new();
+                cursor.read_to_end(&mut data)?;
+                data
+            }
+            Contents::Buffered(data) => data,
+            Contents::Streamed { mut reader, length } => {
+                let mut data = Vec::with_capacity(length as usize);
+                reader.read_to_end(&mut data)?;
+                if data.len() as u64 != length {
+                    Err(DeserializeError::DecompressedLengthMismatch(
+                        length,
+                        data.len() as u64,
+                    ))?
+                }
+                data
+            }
         };
+        Ok((data, deserialized_game_version))
+    }
 
-        for (name, property) in &self.properties {
+    /// Read GvasFile from a reader which can't seek, such as stdin or a network stream
+    ///
+    /// The reader is wrapped in a [`BufferedSeekAdapter`] and read like
+    /// [`GvasFile::read_with_options`], see the [`seek_adapter`] module for the containers this
+    /// supports.
+    ///
+    /// # Errors
+    ///
+    /// If the reader fails, or the data is invalid, this function returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{error::Error, game_version::GameVersion, hints::Hints, options::ReadOptions, GvasFile};
+    ///
+    /// let gvas_file = GvasFile::read_unseekable(
+    ///     std::io::stdin().lock(),
+    ///     GameVersion::Auto,
+    ///     Hints::new(),
+    ///     &ReadOptions::default(),
+    /// )?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn read_unseekable<R: Read>(
+        reader: R,
+        game_version: GameVersion,
+        hints: impl Into<Hints>,
+        read_options: &ReadOptions,
+    ) -> Result<Self, Error> {
+        let mut reader = BufferedSeekAdapter::new(reader);
+        Self::read_with_options(&mut reader, game_version, hints, read_options)
+    }
+
+    /// Read GvasFile from an async reader
+    ///
+    /// The whole save is read into memory without blocking, then parsed like
+    /// [`GvasFile::read_with_options`]. Parsing doesn't wait on the reader, but is still done
+    /// on the calling task, so very large saves may be better read in
+    /// `tokio::task::spawn_blocking`.
+    ///
+    /// # Errors
+    ///
+    /// If the reader fails, or the data is invalid, this function returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{error::Error, game_version::GameVersion, hints::Hints, options::ReadOptions, GvasFile};
+    ///
+    /// # async fn run() -> Result<(), Error> {
+    /// let mut file = tokio::fs::File::open("save.sav").await?;
+    /// let gvas_file = GvasFile::read_async(
+    ///     &mut file,
+    ///     GameVersion::Default,
+    ///     Hints::new(),
+    ///     &ReadOptions::default(),
+    /// )
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn read_async<R: tokio::io::AsyncRead + Unpin>(
+        reader: &mut R,
+        game_version: GameVersion,
+        hints: impl Into<Hints>,
+        read_options: &ReadOptions,
+    ) -> Result<Self, Error> {
+        use tokio::io::AsyncReadExt;
+
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).await?;
+        Self::read_with_options(&mut Cursor::new(data), game_version, hints, read_options)
+    }
+
+    /// Read GvasFile split across several files
+    ///
+    /// The data of `readers` is joined in order before it is read like
+    /// [`GvasFile::read_with_options`], write it with [`GvasFile::write_fragmented`] to split
+    /// it again.
+    ///
+    /// # Errors
+    ///
+    /// If one of the readers fails, or the joined data is invalid, this function returns
+    /// [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{error::Error, game_version::GameVersion, hints::Hints, options::ReadOptions, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let readers = vec![File::open("save.sav.0")?, File::open("save.sav.1")?];
+    /// let gvas_file = GvasFile::read_fragmented(
+    ///     readers,
+    ///     GameVersion::Default,
+    ///     Hints::new(),
+    ///     &ReadOptions::default(),
+    /// )?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn read_fragmented<R: Read>(
+        readers: Vec<R>,
+        game_version: GameVersion,
+        hints: impl Into<Hints>,
+        read_options: &ReadOptions,
+    ) -> Result<Self, Error> {
+        let mut data = Vec::new();
+        for mut reader in readers {
+            reader.read_to_end(&mut data)?;
+        }
+        Self::read_with_options(&mut Cursor::new(data), game_version, hints, read_options)
+    }
+
+    /// Read GvasFile wrapped in a custom container
+    ///
+    /// The GVAS data is unwrapped by `container` before it is parsed. The file keeps the
+    /// container as [`DeserializedGameVersion::Custom`], so it is wrapped again when it is
+    /// written.
+    ///
+    /// # Errors
+    ///
+    /// If the container or the file it wraps is invalid this function returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// See [`ContainerFormat`].
+    ///
+    /// [`ContainerFormat`]: crate::container::ContainerFormat
+    pub fn read_with_container<R: Read>(
+        cursor: &mut R,
+        container: impl ContainerFormat + Send + Sync + 'static,
+        hints: impl Into<Hints>,
+        read_options: &ReadOptions,
+    ) -> Result<Self, Error> {
+        let container = CustomContainer::new(container);
+        let (file, _) =
+            Self::read_custom_container(cursor, container, &hints.into(), read_options, NoHash)?;
+        Ok(file)
+    }
+
+    /// Reads the save wrapped in `container`, hashing it with `hash`.
+    fn read_custom_container<H: SaveHash, R: Read>(
+        cursor: &mut R,
+        container: CustomContainer,
+        hints: &Hints,
+        read_options: &ReadOptions,
+        hash: H,
+    ) -> Result<(Self, H::Digest), Error> {
+        let data = container.read(cursor)?;
+        Self::read_buffered(
+            data,
+            DeserializedGameVersion::Custom(container),
+            hints,
+            read_options,
+            hash,
+        )
+    }
+
+    /// Read GvasFile and report the data that was read without being understood
+    ///
+    /// See [`parse_report`] for the data that is reported. A [`ReportCollector`] already set in
+    /// `read_options` is replaced for this read.
+    ///
+    /// # Errors
+    ///
+    /// If this function reads an invalid file it returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{
+    ///     error::Error,
+    ///     game_version::GameVersion,
+    ///     hints::Hints,
+    ///     options::{DelegateLimit, ReadOptions},
+    ///     GvasFile,
+    /// };
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("save.sav")?;
+    /// let options = ReadOptions {
+    ///     delegate_limit: DelegateLimit::Truncate(100),
+    ///     ..Default::default()
+    /// };
+    /// let (gvas_file, report) =
+    ///     GvasFile::read_with_report(&mut file, GameVersion::Default, Hints::new(), &options)?;
+    ///
+    /// for entry in report.lost() {
+    ///     eprintln!("{}: {:?} would be lost", entry.path, entry.reason);
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn read_with_report<R: Read + Seek>(
+        cursor: &mut R,
+        game_version: GameVersion,
+        hints: impl Into<Hints>,
+        read_options: &ReadOptions,
+    ) -> Result<(Self, ParseReport), Error> {
+        let report = ReportCollector::new();
+        let read_options = ReadOptions {
+            report: Some(report.clone()),
+            ..read_options.clone()
+        };
+        let file = Self::read_with_options(cursor, game_version, hints, &read_options)?;
+        Ok((file, report.take()))
+    }
+
+    /// Read GvasFile, keeping root properties which fail to parse as raw bytes
+    ///
+    /// Returns the file with the name and error of each property that failed, see
+    /// [`ReadOptions::recover_errors`]. Failed properties are stored as an [`UnknownProperty`]
+    /// and written back unchanged.
+    ///
+    /// [`UnknownProperty`]: properties::unknown_property::UnknownProperty
+    ///
+    /// # Errors
+    ///
+    /// If this function reads an invalid header, or a property whose length can't be read, it
+    /// returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{error::Error, game_version::GameVersion, hints::Hints, options::ReadOptions, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("save.sav")?;
+    /// let (gvas_file, errors) = GvasFile::read_tolerant(
+    ///     &mut file,
+    ///     GameVersion::Default,
+    ///     Hints::new(),
+    ///     &ReadOptions::default(),
+    /// )?;
+    ///
+    /// for (path, error) in &errors {
+    ///     eprintln!("{path}: {error}");
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn read_tolerant<R: Read + Seek>(
+        cursor: &mut R,
+        game_version: GameVersion,
+        hints: impl Into<Hints>,
+        read_options: &ReadOptions,
+    ) -> Result<(Self, Vec<(String, Error)>), Error> {
+        let report = read_options.report.clone().unwrap_or_default();
+        let read_options = ReadOptions {
+            recover_errors: true,
+            report: Some(report.clone()),
+            ..read_options.clone()
+        };
+        let file = Self::read_with_options(cursor, game_version, hints, &read_options)?;
+        Ok((file, report.take_errors()))
+    }
+
+    /// Read GvasFile, parsing only the properties at the `.` separated paths in `names`
+    ///
+    /// The other properties are read without parsing their bodies and kept as raw bytes, see
+    /// [`ReadOptions::root_properties`], so the file is still written back unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If this function reads an invalid file it returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gvas::{game_version::GameVersion, hints::Hints, options::ReadOptions, GvasFile};
+    /// use gvas::properties::Property;
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("resources/test/Slot1.sav")?;
+    /// let gvas_file = GvasFile::read_properties_filtered(
+    ///     &mut file,
+    ///     GameVersion::Default,
+    ///     Hints::new(),
+    ///     &ReadOptions::default(),
+    ///     &["str_property", "struct_property.test_field"],
+    /// )?;
+    /// assert!(matches!(
+    ///     gvas_file.properties["str_property"],
+    ///     Property::StrProperty(_)
+    /// ));
+    /// assert!(matches!(
+    ///     gvas_file.properties["u8_test"],
+    ///     Property::UnknownProperty(_)
+    /// ));
+    /// # Ok::<(), gvas::error::Error>(())
+    /// ```
+    pub fn read_properties_filtered<R: Read + Seek>(
+        cursor: &mut R,
+        game_version: GameVersion,
+        hints: impl Into<Hints>,
+        read_options: &ReadOptions,
+        names: &[&str],
+    ) -> Result<Self, Error> {
+        let read_options = ReadOptions {
+            root_properties: Some(names.iter().map(|name| name.to_string()).collect()),
+            ..read_options.clone()
+        };
+        Self::read_with_options(cursor, game_version, hints, &read_options)
+    }
+
+    /// Read the header of a save and return an iterator reading its properties one at a time.
+    ///
+    /// See [`property_iter`] for the containers which are decoded while iterating.
+    ///
+    /// # Errors
+    ///
+    /// If the container or the header is invalid this function returns [`Error`]. Errors reading
+    /// a property are returned by the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gvas::{game_version::GameVersion, hints::Hints, options::ReadOptions, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("resources/test/Slot1.sav")?;
+    /// let mut properties = GvasFile::read_properties_iter(
+    ///     &mut file,
+    ///     GameVersion::Default,
+    ///     Hints::new(),
+    ///     &ReadOptions::default(),
+    /// )?;
+    /// let found = properties.find(|property| {
+    ///     matches!(property, Ok((name, _)) if name == "str_property")
+    /// });
+    /// assert!(found.is_some());
+    /// # Ok::<(), gvas::error::Error>(())
+    /// ```
+    pub fn read_properties_iter<'a, R: Read + Seek>(
+        cursor: &'a mut R,
+        game_version: GameVersion,
+        hints: impl Into<Hints>,
+        read_options: &ReadOptions,
+    ) -> Result<PropertyIter<'a>, Error> {
+        PropertyIter::new(cursor, game_version, hints.into(), read_options)
+    }
+
+    /// Reads fully decompressed data, which may start with a prefix.
+    fn read_buffered<H: SaveHash>(
+        data: Vec<u8>,
+        deserialized_game_version: DeserializedGameVersion,
+        hints: &Hints,
+        read_options: &ReadOptions,
+        hash: H,
+    ) -> Result<(Self, H::Digest), Error> {
+        let (mut cursor, prefix) = buffered_prefix(data)?;
+        Self::read_body(
+            &mut cursor,
+            deserialized_game_version,
+            prefix,
+            hints,
+            read_options,
+            hash,
+        )
+    }
+
+    /// Reads the header and the properties following `prefix`, hashing them with `hash`.
+    #[inline]
+    fn read_body<H: SaveHash, R: Read + Seek>(
+        cursor: &mut R,
+        deserialized_game_version: DeserializedGameVersion,
+        prefix: Vec<u8>,
+        hints: &Hints,
+        read_options: &ReadOptions,
+        hash: H,
+    ) -> Result<(Self, H::Digest), Error> {
+        hash.read_properties(
+            cursor,
+            deserialized_game_version,
+            prefix,
+            hints,
+            read_options,
+        )
+    }
+
+    /// Reads the header and the properties following `prefix`.
+    fn read_properties<R: Read + Seek>(
+        cursor: &mut R,
+        deserialized_game_version: DeserializedGameVersion,
+        prefix: Vec<u8>,
+        hints: &Hints,
+        read_options: &ReadOptions,
+    ) -> Result<Self, Error> {
+        if let (Some(report), false) = (&read_options.report, prefix.is_empty()) {
+            report.push(ReportEntry {
+                path: String::new(),
+                offset: 0,
+                length: prefix.len() as u64,
+                reason: ReportReason::Prefix,
+            });
+        }
+        let (header, custom_version_extra) = GvasHeader::read_with_extra(cursor, read_options)?;
+
+        let hints = &hints.with_presets(header.get_save_game_class_name(), read_options);
+
+        let mut options = PropertyOptions {
+            hints,
+            properties_stack: &mut vec![],
+            custom_versions: header.get_custom_versions(),
+            package_version: header.get_package_version(),
+            read_options,
+            write_options: &WriteOptions::default(),
+        };
+
+        let mut properties = HashableIndexMap::new();
+        while let Some((property_name, property)) = read_root_property(cursor, &mut options, true)?
+        {
+            properties.insert(property_name, property);
+        }
+        let (trailing_padding, trailing_bytes) = read_trailing_padding(cursor)?;
+
+        Ok(GvasFile {
+            deserialized_game_version,
+            prefix,
+            header,
+            custom_version_extra,
+            properties,
+            trailing_padding,
+            trailing_bytes,
+        })
+    }
+
+    /// Read GvasFile from a file at the given path
+    ///
+    /// # Errors
+    ///
+    /// If the file can't be opened or is invalid this function returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{
+    ///     error::Error, game_version::GameVersion, hints::Hints, options::ReadOptions, GvasFile,
+    /// };
+    ///
+    /// let gvas_file = GvasFile::open(
+    ///     "save.sav",
+    ///     GameVersion::Default,
+    ///     Hints::new(),
+    ///     &ReadOptions::default(),
+    /// )?;
+    ///
+    /// println!("{:#?}", gvas_file);
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn open<P: AsRef<Path>>(
+        path: P,
+        game_version: GameVersion,
+        hints: impl Into<Hints>,
+        read_options: &ReadOptions,
+    ) -> Result<Self, Error> {
+        let mut reader = BufReader::new(File::open(path)?);
+        Self::read_with_options(&mut reader, game_version, hints, read_options)
+    }
+
+    /// Save GvasFile to the given path
+    ///
+    /// The file is written to a temporary file next to `path` which then replaces `path`, so a
+    /// failed write never leaves a truncated save behind.
+    ///
+    /// # Errors
+    ///
+    /// If the file can't be written this function returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{error::Error, game_version::GameVersion, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("save.sav")?;
+    /// let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// gvas_file.save("save.sav")?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        self.save_with_options(path, &WriteOptions::default())
+    }
+
+    /// Save GvasFile to the given path using the given [`WriteOptions`]
+    ///
+    /// If [`WriteOptions::backup`] is set, the file being replaced is copied to `<path>.bak`
+    /// first.
+    ///
+    /// # Errors
+    ///
+    /// If the file can't be written this function returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{error::Error, game_version::GameVersion, options::WriteOptions, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("save.sav")?;
+    /// let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// let options = WriteOptions {
+    ///     backup: true,
+    ///     ..Default::default()
+    /// };
+    /// gvas_file.save_with_options("save.sav", &options)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn save_with_options<P: AsRef<Path>>(
+        &self,
+        path: P,
+        write_options: &WriteOptions,
+    ) -> Result<(), Error> {
+        let path = path.as_ref();
+        let temp_path = sibling_path(path, ".tmp");
+
+        if let Err(e) = self.write_synced(&temp_path, write_options) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e);
+        }
+
+        if write_options.backup && path.exists() {
+            fs::copy(path, sibling_path(path, ".bak"))?;
+        }
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    /// Writes to a new file at `path` and flushes it to disk.
+    fn write_synced(&self, path: &Path, write_options: &WriteOptions) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_with_options(&mut writer, write_options)?;
+        let file = writer.into_inner().map_err(|e| e.into_error())?;
+        file.sync_all()?;
+        Ok(())
+    }
+
+    /// Write GvasFile to a binary file
+    ///
+    /// The writer doesn't need to seek, so saves can be written straight to stdout or a network
+    /// stream.
+    ///
+    /// # Errors
+    ///
+    /// If the file was modified in a way that makes it invalid this function returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{error::Error, GvasFile};
+    /// use std::{
+    ///     fs::File,
+    ///     io::Cursor,
+    /// };
+    /// use gvas::game_version::GameVersion;
+    ///
+    /// let mut file = File::open("save.sav")?;
+    /// let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// let mut writer = Cursor::new(Vec::new());
+    /// gvas_file.write(&mut writer)?;
+    /// println!("{:#?}", writer.get_ref());
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn write<W: Write>(&self, cursor: &mut W) -> Result<(), Error> {
+        self.write_with_options(cursor, &WriteOptions::default())
+    }
+
+    /// Write GvasFile to a binary file using the given [`WriteOptions`]
+    ///
+    /// # Errors
+    ///
+    /// If the file was modified in a way that makes it invalid this function returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{
+    ///     error::Error,
+    ///     game_version::GameVersion,
+    ///     options::{TrailingPadding, WriteOptions},
+    ///     GvasFile,
+    /// };
+    /// use std::{fs::File, io::Cursor};
+    ///
+    /// let mut file = File::open("save.sav")?;
+    /// let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// let options = WriteOptions {
+    ///     trailing_padding: TrailingPadding::Omit,
+    ///     ..Default::default()
+    /// };
+    /// let mut writer = Cursor::new(Vec::new());
+    /// gvas_file.write_with_options(&mut writer, &options)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn write_with_options<W: Write>(
+        &self,
+        cursor: &mut W,
+        write_options: &WriteOptions,
+    ) -> Result<(), Error> {
+        let data = self.write_data(write_options)?;
+        match self.deserialized_game_version {
+            DeserializedGameVersion::Palworld(compression_type, _) => {
+                container::palworld::compress_with_options(
+                    cursor,
+                    &data,
+                    compression_type,
+                    write_options,
+                )
+            }
+            ref deserialized_game_version => {
+                Self::compress_raw(cursor, &data, deserialized_game_version)
+            }
+        }
+    }
+
+    /// Write GvasFile to an async writer using the given [`WriteOptions`]
+    ///
+    /// The save is written to memory like [`GvasFile::write_with_options`], then written to
+    /// `writer` and flushed without blocking.
+    ///
+    /// # Errors
+    ///
+    /// If the file was modified in a way that makes it invalid, or the writer fails, this
+    /// function returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{error::Error, options::WriteOptions, GvasFile};
+    ///
+    /// # async fn run(gvas_file: GvasFile) -> Result<(), Error> {
+    /// let mut file = tokio::fs::File::create("save.sav").await?;
+    /// gvas_file.write_async(&mut file, &WriteOptions::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn write_async<W: tokio::io::AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+        write_options: &WriteOptions,
+    ) -> Result<(), Error> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut data = Vec::new();
+        self.write_with_options(&mut data, write_options)?;
+        writer.write_all(&data).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+
+    /// Write GvasFile wrapped in a custom container
+    ///
+    /// The container of [`deserialized_game_version`] is replaced by `container`.
+    ///
+    /// [`deserialized_game_version`]: GvasFile::deserialized_game_version
+    ///
+    /// # Errors
+    ///
+    /// If the file was modified in a way that makes it invalid, or the container fails to write
+    /// it, this function returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// See [`ContainerFormat`].
+    ///
+    /// [`ContainerFormat`]: crate::container::ContainerFormat
+    pub fn write_with_container<W: Write, C: ContainerFormat + ?Sized>(
+        &self,
+        cursor: &mut W,
+        container: &C,
+        write_options: &WriteOptions,
+    ) -> Result<(), Error> {
+        let data = self.write_data(write_options)?;
+        container.write(cursor, &data)
+    }
+
+    /// Write GvasFile split into chunks of at most `chunk_size` bytes
+    ///
+    /// The file is written like [`GvasFile::write_with_options`] and split in order, every chunk
+    /// but the last one holds exactly `chunk_size` bytes. Read the chunks back with
+    /// [`GvasFile::read_fragmented`].
+    ///
+    /// # Errors
+    ///
+    /// If `chunk_size` is zero, or the file was modified in a way that makes it invalid, this
+    /// function returns [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gvas::{error::Error, game_version::GameVersion, options::WriteOptions, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("resources/test/Slot1.sav")?;
+    /// let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// let chunks = gvas_file.write_fragmented(1024, &WriteOptions::default())?;
+    /// assert!(chunks.iter().all(|chunk| chunk.len() <= 1024));
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn write_fragmented(
+        &self,
+        chunk_size: usize,
+        write_options: &WriteOptions,
+    ) -> Result<Vec<Vec<u8>>, Error> {
+        if chunk_size == 0 {
+            Err(SerializeError::invalid_value("Chunk size must not be zero"))?
+        }
+        let mut writer = Cursor::new(Vec::new());
+        self.write_with_options(&mut writer, write_options)?;
+        Ok(writer
+            .into_inner()
+            .chunks(chunk_size)
+            .map(<[u8]>::to_vec)
+            .collect())
+    }
+
+    /// Wrap GVAS data in the container of `deserialized_game_version` without parsing it
+    ///
+    /// `data` is written as is, this is the counterpart of [`GvasFile::decompress_raw`] for
+    /// data edited or produced by other tools.
+    ///
+    /// # Errors
+    ///
+    /// If the container can't hold the data, or the writer fails, this function returns
+    /// [`Error`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gvas::{error::Error, game_version::GameVersion, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("resources/test/palworld_zlib.sav")?;
+    /// let (data, deserialized_game_version) =
+    ///     GvasFile::decompress_raw(&mut file, GameVersion::Palworld)?;
+    /// assert!(data.starts_with(b"GVAS"));
+    ///
+    /// let mut writer = Vec::new();
+    /// GvasFile::compress_raw(&mut writer, &data, &deserialized_game_version)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn compress_raw<W: Write>(
+        cursor: &mut W,
+        data: &[u8],
+        deserialized_game_version: &DeserializedGameVersion,
+    ) -> Result<(), Error> {
+        deserialized_game_version.write(cursor, data)
+    }
+
+    /// Serializes the prefix, header and properties without a container.
+    fn write_data(&self, write_options: &WriteOptions) -> Result<Vec<u8>, Error> {
+        let mut writing_cursor = Cursor::new(Vec::new());
+
+        writing_cursor.write_all(&self.prefix)?;
+        self.header
+            .write_with_extra(&mut writing_cursor, &self.custom_version_extra)?;
+
+        let mut options = PropertyOptions {
+            hints: &HashMap::new(),
+            properties_stack: &mut vec![],
+            custom_versions: self.header.get_custom_versions(),
+            package_version: self.header.get_package_version(),
+            read_options: &ReadOptions::default(),
+            write_options,
+        };
+
+        for (name, property) in &self.properties {
             writing_cursor.write_string(name)?;
             property.write(&mut writing_cursor, true, &mut options)?;
         }
-        writing_cursor.write_string("None")?;
-        writing_cursor.write_i32::<LittleEndian>(0)?; // padding
+        writing_cursor.write_string("None")?;
+        let trailing_padding = match write_options.trailing_padding {
+            TrailingPadding::Preserve => self.trailing_padding,
+            TrailingPadding::Omit => None,
+            TrailingPadding::Value(value) => Some(value),
+        };
+        match trailing_padding {
+            Some(padding) => writing_cursor.write_i32::<LittleEndian>(padding)?,
+            None if write_options.trailing_padding == TrailingPadding::Preserve => {
+                writing_cursor.write_all(&self.trailing_bytes)?
+            }
+            None => {}
+        }
+        Ok(writing_cursor.into_inner())
+    }
+
+    /// Group the top-level properties by a key computed from their names
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{error::Error, game_version::GameVersion, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("save.sav")?;
+    /// let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// let view = gvas_file.group_by(|name| name.split('_').next().unwrap_or_default().to_string());
+    /// for (group, properties) in view.iter() {
+    ///     println!("{group}: {:?}", properties.keys());
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn group_by<K, F>(&self, key: F) -> GroupedView<'_, K>
+    where
+        K: Hash + Eq,
+        F: FnMut(&str) -> K,
+    {
+        GroupedView::new(&self.properties, key)
+    }
+
+    /// Group the top-level properties by a key computed from their names, allowing them to be
+    /// modified
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gvas::{error::Error, game_version::GameVersion, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("save.sav")?;
+    /// let mut gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// let mut view = gvas_file.group_by_mut(|name| name.starts_with("Player"));
+    /// if let Some(players) = view.get_mut(&true) {
+    ///     for (name, property) in players.iter_mut() {
+    ///         println!("{name}: {property:?}");
+    ///     }
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn group_by_mut<K, F>(&mut self, key: F) -> GroupedViewMut<'_, K>
+    where
+        K: Hash + Eq,
+        F: FnMut(&str) -> K,
+    {
+        GroupedViewMut::new(&mut self.properties, key)
+    }
+
+    /// Take a snapshot of the file, see [`Snapshot`].
+    ///
+    /// This copies every property, use [`GvasFile::snapshot_since`] to share the unchanged ones
+    /// with an earlier snapshot.
+    #[inline]
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::new(self, None)
+    }
+
+    /// Take a snapshot of the file, sharing the top-level properties that are equal to those in
+    /// `previous` instead of copying them.
+    #[inline]
+    pub fn snapshot_since(&self, previous: &Snapshot) -> Snapshot {
+        Snapshot::new(self, Some(previous))
+    }
+
+    /// Restore the file to the state of `snapshot`.
+    #[inline]
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        snapshot.restore(self)
+    }
+
+    /// Find a property by a path of `.` separated names.
+    ///
+    /// The first name selects a top-level property, the following ones select fields of custom
+    /// structs. Names must match exactly, see [`GvasFile::get_property_by_path_by`] to ignore
+    /// case.
+    #[inline]
+    pub fn get_property_by_path(&self, path: &str) -> Option<&Property> {
+        self.get_property_by_path_by(path, KeyComparison::Exact)
+    }
+
+    /// Find a property by a path of `.` separated names, see [`GvasFile::get_property_by_path`].
+    #[inline]
+    pub fn get_property_by_path_mut(&mut self, path: &str) -> Option<&mut Property> {
+        self.get_property_by_path_by_mut(path, KeyComparison::Exact)
+    }
+
+    /// Find a property by a path of `.` separated names, matching names with `comparison`.
+    ///
+    /// Names keep their original casing, so a property found ignoring case is written back
+    /// unchanged.
+    ///
+    /// ```
+    /// use gvas::{game_version::GameVersion, types::map::KeyComparison, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("resources/test/Slot1.sav")?;
+    /// let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// let property = gvas_file.get_property_by_path_by("INT32_TEST", KeyComparison::CaseInsensitive);
+    /// assert_eq!(property, gvas_file.get_property_by_path("int32_test"));
+    /// # Ok::<(), gvas::error::Error>(())
+    /// ```
+    pub fn get_property_by_path_by(
+        &self,
+        path: &str,
+        comparison: KeyComparison,
+    ) -> Option<&Property> {
+        let mut names = path.split('.');
+        let mut property = self.properties.get_by(names.next()?, comparison)?;
+        for name in names {
+            property = struct_fields(property)?.get_by(name, comparison)?.first()?;
+        }
+        Some(property)
+    }
+
+    /// Find a property by a path of `.` separated names, matching names with `comparison`, see
+    /// [`GvasFile::get_property_by_path_by`].
+    pub fn get_property_by_path_by_mut(
+        &mut self,
+        path: &str,
+        comparison: KeyComparison,
+    ) -> Option<&mut Property> {
+        let mut names = path.split('.');
+        let mut property = self.properties.get_by_mut(names.next()?, comparison)?;
+        for name in names {
+            property = struct_fields_mut(property)?
+                .get_by_mut(name, comparison)?
+                .first_mut()?;
+        }
+        Some(property)
+    }
+
+    /// Set the value of an existing `IntProperty`, see [`GvasFile::get_property_by_path`].
+    ///
+    /// # Errors
+    ///
+    /// If the property doesn't exist or isn't an `IntProperty` this function returns [`Error`]
+    ///
+    /// ```
+    /// use gvas::{game_version::GameVersion, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("resources/test/Slot1.sav")?;
+    /// let mut gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// gvas_file.set_int("int32_test", 9999)?;
+    /// let property = gvas_file.get_property_by_path("int32_test");
+    /// assert_eq!(property.and_then(|p| p.get_int()).map(|p| p.value), Some(9999));
+    /// # Ok::<(), gvas::error::Error>(())
+    /// ```
+    pub fn set_int(&mut self, path: &str, value: i32) -> Result<(), Error> {
+        self.set_property(path, "IntProperty", |property| {
+            property.get_int_mut()?.value = value;
+            Some(())
+        })
+    }
+
+    /// Set the value of an existing `FloatProperty`, see [`GvasFile::set_int`].
+    pub fn set_float(&mut self, path: &str, value: f32) -> Result<(), Error> {
+        self.set_property(path, "FloatProperty", |property| {
+            property.get_f32_mut()?.value = OrderedFloat(value);
+            Some(())
+        })
+    }
+
+    /// Set the value of an existing `BoolProperty`, see [`GvasFile::set_int`].
+    pub fn set_bool(&mut self, path: &str, value: bool) -> Result<(), Error> {
+        self.set_property(path, "BoolProperty", |property| {
+            property.get_bool_mut()?.value = value;
+            Some(())
+        })
+    }
+
+    /// Set the value of an existing `StrProperty`, see [`GvasFile::set_int`].
+    pub fn set_str(&mut self, path: &str, value: impl Into<String>) -> Result<(), Error> {
+        self.set_property(path, "StrProperty", |property| {
+            property.get_str_mut()?.value = Some(value.into());
+            Some(())
+        })
+    }
+
+    fn set_property<F>(&mut self, path: &str, property_type: &str, set: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut Property) -> Option<()>,
+    {
+        let Some(property) = self.get_property_by_path_mut(path) else {
+            Err(SerializeError::PropertyNotFound(path.into()))?
+        };
+        match set(property) {
+            Some(()) => Ok(()),
+            None => Err(SerializeError::invalid_value(format!(
+                "{path} is not a {property_type}"
+            )))?,
+        }
+    }
+
+    /// Convert the file for UE4 games, see [`GvasHeader::strip_ue5_fields`].
+    ///
+    /// Large world coordinate vectors, rotators and quaternions are converted to single
+    /// precision so the file can be written without the UE5 custom versions.
+    pub fn strip_ue5_fields(&mut self) {
+        struct SinglePrecision;
+        impl VisitorMut for SinglePrecision {
+            fn visit_struct(&mut self, _path: &str, value: &mut StructPropertyValue) -> bool {
+                value.to_single_precision();
+                true
+            }
+        }
+
+        self.header.strip_ue5_fields();
+        self.walk_mut(&mut SinglePrecision);
+    }
+
+    /// Clone this file under `header`, converting the values whose layout depends on it.
+    ///
+    /// Vectors, rotators and quaternions get the precision used with or without large world
+    /// coordinates, text format arguments get the integer width of the target engine, and texts
+    /// lose their culture invariant strings if the target doesn't store them.
+    ///
+    /// # Errors
+    ///
+    /// If a value can't be stored under `header`, such as a 64-bit text format argument which
+    /// doesn't fit in 32 bits, this function returns [`Error`]
+    pub fn retarget(&self, header: GvasHeader) -> Result<GvasFile, Error> {
+        let mut retarget = Retarget::new(&header);
+        let mut file = GvasFile {
+            header,
+            ..self.clone()
+        };
+        file.walk_mut(&mut retarget);
+        match retarget.error {
+            Some(e) => Err(e),
+            None => Ok(file),
+        }
+    }
+
+    /// Replace strings throughout the file.
+    ///
+    /// `f` is called with the path and value of every `StrProperty`, `NameProperty`, string and
+    /// name array element, and the source string of every `TextProperty`, and returns the new
+    /// value or `None` to keep it. Map keys are left untouched. Length fields are computed when
+    /// the file is written, so strings can change length and encoding freely.
+    ///
+    /// Returns the number of replaced strings.
+    ///
+    /// ```
+    /// use gvas::{game_version::GameVersion, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("resources/test/Slot1.sav")?;
+    /// let mut gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// let replaced = gvas_file.replace_strings(|_path, value| {
+    ///     value.contains("world").then(|| value.replace("world", "Welt"))
+    /// });
+    /// assert_eq!(replaced, 4);
+    /// # Ok::<(), gvas::error::Error>(())
+    /// ```
+    pub fn replace_strings<F>(&mut self, f: F) -> usize
+    where
+        F: FnMut(&str, &str) -> Option<String>,
+    {
+        struct Replace<F> {
+            f: F,
+            replaced: usize,
+        }
+        impl<F: FnMut(&str, &str) -> Option<String>> Replace<F> {
+            fn replace(&mut self, path: &str, value: &mut Option<String>) {
+                if let Some(new_value) = value.as_deref().and_then(|value| (self.f)(path, value)) {
+                    *value = Some(new_value);
+                    self.replaced += 1;
+                }
+            }
+        }
+        impl<F: FnMut(&str, &str) -> Option<String>> VisitorMut for Replace<F> {
+            fn visit_property(&mut self, path: &str, property: &mut Property) -> bool {
+                match property {
+                    Property::StrProperty(property) => self.replace(path, &mut property.value),
+                    Property::NameProperty(property) => {
+                        let value = property
+                            .value
+                            .as_deref()
+                            .and_then(|value| (self.f)(path, value));
+                        if let Some(new_value) = value {
+                            property.value = Some(Arc::from(new_value));
+                            self.replaced += 1;
+                        }
+                    }
+                    Property::TextProperty(property) => match &mut property.value.history {
+                        FTextHistory::None {
+                            culture_invariant_string: value,
+                        }
+                        | FTextHistory::Base {
+                            source_string: value,
+                            ..
+                        } => self.replace(path, value),
+                        _ => {}
+                    },
+                    Property::ArrayProperty(ArrayProperty::Strings { strings: values })
+                    | Property::ArrayProperty(ArrayProperty::Names { names: values }) => {
+                        for (index, value) in values.iter_mut().enumerate() {
+                            self.replace(&format!("{path}[{index}]"), value);
+                        }
+                    }
+                    _ => {}
+                }
+                true
+            }
+        }
 
-        match self.deserialized_game_version {
-            DeserializedGameVersion::Default => cursor.write_all(&writing_cursor.into_inner())?,
-            DeserializedGameVersion::Palworld(compression_type) => {
-                let decompressed = writing_cursor.into_inner();
-
-                cursor.write_u32::<LittleEndian>(decompressed.len() as u32)?;
-                let compressed_length_pos = cursor.stream_position()?;
-                cursor.write_u32::<LittleEndian>(0)?; // Compressed length placeholder, will be updated later
-                cursor.write_all(PLZ_MAGIC)?;
-                cursor.write_enum(compression_type)?;
-
-                // Compress and write data directly to the output cursor
-                match compression_type {
-                    PalworldCompressionType::None => cursor.write_all(&decompressed)?,
-                    PalworldCompressionType::Zlib => {
-                        let mut encoder = ZlibEncoder::new(cursor.by_ref(), Compression::new(6));
-                        encoder.write_all(&decompressed)?;
-                        encoder.finish()?;
+        let mut replace = Replace { f, replaced: 0 };
+        self.walk_mut(&mut replace);
+        replace.replaced
+    }
+
+    /// Remove duplicate entries from every multicast delegate list in the file, see
+    /// [`MulticastScriptDelegate::compact`].
+    ///
+    /// Returns the number of removed delegates.
+    ///
+    /// [`MulticastScriptDelegate::compact`]: crate::properties::delegate_property::MulticastScriptDelegate::compact
+    pub fn compact_delegates(&mut self) -> usize {
+        struct Compact(usize);
+        impl VisitorMut for Compact {
+            fn visit_property(&mut self, _path: &str, property: &mut Property) -> bool {
+                match property {
+                    Property::MulticastInlineDelegateProperty(property) => {
+                        self.0 += property.value.compact();
                     }
-                    PalworldCompressionType::ZlibTwice => {
-                        let encoder = ZlibEncoder::new(cursor.by_ref(), Compression::default());
-                        let mut encoder = ZlibEncoder::new(encoder, Compression::default());
-                        encoder.write_all(&decompressed)?;
-                        encoder.finish()?;
+                    Property::MulticastSparseDelegateProperty(property) => {
+                        self.0 += property.value.compact();
                     }
+                    _ => {}
                 }
+                true
+            }
+        }
+
+        let mut compact = Compact(0);
+        self.walk_mut(&mut compact);
+        compact.0
+    }
+
+    /// Walk every property in the file, see [`visitor::walk`].
+    pub fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        for (name, property) in &self.properties {
+            visitor::walk(visitor, name, property);
+        }
+    }
+
+    /// Walk every property in the file, see [`visitor::walk_mut`].
+    pub fn walk_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        for (name, property) in self.properties.iter_mut() {
+            visitor::walk_mut(visitor, name, property);
+        }
+    }
+
+    /// Iterate over every leaf property in the file, together with its full path.
+    ///
+    /// Paths use the format described in [`visitor`]. Arrays, sets, maps and custom structs are
+    /// internal nodes and are never returned, even when their elements aren't properties, like
+    /// the values of [`ArrayProperty::Ints`].
+    ///
+    /// ```
+    /// use gvas::{game_version::GameVersion, GvasFile};
+    /// use std::{collections::HashMap, fs::File};
+    ///
+    /// let mut file = File::open("resources/test/Slot1.sav")?;
+    /// let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// let leaves = gvas_file.iter_leaves().collect::<HashMap<_, _>>();
+    /// assert!(leaves.contains_key("struct_property.test_field"));
+    /// assert!(leaves.contains_key("array_of_structs[1].test_field"));
+    /// # Ok::<(), gvas::error::Error>(())
+    /// ```
+    pub fn iter_leaves(&self) -> impl Iterator<Item = (String, &Property)> {
+        self.properties
+            .iter()
+            .flat_map(|(name, property)| visitor::leaves(name, property))
+    }
+
+    /// Compare the properties of this file with `new`, see [`diff::diff`].
+    pub fn diff<'a>(&'a self, new: &'a GvasFile, options: &DiffOptions) -> Vec<Difference<'a>> {
+        diff::diff(self, new, options)
+    }
+
+    /// Every property matching `query` with its path, see [`query`].
+    ///
+    /// # Errors
+    ///
+    /// If `query` isn't valid this function returns [`ParseQueryError`].
+    pub fn select(&self, query: &str) -> Result<Vec<(String, &Property)>, ParseQueryError> {
+        query::select(self, query)
+    }
+
+    /// Flatten every numeric map and array in the file, see [`table::rows`].
+    pub fn numeric_table(&self) -> Vec<TableRow> {
+        self.properties
+            .iter()
+            .flat_map(|(name, property)| table::rows(name, property))
+            .collect()
+    }
+
+    /// Every statistics map in the file, see [`statistics::collect`].
+    pub fn statistics(&self) -> Statistics<'_> {
+        let mut statistics = Statistics::default();
+        for (name, property) in &self.properties {
+            statistics.extend(statistics::collect(name, property));
+        }
+        statistics
+    }
+
+    /// Every enum value used in the file, see [`enum_usage::collect`].
+    pub fn enum_usage(&self) -> EnumUsage {
+        let mut usage = EnumUsage::new();
+        for (name, property) in &self.properties {
+            usage.extend(enum_usage::collect(name, property));
+        }
+        usage
+    }
+
+    /// Every object reference in the file, see [`references::collect`].
+    pub fn references(&self) -> ReferenceGraph {
+        let mut graph = ReferenceGraph::new();
+        for (name, property) in &self.properties {
+            graph.extend(references::collect(name, property));
+        }
+        graph
+    }
+
+    /// The `n` largest properties and struct fields with their paths and serialized sizes in
+    /// bytes, largest first.
+    ///
+    /// A size includes the name and tag of the property and, for structs, all of their fields,
+    /// so a struct is always listed before its fields. Structs inside arrays, sets and maps are
+    /// only counted as part of their container.
+    ///
+    /// # Errors
+    ///
+    /// If a property can't be written this function returns [`Error`]
+    ///
+    /// ```
+    /// use gvas::{game_version::GameVersion, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("resources/test/Slot1.sav")?;
+    /// let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// for (path, size) in gvas_file.largest_properties(3)? {
+    ///     println!("{path}: {size} bytes");
+    /// }
+    /// # Ok::<(), gvas::error::Error>(())
+    /// ```
+    pub fn largest_properties(&self, n: usize) -> Result<Vec<(String, usize)>, Error> {
+        let mut options = PropertyOptions {
+            hints: &HashMap::new(),
+            properties_stack: &mut vec![],
+            custom_versions: self.header.get_custom_versions(),
+            package_version: self.header.get_package_version(),
+            read_options: &ReadOptions::default(),
+            write_options: &WriteOptions::default(),
+        };
+
+        let mut sizes = Vec::new();
+        for (name, property) in &self.properties {
+            let values = std::slice::from_ref(property);
+            property_sizes(name.clone(), name, values, &mut options, &mut sizes)?;
+        }
+        // Stable, so properties of the same size stay in save order
+        sizes.sort_by(|(_, a), (_, b)| b.cmp(a));
+        sizes.truncate(n);
+        Ok(sizes)
+    }
+
+    /// Apply `transform` to every vector and `Transform` struct in the property at `path`.
+    ///
+    /// Returns the number of values that were changed, see
+    /// [`CoordinateTransform::apply`].
+    ///
+    /// [`CoordinateTransform::apply`]: coordinates::CoordinateTransform::apply
+    #[cfg(feature = "glam")]
+    pub fn transform_coordinates(
+        &mut self,
+        path: &str,
+        transform: &coordinates::CoordinateTransform,
+    ) -> Result<usize, Error> {
+        let Some(property) = self.get_property_by_path_mut(path) else {
+            Err(SerializeError::PropertyNotFound(path.into()))?
+        };
+        Ok(transform.apply(property))
+    }
+
+    /// Export the property at `path` to a [`PropertyFile`].
+    ///
+    /// `hints` are the hints used to read this file, only the ones below `path` are kept.
+    pub fn export_property(
+        &self,
+        path: &str,
+        hints: &HashMap<String, HintValue>,
+    ) -> Result<PropertyFile, Error> {
+        let Some(property) = self.get_property_by_path(path) else {
+            Err(SerializeError::PropertyNotFound(path.into()))?
+        };
+        let prefix = format!("{}.", properties_stack(path).join("."));
+        Ok(PropertyFile {
+            path: path.to_string(),
+            property: property.clone(),
+            package_version: self.header.get_package_version(),
+            custom_versions: self.header.get_custom_versions().clone(),
+            hints: hints
+                .iter()
+                .filter(|(key, _)| key.starts_with(&prefix))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        })
+    }
+
+    /// Create a copy of this file that only contains the property at `path`.
+    ///
+    /// The structs leading to the property are kept with all other fields removed, so writing
+    /// the copy produces a small save that still reproduces how the property is read. This makes
+    /// it possible to attach a save to an issue without sharing the rest of its contents.
+    ///
+    /// This is [`extract`](Self::extract) with a single path, keeping only the first of the
+    /// static array values leading to the property, and failing if the property doesn't exist.
+    #[inline]
+    pub fn minimal_repro(&self, path: &str) -> Result<GvasFile, Error> {
+        self.minimal_repro_by(path, KeyComparison::Exact)
+    }
+
+    /// Create a copy of this file that only contains the property at `path`, matching names
+    /// with `comparison`, see [`GvasFile::minimal_repro`].
+    pub fn minimal_repro_by(
+        &self,
+        path: &str,
+        comparison: KeyComparison,
+    ) -> Result<GvasFile, Error> {
+        let mut file = self.extract_by(&[path], comparison);
+
+        // Only the first of the values leading to the property is needed to reproduce it
+        let mut names = path.split('.');
+        let mut property = names
+            .next()
+            .and_then(|name| file.properties.get_by_mut(name, comparison));
+        for name in names {
+            property = property
+                .and_then(struct_fields_mut)
+                .and_then(|fields| fields.get_by_mut(name, comparison))
+                .and_then(|values| {
+                    values.truncate(1);
+                    values.first_mut()
+                });
+        }
+        if property.is_none() {
+            Err(SerializeError::PropertyNotFound(path.into()))?
+        }
+        Ok(file)
+    }
+
+    /// Create a copy of this file that only contains the properties at `paths`.
+    ///
+    /// Each path selects a property and everything below it, using the `.` separated names of
+    /// [`get_property_by_path`](Self::get_property_by_path). The structs leading to the selected
+    /// properties are kept with all other fields removed, and the header is copied, so the copy
+    /// can be written as a valid save. Paths that don't exist in this file are ignored.
+    ///
+    /// ```
+    /// use gvas::{game_version::GameVersion, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("resources/test/Slot1.sav")?;
+    /// let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// let extract = gvas_file.extract(&["u8_test", "struct_property.test_field"]);
+    /// assert_eq!(
+    ///     extract.properties.keys().collect::<Vec<_>>(),
+    ///     ["u8_test", "struct_property"]
+    /// );
+    /// # Ok::<(), gvas::error::Error>(())
+    /// ```
+    #[inline]
+    pub fn extract(&self, paths: &[&str]) -> GvasFile {
+        self.extract_by(paths, KeyComparison::Exact)
+    }
+
+    /// Create a copy of this file that only contains the properties at `paths`, matching names
+    /// like [`get_property_by_path_by`](Self::get_property_by_path_by) with `comparison`, see
+    /// [`GvasFile::extract`].
+    pub fn extract_by(&self, paths: &[&str], comparison: KeyComparison) -> GvasFile {
+        let sub_paths = sub_paths(&self.properties, paths, comparison);
+        let properties = HashableIndexMap(
+            self.properties
+                .iter()
+                .zip(sub_paths)
+                .filter_map(|((name, property), paths)| {
+                    if paths.is_empty() {
+                        return None;
+                    }
+                    let mut property = property.clone();
+                    retain_paths(&mut property, &paths, comparison)
+                        .then(|| (name.clone(), property))
+                })
+                .collect(),
+        );
+
+        GvasFile {
+            deserialized_game_version: self.deserialized_game_version.clone(),
+            prefix: self.prefix.clone(),
+            header: self.header.clone(),
+            custom_version_extra: self.custom_version_extra.clone(),
+            properties,
+            trailing_padding: self.trailing_padding,
+            trailing_bytes: self.trailing_bytes.clone(),
+        }
+    }
+
+    /// Import the property of a [`PropertyFile`] at `path`, replacing any existing property.
+    ///
+    /// All names in `path` except the last one must already exist.
+    pub fn import_property(&mut self, path: &str, file: PropertyFile) -> Result<(), Error> {
+        self.insert_property(path, file.property)
+    }
+
+    /// Copy the property at `path` of `other` to the same path in this file, replacing any
+    /// existing property.
+    ///
+    /// The copy is converted like [`retarget`](Self::retarget) converts a file, so vectors get
+    /// the precision used by this file and text format arguments its integer width, which lets
+    /// properties move between UE4 and UE5 saves. All names in `path` except the last one must
+    /// already exist in this file.
+    ///
+    /// # Errors
+    ///
+    /// If `other` has no property at `path` this function returns
+    /// [`SerializeError::PropertyNotFound`]. If `other` was saved by an engine other than UE4 or
+    /// UE5 that differs from the engine of this file, or the property can't be stored in this
+    /// file, it returns [`Error`]
+    ///
+    /// ```
+    /// use gvas::{game_version::GameVersion, GvasFile};
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("resources/test/Slot1.sav")?;
+    /// let source = GvasFile::read(&mut file, GameVersion::Default)?;
+    /// let mut file = File::open("resources/test/Slot2.sav")?;
+    /// let mut target = GvasFile::read(&mut file, GameVersion::Default)?;
+    ///
+    /// target.transplant_from(&source, "struct_property")?;
+    /// assert_eq!(
+    ///     target.get_property_by_path("struct_property.test_field"),
+    ///     source.get_property_by_path("struct_property.test_field")
+    /// );
+    /// # Ok::<(), gvas::error::Error>(())
+    /// ```
+    pub fn transplant_from(&mut self, other: &GvasFile, path: &str) -> Result<(), Error> {
+        let Some(property) = other.get_property_by_path(path) else {
+            Err(SerializeError::PropertyNotFound(path.into()))?
+        };
+        // Layouts that differ between UE4 and UE5 are converted below, unknown engines rely on
+        // the package and custom versions of the headers
+        let source = other.header.get_engine_version().family();
+        let target = self.header.get_engine_version().family();
+        match (source, target) {
+            (EngineFamily::UE4 | EngineFamily::UE5, EngineFamily::UE4 | EngineFamily::UE5) => {}
+            (EngineFamily::Other(0), _) | (_, EngineFamily::Other(0)) => {}
+            (source, target) if source == target => {}
+            (source, target) => Err(SerializeError::invalid_value(format!(
+                "Can't transplant {path} from engine {source:?} to {target:?}"
+            )))?,
+        }
+
+        let mut retarget = Retarget::new(&self.header);
+        let mut property = property.clone();
+        visitor::walk_mut(&mut retarget, path, &mut property);
+        if let Some(e) = retarget.error {
+            Err(e)?
+        }
+        self.insert_property(path, property)
+    }
 
-                // Update compressed length
-                let end_pos = cursor.stream_position()?;
-                cursor.seek(SeekFrom::Start(compressed_length_pos))?;
-                cursor.write_u32::<LittleEndian>((end_pos - (compressed_length_pos + 4)) as u32)?;
-                cursor.seek(SeekFrom::Start(end_pos))?;
+    /// Insert `property` at `path`, replacing any existing property.
+    fn insert_property(&mut self, path: &str, property: Property) -> Result<(), Error> {
+        match path.rsplit_once('.') {
+            None => {
+                self.properties.insert(path.to_string(), property);
+            }
+            Some((parent, name)) => {
+                let Some(fields) = self
+                    .get_property_by_path_mut(parent)
+                    .and_then(struct_fields_mut)
+                else {
+                    Err(SerializeError::PropertyNotFound(parent.into()))?
+                };
+                fields.insert(name.to_string(), vec![property]);
             }
         }
         Ok(())
     }
 }
+
+/// Converts the values whose layout depends on the header, see [`GvasFile::retarget`].
+struct Retarget {
+    large_world_coordinates: bool,
+    wide_arguments: bool,
+    culture_invariant: bool,
+    error: Option<Error>,
+}
+
+impl Retarget {
+    fn new(header: &GvasHeader) -> Self {
+        Retarget {
+            large_world_coordinates: header
+                .supports_version(FUE5ReleaseStreamObjectVersion::LargeWorldCoordinates),
+            wide_arguments: header.supports_version(
+                FUE5ReleaseStreamObjectVersion::TextFormatArgumentData64bitSupport,
+            ),
+            culture_invariant: header.supports_version(
+                FEditorObjectVersion::CultureInvariantTextSerializationKeyStability,
+            ),
+            error: None,
+        }
+    }
+}
+
+impl VisitorMut for Retarget {
+    fn visit_property(&mut self, _path: &str, property: &mut Property) -> bool {
+        if let Property::TextProperty(text) = property {
+            if let Err(e) = text
+                .value
+                .retarget(self.wide_arguments, self.culture_invariant)
+            {
+                self.error.get_or_insert(e);
+            }
+        }
+        true
+    }
+
+    fn visit_struct(&mut self, _path: &str, value: &mut StructPropertyValue) -> bool {
+        match self.large_world_coordinates {
+            true => value.to_double_precision(),
+            false => value.to_single_precision(),
+        };
+        true
+    }
+}
+
+/// Adds the serialized size of the property at `path`, and of its struct fields, to `sizes`.
+fn property_sizes(
+    path: String,
+    name: &str,
+    values: &[Property],
+    options: &mut PropertyOptions,
+    sizes: &mut Vec<(String, usize)>,
+) -> Result<usize, Error> {
+    let mut size = 0;
+    for property in values {
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_string(name)?;
+        property.write(&mut writer, true, options)?;
+        size += writer.get_ref().len();
+
+        if let Some(fields) = struct_fields(property) {
+            for (field, values) in fields {
+                property_sizes(format!("{path}.{field}"), field, values, options, sizes)?;
+            }
+        }
+    }
+    sizes.push((path, size));
+    Ok(size)
+}
+
+/// Groups the rest of each path by the entry of `map` its first name selects, found like
+/// [`GvasFile::get_property_by_path_by`] does. A path ending at an entry leaves an empty rest.
+fn sub_paths<'a, V: Hash>(
+    map: &HashableIndexMap<String, V>,
+    paths: &[&'a str],
+    comparison: KeyComparison,
+) -> Vec<Vec<&'a str>> {
+    let mut sub_paths = vec![Vec::new(); map.len()];
+    for path in paths {
+        let (name, rest) = path.split_once('.').unwrap_or((path, ""));
+        if let Some((index, _, _)) = map.get_full_by(name, comparison) {
+            sub_paths[index].push(rest);
+        }
+    }
+    sub_paths
+}
+
+/// Removes the struct fields of `property` that aren't on one of `paths`, returning false if
+/// nothing is left.
+fn retain_paths(property: &mut Property, paths: &[&str], comparison: KeyComparison) -> bool {
+    if paths.contains(&"") {
+        return true;
+    }
+    let Some(fields) = struct_fields_mut(property) else {
+        return false;
+    };
+    let mut sub_paths = sub_paths(fields, paths, comparison).into_iter();
+    fields.retain(|_, values| {
+        let paths = sub_paths.next().unwrap_or_default();
+        if paths.is_empty() {
+            return false;
+        }
+        values.retain_mut(|value| retain_paths(value, &paths, comparison));
+        !values.is_empty()
+    });
+    !fields.is_empty()
+}
+
+/// Appends `suffix` to the file name of `path`.
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+/// Writes the custom version format and the custom versions in that format.
+fn write_custom_versions<W: Write>(
+    cursor: &mut W,
+    custom_version_format: u32,
+    custom_versions: &HashableIndexMap<Guid, u32>,
+    custom_version_names: &HashableIndexMap<Guid, String>,
+) -> Result<usize, Error> {
+    let Ok(format) = CustomVersionFormat::try_from(custom_version_format) else {
+        Err(Error::unsupported(SerializeError::InvalidValue(
+            format!("Custom version format {custom_version_format} not supported").into_boxed_str(),
+        )))?
+    };
+    cursor.write_u32::<LittleEndian>(custom_version_format)?;
+    custom_version::write_container_with_names(
+        cursor,
+        format,
+        custom_versions,
+        custom_version_names,
+    )
+}
+
+/// Reads the next root property, `None` at the `None` terminator.
+///
+/// Properties left out by [`ReadOptions::root_properties`] are kept as raw bytes if
+/// `keep_filtered` is set, and skipped otherwise.
+pub(crate) fn read_root_property<R: Read + Seek>(
+    cursor: &mut R,
+    options: &mut PropertyOptions,
+    keep_filtered: bool,
+) -> Result<Option<(String, Property)>, Error> {
+    loop {
+        let property_name = options.read_string(cursor)?;
+        if property_name == "None" {
+            return Ok(None);
+        }
+
+        let property_type = options.read_type_name(cursor)?;
+        options.properties_stack.push(property_name.clone());
+        let property = match options.is_filtered_out() {
+            false if options.read_options.recover_errors => {
+                read_recoverable_property(cursor, &property_type, options)
+            }
+            false => Property::new(cursor, &property_type, true, options, None).map(Some),
+            true => Property::read_filtered_out(cursor, &property_type, options, keep_filtered)
+                .map_err(|error| error.in_property(options.properties_stack)),
+        };
+        let _ = options.properties_stack.pop();
+
+        if let Some(property) = property? {
+            return Ok(Some((property_name, property)));
+        }
+    }
+}
+
+/// Reads a root property with [`ReadOptions::recover_errors`].
+///
+/// The tag and body are read by the length declared in the tag before parsing, so a property
+/// which fails to parse is kept as an [`UnknownProperty`] without seeking back.
+fn read_recoverable_property<R: Read + Seek>(
+    cursor: &mut R,
+    property_type: &str,
+    options: &mut PropertyOptions,
+) -> Result<Option<Property>, Error> {
+    let start = cursor.stream_position()?;
+    let raw = UnknownProperty::read_with_raw_tag(cursor, property_type.to_string(), options)
+        .map_err(|error| error.in_property(options.properties_stack))?;
+    let end = cursor.stream_position()?;
+
+    let data = [raw.tag().map_or(&[][..], |tag| tag), raw.raw()].concat();
+    let mut reader = PositionReader::with_position(Cursor::new(data), start);
+    let error = match Property::new(&mut reader, property_type, true, options, None) {
+        Ok(property) if reader.position() == end => return Ok(Some(property)),
+        Ok(_) => Error::from(DeserializeError::InvalidValueSize(
+            end - start,
+            reader.position() - start,
+            start,
+        ))
+        .in_property(options.properties_stack),
+        Err(error) => error,
+    };
+
+    options.report(start, end, ReportReason::FailedProperty);
+    if let Some(report) = &options.read_options.report {
+        report.push_error(options.properties_stack.join("."), error);
+    }
+    Ok(Some(raw.into()))
+}
+
+/// Reads the padding after the `None` terminator, `None` if the file ends without it.
+///
+/// If the file ends before a full padding value, the bytes before the end are returned instead.
+pub(crate) fn read_trailing_padding<R: Read>(
+    cursor: &mut R,
+) -> Result<(Option<i32>, Vec<u8>), Error> {
+    let mut padding = Vec::with_capacity(4);
+    cursor.take(4).read_to_end(&mut padding)?;
+    match <[u8; 4]>::try_from(padding.as_slice()) {
+        Ok(padding) => Ok((Some(i32::from_le_bytes(padding)), Vec::new())),
+        Err(_) => Ok((None, padding)),
+    }
+}
+
+/// Replaces `error` with [`DeserializeError::WrongGameVersion`] if the save starting at `start`
+/// looks like a save of another game version than `game_version`.
+fn wrong_game_version<R: Read + Seek>(
+    cursor: &mut R,
+    start: u64,
+    game_version: GameVersion,
+    error: Error,
+) -> Error {
+    match container::suggest(cursor, start, game_version) {
+        Some(suggested) => DeserializeError::WrongGameVersion(game_version, suggested).into(),
+        None => error,
+    }
+}
+
+/// Reads the bytes between the custom version container and the save game class name, which
+/// some engine forks use to extend the header, followed by the class name.
+///
+/// Only reads forward, so readers which can't seek back, such as decompressors, are supported.
+/// A candidate class name is rejected at its first invalid byte, so no data after the class name
+/// is consumed. Class names longer than `max_len` characters, including the terminator, are
+/// rejected like invalid ones.
+fn read_custom_version_extra<R: Read>(
+    cursor: &mut R,
+    max_len: u32,
+) -> Result<(Vec<u8>, String), Error> {
+    let max_len = i32::try_from(max_len).map_or(MAX_CLASS_NAME_LEN, |max_len| {
+        max_len.min(MAX_CLASS_NAME_LEN)
+    });
+    let mut buffer = Vec::new();
+    'scan: for offset in 0..=MAX_CUSTOM_VERSION_EXTRA {
+        let mut len = [0u8; 4];
+        for (index, byte) in (offset..).zip(len.iter_mut()) {
+            let Some(value) = lookahead(cursor, &mut buffer, index)? else {
+                break 'scan;
+            };
+            *byte = value;
+        }
+        let len = i32::from_le_bytes(len);
+        if !(2..=max_len).contains(&len) {
+            continue;
+        }
+
+        // A non-empty ASCII string without control characters
+        let start = offset + 4;
+        let end = start + len as usize - 1;
+        for index in start..=end {
+            let Some(byte) = lookahead(cursor, &mut buffer, index)? else {
+                break 'scan;
+            };
+            if index == end && byte == 0 {
+                let save_game_class_name =
+                    buffer[start..end].iter().copied().map(char::from).collect();
+                buffer.truncate(offset);
+                return Ok((buffer, save_game_class_name));
+            }
+            if !byte.is_ascii_graphic() {
+                continue 'scan;
+            }
+        }
+    }
+    Err(DeserializeError::InvalidHeader(
+        "Save game class name not found after the custom versions".into(),
+    ))?
+}
+
+/// Returns the byte at `index` after the scan start, reading from `cursor` into `buffer` as
+/// needed, or `None` at the end of the data.
+fn lookahead<R: Read>(
+    cursor: &mut R,
+    buffer: &mut Vec<u8>,
+    index: usize,
+) -> Result<Option<u8>, Error> {
+    while buffer.len() <= index {
+        let mut byte = [0u8];
+        match cursor.read_exact(&mut byte) {
+            Ok(()) => buffer.push(byte[0]),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => Err(e)?,
+        }
+    }
+    Ok(Some(buffer[index]))
+}
+
+/// How the GVAS data of a save is hashed while it's read, the digest is only returned when an
+/// algorithm is given.
+trait SaveHash {
+    /// The digest returned with the file.
+    type Digest;
+
+    /// Reads the header and the properties following `prefix`, see
+    /// [`GvasFile::read_properties`].
+    fn read_properties<R: Read + Seek>(
+        self,
+        cursor: &mut R,
+        deserialized_game_version: DeserializedGameVersion,
+        prefix: Vec<u8>,
+        hints: &Hints,
+        read_options: &ReadOptions,
+    ) -> Result<(GvasFile, Self::Digest), Error>;
+}
+
+/// Reads a save without hashing it.
+struct NoHash;
+
+impl SaveHash for NoHash {
+    type Digest = ();
+
+    #[inline]
+    fn read_properties<R: Read + Seek>(
+        self,
+        cursor: &mut R,
+        deserialized_game_version: DeserializedGameVersion,
+        prefix: Vec<u8>,
+        hints: &Hints,
+        read_options: &ReadOptions,
+    ) -> Result<(GvasFile, ()), Error> {
+        let file = GvasFile::read_properties(
+            cursor,
+            deserialized_game_version,
+            prefix,
+            hints,
+            read_options,
+        )?;
+        Ok((file, ()))
+    }
+}
+
+impl SaveHash for DigestAlgorithm {
+    type Digest = Digest;
+
+    fn read_properties<R: Read + Seek>(
+        self,
+        cursor: &mut R,
+        deserialized_game_version: DeserializedGameVersion,
+        prefix: Vec<u8>,
+        hints: &Hints,
+        read_options: &ReadOptions,
+    ) -> Result<(GvasFile, Digest), Error> {
+        let mut reader = HashingReader::new(cursor, self)?;
+        reader.update(&prefix);
+        let file = GvasFile::read_properties(
+            &mut reader,
+            deserialized_game_version,
+            prefix,
+            hints,
+            read_options,
+        )?;
+        Ok((file, reader.digest()))
+    }
+}
+
+/// PNG signature starting a thumbnail prefix.
+const PNG_SIGNATURE: [u8; 8] = *b"\x89PNG\r\n\x1a\n";
+
+/// Largest metadata block accepted as a prefix.
+const MAX_PREFIX_LEN: u32 = 64 * 1024 * 1024;
+
+/// Reads the data before the GVAS magic, returning it with the magic that follows.
+///
+/// Only the prefix shapes known to precede GVAS data are accepted:
+///
+/// ```text
+/// no prefix           "GVAS" ...
+/// PNG thumbnail       PNG signature, chunks up to and including IEND, "GVAS" ...
+/// metadata block      u32 length, length bytes, "GVAS" ...
+/// ```
+///
+/// Anything else is reported as an unrecognized file type, instead of searching the data for
+/// the magic.
+pub(crate) fn read_prefix<R: Read>(reader: &mut R) -> Result<(Vec<u8>, [u8; 4]), Error> {
+    let mut head = [0u8; 4];
+    reader.read_exact(&mut head)?;
+    if head == FILE_TYPE_GVAS.to_le_bytes() {
+        return Ok((Vec::new(), head));
+    }
+
+    let mut prefix = head.to_vec();
+    let shape = match head == PNG_SIGNATURE[..4] {
+        true => read_png_prefix(reader, &mut prefix),
+        false => read_block_prefix(reader, &mut prefix, u32::from_le_bytes(head)),
+    };
+    let magic = match shape {
+        Ok(()) => {
+            let mut magic = [0u8; 4];
+            match reader.read_exact(&mut magic) {
+                Ok(()) => Some(magic),
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => None,
+                Err(e) => Err(e)?,
+            }
+        }
+        // Not one of the known shapes
+        Err(e) if matches!(e.kind(), ErrorKind::UnexpectedEof | ErrorKind::InvalidData) => None,
+        Err(e) => Err(e)?,
+    };
+    match magic {
+        Some(magic) if magic == FILE_TYPE_GVAS.to_le_bytes() => Ok((prefix, magic)),
+        _ => Err(DeserializeError::InvalidHeader(
+            format!("File type {} not recognized", u32::from_le_bytes(head)).into_boxed_str(),
+        ))?,
+    }
+}
+
+/// Reads the prefix of decompressed GVAS data, returning it with a cursor positioned on the
+/// magic.
+pub(crate) fn buffered_prefix(data: Vec<u8>) -> Result<(Cursor<Vec<u8>>, Vec<u8>), Error> {
+    let mut cursor = Cursor::new(data);
+    let (prefix, _) = read_prefix(&mut cursor)?;
+    cursor.set_position(prefix.len() as u64);
+    Ok((cursor, prefix))
+}
+
+/// A reader over streamed GVAS data, see [`stream_prefix`].
+pub(crate) type PrefixedReader<R> = PositionReader<BufReader<io::Chain<Cursor<[u8; 4]>, R>>>;
+
+/// Reads the prefix of streamed GVAS data, returning it with a reader positioned on the magic.
+pub(crate) fn stream_prefix<R: Read>(mut reader: R) -> Result<(PrefixedReader<R>, Vec<u8>), Error> {
+    let (prefix, magic) = read_prefix(&mut reader)?;
+    let reader = PositionReader::with_position(
+        BufReader::new(Cursor::new(magic).chain(reader)),
+        prefix.len() as u64,
+    );
+    Ok((reader, prefix))
+}
+
+/// Reads the rest of a PNG image into `prefix`, up to and including the IEND chunk.
+fn read_png_prefix<R: Read>(reader: &mut R, prefix: &mut Vec<u8>) -> io::Result<()> {
+    let mut signature = [0u8; 4];
+    reader.read_exact(&mut signature)?;
+    prefix.extend_from_slice(&signature);
+    if prefix[..] != PNG_SIGNATURE {
+        return Err(ErrorKind::InvalidData.into());
+    }
+
+    loop {
+        let mut chunk_header = [0u8; 8];
+        reader.read_exact(&mut chunk_header)?;
+        prefix.extend_from_slice(&chunk_header);
+        let [l0, l1, l2, l3, t0, t1, t2, t3] = chunk_header;
+        let length = u32::from_be_bytes([l0, l1, l2, l3]);
+        // Chunk data followed by its CRC
+        read_exact_into(reader, prefix, u64::from(length) + 4)?;
+        if [t0, t1, t2, t3] == *b"IEND" {
+            return Ok(());
+        }
+    }
+}
+
+/// Reads a metadata block of `length` bytes into `prefix`.
+fn read_block_prefix<R: Read>(reader: &mut R, prefix: &mut Vec<u8>, length: u32) -> io::Result<()> {
+    if length > MAX_PREFIX_LEN {
+        return Err(ErrorKind::InvalidData.into());
+    }
+    read_exact_into(reader, prefix, u64::from(length))
+}
+
+/// Appends exactly `length` bytes from `reader` to `buffer`.
+fn read_exact_into<R: Read>(reader: &mut R, buffer: &mut Vec<u8>, length: u64) -> io::Result<()> {
+    let read = reader.take(length).read_to_end(buffer)?;
+    match read as u64 == length {
+        true => Ok(()),
+        false => Err(ErrorKind::UnexpectedEof.into()),
+    }
+}
+
+#[allow(dead_code)]
+const fn assert_send_sync<T: Send + Sync>() {}
+
+// Parsed saves are plain data and must remain shareable between threads.
+const _: () = {
+    assert_send_sync::<GvasFile>();
+    assert_send_sync::<GvasHeader>();
+    assert_send_sync::<Property>();
+    assert_send_sync::<properties::struct_property::StructPropertyValue>();
+    assert_send_sync::<properties::SharedParseContext>();
+    assert_send_sync::<PropertyOptions>();
+    assert_send_sync::<Error>();
+};
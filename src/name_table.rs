@@ -0,0 +1,81 @@
+//! Interning of repeated names
+//!
+//! Property names, object paths and struct type names repeat thousands of times in large saves.
+//! When [`ReadOptions::name_table`] is set, the values of `NameProperty` and `ObjectProperty`
+//! and the type names of `StructProperty` are read through a [`NameTable`], so equal names
+//! share a single allocation. Well-known struct type names like `Vector` are always shared,
+//! with or without a table.
+//!
+//! [`ReadOptions::name_table`]: crate::options::ReadOptions::name_table
+//!
+//! ```
+//! use gvas::name_table::NameTable;
+//! use std::sync::Arc;
+//!
+//! let table = NameTable::new();
+//! let first = table.intern("/Game/Items/Sword");
+//! let second = table.intern("/Game/Items/Sword");
+//! assert!(Arc::ptr_eq(&first, &second));
+//! assert_eq!(table.len(), 1);
+//! ```
+
+use std::{
+    collections::HashSet,
+    fmt::Debug,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+/// A table of interned names, see the [module documentation](self).
+///
+/// Clones share the same table, so a table can be kept to inspect it after reading a file, or
+/// reused to share names between files.
+#[derive(Clone, Default)]
+pub struct NameTable(Arc<Mutex<HashSet<Arc<str>>>>);
+
+impl NameTable {
+    /// Creates a new, empty `NameTable` instance.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the shared copy of `name`, adding it to the table if it isn't there yet.
+    pub fn intern(&self, name: &str) -> Arc<str> {
+        let mut names = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        match names.get(name) {
+            Some(interned) => Arc::clone(interned),
+            None => {
+                let interned = Arc::<str>::from(name);
+                names.insert(Arc::clone(&interned));
+                interned
+            }
+        }
+    }
+
+    /// The number of distinct names in the table.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    /// Returns true if the table holds no names.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Debug for NameTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NameTable")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl PartialEq for NameTable {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for NameTable {}
@@ -1,5 +1,10 @@
 use num_enum::IntoPrimitive;
 
+use crate::ord_ext::OrdExt;
+
+/// Newest UE4 package file version that can be read.
+const NEWEST_SUPPORTED_UE4_VERSION: u32 = 0x20D;
+
 /// UE5 object versions.
 #[derive(IntoPrimitive)]
 #[repr(u32)]
@@ -45,3 +50,68 @@ pub enum EUnrealEngineObjectUE5Version {
     /// Added property tag complete type name and serialization type
     PropertyTagCompleteTypeName,
 }
+
+/// UE4 object versions that change the layout of property tags.
+#[derive(IntoPrimitive)]
+#[repr(u32)]
+pub enum EUnrealEngineObjectUE4Version {
+    /// The oldest package version that can be read
+    OldestLoadablePackage = 214,
+
+    /// `BoolProperty` array elements are stored in one byte instead of four, since UE 4.0
+    ByteBoolArrays = 342,
+
+    /// Struct GUIDs are stored in `StructProperty` tags
+    StructGuidInPropertyTag = 441,
+
+    /// Property GUIDs are stored in property tags, preceded by a flag
+    PropertyGuidInPropertyTag = 503,
+
+    /// The last UE4 version, also used by UE5 packages
+    CorrectLicenseeFlag = 522,
+}
+
+/// Package file versions of a save, see [`GvasHeader`].
+///
+/// [`GvasHeader`]: crate::GvasHeader
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PackageVersion {
+    /// UE4 package file version.
+    pub ue4: u32,
+    /// UE5 package file version, zero for UE4 saves.
+    pub ue5: u32,
+}
+
+impl PackageVersion {
+    /// Returns `true` if property tags use complete type names, which replaced the struct GUID,
+    /// the bool value byte and the property GUID flag of older tags with a flags byte.
+    #[inline]
+    pub fn has_complete_type_names(&self) -> bool {
+        self.ue5 >= EUnrealEngineObjectUE5Version::PropertyTagCompleteTypeName as u32
+    }
+
+    /// Returns why a UE4 package file version can't be read, `None` if it can.
+    pub(crate) fn unsupported_ue4(version: u32) -> Option<String> {
+        let oldest = EUnrealEngineObjectUE4Version::OldestLoadablePackage as u32;
+        (!version.between(oldest, NEWEST_SUPPORTED_UE4_VERSION))
+            .then(|| format!("Package file version {version} not supported"))
+    }
+
+    /// Returns why a UE5 package file version can't be read, `None` if it can.
+    pub(crate) fn unsupported_ue5(version: u32) -> Option<String> {
+        let oldest = EUnrealEngineObjectUE5Version::InitialVersion as u32;
+        let newest = EUnrealEngineObjectUE5Version::DataResources as u32;
+        (!version.between(oldest, newest))
+            .then(|| format!("UE5 Package file version {version} is not supported"))
+    }
+}
+
+impl Default for PackageVersion {
+    #[inline]
+    fn default() -> Self {
+        PackageVersion {
+            ue4: EUnrealEngineObjectUE4Version::CorrectLicenseeFlag as u32,
+            ue5: 0,
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! Options for reading and writing save files
+
+use std::{fmt::Debug, ops::RangeInclusive, sync::Arc};
+
+use crate::{
+    container::CustomContainer, cursor_ext::DEFAULT_MAX_STRING_LEN,
+    game_version::PalworldCompressionType, name_table::NameTable, parse_report::ReportCollector,
+};
+
+/// How duplicate keys in a `MapProperty` are handled while reading.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum DuplicateKeyPolicy {
+    /// Keep the first entry in the map and store the duplicates separately, so they are written
+    /// back out.
+    #[default]
+    Tolerant,
+    /// Fail with [`DeserializeError::DuplicateMapKey`].
+    ///
+    /// [`DeserializeError::DuplicateMapKey`]: crate::error::DeserializeError::DuplicateMapKey
+    Strict,
+}
+
+/// Which [hint presets](crate::hints::presets) are added while reading.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum PresetHints {
+    /// Add the preset of the game version, like [`GameVersion::DeepRockGalactic`], and with the
+    /// `bundled-hints` feature the preset for the save game class of the file.
+    ///
+    /// [`GameVersion::DeepRockGalactic`]: crate::game_version::GameVersion::DeepRockGalactic
+    #[default]
+    Default,
+    /// Add the preset of the game version and the preset for the save game class of the file.
+    Enabled,
+    /// Don't add any presets.
+    Disabled,
+}
+
+/// Limit on the number of entries read from a multicast delegate list.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum DelegateLimit {
+    /// Read every entry.
+    #[default]
+    Unlimited,
+    /// Fail with [`DeserializeError::TooManyDelegates`] when a list has more entries.
+    ///
+    /// [`DeserializeError::TooManyDelegates`]: crate::error::DeserializeError::TooManyDelegates
+    Fail(u32),
+    /// Keep the first entries and skip the rest, which are lost when the file is written.
+    Truncate(u32),
+}
+
+/// Looks up the display string of a string table entry from exported localization data.
+///
+/// The callback receives the table id and the key of the entry.
+///
+/// ```
+/// use gvas::options::{ReadOptions, StringTableResolver};
+///
+/// let read_options = ReadOptions {
+///     string_table_resolver: Some(StringTableResolver::new(|table_id, key| {
+///         (table_id == "/Game/Items" && key == "Sword").then(|| String::from("Iron Sword"))
+///     })),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone)]
+pub struct StringTableResolver(Arc<ResolveFn>);
+
+type ResolveFn = dyn Fn(&str, &str) -> Option<String> + Send + Sync;
+
+impl StringTableResolver {
+    /// Creates a new `StringTableResolver` instance.
+    #[inline]
+    pub fn new<F>(resolve: F) -> Self
+    where
+        F: Fn(&str, &str) -> Option<String> + Send + Sync + 'static,
+    {
+        StringTableResolver(Arc::new(resolve))
+    }
+
+    /// Look up the display string of an entry.
+    #[inline]
+    pub fn resolve(&self, table_id: &str, key: &str) -> Option<String> {
+        (self.0)(table_id, key)
+    }
+}
+
+impl Debug for StringTableResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StringTableResolver")
+    }
+}
+
+impl PartialEq for StringTableResolver {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for StringTableResolver {}
+
+/// Encoding of strings stored as single bytes.
+///
+/// Unreal Engine stores strings made of ANSI characters as single bytes and other strings as
+/// UTF-16. Saves written by older versions of the engine may contain single-byte strings with
+/// characters outside of ASCII, in the codepage of the machine which wrote them.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Codepage {
+    /// Single-byte strings must be valid UTF-8, reading fails with
+    /// [`DeserializeError::FromUtf8Error`] otherwise. Strings with characters outside of ASCII are
+    /// written as UTF-16.
+    ///
+    /// [`DeserializeError::FromUtf8Error`]: crate::error::DeserializeError::FromUtf8Error
+    #[default]
+    Utf8,
+    /// Single-byte strings are decoded as Windows-1252. Strings which only use characters of
+    /// Windows-1252 are written back as single bytes, others as UTF-16.
+    Windows1252,
+}
+
+/// Options used by [`GvasFile::read_with_options`].
+///
+/// [`GvasFile::read_with_options`]: crate::GvasFile::read_with_options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReadOptions {
+    /// Handling of duplicate map keys.
+    pub duplicate_map_keys: DuplicateKeyPolicy,
+    /// Continue reading when the decompressed length doesn't match the declared length, instead
+    /// of failing with [`DeserializeError::DecompressedLengthMismatch`].
+    ///
+    /// [`DeserializeError::DecompressedLengthMismatch`]: crate::error::DeserializeError::DecompressedLengthMismatch
+    pub tolerate_length_mismatch: bool,
+    /// Check that every element of a struct array has the same size, failing with
+    /// [`DeserializeError::MisalignedArrayElement`] at the first element that doesn't.
+    ///
+    /// Only enable this for arrays of fixed-size structs.
+    ///
+    /// [`DeserializeError::MisalignedArrayElement`]: crate::error::DeserializeError::MisalignedArrayElement
+    pub strict_struct_arrays: bool,
+    /// Store arrays of custom structs with identical fields as
+    /// [`ArrayProperty::SharedStructs`], keeping a single copy of the field names.
+    ///
+    /// [`ArrayProperty::SharedStructs`]: crate::properties::array_property::ArrayProperty::SharedStructs
+    pub share_struct_schemas: bool,
+    /// Fills in [`FTextHistory::StringTableEntry`] display strings while reading.
+    ///
+    /// [`FTextHistory::StringTableEntry`]: crate::properties::text_property::FTextHistory::StringTableEntry
+    pub string_table_resolver: Option<StringTableResolver>,
+    /// Which [hint presets](crate::hints::presets) are added to the hints passed in.
+    pub preset_hints: PresetHints,
+    /// Limit on the number of entries of `MulticastInlineDelegateProperty` and
+    /// `MulticastSparseDelegateProperty` lists.
+    pub delegate_limit: DelegateLimit,
+    /// Save game file versions accepted in the header, [`SaveGameVersion::SUPPORTED`] if `None`.
+    ///
+    /// Versions above [`SaveGameVersion::PackageFileSummaryVersionChange`] are read like it,
+    /// for engine forks that bumped the version number without changing the header. Versions
+    /// below [`SaveGameVersion::AddedCustomVersions`] are never accepted.
+    ///
+    /// [`SaveGameVersion::SUPPORTED`]: crate::savegame_version::SaveGameVersion::SUPPORTED
+    /// [`SaveGameVersion::PackageFileSummaryVersionChange`]: crate::savegame_version::SaveGameVersion::PackageFileSummaryVersionChange
+    /// [`SaveGameVersion::AddedCustomVersions`]: crate::savegame_version::SaveGameVersion::AddedCustomVersions
+    pub save_game_versions: Option<RangeInclusive<u32>>,
+    /// Decoding of single-byte string, name and text values.
+    pub codepage: Codepage,
+    /// Maximum length in characters of string and name values, including the terminator,
+    /// [`DEFAULT_MAX_STRING_LEN`] if `None`.
+    ///
+    /// Longer strings fail with [`DeserializeError::StringTooLong`] before anything is
+    /// allocated, so a corrupted length can't cause a huge allocation.
+    ///
+    /// [`DEFAULT_MAX_STRING_LEN`]: crate::cursor_ext::DEFAULT_MAX_STRING_LEN
+    /// [`DeserializeError::StringTooLong`]: crate::error::DeserializeError::StringTooLong
+    pub max_string_len: Option<u32>,
+    /// Keep data that engine forks store between the custom versions and the save game class
+    /// name in the header, instead of failing to read the class name.
+    ///
+    /// The data is stored in [`GvasFile::custom_version_extra`] and written back unchanged.
+    ///
+    /// [`GvasFile::custom_version_extra`]: crate::GvasFile::custom_version_extra
+    pub preserve_custom_version_extra: bool,
+    /// Collects the data read without being understood, see [`parse_report`].
+    ///
+    /// [`parse_report`]: crate::parse_report
+    pub report: Option<ReportCollector>,
+    /// Only parse the properties at these `.` separated paths, like `worldSaveData.Items`, see
+    /// [`GvasFile::read_properties_filtered`].
+    ///
+    /// The other root properties, and the other fields of the structs leading to a path, are read
+    /// using the length in their tag without parsing their bodies. They are kept as an
+    /// [`UnknownProperty`] holding their raw tag and body, so the file is still written back
+    /// unchanged, while a [`PropertyIter`] leaves out the other root properties. Everything inside
+    /// a selected property is read, and so is everything reached through other properties, like
+    /// the elements of an array.
+    ///
+    /// [`GvasFile::read_properties_filtered`]: crate::GvasFile::read_properties_filtered
+    /// [`UnknownProperty`]: crate::properties::unknown_property::UnknownProperty
+    /// [`PropertyIter`]: crate::property_iter::PropertyIter
+    pub root_properties: Option<Vec<String>>,
+    /// Keep properties of unknown types as an [`UnknownProperty`] holding their raw tag and body,
+    /// which is written back byte for byte.
+    ///
+    /// Without this, unknown properties are written back with a generated tag.
+    ///
+    /// [`UnknownProperty`]: crate::properties::unknown_property::UnknownProperty
+    pub raw_passthrough: bool,
+    /// Keep reading when a root property fails to parse, storing it as an [`UnknownProperty`]
+    /// holding its raw tag and body.
+    ///
+    /// The errors are collected by the [`report`](ReadOptions::report) collector, see
+    /// [`ReportCollector::take_errors`] and [`GvasFile::read_tolerant`]. Root properties are read
+    /// into memory by the length in their tag before parsing, so properties whose length can't
+    /// be read still fail.
+    ///
+    /// [`UnknownProperty`]: crate::properties::unknown_property::UnknownProperty
+    /// [`GvasFile::read_tolerant`]: crate::GvasFile::read_tolerant
+    pub recover_errors: bool,
+    /// Share equal names between properties, see [`name_table`].
+    ///
+    /// [`name_table`]: crate::name_table
+    pub name_table: Option<NameTable>,
+    /// Unwrap the save with this container instead of the container of the game version, see
+    /// [`ContainerFormat`].
+    ///
+    /// [`ContainerFormat`]: crate::container::ContainerFormat
+    pub container: Option<CustomContainer>,
+}
+
+impl ReadOptions {
+    /// Options which fully parse only the properties at the paths in `names`.
+    ///
+    /// The other properties are skipped using the length in their tag and kept as an
+    /// [`UnknownProperty`] holding their raw tag and body, so targeted reads of huge saves are
+    /// fast and the file is still written back unchanged. Sets [`root_properties`].
+    ///
+    /// ```
+    /// use gvas::{game_version::GameVersion, hints::Hints, options::ReadOptions, GvasFile};
+    /// use gvas::properties::Property;
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("resources/test/Slot1.sav")?;
+    /// let read_options = ReadOptions::only_properties(&["str_property"]);
+    /// let gvas_file =
+    ///     GvasFile::read_with_options(&mut file, GameVersion::Default, Hints::new(), &read_options)?;
+    /// assert!(matches!(
+    ///     gvas_file.properties["str_property"],
+    ///     Property::StrProperty(_)
+    /// ));
+    /// assert!(matches!(
+    ///     gvas_file.properties["u8_test"],
+    ///     Property::UnknownProperty(_)
+    /// ));
+    /// # Ok::<(), gvas::error::Error>(())
+    /// ```
+    ///
+    /// [`UnknownProperty`]: crate::properties::unknown_property::UnknownProperty
+    /// [`root_properties`]: ReadOptions::root_properties
+    pub fn only_properties(names: &[&str]) -> Self {
+        ReadOptions {
+            root_properties: Some(names.iter().map(|name| name.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    /// The maximum string length of [`ReadOptions::max_string_len`], or the default limit.
+    #[inline]
+    pub(crate) fn string_len_limit(&self) -> u32 {
+        self.max_string_len.unwrap_or(DEFAULT_MAX_STRING_LEN)
+    }
+}
+
+/// Padding written after the `None` terminator of the root property list.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum TrailingPadding {
+    /// Write the padding that was captured when the file was read.
+    #[default]
+    Preserve,
+    /// Don't write any padding.
+    Omit,
+    /// Write the given value.
+    Value(i32),
+}
+
+/// Handling of vector, rotator and quaternion structs whose precision doesn't match the large
+/// world coordinates setting of the file while writing.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum PrecisionMismatch {
+    /// Fail with [`SerializeError::InvalidValue`].
+    ///
+    /// [`SerializeError::InvalidValue`]: crate::error::SerializeError::InvalidValue
+    #[default]
+    Error,
+    /// Write the value with the precision of the file, e.g. a `VectorF` as a `VectorD` when large
+    /// world coordinates are enabled.
+    Convert,
+}
+
+/// Options used by [`GvasFile::write_with_options`] and [`GvasFile::save_with_options`].
+///
+/// [`GvasFile::write_with_options`]: crate::GvasFile::write_with_options
+/// [`GvasFile::save_with_options`]: crate::GvasFile::save_with_options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Padding written after the `None` terminator.
+    pub trailing_padding: TrailingPadding,
+    /// Keep a `.bak` copy of the file replaced by [`GvasFile::save_with_options`].
+    ///
+    /// [`GvasFile::save_with_options`]: crate::GvasFile::save_with_options
+    pub backup: bool,
+    /// Handling of structs with the wrong precision.
+    pub precision_mismatch: PrecisionMismatch,
+    /// Encoding of string, name and text values, use the codepage the file was read with to
+    /// write legacy strings back unchanged.
+    pub codepage: Codepage,
+    /// Write the entries of every `MapProperty` sorted by key, instead of in the order they are
+    /// stored, see [`MapProperty::sorted_by_key`].
+    ///
+    /// [`MapProperty::sorted_by_key`]: crate::properties::map_property::MapProperty::sorted_by_key
+    pub sort_maps: bool,
+    /// Zlib compression level of Palworld saves, from 0 for no compression to 9 for the
+    /// smallest output, [`DEFAULT_COMPRESSION_LEVEL`] if `None`.
+    ///
+    /// Lower levels write faster, higher levels write smaller saves.
+    ///
+    /// [`DEFAULT_COMPRESSION_LEVEL`]: crate::container::palworld::DEFAULT_COMPRESSION_LEVEL
+    pub palworld_compression_level: Option<u32>,
+    /// Compression type of Palworld saves, the type the file was read with if `None`.
+    pub palworld_compression: Option<PalworldCompressionType>,
+    /// Capacity in bytes reserved up front for the compressed data of Palworld saves, grown as
+    /// needed if `None`.
+    ///
+    /// Reserving about the size of the compressed save avoids reallocating while it's written.
+    pub palworld_buffer_size: Option<usize>,
+}
@@ -0,0 +1,291 @@
+//! Pal character containers in a Palworld `Level.sav`.
+//!
+//! `worldSaveData.CharacterContainerSaveData` maps the ID of a container, like a Palbox or a
+//! player's party, to its slots. Each slot stores the character it holds in `RawData`: the
+//! player UID, the instance ID of the character and a permission tribe ID. Older saves also
+//! store the IDs in an `IndividualId` struct, which is kept in sync.
+//!
+//! Emptying a slot doesn't remove the character from `CharacterSaveParameterMap`.
+//!
+//! ```no_run
+//! use gvas::{game_version::GameVersion, hints::Hints, options::ReadOptions, palworld, GvasFile};
+//!
+//! let mut file = GvasFile::open(
+//!     "Level.sav",
+//!     GameVersion::Palworld,
+//!     Hints::new(),
+//!     &ReadOptions::default(),
+//! )?;
+//! for container in palworld::container_ids(&file) {
+//!     let occupied = palworld::slots(&file, container)?
+//!         .into_iter()
+//!         .filter(|slot| !slot.is_empty())
+//!         .count();
+//!     println!("{container}: {occupied} pals");
+//! }
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+
+use crate::{
+    error::{Error, SerializeError},
+    properties::{
+        array_property::ArrayProperty, map_property::MapProperty,
+        struct_property::StructPropertyValue, Property,
+    },
+    types::{map::HashableIndexMap, Guid},
+    GvasFile,
+};
+
+/// Path of the character container map.
+pub const CHARACTER_CONTAINERS_PATH: &str = "worldSaveData.CharacterContainerSaveData";
+
+/// Length of the `RawData` of a slot.
+const RAW_DATA_LEN: usize = 33;
+
+/// The character held by a container slot.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CharacterSlot {
+    /// Index of the slot in its container.
+    pub index: usize,
+    /// UID of the player owning the character.
+    pub player_uid: Guid,
+    /// Instance ID of the character, zero for an empty slot.
+    pub instance_id: Guid,
+    /// Tribe ID the slot is restricted to.
+    pub permission_tribe_id: u8,
+}
+
+impl CharacterSlot {
+    /// Returns `true` if the slot doesn't hold a character.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.instance_id.is_zero()
+    }
+}
+
+/// Returns the IDs of all character containers, in save order.
+///
+/// Files without character containers return an empty list.
+pub fn container_ids(file: &GvasFile) -> Vec<Guid> {
+    containers(file)
+        .map(|containers| containers.keys().filter_map(container_id).collect())
+        .unwrap_or_default()
+}
+
+/// Returns the slots of `container`.
+///
+/// # Errors
+///
+/// If the container doesn't exist this function returns [`Error`]
+pub fn slots(file: &GvasFile, container: Guid) -> Result<Vec<CharacterSlot>, Error> {
+    let slots = containers(file)
+        .and_then(|containers| {
+            containers
+                .iter()
+                .find(|(key, _)| container_id(key) == Some(container))
+        })
+        .and_then(|(_, value)| value.get_struct_value()?.get_custom_struct()?.get("Slots"))
+        .and_then(|slots| match slots.first()? {
+            Property::ArrayProperty(ArrayProperty::Structs { structs, .. }) => Some(structs),
+            _ => None,
+        })
+        .ok_or_else(|| SerializeError::PropertyNotFound(slots_path(container).into()))?;
+    Ok(slots
+        .iter()
+        .enumerate()
+        .map(|(position, slot)| read_slot(position, slot))
+        .collect())
+}
+
+/// Moves the character in slot `from` of `container` to slot `to`.
+///
+/// A character already in slot `to` is moved to slot `from`.
+///
+/// # Errors
+///
+/// If the container or either slot doesn't exist this function returns [`Error`]
+pub fn move_slot(
+    file: &mut GvasFile,
+    container: Guid,
+    from: usize,
+    to: usize,
+) -> Result<(), Error> {
+    let slots = slots_mut(file, container)?;
+    let position = |index| {
+        slots
+            .iter()
+            .enumerate()
+            .position(|(position, slot)| slot_index(position, slot) == index)
+            .ok_or_else(|| {
+                SerializeError::PropertyNotFound(
+                    format!("{}[{index}]", slots_path(container)).into(),
+                )
+            })
+    };
+    let (from, to) = (position(from)?, position(to)?);
+
+    slots.swap(from, to);
+    // The slots swapped their contents but keep their indices
+    if let (Some(from_index), Some(to_index)) =
+        (slot_index_field(&slots[from]), slot_index_field(&slots[to]))
+    {
+        set_slot_index(&mut slots[from], to_index);
+        set_slot_index(&mut slots[to], from_index);
+    }
+    Ok(())
+}
+
+/// Empties slot `index` of `container`, returning the character it held.
+///
+/// # Errors
+///
+/// If the container or the slot doesn't exist this function returns [`Error`]
+pub fn delete_slot(
+    file: &mut GvasFile,
+    container: Guid,
+    index: usize,
+) -> Result<CharacterSlot, Error> {
+    let slots = slots_mut(file, container)?;
+    let Some((position, slot)) = slots
+        .iter_mut()
+        .enumerate()
+        .find(|(position, slot)| slot_index(*position, slot) == index)
+    else {
+        Err(SerializeError::PropertyNotFound(
+            format!("{}[{index}]", slots_path(container)).into(),
+        ))?
+    };
+
+    let previous = read_slot(position, slot);
+    let Some(fields) = slot.get_custom_struct_mut() else {
+        return Ok(previous);
+    };
+    if let Some(bytes) = fields
+        .get_mut("RawData")
+        .and_then(|raw| raw.first_mut()?.get_array_mut()?.bytes_mut())
+    {
+        bytes.fill(0);
+    }
+    if let Some(id) = individual_id_mut(fields) {
+        for name in ["PlayerUId", "InstanceId"] {
+            if let Some(guid) = id
+                .get_mut(name)
+                .and_then(|guid| guid.first_mut()?.get_struct_mut()?.value.get_guid_mut())
+            {
+                *guid = Guid::default();
+            }
+        }
+    }
+    Ok(previous)
+}
+
+fn containers(file: &GvasFile) -> Option<&HashableIndexMap<Property, Property>> {
+    match file.get_property_by_path(CHARACTER_CONTAINERS_PATH)? {
+        Property::MapProperty(MapProperty::Properties { value, .. }) => Some(value),
+        _ => None,
+    }
+}
+
+fn slots_mut(file: &mut GvasFile, container: Guid) -> Result<&mut Vec<StructPropertyValue>, Error> {
+    let slots = match file.get_property_by_path_mut(CHARACTER_CONTAINERS_PATH) {
+        Some(Property::MapProperty(MapProperty::Properties { value, .. })) => value
+            .iter_mut()
+            .find(|(key, _)| container_id(key) == Some(container))
+            .and_then(|(_, value)| {
+                value
+                    .get_struct_value_mut()?
+                    .get_custom_struct_mut()?
+                    .get_mut("Slots")
+            })
+            .and_then(|slots| match slots.first_mut()? {
+                Property::ArrayProperty(ArrayProperty::Structs { structs, .. }) => Some(structs),
+                _ => None,
+            }),
+        _ => None,
+    };
+    slots.ok_or_else(|| SerializeError::PropertyNotFound(slots_path(container).into()).into())
+}
+
+fn slots_path(container: Guid) -> String {
+    format!("{CHARACTER_CONTAINERS_PATH}[{container}].Slots")
+}
+
+fn container_id(key: &Property) -> Option<Guid> {
+    key.get_struct_value()?
+        .get_custom_struct()?
+        .get("ID")?
+        .first()?
+        .get_guid()
+}
+
+fn slot_index_field(slot: &StructPropertyValue) -> Option<i32> {
+    Some(
+        slot.get_custom_struct()?
+            .get("SlotIndex")?
+            .first()?
+            .get_int()?
+            .value,
+    )
+}
+
+fn set_slot_index(slot: &mut StructPropertyValue, index: i32) {
+    if let Some(property) = slot
+        .get_custom_struct_mut()
+        .and_then(|fields| fields.get_mut("SlotIndex")?.first_mut()?.get_int_mut())
+    {
+        property.value = index;
+    }
+}
+
+/// Returns the `SlotIndex` of a slot, or its position in older saves without one.
+fn slot_index(position: usize, slot: &StructPropertyValue) -> usize {
+    slot_index_field(slot)
+        .and_then(|index| usize::try_from(index).ok())
+        .unwrap_or(position)
+}
+
+fn individual_id_mut(
+    fields: &mut HashableIndexMap<String, Vec<Property>>,
+) -> Option<&mut HashableIndexMap<String, Vec<Property>>> {
+    fields
+        .get_mut("IndividualId")?
+        .first_mut()?
+        .get_struct_mut()?
+        .value
+        .get_custom_struct_mut()
+}
+
+fn read_slot(position: usize, slot: &StructPropertyValue) -> CharacterSlot {
+    let mut character = CharacterSlot {
+        index: slot_index(position, slot),
+        ..Default::default()
+    };
+    let Some(fields) = slot.get_custom_struct() else {
+        return character;
+    };
+
+    if let Some(Property::ArrayProperty(ArrayProperty::Bytes { bytes })) =
+        fields.get("RawData").and_then(|raw| raw.first())
+    {
+        if bytes.len() >= RAW_DATA_LEN {
+            character.player_uid.0.copy_from_slice(&bytes[..16]);
+            character.instance_id.0.copy_from_slice(&bytes[16..32]);
+            character.permission_tribe_id = bytes[32];
+        }
+    }
+
+    // Older saves keep the IDs outside of `RawData`
+    let individual_id = fields
+        .get("IndividualId")
+        .and_then(|id| id.first()?.get_struct()?.value.get_custom_struct());
+    if let Some(id) = individual_id {
+        let guid = |name| id.get(name)?.first()?.get_guid();
+        if let Some(player_uid) = guid("PlayerUId").filter(|guid| !guid.is_zero()) {
+            character.player_uid = player_uid;
+        }
+        if let Some(instance_id) = guid("InstanceId").filter(|guid| !guid.is_zero()) {
+            character.instance_id = instance_id;
+        }
+    }
+    character
+}
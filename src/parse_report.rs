@@ -0,0 +1,197 @@
+//! Reports of data read without being understood
+//!
+//! Reading a save can succeed without interpreting every byte: properties of unknown types are
+//! kept as raw bytes, duplicate map keys are stored apart from their map, and delegates beyond
+//! [`DelegateLimit::Truncate`] are dropped. [`GvasFile::read_with_report`] returns a
+//! [`ParseReport`] listing each of them, so you can tell what would be lost by editing and
+//! writing the save.
+//!
+//! ```
+//! use gvas::{game_version::GameVersion, hints::Hints, options::ReadOptions, GvasFile};
+//! use std::fs::File;
+//!
+//! let mut file = File::open("resources/test/Slot1.sav")?;
+//! let (gvas_file, report) = GvasFile::read_with_report(
+//!     &mut file,
+//!     GameVersion::Default,
+//!     Hints::new(),
+//!     &ReadOptions::default(),
+//! )?;
+//!
+//! for entry in &report.entries {
+//!     println!("{} at {:#x}: {:?}", entry.path, entry.offset, entry.reason);
+//! }
+//! assert_eq!(report.lost().count(), 0);
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+//!
+//! Offsets are positions in the GVAS data, after the container was decompressed.
+//!
+//! [`DelegateLimit::Truncate`]: crate::options::DelegateLimit::Truncate
+//! [`GvasFile::read_with_report`]: crate::GvasFile::read_with_report
+//! [`ParseReport`]: crate::parse_report::ParseReport
+
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::error::Error;
+
+/// Why data was reported.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ReportReason {
+    /// A property of this unknown type was kept as raw bytes, it is written back unchanged.
+    UnknownProperty(String),
+    /// A map entry repeating a key was stored with the duplicates of its map, it is written back
+    /// unchanged.
+    DuplicateMapKey,
+    /// This many delegates were dropped from a delegate list, they are lost when the save is
+    /// written.
+    TruncatedDelegates(u32),
+    /// Data between the custom versions and the save game class name was kept in the header, it
+    /// is written back unchanged.
+    CustomVersionExtra,
+    /// A root property left out by [`ReadOptions::root_properties`] was skipped by a
+    /// [`PropertyIter`], it isn't returned.
+    ///
+    /// [`ReadOptions::root_properties`]: crate::options::ReadOptions::root_properties
+    /// [`PropertyIter`]: crate::property_iter::PropertyIter
+    SkippedProperty,
+    /// A property left out by [`ReadOptions::root_properties`] was kept as raw bytes, it is
+    /// written back unchanged.
+    ///
+    /// [`ReadOptions::root_properties`]: crate::options::ReadOptions::root_properties
+    RawProperty,
+    /// A root property that failed to parse was kept as raw bytes, it is written back
+    /// unchanged. See [`ReadOptions::recover_errors`].
+    ///
+    /// [`ReadOptions::recover_errors`]: crate::options::ReadOptions::recover_errors
+    FailedProperty,
+    /// Data before the GVAS header was kept as the prefix, it is written back unchanged.
+    Prefix,
+    /// The decompressed data doesn't have the declared length, the written container declares the
+    /// length of the written data instead.
+    LengthMismatch {
+        /// The length declared by the container.
+        declared: u64,
+    },
+}
+
+impl ReportReason {
+    /// Returns true if the reported data is lost, or changed, when the save is written.
+    #[inline]
+    pub fn is_lost(&self) -> bool {
+        matches!(
+            self,
+            ReportReason::TruncatedDelegates(_)
+                | ReportReason::SkippedProperty
+                | ReportReason::LengthMismatch { .. }
+        )
+    }
+}
+
+/// Data read without being understood.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReportEntry {
+    /// Path of the property holding the data, empty for data outside of the properties.
+    pub path: String,
+    /// Position of the data.
+    pub offset: u64,
+    /// Length of the data in bytes.
+    pub length: u64,
+    /// Why the data was reported.
+    pub reason: ReportReason,
+}
+
+/// The data reported while reading a save, see the [module documentation](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ParseReport {
+    /// Reported data, in the order it was read.
+    pub entries: Vec<ReportEntry>,
+}
+
+impl ParseReport {
+    /// Entries whose data is lost, or changed, when the save is written.
+    pub fn lost(&self) -> impl Iterator<Item = &ReportEntry> {
+        self.entries.iter().filter(|entry| entry.reason.is_lost())
+    }
+
+    /// Returns true if nothing was reported.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Collects the entries of a [`ParseReport`] while reading, see [`ReadOptions::report`].
+///
+/// Also collects the errors of root properties kept as raw bytes, see
+/// [`ReadOptions::recover_errors`].
+///
+/// Clones share their entries, keep one to [`take`](ReportCollector::take) the report after
+/// reading.
+///
+/// [`ReadOptions::report`]: crate::options::ReadOptions::report
+/// [`ReadOptions::recover_errors`]: crate::options::ReadOptions::recover_errors
+#[derive(Clone, Default)]
+pub struct ReportCollector(Arc<Mutex<Collected>>);
+
+#[derive(Default)]
+struct Collected {
+    entries: Vec<ReportEntry>,
+    errors: Vec<(String, Error)>,
+}
+
+impl ReportCollector {
+    /// Creates a new `ReportCollector` instance.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes the entries collected so far.
+    pub fn take(&self) -> ParseReport {
+        let mut collected = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        ParseReport {
+            entries: std::mem::take(&mut collected.entries),
+        }
+    }
+
+    /// Takes the names of the properties kept as raw bytes and their errors collected so far.
+    pub fn take_errors(&self) -> Vec<(String, Error)> {
+        let mut collected = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        std::mem::take(&mut collected.errors)
+    }
+
+    pub(crate) fn push(&self, entry: ReportEntry) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entries
+            .push(entry);
+    }
+
+    pub(crate) fn push_error(&self, path: String, error: Error) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .errors
+            .push((path, error));
+    }
+}
+
+impl Debug for ReportCollector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ReportCollector")
+    }
+}
+
+impl PartialEq for ReportCollector {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ReportCollector {}
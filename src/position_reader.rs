@@ -0,0 +1,51 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// Tracks the position of a reader which can't seek, such as a decompressor.
+///
+/// Only querying the current position is supported, which is all the parser needs.
+pub(crate) struct PositionReader<R> {
+    inner: R,
+    position: u64,
+}
+
+impl<R> PositionReader<R> {
+    /// Create new instance of PositionReader reporting positions from `position`
+    pub(crate) fn with_position(inner: R, position: u64) -> Self {
+        Self { inner, position }
+    }
+
+    /// Unwraps this `PositionReader`, returning the inner reader
+    pub(crate) fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Position of the next byte to read
+    pub(crate) fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+impl<R: Read> Read for PositionReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R> Seek for PositionReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match pos {
+            SeekFrom::Current(0) => Ok(self.position),
+            SeekFrom::Start(position) if position == self.position => Ok(self.position),
+            _ => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Seeking is not supported on this reader",
+            )),
+        }
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.position)
+    }
+}
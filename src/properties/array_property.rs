@@ -1,6 +1,7 @@
 use std::{
     fmt::Debug,
     io::{Cursor, Read, Seek, Write},
+    sync::Arc,
 };
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
@@ -9,7 +10,8 @@ use ordered_float::OrderedFloat;
 use crate::{
     cursor_ext::{ReadExt, WriteExt},
     error::{DeserializeError, Error, SerializeError},
-    types::Guid,
+    object_version::EUnrealEngineObjectUE4Version,
+    types::{map::HashableIndexMap, Guid},
 };
 
 use super::{
@@ -19,7 +21,7 @@ use super::{
     name_property::NameProperty,
     str_property::StrProperty,
     struct_property::{StructProperty, StructPropertyValue},
-    Property, PropertyOptions, PropertyTrait,
+    EmptyBody, Property, PropertyOptions, PropertyTrait,
 };
 
 #[cfg(feature = "serde")]
@@ -36,11 +38,17 @@ pub enum ArrayProperty {
         /// An array of values.
         bools: Vec<bool>,
     },
+    /// An array of BoolProperty values stored in four bytes each, as written by package versions
+    /// before [`EUnrealEngineObjectUE4Version::ByteBoolArrays`].
+    WideBools {
+        /// An array of values.
+        wide_bools: Vec<bool>,
+    },
     /// An array of ByteProperty values.
     Bytes {
-        /// An array of values.
+        /// An array of values, shared between clones until one of them is edited.
         #[cfg_attr(feature = "serde", serde_as(as = "Hex"))]
-        bytes: Vec<u8>,
+        bytes: Arc<[u8]>,
     },
     /// An array of EnumProperty values.
     Enums {
@@ -74,12 +82,38 @@ pub enum ArrayProperty {
         /// Type name.
         type_name: String,
         /// The unique identifier of the property.
-        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Guid::is_zero"))]
-        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                default,
+                skip_serializing_if = "Guid::is_zero",
+                deserialize_with = "crate::types::deserialize_guid"
+            )
+        )]
         guid: Guid,
         /// An array of values.
         structs: Vec<StructPropertyValue>,
     },
+    /// An array of custom StructProperty values that all have the same fields.
+    ///
+    /// The field names are stored once for the whole array, see
+    /// [`ArrayProperty::share_struct_schema`]. Serialized the same way as `Structs`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_deserializing, serialize_with = "serialize_shared_structs")
+    )]
+    SharedStructs {
+        /// Field name.
+        field_name: String,
+        /// Type name.
+        type_name: String,
+        /// The unique identifier of the property.
+        guid: Guid,
+        /// Field name of each value in an element, in the order they are written.
+        fields: Vec<String>,
+        /// Values of each element, in the order of `fields`.
+        values: Vec<Vec<Property>>,
+    },
     /// Any other Property value
     Properties {
         /// The type of Property in `properties`.
@@ -133,9 +167,11 @@ impl ArrayProperty {
                     }) => Ok(*value),
                     _ => Err(()),
                 })
-                .collect::<Result<_, _>>()
+                .collect::<Result<Vec<_>, _>>()
             {
-                Ok(bytes) => Ok(ArrayProperty::Bytes { bytes }),
+                Ok(bytes) => Ok(ArrayProperty::Bytes {
+                    bytes: bytes.into(),
+                }),
                 Err(()) => Ok(ArrayProperty::Properties {
                     property_type,
                     properties,
@@ -196,7 +232,8 @@ impl ArrayProperty {
                     Property::NameProperty(NameProperty {
                         array_index: 0,
                         value,
-                    }) => Ok(value.to_owned()),
+                        empty_body: false,
+                    }) => Ok(value.as_deref().map(String::from)),
                     _ => Err(()),
                 })
                 .collect::<Result<_, _>>()
@@ -211,7 +248,10 @@ impl ArrayProperty {
             ("StrProperty", None) => match properties
                 .iter()
                 .map(|p| match p {
-                    Property::StrProperty(StrProperty { value }) => Ok(value.to_owned()),
+                    Property::StrProperty(StrProperty {
+                        value,
+                        empty_body: false,
+                    }) => Ok(value.to_owned()),
                     _ => Err(()),
                 })
                 .collect::<Result<_, _>>()
@@ -257,6 +297,7 @@ impl ArrayProperty {
     pub(crate) fn get_property_type(&self) -> Result<String, Error> {
         Ok(match self {
             ArrayProperty::Bools { bools: _ } => "BoolProperty".to_string(),
+            ArrayProperty::WideBools { wide_bools: _ } => "BoolProperty".to_string(),
             ArrayProperty::Bytes { bytes: _ } => "ByteProperty".to_string(),
             ArrayProperty::Enums { enums: _ } => "EnumProperty".to_string(),
             ArrayProperty::Floats { floats: _ } => "FloatProperty".to_string(),
@@ -269,6 +310,7 @@ impl ArrayProperty {
                 guid: _,
                 structs: _,
             } => "StructProperty".to_string(),
+            ArrayProperty::SharedStructs { .. } => "StructProperty".to_string(),
             ArrayProperty::Properties {
                 property_type,
                 properties: _,
@@ -276,6 +318,120 @@ impl ArrayProperty {
         })
     }
 
+    /// Returns the values of a `Bytes` array for editing, or `None` for other arrays.
+    ///
+    /// The values are copied first if they are shared with a clone of this array.
+    ///
+    /// ```
+    /// use gvas::properties::{array_property::ArrayProperty, Property};
+    ///
+    /// let Property::ArrayProperty(mut array) = Property::from(vec![1u8, 2, 3]) else {
+    ///     unreachable!()
+    /// };
+    /// let copy = array.clone();
+    /// array.bytes_mut().unwrap()[0] = 9;
+    ///
+    /// assert_eq!(array, ArrayProperty::Bytes { bytes: vec![9, 2, 3].into() });
+    /// assert_eq!(copy, ArrayProperty::Bytes { bytes: vec![1, 2, 3].into() });
+    /// ```
+    #[inline]
+    pub fn bytes_mut(&mut self) -> Option<&mut [u8]> {
+        match self {
+            ArrayProperty::Bytes { bytes } => Some(Arc::make_mut(bytes)),
+            _ => None,
+        }
+    }
+
+    /// Converts an array of custom structs with identical fields to `SharedStructs`, storing the
+    /// field names once.
+    ///
+    /// Other arrays are returned unchanged.
+    pub fn share_struct_schema(self) -> Self {
+        let ArrayProperty::Structs {
+            field_name,
+            type_name,
+            guid,
+            structs,
+        } = self
+        else {
+            return self;
+        };
+
+        fn schema(properties: &HashableIndexMap<String, Vec<Property>>) -> Vec<&String> {
+            properties
+                .iter()
+                .flat_map(|(key, values)| std::iter::repeat_n(key, values.len()))
+                .collect()
+        }
+
+        let custom_structs = structs
+            .iter()
+            .map(|value| match value {
+                StructPropertyValue::CustomStruct(properties) => Some(properties),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        let fields = match custom_structs.as_deref() {
+            Some([first, rest @ ..]) => {
+                let fields = schema(first);
+                rest.iter()
+                    .all(|value| schema(value) == fields)
+                    .then(|| fields.into_iter().cloned().collect())
+            }
+            _ => None,
+        };
+        let Some(fields) = fields else {
+            return ArrayProperty::Structs {
+                field_name,
+                type_name,
+                guid,
+                structs,
+            };
+        };
+
+        // Every struct is a custom struct at this point
+        let values = structs
+            .into_iter()
+            .filter_map(|value| match value {
+                StructPropertyValue::CustomStruct(properties) => {
+                    Some(properties.0.into_values().flatten().collect())
+                }
+                _ => None,
+            })
+            .collect();
+        ArrayProperty::SharedStructs {
+            field_name,
+            type_name,
+            guid,
+            fields,
+            values,
+        }
+    }
+
+    /// Converts `SharedStructs` back to `Structs`.
+    ///
+    /// Other arrays are returned unchanged.
+    pub fn unshare_struct_schema(self) -> Self {
+        match self {
+            ArrayProperty::SharedStructs {
+                field_name,
+                type_name,
+                guid,
+                fields,
+                values,
+            } => ArrayProperty::Structs {
+                field_name,
+                type_name,
+                guid,
+                structs: values
+                    .into_iter()
+                    .map(|values| expand_shared_struct(&fields, values))
+                    .collect(),
+            },
+            _ => self,
+        }
+    }
+
     #[inline]
     pub(crate) fn read<R: Read + Seek>(
         cursor: &mut R,
@@ -285,10 +441,10 @@ impl ArrayProperty {
         if include_header {
             Self::read_header(cursor, options)
         } else {
-            Err(DeserializeError::invalid_property(
+            Err(Error::unsupported(DeserializeError::invalid_property(
                 "ArrayProperty is not supported in arrays",
                 cursor,
-            ))?
+            )))?
         }
     }
 
@@ -302,29 +458,54 @@ impl ArrayProperty {
         property_type: String,
     ) -> Result<Self, Error> {
         let property_count = cursor.read_u32::<LittleEndian>()?;
+        if property_type == "BoolProperty"
+            && !options.supports_package_version(EUnrealEngineObjectUE4Version::ByteBoolArrays)
+        {
+            let wide_bools = (0..property_count)
+                .map(|_| cursor.read_b32())
+                .collect::<Result<_, _>>()?;
+            return Ok(ArrayProperty::WideBools { wide_bools });
+        }
         let mut properties: Vec<Property> = Vec::with_capacity(property_count as usize);
 
         let mut array_struct_info = None;
 
         match property_type.as_str() {
             "StructProperty" => {
-                let field_name = cursor.read_string()?;
+                let field_name = options.read_string(cursor)?;
 
-                let property_type = cursor.read_string()?;
-                assert_eq!(property_type, "StructProperty");
+                let property_type = options.read_string(cursor)?;
+                if property_type != "StructProperty" {
+                    Err(DeserializeError::invalid_property(
+                        format!("Expected StructProperty array elements, got {property_type}"),
+                        cursor,
+                    ))?
+                }
                 let properties_size = cursor.read_u64::<LittleEndian>()?;
 
-                let struct_name = cursor.read_string()?;
-                let guid = cursor.read_guid()?;
-                let terminator = cursor.read_u8()?;
-                if terminator != 0 {
-                    let position = cursor.stream_position()? - 1;
-                    Err(DeserializeError::InvalidTerminator(terminator, position))?
-                }
+                let struct_name = options.read_string(cursor)?;
+                let guid = options.read_struct_guid(cursor)?;
+                options.read_property_guid_flag(cursor)?;
 
                 let properties_start = cursor.stream_position()?;
-                for _ in 0..property_count {
+                let element_size = match options.read_options.strict_struct_arrays {
+                    true => properties_size.checked_div(u64::from(property_count)),
+                    false => None,
+                };
+                for index in 0..property_count {
+                    let element_start = cursor.stream_position()?;
                     let value = StructProperty::read_body(cursor, &struct_name, options)?;
+                    if let Some(element_size) = element_size {
+                        let actual_size = cursor.stream_position()? - element_start;
+                        if actual_size != element_size {
+                            Err(DeserializeError::MisalignedArrayElement(
+                                index,
+                                element_size,
+                                actual_size,
+                                element_start,
+                            ))?
+                        }
+                    }
                     properties.push(Property::from(value));
                 }
                 let properties_end = cursor.stream_position()?;
@@ -355,10 +536,106 @@ impl ArrayProperty {
             }
         };
 
-        ArrayProperty::new(property_type, array_struct_info, properties)
+        let array = ArrayProperty::new(property_type, array_struct_info, properties)?;
+        match options.read_options.share_struct_schemas {
+            true => Ok(array.share_struct_schema()),
+            false => Ok(array),
+        }
+    }
+}
+
+/// Checks that the values of an array of `property_type` can be read back.
+///
+/// Values are written without a tag, so values of another type or byte values mixing numbers
+/// and enum names would produce an array that can't be read.
+fn check_elements(property_type: &str, properties: &[Property]) -> Result<(), Error> {
+    let mut namespaced = None;
+    for property in properties {
+        let valid = match (property_type, property) {
+            ("ByteProperty", Property::ByteProperty(ByteProperty { value, .. })) => {
+                // Read back as numbers or as enum names depending on the average value size
+                let is_namespaced = matches!(value, BytePropertyValue::Namespaced(_));
+                if *namespaced.get_or_insert(is_namespaced) != is_namespaced {
+                    Err(SerializeError::invalid_value(
+                        "ByteProperty array mixes byte and enum name values",
+                    ))?
+                }
+                true
+            }
+            ("EnumProperty", Property::EnumProperty(_))
+            | ("NameProperty", Property::NameProperty(_))
+            | ("TextProperty", Property::TextProperty(_)) => true,
+            // Values read with a raw hint
+            (_, Property::UnknownProperty(unknown)) => unknown.property_name() == property_type,
+            ("ByteProperty" | "EnumProperty" | "NameProperty" | "TextProperty", _) => false,
+            _ => true,
+        };
+        if !valid {
+            Err(SerializeError::invalid_value(format!(
+                "Array property_type {} doesn't match property inside array: {:#?}",
+                property_type, property
+            )))?
+        }
     }
+    Ok(())
 }
 
+/// Builds a custom struct from the shared field names and the values of one element.
+fn expand_shared_struct<I>(fields: &[String], values: I) -> StructPropertyValue
+where
+    I: IntoIterator<Item = Property>,
+{
+    let mut properties: HashableIndexMap<String, Vec<Property>> = HashableIndexMap::new();
+    for (key, value) in fields.iter().zip(values) {
+        match properties.get_mut(key) {
+            Some(entry) => entry.push(value),
+            None => {
+                properties.insert(key.clone(), vec![value]);
+            }
+        }
+    }
+    StructPropertyValue::CustomStruct(properties)
+}
+
+#[cfg(feature = "serde")]
+fn serialize_shared_structs<S: serde::Serializer>(
+    field_name: &str,
+    type_name: &str,
+    guid: &Guid,
+    fields: &[String],
+    values: &[Vec<Property>],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeStruct;
+
+    struct Elements<'a> {
+        fields: &'a [String],
+        values: &'a [Vec<Property>],
+    }
+
+    impl serde::Serialize for Elements<'_> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(
+                self.values
+                    .iter()
+                    .map(|values| expand_shared_struct(self.fields, values.iter().cloned())),
+            )
+        }
+    }
+
+    let mut state = serializer.serialize_struct("SharedStructs", 4)?;
+    state.serialize_field("field_name", field_name)?;
+    state.serialize_field("type_name", type_name)?;
+    match guid.is_zero() {
+        true => state.skip_field("guid")?,
+        false => state.serialize_field("guid", guid)?,
+    }
+    state.serialize_field("structs", &Elements { fields, values })?;
+    state.end()
+}
+
+impl EmptyBody for ArrayProperty {}
+
 impl PropertyTrait for ArrayProperty {
     impl_write!(ArrayProperty, (write_string, fn, get_property_type));
 
@@ -379,10 +656,18 @@ impl PropertyTrait for ArrayProperty {
                 Ok(len)
             }
 
+            ArrayProperty::WideBools { wide_bools } => {
+                cursor.write_u32::<LittleEndian>(wide_bools.len() as u32)?;
+                for b in wide_bools {
+                    cursor.write_b32(*b)?;
+                }
+                Ok(4 + 4 * wide_bools.len())
+            }
+
             ArrayProperty::Bytes { bytes } => {
                 let mut len = 4;
                 cursor.write_u32::<LittleEndian>(bytes.len() as u32)?;
-                for b in bytes {
+                for b in bytes.iter() {
                     let property = Property::from(ByteProperty::new_byte(None, *b));
                     len += property.write(cursor, false, options)?;
                 }
@@ -445,7 +730,7 @@ impl PropertyTrait for ArrayProperty {
                 guid,
                 structs,
             } => {
-                let mut len = 29;
+                let mut len = 12;
                 cursor.write_u32::<LittleEndian>(structs.len() as u32)?;
                 len += cursor.write_string(field_name)?;
                 len += cursor.write_string("StructProperty")?;
@@ -458,16 +743,47 @@ impl PropertyTrait for ArrayProperty {
 
                 cursor.write_u64::<LittleEndian>(buf.len() as u64)?;
                 len += cursor.write_string(type_name)?;
-                cursor.write_guid(guid)?;
-                cursor.write_u8(0)?;
+                len += options.write_struct_guid(cursor, guid)?;
+                len += options.write_property_guid_flag(cursor)?;
+                cursor.write_all(buf)?;
+                Ok(len)
+            }
+
+            ArrayProperty::SharedStructs {
+                field_name,
+                type_name,
+                guid,
+                fields,
+                values,
+            } => {
+                let mut len = 12;
+                cursor.write_u32::<LittleEndian>(values.len() as u32)?;
+                len += cursor.write_string(field_name)?;
+                len += cursor.write_string("StructProperty")?;
+
+                let buf = &mut Cursor::new(Vec::new());
+                for values in values {
+                    for (key, value) in fields.iter().zip(values) {
+                        len += buf.write_string(key)?;
+                        len += value.write(buf, true, options)?;
+                    }
+                    len += buf.write_string("None")?;
+                }
+                let buf = buf.get_ref();
+
+                cursor.write_u64::<LittleEndian>(buf.len() as u64)?;
+                len += cursor.write_string(type_name)?;
+                len += options.write_struct_guid(cursor, guid)?;
+                len += options.write_property_guid_flag(cursor)?;
                 cursor.write_all(buf)?;
                 Ok(len)
             }
 
             ArrayProperty::Properties {
-                property_type: _,
+                property_type,
                 properties,
             } => {
+                check_elements(property_type, properties)?;
                 let mut len = 4;
                 cursor.write_u32::<LittleEndian>(properties.len() as u32)?;
                 for property in properties {
@@ -1,13 +1,18 @@
-use std::io::{Cursor, Read, Seek, Write};
+use std::{
+    collections::HashSet,
+    io::{Cursor, ErrorKind, Read, Seek, SeekFrom, Write},
+};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
 use crate::{
-    cursor_ext::{ReadExt, WriteExt},
-    error::Error,
+    cursor_ext::WriteExt,
+    error::{DeserializeError, Error, SerializeError},
+    options::DelegateLimit,
+    parse_report::ReportReason,
 };
 
-use super::{impl_read, impl_read_header, impl_write, PropertyOptions, PropertyTrait};
+use super::{impl_read, impl_read_header, impl_write, EmptyBody, PropertyOptions, PropertyTrait};
 
 /// An Unreal script delegate
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -30,9 +35,12 @@ impl Delegate {
     }
 
     #[inline]
-    pub(crate) fn read<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
-        let object = cursor.read_string()?;
-        let function_name = cursor.read_string()?;
+    pub(crate) fn read<R: Read + Seek>(
+        cursor: &mut R,
+        options: &PropertyOptions,
+    ) -> Result<Self, Error> {
+        let object = options.read_string(cursor)?;
+        let function_name = options.read_string(cursor)?;
         Ok(Delegate {
             object,
             function_name,
@@ -54,22 +62,52 @@ impl Delegate {
 pub struct DelegateProperty {
     /// Delegate
     pub value: Delegate,
+    /// Read from a zero-length body, see [`EmptyBody`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    pub empty_body: bool,
 }
 
 impl DelegateProperty {
     /// Creates a new `DelegateProperty` instance
     #[inline]
     pub fn new(value: Delegate) -> Self {
-        DelegateProperty { value }
+        DelegateProperty {
+            value,
+            empty_body: false,
+        }
+    }
+
+    impl_read!(options);
+    impl_read_header!(options);
+
+    #[inline]
+    fn read_body<R: Read + Seek>(
+        cursor: &mut R,
+        options: &mut PropertyOptions,
+    ) -> Result<Self, Error> {
+        let value = Delegate::read(cursor, options)?;
+        Ok(DelegateProperty::new(value))
     }
+}
 
-    impl_read!();
-    impl_read_header!();
+impl EmptyBody for DelegateProperty {
+    #[inline]
+    fn empty_body() -> Option<Self> {
+        Some(DelegateProperty {
+            value: Delegate {
+                object: String::new(),
+                function_name: String::new(),
+            },
+            empty_body: true,
+        })
+    }
 
     #[inline]
-    fn read_body<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
-        let value = Delegate::read(cursor)?;
-        Ok(DelegateProperty { value })
+    fn is_empty_body(&self) -> bool {
+        self.empty_body && self.value.object.is_empty() && self.value.function_name.is_empty()
     }
 }
 
@@ -87,11 +125,15 @@ impl PropertyTrait for DelegateProperty {
     }
 }
 
+/// Smallest serialized size of a [`Delegate`], two string lengths.
+const MIN_DELEGATE_SIZE: u64 = 8;
+
 /// Multicast script delegate
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MulticastScriptDelegate {
     /// Delegates
+    #[cfg_attr(feature = "serde", serde(default))]
     pub delegates: Vec<Delegate>,
 }
 
@@ -102,12 +144,78 @@ impl MulticastScriptDelegate {
         MulticastScriptDelegate { delegates }
     }
 
-    #[inline]
-    pub(crate) fn read<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
+    /// Remove duplicate delegates, keeping the first of each.
+    ///
+    /// Returns the number of removed delegates. Call this before writing a file to shrink lists
+    /// that grew in long-running worlds.
+    pub fn compact(&mut self) -> usize {
+        let len = self.delegates.len();
+        let mut seen = HashSet::with_capacity(len);
+        self.delegates
+            .retain(|delegate| seen.insert(delegate.clone()));
+        len - self.delegates.len()
+    }
+
+    /// Read a delegate list, `length` is the size of the property body if it is known.
+    pub(crate) fn read<R: Read + Seek>(
+        cursor: &mut R,
+        options: &PropertyOptions,
+        length: Option<u32>,
+    ) -> Result<Self, Error> {
+        let start = cursor.stream_position()?;
         let delegates_len = cursor.read_u32::<LittleEndian>()?;
-        let mut delegates = Vec::with_capacity(delegates_len as usize);
-        for _ in 0..delegates_len {
-            delegates.push(Delegate::read(cursor)?);
+
+        let remaining = match length {
+            Some(length) => Some(u64::from(length).saturating_sub(4)),
+            None => {
+                let position = cursor.stream_position()?;
+                match cursor.seek(SeekFrom::End(0)) {
+                    Ok(end) => {
+                        cursor.seek(SeekFrom::Start(position))?;
+                        Some(end - position)
+                    }
+                    // Streams which can't seek to their end are read without the check
+                    Err(e) if e.kind() == ErrorKind::Unsupported => None,
+                    Err(e) => Err(e)?,
+                }
+            }
+        };
+        if let Some(remaining) = remaining {
+            if u64::from(delegates_len) * MIN_DELEGATE_SIZE > remaining {
+                Err(DeserializeError::InvalidElementCount(
+                    delegates_len,
+                    remaining,
+                    start,
+                ))?
+            }
+        }
+
+        let kept_len = match options.read_options.delegate_limit {
+            DelegateLimit::Unlimited => delegates_len,
+            DelegateLimit::Fail(limit) if delegates_len > limit => Err(
+                DeserializeError::TooManyDelegates(delegates_len, limit, start),
+            )?,
+            DelegateLimit::Fail(_) => delegates_len,
+            DelegateLimit::Truncate(limit) => delegates_len.min(limit),
+        };
+
+        let capacity = match remaining {
+            Some(_) => kept_len as usize,
+            None => 0,
+        };
+        let mut delegates = Vec::with_capacity(capacity);
+        for _ in 0..kept_len {
+            delegates.push(Delegate::read(cursor, options)?);
+        }
+        // Skipped delegates are read one at a time and dropped
+        let skipped_start = cursor.stream_position()?;
+        for _ in kept_len..delegates_len {
+            Delegate::read(cursor, options)?;
+        }
+        if kept_len < delegates_len {
+            let end = cursor.stream_position()?;
+            let reason = ReportReason::TruncatedDelegates(delegates_len - kept_len);
+            options.report(skipped_start, end, reason);
         }
 
         Ok(MulticastScriptDelegate { delegates })
@@ -115,7 +223,14 @@ impl MulticastScriptDelegate {
 
     #[inline]
     pub(crate) fn write<W: Write>(&self, cursor: &mut W) -> Result<usize, Error> {
-        cursor.write_u32::<LittleEndian>(self.delegates.len() as u32)?;
+        let delegates_len = u32::try_from(self.delegates.len()).map_err(|_| {
+            SerializeError::invalid_value(format!(
+                "Delegate list has {} entries, more than {}",
+                self.delegates.len(),
+                u32::MAX
+            ))
+        })?;
+        cursor.write_u32::<LittleEndian>(delegates_len)?;
 
         let mut len = 4;
         for delegate in &self.delegates {
@@ -132,22 +247,68 @@ impl MulticastScriptDelegate {
 pub struct MulticastInlineDelegateProperty {
     /// Delegate
     pub value: MulticastScriptDelegate,
+    /// Read from a zero-length body, see [`EmptyBody`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    pub empty_body: bool,
 }
 
 impl MulticastInlineDelegateProperty {
     /// Creates a new `MulticastInlineDelegateProperty` instance
     #[inline]
     pub fn new(value: MulticastScriptDelegate) -> Self {
-        MulticastInlineDelegateProperty { value }
+        MulticastInlineDelegateProperty {
+            value,
+            empty_body: false,
+        }
     }
 
-    impl_read!();
-    impl_read_header!();
+    /// Read GVAS property data from a reader.
+    ///
+    /// If `include_header` is true, read the property header first.
+    #[inline]
+    pub fn read<R: Read + Seek>(
+        cursor: &mut R,
+        include_header: bool,
+        options: &mut PropertyOptions,
+    ) -> Result<Self, Error> {
+        if include_header {
+            Self::read_header(cursor, options)
+        } else {
+            let value = MulticastScriptDelegate::read(cursor, options, None)?;
+            Ok(MulticastInlineDelegateProperty::new(value))
+        }
+    }
+
+    impl_read_header!(options, length);
 
     #[inline]
-    pub(crate) fn read_body<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
-        let value = MulticastScriptDelegate::read(cursor)?;
-        Ok(MulticastInlineDelegateProperty { value })
+    pub(crate) fn read_body<R: Read + Seek>(
+        cursor: &mut R,
+        options: &mut PropertyOptions,
+        length: u32,
+    ) -> Result<Self, Error> {
+        let value = MulticastScriptDelegate::read(cursor, options, Some(length))?;
+        Ok(MulticastInlineDelegateProperty::new(value))
+    }
+}
+
+impl EmptyBody for MulticastInlineDelegateProperty {
+    #[inline]
+    fn empty_body() -> Option<Self> {
+        Some(MulticastInlineDelegateProperty {
+            value: MulticastScriptDelegate {
+                delegates: Vec::new(),
+            },
+            empty_body: true,
+        })
+    }
+
+    #[inline]
+    fn is_empty_body(&self) -> bool {
+        self.empty_body && self.value.delegates.is_empty()
     }
 }
 
@@ -171,22 +332,68 @@ impl PropertyTrait for MulticastInlineDelegateProperty {
 pub struct MulticastSparseDelegateProperty {
     /// Delegate
     pub value: MulticastScriptDelegate,
+    /// Read from a zero-length body, see [`EmptyBody`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    pub empty_body: bool,
 }
 
 impl MulticastSparseDelegateProperty {
     /// Creates a new `MulticastSparseDelegateProperty` instance
     #[inline]
     pub fn new(value: MulticastScriptDelegate) -> Self {
-        MulticastSparseDelegateProperty { value }
+        MulticastSparseDelegateProperty {
+            value,
+            empty_body: false,
+        }
+    }
+
+    /// Read GVAS property data from a reader.
+    ///
+    /// If `include_header` is true, read the property header first.
+    #[inline]
+    pub fn read<R: Read + Seek>(
+        cursor: &mut R,
+        include_header: bool,
+        options: &mut PropertyOptions,
+    ) -> Result<Self, Error> {
+        if include_header {
+            Self::read_header(cursor, options)
+        } else {
+            let value = MulticastScriptDelegate::read(cursor, options, None)?;
+            Ok(MulticastSparseDelegateProperty::new(value))
+        }
     }
 
-    impl_read!();
-    impl_read_header!();
+    impl_read_header!(options, length);
+
+    #[inline]
+    pub(crate) fn read_body<R: Read + Seek>(
+        cursor: &mut R,
+        options: &mut PropertyOptions,
+        length: u32,
+    ) -> Result<Self, Error> {
+        let value = MulticastScriptDelegate::read(cursor, options, Some(length))?;
+        Ok(MulticastSparseDelegateProperty::new(value))
+    }
+}
+
+impl EmptyBody for MulticastSparseDelegateProperty {
+    #[inline]
+    fn empty_body() -> Option<Self> {
+        Some(MulticastSparseDelegateProperty {
+            value: MulticastScriptDelegate {
+                delegates: Vec::new(),
+            },
+            empty_body: true,
+        })
+    }
 
     #[inline]
-    pub(crate) fn read_body<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
-        let value = MulticastScriptDelegate::read(cursor)?;
-        Ok(MulticastSparseDelegateProperty { value })
+    fn is_empty_body(&self) -> bool {
+        self.empty_body && self.value.delegates.is_empty()
     }
 }
 
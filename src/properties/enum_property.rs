@@ -2,12 +2,11 @@ use std::io::{Cursor, Read, Seek, Write};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
-use crate::{
-    cursor_ext::{ReadExt, WriteExt},
-    error::Error,
-};
+use crate::{cursor_ext::WriteExt, error::Error};
 
-use super::{impl_read_header, impl_write, impl_write_header_part, PropertyOptions, PropertyTrait};
+use super::{
+    impl_read_header, impl_write, impl_write_header_part, EmptyBody, PropertyOptions, PropertyTrait,
+};
 
 /// A property that holds an enum value.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -31,24 +30,32 @@ impl EnumProperty {
     pub(crate) fn read<R: Read + Seek>(
         cursor: &mut R,
         include_header: bool,
+        options: &mut PropertyOptions,
     ) -> Result<Self, Error> {
         if include_header {
-            Self::read_header(cursor)
+            Self::read_header(cursor, options)
         } else {
-            Self::read_body(cursor, None)
+            let value = options.read_string(cursor)?;
+            Ok(EnumProperty::new(None, value))
         }
     }
 
-    impl_read_header!(enum_type);
+    impl_read_header!(options, enum_type);
 
     #[inline]
-    fn read_body<R: Read + Seek>(cursor: &mut R, enum_type: Option<String>) -> Result<Self, Error> {
-        let value = cursor.read_string()?;
+    fn read_body<R: Read + Seek>(
+        cursor: &mut R,
+        options: &mut PropertyOptions,
+        enum_type: String,
+    ) -> Result<Self, Error> {
+        let value = options.read_string(cursor)?;
 
-        Ok(EnumProperty { enum_type, value })
+        Ok(EnumProperty::new(Some(enum_type), value))
     }
 }
 
+impl EmptyBody for EnumProperty {}
+
 impl PropertyTrait for EnumProperty {
     impl_write!(EnumProperty, (write_fstring, enum_type));
 
@@ -2,12 +2,9 @@ use std::io::{Cursor, Read, Seek, Write};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
-use crate::{
-    cursor_ext::{ReadExt, WriteExt},
-    error::Error,
-};
+use crate::{cursor_ext::WriteExt, error::Error};
 
-use super::{impl_read, impl_read_header, impl_write, PropertyOptions, PropertyTrait};
+use super::{impl_read, impl_read_header, impl_write, EmptyBody, PropertyOptions, PropertyTrait};
 
 /// Field path
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -30,14 +27,17 @@ impl FieldPath {
     }
 
     #[inline]
-    pub(crate) fn read<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
+    pub(crate) fn read<R: Read + Seek>(
+        cursor: &mut R,
+        options: &PropertyOptions,
+    ) -> Result<Self, Error> {
         let path_len = cursor.read_u32::<LittleEndian>()?;
         let mut path = Vec::with_capacity(path_len as usize);
         for _ in 0..path_len {
-            path.push(cursor.read_string()?);
+            path.push(options.read_string(cursor)?);
         }
 
-        let resolved_owner = cursor.read_string()?;
+        let resolved_owner = options.read_string(cursor)?;
 
         Ok(FieldPath {
             path,
@@ -66,23 +66,53 @@ impl FieldPath {
 pub struct FieldPathProperty {
     /// Field path
     pub value: FieldPath,
+    /// Read from a zero-length body, see [`EmptyBody`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    pub empty_body: bool,
 }
 
 impl FieldPathProperty {
     /// Creates a new `FieldPathProperty` instance
     #[inline]
     pub fn new(value: FieldPath) -> Self {
-        FieldPathProperty { value }
+        FieldPathProperty {
+            value,
+            empty_body: false,
+        }
     }
 
-    impl_read!();
-    impl_read_header!();
+    impl_read!(options);
+    impl_read_header!(options);
 
     #[inline]
-    fn read_body<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
-        let value = FieldPath::read(cursor)?;
+    fn read_body<R: Read + Seek>(
+        cursor: &mut R,
+        options: &mut PropertyOptions,
+    ) -> Result<Self, Error> {
+        let value = FieldPath::read(cursor, options)?;
 
-        Ok(FieldPathProperty { value })
+        Ok(FieldPathProperty::new(value))
+    }
+}
+
+impl EmptyBody for FieldPathProperty {
+    #[inline]
+    fn empty_body() -> Option<Self> {
+        Some(FieldPathProperty {
+            value: FieldPath {
+                path: Vec::new(),
+                resolved_owner: String::new(),
+            },
+            empty_body: true,
+        })
+    }
+
+    #[inline]
+    fn is_empty_body(&self) -> bool {
+        self.empty_body && self.value.path.is_empty() && self.value.resolved_owner.is_empty()
     }
 }
 
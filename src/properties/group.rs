@@ -0,0 +1,137 @@
+use super::{
+    array_property::ArrayProperty,
+    enum_property::EnumProperty,
+    field_path_property::FieldPathProperty,
+    int_property::{
+        BoolProperty, ByteProperty, DoubleProperty, FloatProperty, Int16Property, Int64Property,
+        Int8Property, IntProperty, UInt16Property, UInt32Property, UInt64Property,
+    },
+    map_property::MapProperty,
+    name_property::NameProperty,
+    object_property::ObjectProperty,
+    set_property::SetProperty,
+    str_property::StrProperty,
+    struct_property::{StructProperty, StructPropertyValue},
+    text_property::TextProperty,
+    Property,
+};
+
+macro_rules! make_group {
+    ($(#[$meta:meta])* $group:ident { $($name:ident),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum $group<'a> {
+            $(
+                #[doc = concat!("A `", stringify!($name), "`.")]
+                $name(&'a $name),
+            )+
+        }
+
+        impl<'a> $group<'a> {
+            /// Returns the view of `property`, if it belongs to this group.
+            #[inline]
+            pub fn from_property(property: &'a Property) -> Option<Self> {
+                match property {
+                    $(Property::$name(property) => Some(Self::$name(property)),)+
+                    _ => None,
+                }
+            }
+        }
+
+        impl From<$group<'_>> for Property {
+            #[inline]
+            fn from(group: $group<'_>) -> Self {
+                match group {
+                    $($group::$name(property) => Property::$name(property.clone()),)+
+                }
+            }
+        }
+    };
+}
+
+make_group!(
+    /// A property holding a single value, which can be edited in place.
+    ScalarProperty {
+        BoolProperty,
+        ByteProperty,
+        DoubleProperty,
+        EnumProperty,
+        FieldPathProperty,
+        FloatProperty,
+        Int16Property,
+        Int64Property,
+        Int8Property,
+        IntProperty,
+        NameProperty,
+        ObjectProperty,
+        StrProperty,
+        UInt16Property,
+        UInt32Property,
+        UInt64Property,
+    }
+);
+
+make_group!(
+    /// A property holding a list of elements or entries.
+    ContainerProperty {
+        ArrayProperty,
+        MapProperty,
+        SetProperty,
+    }
+);
+
+make_group!(
+    /// A property holding a struct.
+    StructLikeProperty {
+        StructProperty,
+        StructPropertyValue,
+    }
+);
+
+/// A [`Property`] grouped by the kind of value it holds.
+///
+/// New property types are added to an existing group, or to [`PropertyGroup::Other`], so
+/// matching on the groups keeps working when the crate adds variants to [`Property`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PropertyGroup<'a> {
+    /// A single value.
+    Scalar(ScalarProperty<'a>),
+    /// A list of elements or entries.
+    Container(ContainerProperty<'a>),
+    /// A struct.
+    Struct(StructLikeProperty<'a>),
+    /// A localized text.
+    Text(&'a TextProperty),
+    /// Any other property, like delegates and unknown properties.
+    Other(&'a Property),
+}
+
+impl<'a> From<&'a Property> for PropertyGroup<'a> {
+    fn from(property: &'a Property) -> Self {
+        if let Some(scalar) = ScalarProperty::from_property(property) {
+            PropertyGroup::Scalar(scalar)
+        } else if let Some(container) = ContainerProperty::from_property(property) {
+            PropertyGroup::Container(container)
+        } else if let Some(value) = StructLikeProperty::from_property(property) {
+            PropertyGroup::Struct(value)
+        } else if let Property::TextProperty(text) = property {
+            PropertyGroup::Text(text)
+        } else {
+            PropertyGroup::Other(property)
+        }
+    }
+}
+
+impl From<PropertyGroup<'_>> for Property {
+    fn from(group: PropertyGroup<'_>) -> Self {
+        match group {
+            PropertyGroup::Scalar(scalar) => scalar.into(),
+            PropertyGroup::Container(container) => container.into(),
+            PropertyGroup::Struct(value) => value.into(),
+            PropertyGroup::Text(text) => Property::TextProperty(text.clone()),
+            PropertyGroup::Other(property) => property.clone(),
+        }
+    }
+}
@@ -9,7 +9,7 @@ use ordered_float::OrderedFloat;
 use super::{
     impl_write,
     struct_types::{unwrap_value, wrap_type, wrap_value},
-    PropertyOptions, PropertyTrait,
+    EmptyBody, PropertyOptions, PropertyTrait,
 };
 use crate::{
     cursor_ext::{ReadExt, WriteExt},
@@ -17,7 +17,7 @@ use crate::{
 };
 
 macro_rules! check_size {
-    ($cursor:ident, $expected:literal) => {
+    ($cursor:ident, $options:ident, $expected:literal) => {
         let value_size = $cursor.read_u64::<LittleEndian>()?;
         if value_size != $expected {
             Err(DeserializeError::InvalidValueSize(
@@ -26,6 +26,7 @@ macro_rules! check_size {
                 $cursor.stream_position()?,
             ))?
         }
+        $options.read_property_guid_flag($cursor)?;
     };
 }
 
@@ -51,11 +52,10 @@ macro_rules! impl_int_property {
             pub(crate) fn read<R: Read + Seek>(
                 cursor: &mut R,
                 include_header: bool,
+                options: &PropertyOptions,
             ) -> Result<Self, Error> {
                 if include_header {
-                    check_size!(cursor, $size);
-                    let separator = cursor.read_u8()?;
-                    assert_eq!(separator, 0);
+                    check_size!(cursor, options, $size);
                 }
                 Ok(Self::new(cursor.$read_method::<LittleEndian>()?))
             }
@@ -67,6 +67,8 @@ macro_rules! impl_int_property {
             }
         }
 
+        impl EmptyBody for $name {}
+
         impl PropertyTrait for $name {
             impl_write!($name);
 
@@ -105,11 +107,10 @@ impl Int8Property {
     pub(crate) fn read<R: Read + Seek>(
         cursor: &mut R,
         include_header: bool,
+        options: &PropertyOptions,
     ) -> Result<Self, Error> {
         if include_header {
-            check_size!(cursor, 1);
-            let separator = cursor.read_u8()?;
-            assert_eq!(separator, 0);
+            check_size!(cursor, options, 1);
         }
         Ok(Int8Property {
             value: cursor.read_i8()?,
@@ -117,6 +118,8 @@ impl Int8Property {
     }
 }
 
+impl EmptyBody for Int8Property {}
+
 impl PropertyTrait for Int8Property {
     impl_write!(Int8Property);
 
@@ -188,31 +191,29 @@ impl ByteProperty {
     pub(crate) fn read<R: Read + Seek>(
         cursor: &mut R,
         include_header: bool,
+        options: &PropertyOptions,
         mut suggested_length: Option<u32>,
     ) -> Result<Self, Error> {
         let mut name = None;
         if include_header {
             let length = cursor.read_u32::<LittleEndian>()?;
             let array_index = cursor.read_u32::<LittleEndian>()?;
-            assert_eq!(
-                array_index,
-                0,
-                "Expected array_index value zero @ {:#x}",
-                cursor.stream_position()? - 4
-            );
+            if array_index != 0 {
+                let position = cursor.stream_position()? - 4;
+                Err(DeserializeError::InvalidArrayIndex(array_index, position))?
+            }
             suggested_length = Some(length);
 
-            name = Some(cursor.read_string()?);
-            let separator = cursor.read_u8()?;
-            assert_eq!(separator, 0);
+            name = Some(options.read_string(cursor)?);
+            options.read_property_guid_flag(cursor)?;
         }
 
         // -1 to account for separator
-        let length = suggested_length.map(|e| e - 1).unwrap_or(1);
+        let length = suggested_length.map(|e| e.saturating_sub(1)).unwrap_or(1);
 
         let value = match length {
             1 | 0 => BytePropertyValue::Byte(cursor.read_u8()?),
-            _ => BytePropertyValue::Namespaced(cursor.read_string()?),
+            _ => BytePropertyValue::Namespaced(options.read_string(cursor)?),
         };
 
         Ok(ByteProperty { name, value })
@@ -231,7 +232,7 @@ impl PropertyTrait for ByteProperty {
             return self.write_body(cursor, options);
         }
 
-        let mut len = 9;
+        let mut len = 8;
         len += cursor.write_string("ByteProperty")?;
 
         let buf = &mut Cursor::new(Vec::new());
@@ -240,7 +241,7 @@ impl PropertyTrait for ByteProperty {
 
         cursor.write_u64::<LittleEndian>(buf.len() as u64)?;
         len += cursor.write_fstring(self.name.as_deref())?;
-        cursor.write_u8(0)?;
+        len += options.write_property_guid_flag(cursor)?;
         cursor.write_all(buf)?;
 
         Ok(len)
@@ -281,15 +282,42 @@ impl BoolProperty {
     pub(crate) fn read<R: Read + Seek>(
         cursor: &mut R,
         include_header: bool,
+        options: &PropertyOptions,
     ) -> Result<Self, Error> {
-        if include_header {
-            check_size!(cursor, 0);
+        match include_header {
+            true => Self::read_header(cursor, options),
+            false => Self::read_body(cursor),
         }
-        let value = cursor.read_bool()?;
-        if include_header {
-            let indicator = cursor.read_u8()?;
-            assert_eq!(indicator, 0);
+    }
+
+    /// Reads a tagged `BoolProperty`, whose value is stored in the tag and whose body is empty.
+    #[inline]
+    fn read_header<R: Read + Seek>(
+        cursor: &mut R,
+        options: &PropertyOptions,
+    ) -> Result<Self, Error> {
+        let length = cursor.read_u32::<LittleEndian>()?;
+        if length != 0 {
+            Err(DeserializeError::InvalidValueSize(
+                0,
+                u64::from(length),
+                cursor.stream_position()? - 4,
+            ))?
         }
+        let array_index = cursor.read_u32::<LittleEndian>()?;
+        if array_index != 0 {
+            let position = cursor.stream_position()? - 4;
+            Err(DeserializeError::InvalidArrayIndex(array_index, position))?
+        }
+        let value = options.read_tag_bool(cursor)?;
+        options.read_property_guid_flag(cursor)?;
+        Ok(BoolProperty { value })
+    }
+
+    /// Reads an untagged `BoolProperty`, like an array element, whose value is its body.
+    #[inline]
+    fn read_body<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
+        let value = cursor.read_bool()?;
         Ok(BoolProperty { value })
     }
 }
@@ -306,25 +334,29 @@ impl PropertyTrait for BoolProperty {
         &self,
         cursor: &mut W,
         include_header: bool,
-        _options: &mut PropertyOptions,
+        options: &mut PropertyOptions,
     ) -> Result<usize, Error> {
-        let mut len = 0;
-        if include_header {
-            len += cursor.write_string("BoolProperty")?;
-            len += 8;
-            cursor.write_u64::<LittleEndian>(0)?;
-        }
-        len += 1;
-        cursor.write_bool(self.value)?;
-        if include_header {
-            len += 1;
-            cursor.write_u8(0)?;
+        if !include_header {
+            return self.write_body(cursor, options);
         }
+
+        let mut len = cursor.write_string("BoolProperty")?;
+        cursor.write_u32::<LittleEndian>(0)?;
+        cursor.write_u32::<LittleEndian>(0)?;
+        len += 8;
+        len += options.write_tag_bool(cursor, self.value)?;
+        len += options.write_property_guid_flag(cursor)?;
         Ok(len)
     }
 
-    fn write_body<W: Write>(&self, _: &mut W, _: &mut PropertyOptions) -> Result<usize, Error> {
-        unimplemented!()
+    #[inline]
+    fn write_body<W: Write>(
+        &self,
+        cursor: &mut W,
+        _: &mut PropertyOptions,
+    ) -> Result<usize, Error> {
+        cursor.write_bool(self.value)?;
+        Ok(1)
     }
 }
 
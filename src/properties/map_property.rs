@@ -1,4 +1,5 @@
 use std::{
+    cmp::Ordering,
     hash::Hash,
     io::{Cursor, Read, Seek, Write},
 };
@@ -6,21 +7,48 @@ use std::{
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
 use crate::{
-    cursor_ext::{ReadExt, WriteExt},
+    cursor_ext::WriteExt,
     error::{DeserializeError, Error},
+    options::DuplicateKeyPolicy,
+    parse_report::ReportReason,
     properties::{
         enum_property::EnumProperty,
         impl_read_header, impl_write, impl_write_header_part,
-        int_property::{BoolProperty, IntProperty},
+        int_property::{BoolProperty, BytePropertyValue, IntProperty},
         name_property::NameProperty,
         str_property::StrProperty,
-        Property, PropertyOptions, PropertyTrait,
+        struct_property::StructPropertyValue,
+        EmptyBody, Property, PropertyOptions, PropertyTrait,
     },
     scoped_stack_entry::ScopedStackEntry,
     types::map::HashableIndexMap,
 };
 
+/// An entry of a [`MapProperty::Properties`] whose key is already present in the map.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MapDuplicate {
+    /// Position of the entry among all entries of the map, duplicates included.
+    pub position: usize,
+    /// Entry key.
+    pub key: Property,
+    /// Entry value.
+    pub value: Property,
+}
+
 /// A property that stores a map of properties to properties.
+///
+/// # Order
+///
+/// Entries are kept in the order they were read and written back in that order, so reading and
+/// writing a save doesn't reorder its maps. Entries added later are appended. Some games depend
+/// on this order, use [`MapProperty::sort_keys`] or [`MapProperty::sorted_by_key`] to order the
+/// entries explicitly, or [`WriteOptions::sort_maps`] to only sort them while writing.
+///
+/// Duplicate keys of [`MapProperty::Properties`] are written back at the position they were
+/// read from.
+///
+/// [`WriteOptions::sort_maps`]: crate::options::WriteOptions::sort_maps
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(untagged))]
@@ -66,10 +94,17 @@ pub enum MapProperty {
         /// Value type name.
         value_type: String,
         /// Allocation flags.
+        #[cfg_attr(feature = "serde", serde(default))]
         allocation_flags: u32,
         /// Map entries.
         #[cfg_attr(feature = "serde", serde(with = "crate::types::map::serde_seq"))]
         value: HashableIndexMap<Property, Property>,
+        /// Entries whose key was already present in `value`, ordered by position.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        duplicates: Vec<MapDuplicate>,
     },
     /// Map<StrProperty, BoolProperty>
     StrBool {
@@ -132,6 +167,7 @@ impl MapProperty {
                     value_type,
                     allocation_flags,
                     value,
+                    duplicates: Vec::new(),
                 },
             },
 
@@ -157,6 +193,7 @@ impl MapProperty {
                     value_type,
                     allocation_flags,
                     value,
+                    duplicates: Vec::new(),
                 },
             },
 
@@ -184,6 +221,7 @@ impl MapProperty {
                         value_type,
                         allocation_flags,
                         value,
+                        duplicates: Vec::new(),
                     },
                 }
             }
@@ -195,9 +233,10 @@ impl MapProperty {
                         Property::NameProperty(NameProperty {
                             array_index: 0,
                             value: Some(key),
+                            empty_body: false,
                         }),
                         Property::BoolProperty(BoolProperty { value }),
-                    ) => Ok((key.clone(), *value)),
+                    ) => Ok((key.to_string(), *value)),
                     _ => Err(()),
                 })
                 .collect::<Result<_, _>>()
@@ -210,6 +249,7 @@ impl MapProperty {
                     value_type,
                     allocation_flags,
                     value,
+                    duplicates: Vec::new(),
                 },
             },
 
@@ -220,9 +260,10 @@ impl MapProperty {
                         Property::NameProperty(NameProperty {
                             array_index: 0,
                             value: Some(key),
+                            empty_body: false,
                         }),
                         Property::IntProperty(IntProperty { value }),
-                    ) => Ok((key.clone(), *value)),
+                    ) => Ok((key.to_string(), *value)),
                     _ => Err(()),
                 })
                 .collect::<Result<_, _>>()
@@ -235,6 +276,7 @@ impl MapProperty {
                     value_type,
                     allocation_flags,
                     value,
+                    duplicates: Vec::new(),
                 },
             },
 
@@ -246,9 +288,10 @@ impl MapProperty {
                             Property::NameProperty(NameProperty {
                                 array_index: 0,
                                 value: Some(key),
+                                empty_body: false,
                             }),
                             value,
-                        ) => Ok((key.clone(), value.clone())),
+                        ) => Ok((key.to_string(), value.clone())),
                         _ => Err(()),
                     })
                     .collect::<Result<_, _>>()
@@ -262,6 +305,7 @@ impl MapProperty {
                         value_type,
                         allocation_flags,
                         value,
+                        duplicates: Vec::new(),
                     },
                 }
             }
@@ -270,7 +314,10 @@ impl MapProperty {
                 .iter()
                 .map(|e| match e {
                     (
-                        Property::StrProperty(StrProperty { value: Some(key) }),
+                        Property::StrProperty(StrProperty {
+                            value: Some(key),
+                            empty_body: false,
+                        }),
                         Property::BoolProperty(BoolProperty { value }),
                     ) => Ok((key.clone(), *value)),
                     _ => Err(()),
@@ -285,6 +332,7 @@ impl MapProperty {
                     value_type,
                     allocation_flags,
                     value,
+                    duplicates: Vec::new(),
                 },
             },
 
@@ -292,7 +340,10 @@ impl MapProperty {
                 .iter()
                 .map(|e| match e {
                     (
-                        Property::StrProperty(StrProperty { value: Some(key) }),
+                        Property::StrProperty(StrProperty {
+                            value: Some(key),
+                            empty_body: false,
+                        }),
                         Property::IntProperty(IntProperty { value }),
                     ) => Ok((key.clone(), *value)),
                     _ => Err(()),
@@ -307,6 +358,7 @@ impl MapProperty {
                     value_type,
                     allocation_flags,
                     value,
+                    duplicates: Vec::new(),
                 },
             },
 
@@ -314,8 +366,14 @@ impl MapProperty {
                 .iter()
                 .map(|e| match e {
                     (
-                        Property::StrProperty(StrProperty { value: Some(key) }),
-                        Property::StrProperty(StrProperty { value }),
+                        Property::StrProperty(StrProperty {
+                            value: Some(key),
+                            empty_body: false,
+                        }),
+                        Property::StrProperty(StrProperty {
+                            value,
+                            empty_body: false,
+                        }),
                     ) => Ok((key.clone(), value.clone())),
                     _ => Err(()),
                 })
@@ -329,6 +387,7 @@ impl MapProperty {
                     value_type,
                     allocation_flags,
                     value,
+                    duplicates: Vec::new(),
                 },
             },
 
@@ -336,9 +395,13 @@ impl MapProperty {
                 match value
                     .iter()
                     .map(|e| match e {
-                        (Property::StrProperty(StrProperty { value: Some(key) }), value) => {
-                            Ok((key.clone(), value.clone()))
-                        }
+                        (
+                            Property::StrProperty(StrProperty {
+                                value: Some(key),
+                                empty_body: false,
+                            }),
+                            value,
+                        ) => Ok((key.clone(), value.clone())),
                         _ => Err(()),
                     })
                     .collect::<Result<_, _>>()
@@ -352,6 +415,7 @@ impl MapProperty {
                         value_type,
                         allocation_flags,
                         value,
+                        duplicates: Vec::new(),
                     },
                 }
             }
@@ -361,10 +425,64 @@ impl MapProperty {
                 value_type,
                 allocation_flags,
                 value,
+                duplicates: Vec::new(),
             },
         }
     }
 
+    /// Sorts the entries by key.
+    ///
+    /// String keys are ordered by their value. Property keys of the same numeric, string, name,
+    /// enum, object or struct type are ordered by their value, keys which can't be compared keep
+    /// their order. Duplicate keys keep their order relative to each other.
+    pub fn sort_keys(&mut self) {
+        match self {
+            MapProperty::EnumBool { enum_bools: map }
+            | MapProperty::NameBool { name_bools: map }
+            | MapProperty::StrBool { str_bools: map } => map.sort_keys(),
+            MapProperty::EnumInt { enum_ints: map }
+            | MapProperty::NameInt { name_ints: map }
+            | MapProperty::StrInt { str_ints: map } => map.sort_keys(),
+            MapProperty::EnumProperty {
+                enum_props: map, ..
+            }
+            | MapProperty::NameProperty {
+                name_props: map, ..
+            }
+            | MapProperty::StrProperty { str_props: map, .. } => map.sort_keys(),
+            MapProperty::Properties {
+                value, duplicates, ..
+            } => {
+                let mut entries: Vec<_> = std::mem::take(&mut value.0).into_iter().collect();
+                for duplicate in std::mem::take(duplicates) {
+                    let position = duplicate.position.min(entries.len());
+                    entries.insert(position, (duplicate.key, duplicate.value));
+                }
+                entries.sort_by(|(a, _), (b, _)| compare_keys(a, b));
+                for (position, (key, entry)) in entries.into_iter().enumerate() {
+                    match value.contains_key(&key) {
+                        true => duplicates.push(MapDuplicate {
+                            position,
+                            key,
+                            value: entry,
+                        }),
+                        false => {
+                            value.insert(key, entry);
+                        }
+                    }
+                }
+            }
+            MapProperty::StrStr { str_strs } => str_strs.sort_keys(),
+        }
+    }
+
+    /// Returns a copy of this map with its entries sorted by key, see [`MapProperty::sort_keys`].
+    pub fn sorted_by_key(&self) -> Self {
+        let mut sorted = self.clone();
+        sorted.sort_keys();
+        sorted
+    }
+
     #[inline]
     pub(crate) fn get_key_type(&self) -> Result<&str, Error> {
         Ok(self.key_type())
@@ -395,6 +513,7 @@ impl MapProperty {
                 value_type: _,
                 allocation_flags: _,
                 value: _,
+                duplicates: _,
             } => key_type,
             MapProperty::StrBool { str_bools: _ } => "StrProperty",
             MapProperty::StrInt { str_ints: _ } => "StrProperty",
@@ -426,6 +545,7 @@ impl MapProperty {
                 value_type,
                 allocation_flags: _,
                 value: _,
+                duplicates: _,
             } => value_type,
             MapProperty::StrBool { str_bools: _ } => "BoolProperty",
             MapProperty::StrInt { str_ints: _ } => "IntProperty",
@@ -446,10 +566,10 @@ impl MapProperty {
         if include_header {
             Self::read_header(cursor, options)
         } else {
-            Err(DeserializeError::invalid_property(
+            Err(Error::unsupported(DeserializeError::invalid_property(
                 "MapProperty is not supported in arrays",
                 cursor,
-            ))?
+            )))?
         }
     }
 
@@ -466,7 +586,9 @@ impl MapProperty {
         let element_count = cursor.read_u32::<LittleEndian>()?;
 
         let mut map = HashableIndexMap::with_capacity(element_count as usize);
+        let mut duplicates = Vec::new();
         for _ in 0..element_count {
+            let key_position = cursor.stream_position()?;
             let properties_stack = &mut options.properties_stack;
             let key_stack_entry = ScopedStackEntry::new(properties_stack, "Key".to_string());
             let key = Property::new(cursor, &key_type, false, options, None)?;
@@ -477,7 +599,36 @@ impl MapProperty {
             let value = Property::new(cursor, &value_type, false, options, None)?;
             drop(value_stack_entry);
 
-            map.insert(key, value);
+            if !map.contains_key(&key) {
+                map.insert(key, value);
+                continue;
+            }
+            match options.read_options.duplicate_map_keys {
+                DuplicateKeyPolicy::Tolerant => {
+                    let end = cursor.stream_position()?;
+                    options.report(key_position, end, ReportReason::DuplicateMapKey);
+                    duplicates.push(MapDuplicate {
+                        position: map.len() + duplicates.len(),
+                        key,
+                        value,
+                    });
+                }
+                DuplicateKeyPolicy::Strict => Err(DeserializeError::DuplicateMapKey(
+                    format!("{key:?}").into_boxed_str(),
+                    options.properties_stack.join(".").into_boxed_str(),
+                    key_position,
+                ))?,
+            }
+        }
+
+        if !duplicates.is_empty() {
+            return Ok(MapProperty::Properties {
+                key_type,
+                value_type,
+                allocation_flags,
+                value: map,
+                duplicates,
+            });
         }
 
         Ok(MapProperty::new(
@@ -489,6 +640,8 @@ impl MapProperty {
     }
 }
 
+impl EmptyBody for MapProperty {}
+
 impl PropertyTrait for MapProperty {
     impl_write!(
         MapProperty,
@@ -501,6 +654,17 @@ impl PropertyTrait for MapProperty {
         &self,
         cursor: &mut W,
         options: &mut PropertyOptions,
+    ) -> Result<usize, Error> {
+        self.write_entries(cursor, options.write_options.sort_maps, options)
+    }
+}
+
+impl MapProperty {
+    fn write_entries<W: Write>(
+        &self,
+        cursor: &mut W,
+        sort: bool,
+        options: &mut PropertyOptions,
     ) -> Result<usize, Error> {
         match self {
             MapProperty::EnumBool {
@@ -509,7 +673,7 @@ impl PropertyTrait for MapProperty {
                 cursor.write_u32::<LittleEndian>(0)?;
                 cursor.write_u32::<LittleEndian>(enum_bools.len() as u32)?;
                 let mut len = 8;
-                for (key, value) in enum_bools {
+                for (key, value) in sorted(enum_bools.iter(), sort, Ord::cmp) {
                     let k_property = EnumProperty::new(None, key.clone());
                     let v_property = BoolProperty::new(*value);
                     len += k_property.write(cursor, false, options)?;
@@ -524,7 +688,7 @@ impl PropertyTrait for MapProperty {
                 cursor.write_u32::<LittleEndian>(0)?;
                 cursor.write_u32::<LittleEndian>(enum_ints.len() as u32)?;
                 let mut len = 8;
-                for (key, value) in enum_ints {
+                for (key, value) in sorted(enum_ints.iter(), sort, Ord::cmp) {
                     let k_property = EnumProperty::new(None, key.clone());
                     let v_property = IntProperty::new(*value);
                     len += k_property.write(cursor, false, options)?;
@@ -540,7 +704,7 @@ impl PropertyTrait for MapProperty {
                 cursor.write_u32::<LittleEndian>(0)?;
                 cursor.write_u32::<LittleEndian>(enum_props.len() as u32)?;
                 let mut len = 8;
-                for (key, value) in enum_props {
+                for (key, value) in sorted(enum_props.iter(), sort, Ord::cmp) {
                     let property = EnumProperty::new(None, key.clone());
                     len += property.write(cursor, false, options)?;
                     len += value.write(cursor, false, options)?;
@@ -554,7 +718,7 @@ impl PropertyTrait for MapProperty {
                 cursor.write_u32::<LittleEndian>(0)?;
                 cursor.write_u32::<LittleEndian>(name_bools.len() as u32)?;
                 let mut len = 8;
-                for (key, value) in name_bools {
+                for (key, value) in sorted(name_bools.iter(), sort, Ord::cmp) {
                     let k_property = NameProperty::from(key.clone());
                     let v_property = BoolProperty::new(*value);
                     len += k_property.write(cursor, false, options)?;
@@ -569,7 +733,7 @@ impl PropertyTrait for MapProperty {
                 cursor.write_u32::<LittleEndian>(0)?;
                 cursor.write_u32::<LittleEndian>(name_ints.len() as u32)?;
                 let mut len = 8;
-                for (key, value) in name_ints {
+                for (key, value) in sorted(name_ints.iter(), sort, Ord::cmp) {
                     let k_property = NameProperty::from(key.clone());
                     let v_property = IntProperty::new(*value);
                     len += k_property.write(cursor, false, options)?;
@@ -585,7 +749,7 @@ impl PropertyTrait for MapProperty {
                 cursor.write_u32::<LittleEndian>(0)?;
                 cursor.write_u32::<LittleEndian>(name_props.len() as u32)?;
                 let mut len = 8;
-                for (key, value) in name_props {
+                for (key, value) in sorted(name_props.iter(), sort, Ord::cmp) {
                     let property = NameProperty::from(key.clone());
                     len += property.write(cursor, false, options)?;
                     len += value.write(cursor, false, options)?;
@@ -597,12 +761,14 @@ impl PropertyTrait for MapProperty {
                 key_type: _,
                 value_type: _,
                 allocation_flags,
-                value: HashableIndexMap(value),
+                value,
+                duplicates,
             } => {
                 cursor.write_u32::<LittleEndian>(*allocation_flags)?;
-                cursor.write_u32::<LittleEndian>(value.len() as u32)?;
+                cursor.write_u32::<LittleEndian>((value.len() + duplicates.len()) as u32)?;
                 let mut len = 8;
-                for (key, value) in value {
+                let entries = OrderedEntries::new(value, duplicates);
+                for (key, value) in sorted(entries, sort, compare_keys) {
                     len += key.write(cursor, false, options)?;
                     len += value.write(cursor, false, options)?;
                 }
@@ -615,7 +781,7 @@ impl PropertyTrait for MapProperty {
                 cursor.write_u32::<LittleEndian>(0)?;
                 cursor.write_u32::<LittleEndian>(str_bools.len() as u32)?;
                 let mut len = 8;
-                for (key, value) in str_bools {
+                for (key, value) in sorted(str_bools.iter(), sort, Ord::cmp) {
                     let k_property = StrProperty::from(key.clone());
                     let v_property = BoolProperty::new(*value);
                     len += k_property.write(cursor, false, options)?;
@@ -630,7 +796,7 @@ impl PropertyTrait for MapProperty {
                 cursor.write_u32::<LittleEndian>(0)?;
                 cursor.write_u32::<LittleEndian>(str_ints.len() as u32)?;
                 let mut len = 8;
-                for (key, value) in str_ints {
+                for (key, value) in sorted(str_ints.iter(), sort, Ord::cmp) {
                     let k_property = StrProperty::from(key.clone());
                     let v_property = IntProperty::new(*value);
                     len += k_property.write(cursor, false, options)?;
@@ -646,7 +812,7 @@ impl PropertyTrait for MapProperty {
                 cursor.write_u32::<LittleEndian>(0)?;
                 cursor.write_u32::<LittleEndian>(str_props.len() as u32)?;
                 let mut len = 8;
-                for (key, value) in str_props {
+                for (key, value) in sorted(str_props.iter(), sort, Ord::cmp) {
                     let property = StrProperty::from(key.clone());
                     len += property.write(cursor, false, options)?;
                     len += value.write(cursor, false, options)?;
@@ -660,7 +826,7 @@ impl PropertyTrait for MapProperty {
                 cursor.write_u32::<LittleEndian>(0)?;
                 cursor.write_u32::<LittleEndian>(str_strs.len() as u32)?;
                 let mut len = 8;
-                for (key, value) in str_strs {
+                for (key, value) in sorted(str_strs.iter(), sort, Ord::cmp) {
                     let k_property = StrProperty::from(key.clone());
                     let v_property = StrProperty::new(value.clone());
                     len += k_property.write(cursor, false, options)?;
@@ -671,3 +837,126 @@ impl PropertyTrait for MapProperty {
         }
     }
 }
+
+/// Iterates over the entries of a [`MapProperty::Properties`] in the order they are written,
+/// with the duplicates at their positions.
+pub(crate) struct OrderedEntries<'a> {
+    entries: indexmap::map::Iter<'a, Property, Property>,
+    duplicates: std::iter::Peekable<std::slice::Iter<'a, MapDuplicate>>,
+    position: usize,
+}
+
+impl<'a> OrderedEntries<'a> {
+    pub(crate) fn new(
+        entries: &'a HashableIndexMap<Property, Property>,
+        duplicates: &'a [MapDuplicate],
+    ) -> Self {
+        OrderedEntries {
+            entries: entries.iter(),
+            duplicates: duplicates.iter().peekable(),
+            position: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for OrderedEntries<'a> {
+    type Item = (&'a Property, &'a Property);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.position;
+        self.position += 1;
+        match self
+            .duplicates
+            .next_if(|duplicate| duplicate.position <= position)
+        {
+            Some(duplicate) => Some((&duplicate.key, &duplicate.value)),
+            None => self.entries.next().or_else(|| {
+                let duplicate = self.duplicates.next()?;
+                Some((&duplicate.key, &duplicate.value))
+            }),
+        }
+    }
+}
+
+/// Returns `entries`, sorted by key with `compare` if `sort` is set.
+fn sorted<'a, K: 'a, V: 'a>(
+    entries: impl Iterator<Item = (&'a K, &'a V)> + 'a,
+    sort: bool,
+    compare: impl Fn(&K, &K) -> Ordering,
+) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a> {
+    if !sort {
+        return Box::new(entries);
+    }
+    let mut entries: Vec<_> = entries.collect();
+    entries.sort_by(|(a, _), (b, _)| compare(a, b));
+    Box::new(entries.into_iter())
+}
+
+/// Orders map keys of the same type by value. Keys of different types, or of types without an
+/// order, compare equal.
+fn compare_keys(a: &Property, b: &Property) -> Ordering {
+    macro_rules! by_value {
+        ($($variant:ident),+) => {
+            match (a, b) {
+                $((Property::$variant(a), Property::$variant(b)) => a.value.cmp(&b.value),)+
+                (Property::NameProperty(a), Property::NameProperty(b)) => {
+                    (&a.value, a.array_index).cmp(&(&b.value, b.array_index))
+                }
+                (Property::ByteProperty(a), Property::ByteProperty(b)) => {
+                    match (&a.value, &b.value) {
+                        (BytePropertyValue::Byte(a), BytePropertyValue::Byte(b)) => a.cmp(b),
+                        (BytePropertyValue::Namespaced(a), BytePropertyValue::Namespaced(b)) => {
+                            a.cmp(b)
+                        }
+                        _ => Ordering::Equal,
+                    }
+                }
+                (Property::StructProperty(a), Property::StructProperty(b)) => a
+                    .type_name
+                    .cmp(&b.type_name)
+                    .then_with(|| compare_struct_values(&a.value, &b.value)),
+                (Property::StructPropertyValue(a), Property::StructPropertyValue(b)) => {
+                    compare_struct_values(a, b)
+                }
+                _ => Ordering::Equal,
+            }
+        };
+    }
+    by_value!(
+        BoolProperty,
+        DoubleProperty,
+        EnumProperty,
+        FloatProperty,
+        Int16Property,
+        Int64Property,
+        Int8Property,
+        IntProperty,
+        ObjectProperty,
+        StrProperty,
+        UInt16Property,
+        UInt32Property,
+        UInt64Property
+    )
+}
+
+/// Orders guids by their bytes and custom structs field by field.
+fn compare_struct_values(a: &StructPropertyValue, b: &StructPropertyValue) -> Ordering {
+    match (a, b) {
+        (StructPropertyValue::Guid(a), StructPropertyValue::Guid(b)) => a.0.cmp(&b.0),
+        (StructPropertyValue::CustomStruct(a), StructPropertyValue::CustomStruct(b)) => a
+            .iter()
+            .zip(b.iter())
+            .map(|((a_name, a), (b_name, b))| {
+                a_name.cmp(b_name).then_with(|| {
+                    a.iter()
+                        .zip(b)
+                        .map(|(a, b)| compare_keys(a, b))
+                        .find(|ordering| ordering.is_ne())
+                        .unwrap_or_else(|| a.len().cmp(&b.len()))
+                })
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        _ => Ordering::Equal,
+    }
+}
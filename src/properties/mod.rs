@@ -1,15 +1,24 @@
 use std::{
+    borrow::Cow,
     collections::HashMap,
     fmt::Debug,
     hash::Hash,
-    io::{Read, Seek, Write},
+    io::{self, Read, Seek, Write},
+    sync::Arc,
 };
 
+use byteorder::{ReadBytesExt, WriteBytesExt};
 use enum_dispatch::enum_dispatch;
+use ordered_float::OrderedFloat;
 
 use crate::{
+    cursor_ext::{ReadExt, WriteExt},
     custom_version::{CustomVersionTrait, FCustomVersion},
-    error::{DeserializeError, Error},
+    error::{DeserializeError, Error, SerializeError},
+    hints::HintValue,
+    object_version::{EUnrealEngineObjectUE4Version, PackageVersion},
+    options::{Codepage, PrecisionMismatch, ReadOptions, TrailingPadding, WriteOptions},
+    parse_report::{ReportEntry, ReportReason},
     scoped_stack_entry::ScopedStackEntry,
     types::{map::HashableIndexMap, Guid},
 };
@@ -21,6 +30,7 @@ use self::{
     },
     enum_property::EnumProperty,
     field_path_property::FieldPathProperty,
+    group::{ContainerProperty, PropertyGroup, ScalarProperty, StructLikeProperty},
     int_property::{
         BoolProperty, ByteProperty, DoubleProperty, FloatProperty, Int16Property, Int64Property,
         Int8Property, IntProperty, UInt16Property, UInt32Property, UInt64Property,
@@ -28,6 +38,7 @@ use self::{
     map_property::MapProperty,
     name_property::NameProperty,
     object_property::ObjectProperty,
+    property_kind::PropertyKind,
     set_property::SetProperty,
     str_property::StrProperty,
     struct_property::{StructProperty, StructPropertyValue},
@@ -43,6 +54,8 @@ pub mod delegate_property;
 pub mod enum_property;
 /// Module for `FieldPathProperty`
 pub mod field_path_property;
+/// Module for property groups
+pub mod group;
 /// Module for `IntProperty` and various integer properties.
 pub mod int_property;
 /// Module for `MapProperty`
@@ -51,6 +64,8 @@ pub mod map_property;
 pub mod name_property;
 /// Module for `ObjectProperty`
 pub mod object_property;
+/// Module for `PropertyKind`
+pub mod property_kind;
 /// Module for `SetProperty`
 pub mod set_property;
 /// Module for `StrProperty`
@@ -59,8 +74,12 @@ pub mod str_property;
 pub mod struct_property;
 /// Module for `StructProperty` sub-types.
 pub mod struct_types;
+/// Module for resolving `FText` to plain text
+pub mod text_format;
 /// Module for `TextProperty`
 pub mod text_property;
+/// Module for property and struct type names
+pub mod type_names;
 /// Module for `UnknownProperty`
 pub mod unknown_property;
 
@@ -71,16 +90,20 @@ pub mod unknown_property;
 /// This macro must be used in conjunction with a suitable `read_header` function, such as one
 /// generated by `impl_read_header!(...)`.
 macro_rules! impl_read {
-    () => {
+    (options, array_index) => {
         /// Read GVAS property data from a reader.
         ///
         /// If `include_header` is true, read the property header first.
         #[inline]
-        pub fn read<R: Read + Seek>(reader: &mut R, include_header: bool) -> Result<Self, Error> {
+        pub fn read<R: Read + Seek>(
+            reader: &mut R,
+            include_header: bool,
+            options: &mut PropertyOptions,
+        ) -> Result<Self, Error> {
             if include_header {
-                Self::read_header(reader)
+                Self::read_header(reader, options)
             } else {
-                Self::read_body(reader)
+                Self::read_body(reader, options, 0)
             }
         }
     };
@@ -102,20 +125,6 @@ macro_rules! impl_read {
             }
         }
     };
-
-    (array_index) => {
-        /// Read GVAS property data from a reader.
-        ///
-        /// If `include_header` is true, read the property header first.
-        #[inline]
-        pub fn read<R: Read + Seek>(reader: &mut R, include_header: bool) -> Result<Self, Error> {
-            if include_header {
-                Self::read_header(reader)
-            } else {
-                Self::read_body(reader, 0)
-            }
-        }
-    };
 }
 
 /// This macro generates a `read_header` function for reading GVAS property headers from a reader.
@@ -129,7 +138,7 @@ macro_rules! impl_read {
 ///
 /// use crate::{
 ///     error::Error,
-///     properties::{impl_read, impl_read_header},
+///     properties::{impl_read, impl_read_header, PropertyOptions},
 /// };
 ///
 /// struct ExampleProperty(
@@ -137,9 +146,12 @@ macro_rules! impl_read {
 /// );
 ///
 /// impl ExampleProperty {
-///     impl_read!();
-///     impl_read_header!();
-///     fn read_body<R: Read + Seek>(reader: &mut R) -> Result<Self, Error> {
+///     impl_read!(options);
+///     impl_read_header!(options);
+///     fn read_body<R: Read + Seek>(
+///         reader: &mut R,
+///         options: &mut PropertyOptions,
+///     ) -> Result<Self, Error> {
 ///         // Read values from reader...
 ///         Ok(Self (
 ///             // ...
@@ -162,26 +174,32 @@ macro_rules! impl_read_header {
                 Err($crate::error::DeserializeError::InvalidArrayIndex(array_index, position))?
             }
             $(
-                let $var = reader.read_string()?;
+                let $var = options.read_string(reader)?;
             )*
-            let terminator = reader.read_u8()?;
-            if terminator != 0 {
-                let position = reader.stream_position()? - 1;
-                Err($crate::error::DeserializeError::InvalidTerminator(terminator, position))?
+            options.read_property_guid_flag(reader)?;
+
+            if length == 0 {
+                if let Some(empty) = <Self as $crate::properties::EmptyBody>::empty_body() {
+                    return Ok(empty);
+                }
             }
 
             let start = reader.stream_position()?;
             let result = Self::read_body(reader, options, length $(, $var)*)?;
             let end = reader.stream_position()?;
             if end - start != length as u64 {
-                Err($crate::error::DeserializeError::InvalidValueSize(length as u64, end - start, start))?
+                Err($crate::error::DeserializeError::LengthMismatch {
+                    expected: length as u64,
+                    actual: end - start,
+                    position: start,
+                })?
             }
 
             Ok(result)
         }
     };
 
-    (options $(, $var:ident)*) => {
+    (options, array_index) => {
         /// Read GVAS property data from a reader.
         #[inline]
         pub fn read_header<R: Read + Seek>(
@@ -190,63 +208,35 @@ macro_rules! impl_read_header {
         ) -> Result<Self, Error> {
             let length = reader.read_u32::<LittleEndian>()?;
             let array_index = reader.read_u32::<LittleEndian>()?;
-            if array_index != 0 {
-                let position = reader.stream_position()? - 4;
-                Err($crate::error::DeserializeError::InvalidArrayIndex(array_index, position))?
-            }
-            $(
-                let $var = reader.read_string()?;
-            )*
-            let terminator = reader.read_u8()?;
-            if terminator != 0 {
-                let position = reader.stream_position()? - 1;
-                Err($crate::error::DeserializeError::InvalidTerminator(terminator, position))?
-            }
+            options.read_property_guid_flag(reader)?;
 
-            let start = reader.stream_position()?;
-            let result = Self::read_body(reader, options $(, $var)*)?;
-            let end = reader.stream_position()?;
-            if end - start != length as u64 {
-                Err($crate::error::DeserializeError::InvalidValueSize(length as u64, end - start, start))?
-            }
-
-            Ok(result)
-        }
-    };
-
-    (array_index $(, $var:ident)*) => {
-        /// Read GVAS property data from a reader.
-        #[inline]
-        pub fn read_header<R: Read + Seek>(
-            reader: &mut R,
-        ) -> Result<Self, Error> {
-            let length = reader.read_u32::<LittleEndian>()?;
-            let array_index = reader.read_u32::<LittleEndian>()?;
-            $(
-                let $var = reader.read_string()?;
-            )*
-            let terminator = reader.read_u8()?;
-            if terminator != 0 {
-                let position = reader.stream_position()? - 1;
-                Err($crate::error::DeserializeError::InvalidTerminator(terminator, position))?
+            if length == 0 {
+                if let Some(empty) = <Self as $crate::properties::EmptyBody>::empty_body() {
+                    return Ok(Self { array_index, ..empty });
+                }
             }
 
             let start = reader.stream_position()?;
-            let result = Self::read_body(reader, array_index $(, Some($var))*)?;
+            let result = Self::read_body(reader, options, array_index)?;
             let end = reader.stream_position()?;
             if end - start != length as u64 {
-                Err($crate::error::DeserializeError::InvalidValueSize(length as u64, end - start, start))?
+                Err($crate::error::DeserializeError::LengthMismatch {
+                    expected: length as u64,
+                    actual: end - start,
+                    position: start,
+                })?
             }
 
             Ok(result)
         }
     };
 
-    ($($var:ident $(,)? )*) => {
+    (options $(, $var:ident)*) => {
         /// Read GVAS property data from a reader.
         #[inline]
         pub fn read_header<R: Read + Seek>(
             reader: &mut R,
+            options: &mut PropertyOptions,
         ) -> Result<Self, Error> {
             let length = reader.read_u32::<LittleEndian>()?;
             let array_index = reader.read_u32::<LittleEndian>()?;
@@ -255,19 +245,25 @@ macro_rules! impl_read_header {
                 Err($crate::error::DeserializeError::InvalidArrayIndex(array_index, position))?
             }
             $(
-                let $var = reader.read_string()?;
+                let $var = options.read_string(reader)?;
             )*
-            let terminator = reader.read_u8()?;
-            if terminator != 0 {
-                let position = reader.stream_position()? - 1;
-                Err($crate::error::DeserializeError::InvalidTerminator(terminator, position))?
+            options.read_property_guid_flag(reader)?;
+
+            if length == 0 {
+                if let Some(empty) = <Self as $crate::properties::EmptyBody>::empty_body() {
+                    return Ok(empty);
+                }
             }
 
             let start = reader.stream_position()?;
-            let result = Self::read_body(reader $(, Some($var))*)?;
+            let result = Self::read_body(reader, options $(, $var)*)?;
             let end = reader.stream_position()?;
             if end - start != length as u64 {
-                Err($crate::error::DeserializeError::InvalidValueSize(length as u64, end - start, start))?
+                Err($crate::error::DeserializeError::LengthMismatch {
+                    expected: length as u64,
+                    actual: end - start,
+                    position: start,
+                })?
             }
 
             Ok(result)
@@ -325,18 +321,20 @@ macro_rules! impl_write {
                 return self.write_body(writer, options);
             }
 
-            let mut len = 9;
+            let mut len = 8;
             let buf = &mut Cursor::new(Vec::new());
-            len += self.write_body(buf, options)?;
+            if !$crate::properties::EmptyBody::is_empty_body(self) {
+                len += self.write_body(buf, options)?;
+            }
             let buf = buf.get_ref();
 
-            writer.write_string(stringify!($property))?;
+            len += writer.write_string(stringify!($property))?;
             writer.write_u32::<LittleEndian>(buf.len() as u32)?;
             writer.write_u32::<LittleEndian>(self.array_index)?;
             $(
                 len += impl_write_header_part!(self, writer, $header_property);
             )*
-            writer.write_u8(0)?;
+            len += options.write_property_guid_flag(writer)?;
             writer.write_all(buf)?;
 
             Ok(len)
@@ -355,9 +353,11 @@ macro_rules! impl_write {
                 return self.write_body(writer, options);
             }
 
-            let mut len = 9;
+            let mut len = 8;
             let buf = &mut Cursor::new(Vec::new());
-            len += self.write_body(buf, options)?;
+            if !$crate::properties::EmptyBody::is_empty_body(self) {
+                len += self.write_body(buf, options)?;
+            }
             let buf = buf.get_ref();
 
             len += writer.write_string(stringify!($property))?;
@@ -366,7 +366,7 @@ macro_rules! impl_write {
             $(
                 len += impl_write_header_part!(self, writer, $header_property);
             )*
-            writer.write_u8(0)?;
+            len += options.write_property_guid_flag(writer)?;
             writer.write_all(buf)?;
 
             Ok(len)
@@ -436,11 +436,17 @@ pub(crate) use make_matcher;
 /// Property options used for reading and writing.
 pub struct PropertyOptions<'a> {
     /// Hints about property types.
-    pub hints: &'a HashMap<String, String>,
+    pub hints: &'a HashMap<String, HintValue>,
     /// Tracks the property tree location in a GVAS file.
     pub properties_stack: &'a mut Vec<String>,
     /// Custom versions
     pub custom_versions: &'a HashableIndexMap<Guid, u32>,
+    /// Package file versions
+    pub package_version: PackageVersion,
+    /// Options controlling how properties are read.
+    pub read_options: &'a ReadOptions,
+    /// Options controlling how properties are written.
+    pub write_options: &'a WriteOptions,
 }
 
 impl PropertyOptions<'_> {
@@ -463,6 +469,340 @@ impl PropertyOptions<'_> {
     {
         self.get_custom_version::<T>().version >= required.into()
     }
+
+    /// Check for UE4 package file version support
+    #[inline]
+    pub fn supports_package_version(&self, required: EUnrealEngineObjectUE4Version) -> bool {
+        self.package_version.ue4 >= required as u32
+    }
+
+    /// Fails if property tags use complete type names, see
+    /// [`PackageVersion::has_complete_type_names`].
+    #[inline]
+    pub(crate) fn check_tag_layout<R: Seek>(&self, reader: &mut R) -> Result<(), Error> {
+        if self.package_version.has_complete_type_names() {
+            Err(Error::unsupported(DeserializeError::invalid_property(
+                complete_type_names_unsupported(self.package_version),
+                reader,
+            )))?
+        }
+        Ok(())
+    }
+
+    /// Fails if property tags use complete type names, see
+    /// [`PackageVersion::has_complete_type_names`].
+    #[inline]
+    fn check_write_tag_layout(&self) -> Result<(), Error> {
+        if self.package_version.has_complete_type_names() {
+            Err(Error::unsupported(SerializeError::invalid_value(
+                complete_type_names_unsupported(self.package_version),
+            )))?
+        }
+        Ok(())
+    }
+
+    /// Read the struct GUID of a property tag, which is only present in packages from UE4
+    /// version 441 until complete type names.
+    #[inline]
+    pub(crate) fn read_struct_guid<R: Read + Seek>(&self, reader: &mut R) -> Result<Guid, Error> {
+        self.check_tag_layout(reader)?;
+        match self.supports_package_version(EUnrealEngineObjectUE4Version::StructGuidInPropertyTag)
+        {
+            true => Ok(reader.read_guid()?),
+            false => Ok(Guid::default()),
+        }
+    }
+
+    /// Write the struct GUID of a property tag, if the package version stores one.
+    #[inline]
+    pub(crate) fn write_struct_guid<W: Write>(
+        &self,
+        writer: &mut W,
+        guid: &Guid,
+    ) -> Result<usize, Error> {
+        self.check_write_tag_layout()?;
+        match self.supports_package_version(EUnrealEngineObjectUE4Version::StructGuidInPropertyTag)
+        {
+            true => {
+                writer.write_guid(guid)?;
+                Ok(16)
+            }
+            false => Ok(0),
+        }
+    }
+
+    /// Returns `true` if the property on top of the properties stack is left out by
+    /// [`ReadOptions::root_properties`].
+    ///
+    /// Root properties and the fields of structs leading to a selected path are left out unless
+    /// they are on such a path. Everything inside a selected property is read, and so is
+    /// everything reached through other properties, like the elements of an array.
+    pub(crate) fn is_filtered_out(&self) -> bool {
+        let Some(paths) = &self.read_options.root_properties else {
+            return false;
+        };
+        let depth = self.properties_stack.len();
+        let mut parent_selected = depth == 1;
+        for path in paths {
+            match match_path(self.properties_stack, path) {
+                // The path leads to or into this property
+                (_, true) => return false,
+                (matched, false) if matched == depth => return false,
+                (matched, false) => parent_selected |= matched + 1 == depth,
+            }
+        }
+        parent_selected
+    }
+
+    /// Add data read between `start` and `end` to [`ReadOptions::report`], if it is set.
+    pub(crate) fn report(&self, start: u64, end: u64, reason: ReportReason) {
+        if let Some(report) = &self.read_options.report {
+            report.push(ReportEntry {
+                path: self.properties_stack.join("."),
+                offset: start,
+                length: end.saturating_sub(start),
+                reason,
+            });
+        }
+    }
+
+    /// Read a string value, decoding single-byte strings with [`ReadOptions::codepage`].
+    #[inline]
+    pub(crate) fn read_fstring<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+    ) -> Result<Option<String>, Error> {
+        reader.read_fstring_limited(
+            self.read_options.codepage,
+            self.read_options.string_len_limit(),
+        )
+    }
+
+    /// Read a name, which can't be empty.
+    #[inline]
+    pub(crate) fn read_string<R: Read + Seek>(&self, reader: &mut R) -> Result<String, Error> {
+        match reader.read_fstring_limited(Codepage::Utf8, self.read_options.string_len_limit())? {
+            Some(str) => Ok(str),
+            None => Err(DeserializeError::InvalidString(
+                0,
+                reader.stream_position()?,
+            ))?,
+        }
+    }
+
+    /// Read a property or struct type name, well-known names are returned without allocating.
+    #[inline]
+    pub(crate) fn read_type_name<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+    ) -> Result<Cow<'static, str>, Error> {
+        reader.read_type_name_limited(self.read_options.string_len_limit())
+    }
+
+    /// Read a type name to store in a property.
+    ///
+    /// Well-known names share a static copy, the others are interned with
+    /// [`ReadOptions::name_table`].
+    #[inline]
+    pub(crate) fn read_stored_type_name<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+    ) -> Result<Arc<str>, Error> {
+        Ok(match self.read_type_name(reader)? {
+            Cow::Borrowed(name) => type_names::shared(name).unwrap_or_else(|| Arc::from(name)),
+            Cow::Owned(name) => self.intern(name),
+        })
+    }
+
+    /// Read a name which may be empty, interned with [`ReadOptions::name_table`].
+    #[inline]
+    pub(crate) fn read_name<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+    ) -> Result<Option<Arc<str>>, Error> {
+        Ok(self.read_fstring(reader)?.map(|name| self.intern(name)))
+    }
+
+    /// Share `name` with equal names when [`ReadOptions::name_table`] is set.
+    #[inline]
+    pub(crate) fn intern(&self, name: String) -> Arc<str> {
+        match &self.read_options.name_table {
+            Some(name_table) => name_table.intern(&name),
+            None => Arc::from(name),
+        }
+    }
+
+    /// Write a string value, encoding it with [`WriteOptions::codepage`].
+    #[inline]
+    pub(crate) fn write_fstring<W: Write>(
+        &self,
+        writer: &mut W,
+        v: Option<&str>,
+    ) -> Result<usize, Error> {
+        writer.write_fstring_in(v, self.write_options.codepage)
+    }
+
+    /// Read the value byte of a `BoolProperty` tag, which complete type names replaced with a
+    /// tag flag.
+    #[inline]
+    pub(crate) fn read_tag_bool<R: Read + Seek>(&self, reader: &mut R) -> Result<bool, Error> {
+        self.check_tag_layout(reader)?;
+        reader.read_bool()
+    }
+
+    /// Write the value byte of a `BoolProperty` tag.
+    #[inline]
+    pub(crate) fn write_tag_bool<W: Write>(
+        &self,
+        writer: &mut W,
+        value: bool,
+    ) -> Result<usize, Error> {
+        self.check_write_tag_layout()?;
+        writer.write_bool(value)?;
+        Ok(1)
+    }
+
+    /// Read the property GUID flag ending a property tag, if the package version stores one.
+    #[inline]
+    pub(crate) fn read_property_guid_flag<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(), Error> {
+        self.check_tag_layout(reader)?;
+        if self.supports_package_version(EUnrealEngineObjectUE4Version::PropertyGuidInPropertyTag) {
+            let terminator = reader.read_u8()?;
+            if terminator != 0 {
+                let position = reader.stream_position()? - 1;
+                Err(DeserializeError::InvalidSeparator(terminator, position))?
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the property GUID flag ending a property tag, if the package version stores one.
+    #[inline]
+    pub(crate) fn write_property_guid_flag<W: Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, Error> {
+        self.check_write_tag_layout()?;
+        match self
+            .supports_package_version(EUnrealEngineObjectUE4Version::PropertyGuidInPropertyTag)
+        {
+            true => {
+                writer.write_u8(0)?;
+                Ok(1)
+            }
+            false => Ok(0),
+        }
+    }
+}
+
+/// The reason property tags with complete type names can't be read or written.
+pub(crate) fn complete_type_names_unsupported(package_version: PackageVersion) -> String {
+    format!(
+        "Property tags with complete type names (UE5 package version {}) are not supported",
+        package_version.ue5
+    )
+}
+
+/// Matches a properties stack against a `.` separated path, in which each struct leading to a
+/// property is followed by its type name like the hint paths of [`Hints`](crate::hints::Hints).
+///
+/// Returns the number of stack entries matched, and `true` if the whole path was matched.
+fn match_path(stack: &[String], path: &str) -> (usize, bool) {
+    let mut segments = path.split('.').enumerate().flat_map(|(index, name)| {
+        (index > 0)
+            .then_some(PropertyKind::StructProperty.as_str())
+            .into_iter()
+            .chain(Some(name))
+    });
+    let mut matched = 0;
+    for entry in stack {
+        match segments.next() {
+            Some(segment) if segment == entry => matched += 1,
+            Some(_) => return (matched, false),
+            None => return (matched, true),
+        }
+    }
+    (matched, segments.next().is_none())
+}
+
+/// Write options of [`SharedParseContext::options`], which are only used for reading.
+static PARSE_WRITE_OPTIONS: WriteOptions = WriteOptions {
+    trailing_padding: TrailingPadding::Preserve,
+    backup: false,
+    precision_mismatch: PrecisionMismatch::Error,
+    codepage: Codepage::Utf8,
+    sort_maps: false,
+    palworld_compression_level: None,
+    palworld_compression: None,
+    palworld_buffer_size: None,
+};
+
+/// Read-only parsing state that can be shared between threads.
+///
+/// Each thread creates its own [`PropertyOptions`] with [`SharedParseContext::options`], only the
+/// properties stack is exclusive to a single parse.
+#[derive(Debug, Clone, Default)]
+pub struct SharedParseContext {
+    /// Hints about property types.
+    pub hints: Arc<HashMap<String, HintValue>>,
+    /// Custom versions
+    pub custom_versions: Arc<HashableIndexMap<Guid, u32>>,
+    /// Package file versions
+    pub package_version: PackageVersion,
+    /// Options controlling how properties are read.
+    pub read_options: Arc<ReadOptions>,
+}
+
+impl SharedParseContext {
+    /// Creates a new `SharedParseContext` instance.
+    #[inline]
+    pub fn new(
+        hints: Arc<HashMap<String, HintValue>>,
+        custom_versions: Arc<HashableIndexMap<Guid, u32>>,
+    ) -> Self {
+        SharedParseContext {
+            hints,
+            custom_versions,
+            package_version: PackageVersion::default(),
+            read_options: Arc::default(),
+        }
+    }
+
+    /// Creates [`PropertyOptions`] borrowing from this context.
+    #[inline]
+    pub fn options<'a>(&'a self, properties_stack: &'a mut Vec<String>) -> PropertyOptions<'a> {
+        PropertyOptions {
+            hints: &self.hints,
+            properties_stack,
+            custom_versions: &self.custom_versions,
+            package_version: self.package_version,
+            read_options: &self.read_options,
+            write_options: &PARSE_WRITE_OPTIONS,
+        }
+    }
+}
+
+/// Zero-length bodies, which some games write for optional properties.
+///
+/// Properties that can be empty keep an `empty_body` flag when they are read from a zero-length
+/// body. While the flag is set and the value is still empty, the property is written back
+/// without a body, so the save round-trips unchanged.
+pub trait EmptyBody: Sized {
+    /// Returns the value of a zero-length body, `None` if the property can't be empty.
+    #[inline]
+    fn empty_body() -> Option<Self> {
+        None
+    }
+
+    /// Returns `true` if this value was read from a zero-length body and is still empty, so it
+    /// is written back without a body.
+    #[inline]
+    fn is_empty_body(&self) -> bool {
+        false
+    }
 }
 
 /// Property traits.
@@ -492,6 +832,7 @@ pub trait PropertyTrait: Debug + Clone + PartialEq + Eq + Hash {
     serde(tag = "type")
 )]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum Property {
     /// An `ArrayProperty`.
     ArrayProperty,
@@ -556,68 +897,207 @@ impl Property {
         options: &mut PropertyOptions,
         suggested_length: Option<u32>,
     ) -> Result<Self, Error> {
+        let kind = PropertyKind::from_type_name(value_type);
         let _stack_entry = ScopedStackEntry::new(options.properties_stack, value_type.to_string());
-        match value_type {
-            "Int8Property" => Ok(Int8Property::read(cursor, include_header)?.into()),
-            "ByteProperty" => {
-                Ok(ByteProperty::read(cursor, include_header, suggested_length)?.into())
-            }
-            "Int16Property" => Ok(Int16Property::read(cursor, include_header)?.into()),
-            "UInt16Property" => Ok(UInt16Property::read(cursor, include_header)?.into()),
-            "IntProperty" => Ok(IntProperty::read(cursor, include_header)?.into()),
-            "UInt32Property" => Ok(UInt32Property::read(cursor, include_header)?.into()),
-            "Int64Property" => Ok(Int64Property::read(cursor, include_header)?.into()),
-            "UInt64Property" => Ok(UInt64Property::read(cursor, include_header)?.into()),
-            "FloatProperty" => Ok(FloatProperty::read(cursor, include_header)?.into()),
-            "DoubleProperty" => Ok(DoubleProperty::read(cursor, include_header)?.into()),
-            "BoolProperty" => Ok(BoolProperty::read(cursor, include_header)?.into()),
-            "EnumProperty" => Ok(EnumProperty::read(cursor, include_header)?.into()),
-            "StrProperty" => Ok(StrProperty::read(cursor, include_header)?.into()),
-            "TextProperty" => Ok(TextProperty::read(cursor, include_header, options)?.into()),
-            "NameProperty" => Ok(NameProperty::read(cursor, include_header)?.into()),
-            "ObjectProperty" => Ok(ObjectProperty::read(cursor, include_header)?.into()),
-            "DelegateProperty" => Ok(DelegateProperty::read(cursor, include_header)?.into()),
-            "MulticastInlineDelegateProperty" => {
-                Ok(MulticastInlineDelegateProperty::read(cursor, include_header)?.into())
-            }
-            "MulticastSparseDelegateProperty" => {
-                Ok(MulticastSparseDelegateProperty::read(cursor, include_header)?.into())
-            }
-            "FieldPathProperty" => Ok(FieldPathProperty::read(cursor, include_header)?.into()),
-            "StructProperty" => match include_header {
+        Self::read_kind(
+            cursor,
+            value_type,
+            kind,
+            include_header,
+            options,
+            suggested_length,
+        )
+        .map_err(|error| error.in_property(options.properties_stack))
+    }
+
+    /// Skips a property with a tag using the length stored in the tag, without parsing its body.
+    pub(crate) fn skip<R: Read + Seek>(
+        cursor: &mut R,
+        value_type: &str,
+        options: &PropertyOptions,
+    ) -> Result<(), Error> {
+        let (_, length) = UnknownProperty::read_tag(cursor, value_type, options)?;
+
+        // Bodies are read instead of seeking over them, so readers which can't seek are skipped
+        // and the digest covers every byte
+        let skipped = io::copy(
+            &mut cursor.by_ref().take(u64::from(length)),
+            &mut io::sink(),
+        )?;
+        if skipped != u64::from(length) {
+            Err(io::Error::from(io::ErrorKind::UnexpectedEof))?
+        }
+        Ok(())
+    }
+
+    /// Reads a property left out by [`ReadOptions::root_properties`] without parsing its body.
+    ///
+    /// The property is kept as an [`UnknownProperty`] holding its raw tag and body if `keep` is
+    /// set, and skipped otherwise.
+    pub(crate) fn read_filtered_out<R: Read + Seek>(
+        cursor: &mut R,
+        value_type: &str,
+        options: &PropertyOptions,
+        keep: bool,
+    ) -> Result<Option<Self>, Error> {
+        let start = cursor.stream_position()?;
+        let (property, reason) = match keep {
+            true => {
+                let property =
+                    UnknownProperty::read_with_raw_tag(cursor, value_type.to_string(), options)?;
+                (Some(property.into()), ReportReason::RawProperty)
+            }
+            false => {
+                Property::skip(cursor, value_type, options)?;
+                (None, ReportReason::SkippedProperty)
+            }
+        };
+        let end = cursor.stream_position()?;
+        options.report(start, end, reason);
+        Ok(property)
+    }
+
+    /// Reads a property after its type has been pushed onto the properties stack.
+    fn read_kind<R: Read + Seek>(
+        cursor: &mut R,
+        value_type: &str,
+        kind: Option<PropertyKind>,
+        include_header: bool,
+        options: &mut PropertyOptions,
+        mut suggested_length: Option<u32>,
+    ) -> Result<Self, Error> {
+        // Only values without a property tag are read using hints
+        let hints = options.hints;
+        let hint = match include_header || hints.is_empty() {
+            true => None,
+            false => hints.get(&options.properties_stack.join(".")),
+        };
+        match hint {
+            Some(HintValue::ElementWidth(width)) => suggested_length = Some(*width),
+            Some(HintValue::Raw(width)) => {
+                let Some(width) = width.or(suggested_length) else {
+                    Err(DeserializeError::MissingHint(
+                        value_type.into(),
+                        options.properties_stack.join(".").into_boxed_str(),
+                        cursor.stream_position()?,
+                    ))?
+                };
+                return Ok(UnknownProperty::read_with_length(
+                    cursor,
+                    value_type.to_string(),
+                    width,
+                )?
+                .into());
+            }
+            Some(HintValue::Struct(_)) | None => {}
+        }
+
+        let Some(kind) = kind else {
+            let start = cursor.stream_position()?;
+            let property = match (include_header, suggested_length) {
+                (true, _) if options.read_options.raw_passthrough => {
+                    UnknownProperty::read_with_raw_tag(cursor, value_type.to_string(), options)?
+                }
+                (true, _) => {
+                    UnknownProperty::read_with_header(cursor, value_type.to_string(), options)?
+                }
+                (false, Some(suggested_length)) => UnknownProperty::read_with_length(
+                    cursor,
+                    value_type.to_string(),
+                    suggested_length,
+                )?,
+                (false, None) => Err(DeserializeError::invalid_property(value_type, cursor))?,
+            };
+            let end = cursor.stream_position()?;
+            options.report(
+                start,
+                end,
+                ReportReason::UnknownProperty(value_type.to_string()),
+            );
+            return Ok(property.into());
+        };
+        match kind {
+            PropertyKind::Int8Property => {
+                Ok(Int8Property::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::ByteProperty => {
+                Ok(ByteProperty::read(cursor, include_header, options, suggested_length)?.into())
+            }
+            PropertyKind::Int16Property => {
+                Ok(Int16Property::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::UInt16Property => {
+                Ok(UInt16Property::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::IntProperty => {
+                Ok(IntProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::UInt32Property => {
+                Ok(UInt32Property::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::Int64Property => {
+                Ok(Int64Property::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::UInt64Property => {
+                Ok(UInt64Property::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::FloatProperty => {
+                Ok(FloatProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::DoubleProperty => {
+                Ok(DoubleProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::BoolProperty => {
+                Ok(BoolProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::EnumProperty => {
+                Ok(EnumProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::StrProperty => {
+                Ok(StrProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::TextProperty => {
+                Ok(TextProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::NameProperty => {
+                Ok(NameProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::ObjectProperty => {
+                Ok(ObjectProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::DelegateProperty => {
+                Ok(DelegateProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::MulticastInlineDelegateProperty => {
+                Ok(MulticastInlineDelegateProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::MulticastSparseDelegateProperty => {
+                Ok(MulticastSparseDelegateProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::FieldPathProperty => {
+                Ok(FieldPathProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::StructProperty => match include_header {
                 true => Ok(StructProperty::read(cursor, include_header, options)?.into()),
                 false => {
-                    let struct_path = options.properties_stack.join(".");
-                    let Some(hint) = options.hints.get(&struct_path) else {
+                    let Some(HintValue::Struct(hint)) = hint else {
                         Err(DeserializeError::MissingHint(
                             "StructProperty".into(),
-                            struct_path.into_boxed_str(),
+                            options.properties_stack.join(".").into_boxed_str(),
                             cursor.stream_position()?,
                         ))?
                     };
                     Ok(StructProperty::read_body(cursor, hint, options)?.into())
                 }
             },
-            "ArrayProperty" => Ok(ArrayProperty::read(cursor, include_header, options)?.into()),
-            "SetProperty" => Ok(SetProperty::read(cursor, include_header, options)?.into()),
-            "MapProperty" => Ok(MapProperty::read(cursor, include_header, options)?.into()),
-            _ => {
-                if include_header {
-                    return Ok(
-                        UnknownProperty::read_with_header(cursor, value_type.to_string())?.into(),
-                    );
-                }
-
-                if let Some(suggested_length) = suggested_length {
-                    return Ok(UnknownProperty::read_with_length(
-                        cursor,
-                        value_type.to_string(),
-                        suggested_length,
-                    )?
-                    .into());
-                }
-
-                Err(DeserializeError::invalid_property(value_type, cursor))?
+            PropertyKind::ArrayProperty => {
+                Ok(ArrayProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::SetProperty => {
+                Ok(SetProperty::read(cursor, include_header, options)?.into())
+            }
+            PropertyKind::MapProperty => {
+                Ok(MapProperty::read(cursor, include_header, options)?.into())
             }
         }
     }
@@ -653,6 +1133,164 @@ impl Property {
     make_matcher!(SetProperty, get_set, get_set_mut);
     make_matcher!(StrProperty, get_str, get_str_mut);
     make_matcher!(StructProperty, get_struct, get_struct_mut);
+    make_matcher!(StructPropertyValue, get_struct_value, get_struct_value_mut);
     make_matcher!(TextProperty, get_text, get_text_mut);
     make_matcher!(UnknownProperty, get_unknown, get_unknown_mut);
+
+    /// Retrieves the value of a `Guid` struct, stored either as a `StructProperty` or as a
+    /// `StructPropertyValue`.
+    #[inline]
+    pub fn get_guid(&self) -> Option<Guid> {
+        match self {
+            Self::StructProperty(property) => property.as_guid(),
+            Self::StructPropertyValue(value) => value.get_guid().copied(),
+            _ => None,
+        }
+    }
+
+    /// Groups this property by the kind of value it holds.
+    #[inline]
+    pub fn group(&self) -> PropertyGroup<'_> {
+        PropertyGroup::from(self)
+    }
+
+    /// Retrieves this property as a single value.
+    #[inline]
+    pub fn as_scalar(&self) -> Option<ScalarProperty<'_>> {
+        ScalarProperty::from_property(self)
+    }
+
+    /// Retrieves this property as an array, set or map.
+    #[inline]
+    pub fn as_container(&self) -> Option<ContainerProperty<'_>> {
+        ContainerProperty::from_property(self)
+    }
+
+    /// Retrieves this property as a `StructProperty` or `StructPropertyValue`.
+    #[inline]
+    pub fn as_struct(&self) -> Option<StructLikeProperty<'_>> {
+        StructLikeProperty::from_property(self)
+    }
+
+    /// Drops this property without recursing into the properties nested in it.
+    ///
+    /// Dropping a very deep tree, such as one read from an adversarial save, recursively can
+    /// overflow the stack. The nested properties are moved to a heap allocated stack and dropped
+    /// one at a time instead.
+    #[inline]
+    pub fn drop_iteratively(self) {
+        struct_property::drop_iteratively(self);
+    }
+}
+
+macro_rules! impl_from_value {
+    ($($ty:ty => $property:ident),+ $(,)?) => {
+        $(
+            impl From<$ty> for Property {
+                #[doc = concat!("Creates a `", stringify!($property), "`.")]
+                #[inline]
+                fn from(value: $ty) -> Self {
+                    Property::$property($property::new(value))
+                }
+            }
+        )+
+    };
+}
+
+impl_from_value!(
+    bool => BoolProperty,
+    i8 => Int8Property,
+    i16 => Int16Property,
+    i32 => IntProperty,
+    i64 => Int64Property,
+    u16 => UInt16Property,
+    u32 => UInt32Property,
+    u64 => UInt64Property,
+    f32 => FloatProperty,
+    f64 => DoubleProperty,
+);
+
+impl From<u8> for Property {
+    /// Creates a `ByteProperty` without an enum name.
+    #[inline]
+    fn from(value: u8) -> Self {
+        Property::ByteProperty(ByteProperty::new_byte(None, value))
+    }
+}
+
+impl From<&str> for Property {
+    /// Creates a `StrProperty`.
+    #[inline]
+    fn from(value: &str) -> Self {
+        Property::StrProperty(StrProperty::from(value))
+    }
+}
+
+impl From<String> for Property {
+    /// Creates a `StrProperty`.
+    #[inline]
+    fn from(value: String) -> Self {
+        Property::StrProperty(StrProperty::from(value))
+    }
+}
+
+impl From<Vec<bool>> for Property {
+    /// Creates an `ArrayProperty` of `BoolProperty` values.
+    #[inline]
+    fn from(bools: Vec<bool>) -> Self {
+        Property::ArrayProperty(ArrayProperty::Bools { bools })
+    }
+}
+
+impl From<Vec<u8>> for Property {
+    /// Creates an `ArrayProperty` of `ByteProperty` values.
+    #[inline]
+    fn from(bytes: Vec<u8>) -> Self {
+        Property::ArrayProperty(ArrayProperty::Bytes {
+            bytes: bytes.into(),
+        })
+    }
+}
+
+impl From<Arc<[u8]>> for Property {
+    /// Creates an `ArrayProperty` of `ByteProperty` values sharing `bytes`.
+    #[inline]
+    fn from(bytes: Arc<[u8]>) -> Self {
+        Property::ArrayProperty(ArrayProperty::Bytes { bytes })
+    }
+}
+
+impl From<Vec<i32>> for Property {
+    /// Creates an `ArrayProperty` of `IntProperty` values.
+    #[inline]
+    fn from(ints: Vec<i32>) -> Self {
+        Property::ArrayProperty(ArrayProperty::Ints { ints })
+    }
+}
+
+impl From<Vec<f32>> for Property {
+    /// Creates an `ArrayProperty` of `FloatProperty` values.
+    #[inline]
+    fn from(floats: Vec<f32>) -> Self {
+        let floats = floats.into_iter().map(OrderedFloat).collect();
+        Property::ArrayProperty(ArrayProperty::Floats { floats })
+    }
+}
+
+impl From<Vec<String>> for Property {
+    /// Creates an `ArrayProperty` of `StrProperty` values.
+    #[inline]
+    fn from(strings: Vec<String>) -> Self {
+        let strings = strings.into_iter().map(Some).collect();
+        Property::ArrayProperty(ArrayProperty::Strings { strings })
+    }
+}
+
+impl From<Vec<&str>> for Property {
+    /// Creates an `ArrayProperty` of `StrProperty` values.
+    #[inline]
+    fn from(strings: Vec<&str>) -> Self {
+        let strings = strings.into_iter().map(|s| Some(s.to_string())).collect();
+        Property::ArrayProperty(ArrayProperty::Strings { strings })
+    }
 }
@@ -1,10 +1,13 @@
-use std::io::{Cursor, Read, Seek, Write};
+use std::{
+    io::{Cursor, Read, Seek, Write},
+    sync::Arc,
+};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
-use crate::{cursor_ext::ReadExt, cursor_ext::WriteExt, error::Error};
+use crate::{cursor_ext::WriteExt, error::Error};
 
-use super::{impl_read, impl_read_header, impl_write, PropertyOptions, PropertyTrait};
+use super::{impl_read, impl_read_header, impl_write, EmptyBody, PropertyOptions, PropertyTrait};
 
 /// A property that holds a name.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -15,8 +18,16 @@ pub struct NameProperty {
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
     #[cfg_attr(feature = "serde", serde(default))]
     pub array_index: u32,
-    /// Name value.
-    pub value: Option<String>,
+    /// Name value, shared between equal names read with [`ReadOptions::name_table`].
+    ///
+    /// [`ReadOptions::name_table`]: crate::options::ReadOptions::name_table
+    pub value: Option<Arc<str>>,
+    /// Read from a zero-length body, see [`EmptyBody`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    pub empty_body: bool,
 }
 
 #[cfg(feature = "serde")]
@@ -42,18 +53,100 @@ impl From<Option<String>> for NameProperty {
     #[inline]
     fn from(value: Option<String>) -> Self {
         let array_index: u32 = 0;
-        NameProperty { array_index, value }
+        let value = value.map(Arc::from);
+        NameProperty {
+            array_index,
+            value,
+            empty_body: false,
+        }
+    }
+}
+
+/// Split a name into its base and number, following the `Name_Number` convention of `FName`.
+///
+/// The number is the decimal suffix after the last `_`. Suffixes with a leading zero, like
+/// `Name_05`, or that don't fit in an `i32` are part of the base, as in the engine.
+///
+/// ```
+/// use gvas::properties::name_property::split_name;
+///
+/// assert_eq!(split_name("Chest_12"), ("Chest", Some(12)));
+/// assert_eq!(split_name("Chest_012"), ("Chest_012", None));
+/// assert_eq!(split_name("Chest"), ("Chest", None));
+/// ```
+pub fn split_name(name: &str) -> (&str, Option<u32>) {
+    let split = name.rsplit_once('_').and_then(|(base, digits)| {
+        let valid = !digits.is_empty()
+            && digits.bytes().all(|digit| digit.is_ascii_digit())
+            && (digits.len() == 1 || !digits.starts_with('0'));
+        let number = digits.parse::<i32>().ok().filter(|_| valid)?;
+        Some((base, Some(number as u32)))
+    });
+    split.unwrap_or((name, None))
+}
+
+/// Join a base and number into a name, the inverse of [`split_name`].
+#[inline]
+pub fn join_name(base: &str, number: Option<u32>) -> String {
+    match number {
+        Some(number) => format!("{base}_{number}"),
+        None => base.to_string(),
     }
 }
 
 impl NameProperty {
-    impl_read!(array_index);
-    impl_read_header!(array_index);
+    /// The base and number of the name, see [`split_name`].
+    #[inline]
+    pub fn split(&self) -> Option<(&str, Option<u32>)> {
+        self.value.as_deref().map(split_name)
+    }
+
+    /// Compare the base of two names, ignoring their numbers.
+    ///
+    /// ```
+    /// use gvas::properties::name_property::NameProperty;
+    ///
+    /// let name = NameProperty::from("Chest_3");
+    /// assert!(name.eq_ignore_number(&NameProperty::from("Chest_12")));
+    /// assert!(name.eq_ignore_number(&NameProperty::from("Chest")));
+    /// assert!(!name.eq_ignore_number(&NameProperty::from("Crate_3")));
+    /// ```
+    #[inline]
+    pub fn eq_ignore_number(&self, other: &NameProperty) -> bool {
+        self.split().map(|(base, _)| base) == other.split().map(|(base, _)| base)
+    }
+
+    impl_read!(options, array_index);
+    impl_read_header!(options, array_index);
+
+    #[inline]
+    fn read_body<R: Read + Seek>(
+        cursor: &mut R,
+        options: &mut PropertyOptions,
+        array_index: u32,
+    ) -> Result<Self, Error> {
+        let value = options.read_name(cursor)?;
+        Ok(NameProperty {
+            array_index,
+            value,
+            empty_body: false,
+        })
+    }
+}
+
+impl EmptyBody for NameProperty {
+    #[inline]
+    fn empty_body() -> Option<Self> {
+        Some(NameProperty {
+            array_index: 0,
+            value: None,
+            empty_body: true,
+        })
+    }
 
     #[inline]
-    fn read_body<R: Read + Seek>(cursor: &mut R, array_index: u32) -> Result<Self, Error> {
-        let value = cursor.read_fstring()?;
-        Ok(NameProperty { array_index, value })
+    fn is_empty_body(&self) -> bool {
+        self.empty_body && self.value.is_none()
     }
 }
 
@@ -64,9 +157,9 @@ impl PropertyTrait for NameProperty {
     fn write_body<W: Write>(
         &self,
         cursor: &mut W,
-        _options: &mut PropertyOptions,
+        options: &mut PropertyOptions,
     ) -> Result<usize, Error> {
-        let len = cursor.write_fstring(self.value.as_deref())?;
+        let len = options.write_fstring(cursor, self.value.as_deref())?;
         Ok(len)
     }
 }
@@ -1,43 +1,73 @@
-use std::io::{Cursor, Read, Seek, Write};
+use std::{
+    io::{Cursor, Read, Seek, Write},
+    sync::Arc,
+};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
-use crate::{
-    cursor_ext::{ReadExt, WriteExt},
-    error::Error,
-};
+use crate::{cursor_ext::WriteExt, error::Error};
 
-use super::{impl_read, impl_read_header, impl_write, PropertyOptions, PropertyTrait};
+use super::{impl_read, impl_read_header, impl_write, EmptyBody, PropertyOptions, PropertyTrait};
 
 /// A property that describes a reference variable to another object which may be nil.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectProperty {
-    /// Object reference
-    pub value: String,
+    /// Object reference, shared between equal references read with
+    /// [`ReadOptions::name_table`].
+    ///
+    /// [`ReadOptions::name_table`]: crate::options::ReadOptions::name_table
+    pub value: Arc<str>,
+    /// Read from a zero-length body, see [`EmptyBody`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    pub empty_body: bool,
 }
 
 impl From<&str> for ObjectProperty {
     #[inline]
     fn from(value: &str) -> Self {
-        ObjectProperty::new(value.into())
+        ObjectProperty::new(value)
     }
 }
 
 impl ObjectProperty {
     /// Creates a new `ObjectProperty` instance
     #[inline]
-    pub fn new(value: String) -> Self {
-        ObjectProperty { value }
+    pub fn new(value: impl Into<Arc<str>>) -> Self {
+        ObjectProperty {
+            value: value.into(),
+            empty_body: false,
+        }
     }
 
-    impl_read!();
-    impl_read_header!();
+    impl_read!(options);
+    impl_read_header!(options);
+
+    #[inline]
+    fn read_body<R: Read + Seek>(
+        cursor: &mut R,
+        options: &mut PropertyOptions,
+    ) -> Result<Self, Error> {
+        let value = options.intern(options.read_string(cursor)?);
+        Ok(ObjectProperty::new(value))
+    }
+}
+
+impl EmptyBody for ObjectProperty {
+    #[inline]
+    fn empty_body() -> Option<Self> {
+        Some(ObjectProperty {
+            value: Arc::from(""),
+            empty_body: true,
+        })
+    }
 
     #[inline]
-    fn read_body<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
-        let value = cursor.read_string()?;
-        Ok(ObjectProperty { value })
+    fn is_empty_body(&self) -> bool {
+        self.empty_body && self.value.is_empty()
     }
 }
 
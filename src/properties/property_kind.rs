@@ -0,0 +1,118 @@
+use std::fmt::Display;
+
+macro_rules! make_property_kind {
+    ($($name:ident),+ $(,)?) => {
+        /// Property types which have a known serialized type name.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        pub enum PropertyKind {
+            $(
+                #[doc = concat!("`", stringify!($name), "`")]
+                $name,
+            )+
+        }
+
+        impl PropertyKind {
+            /// All property kinds.
+            pub const ALL: &'static [PropertyKind] = &[$(PropertyKind::$name,)+];
+
+            /// Returns the serialized type name of this kind.
+            #[inline]
+            pub const fn as_str(&self) -> &'static str {
+                match self {
+                    $(PropertyKind::$name => stringify!($name),)+
+                }
+            }
+        }
+    };
+}
+
+make_property_kind!(
+    ArrayProperty,
+    BoolProperty,
+    ByteProperty,
+    DelegateProperty,
+    DoubleProperty,
+    EnumProperty,
+    FieldPathProperty,
+    FloatProperty,
+    Int16Property,
+    Int64Property,
+    Int8Property,
+    IntProperty,
+    MapProperty,
+    MulticastInlineDelegateProperty,
+    MulticastSparseDelegateProperty,
+    NameProperty,
+    ObjectProperty,
+    SetProperty,
+    StrProperty,
+    StructProperty,
+    TextProperty,
+    UInt16Property,
+    UInt32Property,
+    UInt64Property,
+);
+
+impl PropertyKind {
+    /// Look up a property kind from its serialized type name.
+    ///
+    /// The candidate is selected using the first byte and the length of the name, so only one
+    /// full string comparison is performed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gvas::properties::property_kind::PropertyKind;
+    ///
+    /// assert_eq!(PropertyKind::from_type_name("IntProperty"), Some(PropertyKind::IntProperty));
+    /// assert_eq!(PropertyKind::from_type_name("Vector"), None);
+    /// ```
+    #[inline]
+    pub fn from_type_name(name: &str) -> Option<Self> {
+        let bytes = name.as_bytes();
+        let candidate = match (bytes.first()?, bytes.len()) {
+            (b'A', 13) => PropertyKind::ArrayProperty,
+            (b'B', 12) => match bytes[1] {
+                b'o' => PropertyKind::BoolProperty,
+                _ => PropertyKind::ByteProperty,
+            },
+            (b'D', 14) => PropertyKind::DoubleProperty,
+            (b'D', 16) => PropertyKind::DelegateProperty,
+            (b'E', 12) => PropertyKind::EnumProperty,
+            (b'F', 13) => PropertyKind::FloatProperty,
+            (b'F', 17) => PropertyKind::FieldPathProperty,
+            (b'I', 11) => PropertyKind::IntProperty,
+            (b'I', 12) => PropertyKind::Int8Property,
+            (b'I', 13) => match bytes[3] {
+                b'1' => PropertyKind::Int16Property,
+                _ => PropertyKind::Int64Property,
+            },
+            (b'M', 11) => PropertyKind::MapProperty,
+            (b'M', 31) => match bytes[9] {
+                b'I' => PropertyKind::MulticastInlineDelegateProperty,
+                _ => PropertyKind::MulticastSparseDelegateProperty,
+            },
+            (b'N', 12) => PropertyKind::NameProperty,
+            (b'O', 14) => PropertyKind::ObjectProperty,
+            (b'S', 11) => match bytes[1] {
+                b'e' => PropertyKind::SetProperty,
+                _ => PropertyKind::StrProperty,
+            },
+            (b'S', 14) => PropertyKind::StructProperty,
+            (b'T', 12) => PropertyKind::TextProperty,
+            (b'U', 14) => match bytes[4] {
+                b'1' => PropertyKind::UInt16Property,
+                b'3' => PropertyKind::UInt32Property,
+                _ => PropertyKind::UInt64Property,
+            },
+            _ => return None,
+        };
+        (candidate.as_str() == name).then_some(candidate)
+    }
+}
+
+impl Display for PropertyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
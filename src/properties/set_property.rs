@@ -3,12 +3,14 @@ use std::io::{Cursor, Read, Seek, Write};
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
 use crate::{
-    cursor_ext::{ReadExt, WriteExt},
+    cursor_ext::WriteExt,
     error::{DeserializeError, Error},
 };
 
 use super::{
-    impl_read_header, impl_write, impl_write_header_part, Property, PropertyOptions, PropertyTrait,
+    impl_read_header, impl_write, impl_write_header_part,
+    int_property::{ByteProperty, BytePropertyValue},
+    EmptyBody, Property, PropertyOptions, PropertyTrait,
 };
 
 /// A property that stores a set of properties.
@@ -18,6 +20,7 @@ pub struct SetProperty {
     /// Property type.
     pub property_type: String,
     /// Allocation flags.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub allocation_flags: u32,
     /// Properties.
     pub properties: Vec<Property>,
@@ -34,6 +37,37 @@ impl SetProperty {
         }
     }
 
+    /// Creates a new `SetProperty` of `ByteProperty` values.
+    #[inline]
+    pub fn new_bytes(allocation_flags: u32, bytes: &[u8]) -> Self {
+        let properties = bytes.iter().map(|&byte| Property::from(byte)).collect();
+        Self::new(String::from("ByteProperty"), allocation_flags, properties)
+    }
+
+    /// Returns the values of a set of plain `ByteProperty` values, or `None` for other sets.
+    ///
+    /// ```
+    /// use gvas::properties::set_property::SetProperty;
+    ///
+    /// let set = SetProperty::new_bytes(0, &[1, 2, 3]);
+    /// assert_eq!(set.get_bytes(), Some(vec![1, 2, 3]));
+    /// ```
+    pub fn get_bytes(&self) -> Option<Vec<u8>> {
+        if self.property_type != "ByteProperty" {
+            return None;
+        }
+        self.properties
+            .iter()
+            .map(|property| match property {
+                Property::ByteProperty(ByteProperty {
+                    name: None,
+                    value: BytePropertyValue::Byte(byte),
+                }) => Some(*byte),
+                _ => None,
+            })
+            .collect()
+    }
+
     #[inline]
     pub(crate) fn read<R: Read + Seek>(
         cursor: &mut R,
@@ -43,10 +77,10 @@ impl SetProperty {
         if include_header {
             Self::read_header(cursor, options)
         } else {
-            Err(DeserializeError::invalid_property(
+            Err(Error::unsupported(DeserializeError::invalid_property(
                 "SetProperty is not supported in arrays",
                 cursor,
-            ))?
+            )))?
         }
     }
 
@@ -64,9 +98,19 @@ impl SetProperty {
         let element_count = cursor.read_u32::<LittleEndian>()?;
         let mut properties: Vec<Property> = Vec::with_capacity(element_count as usize);
 
-        if element_count > 0 {
-            let total_bytes_per_property = (length - 8) / element_count;
-
+        // Like arrays, sets of bytes store the values without any per-element data. Sets of
+        // enums store each value as a string instead.
+        if property_type == "ByteProperty" {
+            let raw = u64::from(length.saturating_sub(8)) == u64::from(element_count);
+            for _ in 0..element_count {
+                properties.push(Property::from(match raw {
+                    true => ByteProperty::new_byte(None, cursor.read_u8()?),
+                    false => ByteProperty::new_namespaced(None, options.read_string(cursor)?),
+                }));
+            }
+        } else if let Some(total_bytes_per_property) =
+            length.saturating_sub(8).checked_div(element_count)
+        {
             for _ in 0..element_count {
                 properties.push(Property::new(
                     cursor,
@@ -86,6 +130,8 @@ impl SetProperty {
     }
 }
 
+impl EmptyBody for SetProperty {}
+
 impl PropertyTrait for SetProperty {
     impl_write!(SetProperty, (write_string, property_type));
 
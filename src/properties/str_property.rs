@@ -2,12 +2,9 @@ use std::io::{Cursor, Read, Seek, Write};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
-use crate::{
-    cursor_ext::{ReadExt, WriteExt},
-    error::Error,
-};
+use crate::{cursor_ext::WriteExt, error::Error};
 
-use super::{impl_read, impl_read_header, impl_write, PropertyOptions, PropertyTrait};
+use super::{impl_read, impl_read_header, impl_write, EmptyBody, PropertyOptions, PropertyTrait};
 
 /// A property that holds a GVAS string value.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -16,6 +13,12 @@ use super::{impl_read, impl_read_header, impl_write, PropertyOptions, PropertyTr
 pub struct StrProperty {
     /// Value of the GVAS string.
     pub value: Option<String>,
+    /// Read from a zero-length body, see [`EmptyBody`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    pub empty_body: bool,
 }
 
 impl From<&str> for StrProperty {
@@ -36,16 +39,37 @@ impl StrProperty {
     /// Creates a new `StrProperty` instance.
     #[inline]
     pub fn new(value: Option<String>) -> Self {
-        StrProperty { value }
+        StrProperty {
+            value,
+            empty_body: false,
+        }
     }
 
-    impl_read!();
-    impl_read_header!();
+    impl_read!(options);
+    impl_read_header!(options);
 
     #[inline]
-    fn read_body<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
-        let value = cursor.read_fstring()?;
-        Ok(StrProperty { value })
+    fn read_body<R: Read + Seek>(
+        cursor: &mut R,
+        options: &mut PropertyOptions,
+    ) -> Result<Self, Error> {
+        let value = options.read_fstring(cursor)?;
+        Ok(StrProperty::new(value))
+    }
+}
+
+impl EmptyBody for StrProperty {
+    #[inline]
+    fn empty_body() -> Option<Self> {
+        Some(StrProperty {
+            value: None,
+            empty_body: true,
+        })
+    }
+
+    #[inline]
+    fn is_empty_body(&self) -> bool {
+        self.empty_body && self.value.is_none()
     }
 }
 
@@ -56,9 +80,9 @@ impl PropertyTrait for StrProperty {
     fn write_body<W: Write>(
         &self,
         cursor: &mut W,
-        _: &mut PropertyOptions,
+        options: &mut PropertyOptions,
     ) -> Result<usize, Error> {
-        let len = cursor.write_fstring(self.value.as_deref())?;
+        let len = options.write_fstring(cursor, self.value.as_deref())?;
         Ok(len)
     }
 }
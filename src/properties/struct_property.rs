@@ -2,6 +2,7 @@ use std::{
     fmt::Debug,
     hash::Hash,
     io::{Cursor, Read, Seek, Write},
+    sync::Arc,
 };
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
@@ -11,16 +12,20 @@ use crate::{
     cursor_ext::{ReadExt, WriteExt},
     custom_version::FUE5ReleaseStreamObjectVersion,
     error::{DeserializeError, Error, SerializeError},
-    properties::{name_property::NameProperty, struct_types::LinearColor},
+    options::PrecisionMismatch,
+    properties::{
+        array_property::ArrayProperty, map_property::MapProperty, name_property::NameProperty,
+        struct_types::LinearColor,
+    },
     scoped_stack_entry::ScopedStackEntry,
     types::{map::HashableIndexMap, Guid},
 };
 
 use super::{
-    impl_write, impl_write_header_part, make_matcher,
+    make_matcher,
     struct_types::{
-        DateTime, IntPoint, QuatD, QuatF, RotatorD, RotatorF, Timespan, Vector2D, Vector2F,
-        VectorD, VectorF,
+        DateTime, Int64Vector, IntPoint, IntVector, IntVector4, QuatD, QuatF, RotatorD, RotatorF,
+        Timespan, UintVector, Vector2D, Vector2F, VectorD, VectorF,
     },
     Property, PropertyOptions, PropertyTrait,
 };
@@ -40,11 +45,19 @@ macro_rules! validate {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StructProperty {
     /// The unique identifier of the property.
-    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Guid::is_zero"))]
-    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Guid::is_zero",
+            deserialize_with = "crate::types::deserialize_guid"
+        )
+    )]
     pub guid: Guid,
-    /// Type name.
-    pub type_name: String,
+    /// Type name, shared between equal type names read with [`ReadOptions::name_table`].
+    ///
+    /// [`ReadOptions::name_table`]: crate::options::ReadOptions::name_table
+    pub type_name: Arc<str>,
     /// The value of the property.
     #[cfg_attr(feature = "serde", serde(flatten))]
     pub value: StructPropertyValue,
@@ -53,6 +66,7 @@ pub struct StructProperty {
 /// The possible values of a `StructProperty`.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum StructPropertyValue {
     /// A `Vector2F` value.
     Vector2F(Vector2F),
@@ -80,6 +94,14 @@ pub enum StructPropertyValue {
     LinearColor(LinearColor),
     /// An `IntPoint` value.
     IntPoint(IntPoint),
+    /// An `IntVector` value.
+    IntVector(IntVector),
+    /// An `IntVector4` value.
+    IntVector4(IntVector4),
+    /// A `UintVector` value.
+    UintVector(UintVector),
+    /// An `Int64Vector` value.
+    Int64Vector(Int64Vector),
     /// A custom struct value.
     CustomStruct(HashableIndexMap<String, Vec<Property>>),
 }
@@ -87,14 +109,20 @@ pub enum StructPropertyValue {
 impl StructProperty {
     /// Creates a new `StructProperty` instance.
     #[inline]
-    pub fn new(guid: Guid, type_name: String, value: StructPropertyValue) -> Self {
+    pub fn new(guid: Guid, type_name: impl Into<Arc<str>>, value: StructPropertyValue) -> Self {
         StructProperty {
             guid,
-            type_name,
+            type_name: type_name.into(),
             value,
         }
     }
 
+    /// Retrieves the value if this is a `Guid` struct.
+    #[inline]
+    pub fn as_guid(&self) -> Option<Guid> {
+        self.value.get_guid().copied()
+    }
+
     #[inline]
     pub(crate) fn read<R: Read + Seek>(
         cursor: &mut R,
@@ -124,25 +152,20 @@ impl StructProperty {
             Err(DeserializeError::InvalidArrayIndex(array_index, position))?
         }
 
-        let type_name = cursor.read_string()?;
-
-        let guid = cursor.read_guid()?;
+        let type_name = options.read_stored_type_name(cursor)?;
 
-        let terminator = cursor.read_u8()?;
-        if terminator != 0 {
-            let position = cursor.stream_position()? - 1;
-            Err(DeserializeError::InvalidTerminator(terminator, position))?
-        }
+        let guid = options.read_struct_guid(cursor)?;
+        options.read_property_guid_flag(cursor)?;
 
         let start = cursor.stream_position()?;
         let value = Self::read_body(cursor, &type_name, options)?;
         let end = cursor.stream_position()?;
         if end - start != length as u64 {
-            Err(DeserializeError::InvalidValueSize(
-                length as u64,
-                end - start,
-                start,
-            ))?
+            Err(DeserializeError::LengthMismatch {
+                expected: length as u64,
+                actual: end - start,
+                position: start,
+            })?
         }
 
         Ok(StructProperty {
@@ -167,41 +190,64 @@ impl StructProperty {
             "Timespan" => StructPropertyValue::read_timespan(cursor)?,
             "LinearColor" => StructPropertyValue::read_linearcolor(cursor)?,
             "IntPoint" => StructPropertyValue::read_intpoint(cursor)?,
+            "IntVector" => StructPropertyValue::read_intvector(cursor)?,
+            "IntVector4" => StructPropertyValue::read_intvector4(cursor)?,
+            "UintVector" => StructPropertyValue::read_uintvector(cursor)?,
+            "Int64Vector" => StructPropertyValue::read_int64vector(cursor)?,
             "Guid" => StructPropertyValue::read_guid(cursor)?,
             _ => StructPropertyValue::read_custom(cursor, options)?,
         };
         Ok(value)
     }
-
-    #[inline]
-    fn get_property_type(&self) -> Result<&str, Error> {
-        Ok(&self.type_name)
-    }
 }
 
-fn insert_property(map: &mut IndexMap<String, Vec<Property>>, key: String, property: Property) {
+fn insert_property<R: Seek>(
+    cursor: &mut R,
+    map: &mut IndexMap<String, Vec<Property>>,
+    key: String,
+    property: Property,
+) -> Result<(), Error> {
     let entry = map.entry(key).or_default();
-    #[cfg(debug_assertions)]
-    {
-        let array_index = match property {
-            // TODO: Move array_index to the Property layer
-            Property::NameProperty(NameProperty { array_index, .. }) => array_index,
-            _ => 0,
-        };
-        let actual_array_index = entry.len() as u32;
-        // Ensure that the position in the array matches the array_index value,
-        // otherwise this conversion would cause data loss.
-        assert_eq!(actual_array_index, array_index);
+    // TODO: Move array_index to the Property layer
+    if let Property::NameProperty(NameProperty { array_index, .. }) = property {
+        // The position in the array has to match the array_index value, otherwise this
+        // conversion would lose data
+        if entry.len() as u32 != array_index {
+            let position = cursor.stream_position()?;
+            Err(DeserializeError::InvalidArrayIndex(array_index, position))?
+        }
     }
     entry.push(property);
+    Ok(())
 }
 
 impl PropertyTrait for StructProperty {
-    impl_write!(
-        StructProperty,
-        (write_string, fn, get_property_type),
-        (write_guid, guid)
-    );
+    #[inline]
+    fn write<W: Write>(
+        &self,
+        cursor: &mut W,
+        include_header: bool,
+        options: &mut PropertyOptions,
+    ) -> Result<usize, Error> {
+        if !include_header {
+            return self.write_body(cursor, options);
+        }
+
+        let mut len = 8;
+        let buf = &mut Cursor::new(Vec::new());
+        len += self.write_body(buf, options)?;
+        let buf = buf.get_ref();
+
+        len += cursor.write_string("StructProperty")?;
+        cursor.write_u32::<LittleEndian>(buf.len() as u32)?;
+        cursor.write_u32::<LittleEndian>(0)?;
+        len += cursor.write_string(&self.type_name)?;
+        len += options.write_struct_guid(cursor, &self.guid)?;
+        len += options.write_property_guid_flag(cursor)?;
+        cursor.write_all(buf)?;
+
+        Ok(len)
+    }
 
     #[inline]
     fn write_body<W: Write>(
@@ -235,6 +281,27 @@ impl PropertyTrait for StructPropertyValue {
         cursor: &mut W,
         options: &mut PropertyOptions,
     ) -> Result<usize, Error> {
+        if options.write_options.precision_mismatch == PrecisionMismatch::Convert {
+            let large_world_coordinates =
+                options.supports_version(FUE5ReleaseStreamObjectVersion::LargeWorldCoordinates);
+            let mismatched = match self {
+                Self::Vector2F(_) | Self::VectorF(_) | Self::RotatorF(_) | Self::QuatF(_) => {
+                    large_world_coordinates
+                }
+                Self::Vector2D(_) | Self::VectorD(_) | Self::RotatorD(_) | Self::QuatD(_) => {
+                    !large_world_coordinates
+                }
+                _ => false,
+            };
+            if mismatched {
+                let mut converted = self.clone();
+                match large_world_coordinates {
+                    true => converted.to_double_precision(),
+                    false => converted.to_single_precision(),
+                };
+                return converted.write_body(cursor, options);
+            }
+        }
         match self {
             StructPropertyValue::Vector2F(vector) => {
                 validate!(
@@ -340,6 +407,31 @@ impl PropertyTrait for StructPropertyValue {
                 cursor.write_i32::<LittleEndian>(int_point.y)?;
                 Ok(8)
             }
+            StructPropertyValue::IntVector(int_vector) => {
+                cursor.write_i32::<LittleEndian>(int_vector.x)?;
+                cursor.write_i32::<LittleEndian>(int_vector.y)?;
+                cursor.write_i32::<LittleEndian>(int_vector.z)?;
+                Ok(12)
+            }
+            StructPropertyValue::IntVector4(int_vector) => {
+                cursor.write_i32::<LittleEndian>(int_vector.x)?;
+                cursor.write_i32::<LittleEndian>(int_vector.y)?;
+                cursor.write_i32::<LittleEndian>(int_vector.z)?;
+                cursor.write_i32::<LittleEndian>(int_vector.w)?;
+                Ok(16)
+            }
+            StructPropertyValue::UintVector(uint_vector) => {
+                cursor.write_u32::<LittleEndian>(uint_vector.x)?;
+                cursor.write_u32::<LittleEndian>(uint_vector.y)?;
+                cursor.write_u32::<LittleEndian>(uint_vector.z)?;
+                Ok(12)
+            }
+            StructPropertyValue::Int64Vector(int_vector) => {
+                cursor.write_i64::<LittleEndian>(int_vector.x)?;
+                cursor.write_i64::<LittleEndian>(int_vector.y)?;
+                cursor.write_i64::<LittleEndian>(int_vector.z)?;
+                Ok(24)
+            }
             StructPropertyValue::Guid(guid) => {
                 cursor.write_guid(guid)?;
                 Ok(16)
@@ -366,16 +458,22 @@ impl StructPropertyValue {
     ) -> Result<StructPropertyValue, Error> {
         let mut properties = HashableIndexMap::new();
         loop {
-            let property_name = cursor.read_string()?;
+            let property_name = options.read_string(cursor)?;
             if property_name == "None" {
                 break;
             }
-            let property_type = cursor.read_string()?;
+            let property_type = options.read_type_name(cursor)?;
             let _property_stack_entry =
                 ScopedStackEntry::new(options.properties_stack, property_name.clone());
 
-            let property = Property::new(cursor, &property_type, true, options, None)?;
-            insert_property(&mut properties, property_name, property);
+            let property = match options.is_filtered_out() {
+                true => Property::read_filtered_out(cursor, &property_type, options, true)
+                    .map_err(|error| error.in_property(options.properties_stack))?,
+                false => Some(Property::new(cursor, &property_type, true, options, None)?),
+            };
+            if let Some(property) = property {
+                insert_property(cursor, &mut properties, property_name, property)?;
+            }
         }
         Ok(StructPropertyValue::CustomStruct(properties))
     }
@@ -391,6 +489,39 @@ impl StructPropertyValue {
         )))
     }
 
+    fn read_intvector<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
+        Ok(Self::IntVector(IntVector::new(
+            cursor.read_i32::<LittleEndian>()?,
+            cursor.read_i32::<LittleEndian>()?,
+            cursor.read_i32::<LittleEndian>()?,
+        )))
+    }
+
+    fn read_intvector4<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
+        Ok(Self::IntVector4(IntVector4::new(
+            cursor.read_i32::<LittleEndian>()?,
+            cursor.read_i32::<LittleEndian>()?,
+            cursor.read_i32::<LittleEndian>()?,
+            cursor.read_i32::<LittleEndian>()?,
+        )))
+    }
+
+    fn read_uintvector<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
+        Ok(Self::UintVector(UintVector::new(
+            cursor.read_u32::<LittleEndian>()?,
+            cursor.read_u32::<LittleEndian>()?,
+            cursor.read_u32::<LittleEndian>()?,
+        )))
+    }
+
+    fn read_int64vector<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
+        Ok(Self::Int64Vector(Int64Vector::new(
+            cursor.read_i64::<LittleEndian>()?,
+            cursor.read_i64::<LittleEndian>()?,
+            cursor.read_i64::<LittleEndian>()?,
+        )))
+    }
+
     fn read_linearcolor<R: Read + Seek>(cursor: &mut R) -> Result<Self, Error> {
         Ok(Self::LinearColor(LinearColor::new(
             cursor.read_f32::<LittleEndian>()?,
@@ -483,6 +614,8 @@ impl StructPropertyValue {
             ))),
         }
     }
+    make_matcher!(Vector2F, get_vector2_f, get_vector2_f_mut);
+    make_matcher!(Vector2D, get_vector2_d, get_vector2_d_mut);
     make_matcher!(VectorF, get_vector_f, get_vector_f_mut);
     make_matcher!(VectorD, get_vector_d, get_vector_d_mut);
     make_matcher!(RotatorF, get_rotator_f, get_rotator_f_mut);
@@ -490,9 +623,67 @@ impl StructPropertyValue {
     make_matcher!(QuatF, get_quat_f, get_quat_f_mut);
     make_matcher!(QuatD, get_quat_d, get_quat_d_mut);
     make_matcher!(DateTime, get_date_time, get_date_time_mut);
+    make_matcher!(Timespan, get_timespan, get_timespan_mut);
+    make_matcher!(LinearColor, get_linear_color, get_linear_color_mut);
     make_matcher!(IntPoint, get_int_point, get_int_point_mut);
+    make_matcher!(IntVector, get_int_vector, get_int_vector_mut);
+    make_matcher!(IntVector4, get_int_vector4, get_int_vector4_mut);
+    make_matcher!(UintVector, get_uint_vector, get_uint_vector_mut);
+    make_matcher!(Int64Vector, get_int64_vector, get_int64_vector_mut);
     make_matcher!(Guid, get_guid, get_guid_mut);
 
+    /// Converts large world coordinate values to their single precision variants.
+    ///
+    /// Returns true if the value was changed.
+    pub fn to_single_precision(&mut self) -> bool {
+        *self = match self {
+            Self::Vector2D(v) => Self::Vector2F(Vector2F::new(v.x.0 as f32, v.y.0 as f32)),
+            Self::VectorD(v) => {
+                Self::VectorF(VectorF::new(v.x.0 as f32, v.y.0 as f32, v.z.0 as f32))
+            }
+            Self::RotatorD(r) => Self::RotatorF(RotatorF::new(
+                r.pitch.0 as f32,
+                r.yaw.0 as f32,
+                r.roll.0 as f32,
+            )),
+            Self::QuatD(q) => Self::QuatF(QuatF::new(
+                q.x.0 as f32,
+                q.y.0 as f32,
+                q.z.0 as f32,
+                q.w.0 as f32,
+            )),
+            _ => return false,
+        };
+        true
+    }
+
+    /// Converts single precision values to their large world coordinate variants.
+    ///
+    /// Returns true if the value was changed.
+    pub fn to_double_precision(&mut self) -> bool {
+        *self = match self {
+            Self::Vector2F(v) => Self::Vector2D(Vector2D::new(f64::from(v.x.0), f64::from(v.y.0))),
+            Self::VectorF(v) => Self::VectorD(VectorD::new(
+                f64::from(v.x.0),
+                f64::from(v.y.0),
+                f64::from(v.z.0),
+            )),
+            Self::RotatorF(r) => Self::RotatorD(RotatorD::new(
+                f64::from(r.pitch.0),
+                f64::from(r.yaw.0),
+                f64::from(r.roll.0),
+            )),
+            Self::QuatF(q) => Self::QuatD(QuatD::new(
+                f64::from(q.x.0),
+                f64::from(q.y.0),
+                f64::from(q.z.0),
+                f64::from(q.w.0),
+            )),
+            _ => return false,
+        };
+        true
+    }
+
     /// Retrieves the enum value as a `CustomStruct`.
     #[inline]
     pub fn get_custom_struct(&self) -> Option<&HashableIndexMap<String, Vec<Property>>> {
@@ -514,6 +705,115 @@ impl StructPropertyValue {
     }
 }
 
+#[cfg(feature = "glam")]
+impl StructPropertyValue {
+    /// Retrieves a `VectorF` or `VectorD` value as a double precision vector.
+    pub fn get_dvec3(&self) -> Option<glam::DVec3> {
+        match self {
+            Self::VectorF(vector) => Some((*vector).into()),
+            Self::VectorD(vector) => Some((*vector).into()),
+            _ => None,
+        }
+    }
+
+    /// Sets a `VectorF` or `VectorD` value, keeping the precision it was saved with.
+    ///
+    /// Returns false if this is not a vector value.
+    pub fn set_dvec3(&mut self, value: glam::DVec3) -> bool {
+        match self {
+            Self::VectorF(vector) => *vector = value.as_vec3().into(),
+            Self::VectorD(vector) => *vector = value.into(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Retrieves a quaternion or rotator value as a double precision quaternion.
+    pub fn get_dquat(&self) -> Option<glam::DQuat> {
+        match self {
+            Self::QuatF(quat) => Some((*quat).into()),
+            Self::QuatD(quat) => Some((*quat).into()),
+            Self::RotatorF(rotator) => Some(glam::Quat::from(*rotator).as_dquat()),
+            Self::RotatorD(rotator) => Some((*rotator).into()),
+            _ => None,
+        }
+    }
+
+    /// Sets a quaternion or rotator value, keeping the type and precision it was saved with.
+    ///
+    /// Returns false if this is not a quaternion or rotator value.
+    pub fn set_dquat(&mut self, value: glam::DQuat) -> bool {
+        match self {
+            Self::QuatF(quat) => *quat = value.as_quat().into(),
+            Self::QuatD(quat) => *quat = value.into(),
+            Self::RotatorF(rotator) => *rotator = value.as_quat().into(),
+            Self::RotatorD(rotator) => *rotator = value.into(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Drops `property` after moving the properties nested in it to a heap allocated stack, see
+/// [`Property::drop_iteratively`].
+pub(crate) fn drop_iteratively(property: Property) {
+    let mut stack = vec![property];
+    while let Some(mut property) = stack.pop() {
+        take_children(&mut property, &mut stack);
+    }
+}
+
+/// Moves the fields of a custom struct to `stack`.
+#[inline]
+fn take_fields(value: &mut StructPropertyValue, stack: &mut Vec<Property>) {
+    if let StructPropertyValue::CustomStruct(fields) = value {
+        stack.extend(std::mem::take(fields).0.into_values().flatten());
+    }
+}
+
+/// Moves the properties nested in `property` to `stack`, leaving it without children.
+fn take_children(property: &mut Property, stack: &mut Vec<Property>) {
+    match property {
+        Property::StructProperty(property) => take_fields(&mut property.value, stack),
+        Property::StructPropertyValue(value) => take_fields(value, stack),
+        Property::ArrayProperty(ArrayProperty::Structs { structs, .. }) => {
+            for value in structs {
+                take_fields(value, stack);
+            }
+        }
+        Property::ArrayProperty(ArrayProperty::SharedStructs { values, .. }) => {
+            stack.extend(std::mem::take(values).into_iter().flatten());
+        }
+        Property::ArrayProperty(ArrayProperty::Properties { properties, .. }) => {
+            stack.append(properties);
+        }
+        Property::SetProperty(property) => stack.append(&mut property.properties),
+        Property::MapProperty(MapProperty::EnumProperty {
+            enum_props: values, ..
+        })
+        | Property::MapProperty(MapProperty::NameProperty {
+            name_props: values, ..
+        })
+        | Property::MapProperty(MapProperty::StrProperty {
+            str_props: values, ..
+        }) => {
+            stack.extend(std::mem::take(values).0.into_values());
+        }
+        Property::MapProperty(MapProperty::Properties {
+            value, duplicates, ..
+        }) => {
+            let duplicates = duplicates
+                .drain(..)
+                .map(|duplicate| (duplicate.key, duplicate.value));
+            for (key, value) in std::mem::take(value).0.into_iter().chain(duplicates) {
+                stack.push(key);
+                stack.push(value);
+            }
+        }
+        _ => {}
+    }
+}
+
 impl From<Vector2F> for StructPropertyValue {
     #[inline]
     fn from(value: Vector2F) -> Self {
@@ -604,3 +904,31 @@ impl From<IntPoint> for StructPropertyValue {
         StructPropertyValue::IntPoint(int_point)
     }
 }
+
+impl From<IntVector> for StructPropertyValue {
+    #[inline]
+    fn from(int_vector: IntVector) -> Self {
+        StructPropertyValue::IntVector(int_vector)
+    }
+}
+
+impl From<IntVector4> for StructPropertyValue {
+    #[inline]
+    fn from(int_vector: IntVector4) -> Self {
+        StructPropertyValue::IntVector4(int_vector)
+    }
+}
+
+impl From<UintVector> for StructPropertyValue {
+    #[inline]
+    fn from(uint_vector: UintVector) -> Self {
+        StructPropertyValue::UintVector(uint_vector)
+    }
+}
+
+impl From<Int64Vector> for StructPropertyValue {
+    #[inline]
+    fn from(int_vector: Int64Vector) -> Self {
+        StructPropertyValue::Int64Vector(int_vector)
+    }
+}
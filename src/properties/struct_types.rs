@@ -176,3 +176,135 @@ make_struct!(
     (x, i32, "X value."),
     (y, i32, "Y value."),
 );
+
+make_struct!(
+    IntVector,
+    "A struct that stores a 3D integer vector.",
+    (x, i32, "X value."),
+    (y, i32, "Y value."),
+    (z, i32, "Z value."),
+);
+
+make_struct!(
+    IntVector4,
+    "A struct that stores a 4D integer vector.",
+    (x, i32, "X value."),
+    (y, i32, "Y value."),
+    (z, i32, "Z value."),
+    (w, i32, "W value."),
+);
+
+make_struct!(
+    UintVector,
+    "A struct that stores a 3D unsigned integer vector.",
+    (x, u32, "X value."),
+    (y, u32, "Y value."),
+    (z, u32, "Z value."),
+);
+
+make_struct!(
+    Int64Vector,
+    "A struct that stores a 3D 64-bit integer vector.",
+    (x, i64, "X value."),
+    (y, i64, "Y value."),
+    (z, i64, "Z value."),
+);
+
+/// Conversions to and from [glam](https://docs.rs/glam) types.
+///
+/// Single precision structs also convert to the double precision glam types, so code written for
+/// UE5 large world coordinates can handle saves from older engines.
+#[cfg(feature = "glam")]
+mod glam_conversions {
+    use glam::{
+        DQuat, DVec2, DVec3, EulerRot, I64Vec3, IVec2, IVec3, IVec4, Quat, UVec3, Vec2, Vec3, Vec4,
+    };
+
+    use super::{
+        Int64Vector, IntPoint, IntVector, IntVector4, LinearColor, QuatD, QuatF, RotatorD,
+        RotatorF, UintVector, Vector2D, Vector2F, VectorD, VectorF,
+    };
+
+    macro_rules! impl_glam {
+        ($name:ident, $glam:ty, $($field:ident),+) => {
+            impl From<$name> for $glam {
+                #[inline]
+                fn from(value: $name) -> Self {
+                    <$glam>::from_array([$(value.$field.into()),+])
+                }
+            }
+
+            impl From<$glam> for $name {
+                #[inline]
+                fn from(value: $glam) -> Self {
+                    let [$($field),+] = value.to_array();
+                    $name::new($($field),+)
+                }
+            }
+        };
+    }
+
+    impl_glam!(Vector2F, Vec2, x, y);
+    impl_glam!(Vector2D, DVec2, x, y);
+    impl_glam!(VectorF, Vec3, x, y, z);
+    impl_glam!(VectorD, DVec3, x, y, z);
+    impl_glam!(QuatF, Quat, x, y, z, w);
+    impl_glam!(QuatD, DQuat, x, y, z, w);
+    impl_glam!(LinearColor, Vec4, r, g, b, a);
+    impl_glam!(IntPoint, IVec2, x, y);
+    impl_glam!(IntVector, IVec3, x, y, z);
+    impl_glam!(IntVector4, IVec4, x, y, z, w);
+    impl_glam!(UintVector, UVec3, x, y, z);
+    impl_glam!(Int64Vector, I64Vec3, x, y, z);
+
+    macro_rules! impl_widening {
+        ($name:ident, $single:ty, $double:ty, $as_double:ident, $double_name:ident) => {
+            impl From<$name> for $double {
+                #[inline]
+                fn from(value: $name) -> Self {
+                    <$single>::from(value).$as_double()
+                }
+            }
+
+            impl From<$single> for $double_name {
+                #[inline]
+                fn from(value: $single) -> Self {
+                    $double_name::from(value.$as_double())
+                }
+            }
+        };
+    }
+
+    impl_widening!(Vector2F, Vec2, DVec2, as_dvec2, Vector2D);
+    impl_widening!(VectorF, Vec3, DVec3, as_dvec3, VectorD);
+    impl_widening!(QuatF, Quat, DQuat, as_dquat, QuatD);
+
+    // Unreal rotators are in degrees, rotating around Z (yaw), then Y (pitch), then X (roll),
+    // with pitch and roll in the opposite direction of a right handed rotation.
+    macro_rules! impl_rotator {
+        ($name:ident, $quat:ty) => {
+            impl From<$name> for $quat {
+                #[inline]
+                fn from(value: $name) -> Self {
+                    <$quat>::from_euler(
+                        EulerRot::ZYX,
+                        value.yaw.0.to_radians(),
+                        -value.pitch.0.to_radians(),
+                        -value.roll.0.to_radians(),
+                    )
+                }
+            }
+
+            impl From<$quat> for $name {
+                #[inline]
+                fn from(value: $quat) -> Self {
+                    let (yaw, pitch, roll) = value.to_euler(EulerRot::ZYX);
+                    $name::new(-pitch.to_degrees(), yaw.to_degrees(), -roll.to_degrees())
+                }
+            }
+        };
+    }
+
+    impl_rotator!(RotatorF, Quat);
+    impl_rotator!(RotatorD, DQuat);
+}
@@ -0,0 +1,526 @@
+//! Plain text for `FText` values
+//!
+//! [`FText::resolve`] builds the string a game would show for a text stored in a save. Format
+//! histories substitute their arguments, numbers, percentages, currencies, dates and times are
+//! formatted, and transforms are applied. Formatting follows the English (`en`) culture, as the
+//! culture data used by the engine isn't available, so the result is a best effort for texts
+//! in other cultures. Dates and times are shown in UTC.
+//!
+//! Format patterns support `{0}` and `{Name}` arguments, the `` ` `` escape character and the
+//! `plural` argument modifier, like `{Count} {Count}|plural(one=item,other=items)`. Other
+//! modifiers are left out of the result.
+//!
+//! [`FText::resolve`]: crate::properties::text_property::FText::resolve
+//!
+//! ```
+//! use gvas::properties::{
+//!     text_format::ResolveContext,
+//!     text_property::{FText, FTextHistory, FormatArgumentValue},
+//! };
+//! use gvas::types::map::HashableIndexMap;
+//!
+//! let text = FText {
+//!     flags: 0,
+//!     history: FTextHistory::NamedFormat {
+//!         source_format: Box::new(FText::new_none(
+//!             0,
+//!             Some(Some(String::from("{Count} {Count}|plural(one=coin,other=coins)"))),
+//!         )),
+//!         arguments: HashableIndexMap::from([(
+//!             String::from("Count"),
+//!             FormatArgumentValue::Int(1200),
+//!         )]),
+//!     },
+//! };
+//! assert_eq!(text.resolve(&ResolveContext::default()), "1200 coins");
+//! ```
+
+use crate::{
+    options::StringTableResolver,
+    properties::{
+        struct_types::DateTime,
+        text_property::{
+            DateTimeStyle, FText, FTextHistory, FormatArgumentValue, NumberFormattingOptions,
+            RoundingMode, TransformType,
+        },
+    },
+};
+
+/// Lookups used by [`FText::resolve`].
+#[derive(Debug, Clone, Default)]
+pub struct ResolveContext {
+    /// Looks up string table entries without a display string, by table id and key.
+    pub string_table_resolver: Option<StringTableResolver>,
+    /// Looks up localized texts by namespace and key, the source string is used if it returns
+    /// `None`.
+    pub localization_resolver: Option<StringTableResolver>,
+}
+
+impl FText {
+    /// The text shown to the player, see the [module documentation](self).
+    ///
+    /// Missing strings resolve to an empty string. String table entries that can't be looked up
+    /// resolve to their key.
+    pub fn resolve(&self, context: &ResolveContext) -> String {
+        match &self.history {
+            FTextHistory::Empty {} => String::new(),
+            FTextHistory::None {
+                culture_invariant_string,
+            } => culture_invariant_string.clone().unwrap_or_default(),
+            FTextHistory::Base {
+                namespace,
+                key,
+                source_string,
+            } => context
+                .localization_resolver
+                .as_ref()
+                .zip(key.as_deref())
+                .and_then(|(resolver, key)| {
+                    resolver.resolve(namespace.as_deref().unwrap_or_default(), key)
+                })
+                .or_else(|| source_string.clone())
+                .unwrap_or_default(),
+            FTextHistory::NamedFormat {
+                source_format,
+                arguments,
+            }
+            | FTextHistory::ArgumentFormat {
+                source_format,
+                arguments,
+            } => format_pattern(&source_format.resolve(context), |name| {
+                arguments
+                    .get(name)
+                    .or_else(|| arguments.get_ci(name))
+                    .map(|value| Argument::new(value, context))
+            }),
+            FTextHistory::OrderedFormat {
+                source_format,
+                arguments,
+            } => format_pattern(&source_format.resolve(context), |name| {
+                let index = name.parse::<usize>().ok()?;
+                arguments
+                    .get(index)
+                    .map(|value| Argument::new(value, context))
+            }),
+            FTextHistory::AsNumber {
+                source_value,
+                format_options,
+                ..
+            } => match Argument::new(source_value, context) {
+                Argument::Number(number) => {
+                    let options = format_options.unwrap_or_else(|| default_options(true, 3));
+                    format_number(number, &options)
+                }
+                Argument::Text(text) => text,
+            },
+            FTextHistory::AsPercent {
+                source_value,
+                format_options,
+                ..
+            } => match Argument::new(source_value, context) {
+                Argument::Number(number) => {
+                    let options = format_options.unwrap_or_else(|| default_options(true, 0));
+                    let percent = match number {
+                        Number::Integer(value) => Number::Integer(value * 100),
+                        Number::Float(value) => Number::Float(value * 100.0),
+                    };
+                    format!("{}%", format_number(percent, &options))
+                }
+                Argument::Text(text) => text,
+            },
+            FTextHistory::AsCurrency {
+                currency_code,
+                source_value,
+                format_options,
+                ..
+            } => {
+                let value = match Argument::new(source_value, context) {
+                    Argument::Number(number) => {
+                        let options = format_options.unwrap_or(NumberFormattingOptions {
+                            minimum_fractional_digits: 2,
+                            ..default_options(true, 2)
+                        });
+                        format_number(number, &options)
+                    }
+                    Argument::Text(text) => text,
+                };
+                match currency_code.as_deref() {
+                    Some("USD") => format!("${value}"),
+                    Some("EUR") => format!("€{value}"),
+                    Some("GBP") => format!("£{value}"),
+                    Some("JPY") => format!("¥{value}"),
+                    Some(code) if !code.is_empty() => format!("{code} {value}"),
+                    _ => value,
+                }
+            }
+            FTextHistory::AsDate {
+                date_time,
+                date_style,
+                ..
+            } => format_date(date_time, *date_style),
+            FTextHistory::AsTime {
+                source_date_time,
+                time_style,
+                ..
+            } => format_time(source_date_time, *time_style),
+            FTextHistory::AsDateTime {
+                source_date_time,
+                date_style,
+                time_style,
+                ..
+            } => format!(
+                "{} {}",
+                format_date(source_date_time, *date_style),
+                format_time(source_date_time, *time_style)
+            ),
+            FTextHistory::Transform {
+                source_text,
+                transform_type,
+            } => {
+                let text = source_text.resolve(context);
+                match transform_type {
+                    TransformType::ToLower => text.to_lowercase(),
+                    TransformType::ToUpper => text.to_uppercase(),
+                    TransformType::Other(_) => text,
+                }
+            }
+            FTextHistory::StringTableEntry {
+                table_id,
+                key,
+                display_string,
+            } => display_string
+                .0
+                .clone()
+                .or_else(|| {
+                    let resolver = context.string_table_resolver.as_ref()?;
+                    resolver.resolve(&table_id.resolve(context), key)
+                })
+                .unwrap_or_else(|| key.clone()),
+        }
+    }
+}
+
+/// A number to format, integers are kept exact.
+#[derive(Debug, Copy, Clone)]
+enum Number {
+    Integer(i128),
+    Float(f64),
+}
+
+/// A resolved format argument.
+enum Argument {
+    Number(Number),
+    Text(String),
+}
+
+impl Argument {
+    fn new(value: &FormatArgumentValue, context: &ResolveContext) -> Self {
+        match value {
+            FormatArgumentValue::Int(value) => {
+                Argument::Number(Number::Integer(i128::from(*value)))
+            }
+            FormatArgumentValue::UInt(value) => {
+                Argument::Number(Number::Integer(i128::from(*value)))
+            }
+            FormatArgumentValue::Int64(value) => {
+                Argument::Number(Number::Integer(i128::from(*value)))
+            }
+            FormatArgumentValue::UInt64(value) => {
+                Argument::Number(Number::Integer(i128::from(*value)))
+            }
+            FormatArgumentValue::Float(value) => {
+                Argument::Number(Number::Float(f64::from(value.0)))
+            }
+            FormatArgumentValue::Double(value) => Argument::Number(Number::Float(value.0)),
+            FormatArgumentValue::Text(text) => Argument::Text(text.resolve(context)),
+        }
+    }
+
+    /// The argument as it appears in a formatted text.
+    fn to_text(&self) -> String {
+        match self {
+            Argument::Number(number) => format_number(*number, &default_options(false, 3)),
+            Argument::Text(text) => text.clone(),
+        }
+    }
+
+    /// The English plural category of the argument.
+    fn plural_category(&self) -> &'static str {
+        match self {
+            Argument::Number(Number::Integer(1)) => "one",
+            Argument::Number(Number::Float(value)) if *value == 1.0 => "one",
+            _ => "other",
+        }
+    }
+}
+
+/// Number formatting options used when a text doesn't store its own.
+fn default_options(use_grouping: bool, maximum_fractional_digits: i32) -> NumberFormattingOptions {
+    NumberFormattingOptions {
+        always_include_sign: false,
+        use_grouping,
+        rounding_mode: RoundingMode::HalfToEven,
+        minimum_integral_digits: 1,
+        maximum_integral_digits: i32::MAX,
+        minimum_fractional_digits: 0,
+        maximum_fractional_digits,
+    }
+}
+
+/// Substitute the arguments of a format pattern.
+fn format_pattern<F>(pattern: &str, argument: F) -> String
+where
+    F: Fn(&str) -> Option<Argument>,
+{
+    let mut output = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(index) = rest.find(['`', '{']) {
+        output.push_str(&rest[..index]);
+        let special = &rest[index..];
+        if let Some(escaped) = special.strip_prefix('`') {
+            match escaped.chars().next() {
+                Some(next @ ('`' | '{' | '}')) => {
+                    output.push(next);
+                    rest = &escaped[1..];
+                }
+                _ => {
+                    output.push('`');
+                    rest = escaped;
+                }
+            }
+            continue;
+        }
+        let Some(end) = special.find('}') else {
+            output.push_str(special);
+            return output;
+        };
+        let name = &special[1..end];
+        rest = &special[end + 1..];
+        let Some(value) = argument(name) else {
+            // Arguments that can't be found are kept, like the engine does
+            output.push_str(&special[..=end]);
+            continue;
+        };
+        match parse_modifier(rest) {
+            Some((modifier, after)) => {
+                rest = after;
+                if modifier.name == "plural" {
+                    let category = value.plural_category();
+                    let form = modifier
+                        .parameters
+                        .iter()
+                        .find(|(key, _)| *key == category)
+                        .or_else(|| modifier.parameters.iter().find(|(key, _)| *key == "other"));
+                    if let Some((_, form)) = form {
+                        output.push_str(form);
+                    }
+                }
+            }
+            None => output.push_str(&value.to_text()),
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// An argument modifier like `|plural(one=item,other=items)`.
+struct Modifier<'a> {
+    name: &'a str,
+    parameters: Vec<(&'a str, String)>,
+}
+
+/// Parse an argument modifier at the start of `text`, returning it with the text after it.
+fn parse_modifier(text: &str) -> Option<(Modifier<'_>, &str)> {
+    let text = text.strip_prefix('|')?;
+    let open = text.find('(')?;
+    let name = &text[..open];
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    let mut parameters = Vec::new();
+    let mut rest = &text[open + 1..];
+    loop {
+        let separator = rest.find(['=', ')'])?;
+        if rest[separator..].starts_with(')') {
+            return Some((Modifier { name, parameters }, &rest[separator + 1..]));
+        }
+        let key = rest[..separator].trim();
+        rest = rest[separator + 1..].trim_start();
+        let mut value = String::new();
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let mut chars = quoted.char_indices();
+            loop {
+                match chars.next()? {
+                    (_, '`') => value.push(chars.next()?.1),
+                    (index, '"') => {
+                        rest = &quoted[index + 1..];
+                        break;
+                    }
+                    (_, c) => value.push(c),
+                }
+            }
+            rest = rest.trim_start();
+        } else {
+            let end = rest.find([',', ')'])?;
+            value.push_str(rest[..end].trim());
+            rest = &rest[end..];
+        }
+        parameters.push((key, value));
+        rest = rest.strip_prefix(',').unwrap_or(rest);
+    }
+}
+
+/// Format a number following the English culture.
+fn format_number(number: Number, options: &NumberFormattingOptions) -> String {
+    let (negative, integral, mut fraction) = match number {
+        Number::Integer(value) => (value < 0, value.unsigned_abs().to_string(), String::new()),
+        Number::Float(value) if !value.is_finite() => return value.to_string(),
+        Number::Float(value) => {
+            let digits = options.maximum_fractional_digits.clamp(0, 17) as usize;
+            let scale = 10f64.powi(digits as i32);
+            let rounded = round(value * scale, options.rounding_mode) / scale;
+            let formatted = format!("{:.digits$}", rounded.abs());
+            let (integral, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+            let negative = rounded < 0.0;
+            (negative, integral.to_string(), fraction.to_string())
+        }
+    };
+
+    let minimum_fraction = options.minimum_fractional_digits.max(0) as usize;
+    while fraction.len() > minimum_fraction && fraction.ends_with('0') {
+        fraction.pop();
+    }
+    while fraction.len() < minimum_fraction {
+        fraction.push('0');
+    }
+
+    let mut integral = integral;
+    let minimum_integral = options.minimum_integral_digits.max(0) as usize;
+    if integral.len() < minimum_integral {
+        integral.insert_str(0, &"0".repeat(minimum_integral - integral.len()));
+    }
+    let maximum_integral = options.maximum_integral_digits.max(1) as usize;
+    if integral.len() > maximum_integral {
+        integral.drain(..integral.len() - maximum_integral);
+    }
+    if options.use_grouping {
+        let digits = integral;
+        integral = String::with_capacity(digits.len() + digits.len() / 3);
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index) % 3 == 0 {
+                integral.push(',');
+            }
+            integral.push(digit);
+        }
+    }
+
+    let sign = match (negative, options.always_include_sign) {
+        (true, _) => "-",
+        (false, true) => "+",
+        (false, false) => "",
+    };
+    match fraction.is_empty() {
+        true => format!("{sign}{integral}"),
+        false => format!("{sign}{integral}.{fraction}"),
+    }
+}
+
+/// Round a value to an integer.
+fn round(value: f64, mode: RoundingMode) -> f64 {
+    match mode {
+        RoundingMode::HalfToEven | RoundingMode::Other(_) => value.round_ties_even(),
+        RoundingMode::HalfFromZero => value.round(),
+        RoundingMode::HalfToZero => match value.fract().abs() == 0.5 {
+            true => value.trunc(),
+            false => value.round(),
+        },
+        RoundingMode::FromZero => match value < 0.0 {
+            true => value.floor(),
+            false => value.ceil(),
+        },
+        RoundingMode::ToZero => value.trunc(),
+        RoundingMode::ToNegativeInfinity => value.floor(),
+        RoundingMode::ToPositiveInfinity => value.ceil(),
+    }
+}
+
+const TICKS_PER_SECOND: u64 = 10_000_000;
+const SECONDS_PER_DAY: u64 = 86_400;
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// Year, month and day of a number of days since 0001-01-01.
+fn civil_from_days(days: u64) -> (u64, usize, u64) {
+    // Days since 0000-03-01, so leap days end the year
+    let days = days + 306;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    (year, month as usize, day)
+}
+
+fn format_date(date_time: &DateTime, style: DateTimeStyle) -> String {
+    let days = date_time.ticks / TICKS_PER_SECOND / SECONDS_PER_DAY;
+    let (year, month, day) = civil_from_days(days);
+    let month_name = MONTHS[month - 1];
+    match style {
+        DateTimeStyle::Short => format!("{month}/{day}/{:02}", year % 100),
+        DateTimeStyle::Default | DateTimeStyle::Medium | DateTimeStyle::Other(_) => {
+            format!("{} {day}, {year}", &month_name[..3])
+        }
+        DateTimeStyle::Long => format!("{month_name} {day}, {year}"),
+        DateTimeStyle::Full => {
+            // 0001-01-01 was a Monday
+            let weekday = WEEKDAYS[((days + 1) % 7) as usize];
+            format!("{weekday}, {month_name} {day}, {year}")
+        }
+    }
+}
+
+fn format_time(date_time: &DateTime, style: DateTimeStyle) -> String {
+    let seconds = date_time.ticks / TICKS_PER_SECOND % SECONDS_PER_DAY;
+    let (hour, minute, second) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    let period = if hour < 12 { "AM" } else { "PM" };
+    let hour = match hour % 12 {
+        0 => 12,
+        hour => hour,
+    };
+    match style {
+        DateTimeStyle::Short => format!("{hour}:{minute:02} {period}"),
+        DateTimeStyle::Default | DateTimeStyle::Medium | DateTimeStyle::Other(_) => {
+            format!("{hour}:{minute:02}:{second:02} {period}")
+        }
+        DateTimeStyle::Long | DateTimeStyle::Full => {
+            format!("{hour}:{minute:02}:{second:02} {period} UTC")
+        }
+    }
+}
@@ -14,10 +14,10 @@ use crate::properties::struct_types::DateTime;
 use crate::types::map::HashableIndexMap;
 use crate::{
     cursor_ext::{ReadExt, WriteExt},
-    error::Error,
+    error::{DeserializeError, Error, SerializeError},
 };
 
-use super::{impl_read, impl_read_header, impl_write, PropertyOptions, PropertyTrait};
+use super::{impl_read, impl_read_header, impl_write, EmptyBody, PropertyOptions, PropertyTrait};
 
 /// A property that stores GVAS Text.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -26,12 +26,21 @@ pub struct TextProperty {
     /// Value
     #[cfg_attr(feature = "serde", serde(flatten))]
     pub value: FText,
+    /// Read from a zero-length body, see [`EmptyBody`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    pub empty_body: bool,
 }
 
 impl TextProperty {
     /// Create a new [`TextProperty`]
     pub fn new(value: FText) -> Self {
-        TextProperty { value }
+        TextProperty {
+            value,
+            empty_body: false,
+        }
     }
 
     #[inline]
@@ -40,13 +49,28 @@ impl TextProperty {
         options: &mut PropertyOptions,
     ) -> Result<Self, Error> {
         let value = FText::read(cursor, options)?;
-        Ok(TextProperty { value })
+        Ok(TextProperty::new(value))
     }
 
     impl_read!(options);
     impl_read_header!(options);
 }
 
+impl EmptyBody for TextProperty {
+    #[inline]
+    fn empty_body() -> Option<Self> {
+        Some(TextProperty {
+            value: FText::new_none(0, None),
+            empty_body: true,
+        })
+    }
+
+    #[inline]
+    fn is_empty_body(&self) -> bool {
+        self.empty_body && self.value.flags == 0 && self.value.history == FTextHistory::Empty {}
+    }
+}
+
 impl PropertyTrait for TextProperty {
     impl_write!(TextProperty);
 
@@ -110,6 +134,23 @@ impl FText {
         }
     }
 
+    /// The string shown for this text, if it is stored in the save.
+    ///
+    /// String table entries only have a display string when it was resolved with
+    /// [`ReadOptions::string_table_resolver`].
+    ///
+    /// [`ReadOptions::string_table_resolver`]: crate::options::ReadOptions::string_table_resolver
+    pub fn display_string(&self) -> Option<&str> {
+        match &self.history {
+            FTextHistory::None {
+                culture_invariant_string,
+            } => culture_invariant_string.as_deref(),
+            FTextHistory::Base { source_string, .. } => source_string.as_deref(),
+            FTextHistory::StringTableEntry { display_string, .. } => display_string.0.as_deref(),
+            _ => None,
+        }
+    }
+
     /// Read [`FText`] from a cursor
     #[inline]
     pub fn read<R: Read + Seek>(cursor: &mut R, options: &PropertyOptions) -> Result<Self, Error> {
@@ -131,6 +172,51 @@ impl FText {
         len += self.history.write(cursor, options)?;
         Ok(len)
     }
+
+    /// Convert this text to the layout of another engine version.
+    ///
+    /// `wide_arguments` is true when the target supports 64-bit format arguments, and
+    /// `culture_invariant` when it stores culture invariant strings.
+    pub(crate) fn retarget(
+        &mut self,
+        wide_arguments: bool,
+        culture_invariant: bool,
+    ) -> Result<(), Error> {
+        let retarget_text = |text: &mut FText| text.retarget(wide_arguments, culture_invariant);
+        let retarget_argument = |argument: &mut FormatArgumentValue| {
+            argument.retarget(wide_arguments, culture_invariant)
+        };
+        match &mut self.history {
+            FTextHistory::None { .. } if !culture_invariant => {
+                self.history = FTextHistory::Empty {};
+            }
+            FTextHistory::NamedFormat {
+                source_format,
+                arguments,
+            }
+            | FTextHistory::ArgumentFormat {
+                source_format,
+                arguments,
+            } => {
+                retarget_text(source_format)?;
+                arguments.values_mut().try_for_each(retarget_argument)?;
+            }
+            FTextHistory::OrderedFormat {
+                source_format,
+                arguments,
+            } => {
+                retarget_text(source_format)?;
+                arguments.iter_mut().try_for_each(retarget_argument)?;
+            }
+            FTextHistory::AsNumber { source_value, .. }
+            | FTextHistory::AsPercent { source_value, .. }
+            | FTextHistory::AsCurrency { source_value, .. } => retarget_argument(source_value)?,
+            FTextHistory::Transform { source_text, .. } => retarget_text(source_text)?,
+            FTextHistory::StringTableEntry { table_id, .. } => retarget_text(table_id)?,
+            _ => {}
+        }
+        Ok(())
+    }
 }
 
 /// Text history type
@@ -290,9 +376,42 @@ pub enum FTextHistory {
         table_id: Box<FText>,
         /// Key
         key: String,
+        /// Display string found by [`ReadOptions::string_table_resolver`], not written to the save
+        ///
+        /// [`ReadOptions::string_table_resolver`]: crate::options::ReadOptions::string_table_resolver
+        #[cfg_attr(feature = "serde", serde(skip))]
+        display_string: ResolvedString,
     },
 }
 
+/// Display string of a string table entry, resolved while reading.
+///
+/// This is a cache of the string table, so it isn't serialized and two entries with different
+/// resolved strings still compare and hash equal.
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedString(pub Option<String>);
+
+impl From<Option<String>> for ResolvedString {
+    #[inline]
+    fn from(value: Option<String>) -> Self {
+        ResolvedString(value)
+    }
+}
+
+impl PartialEq for ResolvedString {
+    #[inline]
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ResolvedString {}
+
+impl Hash for ResolvedString {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
+}
+
 impl FTextHistory {
     /// Read [`FTextHistory`] from a cursor
     #[inline]
@@ -306,7 +425,7 @@ impl FTextHistory {
                 ) {
                     let has_culture_invariant_string = cursor.read_b32()?;
                     if has_culture_invariant_string {
-                        let culture_invariant_string = cursor.read_fstring()?;
+                        let culture_invariant_string = options.read_fstring(cursor)?;
                         FTextHistory::None {
                             culture_invariant_string,
                         }
@@ -318,9 +437,9 @@ impl FTextHistory {
                 }
             }
             TextHistoryType::Base => {
-                let namespace = cursor.read_fstring()?;
-                let key = cursor.read_fstring()?;
-                let source_string = cursor.read_fstring()?;
+                let namespace = options.read_fstring(cursor)?;
+                let key = options.read_fstring(cursor)?;
+                let source_string = options.read_fstring(cursor)?;
 
                 FTextHistory::Base {
                     namespace,
@@ -335,7 +454,7 @@ impl FTextHistory {
                 let mut arguments = HashableIndexMap::with_capacity(argument_count as usize);
 
                 for _ in 0..argument_count {
-                    let key = cursor.read_string()?;
+                    let key = options.read_string(cursor)?;
                     let value = FormatArgumentValue::read(cursor, options)?;
                     arguments.insert(key, value);
                 }
@@ -366,7 +485,7 @@ impl FTextHistory {
                 let mut arguments = HashableIndexMap::with_capacity(count as usize);
 
                 for _ in 0..count {
-                    let key = cursor.read_string()?;
+                    let key = options.read_string(cursor)?;
                     let value = FormatArgumentValue::read(cursor, options)?;
                     arguments.insert(key, value);
                 }
@@ -386,7 +505,7 @@ impl FTextHistory {
                     None
                 };
 
-                let target_culture = cursor.read_fstring()?;
+                let target_culture = options.read_fstring(cursor)?;
 
                 FTextHistory::AsNumber {
                     source_value,
@@ -404,7 +523,7 @@ impl FTextHistory {
                     None
                 };
 
-                let target_culture = cursor.read_fstring()?;
+                let target_culture = options.read_fstring(cursor)?;
 
                 FTextHistory::AsPercent {
                     source_value,
@@ -413,7 +532,7 @@ impl FTextHistory {
                 }
             }
             TextHistoryType::AsCurrency => {
-                let currency_code = cursor.read_fstring()?;
+                let currency_code = options.read_fstring(cursor)?;
 
                 let source_value = Box::new(FormatArgumentValue::read(cursor, options)?);
 
@@ -424,7 +543,7 @@ impl FTextHistory {
                     None
                 };
 
-                let target_culture = cursor.read_fstring()?;
+                let target_culture = options.read_fstring(cursor)?;
 
                 FTextHistory::AsCurrency {
                     currency_code,
@@ -435,10 +554,10 @@ impl FTextHistory {
             }
             TextHistoryType::AsDate => {
                 let date_time = DateTime {
-                    ticks: UInt64Property::read(cursor, false)?.value,
+                    ticks: UInt64Property::read(cursor, false, options)?.value,
                 };
                 let date_style = cursor.read_enum()?;
-                let target_culture = cursor.read_string()?;
+                let target_culture = options.read_string(cursor)?;
 
                 FTextHistory::AsDate {
                     date_time,
@@ -448,11 +567,11 @@ impl FTextHistory {
             }
             TextHistoryType::AsTime => {
                 let source_date_time = DateTime {
-                    ticks: UInt64Property::read(cursor, false)?.value,
+                    ticks: UInt64Property::read(cursor, false, options)?.value,
                 };
                 let time_style = cursor.read_enum()?;
-                let time_zone = cursor.read_string()?;
-                let target_culture = cursor.read_string()?;
+                let time_zone = options.read_string(cursor)?;
+                let target_culture = options.read_string(cursor)?;
 
                 FTextHistory::AsTime {
                     source_date_time,
@@ -463,12 +582,12 @@ impl FTextHistory {
             }
             TextHistoryType::AsDateTime => {
                 let source_date_time = DateTime {
-                    ticks: UInt64Property::read(cursor, false)?.value,
+                    ticks: UInt64Property::read(cursor, false, options)?.value,
                 };
                 let date_style = cursor.read_enum()?;
                 let time_style = cursor.read_enum()?;
-                let time_zone = cursor.read_string()?;
-                let target_culture = cursor.read_string()?;
+                let time_zone = options.read_string(cursor)?;
+                let target_culture = options.read_string(cursor)?;
 
                 FTextHistory::AsDateTime {
                     source_date_time,
@@ -489,11 +608,23 @@ impl FTextHistory {
             }
             TextHistoryType::StringTableEntry => {
                 let table_id = Box::new(FText::read(cursor, options)?);
-                let key = cursor.read_string()?;
-
-                FTextHistory::StringTableEntry { table_id, key }
+                let key = options.read_string(cursor)?;
+                let display_string = options
+                    .read_options
+                    .string_table_resolver
+                    .as_ref()
+                    .and_then(|resolver| resolver.resolve(table_id.display_string()?, &key));
+
+                FTextHistory::StringTableEntry {
+                    table_id,
+                    key,
+                    display_string: ResolvedString(display_string),
+                }
             }
-            _ => unimplemented!("unimplemented history type: {:?}", history_type),
+            _ => Err(Error::unsupported(DeserializeError::invalid_property(
+                format!("Unsupported text history type {history_type:?}"),
+                cursor,
+            )))?,
         })
     }
 
@@ -527,7 +658,7 @@ impl FTextHistory {
                 ) {
                     len += 4;
                     cursor.write_b32(true)?;
-                    len += cursor.write_fstring(culture_invariant_string.as_deref())?;
+                    len += options.write_fstring(cursor, culture_invariant_string.as_deref())?;
                 }
                 Ok(len)
             }
@@ -539,9 +670,9 @@ impl FTextHistory {
             } => {
                 let mut len = 1;
                 cursor.write_enum(TextHistoryType::Base)?;
-                len += cursor.write_fstring(namespace.as_deref())?;
-                len += cursor.write_fstring(key.as_deref())?;
-                len += cursor.write_fstring(source_string.as_deref())?;
+                len += options.write_fstring(cursor, namespace.as_deref())?;
+                len += options.write_fstring(cursor, key.as_deref())?;
+                len += options.write_fstring(cursor, source_string.as_deref())?;
                 Ok(len)
             }
 
@@ -605,7 +736,7 @@ impl FTextHistory {
                 if let Some(format_options) = format_options {
                     len += format_options.write(cursor)?;
                 };
-                len += cursor.write_fstring(target_culture.as_deref())?;
+                len += options.write_fstring(cursor, target_culture.as_deref())?;
                 Ok(len)
             }
 
@@ -622,7 +753,7 @@ impl FTextHistory {
                 if let Some(format_options) = format_options {
                     len += format_options.write(cursor)?;
                 }
-                len += cursor.write_fstring(target_culture.as_deref())?;
+                len += options.write_fstring(cursor, target_culture.as_deref())?;
                 Ok(len)
             }
 
@@ -632,15 +763,16 @@ impl FTextHistory {
                 format_options,
                 target_culture,
             } => {
-                let mut len = 0;
-                len += cursor.write_fstring(currency_code.as_deref())?;
+                let mut len = 1;
+                cursor.write_enum(TextHistoryType::AsCurrency)?;
+                len += options.write_fstring(cursor, currency_code.as_deref())?;
                 len += source_value.write(cursor, options)?;
                 len += 4;
                 cursor.write_b32(format_options.is_some())?;
                 if let Some(format_options) = format_options {
                     len += format_options.write(cursor)?;
                 }
-                len += cursor.write_fstring(target_culture.as_deref())?;
+                len += options.write_fstring(cursor, target_culture.as_deref())?;
                 Ok(len)
             }
 
@@ -700,8 +832,13 @@ impl FTextHistory {
                 Ok(len)
             }
 
-            FTextHistory::StringTableEntry { table_id, key } => {
-                let mut len = 0;
+            FTextHistory::StringTableEntry {
+                table_id,
+                key,
+                display_string: _,
+            } => {
+                let mut len = 1;
+                cursor.write_enum(TextHistoryType::StringTableEntry)?;
                 len += table_id.write(cursor, options)?;
                 len += cursor.write_string(key)?;
                 Ok(len)
@@ -777,7 +914,12 @@ impl FormatArgumentValue {
                 FormatArgumentValue::Double(cursor.read_f64::<LittleEndian>()?.into())
             }
             FormatArgumentType::Text => FormatArgumentValue::Text(FText::read(cursor, options)?),
-            FormatArgumentType::Gender => unimplemented!(),
+            FormatArgumentType::Gender => {
+                Err(Error::unsupported(DeserializeError::invalid_property(
+                    "Unsupported format argument type Gender",
+                    cursor,
+                )))?
+            }
         })
     }
 
@@ -790,45 +932,49 @@ impl FormatArgumentValue {
     ) -> Result<usize, Error> {
         match self {
             FormatArgumentValue::Int(value) => {
-                assert!(
-                    !options.supports_version(
-                        FUE5ReleaseStreamObjectVersion::TextFormatArgumentData64bitSupport,
-                    ),
-                    "FormatArgumentValue::Int is not compatible with TextFormatArgumentData64bitSupport"
-                );
+                if options.supports_version(
+                    FUE5ReleaseStreamObjectVersion::TextFormatArgumentData64bitSupport,
+                ) {
+                    Err(SerializeError::invalid_value(
+                        "FormatArgumentValue::Int is not compatible with TextFormatArgumentData64bitSupport",
+                    ))?
+                }
                 cursor.write_enum(FormatArgumentType::Int)?;
                 cursor.write_i32::<LittleEndian>(*value)?;
                 Ok(5)
             }
             FormatArgumentValue::Int64(value) => {
-                assert!(
-                    options.supports_version(
-                        FUE5ReleaseStreamObjectVersion::TextFormatArgumentData64bitSupport,
-                    ),
-                    "FormatArgumentValue::Int64 requires TextFormatArgumentData64bitSupport"
-                );
+                if !options.supports_version(
+                    FUE5ReleaseStreamObjectVersion::TextFormatArgumentData64bitSupport,
+                ) {
+                    Err(SerializeError::invalid_value(
+                        "FormatArgumentValue::Int64 requires TextFormatArgumentData64bitSupport",
+                    ))?
+                }
                 cursor.write_enum(FormatArgumentType::Int)?;
                 cursor.write_i64::<LittleEndian>(*value)?;
                 Ok(9)
             }
             FormatArgumentValue::UInt(value) => {
-                assert!(
-                    !options.supports_version(
-                        FUE5ReleaseStreamObjectVersion::TextFormatArgumentData64bitSupport,
-                    ),
-                    "FormatArgumentValue::UInt is not compatible with TextFormatArgumentData64bitSupport"
-                );
+                if options.supports_version(
+                    FUE5ReleaseStreamObjectVersion::TextFormatArgumentData64bitSupport,
+                ) {
+                    Err(SerializeError::invalid_value(
+                        "FormatArgumentValue::UInt is not compatible with TextFormatArgumentData64bitSupport",
+                    ))?
+                }
                 cursor.write_enum(FormatArgumentType::UInt)?;
                 cursor.write_u32::<LittleEndian>(*value)?;
                 Ok(5)
             }
             FormatArgumentValue::UInt64(value) => {
-                assert!(
-                    options.supports_version(
-                        FUE5ReleaseStreamObjectVersion::TextFormatArgumentData64bitSupport,
-                    ),
-                    "FormatArgumentValue::UInt64 requires TextFormatArgumentData64bitSupport"
-                );
+                if !options.supports_version(
+                    FUE5ReleaseStreamObjectVersion::TextFormatArgumentData64bitSupport,
+                ) {
+                    Err(SerializeError::invalid_value(
+                        "FormatArgumentValue::UInt64 requires TextFormatArgumentData64bitSupport",
+                    ))?
+                }
                 cursor.write_enum(FormatArgumentType::UInt)?;
                 cursor.write_u64::<LittleEndian>(*value)?;
                 Ok(9)
@@ -851,13 +997,42 @@ impl FormatArgumentValue {
             }
         }
     }
+
+    /// Convert this value to the integer width of another engine version, see
+    /// [`FText::retarget`].
+    fn retarget(&mut self, wide_arguments: bool, culture_invariant: bool) -> Result<(), Error> {
+        *self = match (&mut *self, wide_arguments) {
+            (FormatArgumentValue::Int(value), true) => {
+                FormatArgumentValue::Int64(i64::from(*value))
+            }
+            (FormatArgumentValue::UInt(value), true) => {
+                FormatArgumentValue::UInt64(u64::from(*value))
+            }
+            (FormatArgumentValue::Int64(value), false) => match i32::try_from(*value) {
+                Ok(value) => FormatArgumentValue::Int(value),
+                Err(_) => Err(SerializeError::invalid_value(format!(
+                    "Format argument {value} doesn't fit in 32 bits"
+                )))?,
+            },
+            (FormatArgumentValue::UInt64(value), false) => match u32::try_from(*value) {
+                Ok(value) => FormatArgumentValue::UInt(value),
+                Err(_) => Err(SerializeError::invalid_value(format!(
+                    "Format argument {value} doesn't fit in 32 bits"
+                )))?,
+            },
+            (FormatArgumentValue::Text(text), _) => {
+                return text.retarget(wide_arguments, culture_invariant)
+            }
+            _ => return Ok(()),
+        };
+        Ok(())
+    }
 }
 
 /// Rounding mode
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "rounding"))]
-#[repr(i8)]
 pub enum RoundingMode {
     /// Rounds to the nearest place, equidistant ties go to the value which is closest to an even value: 1.5 becomes 2, 0.5 becomes 0
     HalfToEven,
@@ -873,6 +1048,41 @@ pub enum RoundingMode {
     ToNegativeInfinity,
     /// Rounds to the value which is more positive: 0.1 becomes 1, -0.1 becomes 0
     ToPositiveInfinity,
+    /// A rounding mode unknown to this version of the crate
+    #[cfg_attr(feature = "serde", serde(with = "other_value"))]
+    Other(u8),
+}
+
+impl From<i8> for RoundingMode {
+    #[inline]
+    fn from(value: i8) -> Self {
+        match value {
+            0 => RoundingMode::HalfToEven,
+            1 => RoundingMode::HalfFromZero,
+            2 => RoundingMode::HalfToZero,
+            3 => RoundingMode::FromZero,
+            4 => RoundingMode::ToZero,
+            5 => RoundingMode::ToNegativeInfinity,
+            6 => RoundingMode::ToPositiveInfinity,
+            _ => RoundingMode::Other(value as u8),
+        }
+    }
+}
+
+impl From<RoundingMode> for i8 {
+    #[inline]
+    fn from(value: RoundingMode) -> Self {
+        match value {
+            RoundingMode::HalfToEven => 0,
+            RoundingMode::HalfFromZero => 1,
+            RoundingMode::HalfToZero => 2,
+            RoundingMode::FromZero => 3,
+            RoundingMode::ToZero => 4,
+            RoundingMode::ToNegativeInfinity => 5,
+            RoundingMode::ToPositiveInfinity => 6,
+            RoundingMode::Other(value) => value as i8,
+        }
+    }
 }
 
 /// Number formatting options
@@ -935,9 +1145,8 @@ impl NumberFormattingOptions {
 }
 
 /// Date time style
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[repr(i8)]
 pub enum DateTimeStyle {
     /// Default
     Default,
@@ -949,16 +1158,89 @@ pub enum DateTimeStyle {
     Long,
     /// Full
     Full,
+    /// A date time style unknown to this version of the crate
+    Other(u8),
+}
+
+impl From<i8> for DateTimeStyle {
+    #[inline]
+    fn from(value: i8) -> Self {
+        match value {
+            0 => DateTimeStyle::Default,
+            1 => DateTimeStyle::Short,
+            2 => DateTimeStyle::Medium,
+            3 => DateTimeStyle::Long,
+            4 => DateTimeStyle::Full,
+            _ => DateTimeStyle::Other(value as u8),
+        }
+    }
+}
+
+impl From<DateTimeStyle> for i8 {
+    #[inline]
+    fn from(value: DateTimeStyle) -> Self {
+        match value {
+            DateTimeStyle::Default => 0,
+            DateTimeStyle::Short => 1,
+            DateTimeStyle::Medium => 2,
+            DateTimeStyle::Long => 3,
+            DateTimeStyle::Full => 4,
+            DateTimeStyle::Other(value) => value as i8,
+        }
+    }
 }
 
 /// Transform type
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "transform"))]
-#[repr(i8)]
 pub enum TransformType {
     /// To lowercase
-    ToLower = 0,
+    ToLower,
     /// To uppercase
     ToUpper,
+    /// A transform type unknown to this version of the crate
+    #[cfg_attr(feature = "serde", serde(with = "other_value"))]
+    Other(u8),
+}
+
+impl From<i8> for TransformType {
+    #[inline]
+    fn from(value: i8) -> Self {
+        match value {
+            0 => TransformType::ToLower,
+            1 => TransformType::ToUpper,
+            _ => TransformType::Other(value as u8),
+        }
+    }
+}
+
+impl From<TransformType> for i8 {
+    #[inline]
+    fn from(value: TransformType) -> Self {
+        match value {
+            TransformType::ToLower => 0,
+            TransformType::ToUpper => 1,
+            TransformType::Other(value) => value as i8,
+        }
+    }
+}
+
+/// Internally tagged enums can't hold a bare integer, store unknown values as `{"value": n}`.
+#[cfg(feature = "serde")]
+mod other_value {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct OtherValue {
+        value: u8,
+    }
+
+    pub fn serialize<S: Serializer>(value: &u8, serializer: S) -> Result<S::Ok, S::Error> {
+        OtherValue { value: *value }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+        Ok(OtherValue::deserialize(deserializer)?.value)
+    }
 }
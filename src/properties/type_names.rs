@@ -0,0 +1,248 @@
+//! Type names of properties and structs
+//!
+//! Saves store the type of every property and struct as a string. These constants name the
+//! types known to this crate, so they can be matched without repeating string literals:
+//!
+//! ```
+//! use gvas::properties::type_names::{self, INT_PROPERTY, STR_PROPERTY};
+//!
+//! fn describe(type_name: &str) -> &'static str {
+//!     match type_name {
+//!         INT_PROPERTY => "number",
+//!         STR_PROPERTY => "text",
+//!         _ if type_names::STRUCT_TYPE_NAMES.contains(&type_name) => "struct",
+//!         _ => "other",
+//!     }
+//! }
+//!
+//! assert_eq!(describe("IntProperty"), "number");
+//! assert_eq!(describe(type_names::VECTOR), "struct");
+//! ```
+//!
+//! Type names repeat for every property in a save, the reader also looks them up here to hand
+//! out `&'static str` instead of allocating a new `String` each time. `StructProperty` stores a
+//! shared copy of well-known type names.
+
+use std::sync::{Arc, LazyLock};
+
+/// `ArrayProperty`
+pub const ARRAY_PROPERTY: &str = "ArrayProperty";
+/// `BoolProperty`
+pub const BOOL_PROPERTY: &str = "BoolProperty";
+/// `ByteProperty`
+pub const BYTE_PROPERTY: &str = "ByteProperty";
+/// `DelegateProperty`
+pub const DELEGATE_PROPERTY: &str = "DelegateProperty";
+/// `DoubleProperty`
+pub const DOUBLE_PROPERTY: &str = "DoubleProperty";
+/// `EnumProperty`
+pub const ENUM_PROPERTY: &str = "EnumProperty";
+/// `FieldPathProperty`
+pub const FIELD_PATH_PROPERTY: &str = "FieldPathProperty";
+/// `FloatProperty`
+pub const FLOAT_PROPERTY: &str = "FloatProperty";
+/// `Int16Property`
+pub const INT16_PROPERTY: &str = "Int16Property";
+/// `Int64Property`
+pub const INT64_PROPERTY: &str = "Int64Property";
+/// `Int8Property`
+pub const INT8_PROPERTY: &str = "Int8Property";
+/// `IntProperty`
+pub const INT_PROPERTY: &str = "IntProperty";
+/// `InterfaceProperty`
+pub const INTERFACE_PROPERTY: &str = "InterfaceProperty";
+/// `LazyObjectProperty`
+pub const LAZY_OBJECT_PROPERTY: &str = "LazyObjectProperty";
+/// `MapProperty`
+pub const MAP_PROPERTY: &str = "MapProperty";
+/// `MulticastInlineDelegateProperty`
+pub const MULTICAST_INLINE_DELEGATE_PROPERTY: &str = "MulticastInlineDelegateProperty";
+/// `MulticastSparseDelegateProperty`
+pub const MULTICAST_SPARSE_DELEGATE_PROPERTY: &str = "MulticastSparseDelegateProperty";
+/// `NameProperty`
+pub const NAME_PROPERTY: &str = "NameProperty";
+/// `ObjectProperty`
+pub const OBJECT_PROPERTY: &str = "ObjectProperty";
+/// `SetProperty`
+pub const SET_PROPERTY: &str = "SetProperty";
+/// `SoftClassProperty`
+pub const SOFT_CLASS_PROPERTY: &str = "SoftClassProperty";
+/// `SoftObjectProperty`
+pub const SOFT_OBJECT_PROPERTY: &str = "SoftObjectProperty";
+/// `StrProperty`
+pub const STR_PROPERTY: &str = "StrProperty";
+/// `StructProperty`
+pub const STRUCT_PROPERTY: &str = "StructProperty";
+/// `TextProperty`
+pub const TEXT_PROPERTY: &str = "TextProperty";
+/// `UInt16Property`
+pub const UINT16_PROPERTY: &str = "UInt16Property";
+/// `UInt32Property`
+pub const UINT32_PROPERTY: &str = "UInt32Property";
+/// `UInt64Property`
+pub const UINT64_PROPERTY: &str = "UInt64Property";
+/// `WeakObjectProperty`
+pub const WEAK_OBJECT_PROPERTY: &str = "WeakObjectProperty";
+
+/// The `Box` struct.
+pub const BOX: &str = "Box";
+/// The `Color` struct.
+pub const COLOR: &str = "Color";
+/// The `DateTime` struct.
+pub const DATE_TIME: &str = "DateTime";
+/// The `GameplayTagContainer` struct.
+pub const GAMEPLAY_TAG_CONTAINER: &str = "GameplayTagContainer";
+/// The `Guid` struct.
+pub const GUID: &str = "Guid";
+/// The `Int64Vector` struct.
+pub const INT64_VECTOR: &str = "Int64Vector";
+/// The `IntPoint` struct.
+pub const INT_POINT: &str = "IntPoint";
+/// The `IntVector` struct.
+pub const INT_VECTOR: &str = "IntVector";
+/// The `IntVector4` struct.
+pub const INT_VECTOR4: &str = "IntVector4";
+/// The `LinearColor` struct.
+pub const LINEAR_COLOR: &str = "LinearColor";
+/// The `Quat` struct.
+pub const QUAT: &str = "Quat";
+/// The `Rotator` struct.
+pub const ROTATOR: &str = "Rotator";
+/// The `SoftClassPath` struct.
+pub const SOFT_CLASS_PATH: &str = "SoftClassPath";
+/// The `SoftObjectPath` struct.
+pub const SOFT_OBJECT_PATH: &str = "SoftObjectPath";
+/// The `Timespan` struct.
+pub const TIMESPAN: &str = "Timespan";
+/// The `Transform` struct.
+pub const TRANSFORM: &str = "Transform";
+/// The `UintVector` struct.
+pub const UINT_VECTOR: &str = "UintVector";
+/// The `Vector` struct.
+pub const VECTOR: &str = "Vector";
+/// The `Vector2D` struct.
+pub const VECTOR2D: &str = "Vector2D";
+/// The `Vector4` struct.
+pub const VECTOR4: &str = "Vector4";
+
+/// Every property type name in this module, sorted.
+pub const PROPERTY_TYPE_NAMES: [&str; 29] = [
+    ARRAY_PROPERTY,
+    BOOL_PROPERTY,
+    BYTE_PROPERTY,
+    DELEGATE_PROPERTY,
+    DOUBLE_PROPERTY,
+    ENUM_PROPERTY,
+    FIELD_PATH_PROPERTY,
+    FLOAT_PROPERTY,
+    INT16_PROPERTY,
+    INT64_PROPERTY,
+    INT8_PROPERTY,
+    INT_PROPERTY,
+    INTERFACE_PROPERTY,
+    LAZY_OBJECT_PROPERTY,
+    MAP_PROPERTY,
+    MULTICAST_INLINE_DELEGATE_PROPERTY,
+    MULTICAST_SPARSE_DELEGATE_PROPERTY,
+    NAME_PROPERTY,
+    OBJECT_PROPERTY,
+    SET_PROPERTY,
+    SOFT_CLASS_PROPERTY,
+    SOFT_OBJECT_PROPERTY,
+    STR_PROPERTY,
+    STRUCT_PROPERTY,
+    TEXT_PROPERTY,
+    UINT16_PROPERTY,
+    UINT32_PROPERTY,
+    UINT64_PROPERTY,
+    WEAK_OBJECT_PROPERTY,
+];
+
+/// Every struct type name in this module, sorted.
+pub const STRUCT_TYPE_NAMES: [&str; 20] = [
+    BOX,
+    COLOR,
+    DATE_TIME,
+    GAMEPLAY_TAG_CONTAINER,
+    GUID,
+    INT64_VECTOR,
+    INT_POINT,
+    INT_VECTOR,
+    INT_VECTOR4,
+    LINEAR_COLOR,
+    QUAT,
+    ROTATOR,
+    SOFT_CLASS_PATH,
+    SOFT_OBJECT_PATH,
+    TIMESPAN,
+    TRANSFORM,
+    UINT_VECTOR,
+    VECTOR,
+    VECTOR2D,
+    VECTOR4,
+];
+
+/// Number of known property and struct type names.
+const KNOWN_TYPE_NAMES_LEN: usize = PROPERTY_TYPE_NAMES.len() + STRUCT_TYPE_NAMES.len();
+
+/// Known property and struct type names, sorted for binary search.
+static KNOWN_TYPE_NAMES: [&str; KNOWN_TYPE_NAMES_LEN] =
+    merge_sorted(&PROPERTY_TYPE_NAMES, &STRUCT_TYPE_NAMES);
+
+/// Returns `true` if `a` sorts before `b`, comparing bytes like [`str::cmp`].
+const fn is_less(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut index = 0;
+    while index < a.len() && index < b.len() {
+        if a[index] != b[index] {
+            return a[index] < b[index];
+        }
+        index += 1;
+    }
+    a.len() < b.len()
+}
+
+/// Merges two sorted lists of names, failing to compile if the result isn't strictly sorted.
+const fn merge_sorted<const N: usize>(a: &[&'static str], b: &[&'static str]) -> [&'static str; N] {
+    assert!(a.len() + b.len() == N);
+    let mut merged = [""; N];
+    let (mut i, mut j) = (0, 0);
+    while i + j < N {
+        if j == b.len() || (i < a.len() && is_less(a[i], b[j])) {
+            merged[i + j] = a[i];
+            i += 1;
+        } else {
+            merged[i + j] = b[j];
+            j += 1;
+        }
+        let index = i + j - 1;
+        assert!(
+            index == 0 || is_less(merged[index - 1], merged[index]),
+            "Type names aren't sorted"
+        );
+    }
+    merged
+}
+
+/// Longest name in [`KNOWN_TYPE_NAMES`], including the nul terminator.
+pub(crate) const MAX_KNOWN_TYPE_NAME_LEN: usize = 32;
+
+/// Look up a well-known type name from its raw bytes.
+#[inline]
+pub(crate) fn intern_bytes(name: &[u8]) -> Option<&'static str> {
+    KNOWN_TYPE_NAMES
+        .binary_search_by(|probe| probe.as_bytes().cmp(name))
+        .ok()
+        .map(|index| KNOWN_TYPE_NAMES[index])
+}
+
+/// Shared copies of [`KNOWN_TYPE_NAMES`], stored by properties holding a well-known type name.
+static SHARED_TYPE_NAMES: LazyLock<[Arc<str>; KNOWN_TYPE_NAMES_LEN]> =
+    LazyLock::new(|| KNOWN_TYPE_NAMES.map(Arc::from));
+
+/// Look up the shared copy of a well-known type name.
+#[inline]
+pub(crate) fn shared(name: &str) -> Option<Arc<str>> {
+    let index = KNOWN_TYPE_NAMES.binary_search(&name).ok()?;
+    Some(Arc::clone(&SHARED_TYPE_NAMES[index]))
+}
@@ -1,24 +1,89 @@
-use std::io::{Cursor, Read, Seek, Write};
+use std::{
+    io::{Cursor, ErrorKind, Read, Seek, Write},
+    sync::Arc,
+};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
-use crate::{cursor_ext::WriteExt, error::Error};
+use crate::{
+    cursor_ext::WriteExt,
+    deep_size::DeepSizeOf,
+    error::{DeserializeError, Error, SerializeError},
+    object_version::EUnrealEngineObjectUE4Version,
+    stable_hash::{StableHash, StableHasher},
+};
 
-use super::{PropertyOptions, PropertyTrait};
+use super::{property_kind::PropertyKind, PropertyOptions, PropertyTrait};
 
 /// This struct is read when a property is unknown to the deserializer
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnknownProperty {
     property_name: String,
-    raw: Vec<u8>,
+    raw: Arc<[u8]>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    tag: Option<Arc<[u8]>>,
 }
 
 impl UnknownProperty {
     /// Creates a new `UnknownProperty` instance.
     #[inline]
-    pub fn new(property_name: String, raw: Vec<u8>) -> Self {
-        UnknownProperty { property_name, raw }
+    pub fn new(property_name: String, raw: impl Into<Arc<[u8]>>) -> Self {
+        UnknownProperty {
+            property_name,
+            raw: raw.into(),
+            tag: None,
+        }
+    }
+
+    /// Creates a new `UnknownProperty` instance with the raw tag following its type name.
+    ///
+    /// The tag is written back unchanged, except for the length it starts with, which is
+    /// replaced by the length of `raw`.
+    #[inline]
+    pub fn with_tag(
+        property_name: String,
+        tag: impl Into<Arc<[u8]>>,
+        raw: impl Into<Arc<[u8]>>,
+    ) -> Self {
+        UnknownProperty {
+            property_name,
+            raw: raw.into(),
+            tag: Some(tag.into()),
+        }
+    }
+
+    /// Returns the name of the property.
+    #[inline]
+    pub fn property_name(&self) -> &str {
+        &self.property_name
+    }
+
+    /// Returns the raw body of the property, shared between clones.
+    #[inline]
+    pub fn raw(&self) -> &Arc<[u8]> {
+        &self.raw
+    }
+
+    /// Returns the raw tag following the type name, if it was kept while reading.
+    ///
+    /// See [`ReadOptions::raw_passthrough`].
+    ///
+    /// [`ReadOptions::raw_passthrough`]: crate::options::ReadOptions::raw_passthrough
+    #[inline]
+    pub fn tag(&self) -> Option<&Arc<[u8]>> {
+        self.tag.as_ref()
+    }
+
+    /// Returns the raw body of the property for editing.
+    ///
+    /// The body is copied first if it is shared with a clone of this property.
+    #[inline]
+    pub fn raw_mut(&mut self) -> &mut [u8] {
+        Arc::make_mut(&mut self.raw)
     }
 
     #[inline]
@@ -32,7 +97,8 @@ impl UnknownProperty {
 
         Ok(UnknownProperty {
             property_name,
-            raw: data,
+            raw: data.into(),
+            tag: None,
         })
     }
 
@@ -40,20 +106,121 @@ impl UnknownProperty {
     pub(crate) fn read_with_header<R: Read + Seek>(
         cursor: &mut R,
         property_name: String,
+        options: &PropertyOptions,
     ) -> Result<Self, Error> {
         let length = cursor.read_u32::<LittleEndian>()?;
         let array_index = cursor.read_u32::<LittleEndian>()?;
-        assert_eq!(
-            array_index,
-            0,
-            "Expected array_index value zero @ {:#x}",
-            cursor.stream_position()? - 4
-        );
-        let separator = cursor.read_u8()?;
-        assert_eq!(separator, 0);
+        if array_index != 0 {
+            let position = cursor.stream_position()? - 4;
+            Err(DeserializeError::InvalidArrayIndex(array_index, position))?
+        }
+        options.read_property_guid_flag(cursor)?;
 
         UnknownProperty::read_with_length(cursor, property_name, length)
     }
+
+    /// Reads a property with a tag, keeping the tag and the body as raw bytes.
+    pub(crate) fn read_with_raw_tag<R: Read + Seek>(
+        cursor: &mut R,
+        property_name: String,
+        options: &PropertyOptions,
+    ) -> Result<Self, Error> {
+        let (tag, length) = UnknownProperty::read_tag(cursor, &property_name, options)?;
+        let mut property = UnknownProperty::read_with_length(cursor, property_name, length)?;
+        property.tag = Some(tag.into());
+        Ok(property)
+    }
+
+    /// Reads the raw tag of a property of type `property_name`, returns it with the length of
+    /// the body.
+    pub(crate) fn read_tag<R: Read + Seek>(
+        cursor: &mut R,
+        property_name: &str,
+        options: &PropertyOptions,
+    ) -> Result<(Vec<u8>, u32), Error> {
+        options.check_tag_layout(cursor)?;
+        let mut tag = Vec::new();
+        // Length and array index
+        read_raw(cursor, &mut tag, 8)?;
+        let length = u32::from_le_bytes([tag[0], tag[1], tag[2], tag[3]]);
+
+        match PropertyKind::from_type_name(property_name) {
+            Some(PropertyKind::StructProperty) => {
+                read_raw_string(cursor, &mut tag)?;
+                if options.supports_package_version(
+                    EUnrealEngineObjectUE4Version::StructGuidInPropertyTag,
+                ) {
+                    read_raw(cursor, &mut tag, 16)?;
+                }
+            }
+            Some(PropertyKind::BoolProperty) => read_raw(cursor, &mut tag, 1)?,
+            Some(
+                PropertyKind::ArrayProperty
+                | PropertyKind::ByteProperty
+                | PropertyKind::EnumProperty
+                | PropertyKind::SetProperty,
+            ) => read_raw_string(cursor, &mut tag)?,
+            Some(PropertyKind::MapProperty) => {
+                read_raw_string(cursor, &mut tag)?;
+                read_raw_string(cursor, &mut tag)?;
+            }
+            _ => {}
+        }
+
+        if options
+            .supports_package_version(EUnrealEngineObjectUE4Version::PropertyGuidInPropertyTag)
+        {
+            let has_property_guid = cursor.read_u8()?;
+            tag.push(has_property_guid);
+            if has_property_guid != 0 {
+                read_raw(cursor, &mut tag, 16)?;
+            }
+        }
+        Ok((tag, length))
+    }
+}
+
+/// Appends `len` bytes read from `cursor` to `buf`.
+fn read_raw<R: Read>(cursor: &mut R, buf: &mut Vec<u8>, len: u64) -> Result<(), Error> {
+    let read = cursor.by_ref().take(len).read_to_end(buf)?;
+    if read as u64 != len {
+        Err(std::io::Error::from(ErrorKind::UnexpectedEof))?
+    }
+    Ok(())
+}
+
+/// Appends a string read from `cursor` to `buf` without decoding it.
+fn read_raw_string<R: Read>(cursor: &mut R, buf: &mut Vec<u8>) -> Result<(), Error> {
+    let len = cursor.read_i32::<LittleEndian>()?;
+    buf.extend_from_slice(&len.to_le_bytes());
+    // Negative lengths count UTF-16 code units
+    let bytes = match len < 0 {
+        true => u64::from(len.unsigned_abs()) * 2,
+        false => len as u64,
+    };
+    read_raw(cursor, buf, bytes)
+}
+
+impl DeepSizeOf for UnknownProperty {
+    #[inline]
+    fn heap_size_of(&self) -> usize {
+        self.property_name.heap_size_of()
+            + self.raw.heap_size_of()
+            + self.tag.as_ref().map_or(0, |tag| tag.heap_size_of())
+    }
+}
+
+impl StableHash for UnknownProperty {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        self.property_name.stable_hash_into(hasher);
+        self.raw.stable_hash_into(hasher);
+        // Only hashed when present, so hashes of other unknown properties don't change
+        if let Some(tag) = &self.tag {
+            hasher.write_str("Tag");
+            tag.stable_hash_into(hasher);
+        }
+    }
 }
 
 impl PropertyTrait for UnknownProperty {
@@ -68,6 +235,23 @@ impl PropertyTrait for UnknownProperty {
             return self.write_body(cursor, options);
         }
 
+        if let Some(tag) = &self.tag {
+            // The tag starts with the length and the array index
+            let Some(rest) = tag.get(8..) else {
+                Err(SerializeError::invalid_value(format!(
+                    "Tag of {} is too short",
+                    self.property_name
+                )))?
+            };
+            let name_len = cursor.write_string(&self.property_name)?;
+            // Declares the length of the body, which may have been replaced since reading
+            cursor.write_u32::<LittleEndian>(self.raw.len() as u32)?;
+            cursor.write_all(&tag[4..8])?;
+            cursor.write_all(rest)?;
+            let body_len = self.write_body(cursor, options)?;
+            return Ok(name_len + tag.len() + body_len);
+        }
+
         let buf = &mut Cursor::new(Vec::new());
         let body_len = self.write_body(buf, options)?;
         let buf = buf.get_ref();
@@ -75,10 +259,10 @@ impl PropertyTrait for UnknownProperty {
         let name_len = cursor.write_string(&self.property_name)?;
         cursor.write_u32::<LittleEndian>(buf.len() as u32)?;
         cursor.write_u32::<LittleEndian>(0)?;
-        cursor.write_u8(0)?;
+        let flag_len = options.write_property_guid_flag(cursor)?;
         cursor.write_all(buf)?;
 
-        Ok(9 + name_len + body_len)
+        Ok(8 + name_len + flag_len + body_len)
     }
 
     #[inline]
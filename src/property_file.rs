@@ -0,0 +1,223 @@
+//! Standalone property files (`.gvasprop`)
+//!
+//! A property file stores a single property subtree together with the context needed to read
+//! it again: the package and custom versions of the save it came from and the type hints for
+//! its path. This allows sharing items or builds between saves without the rest of the file.
+//!
+//! # Layout
+//!
+//! | Field             | Type                                  |
+//! |-------------------|---------------------------------------|
+//! | Magic             | `u32`, `GVPR`                         |
+//! | Format version    | `u32`                                 |
+//! | Package versions  | `u32` UE4, `u32` UE5                  |
+//! | Custom versions   | `u32` count, then `Guid` and `u32`    |
+//! | Hints             | `u32` count, then a string and a hint |
+//! | Path              | string                                |
+//! | Property          | string type name, then the property   |
+//!
+//! A hint is a `u8` tag followed by its value: `0` a struct type string, `1` an element width
+//! `u32`, `2` raw without a width and `3` raw followed by its width `u32`. Version 1 files store
+//! every hint as a struct type string without a tag.
+
+use std::{
+    collections::HashMap,
+    io::{Read, Seek, Write},
+};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{
+    cursor_ext::{ReadExt, WriteExt},
+    custom_version::{self, CustomVersionFormat},
+    error::{DeserializeError, Error},
+    hints::HintValue,
+    object_version::PackageVersion,
+    options::{ReadOptions, WriteOptions},
+    properties::{property_kind::PropertyKind, Property, PropertyOptions, PropertyTrait},
+    types::{map::HashableIndexMap, Guid},
+};
+
+/// The four bytes 'GVPR' appear at the beginning of every property file.
+pub const FILE_TYPE_GVPR: u32 = u32::from_le_bytes(*b"GVPR");
+
+/// Current version of the property file layout.
+pub const PROPERTY_FILE_VERSION: u32 = 2;
+
+/// A single property exported from a save, see [`GvasFile::export_property`].
+///
+/// [`GvasFile::export_property`]: crate::GvasFile::export_property
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyFile {
+    /// Path of the property in the save it was exported from.
+    pub path: String,
+    /// The exported property.
+    pub property: Property,
+    /// Package versions of the save it was exported from.
+    pub package_version: PackageVersion,
+    /// Custom versions of the save it was exported from.
+    pub custom_versions: HashableIndexMap<Guid, u32>,
+    /// Hints needed to read the property.
+    pub hints: HashMap<String, HintValue>,
+}
+
+impl PropertyFile {
+    /// Read a property file.
+    pub fn read<R: Read + Seek>(reader: &mut R) -> Result<Self, Error> {
+        let file_type_tag = reader.read_u32::<LittleEndian>()?;
+        if file_type_tag != FILE_TYPE_GVPR {
+            Err(DeserializeError::InvalidHeader(
+                format!("File type {file_type_tag} not recognized").into_boxed_str(),
+            ))?
+        }
+
+        let format_version = reader.read_u32::<LittleEndian>()?;
+        if !(1..=PROPERTY_FILE_VERSION).contains(&format_version) {
+            Err(DeserializeError::InvalidHeader(
+                format!("Property file version {format_version} not supported").into_boxed_str(),
+            ))?
+        }
+
+        let package_version = PackageVersion {
+            ue4: reader.read_u32::<LittleEndian>()?,
+            ue5: reader.read_u32::<LittleEndian>()?,
+        };
+
+        let custom_versions =
+            custom_version::read_container(reader, CustomVersionFormat::Optimized)?;
+
+        let hints_len = reader.read_u32::<LittleEndian>()?;
+        let mut hints = HashMap::with_capacity(hints_len as usize);
+        for _ in 0..hints_len {
+            let key = reader.read_string()?;
+            let value = match format_version {
+                1 => HintValue::Struct(reader.read_string()?),
+                _ => read_hint(reader)?,
+            };
+            hints.insert(key, value);
+        }
+
+        let path = reader.read_string()?;
+        let property_type = reader.read_string()?;
+        let mut properties_stack = properties_stack(&path);
+        let mut options = PropertyOptions {
+            hints: &hints,
+            properties_stack: &mut properties_stack,
+            custom_versions: &custom_versions,
+            package_version,
+            read_options: &ReadOptions::default(),
+            write_options: &WriteOptions::default(),
+        };
+        let property = Property::new(reader, &property_type, true, &mut options, None)?;
+
+        Ok(PropertyFile {
+            path,
+            property,
+            package_version,
+            custom_versions,
+            hints,
+        })
+    }
+
+    /// Write a property file.
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_u32::<LittleEndian>(FILE_TYPE_GVPR)?;
+        writer.write_u32::<LittleEndian>(PROPERTY_FILE_VERSION)?;
+        writer.write_u32::<LittleEndian>(self.package_version.ue4)?;
+        writer.write_u32::<LittleEndian>(self.package_version.ue5)?;
+
+        custom_version::write_container(
+            writer,
+            CustomVersionFormat::Optimized,
+            &self.custom_versions,
+        )?;
+
+        writer.write_u32::<LittleEndian>(self.hints.len() as u32)?;
+        for (key, value) in &self.hints {
+            writer.write_string(key)?;
+            write_hint(writer, value)?;
+        }
+
+        writer.write_string(&self.path)?;
+        let mut options = PropertyOptions {
+            hints: &self.hints,
+            properties_stack: &mut Vec::new(),
+            custom_versions: &self.custom_versions,
+            package_version: self.package_version,
+            read_options: &ReadOptions::default(),
+            write_options: &WriteOptions::default(),
+        };
+        self.property.write(writer, true, &mut options)?;
+        Ok(())
+    }
+}
+
+fn read_hint<R: Read + Seek>(reader: &mut R) -> Result<HintValue, Error> {
+    let tag = reader.read_u8()?;
+    Ok(match tag {
+        0 => HintValue::Struct(reader.read_string()?),
+        1 => HintValue::ElementWidth(reader.read_u32::<LittleEndian>()?),
+        2 => HintValue::Raw(None),
+        3 => HintValue::Raw(Some(reader.read_u32::<LittleEndian>()?)),
+        tag => Err(DeserializeError::InvalidHeader(
+            format!("Hint tag {tag} not recognized").into_boxed_str(),
+        ))?,
+    })
+}
+
+fn write_hint<W: Write>(writer: &mut W, hint: &HintValue) -> Result<(), Error> {
+    match hint {
+        HintValue::Struct(struct_type) => {
+            writer.write_u8(0)?;
+            writer.write_string(struct_type)?;
+        }
+        HintValue::ElementWidth(width) => {
+            writer.write_u8(1)?;
+            writer.write_u32::<LittleEndian>(*width)?;
+        }
+        HintValue::Raw(None) => writer.write_u8(2)?,
+        HintValue::Raw(Some(width)) => {
+            writer.write_u8(3)?;
+            writer.write_u32::<LittleEndian>(*width)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the properties stack of the property at a `.` separated `path`, before the type of
+/// the property itself is pushed.
+///
+/// Each struct leading to the property is followed by its type name, like the hint paths of
+/// [`Hints`](crate::hints::Hints), so `a.b` becomes `a.StructProperty.b`.
+pub(crate) fn properties_stack(path: &str) -> Vec<String> {
+    let mut stack = Vec::new();
+    for name in path.split('.') {
+        if !stack.is_empty() {
+            stack.push(PropertyKind::StructProperty.as_str().to_string());
+        }
+        stack.push(name.to_string());
+    }
+    stack
+}
+
+/// Fields of a custom struct property, if it is one.
+pub(crate) fn struct_fields(
+    property: &Property,
+) -> Option<&HashableIndexMap<String, Vec<Property>>> {
+    match property {
+        Property::StructProperty(property) => property.value.get_custom_struct(),
+        Property::StructPropertyValue(value) => value.get_custom_struct(),
+        _ => None,
+    }
+}
+
+/// Mutable fields of a custom struct property, if it is one.
+pub(crate) fn struct_fields_mut(
+    property: &mut Property,
+) -> Option<&mut HashableIndexMap<String, Vec<Property>>> {
+    match property {
+        Property::StructProperty(property) => property.value.get_custom_struct_mut(),
+        Property::StructPropertyValue(value) => value.get_custom_struct_mut(),
+        _ => None,
+    }
+}
@@ -0,0 +1,184 @@
+//! Reading the properties of a save one at a time
+//!
+//! [`GvasFile::read_properties_iter`] reads the header of a save and returns a [`PropertyIter`],
+//! which parses a root property each time it's advanced. Only the property being read is kept in
+//! memory, so tools can look for a few properties in a large save, like a Palworld `Level.sav`,
+//! without holding the whole property tree.
+//!
+//! ```no_run
+//! use gvas::{game_version::GameVersion, hints::Hints, options::ReadOptions, GvasFile};
+//! use std::fs::File;
+//!
+//! let mut file = File::open("Level.sav")?;
+//! let properties = GvasFile::read_properties_iter(
+//!     &mut file,
+//!     GameVersion::Palworld,
+//!     Hints::new(),
+//!     &ReadOptions::default(),
+//! )?;
+//! for property in properties {
+//!     let (name, _property) = property?;
+//!     println!("{name}");
+//! }
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+//!
+//! Uncompressed and Palworld saves are decoded while they are iterated. Chunked, Grounded and zip
+//! saves are decompressed into memory first, and so are saves with data preceding the header.
+//!
+//! Properties are parsed in full even when they are dropped right away, skipping a property
+//! only saves the memory it would have used. Properties left out by
+//! [`ReadOptions::root_properties`] aren't parsed or returned at all.
+//!
+//! [`GvasFile::read_properties_iter`]: crate::GvasFile::read_properties_iter
+//! [`ReadOptions::root_properties`]: crate::options::ReadOptions::root_properties
+//! [`PropertyIter`]: crate::property_iter::PropertyIter
+
+use std::{
+    borrow::Cow,
+    io::{Read, Seek},
+};
+
+use crate::{
+    buffered_prefix,
+    container::{self, Contents},
+    error::Error,
+    game_version::{DeserializedGameVersion, GameVersion},
+    hints::Hints,
+    options::{ReadOptions, WriteOptions},
+    properties::{Property, PropertyOptions},
+    read_root_property, read_trailing_padding, stream_prefix, GvasHeader,
+};
+
+/// A reader which can seek, boxed by [`PropertyIter`].
+trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+/// Iterator over the root properties of a save, see [`GvasFile::read_properties_iter`].
+///
+/// Iteration stops at the first error.
+///
+/// [`GvasFile::read_properties_iter`]: crate::GvasFile::read_properties_iter
+pub struct PropertyIter<'a> {
+    reader: Box<dyn ReadSeek + 'a>,
+    deserialized_game_version: DeserializedGameVersion,
+    prefix: Vec<u8>,
+    header: GvasHeader,
+    hints: Hints,
+    read_options: ReadOptions,
+    properties_stack: Vec<String>,
+    trailing_padding: Option<i32>,
+    trailing_bytes: Vec<u8>,
+    finished: bool,
+}
+
+impl<'a> PropertyIter<'a> {
+    pub(crate) fn new<R: Read + Seek>(
+        cursor: &'a mut R,
+        game_version: GameVersion,
+        hints: Hints,
+        read_options: &ReadOptions,
+    ) -> Result<Self, Error> {
+        let hints = hints.for_game_version(game_version, read_options);
+        let (contents, deserialized_game_version) = container::contents(cursor, game_version)?;
+        let (mut reader, prefix): (Box<dyn ReadSeek + 'a>, Vec<u8>) = match contents {
+            Contents::Plain(cursor) => (Box::new(cursor), Vec::new()),
+            Contents::Buffered(data) => {
+                let (cursor, prefix) = buffered_prefix(data)?;
+                (Box::new(cursor), prefix)
+            }
+            Contents::Streamed { reader, .. } => {
+                let (reader, prefix) = stream_prefix(reader)?;
+                (Box::new(reader), prefix)
+            }
+        };
+
+        let header = GvasHeader::read_with_options(&mut reader, read_options)?;
+
+        let hints = match hints.with_presets(header.get_save_game_class_name(), read_options) {
+            Cow::Owned(preset) => preset,
+            Cow::Borrowed(_) => hints,
+        };
+
+        Ok(PropertyIter {
+            reader,
+            deserialized_game_version,
+            prefix,
+            header,
+            hints,
+            read_options: read_options.clone(),
+            properties_stack: Vec::new(),
+            trailing_padding: None,
+            trailing_bytes: Vec::new(),
+            finished: false,
+        })
+    }
+
+    /// Returns the header of the save.
+    #[inline]
+    pub fn header(&self) -> &GvasHeader {
+        &self.header
+    }
+
+    /// Returns the container the save was read from.
+    #[inline]
+    pub fn deserialized_game_version(&self) -> &DeserializedGameVersion {
+        &self.deserialized_game_version
+    }
+
+    /// Returns the data preceding the header.
+    #[inline]
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    /// Returns the padding read after the `None` terminator.
+    ///
+    /// This is `None` until every property has been read, or if the file ended without it.
+    #[inline]
+    pub fn trailing_padding(&self) -> Option<i32> {
+        self.trailing_padding
+    }
+
+    /// Returns the bytes read after the `None` terminator when the file ended before a full
+    /// padding value.
+    #[inline]
+    pub fn trailing_bytes(&self) -> &[u8] {
+        &self.trailing_bytes
+    }
+
+    fn read_next(&mut self) -> Result<Option<(String, Property)>, Error> {
+        let mut options = PropertyOptions {
+            hints: &self.hints,
+            properties_stack: &mut self.properties_stack,
+            custom_versions: self.header.get_custom_versions(),
+            package_version: self.header.get_package_version(),
+            read_options: &self.read_options,
+            write_options: &WriteOptions::default(),
+        };
+        let property = read_root_property(&mut self.reader, &mut options, false)?;
+        if property.is_none() {
+            self.finished = true;
+            (self.trailing_padding, self.trailing_bytes) = read_trailing_padding(&mut self.reader)?;
+        }
+        Ok(property)
+    }
+}
+
+impl Iterator for PropertyIter<'_> {
+    type Item = Result<(String, Property), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.read_next() {
+            Ok(property) => property.map(Ok),
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
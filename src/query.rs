@@ -0,0 +1,249 @@
+//! Queries over property paths
+//!
+//! A [`Query`] selects properties by a pattern over the paths described in
+//! [`visitor`]. Patterns are made of these segments:
+//!
+//! - `name` matches a property or struct field, `*` in a name matches any run of characters
+//! - `**` matches any number of segments, including none
+//! - `[2]` matches an element of an array, set or map by index, `[*]` matches every element
+//! - `['playtime_*']` matches the map entries whose key matches the quoted pattern
+//!
+//! Only properties are selected, so the elements of containers that store plain values, like
+//! [`MapProperty::StrInt`] or [`ArrayProperty::Ints`], can't be selected by themselves.
+//!
+//! [`Query`]: crate::query::Query
+//! [`visitor`]: crate::visitor
+//! [`MapProperty::StrInt`]: crate::properties::map_property::MapProperty::StrInt
+//! [`ArrayProperty::Ints`]: crate::properties::array_property::ArrayProperty::Ints
+//!
+//! ```
+//! use gvas::{game_version::GameVersion, query::select, GvasFile};
+//! use std::fs::File;
+//!
+//! let mut file = File::open("resources/test/Slot1.sav")?;
+//! let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+//!
+//! let matches = select(&gvas_file, "array_of_structs[*].test_field")?;
+//! assert_eq!(matches[1].0, "array_of_structs[1].test_field");
+//!
+//! let fields = select(&gvas_file, "**.test_field")?;
+//! assert!(fields.len() > matches.len());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use indexmap::IndexMap;
+
+use crate::{
+    properties::{map_property::MapProperty, Property},
+    table, visitor, GvasFile,
+};
+
+/// An error ocurred while parsing a [`Query`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseQueryError {
+    /// Byte offset of the invalid segment in the query.
+    pub position: usize,
+}
+
+impl Display for ParseQueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid query syntax at offset {}", self.position)
+    }
+}
+
+impl Error for ParseQueryError {}
+
+/// A segment of a [`Query`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// A property or field name pattern.
+    Field(String),
+    /// Any number of segments.
+    AnyDepth,
+    /// An element by index.
+    Index(usize),
+    /// Every element.
+    AnyIndex,
+    /// Map entries by key pattern.
+    Key(String),
+}
+
+/// A segment of a property path.
+#[derive(Debug, Clone, Copy)]
+enum PathSegment<'p> {
+    Field(&'p str),
+    Index(usize),
+}
+
+/// A parsed query, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    segments: Vec<Segment>,
+}
+
+impl Query {
+    /// Every property of `file` matching this query with its path, in file order.
+    pub fn select<'a>(&self, file: &'a GvasFile) -> Vec<(String, &'a Property)> {
+        let nodes = file
+            .properties
+            .iter()
+            .flat_map(|(name, property)| visitor::nodes(name, property))
+            .collect::<IndexMap<_, _>>();
+        nodes
+            .iter()
+            .filter(|(path, _)| {
+                let segments = parse_path(path);
+                matches(&self.segments, &segments, path, &nodes)
+            })
+            .map(|(path, property)| (path.clone(), *property))
+            .collect()
+    }
+}
+
+impl FromStr for Query {
+    type Err = ParseQueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut rest = s;
+        loop {
+            let position = s.len() - rest.len();
+            let error = ParseQueryError { position };
+            if let Some(bracket) = rest.strip_prefix('[') {
+                let (inner, after) = bracket.split_once(']').ok_or(error)?;
+                let segment = if inner == "*" {
+                    Segment::AnyIndex
+                } else if let Ok(index) = inner.parse() {
+                    Segment::Index(index)
+                } else {
+                    let key = ['\'', '"']
+                        .iter()
+                        .find_map(|quote| inner.strip_prefix(*quote)?.strip_suffix(*quote))
+                        .ok_or(error)?;
+                    Segment::Key(key.to_string())
+                };
+                segments.push(segment);
+                rest = after;
+            } else {
+                let rest_of_field = match segments.is_empty() {
+                    true => Some(rest),
+                    false => rest.strip_prefix('.'),
+                };
+                let field = rest_of_field.ok_or(error)?;
+                let end = field.find(['.', '[']).unwrap_or(field.len());
+                let (name, after) = field.split_at(end);
+                segments.push(match name {
+                    "" => Err(error)?,
+                    name if name.contains(']') => Err(error)?,
+                    "**" => Segment::AnyDepth,
+                    name => Segment::Field(name.to_string()),
+                });
+                rest = after;
+            }
+            if rest.is_empty() {
+                return Ok(Query { segments });
+            }
+        }
+    }
+}
+
+/// Every property of `file` matching `query` with its path, see [`Query`].
+///
+/// # Errors
+///
+/// If `query` isn't valid this function returns [`ParseQueryError`].
+pub fn select<'a>(
+    file: &'a GvasFile,
+    query: &str,
+) -> Result<Vec<(String, &'a Property)>, ParseQueryError> {
+    Ok(query.parse::<Query>()?.select(file))
+}
+
+/// Split a path into segments with their byte offset.
+fn parse_path(path: &str) -> Vec<(usize, PathSegment<'_>)> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    while start < path.len() {
+        let rest = &path[start..];
+        if let Some(index) = rest.strip_prefix('[') {
+            let end = index.find(']').unwrap_or(index.len());
+            if let Ok(index) = index[..end].parse() {
+                segments.push((start, PathSegment::Index(index)));
+            }
+            start += end + 2;
+        } else {
+            let field = rest.strip_prefix('.').unwrap_or(rest);
+            let end = field.find(['.', '[']).unwrap_or(field.len());
+            segments.push((start, PathSegment::Field(&field[..end])));
+            start += rest.len() - field.len() + end;
+        }
+    }
+    segments
+}
+
+fn matches(
+    query: &[Segment],
+    segments: &[(usize, PathSegment)],
+    path: &str,
+    nodes: &IndexMap<String, &Property>,
+) -> bool {
+    let Some((segment, query_rest)) = query.split_first() else {
+        return segments.is_empty();
+    };
+    if *segment == Segment::AnyDepth {
+        return matches(query_rest, segments, path, nodes)
+            || (!segments.is_empty() && matches(query, &segments[1..], path, nodes));
+    }
+    let Some(((start, path_segment), segments_rest)) = segments.split_first() else {
+        return false;
+    };
+    let matched = match (segment, path_segment) {
+        (Segment::Field(pattern), PathSegment::Field(name)) => glob(pattern, name),
+        (Segment::Index(expected), PathSegment::Index(index)) => expected == index,
+        (Segment::AnyIndex, PathSegment::Index(_)) => true,
+        (Segment::Key(pattern), PathSegment::Index(index)) => nodes
+            .get(&path[..*start])
+            .and_then(|parent| map_key(parent, *index))
+            .is_some_and(|key| glob(pattern, &key)),
+        _ => false,
+    };
+    matched && matches(query_rest, segments_rest, path, nodes)
+}
+
+/// The key of the map entry at `index`, if `property` is a map with simple keys.
+fn map_key(property: &Property, index: usize) -> Option<String> {
+    match property {
+        Property::MapProperty(MapProperty::EnumProperty {
+            enum_props: map, ..
+        })
+        | Property::MapProperty(MapProperty::NameProperty {
+            name_props: map, ..
+        })
+        | Property::MapProperty(MapProperty::StrProperty { str_props: map, .. }) => {
+            map.get_index(index).map(|(key, _)| key.clone())
+        }
+        Property::MapProperty(MapProperty::Properties { value: map, .. }) => map
+            .get_index(index)
+            .and_then(|(key, _)| table::key_string(key)),
+        _ => None,
+    }
+}
+
+/// Check if `value` matches `pattern`, where `*` matches any run of characters.
+pub(crate) fn glob(pattern: &str, value: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == value,
+        Some((prefix, rest)) => {
+            let Some(value) = value.strip_prefix(prefix) else {
+                return false;
+            };
+            value
+                .char_indices()
+                .map(|(index, _)| index)
+                .chain([value.len()])
+                .any(|index| glob(rest, &value[index..]))
+        }
+    }
+}
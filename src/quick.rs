@@ -0,0 +1,86 @@
+//! Open, edit and save a file in one call
+//!
+//! [`update`] reads a save, passes it to a callback and, if the callback succeeds, writes it
+//! back to the same path. The previous file is kept as `<path>.bak` and the new one replaces it
+//! atomically, see [`GvasFile::save_with_options`].
+//!
+//! ```no_run
+//! use gvas::{game_version::GameVersion, quick};
+//!
+//! quick::update("SaveSlot.sav", GameVersion::Auto, |file| {
+//!     file.set_int("HighScore", 9999)?;
+//!     Ok(())
+//! })?;
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+//!
+//! Files which need hints or read options can use [`update_with_options`].
+//!
+//! [`update`]: crate::quick::update
+//! [`update_with_options`]: crate::quick::update_with_options
+//! [`GvasFile::save_with_options`]: crate::GvasFile::save_with_options
+
+use std::path::Path;
+
+use crate::{
+    error::Error,
+    game_version::GameVersion,
+    hints::Hints,
+    options::{ReadOptions, WriteOptions},
+    GvasFile,
+};
+
+/// Read the file at `path`.
+///
+/// # Errors
+///
+/// If the file can't be read this function returns [`Error`]
+#[inline]
+pub fn read<P: AsRef<Path>>(path: P, game_version: GameVersion) -> Result<GvasFile, Error> {
+    GvasFile::open(path, game_version, Hints::new(), &ReadOptions::default())
+}
+
+/// Read the file at `path`, edit it with `f` and save it, keeping a backup.
+///
+/// Nothing is written if `f` returns an error.
+///
+/// # Errors
+///
+/// If the file can't be read or written, or `f` fails, this function returns [`Error`]
+#[inline]
+pub fn update<P, F>(path: P, game_version: GameVersion, f: F) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&mut GvasFile) -> Result<(), Error>,
+{
+    update_with_options(path, game_version, Hints::new(), &ReadOptions::default(), f)
+}
+
+/// Read the file at `path` with `hints` and `read_options`, edit it with `f` and save it,
+/// keeping a backup.
+///
+/// # Errors
+///
+/// If the file can't be read or written, or `f` fails, this function returns [`Error`]
+pub fn update_with_options<P, F>(
+    path: P,
+    game_version: GameVersion,
+    hints: impl Into<Hints>,
+    read_options: &ReadOptions,
+    f: F,
+) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&mut GvasFile) -> Result<(), Error>,
+{
+    let path = path.as_ref();
+    let mut file = GvasFile::open(path, game_version, hints, read_options)?;
+    f(&mut file)?;
+
+    let write_options = WriteOptions {
+        backup: true,
+        codepage: read_options.codepage,
+        ..Default::default()
+    };
+    file.save_with_options(path, &write_options)
+}
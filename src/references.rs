@@ -0,0 +1,259 @@
+//! Graphs of object references
+//!
+//! Saves refer to objects of the game by path: `ObjectProperty` values, soft object paths and
+//! the objects bound to delegates. [`collect`] finds every reference below a property and
+//! groups them into a [`ReferenceGraph`], whose nodes are object paths and whose edges are the
+//! paths of the properties referring to them. [`ReferenceGraph::write_dot`] writes the graph in
+//! the DOT format of Graphviz.
+//!
+//! Paths use the format described in [`visitor`](crate::visitor).
+//!
+//! [`collect`]: crate::references::collect
+//! [`ReferenceGraph`]: crate::references::ReferenceGraph
+//! [`ReferenceGraph::write_dot`]: crate::references::ReferenceGraph::write_dot
+//!
+//! ```
+//! use gvas::{
+//!     properties::{object_property::ObjectProperty, Property},
+//!     references::{self, ReferenceKind},
+//! };
+//!
+//! let property = Property::from(ObjectProperty::from("/Game/Maps/Level.Level:Door_1"));
+//! let graph = references::collect("Target", &property);
+//!
+//! let references = &graph.objects["/Game/Maps/Level.Level:Door_1"];
+//! assert_eq!(references[0].path, "Target");
+//! assert_eq!(references[0].kind, ReferenceKind::Object);
+//!
+//! let mut dot = Vec::new();
+//! graph.write_dot(&mut dot)?;
+//! assert!(String::from_utf8(dot).expect("DOT is valid UTF-8").starts_with("digraph"));
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+
+use std::io::{Cursor, Write};
+
+use indexmap::IndexMap;
+
+use crate::{
+    cursor_ext::ReadExt,
+    error::Error,
+    properties::{
+        delegate_property::Delegate,
+        type_names::{
+            SOFT_CLASS_PATH, SOFT_CLASS_PROPERTY, SOFT_OBJECT_PATH, SOFT_OBJECT_PROPERTY,
+        },
+        Property,
+    },
+    visitor::{walk, Visitor},
+};
+
+/// How a property refers to an object.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ReferenceKind {
+    /// The value of an `ObjectProperty`.
+    Object,
+    /// A `SoftObjectProperty`, `SoftClassProperty`, `SoftObjectPath` or `SoftClassPath` value.
+    SoftObject,
+    /// The object bound to a delegate, with the name of the bound function.
+    Delegate(String),
+}
+
+/// A property referring to an object.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Reference {
+    /// Path of the property.
+    pub path: String,
+    /// How the property refers to the object.
+    pub kind: ReferenceKind,
+}
+
+/// The objects referred to by a property tree, see the [module documentation](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReferenceGraph {
+    /// Every referenced object path with the properties referring to it, in the order they were
+    /// found.
+    pub objects: IndexMap<String, Vec<Reference>>,
+}
+
+impl ReferenceGraph {
+    /// Creates a new, empty `ReferenceGraph` instance.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the references of `other` to this graph.
+    pub fn extend(&mut self, other: ReferenceGraph) {
+        for (object, references) in other.objects {
+            self.objects.entry(object).or_default().extend(references);
+        }
+    }
+
+    /// The number of references in the graph.
+    pub fn reference_count(&self) -> usize {
+        self.objects.values().map(Vec::len).sum()
+    }
+
+    /// Write the graph in the DOT format.
+    ///
+    /// Every root property and every object is a node. Each reference is an edge from the root
+    /// property holding it to the object, labelled with the path of the referring property.
+    pub fn write_dot<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(b"digraph references {\n")?;
+        let mut roots = Vec::new();
+        for reference in self.objects.values().flatten() {
+            let root = root_name(&reference.path);
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+        for root in roots {
+            writeln!(writer, "    {} [shape=ellipse];", dot_id(root))?;
+        }
+        writer.write_all(b"    node [shape=box];\n")?;
+        for (object, references) in &self.objects {
+            writeln!(writer, "    {};", dot_id(object))?;
+            for reference in references {
+                let root = root_name(&reference.path);
+                let label = match &reference.kind {
+                    ReferenceKind::Object => reference.path.clone(),
+                    ReferenceKind::SoftObject => format!("{} (soft)", reference.path),
+                    ReferenceKind::Delegate(function_name) => {
+                        format!("{} ({function_name})", reference.path)
+                    }
+                };
+                writeln!(
+                    writer,
+                    "    {} -> {} [label={}];",
+                    dot_id(root),
+                    dot_id(object),
+                    dot_id(&label)
+                )?;
+            }
+        }
+        writer.write_all(b"}\n")?;
+        Ok(())
+    }
+
+    fn push(&mut self, object: &str, path: &str, kind: ReferenceKind) {
+        if object.is_empty() || object == "None" {
+            return;
+        }
+        let reference = Reference {
+            path: path.to_string(),
+            kind,
+        };
+        match self.objects.get_mut(object) {
+            Some(references) => references.push(reference),
+            None => {
+                self.objects.insert(object.to_string(), vec![reference]);
+            }
+        }
+    }
+
+    fn push_delegate(&mut self, delegate: &Delegate, path: &str) {
+        let kind = ReferenceKind::Delegate(delegate.function_name.clone());
+        self.push(&delegate.object, path, kind);
+    }
+}
+
+/// Find every object reference below `property`, with `path` as the path of `property`.
+///
+/// Soft object paths are only found when they are kept as raw bytes, like values of
+/// `SoftObjectProperty`, which is not a known property type. Null references are left out.
+pub fn collect(path: &str, property: &Property) -> ReferenceGraph {
+    let mut visitor = ReferenceVisitor {
+        graph: ReferenceGraph::new(),
+    };
+    walk(&mut visitor, path, property);
+    visitor.graph
+}
+
+/// Collects the references of a property tree, see [`collect`].
+struct ReferenceVisitor {
+    graph: ReferenceGraph,
+}
+
+impl Visitor for ReferenceVisitor {
+    fn visit_property(&mut self, path: &str, property: &Property) -> bool {
+        match property {
+            Property::ObjectProperty(property) => {
+                self.graph
+                    .push(&property.value, path, ReferenceKind::Object);
+            }
+            Property::DelegateProperty(property) => {
+                self.graph.push_delegate(&property.value, path);
+            }
+            Property::MulticastInlineDelegateProperty(property) => {
+                for (index, delegate) in property.value.delegates.iter().enumerate() {
+                    self.graph
+                        .push_delegate(delegate, &format!("{path}[{index}]"));
+                }
+            }
+            Property::MulticastSparseDelegateProperty(property) => {
+                for (index, delegate) in property.value.delegates.iter().enumerate() {
+                    self.graph
+                        .push_delegate(delegate, &format!("{path}[{index}]"));
+                }
+            }
+            Property::UnknownProperty(property) => {
+                let soft = matches!(
+                    property.property_name(),
+                    SOFT_OBJECT_PROPERTY | SOFT_CLASS_PROPERTY | SOFT_OBJECT_PATH | SOFT_CLASS_PATH
+                );
+                if let Some(object) = soft.then(|| soft_object_path(property.raw())).flatten() {
+                    self.graph.push(&object, path, ReferenceKind::SoftObject);
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Decodes a serialized soft object path as `/Package/Path.Asset:SubPath`.
+///
+/// UE4 stores the asset path and the sub-path, UE5 splits the asset path into the package and
+/// the asset name.
+fn soft_object_path(raw: &[u8]) -> Option<String> {
+    let mut cursor = Cursor::new(raw);
+    let mut strings = Vec::new();
+    while cursor.position() < raw.len() as u64 {
+        strings.push(cursor.read_fstring().ok()?.unwrap_or_default());
+    }
+    let (asset_path, sub_path) = match strings.as_slice() {
+        [asset_path, sub_path] => (asset_path.clone(), sub_path),
+        [package, asset, sub_path] if asset.is_empty() || asset == "None" => {
+            (package.clone(), sub_path)
+        }
+        [package, asset, sub_path] => (format!("{package}.{asset}"), sub_path),
+        _ => return None,
+    };
+    match sub_path.is_empty() {
+        true => Some(asset_path),
+        false => Some(format!("{asset_path}:{sub_path}")),
+    }
+}
+
+/// The root property of `path`.
+fn root_name(path: &str) -> &str {
+    let end = path.find(['.', '[']).unwrap_or(path.len());
+    &path[..end]
+}
+
+/// Quotes `value` as a DOT identifier.
+fn dot_id(value: &str) -> String {
+    let mut id = String::with_capacity(value.len() + 2);
+    id.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => id.push_str("\\\""),
+            '\\' => id.push_str("\\\\"),
+            '\n' => id.push_str("\\n"),
+            c => id.push(c),
+        }
+    }
+    id.push('"');
+    id
+}
@@ -1,3 +1,5 @@
+use std::ops::RangeInclusive;
+
 use num_enum::IntoPrimitive;
 
 /// Save Game File Version from FSaveGameFileVersion::Type
@@ -11,3 +13,11 @@ pub enum SaveGameVersion {
     /// added a new UE5 version number to FPackageFileSummary
     PackageFileSummaryVersionChange = 3,
 }
+
+impl SaveGameVersion {
+    /// The versions accepted by default, see [`ReadOptions::save_game_versions`].
+    ///
+    /// [`ReadOptions::save_game_versions`]: crate::options::ReadOptions::save_game_versions
+    pub const SUPPORTED: RangeInclusive<u32> = SaveGameVersion::AddedCustomVersions as u32
+        ..=SaveGameVersion::PackageFileSummaryVersionChange as u32;
+}
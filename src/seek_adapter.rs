@@ -0,0 +1,157 @@
+//! Seeking over readers which can't seek, such as stdin or a network stream.
+//!
+//! The parser mostly asks for its position and only looks back a few bytes, for example when
+//! [`GameVersion::Auto`] detects the container. A [`BufferedSeekAdapter`] keeps the most recently
+//! read bytes in a ring buffer, so seeking back within that window and seeking forward work on
+//! any [`Read`]. Seeking from the end of the stream isn't supported.
+//!
+//! ```no_run
+//! use gvas::{game_version::GameVersion, seek_adapter::BufferedSeekAdapter, GvasFile};
+//!
+//! let mut reader = BufferedSeekAdapter::new(std::io::stdin().lock());
+//! let file = GvasFile::read(&mut reader, GameVersion::Auto)?;
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+//!
+//! Uncompressed and Palworld saves are parsed while they are read. Chunked and Grounded saves are
+//! decompressed into memory first, and zip saves need a reader which can seek to the end.
+//!
+//! [`BufferedSeekAdapter`]: crate::seek_adapter::BufferedSeekAdapter
+//! [`GameVersion::Auto`]: crate::game_version::GameVersion::Auto
+
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Seek, SeekFrom},
+};
+
+/// Number of bytes kept for seeking back by [`BufferedSeekAdapter::new`].
+pub const DEFAULT_LOOKBACK: usize = 64 * 1024;
+
+/// Adapts a [`Read`] into a [`Read`] + [`Seek`] with a bounded lookback window.
+pub struct BufferedSeekAdapter<R> {
+    inner: R,
+    /// The last bytes read from `inner`, ending at `end`.
+    buffer: VecDeque<u8>,
+    lookback: usize,
+    /// Number of bytes read from `inner`.
+    end: u64,
+    position: u64,
+}
+
+impl<R: Read> BufferedSeekAdapter<R> {
+    /// Creates a new `BufferedSeekAdapter` keeping [`DEFAULT_LOOKBACK`] bytes.
+    #[inline]
+    pub fn new(inner: R) -> Self {
+        Self::with_lookback(inner, DEFAULT_LOOKBACK)
+    }
+
+    /// Creates a new `BufferedSeekAdapter` keeping the last `lookback` bytes.
+    #[inline]
+    pub fn with_lookback(inner: R, lookback: usize) -> Self {
+        BufferedSeekAdapter {
+            inner,
+            buffer: VecDeque::with_capacity(lookback.min(DEFAULT_LOOKBACK)),
+            lookback,
+            end: 0,
+            position: 0,
+        }
+    }
+
+    /// Returns the earliest position which can still be seeked to.
+    #[inline]
+    pub fn window_start(&self) -> u64 {
+        self.end - self.buffer.len() as u64
+    }
+
+    /// Unwraps this `BufferedSeekAdapter`, returning the inner reader.
+    ///
+    /// Bytes read from the inner reader but not yet from the adapter are lost.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads and drops bytes until `target` is reached.
+    fn skip_to(&mut self, target: u64) -> io::Result<()> {
+        let mut scratch = [0u8; 4096];
+        while self.position < target {
+            let len = (target - self.position).min(scratch.len() as u64) as usize;
+            if self.read(&mut scratch[..len])? == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "Seeking past the end of the stream",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for BufferedSeekAdapter<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position < self.end {
+            let offset = (self.position - self.window_start()) as usize;
+            let (front, back) = self.buffer.as_slices();
+            let buffered = match offset < front.len() {
+                true => &front[offset..],
+                false => &back[offset - front.len()..],
+            };
+            let len = buffered.len().min(buf.len());
+            buf[..len].copy_from_slice(&buffered[..len]);
+            self.position += len as u64;
+            return Ok(len);
+        }
+
+        let read = self.inner.read(buf)?;
+        let kept = &buf[read - read.min(self.lookback)..read];
+        let overflow = (self.buffer.len() + kept.len()).saturating_sub(self.lookback);
+        self.buffer.drain(..overflow);
+        self.buffer.extend(kept);
+        self.end += read as u64;
+        self.position = self.end;
+        Ok(read)
+    }
+}
+
+impl<R: Read> Seek for BufferedSeekAdapter<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(position) => Some(position),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+            SeekFrom::End(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "Seeking from the end is not supported on this reader",
+                ))
+            }
+        };
+        let Some(target) = target else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Seeking to a negative position",
+            ));
+        };
+
+        if target < self.window_start() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "Seeking to {target:#x} is before the lookback window starting at {:#x}",
+                    self.window_start()
+                ),
+            ));
+        }
+        if target <= self.end {
+            self.position = target;
+        } else {
+            self.position = self.end;
+            self.skip_to(target)?;
+        }
+        Ok(self.position)
+    }
+
+    #[inline]
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.position)
+    }
+}
@@ -0,0 +1,231 @@
+//! HMAC signatures of saves
+//!
+//! Private servers of some games sign the saves they hand out, so players can't edit them
+//! offline. A [`Signer`] computes an HMAC-SHA256 of a save with a secret key, stores it when
+//! writing and checks it again when reading.
+//!
+//! With [`SignatureLocation::Property`] the signature covers the GVAS data written without the
+//! signature property, and is stored as a byte array root property after the other root
+//! properties. With [`SignatureLocation::Trailer`] the signature covers every byte written,
+//! including any container, and is appended after them.
+//!
+//! ```
+//! use gvas::{
+//!     error::{DeserializeError, Error},
+//!     game_version::GameVersion,
+//!     hints::Hints,
+//!     options::{ReadOptions, WriteOptions},
+//!     signature::{SignatureLocation, Signer},
+//!     GvasFile,
+//! };
+//! use std::{fs::File, io::Cursor};
+//!
+//! let mut file = File::open("resources/test/Slot1.sav")?;
+//! let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+//!
+//! let signer = Signer::new("secret", SignatureLocation::Property(String::from("Signature")));
+//! let mut writer = Cursor::new(Vec::new());
+//! signer.write(&gvas_file, &mut writer, &WriteOptions::default())?;
+//!
+//! let read = |data: Vec<u8>| {
+//!     let mut reader = Cursor::new(data);
+//!     signer.read(&mut reader, GameVersion::Default, Hints::new(), &ReadOptions::default())
+//! };
+//! let mut signed = read(writer.into_inner())?;
+//! assert!(signed.properties.contains_key("Signature"));
+//!
+//! // Saves edited without the key are rejected
+//! signed.properties.shift_remove("u8_test");
+//! let mut writer = Cursor::new(Vec::new());
+//! signed.write(&mut writer)?;
+//! let error = read(writer.into_inner()).unwrap_err();
+//! assert!(matches!(
+//!     error.as_deserialize(),
+//!     Some(DeserializeError::InvalidSignature(_))
+//! ));
+//! # Ok::<(), Error>(())
+//! ```
+
+use std::{
+    fmt::Debug,
+    io::{Cursor, Read, Seek, Write},
+};
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{
+    error::{DeserializeError, Error, SerializeError},
+    game_version::GameVersion,
+    hints::Hints,
+    options::{ReadOptions, WriteOptions},
+    properties::{array_property::ArrayProperty, Property},
+    GvasFile,
+};
+
+/// Length of a signature in bytes.
+pub const SIGNATURE_LEN: usize = 32;
+
+/// Where a signature is stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureLocation {
+    /// In a root byte array property with this name.
+    Property(String),
+    /// After the end of the save.
+    Trailer,
+}
+
+/// Signs and verifies saves with a secret key, see the [module documentation](self).
+#[derive(Clone)]
+pub struct Signer {
+    key: Vec<u8>,
+    location: SignatureLocation,
+}
+
+impl Debug for Signer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The key is left out so it doesn't end up in logs
+        f.debug_struct("Signer")
+            .field("location", &self.location)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Signer {
+    /// Creates a signer storing signatures made with `key` at `location`.
+    #[inline]
+    pub fn new(key: impl Into<Vec<u8>>, location: SignatureLocation) -> Self {
+        Signer {
+            key: key.into(),
+            location,
+        }
+    }
+
+    /// Where signatures are stored.
+    #[inline]
+    pub fn location(&self) -> &SignatureLocation {
+        &self.location
+    }
+
+    /// The signature of `data`.
+    ///
+    /// # Errors
+    ///
+    /// If the key can't be used this function returns [`Error`]
+    pub fn sign(&self, data: &[u8]) -> Result<[u8; SIGNATURE_LEN], Error> {
+        let mut mac = self.mac()?;
+        mac.update(data);
+        Ok(mac.finalize().into_bytes().into())
+    }
+
+    /// Check that `signature` is the signature of `data`.
+    ///
+    /// # Errors
+    ///
+    /// If the signature doesn't match this function returns
+    /// [`DeserializeError::InvalidSignature`]
+    pub fn verify(&self, data: &[u8], signature: &[u8]) -> Result<(), Error> {
+        let mut mac = self.mac()?;
+        mac.update(data);
+        match mac.verify_slice(signature) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(DeserializeError::InvalidSignature(
+                "Signature doesn't match the contents".into(),
+            ))?,
+        }
+    }
+
+    /// Write `file` signed with the key of this signer.
+    ///
+    /// A signature property already in `file` is replaced.
+    ///
+    /// # Errors
+    ///
+    /// If this function fails to write the file it returns [`Error`]
+    pub fn write<W: Write>(
+        &self,
+        file: &GvasFile,
+        cursor: &mut W,
+        write_options: &WriteOptions,
+    ) -> Result<(), Error> {
+        match &self.location {
+            SignatureLocation::Property(name) => {
+                let mut file = file.clone();
+                file.properties.shift_remove(name);
+                let signature = self.sign(&file.write_data(write_options)?)?;
+                file.properties.insert(
+                    name.clone(),
+                    Property::from(ArrayProperty::Bytes {
+                        bytes: signature.as_slice().into(),
+                    }),
+                );
+                file.write_with_options(cursor, write_options)
+            }
+            SignatureLocation::Trailer => {
+                let mut writer = Cursor::new(Vec::new());
+                file.write_with_options(&mut writer, write_options)?;
+                let data = writer.into_inner();
+                cursor.write_all(&data)?;
+                cursor.write_all(&self.sign(&data)?)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Read a file signed with the key of this signer, see [`GvasFile::read_with_options`].
+    ///
+    /// The signature property is kept in the returned file, the signature trailer is dropped.
+    ///
+    /// # Errors
+    ///
+    /// If the signature is missing or doesn't match this function returns
+    /// [`DeserializeError::InvalidSignature`], if the file is invalid it returns [`Error`]
+    pub fn read<R: Read + Seek>(
+        &self,
+        cursor: &mut R,
+        game_version: GameVersion,
+        hints: impl Into<Hints>,
+        read_options: &ReadOptions,
+    ) -> Result<GvasFile, Error> {
+        match &self.location {
+            SignatureLocation::Property(name) => {
+                let file = GvasFile::read_with_options(cursor, game_version, hints, read_options)?;
+                let mut unsigned = file.clone();
+                let Some(Property::ArrayProperty(ArrayProperty::Bytes { bytes })) =
+                    unsigned.properties.shift_remove(name)
+                else {
+                    Err(DeserializeError::InvalidSignature(
+                        format!("Missing signature property {name}").into(),
+                    ))?
+                };
+                self.verify(&unsigned.write_data(&WriteOptions::default())?, &bytes)?;
+                Ok(file)
+            }
+            SignatureLocation::Trailer => {
+                let mut data = Vec::new();
+                cursor.read_to_end(&mut data)?;
+                let Some(length) = data.len().checked_sub(SIGNATURE_LEN) else {
+                    Err(DeserializeError::InvalidSignature(
+                        "Missing signature trailer".into(),
+                    ))?
+                };
+                let (body, signature) = data.split_at(length);
+                self.verify(body, signature)?;
+                data.truncate(length);
+                GvasFile::read_with_options(
+                    &mut Cursor::new(data),
+                    game_version,
+                    hints,
+                    read_options,
+                )
+            }
+        }
+    }
+
+    fn mac(&self) -> Result<Hmac<Sha256>, Error> {
+        match Hmac::new_from_slice(&self.key) {
+            Ok(mac) => Ok(mac),
+            Err(_) => Err(SerializeError::invalid_value("Invalid signature key"))?,
+        }
+    }
+}
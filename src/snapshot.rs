@@ -0,0 +1,218 @@
+//! Snapshots for undo and redo
+//!
+//! A [`Snapshot`] keeps every top-level property behind an [`Arc`](std::sync::Arc). Taking a
+//! snapshot with [`GvasFile::snapshot_since`] reuses the properties that didn't change since the
+//! previous snapshot, so a history of edits only stores the properties that were modified.
+
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+
+use crate::{game_version::DeserializedGameVersion, properties::Property, GvasFile, GvasHeader};
+
+/// An immutable copy of a [`GvasFile`], see [`GvasFile::snapshot`].
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    deserialized_game_version: DeserializedGameVersion,
+    prefix: Arc<Vec<u8>>,
+    header: Arc<GvasHeader>,
+    custom_version_extra: Vec<u8>,
+    properties: IndexMap<String, Arc<Property>>,
+    trailing_padding: Option<i32>,
+    trailing_bytes: Vec<u8>,
+}
+
+impl Snapshot {
+    pub(crate) fn new(file: &GvasFile, previous: Option<&Snapshot>) -> Self {
+        let share = |value: &Property, previous: Option<&Arc<Property>>| match previous {
+            Some(previous) if **previous == *value => previous.clone(),
+            _ => Arc::new(value.clone()),
+        };
+        let prefix = match previous {
+            Some(previous) if *previous.prefix == file.prefix => previous.prefix.clone(),
+            _ => Arc::new(file.prefix.clone()),
+        };
+        let header = match previous {
+            Some(previous) if *previous.header == file.header => previous.header.clone(),
+            _ => Arc::new(file.header.clone()),
+        };
+        let properties = file
+            .properties
+            .iter()
+            .map(|(name, property)| {
+                let previous = previous.and_then(|previous| previous.properties.get(name));
+                (name.clone(), share(property, previous))
+            })
+            .collect();
+
+        Snapshot {
+            deserialized_game_version: file.deserialized_game_version.clone(),
+            prefix,
+            header,
+            custom_version_extra: file.custom_version_extra.clone(),
+            properties,
+            trailing_padding: file.trailing_padding,
+            trailing_bytes: file.trailing_bytes.clone(),
+        }
+    }
+
+    /// Get a top-level property.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&Arc<Property>> {
+        self.properties.get(name)
+    }
+
+    /// Number of top-level properties stored in both snapshots without being copied.
+    pub fn shared_with(&self, other: &Snapshot) -> usize {
+        self.properties
+            .iter()
+            .filter(|(name, property)| {
+                other
+                    .properties
+                    .get(*name)
+                    .is_some_and(|other| Arc::ptr_eq(property, other))
+            })
+            .count()
+    }
+
+    /// Overwrite `file` with the contents of this snapshot.
+    ///
+    /// Properties that are already equal to the snapshot are left untouched.
+    pub(crate) fn restore(&self, file: &mut GvasFile) {
+        file.deserialized_game_version = self.deserialized_game_version.clone();
+        if file.prefix != *self.prefix {
+            file.prefix = (*self.prefix).clone();
+        }
+        if file.header != *self.header {
+            file.header = (*self.header).clone();
+        }
+        file.custom_version_extra
+            .clone_from(&self.custom_version_extra);
+        file.trailing_padding = self.trailing_padding;
+        file.trailing_bytes.clone_from(&self.trailing_bytes);
+
+        let unchanged = file.properties.len() == self.properties.len()
+            && file
+                .properties
+                .keys()
+                .zip(self.properties.keys())
+                .all(|(a, b)| a == b);
+        if unchanged {
+            for (property, snapshot) in file.properties.values_mut().zip(self.properties.values()) {
+                if *property != **snapshot {
+                    *property = (**snapshot).clone();
+                }
+            }
+        } else {
+            let mut properties = std::mem::take(&mut file.properties);
+            for (name, snapshot) in &self.properties {
+                let property = match properties.shift_remove(name) {
+                    Some(property) if property == **snapshot => property,
+                    _ => (**snapshot).clone(),
+                };
+                file.properties.insert(name.clone(), property);
+            }
+        }
+    }
+}
+
+/// An undo and redo stack of [`Snapshot`]s.
+///
+/// ```
+/// use gvas::{properties::{int_property::IntProperty, Property}, snapshot::History};
+/// # use gvas::{game_version::GameVersion, GvasFile};
+/// # let mut file = GvasFile::read(
+/// #     &mut std::fs::File::open("resources/test/Slot1.sav").unwrap(),
+/// #     GameVersion::Default,
+/// # ).unwrap();
+///
+/// let mut history = History::new(&file);
+/// file.properties.insert(String::from("Money"), Property::from(IntProperty::new(1000)));
+/// history.commit(&file);
+///
+/// assert!(history.undo(&mut file));
+/// assert!(!file.properties.contains_key("Money"));
+/// assert!(history.redo(&mut file));
+/// assert!(file.properties.contains_key("Money"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct History {
+    snapshots: Vec<Snapshot>,
+    position: usize,
+    limit: Option<usize>,
+}
+
+impl History {
+    /// Creates a new `History` starting at the current state of `file`.
+    #[inline]
+    pub fn new(file: &GvasFile) -> Self {
+        History {
+            snapshots: vec![file.snapshot()],
+            position: 0,
+            limit: None,
+        }
+    }
+
+    /// Keep at most `limit` undo steps, dropping the oldest ones first.
+    #[inline]
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self.trim();
+        self
+    }
+
+    /// Record the current state of `file`, discarding any redo steps.
+    pub fn commit(&mut self, file: &GvasFile) {
+        let snapshot = file.snapshot_since(&self.snapshots[self.position]);
+        self.snapshots.truncate(self.position + 1);
+        self.snapshots.push(snapshot);
+        self.position += 1;
+        self.trim();
+    }
+
+    /// Restore the previous state into `file`, returns false if there is nothing to undo.
+    pub fn undo(&mut self, file: &mut GvasFile) -> bool {
+        if !self.can_undo() {
+            return false;
+        }
+        self.position -= 1;
+        file.restore(&self.snapshots[self.position]);
+        true
+    }
+
+    /// Restore the next state into `file`, returns false if there is nothing to redo.
+    pub fn redo(&mut self, file: &mut GvasFile) -> bool {
+        if !self.can_redo() {
+            return false;
+        }
+        self.position += 1;
+        file.restore(&self.snapshots[self.position]);
+        true
+    }
+
+    /// Returns true if [`History::undo`] would change the file.
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        self.position > 0
+    }
+
+    /// Returns true if [`History::redo`] would change the file.
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        self.position + 1 < self.snapshots.len()
+    }
+
+    /// The snapshot matching the current state.
+    #[inline]
+    pub fn current(&self) -> &Snapshot {
+        &self.snapshots[self.position]
+    }
+
+    fn trim(&mut self) {
+        if let Some(limit) = self.limit {
+            let excess = self.snapshots.len().saturating_sub(limit + 1);
+            self.snapshots.drain(..excess);
+            self.position -= excess;
+        }
+    }
+}
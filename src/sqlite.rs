@@ -0,0 +1,573 @@
+//! SQLite export
+//!
+//! [`export`] writes every map, set and array of a save to its own SQLite table, so large saves
+//! can be queried with SQL. Tables are named after the path of the container with the indices
+//! left out, like `achievementHistoryScope.metrics.valueByFilter`, so the containers found in
+//! every element of an array share a table. Paths use the format described in
+//! [`visitor`](crate::visitor).
+//!
+//! [`export`]: crate::sqlite::export
+//!
+//! Every table starts with these columns, followed by the columns of the elements:
+//!
+//! | Column     | Contents                                                          |
+//! |------------|-------------------------------------------------------------------|
+//! | `parent`   | Path of the container the row belongs to, like `metrics[0].value` |
+//! | `position` | Index of the element or entry in the container                    |
+//!
+//! Map keys are stored in a `key` column, or `key.x` and so on for struct keys.
+//! Elements holding a single value are stored in a `value` column. Structs are flattened into a
+//! column per field, like `health` or `location.x`, and containers in an element are written to
+//! their own table, except byte arrays, which are stored as a blob. Integers, floats and strings
+//! are stored as SQLite integers, reals and text, `UInt64Property` values above `i64::MAX` as
+//! text. Delegates, field paths and unknown properties are left out.
+//!
+//! Columns named like a fixed column, ignoring case, or starting with `_` are prefixed with `_`,
+//! so a struct field named `parent` is stored in a `_parent` column.
+//!
+//! ```
+//! use gvas::{
+//!     properties::{map_property::MapProperty, Property},
+//!     sqlite, GvasFile,
+//!     types::map::HashableIndexMap,
+//! };
+//! use rusqlite::Connection;
+//!
+//! # let mut file = std::fs::File::open("resources/test/Slot1.sav")?;
+//! # let mut gvas_file = GvasFile::read(&mut file, gvas::game_version::GameVersion::Default)?;
+//! gvas_file.properties.insert(
+//!     String::from("dbNumbers"),
+//!     Property::from(MapProperty::StrInt {
+//!         str_ints: HashableIndexMap::from([
+//!             (String::from("playtime_a"), 38),
+//!             (String::from("playtime_b"), 4),
+//!         ]),
+//!     }),
+//! );
+//!
+//! let mut connection = Connection::open_in_memory()?;
+//! sqlite::export(&gvas_file, &mut connection)?;
+//!
+//! let playtime: i64 = connection.query_row(
+//!     "SELECT SUM(value) FROM dbNumbers WHERE key LIKE 'playtime_%'",
+//!     [],
+//!     |row| row.get(0),
+//! )?;
+//! assert_eq!(playtime, 42);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use indexmap::IndexMap;
+use rusqlite::{params_from_iter, types::Value, Connection};
+
+use crate::{
+    properties::{
+        array_property::ArrayProperty,
+        int_property::BytePropertyValue,
+        map_property::{MapProperty, OrderedEntries},
+        set_property::SetProperty,
+        struct_property::StructPropertyValue,
+        Property,
+    },
+    GvasFile,
+};
+
+/// Columns present in every table.
+const FIXED_COLUMNS: [(&str, &str); 2] = [("parent", "TEXT"), ("position", "INTEGER")];
+
+/// Write every map, set and array of `file` to a table in `connection`.
+///
+/// Tables that already exist with the same name are replaced. Everything is written in a single
+/// transaction. Returns the names of the tables, in the order the containers appear in the file.
+///
+/// # Errors
+///
+/// If a statement fails this function returns [`rusqlite::Error`] and nothing is written.
+pub fn export(file: &GvasFile, connection: &mut Connection) -> rusqlite::Result<Vec<String>> {
+    let mut exporter = Exporter::default();
+    for (name, property) in file.properties.iter() {
+        exporter.flatten(&mut Row::new(), "", name, name, property);
+    }
+
+    let transaction = connection.transaction()?;
+    for (name, table) in &exporter.tables {
+        let columns = FIXED_COLUMNS
+            .iter()
+            .map(|(column, column_type)| (*column, Some(*column_type)))
+            .chain(
+                table
+                    .columns
+                    .iter()
+                    .map(|(column, column_type)| (column.as_str(), *column_type)),
+            )
+            .collect::<Vec<_>>();
+        let definitions = columns
+            .iter()
+            .map(|(column, column_type)| match column_type {
+                Some(column_type) => format!("{} {column_type}", quote(column)),
+                None => quote(column),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        transaction.execute(&format!("DROP TABLE IF EXISTS {}", quote(name)), [])?;
+        transaction.execute(&format!("CREATE TABLE {} ({definitions})", quote(name)), [])?;
+
+        let names = columns
+            .iter()
+            .map(|(column, _)| quote(column))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let placeholders = vec!["?"; columns.len()].join(", ");
+        let mut statement = transaction.prepare(&format!(
+            "INSERT INTO {} ({names}) VALUES ({placeholders})",
+            quote(name)
+        ))?;
+        for row in &table.rows {
+            let values = [
+                Value::Text(row.parent.clone()),
+                Value::Integer(row.position as i64),
+            ]
+            .into_iter()
+            .chain(table.columns.keys().map(|column| {
+                row.values
+                    .get(column.as_str())
+                    .cloned()
+                    .unwrap_or(Value::Null)
+            }));
+            statement.execute(params_from_iter(values))?;
+        }
+    }
+    transaction.commit()?;
+
+    Ok(exporter.tables.into_keys().collect())
+}
+
+/// A table with its column types, taken from the first value that isn't `NULL`.
+#[derive(Default)]
+struct Table {
+    columns: IndexMap<String, Option<&'static str>>,
+    rows: Vec<Row>,
+}
+
+#[derive(Default)]
+struct Row {
+    parent: String,
+    position: usize,
+    values: IndexMap<String, Value>,
+}
+
+impl Row {
+    #[inline]
+    fn new() -> Self {
+        Row::default()
+    }
+
+    /// Set a column, `value` when `column` is empty.
+    #[inline]
+    fn set(&mut self, column: &str, value: Value) {
+        let column = match column {
+            "" => "value",
+            column => column,
+        };
+        self.values.insert(escape(column), value);
+    }
+}
+
+/// A key or value of a container element.
+enum Element<'a> {
+    Value(Value),
+    Property(&'a Property),
+}
+
+impl From<&String> for Element<'_> {
+    #[inline]
+    fn from(value: &String) -> Self {
+        Element::Value(Value::Text(value.clone()))
+    }
+}
+
+impl From<&Option<String>> for Element<'_> {
+    #[inline]
+    fn from(value: &Option<String>) -> Self {
+        Element::Value(value.clone().map_or(Value::Null, Value::Text))
+    }
+}
+
+#[derive(Default)]
+struct Exporter {
+    tables: IndexMap<String, Table>,
+}
+
+impl Exporter {
+    /// Add the rows of a container.
+    fn add_container(&mut self, table: &str, path: &str, property: &Property) {
+        // Reserve the position of the table before the tables of nested containers
+        self.tables.entry(table.to_string()).or_default();
+        let mut rows = Vec::new();
+        match property {
+            Property::ArrayProperty(
+                ArrayProperty::Bools { bools } | ArrayProperty::WideBools { wide_bools: bools },
+            ) => {
+                for value in bools {
+                    let value = Element::Value(Value::Integer(i64::from(*value)));
+                    self.add_element(&mut rows, table, path, None, value);
+                }
+            }
+            Property::ArrayProperty(ArrayProperty::Enums { enums }) => {
+                for value in enums {
+                    self.add_element(&mut rows, table, path, None, value.into());
+                }
+            }
+            Property::ArrayProperty(ArrayProperty::Floats { floats }) => {
+                for value in floats {
+                    let value = Element::Value(Value::Real(f64::from(value.0)));
+                    self.add_element(&mut rows, table, path, None, value);
+                }
+            }
+            Property::ArrayProperty(ArrayProperty::Ints { ints }) => {
+                for value in ints {
+                    let value = Element::Value(Value::Integer(i64::from(*value)));
+                    self.add_element(&mut rows, table, path, None, value);
+                }
+            }
+            Property::ArrayProperty(ArrayProperty::Names { names: values })
+            | Property::ArrayProperty(ArrayProperty::Strings { strings: values }) => {
+                for value in values {
+                    self.add_element(&mut rows, table, path, None, value.into());
+                }
+            }
+            Property::ArrayProperty(ArrayProperty::Structs { structs, .. }) => {
+                for value in structs {
+                    let position = rows.len();
+                    let mut row = Row {
+                        parent: path.to_string(),
+                        position,
+                        values: IndexMap::new(),
+                    };
+                    let element_path = format!("{path}[{position}]");
+                    self.flatten_struct(&mut row, "", table, &element_path, value);
+                    rows.push(row);
+                }
+            }
+            Property::ArrayProperty(ArrayProperty::SharedStructs { fields, values, .. }) => {
+                for value in values {
+                    let position = rows.len();
+                    let mut row = Row {
+                        parent: path.to_string(),
+                        position,
+                        values: IndexMap::new(),
+                    };
+                    for (field, property) in fields.iter().zip(value) {
+                        self.flatten(
+                            &mut row,
+                            field,
+                            &format!("{table}.{field}"),
+                            &format!("{path}[{position}].{field}"),
+                            property,
+                        );
+                    }
+                    rows.push(row);
+                }
+            }
+            Property::ArrayProperty(ArrayProperty::Properties { properties, .. })
+            | Property::SetProperty(SetProperty { properties, .. }) => {
+                for value in properties {
+                    let value = Element::Property(value);
+                    self.add_element(&mut rows, table, path, None, value);
+                }
+            }
+            Property::MapProperty(MapProperty::EnumBool { enum_bools: map })
+            | Property::MapProperty(MapProperty::NameBool { name_bools: map })
+            | Property::MapProperty(MapProperty::StrBool { str_bools: map }) => {
+                for (key, value) in map.iter() {
+                    let value = Element::Value(Value::Integer(i64::from(*value)));
+                    self.add_element(&mut rows, table, path, Some(key.into()), value);
+                }
+            }
+            Property::MapProperty(MapProperty::EnumInt { enum_ints: map })
+            | Property::MapProperty(MapProperty::NameInt { name_ints: map })
+            | Property::MapProperty(MapProperty::StrInt { str_ints: map }) => {
+                for (key, value) in map.iter() {
+                    let value = Element::Value(Value::Integer(i64::from(*value)));
+                    self.add_element(&mut rows, table, path, Some(key.into()), value);
+                }
+            }
+            Property::MapProperty(MapProperty::EnumProperty {
+                enum_props: map, ..
+            })
+            | Property::MapProperty(MapProperty::NameProperty {
+                name_props: map, ..
+            })
+            | Property::MapProperty(MapProperty::StrProperty { str_props: map, .. }) => {
+                for (key, value) in map.iter() {
+                    let value = Element::Property(value);
+                    self.add_element(&mut rows, table, path, Some(key.into()), value);
+                }
+            }
+            Property::MapProperty(MapProperty::Properties {
+                value: map,
+                duplicates,
+                ..
+            }) => {
+                for (key, value) in OrderedEntries::new(map, duplicates) {
+                    let key = Element::Property(key);
+                    let value = Element::Property(value);
+                    self.add_element(&mut rows, table, path, Some(key), value);
+                }
+            }
+            Property::MapProperty(MapProperty::StrStr { str_strs: map }) => {
+                for (key, value) in map.iter() {
+                    self.add_element(&mut rows, table, path, Some(key.into()), value.into());
+                }
+            }
+            _ => {}
+        }
+
+        let table = self.tables.entry(table.to_string()).or_default();
+        for row in &rows {
+            for (column, value) in &row.values {
+                let column_type = table.columns.entry(column.clone()).or_default();
+                if column_type.is_none() {
+                    *column_type = value_type(value);
+                }
+            }
+        }
+        table.rows.extend(rows);
+    }
+
+    /// Add a row for a container element.
+    fn add_element(
+        &mut self,
+        rows: &mut Vec<Row>,
+        table: &str,
+        path: &str,
+        key: Option<Element>,
+        value: Element,
+    ) {
+        let position = rows.len();
+        let element_path = format!("{path}[{position}]");
+        let mut row = Row {
+            parent: path.to_string(),
+            position,
+            values: IndexMap::new(),
+        };
+        for (column, element) in [("key", key), ("", Some(value))] {
+            match element {
+                Some(Element::Value(value)) => row.set(column, value),
+                Some(Element::Property(property)) => {
+                    self.flatten(&mut row, column, table, &element_path, property)
+                }
+                None => {}
+            }
+        }
+        rows.push(row);
+    }
+
+    /// Add the value of `property` to `row` under `column`, or write it to its own table if it
+    /// is a container.
+    fn flatten(
+        &mut self,
+        row: &mut Row,
+        column: &str,
+        table: &str,
+        path: &str,
+        property: &Property,
+    ) {
+        let value = match property {
+            Property::BoolProperty(property) => Value::Integer(i64::from(property.value)),
+            Property::ByteProperty(property) => match &property.value {
+                BytePropertyValue::Byte(value) => Value::Integer(i64::from(*value)),
+                BytePropertyValue::Namespaced(value) => Value::Text(value.clone()),
+            },
+            Property::Int8Property(property) => Value::Integer(i64::from(property.value)),
+            Property::Int16Property(property) => Value::Integer(i64::from(property.value)),
+            Property::IntProperty(property) => Value::Integer(i64::from(property.value)),
+            Property::Int64Property(property) => Value::Integer(property.value),
+            Property::UInt16Property(property) => Value::Integer(i64::from(property.value)),
+            Property::UInt32Property(property) => Value::Integer(i64::from(property.value)),
+            Property::UInt64Property(property) => match i64::try_from(property.value) {
+                Ok(value) => Value::Integer(value),
+                Err(_) => Value::Text(property.value.to_string()),
+            },
+            Property::FloatProperty(property) => Value::Real(f64::from(property.value.0)),
+            Property::DoubleProperty(property) => Value::Real(property.value.0),
+            Property::EnumProperty(property) => Value::Text(property.value.to_string()),
+            Property::NameProperty(property) => text(property.value.as_deref()),
+            Property::StrProperty(property) => text(property.value.as_deref()),
+            Property::ObjectProperty(property) => Value::Text(property.value.to_string()),
+            Property::TextProperty(property) => text(property.value.display_string()),
+            Property::StructProperty(property) => {
+                return self.flatten_struct(row, column, table, path, &property.value);
+            }
+            Property::StructPropertyValue(value) => {
+                return self.flatten_struct(row, column, table, path, value);
+            }
+            Property::ArrayProperty(ArrayProperty::Bytes { bytes }) => Value::Blob(bytes.to_vec()),
+            Property::ArrayProperty(_) | Property::MapProperty(_) | Property::SetProperty(_) => {
+                return self.add_container(table, path, property);
+            }
+            _ => return,
+        };
+        row.set(column, value);
+    }
+
+    /// Add the fields of a struct to `row`, prefixed with `column`.
+    fn flatten_struct(
+        &mut self,
+        row: &mut Row,
+        column: &str,
+        table: &str,
+        path: &str,
+        value: &StructPropertyValue,
+    ) {
+        let fields = match value {
+            StructPropertyValue::Vector2F(value) => {
+                vec![("x", real(value.x.0)), ("y", real(value.y.0))]
+            }
+            StructPropertyValue::Vector2D(value) => {
+                vec![("x", Value::Real(value.x.0)), ("y", Value::Real(value.y.0))]
+            }
+            StructPropertyValue::VectorF(value) => vec![
+                ("x", real(value.x.0)),
+                ("y", real(value.y.0)),
+                ("z", real(value.z.0)),
+            ],
+            StructPropertyValue::VectorD(value) => vec![
+                ("x", Value::Real(value.x.0)),
+                ("y", Value::Real(value.y.0)),
+                ("z", Value::Real(value.z.0)),
+            ],
+            StructPropertyValue::RotatorF(value) => vec![
+                ("pitch", real(value.pitch.0)),
+                ("yaw", real(value.yaw.0)),
+                ("roll", real(value.roll.0)),
+            ],
+            StructPropertyValue::RotatorD(value) => vec![
+                ("pitch", Value::Real(value.pitch.0)),
+                ("yaw", Value::Real(value.yaw.0)),
+                ("roll", Value::Real(value.roll.0)),
+            ],
+            StructPropertyValue::QuatF(value) => vec![
+                ("x", real(value.x.0)),
+                ("y", real(value.y.0)),
+                ("z", real(value.z.0)),
+                ("w", real(value.w.0)),
+            ],
+            StructPropertyValue::QuatD(value) => vec![
+                ("x", Value::Real(value.x.0)),
+                ("y", Value::Real(value.y.0)),
+                ("z", Value::Real(value.z.0)),
+                ("w", Value::Real(value.w.0)),
+            ],
+            StructPropertyValue::LinearColor(value) => vec![
+                ("r", real(value.r.0)),
+                ("g", real(value.g.0)),
+                ("b", real(value.b.0)),
+                ("a", real(value.a.0)),
+            ],
+            StructPropertyValue::IntPoint(value) => vec![
+                ("x", Value::Integer(i64::from(value.x))),
+                ("y", Value::Integer(i64::from(value.y))),
+            ],
+            StructPropertyValue::IntVector(value) => vec![
+                ("x", Value::Integer(i64::from(value.x))),
+                ("y", Value::Integer(i64::from(value.y))),
+                ("z", Value::Integer(i64::from(value.z))),
+            ],
+            StructPropertyValue::IntVector4(value) => vec![
+                ("x", Value::Integer(i64::from(value.x))),
+                ("y", Value::Integer(i64::from(value.y))),
+                ("z", Value::Integer(i64::from(value.z))),
+                ("w", Value::Integer(i64::from(value.w))),
+            ],
+            StructPropertyValue::UintVector(value) => vec![
+                ("x", Value::Integer(i64::from(value.x))),
+                ("y", Value::Integer(i64::from(value.y))),
+                ("z", Value::Integer(i64::from(value.z))),
+            ],
+            StructPropertyValue::Int64Vector(value) => vec![
+                ("x", Value::Integer(value.x)),
+                ("y", Value::Integer(value.y)),
+                ("z", Value::Integer(value.z)),
+            ],
+            StructPropertyValue::DateTime(value) => {
+                return row.set(column, Value::Integer(value.ticks as i64));
+            }
+            StructPropertyValue::Timespan(value) => {
+                return row.set(column, Value::Integer(value.ticks as i64));
+            }
+            StructPropertyValue::Guid(value) => {
+                return row.set(column, Value::Text(value.to_string()))
+            }
+            StructPropertyValue::CustomStruct(fields) => {
+                for (name, properties) in fields.iter() {
+                    for (index, property) in properties.iter().enumerate() {
+                        let field = match properties.len() {
+                            1 => name.clone(),
+                            _ => format!("{name}[{index}]"),
+                        };
+                        self.flatten(
+                            row,
+                            &join(column, &field),
+                            &format!("{table}.{name}"),
+                            &format!("{path}.{field}"),
+                            property,
+                        );
+                    }
+                }
+                return;
+            }
+        };
+        for (field, value) in fields {
+            row.set(&join(column, field), value);
+        }
+    }
+}
+
+/// Join a column prefix and a field name.
+#[inline]
+fn join(column: &str, field: &str) -> String {
+    match column {
+        "" => field.to_string(),
+        column => format!("{column}.{field}"),
+    }
+}
+
+#[inline]
+fn real(value: f32) -> Value {
+    Value::Real(f64::from(value))
+}
+
+#[inline]
+fn text(value: Option<&str>) -> Value {
+    value.map_or(Value::Null, |value| Value::Text(value.to_string()))
+}
+
+/// The SQLite type of a value, `None` for `NULL`.
+fn value_type(value: &Value) -> Option<&'static str> {
+    match value {
+        Value::Null => None,
+        Value::Integer(_) => Some("INTEGER"),
+        Value::Real(_) => Some("REAL"),
+        Value::Text(_) => Some("TEXT"),
+        Value::Blob(_) => Some("BLOB"),
+    }
+}
+
+/// Prefix a column that would collide with a fixed column with `_`. Columns that already start
+/// with `_` are prefixed too, so no two columns end up with the same name.
+fn escape(column: &str) -> String {
+    let fixed = FIXED_COLUMNS
+        .iter()
+        .any(|(fixed, _)| fixed.eq_ignore_ascii_case(column));
+    if fixed || column.starts_with('_') {
+        format!("_{column}")
+    } else {
+        column.to_string()
+    }
+}
+
+/// Quote an identifier.
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
@@ -0,0 +1,976 @@
+//! Hashes that stay the same across crate versions
+//!
+//! [`std::hash::Hash`] is only meant for hash tables within one process: the algorithm behind
+//! [`DefaultHasher`] may change with every Rust release. [`StableHash`] instead hashes a defined
+//! canonical encoding of a value with 64-bit FNV-1a, so the result can be stored and used as a
+//! cache key or to find duplicate saves.
+//!
+//! [`DefaultHasher`]: std::collections::hash_map::DefaultHasher
+//! [`StableHash`]: crate::stable_hash::StableHash
+//!
+//! # Encoding
+//!
+//! The hasher starts with [`STABLE_HASH_VERSION`] as a `u32`, then each value is encoded as:
+//!
+//! | Value                   | Encoding                                                  |
+//! |-------------------------|-----------------------------------------------------------|
+//! | Integers                | Little endian, at their own width                         |
+//! | `bool`                  | `u8`, `0` or `1`                                          |
+//! | Floats                  | The IEEE 754 bits, little endian                          |
+//! | Strings                 | `u64` length in bytes, then the UTF-8 bytes               |
+//! | `Option`                | `u8` `0` for `None`, or `1` followed by the value         |
+//! | Lists and maps          | `u64` length, then every element or key and value in order |
+//! | Structs                 | Every field, in the order they are declared               |
+//! | Enums and [`Property`]  | The variant name as a string, then its fields             |
+//!
+//! [`STABLE_HASH_VERSION`]: crate::stable_hash::STABLE_HASH_VERSION
+//! [`Property`]: crate::properties::Property
+//!
+//! A [`GvasFile`] is encoded as its prefix, header, properties and trailing padding. How the
+//! file was stored, [`GvasFile::deserialized_game_version`], is left out, as is the display
+//! string of string table entries, which isn't written to the save.
+//!
+//! [`GvasFile`]: crate::GvasFile
+//! [`GvasFile::deserialized_game_version`]: crate::GvasFile::deserialized_game_version
+//!
+//! # Stability
+//!
+//! The hash of a value only changes when the encoding does, which bumps
+//! [`STABLE_HASH_VERSION`] and is noted in the release notes. Fields added to existing types in
+//! later versions are appended to the encoding of the type, so they do change its hash.
+//!
+//! Values that hash the same are very likely equal, as with any 64-bit hash two different values
+//! can collide. Equal values don't always hash the same: the order of map entries, the sign of a
+//! floating point zero and the bits of a NaN are encoded, while `==` ignores them.
+//!
+//! ```
+//! use gvas::{game_version::GameVersion, stable_hash::StableHash, GvasFile};
+//! use std::fs::File;
+//!
+//! let mut file = File::open("resources/test/Slot1.sav")?;
+//! let mut gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+//! let hash = gvas_file.stable_hash();
+//!
+//! gvas_file.properties.insert(String::from("u8_test"), 1u8.into());
+//! assert_ne!(gvas_file.stable_hash(), hash);
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+
+use std::sync::Arc;
+
+use ordered_float::OrderedFloat;
+
+use crate::{
+    engine_version::FEngineVersion,
+    properties::{
+        array_property::ArrayProperty,
+        delegate_property::{
+            Delegate, DelegateProperty, MulticastInlineDelegateProperty, MulticastScriptDelegate,
+            MulticastSparseDelegateProperty,
+        },
+        enum_property::EnumProperty,
+        field_path_property::{FieldPath, FieldPathProperty},
+        int_property::{
+            BoolProperty, ByteProperty, BytePropertyValue, DoubleProperty, FloatProperty,
+            Int16Property, Int64Property, Int8Property, IntProperty, UInt16Property,
+            UInt32Property, UInt64Property,
+        },
+        map_property::{MapDuplicate, MapProperty},
+        name_property::NameProperty,
+        object_property::ObjectProperty,
+        set_property::SetProperty,
+        str_property::StrProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        struct_types::{
+            DateTime, Int64Vector, IntPoint, IntVector, IntVector4, LinearColor, QuatD, QuatF,
+            RotatorD, RotatorF, Timespan, UintVector, Vector2D, Vector2F, VectorD, VectorF,
+        },
+        text_property::{
+            DateTimeStyle, FText, FTextHistory, FormatArgumentValue, NumberFormattingOptions,
+            RoundingMode, TextProperty, TransformType,
+        },
+        Property,
+    },
+    savegame_version::SaveGameVersion,
+    types::{map::HashableIndexMap, Guid},
+    GvasFile, GvasHeader,
+};
+
+/// Version of the canonical encoding, see the [module documentation](self).
+pub const STABLE_HASH_VERSION: u32 = 1;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// 64-bit FNV-1a hasher over the canonical encoding.
+#[derive(Debug, Clone)]
+pub struct StableHasher {
+    state: u64,
+}
+
+impl StableHasher {
+    /// Creates a new `StableHasher` instance, starting with [`STABLE_HASH_VERSION`].
+    #[inline]
+    pub fn new() -> Self {
+        let mut hasher = StableHasher {
+            state: FNV_OFFSET_BASIS,
+        };
+        hasher.write(&STABLE_HASH_VERSION.to_le_bytes());
+        hasher
+    }
+
+    /// Hash raw bytes.
+    #[inline]
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Hash a length or count.
+    #[inline]
+    pub fn write_len(&mut self, len: usize) {
+        self.write(&(len as u64).to_le_bytes());
+    }
+
+    /// Hash a string, preceded by its length.
+    #[inline]
+    pub fn write_str(&mut self, value: &str) {
+        self.write_len(value.len());
+        self.write(value.as_bytes());
+    }
+
+    /// The hash of everything written so far.
+    #[inline]
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+impl Default for StableHasher {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A value with a hash that stays the same across crate versions.
+///
+/// ```
+/// use gvas::{properties::Property, stable_hash::StableHash};
+///
+/// let property = Property::from(5);
+/// assert_eq!(property.stable_hash(), Property::from(5).stable_hash());
+/// assert_ne!(property.stable_hash(), Property::from(5i64).stable_hash());
+/// ```
+pub trait StableHash {
+    /// Write the canonical encoding of the value to `hasher`.
+    fn stable_hash_into(&self, hasher: &mut StableHasher);
+
+    /// The hash of the canonical encoding of the value.
+    #[inline]
+    fn stable_hash(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        self.stable_hash_into(&mut hasher);
+        hasher.finish()
+    }
+}
+
+macro_rules! impl_le_bytes {
+    ($($type:ty),+ $(,)?) => {
+        $(
+            impl StableHash for $type {
+                #[inline]
+                fn stable_hash_into(&self, hasher: &mut StableHasher) {
+                    hasher.write(&self.to_le_bytes());
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_fields {
+    ($($type:ty { $($field:ident),+ }),+ $(,)?) => {
+        $(
+            impl StableHash for $type {
+                #[inline]
+                fn stable_hash_into(&self, hasher: &mut StableHasher) {
+                    $(self.$field.stable_hash_into(hasher);)+
+                }
+            }
+        )+
+    };
+}
+
+/// Like `impl_fields`, for properties which can be read from a zero-length body.
+///
+/// The flag is only hashed when set, so hashes of other values don't change.
+macro_rules! impl_empty_body_fields {
+    ($($type:ty { $($field:ident),+ }),+ $(,)?) => {
+        $(
+            impl StableHash for $type {
+                #[inline]
+                fn stable_hash_into(&self, hasher: &mut StableHasher) {
+                    $(self.$field.stable_hash_into(hasher);)+
+                    if self.empty_body {
+                        hasher.write_str("EmptyBody");
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_le_bytes!(u8, i8, u16, i16, u32, i32, u64, i64);
+
+impl StableHash for bool {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        u8::from(*self).stable_hash_into(hasher);
+    }
+}
+
+impl StableHash for f32 {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        self.to_bits().stable_hash_into(hasher);
+    }
+}
+
+impl StableHash for f64 {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        self.to_bits().stable_hash_into(hasher);
+    }
+}
+
+impl StableHash for OrderedFloat<f32> {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        self.0.stable_hash_into(hasher);
+    }
+}
+
+impl StableHash for OrderedFloat<f64> {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        self.0.stable_hash_into(hasher);
+    }
+}
+
+impl StableHash for str {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        hasher.write_str(self);
+    }
+}
+
+impl StableHash for String {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        hasher.write_str(self);
+    }
+}
+
+impl StableHash for Guid {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        hasher.write(&self.0);
+    }
+}
+
+impl<T: StableHash> StableHash for Option<T> {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        match self {
+            None => hasher.write(&[0]),
+            Some(value) => {
+                hasher.write(&[1]);
+                value.stable_hash_into(hasher);
+            }
+        }
+    }
+}
+
+impl<T: StableHash> StableHash for Box<T> {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        T::stable_hash_into(self, hasher);
+    }
+}
+
+impl<T: StableHash> StableHash for [T] {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        hasher.write_len(self.len());
+        for element in self {
+            element.stable_hash_into(hasher);
+        }
+    }
+}
+
+impl<T: StableHash + ?Sized> StableHash for Arc<T> {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        T::stable_hash_into(self, hasher);
+    }
+}
+
+impl<A: StableHash, B: StableHash> StableHash for (A, B) {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        self.0.stable_hash_into(hasher);
+        self.1.stable_hash_into(hasher);
+    }
+}
+
+impl<T: StableHash> StableHash for Vec<T> {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        self.as_slice().stable_hash_into(hasher);
+    }
+}
+
+impl<K, V> StableHash for HashableIndexMap<K, V>
+where
+    K: std::hash::Hash + Eq + StableHash,
+    V: std::hash::Hash + StableHash,
+{
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        hasher.write_len(self.len());
+        for (key, value) in &self.0 {
+            key.stable_hash_into(hasher);
+            value.stable_hash_into(hasher);
+        }
+    }
+}
+
+impl_fields!(
+    Vector2F { x, y },
+    Vector2D { x, y },
+    VectorF { x, y, z },
+    VectorD { x, y, z },
+    RotatorF { pitch, yaw, roll },
+    RotatorD { pitch, yaw, roll },
+    QuatF { x, y, z, w },
+    QuatD { x, y, z, w },
+    DateTime { ticks },
+    Timespan { ticks },
+    LinearColor { r, g, b, a },
+    IntPoint { x, y },
+    IntVector { x, y, z },
+    IntVector4 { x, y, z, w },
+    UintVector { x, y, z },
+    Int64Vector { x, y, z },
+    FEngineVersion {
+        major,
+        minor,
+        patch,
+        change_list,
+        branch
+    },
+    Int8Property { value },
+    Int16Property { value },
+    IntProperty { value },
+    Int64Property { value },
+    UInt16Property { value },
+    UInt32Property { value },
+    UInt64Property { value },
+    FloatProperty { value },
+    DoubleProperty { value },
+    BoolProperty { value },
+    ByteProperty { name, value },
+    Delegate {
+        object,
+        function_name
+    },
+    MulticastScriptDelegate { delegates },
+    EnumProperty { enum_type, value },
+    FieldPath {
+        path,
+        resolved_owner
+    },
+    SetProperty {
+        property_type,
+        allocation_flags,
+        properties
+    },
+    StructProperty {
+        guid,
+        type_name,
+        value
+    },
+    FText { flags, history },
+    NumberFormattingOptions {
+        always_include_sign,
+        use_grouping,
+        rounding_mode,
+        minimum_integral_digits,
+        maximum_integral_digits,
+        minimum_fractional_digits,
+        maximum_fractional_digits
+    },
+);
+
+impl_empty_body_fields!(
+    DelegateProperty { value },
+    MulticastInlineDelegateProperty { value },
+    MulticastSparseDelegateProperty { value },
+    FieldPathProperty { value },
+    NameProperty { array_index, value },
+    ObjectProperty { value },
+    StrProperty { value },
+    TextProperty { value },
+);
+
+impl StableHash for BytePropertyValue {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        match self {
+            BytePropertyValue::Byte(value) => {
+                hasher.write_str("Byte");
+                value.stable_hash_into(hasher);
+            }
+            BytePropertyValue::Namespaced(value) => {
+                hasher.write_str("Namespaced");
+                value.stable_hash_into(hasher);
+            }
+        }
+    }
+}
+
+impl StableHash for RoundingMode {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        match self {
+            RoundingMode::HalfToEven => hasher.write_str("HalfToEven"),
+            RoundingMode::HalfFromZero => hasher.write_str("HalfFromZero"),
+            RoundingMode::HalfToZero => hasher.write_str("HalfToZero"),
+            RoundingMode::FromZero => hasher.write_str("FromZero"),
+            RoundingMode::ToZero => hasher.write_str("ToZero"),
+            RoundingMode::ToNegativeInfinity => hasher.write_str("ToNegativeInfinity"),
+            RoundingMode::ToPositiveInfinity => hasher.write_str("ToPositiveInfinity"),
+            RoundingMode::Other(value) => {
+                hasher.write_str("Other");
+                value.stable_hash_into(hasher);
+            }
+        }
+    }
+}
+
+impl StableHash for DateTimeStyle {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        match self {
+            DateTimeStyle::Default => hasher.write_str("Default"),
+            DateTimeStyle::Short => hasher.write_str("Short"),
+            DateTimeStyle::Medium => hasher.write_str("Medium"),
+            DateTimeStyle::Long => hasher.write_str("Long"),
+            DateTimeStyle::Full => hasher.write_str("Full"),
+            DateTimeStyle::Other(value) => {
+                hasher.write_str("Other");
+                value.stable_hash_into(hasher);
+            }
+        }
+    }
+}
+
+impl StableHash for TransformType {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        match self {
+            TransformType::ToLower => hasher.write_str("ToLower"),
+            TransformType::ToUpper => hasher.write_str("ToUpper"),
+            TransformType::Other(value) => {
+                hasher.write_str("Other");
+                value.stable_hash_into(hasher);
+            }
+        }
+    }
+}
+
+impl StableHash for FormatArgumentValue {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        match self {
+            FormatArgumentValue::Int(value) => {
+                hasher.write_str("Int");
+                value.stable_hash_into(hasher);
+            }
+            FormatArgumentValue::UInt(value) => {
+                hasher.write_str("UInt");
+                value.stable_hash_into(hasher);
+            }
+            FormatArgumentValue::Float(value) => {
+                hasher.write_str("Float");
+                value.stable_hash_into(hasher);
+            }
+            FormatArgumentValue::Double(value) => {
+                hasher.write_str("Double");
+                value.stable_hash_into(hasher);
+            }
+            FormatArgumentValue::Text(value) => {
+                hasher.write_str("Text");
+                value.stable_hash_into(hasher);
+            }
+            FormatArgumentValue::Int64(value) => {
+                hasher.write_str("Int64");
+                value.stable_hash_into(hasher);
+            }
+            FormatArgumentValue::UInt64(value) => {
+                hasher.write_str("UInt64");
+                value.stable_hash_into(hasher);
+            }
+        }
+    }
+}
+
+impl StableHash for FTextHistory {
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        match self {
+            FTextHistory::Empty {} => hasher.write_str("Empty"),
+            FTextHistory::None {
+                culture_invariant_string,
+            } => {
+                hasher.write_str("None");
+                culture_invariant_string.stable_hash_into(hasher);
+            }
+            FTextHistory::Base {
+                namespace,
+                key,
+                source_string,
+            } => {
+                hasher.write_str("Base");
+                namespace.stable_hash_into(hasher);
+                key.stable_hash_into(hasher);
+                source_string.stable_hash_into(hasher);
+            }
+            FTextHistory::NamedFormat {
+                source_format,
+                arguments,
+            } => {
+                hasher.write_str("NamedFormat");
+                source_format.stable_hash_into(hasher);
+                arguments.stable_hash_into(hasher);
+            }
+            FTextHistory::OrderedFormat {
+                source_format,
+                arguments,
+            } => {
+                hasher.write_str("OrderedFormat");
+                source_format.stable_hash_into(hasher);
+                arguments.stable_hash_into(hasher);
+            }
+            FTextHistory::ArgumentFormat {
+                source_format,
+                arguments,
+            } => {
+                hasher.write_str("ArgumentFormat");
+                source_format.stable_hash_into(hasher);
+                arguments.stable_hash_into(hasher);
+            }
+            FTextHistory::AsNumber {
+                source_value,
+                format_options,
+                target_culture,
+            } => {
+                hasher.write_str("AsNumber");
+                source_value.stable_hash_into(hasher);
+                format_options.stable_hash_into(hasher);
+                target_culture.stable_hash_into(hasher);
+            }
+            FTextHistory::AsPercent {
+                source_value,
+                format_options,
+                target_culture,
+            } => {
+                hasher.write_str("AsPercent");
+                source_value.stable_hash_into(hasher);
+                format_options.stable_hash_into(hasher);
+                target_culture.stable_hash_into(hasher);
+            }
+            FTextHistory::AsCurrency {
+                currency_code,
+                source_value,
+                format_options,
+                target_culture,
+            } => {
+                hasher.write_str("AsCurrency");
+                currency_code.stable_hash_into(hasher);
+                source_value.stable_hash_into(hasher);
+                format_options.stable_hash_into(hasher);
+                target_culture.stable_hash_into(hasher);
+            }
+            FTextHistory::AsDate {
+                date_time,
+                date_style,
+                target_culture,
+            } => {
+                hasher.write_str("AsDate");
+                date_time.stable_hash_into(hasher);
+                date_style.stable_hash_into(hasher);
+                target_culture.stable_hash_into(hasher);
+            }
+            FTextHistory::AsTime {
+                source_date_time,
+                time_style,
+                time_zone,
+                target_culture,
+            } => {
+                hasher.write_str("AsTime");
+                source_date_time.stable_hash_into(hasher);
+                time_style.stable_hash_into(hasher);
+                time_zone.stable_hash_into(hasher);
+                target_culture.stable_hash_into(hasher);
+            }
+            FTextHistory::AsDateTime {
+                source_date_time,
+                date_style,
+                time_style,
+                time_zone,
+                target_culture,
+            } => {
+                hasher.write_str("AsDateTime");
+                source_date_time.stable_hash_into(hasher);
+                date_style.stable_hash_into(hasher);
+                time_style.stable_hash_into(hasher);
+                time_zone.stable_hash_into(hasher);
+                target_culture.stable_hash_into(hasher);
+            }
+            FTextHistory::Transform {
+                source_text,
+                transform_type,
+            } => {
+                hasher.write_str("Transform");
+                source_text.stable_hash_into(hasher);
+                transform_type.stable_hash_into(hasher);
+            }
+            FTextHistory::StringTableEntry {
+                table_id,
+                key,
+                display_string: _,
+            } => {
+                hasher.write_str("StringTableEntry");
+                table_id.stable_hash_into(hasher);
+                key.stable_hash_into(hasher);
+            }
+        }
+    }
+}
+
+impl StableHash for ArrayProperty {
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        match self {
+            ArrayProperty::Bools { bools } => {
+                hasher.write_str("Bools");
+                bools.stable_hash_into(hasher);
+            }
+            ArrayProperty::WideBools { wide_bools } => {
+                hasher.write_str("WideBools");
+                wide_bools.stable_hash_into(hasher);
+            }
+            ArrayProperty::Bytes { bytes } => {
+                hasher.write_str("Bytes");
+                hasher.write_len(bytes.len());
+                hasher.write(bytes);
+            }
+            ArrayProperty::Enums { enums } => {
+                hasher.write_str("Enums");
+                enums.stable_hash_into(hasher);
+            }
+            ArrayProperty::Floats { floats } => {
+                hasher.write_str("Floats");
+                floats.stable_hash_into(hasher);
+            }
+            ArrayProperty::Ints { ints } => {
+                hasher.write_str("Ints");
+                ints.stable_hash_into(hasher);
+            }
+            ArrayProperty::Names { names } => {
+                hasher.write_str("Names");
+                names.stable_hash_into(hasher);
+            }
+            ArrayProperty::Strings { strings } => {
+                hasher.write_str("Strings");
+                strings.stable_hash_into(hasher);
+            }
+            ArrayProperty::Structs {
+                field_name,
+                type_name,
+                guid,
+                structs,
+            } => {
+                hasher.write_str("Structs");
+                field_name.stable_hash_into(hasher);
+                type_name.stable_hash_into(hasher);
+                guid.stable_hash_into(hasher);
+                structs.stable_hash_into(hasher);
+            }
+            ArrayProperty::SharedStructs {
+                field_name,
+                type_name,
+                guid,
+                fields,
+                values,
+            } => {
+                hasher.write_str("SharedStructs");
+                field_name.stable_hash_into(hasher);
+                type_name.stable_hash_into(hasher);
+                guid.stable_hash_into(hasher);
+                fields.stable_hash_into(hasher);
+                values.stable_hash_into(hasher);
+            }
+            ArrayProperty::Properties {
+                property_type,
+                properties,
+            } => {
+                hasher.write_str("Properties");
+                property_type.stable_hash_into(hasher);
+                properties.stable_hash_into(hasher);
+            }
+        }
+    }
+}
+
+impl StableHash for MapDuplicate {
+    #[inline]
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        hasher.write_len(self.position);
+        self.key.stable_hash_into(hasher);
+        self.value.stable_hash_into(hasher);
+    }
+}
+
+impl StableHash for MapProperty {
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        match self {
+            MapProperty::EnumBool { enum_bools } => {
+                hasher.write_str("EnumBool");
+                enum_bools.stable_hash_into(hasher);
+            }
+            MapProperty::EnumInt { enum_ints } => {
+                hasher.write_str("EnumInt");
+                enum_ints.stable_hash_into(hasher);
+            }
+            MapProperty::EnumProperty {
+                value_type,
+                enum_props,
+            } => {
+                hasher.write_str("EnumProperty");
+                value_type.stable_hash_into(hasher);
+                enum_props.stable_hash_into(hasher);
+            }
+            MapProperty::NameBool { name_bools } => {
+                hasher.write_str("NameBool");
+                name_bools.stable_hash_into(hasher);
+            }
+            MapProperty::NameInt { name_ints } => {
+                hasher.write_str("NameInt");
+                name_ints.stable_hash_into(hasher);
+            }
+            MapProperty::NameProperty {
+                value_type,
+                name_props,
+            } => {
+                hasher.write_str("NameProperty");
+                value_type.stable_hash_into(hasher);
+                name_props.stable_hash_into(hasher);
+            }
+            MapProperty::Properties {
+                key_type,
+                value_type,
+                allocation_flags,
+                value,
+                duplicates,
+            } => {
+                hasher.write_str("Properties");
+                key_type.stable_hash_into(hasher);
+                value_type.stable_hash_into(hasher);
+                allocation_flags.stable_hash_into(hasher);
+                value.stable_hash_into(hasher);
+                duplicates.stable_hash_into(hasher);
+            }
+            MapProperty::StrBool { str_bools } => {
+                hasher.write_str("StrBool");
+                str_bools.stable_hash_into(hasher);
+            }
+            MapProperty::StrInt { str_ints } => {
+                hasher.write_str("StrInt");
+                str_ints.stable_hash_into(hasher);
+            }
+            MapProperty::StrProperty {
+                value_type,
+                str_props,
+            } => {
+                hasher.write_str("StrProperty");
+                value_type.stable_hash_into(hasher);
+                str_props.stable_hash_into(hasher);
+            }
+            MapProperty::StrStr { str_strs } => {
+                hasher.write_str("StrStr");
+                str_strs.stable_hash_into(hasher);
+            }
+        }
+    }
+}
+
+impl StableHash for StructPropertyValue {
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        macro_rules! variants {
+            ($($variant:ident),+) => {
+                match self {
+                    $(
+                        StructPropertyValue::$variant(value) => {
+                            hasher.write_str(stringify!($variant));
+                            value.stable_hash_into(hasher);
+                        }
+                    )+
+                }
+            };
+        }
+        variants!(
+            Vector2F,
+            Vector2D,
+            VectorF,
+            VectorD,
+            RotatorF,
+            RotatorD,
+            QuatF,
+            QuatD,
+            DateTime,
+            Timespan,
+            Guid,
+            LinearColor,
+            IntPoint,
+            IntVector,
+            IntVector4,
+            UintVector,
+            Int64Vector,
+            CustomStruct
+        );
+    }
+}
+
+impl StableHash for Property {
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        macro_rules! variants {
+            ($($variant:ident),+) => {
+                match self {
+                    $(
+                        Property::$variant(property) => {
+                            hasher.write_str(stringify!($variant));
+                            property.stable_hash_into(hasher);
+                        }
+                    )+
+                }
+            };
+        }
+        variants!(
+            ArrayProperty,
+            BoolProperty,
+            ByteProperty,
+            DoubleProperty,
+            EnumProperty,
+            FieldPathProperty,
+            FloatProperty,
+            Int16Property,
+            Int64Property,
+            Int8Property,
+            IntProperty,
+            MapProperty,
+            NameProperty,
+            ObjectProperty,
+            SetProperty,
+            StrProperty,
+            StructProperty,
+            StructPropertyValue,
+            TextProperty,
+            UInt16Property,
+            UInt32Property,
+            UInt64Property,
+            UnknownProperty,
+            DelegateProperty,
+            MulticastInlineDelegateProperty,
+            MulticastSparseDelegateProperty
+        );
+    }
+}
+
+impl StableHash for GvasHeader {
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        match self {
+            GvasHeader::Version2 {
+                package_file_version,
+                engine_version,
+                custom_version_format,
+                custom_versions,
+                custom_version_names,
+                save_game_class_name,
+            } => {
+                hasher.write_str("Version2");
+                package_file_version.stable_hash_into(hasher);
+                engine_version.stable_hash_into(hasher);
+                custom_version_format.stable_hash_into(hasher);
+                custom_versions.stable_hash_into(hasher);
+                hash_custom_version_names(custom_version_names, hasher);
+                save_game_class_name.stable_hash_into(hasher);
+            }
+            GvasHeader::Version3 {
+                save_game_file_version,
+                package_file_version,
+                package_file_version_ue5,
+                engine_version,
+                custom_version_format,
+                custom_versions,
+                custom_version_names,
+                save_game_class_name,
+            } => {
+                hasher.write_str("Version3");
+                // Only hashed when it isn't 3, so hashes of other headers don't change
+                if *save_game_file_version
+                    != SaveGameVersion::PackageFileSummaryVersionChange as u32
+                {
+                    hasher.write_str("SaveGameFileVersion");
+                    save_game_file_version.stable_hash_into(hasher);
+                }
+                package_file_version.stable_hash_into(hasher);
+                package_file_version_ue5.stable_hash_into(hasher);
+                engine_version.stable_hash_into(hasher);
+                custom_version_format.stable_hash_into(hasher);
+                custom_versions.stable_hash_into(hasher);
+                hash_custom_version_names(custom_version_names, hasher);
+                save_game_class_name.stable_hash_into(hasher);
+            }
+        }
+    }
+}
+
+/// Hashes the friendly names of the custom versions, only when present so hashes of other
+/// headers don't change.
+fn hash_custom_version_names(names: &HashableIndexMap<Guid, String>, hasher: &mut StableHasher) {
+    if !names.is_empty() {
+        hasher.write_str("CustomVersionNames");
+        names.stable_hash_into(hasher);
+    }
+}
+
+impl StableHash for GvasFile {
+    fn stable_hash_into(&self, hasher: &mut StableHasher) {
+        hasher.write_len(self.prefix.len());
+        hasher.write(&self.prefix);
+        self.header.stable_hash_into(hasher);
+        // Only hashed when present, so hashes of other files don't change
+        if !self.custom_version_extra.is_empty() {
+            hasher.write_str("CustomVersionExtra");
+            self.custom_version_extra.stable_hash_into(hasher);
+        }
+        self.properties.stable_hash_into(hasher);
+        self.trailing_padding.stable_hash_into(hasher);
+        if !self.trailing_bytes.is_empty() {
+            hasher.write_str("TrailingBytes");
+            self.trailing_bytes.stable_hash_into(hasher);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Statistics kept in maps of integers
+//!
+//! Games often keep statistics in a `MapProperty` from strings or names to integers, with keys
+//! made of a prefix naming the statistic and an id naming what it's about, like
+//! `playtime_418AD60A45FCE310E9D3DEB5B9894EF2` and `bestrank_418AD60A45FCE310E9D3DEB5B9894EF2`.
+//! [`collect`] finds these maps below a property, and [`Statistics`] iterates over their entries
+//! split into prefix, id and value without copying them.
+//!
+//! Keys are split at the first `_`. Keys without one, like `money`, have an empty id.
+//!
+//! Paths use the format described in [`visitor`](crate::visitor).
+//!
+//! [`collect`]: crate::statistics::collect
+//! [`Statistics`]: crate::statistics::Statistics
+//!
+//! ```
+//! use gvas::{
+//!     properties::{map_property::MapProperty, Property},
+//!     statistics,
+//!     types::map::HashableIndexMap,
+//! };
+//!
+//! let property = Property::from(MapProperty::StrInt {
+//!     str_ints: HashableIndexMap::from([
+//!         (String::from("playtime_Level1"), 38),
+//!         (String::from("bestrank_Level1"), 4),
+//!         (String::from("playtime_Level2"), 68),
+//!         (String::from("money"), 14),
+//!     ]),
+//! });
+//!
+//! let statistics = statistics::collect("dbNumbers", &property);
+//! let playtime = statistics.with_prefix("playtime").collect::<Vec<_>>();
+//! assert_eq!(playtime, [("Level1", 38), ("Level2", 68)]);
+//! assert_eq!(statistics.get("bestrank", "Level1"), Some(4));
+//! assert_eq!(statistics.get("money", ""), Some(14));
+//! assert_eq!(statistics.prefixes(), ["playtime", "bestrank", "money"]);
+//! ```
+
+use crate::{
+    properties::{map_property::MapProperty, Property},
+    types::map::HashableIndexMap,
+    visitor,
+};
+
+/// An entry of a statistics map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Statistic<'a> {
+    /// Path of the map.
+    pub path: &'a str,
+    /// The key before the first `_`, or the whole key.
+    pub prefix: &'a str,
+    /// The key after the first `_`, empty if the key has none.
+    pub id: &'a str,
+    /// Value of the entry.
+    pub value: i32,
+}
+
+/// The statistics maps below a property, see the [module documentation](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Statistics<'a> {
+    maps: Vec<(String, &'a HashableIndexMap<String, i32>)>,
+}
+
+impl<'a> Statistics<'a> {
+    /// Adds the maps of `other` after the maps of these statistics.
+    #[inline]
+    pub fn extend(&mut self, other: Statistics<'a>) {
+        self.maps.extend(other.maps);
+    }
+
+    /// Returns true if no statistics map was found.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.maps.is_empty()
+    }
+
+    /// The paths of the statistics maps, in the order they were found.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.maps.iter().map(|(path, _)| path.as_str())
+    }
+
+    /// Every entry of every map, in the order they are stored.
+    pub fn iter(&self) -> impl Iterator<Item = Statistic<'_>> {
+        self.maps.iter().flat_map(|(path, map)| {
+            map.iter().map(move |(key, value)| {
+                let (prefix, id) = split_key(key);
+                Statistic {
+                    path,
+                    prefix,
+                    id,
+                    value: *value,
+                }
+            })
+        })
+    }
+
+    /// The id and value of every entry whose key starts with `prefix` and `_`.
+    pub fn with_prefix<'s>(&'s self, prefix: &'s str) -> impl Iterator<Item = (&'s str, i32)> {
+        self.iter()
+            .filter(move |statistic| statistic.prefix == prefix)
+            .map(|statistic| (statistic.id, statistic.value))
+    }
+
+    /// The value of the first entry with `prefix` and `id`.
+    pub fn get(&self, prefix: &str, id: &str) -> Option<i32> {
+        self.iter()
+            .find(|statistic| statistic.prefix == prefix && statistic.id == id)
+            .map(|statistic| statistic.value)
+    }
+
+    /// Every prefix, in the order they first appear.
+    pub fn prefixes(&self) -> Vec<&str> {
+        let mut prefixes = Vec::new();
+        for statistic in self.iter() {
+            if !prefixes.contains(&statistic.prefix) {
+                prefixes.push(statistic.prefix);
+            }
+        }
+        prefixes
+    }
+}
+
+/// Find every map from strings or names to integers below `property`, with `path` as the path
+/// of `property`.
+pub fn collect<'a>(path: &str, property: &'a Property) -> Statistics<'a> {
+    let maps = visitor::nodes(path, property)
+        .into_iter()
+        .filter_map(|(path, property)| match property {
+            Property::MapProperty(MapProperty::StrInt { str_ints }) => Some((path, str_ints)),
+            Property::MapProperty(MapProperty::NameInt { name_ints }) => Some((path, name_ints)),
+            _ => None,
+        })
+        .collect();
+    Statistics { maps }
+}
+
+/// Splits a key into its prefix and id at the first `_`.
+#[inline]
+fn split_key(key: &str) -> (&str, &str) {
+    key.split_once('_').unwrap_or((key, ""))
+}
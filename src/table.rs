@@ -0,0 +1,213 @@
+//! Flat tables of numeric data
+//!
+//! Saves often keep statistics in maps and arrays of numbers, like the `playtime_*` and
+//! `bestrank_*` entries of a `MapProperty`. [`rows`] flattens every numeric map and array below a
+//! property into [`TableRow`]s holding the path of the container, the key or index of the entry,
+//! its value and its type, which [`write_csv`] writes as CSV that spreadsheets can open.
+//!
+//! Paths use the format described in [`visitor`](crate::visitor).
+//!
+//! [`rows`]: crate::table::rows
+//! [`TableRow`]: crate::table::TableRow
+//! [`write_csv`]: crate::table::write_csv
+//!
+//! ```
+//! use gvas::{
+//!     properties::{map_property::MapProperty, Property},
+//!     table,
+//!     types::map::HashableIndexMap,
+//! };
+//!
+//! let property = Property::from(MapProperty::StrInt {
+//!     str_ints: HashableIndexMap::from([(String::from("money"), 14)]),
+//! });
+//!
+//! let mut csv = Vec::new();
+//! table::write_csv(&mut csv, &table::rows("dbNumbers", &property))?;
+//! assert_eq!(
+//!     String::from_utf8(csv).expect("CSV is valid UTF-8"),
+//!     "path,key,value,type\r\ndbNumbers,money,14,IntProperty\r\n"
+//! );
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+
+use std::io::Write;
+
+use crate::{
+    error::Error,
+    properties::{
+        array_property::ArrayProperty, int_property::BytePropertyValue, map_property::MapProperty,
+        property_kind::PropertyKind, Property,
+    },
+    visitor::{walk, Visitor},
+};
+
+/// A single entry of a numeric map or array.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TableRow {
+    /// Path of the map or array.
+    pub path: String,
+    /// Key of the map entry, or index of the array element.
+    pub key: String,
+    /// Value of the entry.
+    pub value: String,
+    /// Property type of the value, e.g. `IntProperty`.
+    pub value_type: &'static str,
+}
+
+/// Flatten every numeric map and array below `property`, with `path` as the path of `property`.
+///
+/// Map entries whose key isn't a string, name, enum or number are left out.
+pub fn rows(path: &str, property: &Property) -> Vec<TableRow> {
+    let mut visitor = RowVisitor { rows: Vec::new() };
+    walk(&mut visitor, path, property);
+    visitor.rows
+}
+
+/// Write `rows` as CSV with a `path,key,value,type` header.
+///
+/// Fields are quoted when needed and lines end with `\r\n`.
+pub fn write_csv<W: Write>(writer: &mut W, rows: &[TableRow]) -> Result<(), Error> {
+    writer.write_all(b"path,key,value,type\r\n")?;
+    for row in rows {
+        let fields = [
+            row.path.as_str(),
+            row.key.as_str(),
+            row.value.as_str(),
+            row.value_type,
+        ];
+        for (index, field) in fields.into_iter().enumerate() {
+            if index > 0 {
+                writer.write_all(b",")?;
+            }
+            if field.contains([',', '"', '\r', '\n']) {
+                write!(writer, "\"{}\"", field.replace('"', "\"\""))?;
+            } else {
+                writer.write_all(field.as_bytes())?;
+            }
+        }
+        writer.write_all(b"\r\n")?;
+    }
+    Ok(())
+}
+
+struct RowVisitor {
+    rows: Vec<TableRow>,
+}
+
+impl RowVisitor {
+    fn push(&mut self, path: &str, key: String, (value, value_type): (String, &'static str)) {
+        self.rows.push(TableRow {
+            path: path.to_string(),
+            key,
+            value,
+            value_type,
+        });
+    }
+}
+
+impl Visitor for RowVisitor {
+    fn visit_property(&mut self, path: &str, property: &Property) -> bool {
+        match property {
+            Property::MapProperty(MapProperty::EnumInt { enum_ints: map })
+            | Property::MapProperty(MapProperty::NameInt { name_ints: map })
+            | Property::MapProperty(MapProperty::StrInt { str_ints: map }) => {
+                for (key, value) in map.iter() {
+                    let value = (value.to_string(), PropertyKind::IntProperty.as_str());
+                    self.push(path, key.clone(), value);
+                }
+            }
+            Property::MapProperty(MapProperty::EnumProperty {
+                enum_props: map, ..
+            })
+            | Property::MapProperty(MapProperty::NameProperty {
+                name_props: map, ..
+            })
+            | Property::MapProperty(MapProperty::StrProperty { str_props: map, .. }) => {
+                for (key, value) in map.iter() {
+                    if let Some(value) = numeric_value(value) {
+                        self.push(path, key.clone(), value);
+                    }
+                }
+            }
+            Property::MapProperty(MapProperty::Properties { value: map, .. }) => {
+                for (key, value) in map.iter() {
+                    if let (Some(key), Some(value)) = (key_string(key), numeric_value(value)) {
+                        self.push(path, key, value);
+                    }
+                }
+            }
+            Property::ArrayProperty(ArrayProperty::Ints { ints }) => {
+                for (index, value) in ints.iter().enumerate() {
+                    let value = (value.to_string(), PropertyKind::IntProperty.as_str());
+                    self.push(path, index.to_string(), value);
+                }
+            }
+            Property::ArrayProperty(ArrayProperty::Floats { floats }) => {
+                for (index, value) in floats.iter().enumerate() {
+                    self.push(
+                        path,
+                        index.to_string(),
+                        (value.to_string(), PropertyKind::FloatProperty.as_str()),
+                    );
+                }
+            }
+            Property::ArrayProperty(ArrayProperty::Properties { properties, .. }) => {
+                for (index, value) in properties.iter().enumerate() {
+                    if let Some(value) = numeric_value(value) {
+                        self.push(path, index.to_string(), value);
+                    }
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
+/// The value and type name of a numeric property.
+fn numeric_value(property: &Property) -> Option<(String, &'static str)> {
+    let (value, kind) = match property {
+        Property::ByteProperty(property) => match property.value {
+            BytePropertyValue::Byte(value) => (value.to_string(), PropertyKind::ByteProperty),
+            BytePropertyValue::Namespaced(_) => return None,
+        },
+        Property::Int8Property(property) => {
+            (property.value.to_string(), PropertyKind::Int8Property)
+        }
+        Property::Int16Property(property) => {
+            (property.value.to_string(), PropertyKind::Int16Property)
+        }
+        Property::IntProperty(property) => (property.value.to_string(), PropertyKind::IntProperty),
+        Property::Int64Property(property) => {
+            (property.value.to_string(), PropertyKind::Int64Property)
+        }
+        Property::UInt16Property(property) => {
+            (property.value.to_string(), PropertyKind::UInt16Property)
+        }
+        Property::UInt32Property(property) => {
+            (property.value.to_string(), PropertyKind::UInt32Property)
+        }
+        Property::UInt64Property(property) => {
+            (property.value.to_string(), PropertyKind::UInt64Property)
+        }
+        Property::FloatProperty(property) => {
+            (property.value.to_string(), PropertyKind::FloatProperty)
+        }
+        Property::DoubleProperty(property) => {
+            (property.value.to_string(), PropertyKind::DoubleProperty)
+        }
+        _ => return None,
+    };
+    Some((value, kind.as_str()))
+}
+
+/// A map key as a string, if it is a string, name, enum or number.
+pub(crate) fn key_string(property: &Property) -> Option<String> {
+    match property {
+        Property::StrProperty(property) => property.value.clone(),
+        Property::NameProperty(property) => property.value.as_deref().map(String::from),
+        Property::EnumProperty(property) => Some(property.value.clone()),
+        property => numeric_value(property).map(|(value, _)| value),
+    }
+}
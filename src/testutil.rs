@@ -0,0 +1,147 @@
+//! Test helpers
+//!
+//! [`assert_gvas_eq!`] compares two [`GvasFile`]s and, when they differ, only prints the parts
+//! that changed, with the path of every differing property, instead of the `Debug` output of both
+//! files.
+//!
+//! [`assert_gvas_eq!`]: crate::assert_gvas_eq
+//! [`GvasFile`]: crate::GvasFile
+//!
+//! ```should_panic
+//! use gvas::{assert_gvas_eq, game_version::GameVersion, GvasFile};
+//! use std::fs::File;
+//!
+//! let mut file = File::open("resources/test/Slot1.sav")?;
+//! let expected = GvasFile::read(&mut file, GameVersion::Default)?;
+//! let mut actual = expected.clone();
+//! actual.properties.insert(String::from("int32_test"), 5.into());
+//!
+//! // Panics with:
+//! // --- int32_test
+//! // -IntProperty(
+//! // -    5i32,
+//! // -)
+//! // +IntProperty(
+//! // +    -2147483647i32,
+//! // +)
+//! assert_gvas_eq!(actual, expected);
+//! # Ok::<(), gvas::error::Error>(())
+//! ```
+
+use std::fmt::{Debug, Write};
+
+use crate::{
+    diff::{diff, Change, DiffOptions},
+    GvasFile,
+};
+
+/// Asserts that two [`GvasFile`]s are equal, printing the differences if they aren't.
+///
+/// Like [`assert_eq!`], a custom message can be passed after the two files.
+///
+/// [`GvasFile`]: crate::GvasFile
+#[macro_export]
+macro_rules! assert_gvas_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    panic!(
+                        "assertion `left == right` failed\n{}",
+                        $crate::testutil::describe_differences(left, right)
+                    );
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    panic!(
+                        "assertion `left == right` failed: {}\n{}",
+                        format_args!($($arg)+),
+                        $crate::testutil::describe_differences(left, right)
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Describe the differences between `left` and `right` as a unified diff.
+///
+/// Every differing header field and property gets a `--- path` line, followed by the `Debug`
+/// output of the `left` value prefixed with `-` and of the `right` value prefixed with `+`.
+pub fn describe_differences(left: &GvasFile, right: &GvasFile) -> String {
+    let mut output = String::new();
+    if left.deserialized_game_version != right.deserialized_game_version {
+        write_change(
+            &mut output,
+            "deserialized_game_version",
+            Some(&left.deserialized_game_version),
+            Some(&right.deserialized_game_version),
+        );
+    }
+    if left.prefix != right.prefix {
+        write_change(
+            &mut output,
+            "prefix",
+            Some(&left.prefix),
+            Some(&right.prefix),
+        );
+    }
+    if left.header != right.header {
+        write_change(
+            &mut output,
+            "header",
+            Some(&left.header),
+            Some(&right.header),
+        );
+    }
+    for difference in diff(left, right, &DiffOptions::default()) {
+        let (left, right) = match difference.change {
+            Change::Added(property) => (None, Some(property)),
+            Change::Removed(property) => (Some(property), None),
+            Change::Changed(left, right) => (Some(left), Some(right)),
+        };
+        write_change(&mut output, &difference.path, left, right);
+    }
+    if left.trailing_padding != right.trailing_padding {
+        write_change(
+            &mut output,
+            "trailing_padding",
+            Some(&left.trailing_padding),
+            Some(&right.trailing_padding),
+        );
+    }
+    if left.trailing_bytes != right.trailing_bytes {
+        write_change(
+            &mut output,
+            "trailing_bytes",
+            Some(&left.trailing_bytes),
+            Some(&right.trailing_bytes),
+        );
+    }
+    if output.is_empty() {
+        // Containers that only differ in their own fields, like the type name of a struct
+        for (name, property) in &left.properties {
+            let other = right.properties.get(name);
+            if other != Some(property) {
+                write_change(&mut output, name, Some(property), other);
+            }
+        }
+    }
+    output
+}
+
+fn write_change<T: Debug>(output: &mut String, path: &str, left: Option<&T>, right: Option<&T>) {
+    let _ = writeln!(output, "--- {path}");
+    for (prefix, value) in [('-', left), ('+', right)] {
+        if let Some(value) = value {
+            for line in format!("{value:#?}").lines() {
+                let _ = writeln!(output, "{prefix}{line}");
+            }
+        }
+    }
+}
@@ -207,11 +207,29 @@ impl<'de> serde::Deserialize<'de> for Guid {
     }
 }
 
+#[cfg(feature = "serde")]
+mod guid_policy;
+
+#[cfg(feature = "serde")]
+pub use guid_policy::{GuidPolicy, WithGuidPolicy};
+
+/// Deserializes the guid of a struct property, reading `null` as zero.
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_guid<'de, D>(deserializer: D) -> Result<Guid, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    Ok(Option::<Guid>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 /// Map types
 pub mod map {
     use std::{
+        collections::hash_map::DefaultHasher,
         fmt::Debug,
-        hash::Hash,
+        hash::{Hash, Hasher},
         ops::{Deref, DerefMut},
     };
 
@@ -243,16 +261,103 @@ pub mod map {
         }
     }
 
+    /// How string keys are compared when looking up entries.
+    ///
+    /// Unreal Engine property names are `FName`s, which compare case-insensitively.
+    #[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+    pub enum KeyComparison {
+        /// Keys must match exactly.
+        #[default]
+        Exact,
+        /// Keys are compared ignoring ASCII case.
+        CaseInsensitive,
+    }
+
+    impl<V> HashableIndexMap<String, V>
+    where
+        V: Hash,
+    {
+        /// Get the index, key and value of an entry using the given key comparison.
+        ///
+        /// An exact match is always preferred, the original casing of the key is preserved.
+        pub fn get_full_by(
+            &self,
+            key: &str,
+            comparison: KeyComparison,
+        ) -> Option<(usize, &String, &V)> {
+            if let Some(entry) = self.0.get_full(key) {
+                return Some(entry);
+            }
+            match comparison {
+                KeyComparison::Exact => None,
+                KeyComparison::CaseInsensitive => self
+                    .0
+                    .iter()
+                    .enumerate()
+                    .find(|(_, (k, _))| k.eq_ignore_ascii_case(key))
+                    .map(|(index, (k, v))| (index, k, v)),
+            }
+        }
+
+        /// Get a value using the given key comparison.
+        #[inline]
+        pub fn get_by(&self, key: &str, comparison: KeyComparison) -> Option<&V> {
+            self.get_full_by(key, comparison).map(|(_, _, v)| v)
+        }
+
+        /// Get a mutable value using the given key comparison.
+        #[inline]
+        pub fn get_by_mut(&mut self, key: &str, comparison: KeyComparison) -> Option<&mut V> {
+            let (index, _, _) = self.get_full_by(key, comparison)?;
+            self.0.get_index_mut(index).map(|(_, v)| v)
+        }
+
+        /// Get a value, ignoring ASCII case in the key.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use gvas::types::map::HashableIndexMap;
+        ///
+        /// let map = HashableIndexMap::from([("HighScore".to_string(), 2649)]);
+        /// assert_eq!(map.get_ci("highscore"), Some(&2649));
+        /// ```
+        #[inline]
+        pub fn get_ci(&self, key: &str) -> Option<&V> {
+            self.get_by(key, KeyComparison::CaseInsensitive)
+        }
+
+        /// Get a mutable value, ignoring ASCII case in the key.
+        #[inline]
+        pub fn get_ci_mut(&mut self, key: &str) -> Option<&mut V> {
+            self.get_by_mut(key, KeyComparison::CaseInsensitive)
+        }
+
+        /// Return `true` if a key matches, ignoring ASCII case.
+        #[inline]
+        pub fn contains_key_ci(&self, key: &str) -> bool {
+            self.get_ci(key).is_some()
+        }
+    }
+
     impl<K, V> Hash for HashableIndexMap<K, V>
     where
         K: Hash + Eq,
         V: Hash,
     {
-        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-            for (key, value) in &self.0 {
-                key.hash(state);
-                value.hash(state);
-            }
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            // Equality ignores the order of the entries, so the hash must ignore it as well.
+            let entries = self
+                .0
+                .iter()
+                .map(|entry| {
+                    let mut hasher = DefaultHasher::new();
+                    entry.hash(&mut hasher);
+                    hasher.finish()
+                })
+                .fold(0u64, u64::wrapping_add);
+            state.write_usize(self.0.len());
+            state.write_u64(entries);
         }
     }
 
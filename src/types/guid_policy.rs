@@ -0,0 +1,533 @@
+//! Serialization of zero struct guids, see [`WithGuidPolicy`].
+
+use std::fmt::Display;
+
+use serde::ser::{
+    Impossible, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+
+use super::Guid;
+
+/// How zero guids of struct properties are serialized, see [`WithGuidPolicy`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum GuidPolicy {
+    /// Leave zero guids out.
+    #[default]
+    SkipZero,
+    /// Write zero guids as `"0"`.
+    Always,
+    /// Write zero guids as `null`.
+    Null,
+}
+
+/// Serializes a value with zero guids of struct properties written according to a
+/// [`GuidPolicy`].
+///
+/// Serializing a value directly uses [`GuidPolicy::SkipZero`]. Guids of struct properties are
+/// read back as zero when they are missing, `"0"` or `null` with every policy, so JSON passed
+/// through tools which normalize missing and `null` fields reads back as the same properties.
+///
+/// ```
+/// use gvas::{
+///     properties::{
+///         struct_property::{StructProperty, StructPropertyValue},
+///         Property,
+///     },
+///     types::{Guid, GuidPolicy, WithGuidPolicy},
+/// };
+///
+/// let property = Property::from(StructProperty::new(
+///     Guid::default(),
+///     "Guid",
+///     StructPropertyValue::Guid(Guid::from_u128(1)),
+/// ));
+/// let json = serde_json::to_value(WithGuidPolicy::new(&property, GuidPolicy::Null))?;
+/// assert!(json["guid"].is_null());
+/// assert_eq!(serde_json::from_value::<Property>(json)?, property);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct WithGuidPolicy<'a, T: ?Sized> {
+    value: &'a T,
+    policy: GuidPolicy,
+}
+
+impl<'a, T: ?Sized> WithGuidPolicy<'a, T> {
+    /// Creates a new `WithGuidPolicy` instance serializing `value` with `policy`.
+    #[inline]
+    pub fn new(value: &'a T, policy: GuidPolicy) -> Self {
+        WithGuidPolicy { value, policy }
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for WithGuidPolicy<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(PolicySerializer {
+            inner: serializer,
+            policy: self.policy,
+        })
+    }
+}
+
+/// A zero guid written with a policy other than [`GuidPolicy::SkipZero`].
+struct ZeroGuid(GuidPolicy);
+
+impl Serialize for ZeroGuid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            GuidPolicy::Null => serializer.serialize_none(),
+            _ => Guid::default().serialize(serializer),
+        }
+    }
+}
+
+/// Passes everything to `inner`, adding back the zero guids of struct properties which were
+/// skipped while serializing them.
+///
+/// The guid of a `StructProperty` is the field after its `type` tag, and struct arrays skip
+/// their `guid` field explicitly.
+struct PolicySerializer<S> {
+    inner: S,
+    policy: GuidPolicy,
+}
+
+macro_rules! forward {
+    ($($method:ident($($arg:ident: $type:ty),*);)+) => {
+        $(
+            #[inline]
+            fn $method(self, $($arg: $type),*) -> Result<S::Ok, S::Error> {
+                self.inner.$method($($arg),*)
+            }
+        )+
+    };
+}
+
+impl<S: Serializer> Serializer for PolicySerializer<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = Compound<S::SerializeSeq>;
+    type SerializeTuple = Compound<S::SerializeTuple>;
+    type SerializeTupleStruct = Compound<S::SerializeTupleStruct>;
+    type SerializeTupleVariant = Compound<S::SerializeTupleVariant>;
+    type SerializeMap = Compound<S::SerializeMap>;
+    type SerializeStruct = Compound<S::SerializeStruct>;
+    type SerializeStructVariant = Compound<S::SerializeStructVariant>;
+
+    forward! {
+        serialize_bool(v: bool);
+        serialize_i8(v: i8);
+        serialize_i16(v: i16);
+        serialize_i32(v: i32);
+        serialize_i64(v: i64);
+        serialize_i128(v: i128);
+        serialize_u8(v: u8);
+        serialize_u16(v: u16);
+        serialize_u32(v: u32);
+        serialize_u64(v: u64);
+        serialize_u128(v: u128);
+        serialize_f32(v: f32);
+        serialize_f64(v: f64);
+        serialize_char(v: char);
+        serialize_str(v: &str);
+        serialize_bytes(v: &[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(name: &'static str);
+        serialize_unit_variant(name: &'static str, index: u32, variant: &'static str);
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.inner
+            .serialize_some(&WithGuidPolicy::new(value, self.policy))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner
+            .serialize_newtype_struct(name, &WithGuidPolicy::new(value, self.policy))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_newtype_variant(
+            name,
+            index,
+            variant,
+            &WithGuidPolicy::new(value, self.policy),
+        )
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        let inner = self.inner.serialize_seq(len)?;
+        Ok(Compound::new(inner, self.policy))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        let inner = self.inner.serialize_tuple(len)?;
+        Ok(Compound::new(inner, self.policy))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        let inner = self.inner.serialize_tuple_struct(name, len)?;
+        Ok(Compound::new(inner, self.policy))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        let inner = self
+            .inner
+            .serialize_tuple_variant(name, index, variant, len)?;
+        Ok(Compound::new(inner, self.policy))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        let inner = self.inner.serialize_map(len)?;
+        Ok(Compound::new(inner, self.policy))
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        let inner = self.inner.serialize_struct(name, len)?;
+        Ok(Compound::new(inner, self.policy))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        let inner = self
+            .inner
+            .serialize_struct_variant(name, index, variant, len)?;
+        Ok(Compound::new(inner, self.policy))
+    }
+
+    fn collect_str<T: Display + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.inner.collect_str(value)
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+/// A sequence, tuple, map or struct of a [`PolicySerializer`].
+struct Compound<C> {
+    inner: C,
+    policy: GuidPolicy,
+    /// The last map key was `type`.
+    type_key: bool,
+    /// The map is a `StructProperty` whose guid hasn't been written yet.
+    pending_guid: bool,
+}
+
+impl<C> Compound<C> {
+    fn new(inner: C, policy: GuidPolicy) -> Self {
+        Compound {
+            inner,
+            policy,
+            type_key: false,
+            pending_guid: false,
+        }
+    }
+
+    #[inline]
+    fn wrap<'a, T: ?Sized>(&self, value: &'a T) -> WithGuidPolicy<'a, T> {
+        WithGuidPolicy::new(value, self.policy)
+    }
+}
+
+impl<C: SerializeMap> Compound<C> {
+    /// Writes the skipped guid of a `StructProperty` before the field after its tag.
+    fn before_key<K: Serialize + ?Sized>(&mut self, key: &K) -> Result<(), C::Error> {
+        let guid_key = is_str(key, "guid");
+        if std::mem::take(&mut self.pending_guid) && !guid_key {
+            self.inner.serialize_entry("guid", &ZeroGuid(self.policy))?;
+        }
+        self.type_key = !guid_key && is_str(key, "type");
+        Ok(())
+    }
+
+    fn after_value<V: Serialize + ?Sized>(&mut self, value: &V) {
+        self.pending_guid = std::mem::take(&mut self.type_key)
+            && self.policy != GuidPolicy::SkipZero
+            && is_str(value, "StructProperty");
+    }
+}
+
+macro_rules! impl_compound {
+    ($($trait:ident::$method:ident),+) => {
+        $(
+            impl<C: $trait> $trait for Compound<C> {
+                type Ok = C::Ok;
+                type Error = C::Error;
+
+                fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+                    self.inner.$method(&self.wrap(value))
+                }
+
+                fn end(self) -> Result<C::Ok, C::Error> {
+                    self.inner.end()
+                }
+            }
+        )+
+    };
+}
+
+impl_compound!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field
+);
+
+impl<C: SerializeMap> SerializeMap for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), C::Error> {
+        self.before_key(key)?;
+        self.inner.serialize_key(&self.wrap(key))
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+        self.after_value(value);
+        self.inner.serialize_value(&self.wrap(value))
+    }
+
+    fn serialize_entry<K: Serialize + ?Sized, V: Serialize + ?Sized>(
+        &mut self,
+        key: &K,
+        value: &V,
+    ) -> Result<(), C::Error> {
+        self.before_key(key)?;
+        self.after_value(value);
+        self.inner
+            .serialize_entry(&self.wrap(key), &self.wrap(value))
+    }
+
+    fn end(mut self) -> Result<C::Ok, C::Error> {
+        if self.pending_guid {
+            self.inner.serialize_entry("guid", &ZeroGuid(self.policy))?;
+        }
+        self.inner.end()
+    }
+}
+
+impl<C: SerializeStruct> SerializeStruct for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        self.inner.serialize_field(key, &self.wrap(value))
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), C::Error> {
+        match (key, self.policy) {
+            (_, GuidPolicy::SkipZero) => self.inner.skip_field(key),
+            ("guid", policy) => self.inner.serialize_field(key, &ZeroGuid(policy)),
+            _ => self.inner.skip_field(key),
+        }
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: SerializeStructVariant> SerializeStructVariant for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        self.inner.serialize_field(key, &self.wrap(value))
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), C::Error> {
+        match (key, self.policy) {
+            (_, GuidPolicy::SkipZero) => self.inner.skip_field(key),
+            ("guid", policy) => self.inner.serialize_field(key, &ZeroGuid(policy)),
+            _ => self.inner.skip_field(key),
+        }
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+/// Returns true if `value` serializes as the string `expected`.
+fn is_str<T: Serialize + ?Sized>(value: &T, expected: &str) -> bool {
+    value.serialize(StrProbe(expected)).unwrap_or(false)
+}
+
+/// A serializer comparing strings with the expected string, failing on everything else.
+struct StrProbe<'a>(&'a str);
+
+/// The value given to a [`StrProbe`] isn't a string.
+#[derive(Debug)]
+struct NotAString;
+
+impl Display for NotAString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("not a string")
+    }
+}
+
+impl std::error::Error for NotAString {}
+
+impl serde::ser::Error for NotAString {
+    fn custom<T: Display>(_: T) -> Self {
+        NotAString
+    }
+}
+
+macro_rules! reject {
+    ($($method:ident($($type:ty),*);)+) => {
+        $(
+            #[inline]
+            fn $method(self, $(_: $type),*) -> Result<bool, NotAString> {
+                Err(NotAString)
+            }
+        )+
+    };
+}
+
+impl Serializer for StrProbe<'_> {
+    type Ok = bool;
+    type Error = NotAString;
+    type SerializeSeq = Impossible<bool, NotAString>;
+    type SerializeTuple = Impossible<bool, NotAString>;
+    type SerializeTupleStruct = Impossible<bool, NotAString>;
+    type SerializeTupleVariant = Impossible<bool, NotAString>;
+    type SerializeMap = Impossible<bool, NotAString>;
+    type SerializeStruct = Impossible<bool, NotAString>;
+    type SerializeStructVariant = Impossible<bool, NotAString>;
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<bool, NotAString> {
+        Ok(v == self.0)
+    }
+
+    reject! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_char(char);
+        serialize_bytes(&[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(&'static str);
+        serialize_unit_variant(&'static str, u32, &'static str);
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<bool, NotAString> {
+        Err(NotAString)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<bool, NotAString> {
+        Err(NotAString)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<bool, NotAString> {
+        Err(NotAString)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, NotAString> {
+        Err(NotAString)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, NotAString> {
+        Err(NotAString)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, NotAString> {
+        Err(NotAString)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, NotAString> {
+        Err(NotAString)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, NotAString> {
+        Err(NotAString)
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, NotAString> {
+        Err(NotAString)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, NotAString> {
+        Err(NotAString)
+    }
+}
@@ -0,0 +1,1072 @@
+//! Conversion to and from the JSON format of uesave
+//!
+//! [uesave](https://github.com/trumank/uesave-rs) writes saves as JSON documents with a
+//! `header`, a `root` holding the save game class name and the properties, and the `extra`
+//! bytes following the properties. [`to_value`] converts a [`GvasFile`] to such a document and
+//! [`from_value`] converts it back, so saves edited with either tool can be passed to the other.
+//!
+//! Hints use the same paths in both tools and can be shared as they are.
+//!
+//! Only the properties uesave knows are converted. Text, delegate, field path and unknown
+//! properties, custom struct fields with an array index, structs uesave has no type for, and
+//! containers with allocation flags are rejected, as are data before the header and data between
+//! the custom versions and the save game class name. The container of the save isn't part of
+//! the document, a converted file is written without one.
+//!
+//! Properties read back from a document are normalised the way [`ArrayProperty::new`],
+//! [`MapProperty::new`] and [`SetProperty::new`] normalise them. Vectors, rotators and
+//! quaternions have double precision components if the header supports large world coordinates.
+//!
+//! [`to_value`]: crate::uesave::to_value
+//! [`from_value`]: crate::uesave::from_value
+//! [`GvasFile`]: crate::GvasFile
+//! [`ArrayProperty::new`]: crate::properties::array_property::ArrayProperty::new
+//! [`MapProperty::new`]: crate::properties::map_property::MapProperty::new
+//! [`SetProperty::new`]: crate::properties::set_property::SetProperty::new
+//!
+//! ```
+//! use gvas::{game_version::GameVersion, uesave, GvasFile};
+//! use std::fs::File;
+//!
+//! let mut file = File::open("resources/test/Slot1.sav")?;
+//! let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+//!
+//! let value = uesave::to_value(&gvas_file)?;
+//! assert_eq!(value["root"]["properties"]["str_property"]["Str"]["value"], "Hello world");
+//! assert_eq!(uesave::from_value(&value)?, gvas_file);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::fmt::Display;
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    custom_version::FUE5ReleaseStreamObjectVersion,
+    engine_version::FEngineVersion,
+    error::{Error, SerializeError},
+    game_version::DeserializedGameVersion,
+    properties::{
+        array_property::ArrayProperty,
+        enum_property::EnumProperty,
+        int_property::{
+            BoolProperty, ByteProperty, BytePropertyValue, DoubleProperty, FloatProperty,
+            Int16Property, Int64Property, Int8Property, IntProperty, UInt16Property,
+            UInt32Property, UInt64Property,
+        },
+        map_property::{MapDuplicate, MapProperty, OrderedEntries},
+        name_property::NameProperty,
+        object_property::ObjectProperty,
+        set_property::SetProperty,
+        str_property::StrProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        struct_types::{
+            DateTime, IntPoint, IntVector, LinearColor, QuatD, QuatF, RotatorD, RotatorF, Timespan,
+            Vector2D, Vector2F, VectorD, VectorF,
+        },
+        Property,
+    },
+    types::{map::HashableIndexMap, Guid},
+    GvasFile, GvasHeader, FILE_TYPE_GVAS,
+};
+
+/// Struct types uesave writes as a plain string instead of `{"Struct": "TypeName"}`.
+const STRUCT_TYPES: [&str; 13] = [
+    "Guid",
+    "DateTime",
+    "Timespan",
+    "Vector2D",
+    "Vector",
+    "Box",
+    "IntPoint",
+    "Quat",
+    "LinearColor",
+    "Color",
+    "Rotator",
+    "SoftObjectPath",
+    "GameplayTagContainer",
+];
+
+/// Convert `file` to a uesave JSON document.
+///
+/// # Errors
+///
+/// Returns [`SerializeError::InvalidValue`] if the file holds data uesave can't represent, see
+/// the [module documentation](self).
+pub fn to_value(file: &GvasFile) -> Result<Value, Error> {
+    if !file.prefix.is_empty() {
+        Err(invalid("", "Data before the header"))?
+    }
+    if !file.custom_version_extra.is_empty() {
+        Err(invalid("header", "Data after the custom versions"))?
+    }
+    let extra = match file.trailing_padding {
+        Some(padding) => padding.to_le_bytes().to_vec(),
+        None => file.trailing_bytes.clone(),
+    };
+    Ok(json!({
+        "header": header_to_value(&file.header)?,
+        "root": {
+            "save_game_type": file.header.get_save_game_class_name(),
+            "properties": properties_to_value("", &file.properties)?,
+        },
+        "extra": extra,
+    }))
+}
+
+/// Convert a uesave JSON document to a [`GvasFile`].
+///
+/// # Errors
+///
+/// Returns [`SerializeError::InvalidValue`] if `value` isn't a uesave document, or holds
+/// properties this crate can't represent.
+pub fn from_value(value: &Value) -> Result<GvasFile, Error> {
+    let root = field("", value, "root")?;
+    let save_game_class_name = string("root", field("root", root, "save_game_type")?)?;
+    let header = header_from_value(field("", value, "header")?, save_game_class_name)?;
+
+    let reader = Reader {
+        large_world_coordinates: header
+            .supports_version(FUE5ReleaseStreamObjectVersion::LargeWorldCoordinates),
+    };
+    let properties = reader.properties("", field("root", root, "properties")?)?;
+
+    let extra = array("extra", field("", value, "extra")?)?
+        .iter()
+        .map(|byte| number::<u8>("extra", byte))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (trailing_padding, trailing_bytes) = match extra.as_slice() {
+        &[a, b, c, d] => (Some(i32::from_le_bytes([a, b, c, d])), Vec::new()),
+        bytes if bytes.len() < 4 => (None, extra),
+        _ => Err(invalid("extra", "Expected at most 4 bytes"))?,
+    };
+
+    Ok(GvasFile {
+        deserialized_game_version: DeserializedGameVersion::Default,
+        prefix: Vec::new(),
+        header,
+        custom_version_extra: Vec::new(),
+        properties,
+        trailing_padding,
+        trailing_bytes,
+    })
+}
+
+fn header_to_value(header: &GvasHeader) -> Result<Value, Error> {
+    let (save_game_version, ue5, engine_version, custom_version_format, custom_versions) =
+        match header {
+            GvasHeader::Version2 {
+                engine_version,
+                custom_version_format,
+                custom_versions,
+                ..
+            } => (
+                2,
+                None,
+                engine_version,
+                custom_version_format,
+                custom_versions,
+            ),
+            GvasHeader::Version3 {
+                save_game_file_version,
+                package_file_version_ue5,
+                engine_version,
+                custom_version_format,
+                custom_versions,
+                ..
+            } => (
+                *save_game_file_version,
+                Some(package_file_version_ue5),
+                engine_version,
+                custom_version_format,
+                custom_versions,
+            ),
+        };
+
+    let custom_format = custom_versions
+        .iter()
+        .map(|(id, value)| json!({ "id": guid_to_value(id), "value": value }))
+        .collect::<Vec<_>>();
+    Ok(json!({
+        "magic": FILE_TYPE_GVAS,
+        "save_game_version": save_game_version,
+        "package_version": {
+            "ue4": header.get_package_version().ue4,
+            "ue5": ue5,
+        },
+        "engine_version_major": engine_version.major,
+        "engine_version_minor": engine_version.minor,
+        "engine_version_patch": engine_version.patch,
+        "engine_version_build": engine_version.change_list,
+        "engine_version": engine_version.branch,
+        "custom_format_version": custom_version_format,
+        "custom_format": custom_format,
+    }))
+}
+
+fn header_from_value(value: &Value, save_game_class_name: String) -> Result<GvasHeader, Error> {
+    let path = "header";
+    if number::<u32>(path, field(path, value, "magic")?)? != FILE_TYPE_GVAS {
+        Err(invalid(path, "Invalid magic"))?
+    }
+    let package_version = field(path, value, "package_version")?;
+    let package_file_version = number(path, field(path, package_version, "ue4")?)?;
+    let ue5 = match package_version.get("ue5") {
+        None | Some(Value::Null) => None,
+        Some(ue5) => Some(number::<u32>(path, ue5)?),
+    };
+
+    let engine_version = FEngineVersion {
+        major: number(path, field(path, value, "engine_version_major")?)?,
+        minor: number(path, field(path, value, "engine_version_minor")?)?,
+        patch: number(path, field(path, value, "engine_version_patch")?)?,
+        change_list: number(path, field(path, value, "engine_version_build")?)?,
+        branch: string(path, field(path, value, "engine_version")?)?,
+    };
+    let custom_version_format = number(path, field(path, value, "custom_format_version")?)?;
+    let mut custom_versions = HashableIndexMap::new();
+    for custom_version in array(path, field(path, value, "custom_format")?)? {
+        custom_versions.insert(
+            guid(path, field(path, custom_version, "id")?)?,
+            number(path, field(path, custom_version, "value")?)?,
+        );
+    }
+
+    let save_game_version = number::<u32>(path, field(path, value, "save_game_version")?)?;
+    Ok(match (save_game_version, ue5) {
+        (2, None) => GvasHeader::Version2 {
+            package_file_version,
+            engine_version,
+            custom_version_format,
+            custom_versions,
+            custom_version_names: HashableIndexMap::new(),
+            save_game_class_name,
+        },
+        (save_game_file_version, Some(package_file_version_ue5)) if save_game_file_version >= 3 => {
+            GvasHeader::Version3 {
+                save_game_file_version,
+                package_file_version,
+                package_file_version_ue5,
+                engine_version,
+                custom_version_format,
+                custom_versions,
+                custom_version_names: HashableIndexMap::new(),
+                save_game_class_name,
+            }
+        }
+        _ => Err(invalid(
+            path,
+            format!("Unsupported save game version {save_game_version} with UE5 version {ue5:?}"),
+        ))?,
+    })
+}
+
+fn properties_to_value<'a>(
+    path: &str,
+    properties: impl IntoIterator<Item = (&'a String, &'a Property)>,
+) -> Result<Value, Error> {
+    let mut map = Map::new();
+    for (name, property) in properties {
+        let path = join(path, name);
+        map.insert(name.clone(), property_to_value(&path, property)?);
+    }
+    Ok(Value::Object(map))
+}
+
+/// Converts a property stored with its tag.
+fn property_to_value(path: &str, property: &Property) -> Result<Value, Error> {
+    let (tag, value) = match property {
+        Property::Int8Property(property) => ("Int8", json!({ "value": property.value })),
+        Property::Int16Property(property) => ("Int16", json!({ "value": property.value })),
+        Property::IntProperty(property) => ("Int", json!({ "value": property.value })),
+        Property::Int64Property(property) => ("Int64", json!({ "value": property.value })),
+        Property::UInt16Property(property) => ("UInt16", json!({ "value": property.value })),
+        Property::UInt32Property(property) => ("UInt32", json!({ "value": property.value })),
+        Property::UInt64Property(property) => ("UInt64", json!({ "value": property.value })),
+        Property::FloatProperty(property) => ("Float", json!({ "value": property.value.0 })),
+        Property::DoubleProperty(property) => ("Double", json!({ "value": property.value.0 })),
+        Property::BoolProperty(property) => ("Bool", json!({ "value": property.value })),
+        Property::ByteProperty(property) => (
+            "Byte",
+            json!({ "value": byte_to_value(&property.value), "enum_type": property.name }),
+        ),
+        Property::EnumProperty(property) => (
+            "Enum",
+            json!({ "value": property.value, "enum_type": property.enum_type }),
+        ),
+        Property::StrProperty(property) => ("Str", json!({ "value": property.value })),
+        Property::NameProperty(property) => {
+            if property.array_index != 0 {
+                Err(invalid(path, "Name with an array index"))?
+            }
+            ("Name", json!({ "value": property.value.as_deref() }))
+        }
+        Property::ObjectProperty(property) => ("Object", json!({ "value": &*property.value })),
+        Property::StructProperty(property) => (
+            "Struct",
+            json!({
+                "value": struct_to_value(path, &property.value)?,
+                "struct_type": struct_type_to_value(&property.type_name),
+                "struct_id": guid_to_value(&property.guid),
+            }),
+        ),
+        Property::ArrayProperty(property) => (
+            "Array",
+            json!({
+                "array_type": array_type(property),
+                "value": array_to_value(path, property)?,
+            }),
+        ),
+        Property::SetProperty(property) => {
+            if property.allocation_flags != 0 {
+                Err(invalid(path, "Set with allocation flags"))?
+            }
+            let value = match property.property_type.as_str() {
+                "StructProperty" => {
+                    json!({ "Struct": struct_elements(path, &property.properties)? })
+                }
+                property_type => json!({
+                    "Base": value_vec(path, property_type, &property.properties)?,
+                }),
+            };
+            (
+                "Set",
+                json!({ "set_type": property.property_type, "value": value }),
+            )
+        }
+        Property::MapProperty(property) => ("Map", map_to_value(path, property)?),
+        property => Err(invalid(
+            path,
+            format!(
+                "{} has no uesave representation",
+                property_type_name(property)
+            ),
+        ))?,
+    };
+    Ok(json!({ tag: value }))
+}
+
+/// Converts a property stored without its tag, in an array, set or map.
+fn element_to_value(path: &str, property: &Property) -> Result<Value, Error> {
+    let (tag, value) = element_inner(path, property)?;
+    Ok(json!({ tag: value }))
+}
+
+fn element_inner(path: &str, property: &Property) -> Result<(&'static str, Value), Error> {
+    Ok(match property {
+        Property::Int8Property(property) => ("Int8", json!(property.value)),
+        Property::Int16Property(property) => ("Int16", json!(property.value)),
+        Property::IntProperty(property) => ("Int", json!(property.value)),
+        Property::Int64Property(property) => ("Int64", json!(property.value)),
+        Property::UInt16Property(property) => ("UInt16", json!(property.value)),
+        Property::UInt32Property(property) => ("UInt32", json!(property.value)),
+        Property::UInt64Property(property) => ("UInt64", json!(property.value)),
+        Property::FloatProperty(property) => ("Float", json!(property.value.0)),
+        Property::DoubleProperty(property) => ("Double", json!(property.value.0)),
+        Property::BoolProperty(property) => ("Bool", json!(property.value)),
+        Property::ByteProperty(property) => ("Byte", byte_to_value(&property.value)),
+        Property::EnumProperty(property) => ("Enum", json!(property.value)),
+        Property::StrProperty(property) => ("Str", json!(property.value)),
+        Property::NameProperty(property) => ("Name", json!(property.value.as_deref())),
+        Property::ObjectProperty(property) => ("Object", json!(&*property.value)),
+        Property::StructPropertyValue(value) => ("Struct", struct_to_value(path, value)?),
+        Property::StructProperty(property) => ("Struct", struct_to_value(path, &property.value)?),
+        property => Err(invalid(
+            path,
+            format!(
+                "{} has no uesave representation",
+                property_type_name(property)
+            ),
+        ))?,
+    })
+}
+
+fn byte_to_value(value: &BytePropertyValue) -> Value {
+    match value {
+        BytePropertyValue::Byte(byte) => json!({ "Byte": byte }),
+        BytePropertyValue::Namespaced(label) => json!({ "Label": label }),
+    }
+}
+
+/// Converts the elements of an array or set of `property_type` other than structs.
+fn value_vec(path: &str, property_type: &str, properties: &[Property]) -> Result<Value, Error> {
+    let tag = type_tag(property_type);
+    if tag == "Byte" {
+        let bytes = properties
+            .iter()
+            .map(|property| match property {
+                Property::ByteProperty(ByteProperty {
+                    value: BytePropertyValue::Byte(byte),
+                    ..
+                }) => Some(*byte),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        let labels = properties
+            .iter()
+            .map(|property| match property {
+                Property::ByteProperty(ByteProperty {
+                    value: BytePropertyValue::Namespaced(label),
+                    ..
+                }) => Some(label.as_str()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        return match (bytes, labels) {
+            (Some(bytes), _) => Ok(json!({ "Byte": { "Byte": bytes } })),
+            (None, Some(labels)) => Ok(json!({ "Byte": { "Label": labels } })),
+            (None, None) => Err(invalid(path, "Byte elements mix bytes and labels")),
+        };
+    }
+
+    let mut values = Vec::with_capacity(properties.len());
+    for (index, property) in properties.iter().enumerate() {
+        let path = format!("{path}[{index}]");
+        let (element_tag, value) = element_inner(&path, property)?;
+        if element_tag != tag {
+            Err(invalid(
+                &path,
+                format!("{element_tag} element in a {property_type} container"),
+            ))?
+        }
+        values.push(value);
+    }
+    Ok(json!({ tag: values }))
+}
+
+fn struct_elements(path: &str, properties: &[Property]) -> Result<Vec<Value>, Error> {
+    properties
+        .iter()
+        .enumerate()
+        .map(|(index, property)| match property {
+            Property::StructPropertyValue(value) => {
+                struct_to_value(&format!("{path}[{index}]"), value)
+            }
+            Property::StructProperty(property) => {
+                struct_to_value(&format!("{path}[{index}]"), &property.value)
+            }
+            property => Err(invalid(
+                &format!("{path}[{index}]"),
+                format!(
+                    "{} element in a struct container",
+                    property_type_name(property)
+                ),
+            )),
+        })
+        .collect()
+}
+
+fn array_type(array: &ArrayProperty) -> &str {
+    match array {
+        ArrayProperty::Bools { .. } | ArrayProperty::WideBools { .. } => "BoolProperty",
+        ArrayProperty::Bytes { .. } => "ByteProperty",
+        ArrayProperty::Enums { .. } => "EnumProperty",
+        ArrayProperty::Floats { .. } => "FloatProperty",
+        ArrayProperty::Ints { .. } => "IntProperty",
+        ArrayProperty::Names { .. } => "NameProperty",
+        ArrayProperty::Strings { .. } => "StrProperty",
+        ArrayProperty::Structs { .. } | ArrayProperty::SharedStructs { .. } => "StructProperty",
+        ArrayProperty::Properties { property_type, .. } => property_type,
+    }
+}
+
+fn array_to_value(path: &str, array: &ArrayProperty) -> Result<Value, Error> {
+    let base = match array {
+        ArrayProperty::Bools { bools } => json!({ "Bool": bools }),
+        ArrayProperty::WideBools { .. } => Err(invalid(path, "Bool array with wide elements"))?,
+        ArrayProperty::Bytes { bytes } => json!({ "Byte": { "Byte": bytes.as_ref() } }),
+        ArrayProperty::Enums { enums } => json!({ "Enum": enums }),
+        ArrayProperty::Floats { floats } => {
+            json!({ "Float": floats.iter().map(|float| float.0).collect::<Vec<_>>() })
+        }
+        ArrayProperty::Ints { ints } => json!({ "Int": ints }),
+        ArrayProperty::Names { names } => json!({ "Name": names }),
+        ArrayProperty::Strings { strings } => json!({ "Str": strings }),
+        ArrayProperty::Structs {
+            field_name,
+            type_name,
+            guid,
+            structs,
+        } => {
+            let structs = structs
+                .iter()
+                .enumerate()
+                .map(|(index, value)| struct_to_value(&format!("{path}[{index}]"), value))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(json!({
+                "Struct": {
+                    "_type": "StructProperty",
+                    "name": field_name,
+                    "struct_type": struct_type_to_value(type_name),
+                    "id": guid_to_value(guid),
+                    "value": structs,
+                }
+            }));
+        }
+        ArrayProperty::SharedStructs { .. } => {
+            return array_to_value(path, &array.clone().unshare_struct_schema());
+        }
+        ArrayProperty::Properties {
+            property_type,
+            properties,
+        } => value_vec(path, property_type, properties)?,
+    };
+    Ok(json!({ "Base": base }))
+}
+
+fn map_to_value(path: &str, map: &MapProperty) -> Result<Value, Error> {
+    fn entries<'a, K: 'a, V: 'a>(
+        map: impl IntoIterator<Item = (&'a K, &'a V)>,
+        mut convert: impl FnMut(&'a K, &'a V) -> Result<(Value, Value), Error>,
+    ) -> Result<Vec<Value>, Error> {
+        map.into_iter()
+            .map(|(key, value)| {
+                let (key, value) = convert(key, value)?;
+                Ok(json!({ "key": key, "value": value }))
+            })
+            .collect()
+    }
+
+    let (key_type, value_type, entries) = match map {
+        MapProperty::EnumBool { enum_bools } => (
+            "EnumProperty",
+            "BoolProperty",
+            entries(enum_bools.iter(), |key, value| {
+                Ok((json!({ "Enum": key }), json!({ "Bool": value })))
+            })?,
+        ),
+        MapProperty::EnumInt { enum_ints } => (
+            "EnumProperty",
+            "IntProperty",
+            entries(enum_ints.iter(), |key, value| {
+                Ok((json!({ "Enum": key }), json!({ "Int": value })))
+            })?,
+        ),
+        MapProperty::EnumProperty {
+            value_type,
+            enum_props,
+        } => (
+            "EnumProperty",
+            value_type.as_str(),
+            entries(enum_props.iter(), |key, value| {
+                let value = element_to_value(&format!("{path}[{key}]"), value)?;
+                Ok((json!({ "Enum": key }), value))
+            })?,
+        ),
+        MapProperty::NameBool { name_bools } => (
+            "NameProperty",
+            "BoolProperty",
+            entries(name_bools.iter(), |key, value| {
+                Ok((json!({ "Name": key }), json!({ "Bool": value })))
+            })?,
+        ),
+        MapProperty::NameInt { name_ints } => (
+            "NameProperty",
+            "IntProperty",
+            entries(name_ints.iter(), |key, value| {
+                Ok((json!({ "Name": key }), json!({ "Int": value })))
+            })?,
+        ),
+        MapProperty::NameProperty {
+            value_type,
+            name_props,
+        } => (
+            "NameProperty",
+            value_type.as_str(),
+            entries(name_props.iter(), |key, value| {
+                let value = element_to_value(&format!("{path}[{key}]"), value)?;
+                Ok((json!({ "Name": key }), value))
+            })?,
+        ),
+        MapProperty::StrBool { str_bools } => (
+            "StrProperty",
+            "BoolProperty",
+            entries(str_bools.iter(), |key, value| {
+                Ok((json!({ "Str": key }), json!({ "Bool": value })))
+            })?,
+        ),
+        MapProperty::StrInt { str_ints } => (
+            "StrProperty",
+            "IntProperty",
+            entries(str_ints.iter(), |key, value| {
+                Ok((json!({ "Str": key }), json!({ "Int": value })))
+            })?,
+        ),
+        MapProperty::StrProperty {
+            value_type,
+            str_props,
+        } => (
+            "StrProperty",
+            value_type.as_str(),
+            entries(str_props.iter(), |key, value| {
+                let value = element_to_value(&format!("{path}[{key}]"), value)?;
+                Ok((json!({ "Str": key }), value))
+            })?,
+        ),
+        MapProperty::StrStr { str_strs } => (
+            "StrProperty",
+            "StrProperty",
+            entries(str_strs.iter(), |key, value| {
+                Ok((json!({ "Str": key }), json!({ "Str": value })))
+            })?,
+        ),
+        MapProperty::Properties {
+            key_type,
+            value_type,
+            allocation_flags,
+            value,
+            duplicates,
+        } => {
+            if *allocation_flags != 0 {
+                Err(invalid(path, "Map with allocation flags"))?
+            }
+            let ordered = OrderedEntries::new(value, duplicates);
+            let entries = entries(ordered, |key, value| {
+                let path = format!("{path}[]");
+                Ok((
+                    element_to_value(&path, key)?,
+                    element_to_value(&path, value)?,
+                ))
+            })?;
+            (key_type.as_str(), value_type.as_str(), entries)
+        }
+    };
+    Ok(json!({ "key_type": key_type, "value_type": value_type, "value": entries }))
+}
+
+fn struct_to_value(path: &str, value: &StructPropertyValue) -> Result<Value, Error> {
+    let (tag, value) = match value {
+        StructPropertyValue::Vector2F(v) => ("Vector2D", json!({ "x": v.x.0, "y": v.y.0 })),
+        StructPropertyValue::Vector2D(v) => ("Vector2D", json!({ "x": v.x.0, "y": v.y.0 })),
+        StructPropertyValue::VectorF(v) => {
+            ("Vector", json!({ "x": v.x.0, "y": v.y.0, "z": v.z.0 }))
+        }
+        StructPropertyValue::VectorD(v) => {
+            ("Vector", json!({ "x": v.x.0, "y": v.y.0, "z": v.z.0 }))
+        }
+        StructPropertyValue::RotatorF(v) => (
+            "Rotator",
+            json!({ "x": v.pitch.0, "y": v.yaw.0, "z": v.roll.0 }),
+        ),
+        StructPropertyValue::RotatorD(v) => (
+            "Rotator",
+            json!({ "x": v.pitch.0, "y": v.yaw.0, "z": v.roll.0 }),
+        ),
+        StructPropertyValue::QuatF(v) => (
+            "Quat",
+            json!({ "x": v.x.0, "y": v.y.0, "z": v.z.0, "w": v.w.0 }),
+        ),
+        StructPropertyValue::QuatD(v) => (
+            "Quat",
+            json!({ "x": v.x.0, "y": v.y.0, "z": v.z.0, "w": v.w.0 }),
+        ),
+        StructPropertyValue::DateTime(v) => ("DateTime", json!(v.ticks)),
+        StructPropertyValue::Timespan(v) => ("Timespan", json!(v.ticks)),
+        StructPropertyValue::Guid(guid) => ("Guid", guid_to_value(guid)),
+        StructPropertyValue::LinearColor(v) => (
+            "LinearColor",
+            json!({ "r": v.r.0, "g": v.g.0, "b": v.b.0, "a": v.a.0 }),
+        ),
+        StructPropertyValue::IntPoint(v) => ("IntPoint", json!({ "x": v.x, "y": v.y })),
+        StructPropertyValue::IntVector(v) => ("IntVector", json!({ "x": v.x, "y": v.y, "z": v.z })),
+        StructPropertyValue::CustomStruct(fields) => {
+            let mut properties = Vec::with_capacity(fields.len());
+            for (name, values) in fields.iter() {
+                match values.as_slice() {
+                    [property] => properties.push((name, property)),
+                    _ => Err(invalid(
+                        &join(path, name),
+                        "Struct field with an array index",
+                    ))?,
+                }
+            }
+            ("Struct", properties_to_value(path, properties)?)
+        }
+        value => Err(invalid(
+            path,
+            format!("{value:?} has no uesave representation"),
+        ))?,
+    };
+    Ok(json!({ tag: value }))
+}
+
+fn struct_type_to_value(type_name: &str) -> Value {
+    match STRUCT_TYPES.contains(&type_name) {
+        true => json!(type_name),
+        false => json!({ "Struct": type_name }),
+    }
+}
+
+/// Reads properties from a uesave document.
+struct Reader {
+    /// Vectors, rotators and quaternions have double precision components.
+    large_world_coordinates: bool,
+}
+
+impl Reader {
+    fn properties(
+        &self,
+        path: &str,
+        value: &Value,
+    ) -> Result<HashableIndexMap<String, Property>, Error> {
+        let Value::Object(map) = value else {
+            Err(invalid(path, "Expected an object of properties"))?
+        };
+        let mut properties = HashableIndexMap::new();
+        for (name, value) in map {
+            let path = join(path, name);
+            properties.insert(name.clone(), self.property(&path, value)?);
+        }
+        Ok(properties)
+    }
+
+    /// Reads a property stored with its tag.
+    fn property(&self, path: &str, value: &Value) -> Result<Property, Error> {
+        let (tag, value) = tagged(path, value)?;
+        let inner = field(path, value, "value")?;
+        Ok(match tag {
+            "Byte" => Property::from(ByteProperty::new(
+                optional_string(path, value.get("enum_type").unwrap_or(&Value::Null))?,
+                byte(path, inner)?,
+            )),
+            "Enum" => Property::from(EnumProperty::new(
+                optional_string(path, value.get("enum_type").unwrap_or(&Value::Null))?,
+                string(path, inner)?,
+            )),
+            "Struct" => Property::from(StructProperty::new(
+                match value.get("struct_id") {
+                    Some(id) => guid(path, id)?,
+                    None => Guid::default(),
+                },
+                struct_type(path, field(path, value, "struct_type")?)?,
+                self.struct_value(path, inner)?,
+            )),
+            "Array" => {
+                let array_type = string(path, field(path, value, "array_type")?)?;
+                Property::from(self.array(path, array_type, inner)?)
+            }
+            "Set" => {
+                let set_type = string(path, field(path, value, "set_type")?)?;
+                let (tag, elements) = tagged(path, inner)?;
+                let properties = match tag {
+                    "Base" => self.value_vec(path, elements)?,
+                    "Struct" => self.struct_elements(path, elements)?,
+                    tag => Err(invalid(path, format!("Unknown set value {tag}")))?,
+                };
+                Property::from(SetProperty::new(set_type, 0, properties))
+            }
+            "Map" => Property::from(self.map(path, value)?),
+            tag => self.element(path, tag, inner)?,
+        })
+    }
+
+    /// Reads a property stored without its tag, from its uesave type and value.
+    fn element(&self, path: &str, tag: &str, value: &Value) -> Result<Property, Error> {
+        Ok(match tag {
+            "Int8" => Property::from(Int8Property::new(number(path, value)?)),
+            "Int16" => Property::from(Int16Property::new(number(path, value)?)),
+            "Int" => Property::from(IntProperty::new(number(path, value)?)),
+            "Int64" => Property::from(Int64Property::new(number(path, value)?)),
+            "UInt16" => Property::from(UInt16Property::new(number(path, value)?)),
+            "UInt32" => Property::from(UInt32Property::new(number(path, value)?)),
+            "UInt64" => Property::from(UInt64Property::new(number(path, value)?)),
+            "Float" => Property::from(FloatProperty::new(float(path, value)? as f32)),
+            "Double" => Property::from(DoubleProperty::new(float(path, value)?)),
+            "Bool" => Property::from(BoolProperty::new(boolean(path, value)?)),
+            "Byte" => Property::from(ByteProperty::new(None, byte(path, value)?)),
+            "Enum" => Property::from(EnumProperty::new(None, string(path, value)?)),
+            "Str" => Property::from(StrProperty::new(optional_string(path, value)?)),
+            "Name" => Property::from(NameProperty::from(optional_string(path, value)?)),
+            "Object" => Property::from(ObjectProperty::new(string(path, value)?)),
+            "Struct" => Property::from(self.struct_value(path, value)?),
+            tag => Err(invalid(path, format!("Unsupported property {tag}")))?,
+        })
+    }
+
+    /// Reads an element of a map from its tagged value.
+    fn tagged_element(&self, path: &str, value: &Value) -> Result<Property, Error> {
+        let (tag, value) = tagged(path, value)?;
+        self.element(path, tag, value)
+    }
+
+    /// Reads the elements of an array or set other than structs.
+    fn value_vec(&self, path: &str, value: &Value) -> Result<Vec<Property>, Error> {
+        let (tag, elements) = tagged(path, value)?;
+        if tag == "Byte" {
+            let (byte_tag, elements) = tagged(path, elements)?;
+            return array(path, elements)?
+                .iter()
+                .map(|element| {
+                    byte(path, &json!({ byte_tag: element }))
+                        .map(|value| Property::from(ByteProperty::new(None, value)))
+                })
+                .collect();
+        }
+        array(path, elements)?
+            .iter()
+            .enumerate()
+            .map(|(index, element)| self.element(&format!("{path}[{index}]"), tag, element))
+            .collect()
+    }
+
+    fn struct_elements(&self, path: &str, value: &Value) -> Result<Vec<Property>, Error> {
+        array(path, value)?
+            .iter()
+            .enumerate()
+            .map(|(index, element)| {
+                let value = self.struct_value(&format!("{path}[{index}]"), element)?;
+                Ok(Property::from(value))
+            })
+            .collect()
+    }
+
+    fn array(&self, path: &str, array_type: String, value: &Value) -> Result<ArrayProperty, Error> {
+        let (tag, value) = tagged(path, value)?;
+        match tag {
+            "Base" => ArrayProperty::new(array_type, None, self.value_vec(path, value)?),
+            "Struct" => {
+                let structs = array(path, field(path, value, "value")?)?
+                    .iter()
+                    .enumerate()
+                    .map(|(index, element)| self.struct_value(&format!("{path}[{index}]"), element))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(ArrayProperty::Structs {
+                    field_name: string(path, field(path, value, "name")?)?,
+                    type_name: struct_type(path, field(path, value, "struct_type")?)?,
+                    guid: match value.get("id") {
+                        Some(id) => guid(path, id)?,
+                        None => Guid::default(),
+                    },
+                    structs,
+                })
+            }
+            tag => Err(invalid(path, format!("Unknown array value {tag}"))),
+        }
+    }
+
+    fn map(&self, path: &str, value: &Value) -> Result<MapProperty, Error> {
+        let key_type = string(path, field(path, value, "key_type")?)?;
+        let value_type = string(path, field(path, value, "value_type")?)?;
+        let mut entries = HashableIndexMap::new();
+        let mut duplicates = Vec::new();
+        for entry in array(path, field(path, value, "value")?)? {
+            let path = format!("{path}[]");
+            let key = self.tagged_element(&path, field(&path, entry, "key")?)?;
+            let value = self.tagged_element(&path, field(&path, entry, "value")?)?;
+            match entries.contains_key(&key) {
+                true => duplicates.push(MapDuplicate {
+                    position: entries.len() + duplicates.len(),
+                    key,
+                    value,
+                }),
+                false => {
+                    entries.insert(key, value);
+                }
+            }
+        }
+        Ok(match duplicates.is_empty() {
+            true => MapProperty::new(key_type, value_type, 0, entries),
+            false => MapProperty::Properties {
+                key_type,
+                value_type,
+                allocation_flags: 0,
+                value: entries,
+                duplicates,
+            },
+        })
+    }
+
+    fn struct_value(&self, path: &str, value: &Value) -> Result<StructPropertyValue, Error> {
+        let (tag, value) = tagged(path, value)?;
+        let component = |name: &str| float(path, field(path, value, name)?);
+        let int = |name: &str| number::<i32>(path, field(path, value, name)?);
+        let double = self.large_world_coordinates;
+        Ok(match tag {
+            "Vector2D" if double => {
+                StructPropertyValue::from(Vector2D::new(component("x")?, component("y")?))
+            }
+            "Vector2D" => StructPropertyValue::from(Vector2F::new(
+                component("x")? as f32,
+                component("y")? as f32,
+            )),
+            "Vector" if double => StructPropertyValue::from(VectorD::new(
+                component("x")?,
+                component("y")?,
+                component("z")?,
+            )),
+            "Vector" => StructPropertyValue::from(VectorF::new(
+                component("x")? as f32,
+                component("y")? as f32,
+                component("z")? as f32,
+            )),
+            "Rotator" if double => StructPropertyValue::from(RotatorD::new(
+                component("x")?,
+                component("y")?,
+                component("z")?,
+            )),
+            "Rotator" => StructPropertyValue::from(RotatorF::new(
+                component("x")? as f32,
+                component("y")? as f32,
+                component("z")? as f32,
+            )),
+            "Quat" if double => StructPropertyValue::from(QuatD::new(
+                component("x")?,
+                component("y")?,
+                component("z")?,
+                component("w")?,
+            )),
+            "Quat" => StructPropertyValue::from(QuatF::new(
+                component("x")? as f32,
+                component("y")? as f32,
+                component("z")? as f32,
+                component("w")? as f32,
+            )),
+            "DateTime" => StructPropertyValue::from(DateTime::new(number(path, value)?)),
+            "Timespan" => StructPropertyValue::from(Timespan::new(number(path, value)?)),
+            "Guid" => StructPropertyValue::from(guid(path, value)?),
+            "LinearColor" => StructPropertyValue::from(LinearColor::new(
+                component("r")? as f32,
+                component("g")? as f32,
+                component("b")? as f32,
+                component("a")? as f32,
+            )),
+            "IntPoint" => StructPropertyValue::from(IntPoint::new(int("x")?, int("y")?)),
+            "IntVector" => {
+                StructPropertyValue::from(IntVector::new(int("x")?, int("y")?, int("z")?))
+            }
+            "Struct" => StructPropertyValue::CustomStruct(HashableIndexMap(
+                self.properties(path, value)?
+                    .0
+                    .into_iter()
+                    .map(|(name, property)| (name, vec![property]))
+                    .collect(),
+            )),
+            tag => Err(invalid(path, format!("Unsupported struct {tag}")))?,
+        })
+    }
+}
+
+fn struct_type(path: &str, value: &Value) -> Result<String, Error> {
+    match value {
+        Value::String(type_name) => Ok(type_name.clone()),
+        value => string(path, field(path, value, "Struct")?),
+    }
+}
+
+/// Returns the uesave name of a property type, `IntProperty` is `Int`.
+fn type_tag(property_type: &str) -> &str {
+    property_type
+        .strip_suffix("Property")
+        .unwrap_or(property_type)
+}
+
+/// Returns the type name of the properties uesave has no representation for.
+fn property_type_name(property: &Property) -> &str {
+    match property {
+        Property::TextProperty(_) => "TextProperty",
+        Property::DelegateProperty(_) => "DelegateProperty",
+        Property::MulticastInlineDelegateProperty(_) => "MulticastInlineDelegateProperty",
+        Property::MulticastSparseDelegateProperty(_) => "MulticastSparseDelegateProperty",
+        Property::FieldPathProperty(_) => "FieldPathProperty",
+        Property::StructPropertyValue(_) => "Struct value",
+        Property::UnknownProperty(property) => property.property_name(),
+        _ => "Property",
+    }
+}
+
+fn guid_to_value(guid: &Guid) -> Value {
+    let bytes = u128::from_be_bytes(guid.0);
+    let hex = format!("{bytes:032x}");
+    json!(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
+
+fn join(path: &str, name: &str) -> String {
+    match path.is_empty() {
+        true => name.to_string(),
+        false => format!("{path}.{name}"),
+    }
+}
+
+fn invalid(path: &str, message: impl Display) -> Error {
+    let message = match path.is_empty() {
+        true => message.to_string(),
+        false => format!("{message} at {path}"),
+    };
+    SerializeError::invalid_value(message).into()
+}
+
+fn field<'a>(path: &str, value: &'a Value, name: &str) -> Result<&'a Value, Error> {
+    value
+        .get(name)
+        .ok_or_else(|| invalid(path, format!("Missing field {name}")))
+}
+
+/// Splits an externally tagged value like `{"Int": 1}` into its tag and value.
+fn tagged<'a>(path: &str, value: &'a Value) -> Result<(&'a str, &'a Value), Error> {
+    match value.as_object().map(|map| (map.len(), map.iter().next())) {
+        Some((1, Some((tag, value)))) => Ok((tag.as_str(), value)),
+        _ => Err(invalid(path, "Expected an object with a single key")),
+    }
+}
+
+fn array<'a>(path: &str, value: &'a Value) -> Result<&'a Vec<Value>, Error> {
+    value
+        .as_array()
+        .ok_or_else(|| invalid(path, "Expected an array"))
+}
+
+fn string(path: &str, value: &Value) -> Result<String, Error> {
+    value
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| invalid(path, "Expected a string"))
+}
+
+fn optional_string(path: &str, value: &Value) -> Result<Option<String>, Error> {
+    match value {
+        Value::Null => Ok(None),
+        value => string(path, value).map(Some),
+    }
+}
+
+fn boolean(path: &str, value: &Value) -> Result<bool, Error> {
+    value
+        .as_bool()
+        .ok_or_else(|| invalid(path, "Expected a boolean"))
+}
+
+fn number<T: TryFrom<i128>>(path: &str, value: &Value) -> Result<T, Error> {
+    value
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| value.as_u64().map(i128::from))
+        .and_then(|number| T::try_from(number).ok())
+        .ok_or_else(|| invalid(path, format!("Expected an integer in range, got {value}")))
+}
+
+fn float(path: &str, value: &Value) -> Result<f64, Error> {
+    value
+        .as_f64()
+        .ok_or_else(|| invalid(path, "Expected a number"))
+}
+
+fn byte(path: &str, value: &Value) -> Result<BytePropertyValue, Error> {
+    match tagged(path, value)? {
+        ("Byte", byte) => Ok(BytePropertyValue::Byte(number(path, byte)?)),
+        ("Label", label) => Ok(BytePropertyValue::Namespaced(string(path, label)?)),
+        (tag, _) => Err(invalid(path, format!("Unknown byte value {tag}"))),
+    }
+}
+
+fn guid(path: &str, value: &Value) -> Result<Guid, Error> {
+    string(path, value)?
+        .parse()
+        .map_err(|_| invalid(path, "Invalid GUID"))
+}
@@ -0,0 +1,329 @@
+//! Visitors over property trees
+//!
+//! A [`Visitor`] is called for every property and struct value below a property, together with
+//! its path. Paths are `.` separated field names like the ones used by
+//! [`GvasFile::get_property_by_path`], with `[index]` appended for the elements of arrays, sets
+//! and maps, e.g. `Inventory[2].Count`.
+//!
+//! [`GvasFile::get_property_by_path`]: crate::GvasFile::get_property_by_path
+
+use crate::properties::{
+    array_property::ArrayProperty, map_property::MapProperty, set_property::SetProperty,
+    struct_property::StructPropertyValue, Property,
+};
+
+/// Visits a property tree, see [`walk`].
+pub trait Visitor {
+    /// Called for every property, before its children are visited.
+    ///
+    /// Returns false to skip the children of this property.
+    #[inline]
+    fn visit_property(&mut self, path: &str, property: &Property) -> bool {
+        let _ = (path, property);
+        true
+    }
+
+    /// Called for every struct value, including the elements of struct arrays.
+    ///
+    /// Returns false to skip the fields of this struct.
+    #[inline]
+    fn visit_struct(&mut self, path: &str, value: &StructPropertyValue) -> bool {
+        let _ = (path, value);
+        true
+    }
+}
+
+/// Visits a mutable property tree, see [`walk_mut`].
+pub trait VisitorMut {
+    /// Called for every property, before its children are visited.
+    ///
+    /// Returns false to skip the children of this property.
+    #[inline]
+    fn visit_property(&mut self, path: &str, property: &mut Property) -> bool {
+        let _ = (path, property);
+        true
+    }
+
+    /// Called for every struct value, including the elements of struct arrays.
+    ///
+    /// Returns false to skip the fields of this struct.
+    #[inline]
+    fn visit_struct(&mut self, path: &str, value: &mut StructPropertyValue) -> bool {
+        let _ = (path, value);
+        true
+    }
+}
+
+/// A [`Visitor`] that gets references living as long as the walked tree.
+trait VisitorRef<'a> {
+    fn visit_property(&mut self, path: &str, property: &'a Property) -> bool;
+
+    fn visit_struct(&mut self, path: &str, value: &'a StructPropertyValue) -> bool;
+}
+
+/// Walks a [`Visitor`] through [`VisitorRef`].
+struct Borrowed<'v, V: ?Sized>(&'v mut V);
+
+impl<V: Visitor + ?Sized> VisitorRef<'_> for Borrowed<'_, V> {
+    #[inline]
+    fn visit_property(&mut self, path: &str, property: &Property) -> bool {
+        self.0.visit_property(path, property)
+    }
+
+    #[inline]
+    fn visit_struct(&mut self, path: &str, value: &StructPropertyValue) -> bool {
+        self.0.visit_struct(path, value)
+    }
+}
+
+/// Collects the leaves of a property tree, see [`leaves`].
+struct Leaves<'a> {
+    leaves: Vec<(String, &'a Property)>,
+}
+
+impl<'a> VisitorRef<'a> for Leaves<'a> {
+    fn visit_property(&mut self, path: &str, property: &'a Property) -> bool {
+        let container = match property {
+            Property::ArrayProperty(_) | Property::SetProperty(_) | Property::MapProperty(_) => {
+                true
+            }
+            Property::StructProperty(property) => property.value.get_custom_struct().is_some(),
+            Property::StructPropertyValue(value) => value.get_custom_struct().is_some(),
+            _ => false,
+        };
+        if !container {
+            self.leaves.push((path.to_string(), property));
+        }
+        container
+    }
+
+    #[inline]
+    fn visit_struct(&mut self, _path: &str, _value: &'a StructPropertyValue) -> bool {
+        true
+    }
+}
+
+/// Every property below `property` that isn't an array, set, map or custom struct, with its
+/// path.
+///
+/// Containers are never returned, even when their elements aren't properties, like the values
+/// of [`ArrayProperty::Ints`].
+pub(crate) fn leaves<'a>(path: &str, property: &'a Property) -> Vec<(String, &'a Property)> {
+    let mut visitor = Leaves { leaves: Vec::new() };
+    walk_property(&mut visitor, &mut path.to_string(), property);
+    visitor.leaves
+}
+
+/// Collects every property of a property tree, see [`nodes`].
+struct Nodes<'a> {
+    nodes: Vec<(String, &'a Property)>,
+}
+
+impl<'a> VisitorRef<'a> for Nodes<'a> {
+    #[inline]
+    fn visit_property(&mut self, path: &str, property: &'a Property) -> bool {
+        self.nodes.push((path.to_string(), property));
+        true
+    }
+
+    #[inline]
+    fn visit_struct(&mut self, _path: &str, _value: &'a StructPropertyValue) -> bool {
+        true
+    }
+}
+
+/// `property` and every property below it with their paths, parents before their children.
+pub(crate) fn nodes<'a>(path: &str, property: &'a Property) -> Vec<(String, &'a Property)> {
+    let mut visitor = Nodes { nodes: Vec::new() };
+    walk_property(&mut visitor, &mut path.to_string(), property);
+    visitor.nodes
+}
+
+/// Walk `property` and everything nested in it, with `path` as the path of `property`.
+pub fn walk<V: Visitor + ?Sized>(visitor: &mut V, path: &str, property: &Property) {
+    walk_property(&mut Borrowed(visitor), &mut path.to_string(), property);
+}
+
+/// Walk `property` and everything nested in it, with `path` as the path of `property`.
+pub fn walk_mut<V: VisitorMut + ?Sized>(visitor: &mut V, path: &str, property: &mut Property) {
+    walk_property_mut(visitor, &mut path.to_string(), property);
+}
+
+/// Appends `[index]` to `path` while running `f`.
+#[inline]
+fn with_index<T>(path: &mut String, index: usize, f: impl FnOnce(&mut String) -> T) -> T {
+    let len = path.len();
+    path.push_str(&format!("[{index}]"));
+    let result = f(path);
+    path.truncate(len);
+    result
+}
+
+/// Appends `.name` to `path` while running `f`.
+#[inline]
+fn with_field<T>(path: &mut String, name: &str, f: impl FnOnce(&mut String) -> T) -> T {
+    let len = path.len();
+    if !path.is_empty() {
+        path.push('.');
+    }
+    path.push_str(name);
+    let result = f(path);
+    path.truncate(len);
+    result
+}
+
+fn walk_property<'a, V: VisitorRef<'a>>(
+    visitor: &mut V,
+    path: &mut String,
+    property: &'a Property,
+) {
+    if !visitor.visit_property(path, property) {
+        return;
+    }
+    match property {
+        Property::StructProperty(property) => walk_struct(visitor, path, &property.value),
+        Property::StructPropertyValue(value) => walk_struct(visitor, path, value),
+        Property::ArrayProperty(ArrayProperty::Structs { structs, .. }) => {
+            for (index, value) in structs.iter().enumerate() {
+                with_index(path, index, |path| walk_struct(visitor, path, value));
+            }
+        }
+        Property::ArrayProperty(ArrayProperty::SharedStructs { fields, values, .. }) => {
+            for (index, values) in values.iter().enumerate() {
+                with_index(path, index, |path| {
+                    for (name, property) in fields.iter().zip(values) {
+                        with_field(path, name, |path| walk_property(visitor, path, property));
+                    }
+                });
+            }
+        }
+        Property::ArrayProperty(ArrayProperty::Properties { properties, .. })
+        | Property::SetProperty(SetProperty { properties, .. }) => {
+            for (index, property) in properties.iter().enumerate() {
+                with_index(path, index, |path| walk_property(visitor, path, property));
+            }
+        }
+        Property::MapProperty(MapProperty::EnumProperty {
+            enum_props: map, ..
+        })
+        | Property::MapProperty(MapProperty::NameProperty {
+            name_props: map, ..
+        })
+        | Property::MapProperty(MapProperty::StrProperty { str_props: map, .. }) => {
+            for (index, property) in map.values().enumerate() {
+                with_index(path, index, |path| walk_property(visitor, path, property));
+            }
+        }
+        Property::MapProperty(MapProperty::Properties { value: map, .. }) => {
+            for (index, property) in map.values().enumerate() {
+                with_index(path, index, |path| walk_property(visitor, path, property));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn walk_struct<'a, V: VisitorRef<'a>>(
+    visitor: &mut V,
+    path: &mut String,
+    value: &'a StructPropertyValue,
+) {
+    if !visitor.visit_struct(path, value) {
+        return;
+    }
+    if let StructPropertyValue::CustomStruct(fields) = value {
+        for (name, properties) in fields {
+            with_field(path, name, |path| match properties.as_slice() {
+                [property] => walk_property(visitor, path, property),
+                properties => {
+                    for (index, property) in properties.iter().enumerate() {
+                        with_index(path, index, |path| walk_property(visitor, path, property));
+                    }
+                }
+            });
+        }
+    }
+}
+
+fn walk_property_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &mut String,
+    property: &mut Property,
+) {
+    if !visitor.visit_property(path, property) {
+        return;
+    }
+    match property {
+        Property::StructProperty(property) => walk_struct_mut(visitor, path, &mut property.value),
+        Property::StructPropertyValue(value) => walk_struct_mut(visitor, path, value),
+        Property::ArrayProperty(ArrayProperty::Structs { structs, .. }) => {
+            for (index, value) in structs.iter_mut().enumerate() {
+                with_index(path, index, |path| walk_struct_mut(visitor, path, value));
+            }
+        }
+        Property::ArrayProperty(ArrayProperty::SharedStructs { fields, values, .. }) => {
+            for (index, values) in values.iter_mut().enumerate() {
+                with_index(path, index, |path| {
+                    for (name, property) in fields.iter().zip(values) {
+                        with_field(path, name, |path| {
+                            walk_property_mut(visitor, path, property)
+                        });
+                    }
+                });
+            }
+        }
+        Property::ArrayProperty(ArrayProperty::Properties { properties, .. })
+        | Property::SetProperty(SetProperty { properties, .. }) => {
+            for (index, property) in properties.iter_mut().enumerate() {
+                with_index(path, index, |path| {
+                    walk_property_mut(visitor, path, property)
+                });
+            }
+        }
+        Property::MapProperty(MapProperty::EnumProperty {
+            enum_props: map, ..
+        })
+        | Property::MapProperty(MapProperty::NameProperty {
+            name_props: map, ..
+        })
+        | Property::MapProperty(MapProperty::StrProperty { str_props: map, .. }) => {
+            for (index, property) in map.values_mut().enumerate() {
+                with_index(path, index, |path| {
+                    walk_property_mut(visitor, path, property)
+                });
+            }
+        }
+        Property::MapProperty(MapProperty::Properties { value: map, .. }) => {
+            for (index, property) in map.values_mut().enumerate() {
+                with_index(path, index, |path| {
+                    walk_property_mut(visitor, path, property)
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+fn walk_struct_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &mut String,
+    value: &mut StructPropertyValue,
+) {
+    if !visitor.visit_struct(path, value) {
+        return;
+    }
+    if let StructPropertyValue::CustomStruct(fields) = value {
+        for (name, properties) in fields.iter_mut() {
+            with_field(path, name, |path| match properties.as_mut_slice() {
+                [property] => walk_property_mut(visitor, path, property),
+                properties => {
+                    for (index, property) in properties.iter_mut().enumerate() {
+                        with_index(path, index, |path| {
+                            walk_property_mut(visitor, path, property)
+                        });
+                    }
+                }
+            });
+        }
+    }
+}
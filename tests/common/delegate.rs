@@ -19,6 +19,7 @@ const DELEGATE_STR: &str =
 pub(crate) fn expected() -> GvasFile {
     GvasFile {
         deserialized_game_version: DeserializedGameVersion::Default,
+        prefix: Vec::new(),
         header: GvasHeader::Version2 {
             package_file_version: 517,
             engine_version: FEngineVersion {
@@ -211,8 +212,10 @@ pub(crate) fn expected() -> GvasFile {
                     3,
                 ),
             ]),
+            custom_version_names: HashableIndexMap::new(),
             save_game_class_name: String::from("/Script/SaveFileTest.TestSaveGame"),
         },
+        custom_version_extra: Vec::new(),
         properties: HashableIndexMap::from([
             (
                 String::from("DynamicDelegate"),
@@ -240,5 +243,7 @@ pub(crate) fn expected() -> GvasFile {
                 )),
             ),
         ]),
+        trailing_padding: Some(0),
+        trailing_bytes: Vec::new(),
     }
 }
@@ -10,6 +10,7 @@ use std::str::FromStr;
 pub(crate) fn expected() -> GvasFile {
     GvasFile {
         deserialized_game_version: DeserializedGameVersion::Default,
+        prefix: Vec::new(),
         header: GvasHeader::Version2 {
             package_file_version: 518,
             engine_version: FEngineVersion {
@@ -222,8 +223,10 @@ pub(crate) fn expected() -> GvasFile {
                     1,
                 ),
             ]),
+            custom_version_names: HashableIndexMap::new(),
             save_game_class_name: "/Game/UI/BP_SaveOptions.BP_SaveOptions_C".into(),
         },
+        custom_version_extra: Vec::new(),
         properties: HashableIndexMap::from([
             (
                 "Slider1".into(),
@@ -234,5 +237,7 @@ pub(crate) fn expected() -> GvasFile {
                 Property::from(FloatProperty::new(0.28251615)),
             ),
         ]),
+        trailing_padding: Some(0),
+        trailing_bytes: Vec::new(),
     }
 }
@@ -37,6 +37,7 @@ pub(crate) fn hints() -> HashMap<String, String> {
 pub(crate) fn expected() -> GvasFile {
     GvasFile {
         deserialized_game_version: gvas::game_version::DeserializedGameVersion::Default,
+        prefix: Vec::new(),
         header: GvasHeader::Version2 {
             package_file_version: 522,
             engine_version: FEngineVersion {
@@ -269,13 +270,15 @@ pub(crate) fn expected() -> GvasFile {
                     1,
                 ),
             ]),
+            custom_version_names: HashableIndexMap::new(),
             save_game_class_name: String::from("/Script/CD.CDSave_GameState"),
         },
+        custom_version_extra: Vec::new(),
         properties: HashableIndexMap::from([
             (
                 String::from("LastSaveTime"),
                 Property::from(StructProperty {
-                    type_name: String::from("DateTime"),
+                    type_name: "DateTime".into(),
                     guid: Guid::default(),
                     value: StructPropertyValue::from(DateTime {
                         ticks: 638160761644140000,
@@ -434,6 +437,7 @@ pub(crate) fn expected() -> GvasFile {
                             Property::from(FloatProperty::new(0f32)),
                         ),
                     ]),
+                    duplicates: vec![],
                 }),
             ),
             (
@@ -443,6 +447,8 @@ pub(crate) fn expected() -> GvasFile {
                 )),
             ),
         ]),
+        trailing_padding: Some(0),
+        trailing_bytes: Vec::new(),
     }
 }
 
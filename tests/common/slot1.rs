@@ -22,6 +22,7 @@ use std::str::FromStr;
 pub(crate) fn expected() -> GvasFile {
     GvasFile {
         deserialized_game_version: DeserializedGameVersion::Default,
+        prefix: Vec::new(),
         header: GvasHeader::Version2 {
             package_file_version: 522,
             engine_version: FEngineVersion {
@@ -262,8 +263,10 @@ pub(crate) fn expected() -> GvasFile {
                     3,
                 ),
             ]),
+            custom_version_names: HashableIndexMap::new(),
             save_game_class_name: String::from("/Script/UE4SaveFile.TestSaveGame"),
         },
+        custom_version_extra: Vec::new(),
         properties: HashableIndexMap::from([
             (
                 String::from("u8_test"),
@@ -315,7 +318,7 @@ pub(crate) fn expected() -> GvasFile {
             (
                 String::from("struct_property"),
                 Property::from(StructProperty {
-                    type_name: String::from("CustomStruct"),
+                    type_name: "CustomStruct".into(),
                     guid: Guid::default(),
                     value: StructPropertyValue::CustomStruct(HashableIndexMap::from([(
                         String::from("test_field"),
@@ -326,7 +329,7 @@ pub(crate) fn expected() -> GvasFile {
             (
                 String::from("date_time_property"),
                 Property::from(StructProperty {
-                    type_name: String::from("DateTime"),
+                    type_name: "DateTime".into(),
                     guid: Guid::default(),
                     value: StructPropertyValue::from(DateTime {
                         ticks: 637864237380020000,
@@ -368,6 +371,8 @@ pub(crate) fn expected() -> GvasFile {
                 }),
             ),
         ]),
+        trailing_padding: Some(0),
+        trailing_bytes: Vec::new(),
     }
 }
 
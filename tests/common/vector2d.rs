@@ -20,7 +20,9 @@ const DELEGATE_PREFIX: &str = "/Game/DefaultMap.DefaultMap:PersistentLevel.";
 pub(crate) fn expected() -> GvasFile {
     GvasFile {
         deserialized_game_version: DeserializedGameVersion::Default,
+        prefix: Vec::new(),
         header: GvasHeader::Version3 {
+            save_game_file_version: 3,
             package_file_version: 522,
             package_file_version_ue5: 1009,
             engine_version: FEngineVersion {
@@ -329,10 +331,12 @@ pub(crate) fn expected() -> GvasFile {
                     1,
                 ),
             ]),
+            custom_version_names: HashableIndexMap::new(),
             save_game_class_name: String::from(
                 "/Game/_Blueprints/BP_SettingsSave.BP_SettingsSave_C",
             ),
         },
+        custom_version_extra: Vec::new(),
         properties: HashableIndexMap::from([
             (
                 String::from("SettingsChanged"),
@@ -712,13 +716,14 @@ pub(crate) fn expected() -> GvasFile {
                             ),
                         ],
                     },
+                    empty_body: false,
                 }),
             ),
             (
                 String::from("AudioSettings"),
                 Property::StructProperty(StructProperty {
                     guid: Guid::default(),
-                    type_name: String::from("GameAudioSettings"),
+                    type_name: "GameAudioSettings".into(),
                     value: StructPropertyValue::CustomStruct(HashableIndexMap::from([
                         (
                             String::from("MasterLevel"),
@@ -745,7 +750,7 @@ pub(crate) fn expected() -> GvasFile {
                 String::from("GameSettings"),
                 Property::StructProperty(StructProperty {
                     guid: Guid::default(),
-                    type_name: String::from("GameSettings"),
+                    type_name: "GameSettings".into(),
                     value: StructPropertyValue::CustomStruct(HashableIndexMap::from([
                         (
                             String::from("CurrentSaveSlot"),
@@ -782,7 +787,7 @@ pub(crate) fn expected() -> GvasFile {
                         (
                             String::from("CameraAngle"),
                             vec![Property::from(StructProperty {
-                                type_name: String::from("Vector2D"),
+                                type_name: "Vector2D".into(),
                                 guid: Guid::default(),
                                 value: StructPropertyValue::Vector2D(Vector2D {
                                     x: OrderedFloat::from(30.574748247861862),
@@ -798,6 +803,8 @@ pub(crate) fn expected() -> GvasFile {
                 Property::IntProperty(IntProperty { value: 2649 }),
             ),
         ]),
+        trailing_padding: Some(0),
+        trailing_bytes: Vec::new(),
     }
 }
 
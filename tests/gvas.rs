@@ -32,8 +32,8 @@ fn test_gvas_file_(
     // Compare the two Vec<u8>s
     if match file.deserialized_game_version {
         DeserializedGameVersion::Default => true,
-        DeserializedGameVersion::Palworld(PalworldCompressionType::Zlib) => false,
-        DeserializedGameVersion::Palworld(PalworldCompressionType::ZlibTwice) => false,
+        DeserializedGameVersion::Palworld(PalworldCompressionType::Zlib, _) => false,
+        DeserializedGameVersion::Palworld(PalworldCompressionType::ZlibTwice, _) => false,
         _ => unimplemented!(),
     } {
         assert_eq!(cursor.get_ref(), writer.get_ref());
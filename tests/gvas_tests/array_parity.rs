@@ -0,0 +1,241 @@
+use std::{collections::HashMap, io::Cursor};
+
+use gvas::{
+    cursor_ext::ReadExt,
+    custom_version::{CustomVersionTrait, FEditorObjectVersion},
+    error::{Error, SerializeError},
+    object_version::PackageVersion,
+    options::{ReadOptions, WriteOptions},
+    properties::{
+        array_property::ArrayProperty,
+        enum_property::EnumProperty,
+        int_property::ByteProperty,
+        name_property::NameProperty,
+        str_property::StrProperty,
+        text_property::{FText, FTextHistory, FormatArgumentValue, TextProperty},
+        Property, PropertyOptions, PropertyTrait,
+    },
+    types::{map::HashableIndexMap, Guid},
+};
+
+/// Stores culture invariant strings of texts.
+fn custom_versions() -> HashableIndexMap<Guid, u32> {
+    HashableIndexMap::from([(
+        FEditorObjectVersion::GUID,
+        FEditorObjectVersion::CultureInvariantTextSerializationKeyStability.into(),
+    )])
+}
+
+fn write(array: &ArrayProperty) -> Result<Vec<u8>, Error> {
+    let mut writer = Cursor::new(Vec::new());
+    array.write(
+        &mut writer,
+        true,
+        &mut PropertyOptions {
+            hints: &HashMap::new(),
+            properties_stack: &mut Vec::new(),
+            custom_versions: &custom_versions(),
+            package_version: PackageVersion::default(),
+            read_options: &ReadOptions::default(),
+            write_options: &WriteOptions::default(),
+        },
+    )?;
+    Ok(writer.into_inner())
+}
+
+fn round_trip(array: &ArrayProperty) -> Property {
+    let bytes = write(array).expect("Failed to write ArrayProperty");
+    let mut reader = Cursor::new(bytes);
+    let property_type = reader.read_string().expect("Read property type");
+    assert_eq!(property_type, "ArrayProperty");
+    let property = Property::new(
+        &mut reader,
+        &property_type,
+        true,
+        &mut PropertyOptions {
+            hints: &HashMap::new(),
+            properties_stack: &mut Vec::new(),
+            custom_versions: &custom_versions(),
+            package_version: PackageVersion::default(),
+            read_options: &ReadOptions::default(),
+            write_options: &WriteOptions::default(),
+        },
+        None,
+    )
+    .expect("Failed to read ArrayProperty");
+    assert_eq!(reader.position(), reader.get_ref().len() as u64);
+    property
+}
+
+fn array(property_type: &str, properties: Vec<Property>) -> ArrayProperty {
+    ArrayProperty::new(String::from(property_type), None, properties).expect("ArrayProperty::new")
+}
+
+fn assert_round_trip(array: ArrayProperty) {
+    assert_eq!(round_trip(&array), Property::from(array));
+}
+
+fn text(history: FTextHistory) -> Property {
+    Property::from(TextProperty::new(FText { flags: 0, history }))
+}
+
+#[test]
+fn text_array() {
+    assert_round_trip(array(
+        "TextProperty",
+        vec![
+            text(FTextHistory::Empty {}),
+            text(FTextHistory::None {
+                culture_invariant_string: Some(String::from("invariant")),
+            }),
+            text(FTextHistory::Base {
+                namespace: Some(String::from("Game")),
+                key: Some(String::from("Key")),
+                source_string: Some(String::from("Source")),
+            }),
+            text(FTextHistory::OrderedFormat {
+                source_format: Box::new(FText::new_base(
+                    0,
+                    None,
+                    Some(String::from("Format")),
+                    Some(String::from("{0} of {1}")),
+                )),
+                arguments: vec![FormatArgumentValue::Int(1), FormatArgumentValue::Int(3)],
+            }),
+        ],
+    ));
+}
+
+#[test]
+fn text_array_empty() {
+    assert_round_trip(array("TextProperty", vec![]));
+}
+
+#[test]
+fn enum_array() {
+    let array = array(
+        "EnumProperty",
+        vec![
+            Property::from(EnumProperty::new(None, String::from("EColor::Red"))),
+            Property::from(EnumProperty::new(None, String::from("EColor::Green"))),
+        ],
+    );
+    assert!(matches!(array, ArrayProperty::Enums { .. }));
+    assert_round_trip(array);
+}
+
+#[test]
+fn enum_array_with_enum_type() {
+    // The enum type isn't stored for array values
+    let array = array(
+        "EnumProperty",
+        vec![
+            Property::from(EnumProperty::new(
+                Some(String::from("EColor")),
+                String::from("EColor::Red"),
+            )),
+            Property::from(EnumProperty::new(None, String::from("EColor::Blue"))),
+        ],
+    );
+    assert!(matches!(array, ArrayProperty::Properties { .. }));
+    assert_eq!(
+        round_trip(&array),
+        Property::from(ArrayProperty::Enums {
+            enums: vec![String::from("EColor::Red"), String::from("EColor::Blue")],
+        })
+    );
+}
+
+#[test]
+fn name_array() {
+    let array = array(
+        "NameProperty",
+        vec![
+            Property::from(NameProperty::from("Chest_1")),
+            Property::from(NameProperty::from(None)),
+            Property::from(NameProperty::from("None")),
+        ],
+    );
+    assert!(matches!(array, ArrayProperty::Names { .. }));
+    assert_round_trip(array);
+}
+
+#[test]
+fn name_array_with_array_index() {
+    // The array index isn't stored for array values
+    let array = array(
+        "NameProperty",
+        vec![
+            Property::from(NameProperty {
+                array_index: 2,
+                value: Some("Chest".into()),
+                empty_body: false,
+            }),
+            Property::from(NameProperty::from("Crate")),
+        ],
+    );
+    assert!(matches!(array, ArrayProperty::Properties { .. }));
+    assert_eq!(
+        round_trip(&array),
+        Property::from(ArrayProperty::Names {
+            names: vec![Some(String::from("Chest")), Some(String::from("Crate"))],
+        })
+    );
+}
+
+#[test]
+fn byte_array_with_enum_name() {
+    // The enum name isn't stored for array values
+    let array = array(
+        "ByteProperty",
+        vec![
+            Property::from(ByteProperty::new_byte(Some(String::from("EColor")), 1)),
+            Property::from(ByteProperty::new_byte(None, 2)),
+        ],
+    );
+    assert!(matches!(array, ArrayProperty::Properties { .. }));
+    assert_eq!(round_trip(&array), Property::from(vec![1u8, 2]));
+}
+
+#[test]
+fn byte_array_namespaced() {
+    assert_round_trip(array(
+        "ByteProperty",
+        vec![
+            Property::from(ByteProperty::new_namespaced(None, String::from("E::A"))),
+            Property::from(ByteProperty::new_namespaced(None, String::from("E::Long"))),
+            Property::from(ByteProperty::new_namespaced(None, String::new())),
+        ],
+    ));
+}
+
+#[test]
+fn byte_array_mixed() {
+    let array = array(
+        "ByteProperty",
+        vec![
+            Property::from(ByteProperty::new_byte(None, 1)),
+            Property::from(ByteProperty::new_namespaced(None, String::from("E::A"))),
+        ],
+    );
+    let error = write(&array).expect_err("Mixed byte array written");
+    assert!(matches!(
+        error.as_serialize(),
+        Some(SerializeError::InvalidValue(_))
+    ));
+}
+
+#[test]
+fn mismatched_element_type() {
+    for property_type in ["EnumProperty", "NameProperty", "TextProperty"] {
+        let array = ArrayProperty::Properties {
+            property_type: String::from(property_type),
+            properties: vec![Property::from(StrProperty::from("value"))],
+        };
+        let error = write(&array).expect_err("Mismatched array written");
+        assert!(
+            matches!(error.as_serialize(), Some(SerializeError::InvalidValue(_))),
+            "{property_type}: {error:?}"
+        );
+    }
+}
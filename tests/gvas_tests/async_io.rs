@@ -0,0 +1,42 @@
+#![cfg(feature = "tokio")]
+
+use std::fs;
+
+use crate::common::{PALWORLD_ZLIB_PATH, SLOT1_PATH};
+use gvas::{
+    game_version::GameVersion,
+    hints::Hints,
+    options::{ReadOptions, WriteOptions},
+    GvasFile,
+};
+
+#[tokio::test]
+async fn async_roundtrip() {
+    for (path, game_version, hints) in [
+        (SLOT1_PATH, GameVersion::Default, Hints::new()),
+        (
+            PALWORLD_ZLIB_PATH,
+            GameVersion::Palworld,
+            crate::common::palworld::hints().into(),
+        ),
+    ] {
+        let data = fs::read(path).expect("Failed to read test asset");
+        let file = GvasFile::read_async(
+            &mut data.as_slice(),
+            game_version,
+            hints,
+            &ReadOptions::default(),
+        )
+        .await
+        .expect("Failed to parse gvas file");
+
+        let mut expected = Vec::new();
+        file.write(&mut expected)
+            .expect("Failed to write gvas file");
+        let mut writer = Vec::new();
+        file.write_async(&mut writer, &WriteOptions::default())
+            .await
+            .expect("Failed to write gvas file");
+        assert_eq!(writer, expected, "{path}");
+    }
+}
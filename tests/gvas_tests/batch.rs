@@ -0,0 +1,60 @@
+use std::{fs, path::Path};
+
+use gvas::{
+    batch::{process_dir, BatchOptions},
+    error::{DeserializeError, Error, ErrorKind},
+};
+
+fn test_dir() -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("resources/test")
+}
+
+#[test]
+fn process_saves() {
+    let options = BatchOptions {
+        threads: 3,
+        extension: Some(String::from("SAV")),
+        ..Default::default()
+    };
+    let results = process_dir(test_dir(), &options, |_path, file| file.properties.len())
+        .expect("Failed to list test assets");
+
+    let mut expected = fs::read_dir(test_dir())
+        .expect("Failed to list test assets")
+        .map(|entry| entry.expect("Failed to read entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sav"))
+        .collect::<Vec<_>>();
+    expected.sort();
+    let paths = results
+        .iter()
+        .map(|result| result.path.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(paths, expected);
+
+    let result = |name: &str| {
+        &results
+            .iter()
+            .find(|result| result.path.ends_with(name))
+            .expect("Missing result")
+            .result
+    };
+    assert!(matches!(result("Slot1.sav"), Ok(16)));
+    assert!(matches!(result("palworld_zlib.sav"), Ok(3)));
+    assert!(matches!(
+        result("SaveSlot_03.sav"),
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::MissingHint(..)),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn missing_dir() {
+    let result = process_dir(
+        test_dir().join("missing"),
+        &BatchOptions::default(),
+        |_path, _file| (),
+    );
+    assert!(matches!(result, Err(Error::Io(_))));
+}
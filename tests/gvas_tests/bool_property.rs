@@ -0,0 +1,202 @@
+use std::{collections::HashMap, io::Cursor};
+
+use gvas::{
+    cursor_ext::ReadExt,
+    error::{DeserializeError, Error},
+    object_version::{
+        EUnrealEngineObjectUE4Version, EUnrealEngineObjectUE5Version, PackageVersion,
+    },
+    options::{ReadOptions, WriteOptions},
+    properties::{
+        array_property::ArrayProperty, int_property::BoolProperty, Property, PropertyOptions,
+        PropertyTrait,
+    },
+    types::map::HashableIndexMap,
+};
+
+fn round_trip(property: Property, package_version: PackageVersion, expected: &[u8]) {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version,
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+
+    let mut writer = Cursor::new(Vec::new());
+    let len = property
+        .write(&mut writer, true, &mut options)
+        .expect("Failed to write property");
+    assert_eq!(writer.get_ref(), expected);
+    assert_eq!(len, expected.len());
+
+    let mut reader = Cursor::new(writer.into_inner());
+    let property_type = reader.read_string().expect("Failed to read type");
+    let read = Property::new(&mut reader, &property_type, true, &mut options, None)
+        .expect("Failed to read property");
+    assert_eq!(reader.position(), expected.len() as u64);
+    assert_eq!(read, property);
+}
+
+const TAGGED_TRUE: [u8; 27] = [
+    13, 0, 0, 0, b'B', b'o', b'o', b'l', b'P', b'r', b'o', b'p', b'e', b'r', b't', b'y',
+    0, // type
+    0, 0, 0, 0, // length
+    0, 0, 0, 0, // array_index
+    1, // value
+    0, // property guid flag
+];
+
+#[test]
+fn value_in_tag() {
+    round_trip(
+        Property::from(BoolProperty::new(true)),
+        PackageVersion::default(),
+        &TAGGED_TRUE,
+    );
+}
+
+#[test]
+fn property_guid_flag_is_version_gated() {
+    let package_version = PackageVersion {
+        ue4: EUnrealEngineObjectUE4Version::PropertyGuidInPropertyTag as u32 - 1,
+        ue5: 0,
+    };
+    round_trip(
+        Property::from(BoolProperty::new(true)),
+        package_version,
+        &TAGGED_TRUE[..TAGGED_TRUE.len() - 1],
+    );
+}
+
+#[test]
+fn value_in_tag_until_complete_type_names() {
+    let package_version = PackageVersion {
+        ue4: EUnrealEngineObjectUE4Version::CorrectLicenseeFlag as u32,
+        ue5: EUnrealEngineObjectUE5Version::PropertyTagExtensionAndOverridableSerialization as u32,
+    };
+    round_trip(
+        Property::from(BoolProperty::new(true)),
+        package_version,
+        &TAGGED_TRUE,
+    );
+
+    // Complete type names moved the value into the tag flags
+    let package_version = PackageVersion {
+        ue5: EUnrealEngineObjectUE5Version::PropertyTagCompleteTypeName as u32,
+        ..package_version
+    };
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version,
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let result = BoolProperty::new(true).write(&mut Cursor::new(Vec::new()), true, &mut options);
+    assert!(matches!(result, Err(Error::Unsupported(_))), "{result:?}");
+
+    let mut reader = Cursor::new(TAGGED_TRUE);
+    reader.read_string().expect("Failed to read type");
+    let result = Property::new(&mut reader, "BoolProperty", true, &mut options, None);
+    assert!(matches!(result, Err(Error::Unsupported(_))), "{result:?}");
+}
+
+#[test]
+fn array_values_in_body() {
+    let array = Property::from(vec![true, false, true]);
+    let mut expected = Vec::new();
+    expected.extend([14, 0, 0, 0]);
+    expected.extend(b"ArrayProperty\0");
+    expected.extend([7, 0, 0, 0]); // length
+    expected.extend([0, 0, 0, 0]); // array_index
+    expected.extend(&TAGGED_TRUE[..17]); // element type
+    expected.push(0); // terminator
+    expected.extend([3, 0, 0, 0, 1, 0, 1]);
+    round_trip(array, PackageVersion::default(), &expected);
+
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let mut writer = Cursor::new(Vec::new());
+    let len = BoolProperty::new(true)
+        .write_body(&mut writer, &mut options)
+        .expect("Failed to write body");
+    assert_eq!((len, writer.into_inner()), (1, vec![1]));
+    assert!(matches!(
+        ArrayProperty::new(
+            String::from("BoolProperty"),
+            None,
+            vec![Property::from(BoolProperty::new(false))]
+        ),
+        Ok(ArrayProperty::Bools { bools }) if bools == [false]
+    ));
+}
+
+fn read_error(data: &[u8]) -> DeserializeError {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let mut reader = Cursor::new(data.to_vec());
+    let property_type = reader.read_string().expect("Failed to read type");
+    let err = Property::new(&mut reader, &property_type, true, &mut options, None)
+        .expect_err("Expected an invalid tag");
+    match err.as_deserialize() {
+        Some(DeserializeError::InvalidValueSize(expected, read, position)) => {
+            DeserializeError::InvalidValueSize(*expected, *read, *position)
+        }
+        Some(DeserializeError::InvalidArrayIndex(index, position)) => {
+            DeserializeError::InvalidArrayIndex(*index, *position)
+        }
+        Some(DeserializeError::InvalidSeparator(separator, position)) => {
+            DeserializeError::InvalidSeparator(*separator, *position)
+        }
+        Some(DeserializeError::InvalidBoolean(value, position)) => {
+            DeserializeError::InvalidBoolean(*value, *position)
+        }
+        _ => panic!("Unexpected error {err:?}"),
+    }
+}
+
+#[test]
+fn invalid_tags() {
+    let mut data = TAGGED_TRUE;
+    data[17] = 1;
+    assert!(matches!(
+        read_error(&data),
+        DeserializeError::InvalidValueSize(0, 1, 17)
+    ));
+
+    let mut data = TAGGED_TRUE;
+    data[21] = 2;
+    assert!(matches!(
+        read_error(&data),
+        DeserializeError::InvalidArrayIndex(2, 21)
+    ));
+
+    let mut data = TAGGED_TRUE;
+    data[25] = 2;
+    assert!(matches!(
+        read_error(&data),
+        DeserializeError::InvalidBoolean(2, 26)
+    ));
+
+    let mut data = TAGGED_TRUE;
+    data[26] = 1;
+    assert!(matches!(
+        read_error(&data),
+        DeserializeError::InvalidSeparator(1, 26)
+    ));
+}
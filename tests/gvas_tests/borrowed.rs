@@ -0,0 +1,104 @@
+use std::{borrow::Cow, collections::HashMap, fs, io::Cursor};
+
+use crate::common::*;
+use gvas::{
+    borrowed::{GvasFileRef, ValueRef},
+    game_version::GameVersion,
+    GvasFile,
+};
+
+/// Parses `data` with both readers and checks that they agree on the root properties.
+fn compare(path: &str, data: &[u8], hints: &HashMap<String, String>) {
+    let file = GvasFile::read_with_hints(&mut Cursor::new(data), GameVersion::Default, hints)
+        .expect("Failed to parse gvas file");
+    let borrowed = GvasFileRef::parse_with_hints(data, hints).expect(path);
+
+    assert_eq!(borrowed.header, file.header, "{path}");
+    assert_eq!(borrowed.trailing_padding, file.trailing_padding, "{path}");
+    let names = borrowed.properties.keys().collect::<Vec<_>>();
+    assert_eq!(names, file.properties.keys().collect::<Vec<_>>(), "{path}");
+    for (name, property) in &borrowed.properties {
+        assert!(matches!(name, Cow::Borrowed(_)), "{path} {name}");
+        assert!(
+            matches!(property.property_type, Cow::Borrowed(_)),
+            "{path} {name}"
+        );
+    }
+}
+
+#[test]
+fn borrowed_matches_owned() {
+    let no_hints = HashMap::new();
+    for (path, hints) in [
+        (ASSERT_FAILED_PATH, &no_hints),
+        (COMPONENT8_PATH, &no_hints),
+        (DELEGATE_PATH, &no_hints),
+        (ENUM_ARRAY_PATH, &no_hints),
+        (FEATURES_01_PATH, &features::hints()),
+        (OPTIONS_PATH, &no_hints),
+        (PACKAGE_VERSION_524_PATH, &no_hints),
+        (PROFILE_0_PATH, &profile0::hints()),
+        (REGRESSION_01_PATH, &no_hints),
+        (RO_64BIT_FAV_PATH, &no_hints),
+        (SAVESLOT_03_PATH, &saveslot3::hints()),
+        (SLOT1_PATH, &no_hints),
+        (SLOT2_PATH, &no_hints),
+        (SLOT3_PATH, &no_hints),
+        (TEXT_PROPERTY_NOARRAY, &no_hints),
+        (TRANSFORM_PATH, &no_hints),
+        (VECTOR2D_PATH, &no_hints),
+    ] {
+        let data = fs::read(path).expect("Failed to read test asset");
+        compare(path, &data, hints);
+    }
+
+    for (path, hints) in [
+        (PALWORLD_ZLIB_PATH, HashMap::new()),
+        (PALWORLD_ZLIB_TWICE_PATH, palworld::hints()),
+    ] {
+        let mut reader = Cursor::new(fs::read(path).expect("Failed to read test asset"));
+        let (data, _) = GvasFile::decompress_raw(&mut reader, GameVersion::Palworld)
+            .expect("Failed to decompress gvas file");
+        compare(path, &data, &hints);
+    }
+}
+
+#[test]
+fn borrowed_slot1() {
+    let data = fs::read(SLOT1_PATH).expect("Failed to read test asset");
+    let file = GvasFileRef::parse(&data).expect("Failed to parse gvas file");
+
+    assert_eq!(file.properties["u8_test"].value, ValueRef::Byte(129));
+    assert_eq!(file.properties["i8_test"].value.as_i64(), Some(-123));
+    assert_eq!(
+        file.properties["ulong_test"].value,
+        ValueRef::UInt(18446744073709551614)
+    );
+    assert_eq!(
+        file.properties["long_test"].value.as_i64(),
+        Some(-9223372036854775807)
+    );
+    assert!(matches!(
+        &file.properties["date_time_property"].value,
+        ValueRef::BinaryStruct { type_name, data } if type_name == "DateTime" && data.len() == 8
+    ));
+
+    let ValueRef::Array { elements, .. } = &file.properties["array_of_structs"].value else {
+        panic!("Expected an array");
+    };
+    assert_eq!(elements.len(), 2);
+    let field = elements[1].field("test_field").expect("Missing field");
+    assert_eq!(field.value, ValueRef::UInt(10));
+
+    let ValueRef::Array { elements, .. } = &file.properties["array_of_strings"].value else {
+        panic!("Expected an array");
+    };
+    assert_eq!(elements[0].as_str(), Some("Hello world from array"));
+}
+
+#[test]
+fn borrowed_invalid() {
+    let data = fs::read(SLOT1_PATH).expect("Failed to read test asset");
+    assert!(GvasFileRef::parse(&data[..data.len() / 2]).is_err());
+    assert!(GvasFileRef::parse(&data[4..]).is_err());
+}
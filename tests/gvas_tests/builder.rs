@@ -0,0 +1,194 @@
+use std::io::Cursor;
+
+use crate::common::{SLOT1_PATH, SLOT3_PATH, VECTOR2D_PATH};
+use gvas::{
+    builder::GvasHeaderBuilder,
+    custom_version::{
+        CustomVersionFormat, CustomVersionTrait, FEditorObjectVersion,
+        FUE5ReleaseStreamObjectVersion,
+    },
+    engine_version::FEngineVersion,
+    error::SerializeError,
+    game_version::GameVersion,
+    object_version::PackageVersion,
+    properties::{
+        int_property::IntProperty,
+        str_property::StrProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        struct_types::{VectorD, VectorF},
+    },
+    types::Guid,
+    GvasFile, GvasHeader,
+};
+
+fn read_header(path: &str) -> GvasHeader {
+    let mut file = std::fs::File::open(path).expect("Failed to open test asset");
+    GvasHeader::read(&mut file).expect("Failed to read header")
+}
+
+fn assert_versions(built: GvasHeaderBuilder, path: &str) {
+    let built = built
+        .save_game_class_name("/Script/Game.SaveGame")
+        .build()
+        .expect("Failed to build header");
+    let header = read_header(path);
+    assert_eq!(built.get_package_version(), header.get_package_version());
+    let engine_version = built.get_engine_version();
+    let expected = header.get_engine_version();
+    assert_eq!(
+        (engine_version.major, engine_version.minor),
+        (expected.major, expected.minor)
+    );
+    for (key, version) in built.get_custom_versions().iter() {
+        assert_eq!(
+            header.get_custom_versions().get(key),
+            Some(version),
+            "{key}"
+        );
+    }
+}
+
+#[test]
+fn presets_match_engine_saves() {
+    assert_versions(GvasHeader::new_ue4_27(), SLOT1_PATH);
+    assert_versions(GvasHeader::new_ue5_0(), SLOT3_PATH);
+    assert_versions(GvasHeader::new_ue5_3(), VECTOR2D_PATH);
+}
+
+#[test]
+fn presets_header_version() {
+    let build = |builder: GvasHeaderBuilder| {
+        builder
+            .save_game_class_name("/Script/Game.SaveGame")
+            .build()
+            .expect("Failed to build header")
+    };
+    assert!(matches!(
+        build(GvasHeader::new_ue4_27()),
+        GvasHeader::Version2 { .. }
+    ));
+    assert!(matches!(
+        build(GvasHeader::new_ue5_3()),
+        GvasHeader::Version3 { .. }
+    ));
+    let header = build(GvasHeader::new_ue5_3());
+    assert!(header.supports_version(FUE5ReleaseStreamObjectVersion::LargeWorldCoordinates));
+    assert!(header
+        .supports_version(FEditorObjectVersion::CultureInvariantTextSerializationKeyStability));
+}
+
+#[test]
+fn build_round_trip() {
+    // Vectors have double components with large world coordinates
+    for (header, position) in [
+        (
+            GvasHeader::new_ue4_27(),
+            StructPropertyValue::from(VectorF::new(1.0, 2.0, 3.0)),
+        ),
+        (
+            GvasHeader::new_ue5_0(),
+            StructPropertyValue::from(VectorD::new(1.0, 2.0, 3.0)),
+        ),
+        (
+            GvasHeader::new_ue5_3(),
+            StructPropertyValue::from(VectorD::new(1.0, 2.0, 3.0)),
+        ),
+    ] {
+        let file = GvasFile::builder(header)
+            .save_game_class_name("/Script/Game.SaveGame")
+            .custom_version(Guid::from(0x1234u128), 5)
+            .property("Level", IntProperty::new(3))
+            .properties([
+                ("Name", StrProperty::from("Player")),
+                ("Title", StrProperty::from("Knight")),
+            ])
+            .property(
+                "Position",
+                StructProperty::new(Guid::default(), String::from("Vector"), position),
+            )
+            .build()
+            .expect("Failed to build file");
+        assert_eq!(file.properties.len(), 4);
+        assert_eq!(
+            file.header.get_custom_versions()[&Guid::from(0x1234u128)],
+            5
+        );
+
+        let mut writer = Cursor::new(Vec::new());
+        file.write(&mut writer).expect("Failed to write file");
+        writer.set_position(0);
+        let read = GvasFile::read(&mut writer, GameVersion::Default).expect("Failed to read");
+        assert_eq!(read, file);
+    }
+}
+
+#[test]
+fn build_custom_header() {
+    let header = GvasHeaderBuilder::new(
+        PackageVersion::default(),
+        FEngineVersion::new(4, 26, 0, 0, String::from("++UE4+Release-4.26")),
+    )
+    .custom_versions([(FEditorObjectVersion::GUID, 30)])
+    .custom_version_format(CustomVersionFormat::Guids)
+    .save_game_class_name("/Script/Game.SaveGame")
+    .build()
+    .expect("Failed to build header");
+
+    let mut writer = Cursor::new(Vec::new());
+    header.write(&mut writer).expect("Failed to write header");
+    writer.set_position(0);
+    assert_eq!(
+        GvasHeader::read(&mut writer).expect("Failed to read"),
+        header
+    );
+}
+
+fn assert_invalid<T: std::fmt::Debug>(result: Result<T, gvas::error::Error>) {
+    let error = result.expect_err("Invalid value built");
+    assert!(
+        matches!(error.as_serialize(), Some(SerializeError::InvalidValue(_))),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn build_validation() {
+    // Missing save game class name
+    assert_invalid(GvasHeader::new_ue5_3().build());
+    assert_invalid(GvasFile::builder(GvasHeader::new_ue5_3()).build());
+
+    // Package versions the reader rejects
+    assert_invalid(
+        GvasHeader::new_ue4_27()
+            .package_version(PackageVersion { ue4: 1, ue5: 0 })
+            .save_game_class_name("/Script/Game.SaveGame")
+            .build(),
+    );
+    assert_invalid(
+        GvasHeader::new_ue5_3()
+            .package_version(PackageVersion {
+                ue4: 522,
+                ue5: 2000,
+            })
+            .save_game_class_name("/Script/Game.SaveGame")
+            .build(),
+    );
+
+    // Custom versions that can't be stored as enum tags
+    assert_invalid(
+        GvasHeader::new_ue5_3()
+            .custom_version_format(CustomVersionFormat::Enums)
+            .save_game_class_name("/Script/Game.SaveGame")
+            .build(),
+    );
+
+    // Property names which end the property list
+    for name in ["", "None"] {
+        assert_invalid(
+            GvasFile::builder(GvasHeader::new_ue5_3())
+                .save_game_class_name("/Script/Game.SaveGame")
+                .property(name, IntProperty::new(1))
+                .build(),
+        );
+    }
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "bundled-hints")]
+
+use crate::common::{FEATURES_01_PATH, PALWORLD_ZLIB_TWICE_PATH};
+use gvas::{
+    error::{DeserializeError, Error, ErrorKind},
+    game_version::GameVersion,
+    hints::{presets, HintValue, Hints},
+    options::{PresetHints, ReadOptions},
+    GvasFile,
+};
+use std::{fs, io::Cursor, path::Path};
+
+const MISSIONS_KEY: &str = "UnLockedMissionParameters.MapProperty.Key.StructProperty";
+
+fn read(
+    path: &str,
+    game_version: GameVersion,
+    read_options: &ReadOptions,
+) -> Result<GvasFile, Error> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    let data = fs::read(path)?;
+    GvasFile::read_with_options(
+        &mut Cursor::new(data),
+        game_version,
+        Hints::new(),
+        read_options,
+    )
+}
+
+#[test]
+fn deep_rock_galactic() -> Result<(), Error> {
+    let file = read(
+        FEATURES_01_PATH,
+        GameVersion::Default,
+        &ReadOptions::default(),
+    )?;
+    assert_eq!(
+        file.header.get_save_game_class_name(),
+        "/Script/FSD.FSDSaveGame"
+    );
+    assert!(file.properties.contains_key("UnLockedMissionParameters"));
+    Ok(())
+}
+
+#[test]
+fn palworld() -> Result<(), Error> {
+    let file = read(
+        PALWORLD_ZLIB_TWICE_PATH,
+        GameVersion::Palworld,
+        &ReadOptions::default(),
+    )?;
+    assert_eq!(
+        file.header.get_save_game_class_name(),
+        "/Script/Pal.PalWorldSaveGame"
+    );
+    Ok(())
+}
+
+#[test]
+fn presets_disabled() {
+    let read_options = ReadOptions {
+        preset_hints: PresetHints::Disabled,
+        ..Default::default()
+    };
+    let err = read(FEATURES_01_PATH, GameVersion::Default, &read_options)
+        .expect_err("Expected a missing hint");
+    assert!(matches!(
+        err,
+        Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::MissingHint(..)),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn applied_presets() {
+    let class_name = "/Script/FSD.FSDSaveGame";
+    let preset = presets::get(class_name).expect("Preset");
+    let hints = Hints::from_iter([(MISSIONS_KEY, HintValue::Raw(Some(16)))]);
+    let applied = presets::applied(class_name, &hints);
+    assert_eq!(applied.len(), preset.len() - 1);
+    assert!(!applied.contains_key(MISSIONS_KEY));
+
+    assert!(presets::applied("/Script/Unknown.SaveGame", &hints).is_empty());
+}
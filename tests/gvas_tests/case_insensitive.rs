@@ -0,0 +1,88 @@
+use std::io::Cursor;
+
+use gvas::{
+    game_version::GameVersion,
+    properties::{
+        int_property::IntProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        Property,
+    },
+    types::{
+        map::{HashableIndexMap, KeyComparison},
+        Guid,
+    },
+    GvasFile, GvasHeader,
+};
+
+#[test]
+fn get_ci() {
+    let mut map = HashableIndexMap::from([
+        ("HighScore".to_string(), Property::from(IntProperty::new(1))),
+        (
+            "highscore_old".to_string(),
+            Property::from(IntProperty::new(2)),
+        ),
+    ]);
+
+    assert_eq!(map.get("highscore"), None);
+    assert_eq!(
+        map.get_ci("highscore"),
+        Some(&Property::from(IntProperty::new(1)))
+    );
+    assert_eq!(map.get_by("HIGHSCORE", KeyComparison::Exact), None);
+    assert!(map.contains_key_ci("HIGHSCORE_OLD"));
+
+    // Mutating through a case-insensitive lookup keeps the original key casing
+    *map.get_ci_mut("HIGHSCORE").expect("get_ci_mut") = Property::from(IntProperty::new(3));
+    let keys: Vec<&String> = map.keys().collect();
+    assert_eq!(keys, ["HighScore", "highscore_old"]);
+    assert_eq!(
+        map.get("HighScore"),
+        Some(&Property::from(IntProperty::new(3)))
+    );
+}
+
+#[test]
+fn get_ci_prefers_exact_match() {
+    let map = HashableIndexMap::from([("value".to_string(), 1), ("Value".to_string(), 2)]);
+    assert_eq!(map.get_ci("Value"), Some(&2));
+    assert_eq!(map.get_ci("VALUE"), Some(&1));
+}
+
+#[test]
+fn path_key_comparison() {
+    let stats = StructProperty::new(
+        Guid::default(),
+        "Stats",
+        StructPropertyValue::CustomStruct(HashableIndexMap::from([(
+            String::from("HighScore"),
+            vec![Property::from(IntProperty::new(1))],
+        )])),
+    );
+    let mut file = GvasFile::builder(GvasHeader::new_ue4_27())
+        .save_game_class_name("/Script/Game.SaveGame")
+        .property("PlayerStats", stats)
+        .build()
+        .expect("Failed to build file");
+    let original = file.clone();
+
+    // Top-level properties and custom struct fields are matched ignoring case
+    assert_eq!(file.get_property_by_path("playerstats.HIGHSCORE"), None);
+    let property = file
+        .get_property_by_path_by_mut("playerstats.HIGHSCORE", KeyComparison::CaseInsensitive)
+        .expect("Failed to find property");
+    *property = Property::from(IntProperty::new(2));
+    let property =
+        file.get_property_by_path_by("PLAYERSTATS.highscore", KeyComparison::CaseInsensitive);
+    assert_eq!(property, Some(&Property::from(IntProperty::new(2))));
+
+    // The original casing is written back
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("Failed to write file");
+    let mut written = GvasFile::read(&mut Cursor::new(writer.into_inner()), GameVersion::Default)
+        .expect("Failed to read file");
+    written
+        .set_int("PlayerStats.HighScore", 1)
+        .expect("Failed to set int");
+    assert_eq!(written.properties, original.properties);
+}
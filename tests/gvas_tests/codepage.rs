@@ -0,0 +1,114 @@
+use std::{collections::HashMap, io::Cursor};
+
+use gvas::{
+    cursor_ext::{ReadExt, WriteExt},
+    error::DeserializeError,
+    object_version::PackageVersion,
+    options::{Codepage, ReadOptions, WriteOptions},
+    properties::{
+        name_property::NameProperty, str_property::StrProperty, Property, PropertyOptions,
+        PropertyTrait,
+    },
+    types::map::HashableIndexMap,
+};
+
+// "Café €" in Windows-1252
+const CAFE: [u8; 11] = [
+    7, 0, 0, 0, // length
+    b'C', b'a', b'f', 0xe9, b' ', 0x80, 0, // value
+];
+
+fn read(
+    data: &[u8],
+    property_type: &str,
+    codepage: Codepage,
+) -> Result<Property, gvas::error::Error> {
+    let read_options = ReadOptions {
+        codepage,
+        ..Default::default()
+    };
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &read_options,
+        write_options: &WriteOptions::default(),
+    };
+    let mut reader = Cursor::new(data.to_vec());
+    Property::new(&mut reader, property_type, false, &mut options, None)
+}
+
+fn write(property: &Property, codepage: Codepage) -> Vec<u8> {
+    let write_options = WriteOptions {
+        codepage,
+        ..Default::default()
+    };
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &write_options,
+    };
+    let mut writer = Cursor::new(Vec::new());
+    property
+        .write(&mut writer, false, &mut options)
+        .expect("Failed to write property");
+    writer.into_inner()
+}
+
+#[test]
+fn utf8_is_strict() {
+    let err = read(&CAFE, "StrProperty", Codepage::Utf8).expect_err("Expected a UTF-8 error");
+    assert!(matches!(
+        err.as_deserialize(),
+        Some(DeserializeError::FromUtf8Error(_, 0))
+    ));
+}
+
+#[test]
+fn windows_1252_round_trip() {
+    for property_type in ["StrProperty", "NameProperty"] {
+        let property =
+            read(&CAFE, property_type, Codepage::Windows1252).expect("Failed to read property");
+        let expected = match property_type {
+            "StrProperty" => Property::from(StrProperty::from("Café €")),
+            _ => Property::from(NameProperty::from("Café €")),
+        };
+        assert_eq!(property, expected);
+        assert_eq!(write(&property, Codepage::Windows1252), CAFE);
+
+        // Without the codepage the value is written as UTF-16
+        let utf16 = write(&property, Codepage::Utf8);
+        assert_eq!(utf16[..4], (-7i32).to_le_bytes());
+        assert_eq!(
+            read(&utf16, property_type, Codepage::Utf8).unwrap(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn windows_1252_fallback() {
+    // Characters outside of Windows-1252 are written as UTF-16
+    let mut writer = Cursor::new(Vec::new());
+    let len = writer
+        .write_fstring_in(Some("Café ☕"), Codepage::Windows1252)
+        .expect("Failed to write string");
+    assert_eq!(len, 4 + 7 * 2);
+
+    // Unassigned bytes round trip through the C1 control characters
+    let data = [4, 0, 0, 0, 0x81, 0x8d, 0x9d, 0];
+    let mut reader = Cursor::new(data);
+    let string = reader
+        .read_fstring_in(Codepage::Windows1252)
+        .expect("Failed to read string");
+    assert_eq!(string.as_deref(), Some("\u{81}\u{8d}\u{9d}"));
+    let mut writer = Cursor::new(Vec::new());
+    writer
+        .write_fstring_in(string.as_deref(), Codepage::Windows1252)
+        .expect("Failed to write string");
+    assert_eq!(writer.into_inner(), data);
+}
@@ -0,0 +1,575 @@
+use std::io::{Cursor, Read, Write};
+
+use crate::common::{PALWORLD_ZLIB_PATH, PALWORLD_ZLIB_TWICE_PATH, SLOT1_PATH};
+use gvas::{
+    container::{self, chunked, grounded, palworld, ContainerFormat, CustomContainer},
+    error::{DeserializeError, Error, SerializeError},
+    game_version::{DeserializedGameVersion, GameVersion, PalworldCompressionType},
+    hints::Hints,
+    options::{ReadOptions, WriteOptions},
+    GvasFile,
+};
+
+fn roundtrip(file: &GvasFile, expected_game_version: GameVersion) -> Result<GvasFile, Error> {
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer)?;
+
+    let mut reader = Cursor::new(writer.into_inner());
+    assert_eq!(container::detect(&mut reader)?, expected_game_version);
+    assert_eq!(reader.position(), 0);
+
+    GvasFile::read(&mut reader, GameVersion::Auto)
+}
+
+#[test]
+fn chunked_roundtrip() -> Result<(), Error> {
+    let data = std::fs::read("resources/test/Slot2.sav")?;
+    let mut file = GvasFile::read(&mut Cursor::new(&data), GameVersion::Default)?;
+
+    for block_size in [chunked::DEFAULT_BLOCK_SIZE, 1000] {
+        file.deserialized_game_version = DeserializedGameVersion::Chunked(block_size);
+        let read = roundtrip(&file, GameVersion::Chunked)?;
+        assert_eq!(read, file);
+    }
+
+    let mut compressed = Vec::new();
+    chunked::compress(&mut compressed, &data, 1000)?;
+    let (decompressed, block_size) = chunked::decompress(&mut compressed.as_slice())?;
+    assert_eq!(decompressed, data);
+    assert_eq!(block_size, 1000);
+
+    Ok(())
+}
+
+#[test]
+fn grounded_roundtrip() -> Result<(), Error> {
+    let data = std::fs::read("resources/test/Slot2.sav")?;
+    let mut file = GvasFile::read(&mut Cursor::new(&data), GameVersion::Default)?;
+    file.deserialized_game_version = DeserializedGameVersion::Grounded;
+
+    let read = roundtrip(&file, GameVersion::Grounded)?;
+    assert_eq!(read, file);
+
+    let mut compressed = Vec::new();
+    grounded::compress(&mut compressed, &data)?;
+    assert_eq!(&compressed[..4], (data.len() as u32).to_le_bytes());
+    assert_eq!(
+        &compressed[4..8],
+        ((compressed.len() - grounded::HEADER_LEN) as u32).to_le_bytes()
+    );
+    assert_eq!(grounded::decompress(&mut compressed.as_slice())?, data);
+
+    Ok(())
+}
+
+#[test]
+fn grounded_length_mismatch() -> Result<(), Error> {
+    let data = std::fs::read("resources/test/Slot2.sav")?;
+    let mut compressed = Vec::new();
+    grounded::compress(&mut compressed, &data)?;
+    compressed[..4].copy_from_slice(&(data.len() as u32 + 1).to_le_bytes());
+
+    let error = GvasFile::read(&mut Cursor::new(compressed), GameVersion::Grounded)
+        .expect_err("Mismatched length read");
+    assert!(matches!(
+        error,
+        Error::Limit(DeserializeError::DecompressedLengthMismatch(..))
+    ));
+
+    Ok(())
+}
+
+#[cfg(feature = "zip")]
+#[test]
+fn zip_roundtrip() -> Result<(), Error> {
+    let data = std::fs::read("resources/test/Slot1.sav")?;
+    let mut file = GvasFile::read(&mut Cursor::new(&data), GameVersion::Default)?;
+    file.deserialized_game_version = DeserializedGameVersion::Zip(String::from("Slot1.sav"));
+
+    let read = roundtrip(&file, GameVersion::Zip)?;
+    assert_eq!(read, file);
+
+    Ok(())
+}
+
+#[cfg(not(feature = "zip"))]
+#[test]
+fn zip_unsupported() -> Result<(), Error> {
+    let data = std::fs::read(SLOT1_PATH)?;
+    let mut file = GvasFile::read(&mut Cursor::new(&data), GameVersion::Default)?;
+    file.deserialized_game_version = DeserializedGameVersion::Zip(String::from("Slot1.sav"));
+
+    let result = file.write(&mut Cursor::new(Vec::new()));
+    assert!(matches!(result, Err(Error::Unsupported(_))), "{result:?}");
+
+    let archive = b"PK\x03\x04\x14\x00\x00\x00\x08\x00";
+    assert_eq!(container::detect_magic(archive), GameVersion::Zip);
+    let result = GvasFile::read(&mut Cursor::new(archive), GameVersion::Zip);
+    assert!(matches!(result, Err(Error::Unsupported(_))), "{result:?}");
+
+    Ok(())
+}
+
+#[test]
+fn detect_default() -> Result<(), Error> {
+    let data = std::fs::read("resources/test/Slot1.sav")?;
+    assert_eq!(container::detect_magic(&data), GameVersion::Default);
+
+    let data = std::fs::read("resources/test/palworld_zlib.sav")?;
+    assert_eq!(container::detect_magic(&data), GameVersion::Palworld);
+
+    Ok(())
+}
+
+/// Stores the GVAS data after a magic, with every byte inverted.
+struct Inverted;
+
+impl ContainerFormat for Inverted {
+    fn read(&self, reader: &mut dyn Read) -> Result<Vec<u8>, Error> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != b"INV1" {
+            Err(DeserializeError::InvalidHeader("Invalid magic".into()))?
+        }
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        Ok(data.into_iter().map(|byte| !byte).collect())
+    }
+
+    fn write(&self, writer: &mut dyn Write, data: &[u8]) -> Result<(), Error> {
+        writer.write_all(b"INV1")?;
+        writer.write_all(&data.iter().map(|byte| !byte).collect::<Vec<_>>())?;
+        Ok(())
+    }
+}
+
+#[test]
+fn custom_container_roundtrip() -> Result<(), Error> {
+    let data = std::fs::read(SLOT1_PATH)?;
+    let file = GvasFile::read(&mut Cursor::new(&data), GameVersion::Default)?;
+
+    let mut wrapped = Vec::new();
+    file.write_with_container(&mut wrapped, &Inverted, &WriteOptions::default())?;
+    assert_eq!(&wrapped[..4], b"INV1");
+    assert_eq!(wrapped.len(), data.len() + 4);
+
+    let read = GvasFile::read_with_container(
+        &mut wrapped.as_slice(),
+        Inverted,
+        Hints::new(),
+        &ReadOptions::default(),
+    )?;
+    assert_eq!(read.properties, file.properties);
+    assert!(matches!(
+        read.deserialized_game_version,
+        DeserializedGameVersion::Custom(_)
+    ));
+
+    // Written back in the container it was read from
+    let mut writer = Cursor::new(Vec::new());
+    read.write(&mut writer)?;
+    assert_eq!(writer.into_inner(), wrapped);
+
+    // Or without it
+    let mut unwrapped = read.clone();
+    unwrapped.deserialized_game_version = DeserializedGameVersion::Default;
+    let mut writer = Cursor::new(Vec::new());
+    unwrapped.write(&mut writer)?;
+    assert_eq!(writer.into_inner(), data);
+
+    Ok(())
+}
+
+#[test]
+fn custom_container_read_options() -> Result<(), Error> {
+    let data = std::fs::read(SLOT1_PATH)?;
+    let file = GvasFile::read(&mut Cursor::new(&data), GameVersion::Default)?;
+    let mut wrapped = Vec::new();
+    file.write_with_container(&mut wrapped, &Inverted, &WriteOptions::default())?;
+
+    let container = CustomContainer::new(Inverted);
+    let read_options = ReadOptions {
+        container: Some(container.clone()),
+        ..Default::default()
+    };
+    let read = GvasFile::read_with_options(
+        &mut Cursor::new(&wrapped),
+        GameVersion::Default,
+        Hints::new(),
+        &read_options,
+    )?;
+    assert_eq!(read.properties, file.properties);
+    assert_eq!(
+        read.deserialized_game_version,
+        DeserializedGameVersion::Custom(container)
+    );
+
+    let mut writer = Cursor::new(Vec::new());
+    read.write(&mut writer)?;
+    assert_eq!(writer.into_inner(), wrapped);
+    Ok(())
+}
+
+#[test]
+fn builtin_container_format() -> Result<(), Error> {
+    let data = std::fs::read(PALWORLD_ZLIB_PATH)?;
+    let (gvas, deserialized_game_version) =
+        GvasFile::decompress_raw(&mut Cursor::new(&data), GameVersion::Palworld)?;
+
+    let container: &dyn ContainerFormat = &deserialized_game_version;
+    assert_eq!(container.read(&mut data.as_slice())?, gvas);
+    let mut wrapped = Vec::new();
+    container.write(&mut wrapped, &gvas)?;
+    assert_eq!(container.read(&mut wrapped.as_slice())?, gvas);
+    Ok(())
+}
+
+#[test]
+fn custom_container_invalid() {
+    let error = GvasFile::read_with_container(
+        &mut b"GVAS".as_slice(),
+        Inverted,
+        Hints::new(),
+        &ReadOptions::default(),
+    )
+    .expect_err("Invalid container read");
+    assert!(matches!(
+        error.as_deserialize(),
+        Some(DeserializeError::InvalidHeader(_))
+    ));
+}
+
+#[test]
+fn fragmented_roundtrip() -> Result<(), Error> {
+    let slot1 = GvasFile::read(&mut std::fs::File::open(SLOT1_PATH)?, GameVersion::Default)?;
+    // Containers are split after the data is wrapped
+    let mut chunked = slot1.clone();
+    chunked.deserialized_game_version = DeserializedGameVersion::Chunked(1000);
+
+    for file in [&slot1, &chunked] {
+        let chunks = file.write_fragmented(1000, &WriteOptions::default())?;
+        assert!(chunks.len() > 1);
+        assert!(chunks[..chunks.len() - 1]
+            .iter()
+            .all(|chunk| chunk.len() == 1000));
+        let mut written = Cursor::new(Vec::new());
+        file.write(&mut written)?;
+        assert_eq!(chunks.concat(), written.into_inner());
+
+        let readers = chunks.iter().map(Cursor::new).collect();
+        let read = GvasFile::read_fragmented(
+            readers,
+            GameVersion::Auto,
+            Hints::new(),
+            &ReadOptions::default(),
+        )?;
+        assert_eq!(&read, file);
+    }
+
+    let error = slot1
+        .write_fragmented(0, &WriteOptions::default())
+        .expect_err("Expected an invalid chunk size");
+    assert!(
+        matches!(error.as_serialize(), Some(SerializeError::InvalidValue(_))),
+        "{error:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn raw_roundtrip() -> Result<(), Error> {
+    let data = std::fs::read("resources/test/Slot2.sav")?;
+    let mut file = GvasFile::read(&mut Cursor::new(&data), GameVersion::Default)?;
+
+    for deserialized_game_version in [
+        DeserializedGameVersion::Default,
+        DeserializedGameVersion::Chunked(1000),
+        DeserializedGameVersion::Grounded,
+    ] {
+        file.deserialized_game_version = deserialized_game_version.clone();
+        let mut written = Vec::new();
+        file.write(&mut written)?;
+
+        let (raw, read_game_version) =
+            GvasFile::decompress_raw(&mut Cursor::new(&written), GameVersion::Auto)?;
+        assert_eq!(raw, data);
+        assert_eq!(read_game_version, deserialized_game_version);
+
+        let mut compressed = Vec::new();
+        GvasFile::compress_raw(&mut compressed, &raw, &read_game_version)?;
+        assert_eq!(compressed, written);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn raw_palworld() -> Result<(), Error> {
+    let mut reader = Cursor::new(std::fs::read(PALWORLD_ZLIB_PATH)?);
+    let (raw, deserialized_game_version) =
+        GvasFile::decompress_raw(&mut reader, GameVersion::Palworld)?;
+    assert!(raw.starts_with(b"GVAS"));
+
+    let file = GvasFile::read(&mut Cursor::new(&raw), GameVersion::Default)?;
+    let mut written = Vec::new();
+    file.write(&mut written)?;
+    assert_eq!(written, raw);
+
+    let mut compressed = Vec::new();
+    GvasFile::compress_raw(&mut compressed, &raw, &deserialized_game_version)?;
+    let (decompressed, _) =
+        GvasFile::decompress_raw(&mut Cursor::new(compressed), GameVersion::Auto)?;
+    assert_eq!(decompressed, raw);
+
+    Ok(())
+}
+
+#[test]
+fn palworld_helpers() -> Result<(), Error> {
+    let mut buffer = Vec::new();
+    for path in [PALWORLD_ZLIB_PATH, PALWORLD_ZLIB_TWICE_PATH] {
+        let compressed = std::fs::read(path)?;
+        let (data, compression_type, lengths) =
+            palworld::decompress(&mut Cursor::new(&compressed))?;
+        let (raw, deserialized_game_version) =
+            GvasFile::decompress_raw(&mut Cursor::new(&compressed), GameVersion::Palworld)?;
+        assert_eq!(data, raw);
+        assert_eq!(
+            deserialized_game_version,
+            DeserializedGameVersion::Palworld(compression_type, lengths)
+        );
+
+        // The buffer is cleared and reused
+        assert_eq!(
+            palworld::decompress_into(&mut Cursor::new(&compressed), &mut buffer)?,
+            (compression_type, lengths)
+        );
+        assert_eq!(buffer, data);
+
+        let mut written = Vec::new();
+        palworld::compress(&mut written, &data, compression_type)?;
+        let (decompressed, written_compression_type, _) =
+            palworld::decompress(&mut Cursor::new(written))?;
+        assert_eq!(decompressed, data);
+        assert_eq!(written_compression_type, compression_type);
+    }
+    Ok(())
+}
+
+#[test]
+fn palworld_parallel() -> Result<(), Error> {
+    let paths = [PALWORLD_ZLIB_PATH, PALWORLD_ZLIB_TWICE_PATH];
+    let hints = Hints::from(crate::common::palworld::hints());
+    let files = std::thread::scope(|scope| {
+        paths
+            .map(|path| {
+                let hints = &hints;
+                scope.spawn(move || {
+                    let mut reader = Cursor::new(std::fs::read(path)?);
+                    let (data, _, _) = palworld::decompress(&mut reader)?;
+                    GvasFile::read_with_hints(&mut Cursor::new(data), GameVersion::Default, hints)
+                })
+            })
+            .map(|handle| handle.join().expect("decompression thread panicked"))
+    });
+    for (path, file) in paths.into_iter().zip(files) {
+        let expected = GvasFile::read_with_hints(
+            &mut Cursor::new(std::fs::read(path)?),
+            GameVersion::Palworld,
+            &hints,
+        )?;
+        assert_eq!(file?.properties, expected.properties);
+    }
+    Ok(())
+}
+
+#[test]
+fn palworld_prefix() -> Result<(), Error> {
+    let hints = Hints::from(crate::common::palworld::hints());
+    let compressed = std::fs::read(PALWORLD_ZLIB_PATH)?;
+    let (raw, compression_type, _) = palworld::decompress(&mut Cursor::new(&compressed))?;
+    let prefix = [&4u32.to_le_bytes()[..], b"meta"].concat();
+    let mut data = prefix.clone();
+    data.extend_from_slice(&raw);
+    let mut with_prefix = Vec::new();
+    palworld::compress(&mut with_prefix, &data, compression_type)?;
+
+    let file = GvasFile::read_with_hints(
+        &mut Cursor::new(&with_prefix),
+        GameVersion::Palworld,
+        &hints,
+    )?;
+    assert_eq!(file.prefix, prefix);
+
+    let mut written = Vec::new();
+    file.write(&mut written)?;
+    let (decompressed, _, _) = palworld::decompress(&mut Cursor::new(written))?;
+    assert_eq!(decompressed, data);
+
+    let mut reader = Cursor::new(&with_prefix);
+    let iter = GvasFile::read_properties_iter(
+        &mut reader,
+        GameVersion::Palworld,
+        hints,
+        &ReadOptions::default(),
+    )?;
+    assert_eq!(iter.prefix(), prefix);
+    assert_eq!(iter.count(), file.properties.len());
+
+    Ok(())
+}
+
+#[test]
+fn palworld_invalid_magic() {
+    let mut data = vec![0u8; palworld::HEADER_LEN];
+    data[8..11].copy_from_slice(b"PlX");
+    let error = palworld::decompress(&mut Cursor::new(data)).expect_err("Invalid magic read");
+    assert!(matches!(
+        error.as_deserialize(),
+        Some(DeserializeError::InvalidHeader(_))
+    ));
+}
+
+#[test]
+fn palworld_write_options() -> Result<(), Error> {
+    let hints = Hints::from(crate::common::palworld::hints());
+    let data = std::fs::read(PALWORLD_ZLIB_PATH)?;
+    let file = GvasFile::read_with_hints(&mut Cursor::new(&data), GameVersion::Palworld, &hints)?;
+
+    let write = |write_options: &WriteOptions| -> Result<Vec<u8>, Error> {
+        let mut writer = Vec::new();
+        file.write_with_options(&mut writer, write_options)?;
+        Ok(writer)
+    };
+
+    let mut default = Vec::new();
+    file.write(&mut default)?;
+    let level_6 = write(&WriteOptions {
+        palworld_compression_level: Some(palworld::DEFAULT_COMPRESSION_LEVEL),
+        ..Default::default()
+    })?;
+    assert_eq!(level_6, default);
+    // The buffer size only reserves memory
+    let buffered = write(&WriteOptions {
+        palworld_buffer_size: Some(default.len()),
+        ..Default::default()
+    })?;
+    assert_eq!(buffered, default);
+
+    let stored = write(&WriteOptions {
+        palworld_compression_level: Some(0),
+        ..Default::default()
+    })?;
+    let smallest = write(&WriteOptions {
+        palworld_compression_level: Some(9),
+        ..Default::default()
+    })?;
+    assert!(smallest.len() < stored.len());
+    for written in [stored, smallest] {
+        let read =
+            GvasFile::read_with_hints(&mut Cursor::new(written), GameVersion::Palworld, &hints)?;
+        assert_eq!(read, file);
+    }
+
+    for compression_type in [
+        PalworldCompressionType::None,
+        PalworldCompressionType::ZlibTwice,
+    ] {
+        let written = write(&WriteOptions {
+            palworld_compression: Some(compression_type),
+            ..Default::default()
+        })?;
+        let (_, written_compression_type, _) = palworld::decompress(&mut Cursor::new(&written))?;
+        assert_eq!(written_compression_type, compression_type);
+        let read =
+            GvasFile::read_with_hints(&mut Cursor::new(written), GameVersion::Palworld, &hints)?;
+        assert_eq!(read.properties, file.properties);
+    }
+
+    let error = write(&WriteOptions {
+        palworld_compression_level: Some(10),
+        ..Default::default()
+    })
+    .expect_err("Invalid compression level written");
+    assert!(matches!(
+        error.as_serialize(),
+        Some(SerializeError::InvalidValue(_))
+    ));
+    Ok(())
+}
+
+fn suggested_game_version(error: &Error) -> Option<(GameVersion, GameVersion)> {
+    match error.as_deserialize() {
+        Some(&DeserializeError::WrongGameVersion(requested, suggested)) => {
+            Some((requested, suggested))
+        }
+        _ => None,
+    }
+}
+
+#[test]
+fn wrong_game_version() -> Result<(), Error> {
+    let palworld = std::fs::read(PALWORLD_ZLIB_PATH)?;
+    for requested in [
+        GameVersion::Default,
+        GameVersion::DeepRockGalactic,
+        GameVersion::Grounded,
+    ] {
+        let error =
+            GvasFile::read(&mut Cursor::new(&palworld), requested).expect_err("Palworld save read");
+        assert!(matches!(error, Error::Header(_)));
+        assert_eq!(
+            suggested_game_version(&error),
+            Some((requested, GameVersion::Palworld))
+        );
+    }
+    let error = GvasFile::decompress_raw(&mut Cursor::new(&palworld), GameVersion::Chunked)
+        .expect_err("Palworld save decompressed");
+    assert_eq!(
+        suggested_game_version(&error),
+        Some((GameVersion::Chunked, GameVersion::Palworld))
+    );
+
+    let gvas = std::fs::read(SLOT1_PATH)?;
+    let error = GvasFile::read(&mut Cursor::new(&gvas), GameVersion::Palworld)
+        .expect_err("GVAS save read as Palworld");
+    assert_eq!(
+        suggested_game_version(&error),
+        Some((GameVersion::Palworld, GameVersion::Default))
+    );
+    assert_eq!(
+        error.to_string(),
+        "Save can't be read as GameVersion::Palworld, it looks like GameVersion::Default"
+    );
+
+    let mut file = GvasFile::read(&mut Cursor::new(&gvas), GameVersion::Default)?;
+    file.deserialized_game_version = DeserializedGameVersion::Chunked(chunked::DEFAULT_BLOCK_SIZE);
+    let mut chunked = Vec::new();
+    file.write(&mut chunked)?;
+    let error = GvasFile::read(&mut Cursor::new(&chunked), GameVersion::Grounded)
+        .expect_err("Chunked save read as Grounded");
+    assert_eq!(
+        suggested_game_version(&error),
+        Some((GameVersion::Grounded, GameVersion::Chunked))
+    );
+    Ok(())
+}
+
+#[test]
+fn wrong_game_version_unknown_data() {
+    // Data matching no container keeps the original error
+    let data = vec![0xffu8; 64];
+    for requested in [GameVersion::Default, GameVersion::Palworld] {
+        let error =
+            GvasFile::read(&mut Cursor::new(&data), requested).expect_err("Invalid data read");
+        assert_eq!(suggested_game_version(&error), None);
+    }
+
+    // Errors in the properties of a save read with the right game version are kept as well
+    let mut data = std::fs::read(SLOT1_PATH).expect("Failed to read test asset");
+    let len = data.len();
+    data.truncate(len - 20);
+    let error = GvasFile::read(&mut Cursor::new(&data), GameVersion::Default)
+        .expect_err("Truncated save read");
+    assert_eq!(suggested_game_version(&error), None);
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "glam")]
+
+use std::{fs::File, path::Path};
+
+use crate::common::TRANSFORM_PATH;
+use glam::{DQuat, DVec3};
+use gvas::{
+    coordinates::CoordinateTransform,
+    game_version::GameVersion,
+    properties::{
+        array_property::ArrayProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        struct_types::{QuatF, VectorF},
+        Property,
+    },
+    types::{map::HashableIndexMap, Guid},
+    GvasFile,
+};
+
+fn struct_property(type_name: &str, value: StructPropertyValue) -> Property {
+    Property::from(StructProperty::new(
+        Guid::default(),
+        String::from(type_name),
+        value,
+    ))
+}
+
+fn field(property: &Property, name: &str) -> StructPropertyValue {
+    let fields = property
+        .get_struct()
+        .and_then(|property| property.value.get_custom_struct())
+        .expect("Custom struct");
+    fields[name][0].get_struct().expect("Struct").value.clone()
+}
+
+#[test]
+fn transform_struct() {
+    let fields = HashableIndexMap::from([
+        (
+            String::from("Rotation"),
+            vec![struct_property(
+                "Quat",
+                StructPropertyValue::QuatF(QuatF::new(0.0, 0.0, 0.0, 1.0)),
+            )],
+        ),
+        (
+            String::from("Translation"),
+            vec![struct_property(
+                "Vector",
+                StructPropertyValue::VectorF(VectorF::new(1.0, 0.0, 0.0)),
+            )],
+        ),
+        (
+            String::from("Scale3D"),
+            vec![struct_property(
+                "Vector",
+                StructPropertyValue::VectorF(VectorF::new(1.0, 1.0, 1.0)),
+            )],
+        ),
+    ]);
+    let mut property = struct_property("Transform", StructPropertyValue::CustomStruct(fields));
+
+    let transform = CoordinateTransform::new(
+        DVec3::splat(2.0),
+        DQuat::from_rotation_z(90f64.to_radians()),
+        DVec3::new(0.0, 0.0, 10.0),
+    );
+    assert_eq!(transform.apply(&mut property), 1);
+
+    let translation = field(&property, "Translation").get_dvec3().unwrap();
+    assert!(translation.abs_diff_eq(DVec3::new(0.0, 2.0, 10.0), 1e-6));
+    let scale = field(&property, "Scale3D").get_dvec3().unwrap();
+    assert_eq!(scale, DVec3::splat(2.0));
+    let rotation = field(&property, "Rotation").get_dquat().unwrap();
+    assert!(rotation.abs_diff_eq(transform.rotation, 1e-6));
+}
+
+#[test]
+fn transform_file() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(TRANSFORM_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    let mut file =
+        GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file");
+    let before = file.properties["playerlocationarray"].clone();
+
+    let transform = CoordinateTransform::from_translation(DVec3::new(-100.0, 0.0, 0.0));
+    let changed = file
+        .transform_coordinates("playerlocationarray", &transform)
+        .expect("transform_coordinates");
+    assert_eq!(changed, 3);
+
+    let locations = |property: &Property| -> Vec<DVec3> {
+        let Property::ArrayProperty(ArrayProperty::Structs { structs, .. }) = property else {
+            panic!("Unexpected property {property:?}");
+        };
+        structs
+            .iter()
+            .filter_map(|value| value.get_dvec3())
+            .collect()
+    };
+    let expected = locations(&before)
+        .into_iter()
+        .map(|location| location - DVec3::new(100.0, 0.0, 0.0))
+        .collect::<Vec<_>>();
+    assert_eq!(locations(&file.properties["playerlocationarray"]), expected);
+
+    // Missing paths are reported
+    assert!(file.transform_coordinates("missing", &transform).is_err());
+}
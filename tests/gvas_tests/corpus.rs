@@ -0,0 +1,72 @@
+#![cfg(feature = "testutil")]
+
+use std::fs;
+
+use gvas::{
+    corpus::{verify_roundtrip, CorpusOptions, Mismatch},
+    options::{TrailingPadding, WriteOptions},
+};
+
+use crate::common::{palworld, PALWORLD_ZLIB_PATH, SLOT1_PATH, SLOT2_PATH};
+
+#[test]
+fn verify_corpus() {
+    let dir = std::env::temp_dir().join(format!("gvas_test_corpus_{}", std::process::id()));
+    fs::create_dir_all(dir.join("palworld")).expect("Failed to create corpus");
+    fs::copy(SLOT1_PATH, dir.join("Slot1.sav")).expect("Failed to copy Slot1");
+    fs::copy(SLOT2_PATH, dir.join("Slot2.SAV")).expect("Failed to copy Slot2");
+    fs::copy(PALWORLD_ZLIB_PATH, dir.join("palworld/Level.sav")).expect("Failed to copy Level");
+    fs::write(dir.join("broken.sav"), b"GVAS").expect("Failed to write broken save");
+    fs::write(dir.join("notes.txt"), b"not a save").expect("Failed to write notes");
+
+    let options = CorpusOptions {
+        hints: palworld::hints().into(),
+        ..Default::default()
+    };
+    let results = verify_roundtrip(&dir, &options).expect("Failed to check corpus");
+    let names = results
+        .iter()
+        .map(|result| result.path.strip_prefix(&dir).expect("Path outside corpus"))
+        .map(|path| path.to_string_lossy().replace('\\', "/"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        ["Slot1.sav", "Slot2.SAV", "broken.sav", "palworld/Level.sav"]
+    );
+    let mismatches = results
+        .iter()
+        .map(|result| result.mismatch.as_ref())
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        mismatches[..],
+        [None, None, Some(Mismatch::Read(_)), None]
+    ));
+
+    // Dropping the trailing padding changes the written bytes
+    let options = CorpusOptions {
+        recursive: false,
+        write_options: WriteOptions {
+            trailing_padding: TrailingPadding::Omit,
+            ..Default::default()
+        },
+        ..options
+    };
+    let results = verify_roundtrip(&dir, &options).expect("Failed to check corpus");
+    let original_len = fs::metadata(SLOT1_PATH).expect("Slot1").len() as usize;
+    match &results[0].mismatch {
+        Some(Mismatch::Bytes {
+            offset,
+            original_len: read,
+            written_len,
+        }) => {
+            assert_eq!(
+                (*offset, *read, *written_len),
+                (original_len - 4, original_len, original_len - 4)
+            );
+        }
+        mismatch => panic!("Unexpected mismatch {mismatch:?}"),
+    }
+    assert_eq!(results.len(), 3);
+
+    fs::remove_dir_all(&dir).expect("Failed to remove corpus");
+}
@@ -0,0 +1,233 @@
+use crate::common::{palworld::hints, PALWORLD_ZLIB_PATH, SLOT1_PATH};
+use gvas::{
+    custom_version::{
+        read_container, read_container_with_names, write_container, write_container_with_names,
+        CustomVersionFormat,
+    },
+    error::{Error, ErrorKind, SerializeError},
+    game_version::GameVersion,
+    hints::Hints,
+    options::ReadOptions,
+    types::{map::HashableIndexMap, Guid},
+    GvasFile, GvasHeader,
+};
+use std::{fs::File, io::Cursor, path::Path};
+
+fn versions() -> HashableIndexMap<Guid, u32> {
+    HashableIndexMap::from([
+        (Guid::from_u32([0, 0, 0, 7]), 3),
+        (Guid::from_u32([0, 0, 0, 12]), 1),
+    ])
+}
+
+fn roundtrip(format: CustomVersionFormat, versions: &HashableIndexMap<Guid, u32>) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+    write_container(&mut writer, format, versions).expect("write_container");
+
+    let mut reader = Cursor::new(writer.get_ref().clone());
+    let read = read_container(&mut reader, format).expect("read_container");
+    assert_eq!(&read, versions);
+    assert_eq!(reader.position() as usize, reader.get_ref().len());
+    writer.into_inner()
+}
+
+#[test]
+fn enums() {
+    let data = roundtrip(CustomVersionFormat::Enums, &versions());
+    assert_eq!(
+        data,
+        [2, 0, 0, 0, 7, 0, 0, 0, 3, 0, 0, 0, 12, 0, 0, 0, 1, 0, 0, 0]
+    );
+}
+
+#[test]
+fn enums_reject_guid_keys() {
+    let versions = HashableIndexMap::from([(Guid::from_u32([1, 0, 0, 7]), 3)]);
+    let err = write_container(
+        &mut Cursor::new(Vec::new()),
+        CustomVersionFormat::Enums,
+        &versions,
+    )
+    .expect_err("Expected err");
+    assert!(matches!(
+        err,
+        Error::Property {
+            kind: ErrorKind::Serialize(SerializeError::InvalidValue(_)),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn guids() {
+    let data = roundtrip(CustomVersionFormat::Guids, &versions());
+    // Count, then a guid, a version and an empty friendly name per entry
+    assert_eq!(data.len(), 4 + 2 * (16 + 4 + 5));
+}
+
+#[test]
+fn guids_skip_friendly_names() {
+    let mut data = vec![1, 0, 0, 0];
+    data.extend(Guid::from_u32([1, 2, 3, 4]).to_u8());
+    data.extend([9, 0, 0, 0]);
+    data.extend([5, 0, 0, 0]);
+    data.extend(b"Test\0");
+    let versions =
+        read_container(&mut Cursor::new(data), CustomVersionFormat::Guids).expect("read_container");
+    assert_eq!(
+        versions,
+        HashableIndexMap::from([(Guid::from_u32([1, 2, 3, 4]), 9)])
+    );
+}
+
+#[test]
+fn optimized() {
+    let data = roundtrip(CustomVersionFormat::Optimized, &versions());
+    assert_eq!(data.len(), 4 + 2 * (16 + 4));
+}
+
+#[test]
+fn header_with_guids_format() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SLOT1_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    let mut file =
+        GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file");
+    let GvasHeader::Version2 {
+        custom_version_format,
+        custom_versions,
+        custom_version_names,
+        ..
+    } = &mut file.header
+    else {
+        panic!("Expected a Version2 header")
+    };
+    *custom_version_format = CustomVersionFormat::Guids.into();
+    // Friendly names are kept, entries without one are written with an empty name
+    let key = *custom_versions.keys().next().expect("Custom version");
+    custom_version_names.insert(key, String::from("Dev-Core"));
+
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("GvasFile::write");
+    writer.set_position(0);
+    let read = GvasFile::read(&mut writer, GameVersion::Default).expect("GvasFile::read");
+    assert_eq!(read, file);
+}
+
+#[test]
+fn container_with_names() {
+    let names = HashableIndexMap::from([(Guid::from_u32([0, 0, 0, 7]), String::from("Core"))]);
+    let mut writer = Cursor::new(Vec::new());
+    write_container_with_names(&mut writer, CustomVersionFormat::Guids, &versions(), &names)
+        .expect("write_container_with_names");
+    writer.set_position(0);
+    let read = read_container_with_names(&mut writer, CustomVersionFormat::Guids)
+        .expect("read_container_with_names");
+    assert_eq!(read, (versions(), names.clone()));
+
+    // Other formats have no names
+    let mut writer = Cursor::new(Vec::new());
+    write_container_with_names(&mut writer, CustomVersionFormat::Enums, &versions(), &names)
+        .expect("write_container_with_names");
+    writer.set_position(0);
+    let read = read_container_with_names(&mut writer, CustomVersionFormat::Enums)
+        .expect("read_container_with_names");
+    assert_eq!(read, (versions(), HashableIndexMap::new()));
+}
+
+#[test]
+fn header_with_custom_version_extra() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SLOT1_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    let mut file =
+        GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file");
+    file.custom_version_extra = vec![2, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 1];
+
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("GvasFile::write");
+    let data = writer.into_inner();
+
+    // The extra data is read as part of the class name without the option
+    assert!(GvasFile::read(&mut Cursor::new(&data), GameVersion::Default).is_err());
+
+    let read_options = ReadOptions {
+        preserve_custom_version_extra: true,
+        ..Default::default()
+    };
+    let read = GvasFile::read_with_options(
+        &mut Cursor::new(&data),
+        GameVersion::Default,
+        Hints::new(),
+        &read_options,
+    )
+    .expect("GvasFile::read_with_options");
+    assert_eq!(read, file);
+
+    let mut writer = Cursor::new(Vec::new());
+    read.write(&mut writer).expect("GvasFile::write");
+    assert_eq!(writer.into_inner(), data);
+}
+
+#[test]
+fn header_without_custom_version_extra() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SLOT1_PATH);
+    let read_options = ReadOptions {
+        preserve_custom_version_extra: true,
+        ..Default::default()
+    };
+    let file = GvasFile::read_with_options(
+        &mut File::open(path).expect("Failed to open test asset"),
+        GameVersion::Default,
+        Hints::new(),
+        &read_options,
+    )
+    .expect("Failed to parse gvas file");
+    assert!(file.custom_version_extra.is_empty());
+    assert_eq!(
+        file.header.get_save_game_class_name(),
+        "/Script/UE4SaveFile.TestSaveGame"
+    );
+}
+
+#[test]
+fn palworld_without_custom_version_extra() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(PALWORLD_ZLIB_PATH);
+    let read_options = ReadOptions {
+        preserve_custom_version_extra: true,
+        ..Default::default()
+    };
+    let file = GvasFile::open(&path, GameVersion::Palworld, hints(), &read_options)
+        .expect("Failed to parse gvas file");
+    assert!(file.custom_version_extra.is_empty());
+
+    let expected = GvasFile::open(
+        path,
+        GameVersion::Palworld,
+        hints(),
+        &ReadOptions::default(),
+    )
+    .expect("Failed to parse gvas file");
+    assert_eq!(file, expected);
+}
+
+#[test]
+fn custom_version_extra_at_end_of_file() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SLOT1_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    let file = GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file");
+    let mut writer = Cursor::new(Vec::new());
+    file.header.write(&mut writer).expect("GvasHeader::write");
+    let mut data = writer.into_inner();
+    // Drop the class name, so the scan runs into the end of the data
+    data.truncate(data.len() - file.header.get_save_game_class_name().len() - 5);
+
+    let read_options = ReadOptions {
+        preserve_custom_version_extra: true,
+        ..Default::default()
+    };
+    let err = GvasHeader::read_with_options(&mut Cursor::new(data), &read_options)
+        .expect_err("Expected err");
+    assert_eq!(
+        err.to_string(),
+        "Invalid header: Save game class name not found after the custom versions"
+    );
+}
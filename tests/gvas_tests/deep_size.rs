@@ -0,0 +1,46 @@
+use std::io::Cursor;
+
+use gvas::{
+    deep_size::DeepSizeOf,
+    error::Error,
+    game_version::GameVersion,
+    properties::{str_property::StrProperty, Property},
+    GvasFile,
+};
+
+#[test]
+fn deep_size_of_property() {
+    let empty = Property::from(StrProperty::new(None));
+    assert_eq!(empty.deep_size_of(), std::mem::size_of::<Property>());
+
+    let value = "x".repeat(1000);
+    let capacity = value.capacity();
+    let property = Property::from(StrProperty::new(Some(value)));
+    assert_eq!(
+        property.deep_size_of(),
+        std::mem::size_of::<Property>() + capacity
+    );
+}
+
+#[test]
+fn deep_size_of_file() -> Result<(), Error> {
+    let data = std::fs::read("resources/test/component8.sav")?;
+    let file = GvasFile::read(&mut Cursor::new(&data), GameVersion::Default)?;
+
+    let properties_size: usize = file
+        .properties
+        .values()
+        .map(|property| property.deep_size_of())
+        .sum();
+    assert!(properties_size > 0);
+    assert!(file.deep_size_of() > properties_size);
+
+    let mut larger = file.clone();
+    let before = larger.deep_size_of();
+    larger.properties.insert(
+        String::from("Extra"),
+        Property::from(StrProperty::from("x".repeat(4096).as_str())),
+    );
+    assert!(larger.deep_size_of() >= before + 4096);
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use gvas::{
+    properties::{
+        int_property::IntProperty,
+        map_property::{MapDuplicate, MapProperty},
+        set_property::SetProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        Property,
+    },
+    types::{map::HashableIndexMap, Guid},
+};
+
+#[test]
+fn drop_deep_tree() {
+    // Deep enough to overflow the test thread stack if dropped recursively
+    let mut property = Property::from(IntProperty::new(0));
+    for depth in 0..200_000 {
+        let fields = HashableIndexMap::from([(String::from("Inner"), vec![property])]);
+        let value = StructPropertyValue::CustomStruct(fields);
+        property = match depth % 5 {
+            0 => Property::from(value),
+            1 => Property::from(StructProperty::new(
+                Guid::default(),
+                String::from("Nested"),
+                value,
+            )),
+            2 => Property::from(SetProperty::new(
+                String::from("StructProperty"),
+                0,
+                vec![Property::from(value)],
+            )),
+            3 => Property::from(MapProperty::new(
+                String::from("IntProperty"),
+                String::from("StructProperty"),
+                0,
+                HashableIndexMap::from([(
+                    Property::from(IntProperty::new(depth)),
+                    Property::from(value),
+                )]),
+            )),
+            _ => Property::from(MapProperty::Properties {
+                key_type: String::from("IntProperty"),
+                value_type: String::from("StructProperty"),
+                allocation_flags: 0,
+                value: HashableIndexMap::new(),
+                duplicates: vec![MapDuplicate {
+                    position: 0,
+                    key: Property::from(IntProperty::new(depth)),
+                    value: Property::from(value),
+                }],
+            }),
+        };
+    }
+    property.drop_iteratively();
+}
@@ -0,0 +1,110 @@
+use std::{collections::HashMap, io::Cursor, path::Path};
+
+use gvas::{
+    cursor_ext::ReadExt,
+    error::{DeserializeError, Error, ErrorKind},
+    game_version::GameVersion,
+    hints::Hints,
+    object_version::PackageVersion,
+    options::{DelegateLimit, ReadOptions, WriteOptions},
+    properties::{
+        delegate_property::{Delegate, MulticastInlineDelegateProperty, MulticastScriptDelegate},
+        Property, PropertyOptions, PropertyTrait,
+    },
+    types::map::HashableIndexMap,
+    GvasFile,
+};
+
+use crate::common::{delegate, DELEGATE_PATH};
+
+fn read_delegate_file(delegate_limit: DelegateLimit) -> Result<GvasFile, Error> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(DELEGATE_PATH);
+    let read_options = ReadOptions {
+        delegate_limit,
+        ..Default::default()
+    };
+    GvasFile::open(path, GameVersion::Default, Hints::new(), &read_options)
+}
+
+fn delegate_count(file: &GvasFile, name: &str) -> usize {
+    match &file.properties[name] {
+        Property::MulticastInlineDelegateProperty(property) => property.value.delegates.len(),
+        Property::MulticastSparseDelegateProperty(property) => property.value.delegates.len(),
+        property => panic!("Unexpected property {property:?}"),
+    }
+}
+
+#[test]
+fn delegate_limit_within_limit() -> Result<(), Error> {
+    let file = read_delegate_file(DelegateLimit::Fail(2))?;
+    assert_eq!(file, delegate::expected());
+    Ok(())
+}
+
+#[test]
+fn delegate_limit_fail() {
+    match read_delegate_file(DelegateLimit::Fail(1)) {
+        Err(Error::Limit(DeserializeError::TooManyDelegates(2, 1, _))) => {}
+        result => panic!("Unexpected result {result:?}"),
+    }
+}
+
+#[test]
+fn delegate_limit_truncate() -> Result<(), Error> {
+    let file = read_delegate_file(DelegateLimit::Truncate(1))?;
+    assert_eq!(delegate_count(&file, "MulticastDelegate"), 1);
+    assert_eq!(delegate_count(&file, "MulticastSparseDelegate"), 1);
+    assert_eq!(file.properties.len(), delegate::expected().properties.len());
+    Ok(())
+}
+
+#[test]
+fn delegate_count_exceeds_length() -> Result<(), Error> {
+    let property =
+        MulticastInlineDelegateProperty::new(MulticastScriptDelegate::new(vec![Delegate::new(
+            String::from("a"),
+            String::from("b"),
+        )]));
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let mut writer = Cursor::new(Vec::new());
+    property.write(&mut writer, true, &mut options)?;
+
+    // The body is the delegate count followed by two strings of 6 bytes
+    let mut data = writer.into_inner();
+    let count_position = data.len() - 16;
+    data[count_position..count_position + 4].copy_from_slice(&3u32.to_le_bytes());
+
+    let mut reader = Cursor::new(data);
+    let property_type = reader.read_string()?;
+    match Property::new(&mut reader, &property_type, true, &mut options, None) {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::InvalidElementCount(3, 12, _)),
+            ..
+        }) => {}
+        result => panic!("Unexpected result {result:?}"),
+    }
+    Ok(())
+}
+
+#[test]
+fn compact_delegates() {
+    let mut file = delegate::expected();
+    let Property::MulticastInlineDelegateProperty(property) =
+        &mut file.properties["MulticastDelegate"]
+    else {
+        panic!("Unexpected property");
+    };
+    let delegates = property.value.delegates.clone();
+    property.value.delegates.extend(delegates.iter().cloned());
+    property.value.delegates.extend(delegates);
+
+    assert_eq!(file.compact_delegates(), 4);
+    assert_eq!(file, delegate::expected());
+}
@@ -0,0 +1,97 @@
+use std::{fs::File, path::Path};
+
+use crate::common::SLOT1_PATH;
+use gvas::{
+    diff::{Change, DiffOptions, Difference},
+    game_version::GameVersion,
+    properties::{
+        int_property::{IntProperty, UInt64Property},
+        str_property::StrProperty,
+        Property,
+    },
+    GvasFile,
+};
+
+fn read_slot1() -> GvasFile {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SLOT1_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file")
+}
+
+fn paths(differences: Vec<Difference>) -> Vec<String> {
+    differences
+        .into_iter()
+        .map(|difference| difference.path)
+        .collect()
+}
+
+#[test]
+fn identical() {
+    let file = read_slot1();
+    assert!(file.diff(&file.clone(), &DiffOptions::default()).is_empty());
+}
+
+#[test]
+fn nested_changes() {
+    let old = read_slot1();
+    let mut new = old.clone();
+    *new.get_property_by_path_mut("struct_property.test_field")
+        .expect("Missing struct field") = Property::from(UInt64Property::new(1));
+    new.properties.shift_remove("str_property");
+    new.properties
+        .insert(String::from("added"), Property::from(IntProperty::new(1)));
+
+    let differences = old.diff(&new, &DiffOptions::default());
+    assert_eq!(
+        paths(differences.clone()),
+        ["str_property", "struct_property.test_field", "added"]
+    );
+    assert_eq!(
+        differences[0].change,
+        Change::Removed(&Property::from(StrProperty::from("Hello world")))
+    );
+    assert_eq!(
+        differences[1].change,
+        Change::Changed(
+            &Property::from(UInt64Property::new(12345)),
+            &Property::from(UInt64Property::new(1))
+        )
+    );
+    assert_eq!(
+        differences[2].change,
+        Change::Added(&Property::from(IntProperty::new(1)))
+    );
+}
+
+#[test]
+fn ignore_patterns() {
+    let old = read_slot1();
+    let mut new = old.clone();
+    *new.get_property_by_path_mut("struct_property.test_field")
+        .expect("Missing struct field") = Property::from(UInt64Property::new(1));
+    new.properties.insert(
+        String::from("str_property"),
+        Property::from(StrProperty::from("changed")),
+    );
+    new.properties.insert(
+        String::from("int32_test"),
+        Property::from(IntProperty::new(1)),
+    );
+
+    let options = DiffOptions::default().ignore("str_property");
+    assert_eq!(
+        paths(old.diff(&new, &options)),
+        ["int32_test", "struct_property.test_field"]
+    );
+
+    let options = options.ignore("struct_property");
+    assert_eq!(paths(old.diff(&new, &options)), ["int32_test"]);
+
+    let options = DiffOptions::default().ignore("*test*");
+    assert_eq!(paths(old.diff(&new, &options)), ["str_property"]);
+
+    let options = DiffOptions::default().ignore("str");
+    assert!(!options.is_ignored("str_property"));
+    assert!(options.is_ignored("str.value"));
+    assert!(options.is_ignored("str[2]"));
+}
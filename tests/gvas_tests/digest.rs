@@ -0,0 +1,110 @@
+use std::{
+    fs,
+    io::{Cursor, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use crate::common::{PALWORLD_ZLIB_PATH, SLOT1_PATH, VECTOR2D_PATH};
+use gvas::{
+    cursor_ext::{Digest, DigestAlgorithm, HashingReader, ReadExt},
+    game_version::{DeserializedGameVersion, GameVersion},
+    hints::Hints,
+    options::ReadOptions,
+    GvasFile,
+};
+
+fn read_data(path: &str) -> Vec<u8> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    fs::read(path).expect("Failed to read test asset")
+}
+
+fn read_with_digest(
+    data: &[u8],
+    game_version: GameVersion,
+    algorithm: DigestAlgorithm,
+) -> (GvasFile, Digest) {
+    GvasFile::read_with_digest(
+        &mut Cursor::new(data),
+        game_version,
+        Hints::new(),
+        &ReadOptions::default(),
+        algorithm,
+    )
+    .expect("Failed to parse gvas file")
+}
+
+#[test]
+fn digest_file_unchanged() {
+    let data = read_data(SLOT1_PATH);
+    let (file, _) = read_with_digest(&data, GameVersion::Default, DigestAlgorithm::Crc32);
+    let expected = GvasFile::read(&mut Cursor::new(data), GameVersion::Default)
+        .expect("Failed to parse gvas file");
+    assert_eq!(file, expected);
+}
+
+#[test]
+fn crc32_covers_parsed_bytes() {
+    for path in [SLOT1_PATH, VECTOR2D_PATH] {
+        let data = read_data(path);
+        let (_, digest) = read_with_digest(&data, GameVersion::Default, DigestAlgorithm::Crc32);
+        assert_eq!(digest, Digest::Crc32(crc32fast::hash(&data)));
+    }
+}
+
+#[test]
+fn digest_detects_modifications() {
+    let data = read_data(SLOT1_PATH);
+    let (file, digest) = read_with_digest(&data, GameVersion::Default, DigestAlgorithm::Crc32);
+
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("Failed to write file");
+    let (_, same) = read_with_digest(
+        writer.get_ref(),
+        GameVersion::Default,
+        DigestAlgorithm::Crc32,
+    );
+    assert_eq!(same, digest);
+
+    let mut data = data;
+    let last = data.len() - 1;
+    data[last] ^= 1;
+    let (_, changed) = read_with_digest(&data, GameVersion::Default, DigestAlgorithm::Crc32);
+    assert_ne!(changed, digest);
+}
+
+#[test]
+fn palworld_hashes_decompressed_bytes() {
+    let data = read_data(PALWORLD_ZLIB_PATH);
+    let (mut file, digest) = read_with_digest(&data, GameVersion::Palworld, DigestAlgorithm::Crc32);
+
+    file.deserialized_game_version = DeserializedGameVersion::Default;
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("Failed to write file");
+    assert_eq!(digest, Digest::Crc32(crc32fast::hash(writer.get_ref())));
+}
+
+#[test]
+fn hashing_reader_skips_rereads() {
+    let data = b"\x05\0\0\0None\0\x03\0\0\0ab\0";
+    let mut reader = HashingReader::new(Cursor::new(data), DigestAlgorithm::Crc32)
+        .expect("Failed to create reader");
+    assert_eq!(reader.read_string().expect("Failed to read string"), "None");
+    reader
+        .seek(SeekFrom::Start(4))
+        .expect("Failed to seek backwards");
+    let mut buf = [0u8; 5];
+    reader.read_exact(&mut buf).expect("Failed to reread");
+    assert_eq!(reader.read_string().expect("Failed to read string"), "ab");
+    assert_eq!(reader.position(), data.len() as u64);
+    assert_eq!(reader.digest(), Digest::Crc32(crc32fast::hash(data)));
+}
+
+#[cfg(feature = "sha2")]
+#[test]
+fn sha256() {
+    use sha2::{Digest as _, Sha256};
+
+    let data = read_data(SLOT1_PATH);
+    let (_, digest) = read_with_digest(&data, GameVersion::Default, DigestAlgorithm::Sha256);
+    assert_eq!(digest, Digest::Sha256(Sha256::digest(&data).into()));
+}
@@ -0,0 +1,172 @@
+use std::{collections::HashMap, fs, io::Cursor, path::Path};
+
+use gvas::{
+    cursor_ext::ReadExt,
+    game_version::GameVersion,
+    object_version::{EUnrealEngineObjectUE4Version, PackageVersion},
+    options::{ReadOptions, WriteOptions},
+    properties::{array_property::ArrayProperty, Property, PropertyOptions, PropertyTrait},
+    types::map::HashableIndexMap,
+    GvasFile, GvasHeader,
+};
+
+use crate::common::{profile0, PROFILE_0_PATH, SLOT1_PATH};
+
+fn write(file: &GvasFile) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("Failed to write file");
+    writer.into_inner()
+}
+
+/// Rewrites `path` with an older package version and reads it back.
+fn downgrade(
+    path: &str,
+    hints: &HashMap<String, String>,
+    package_file_version: u32,
+) -> (usize, usize) {
+    let data = fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join(path))
+        .expect("Failed to read test asset");
+    let mut file = GvasFile::read_with_hints(&mut Cursor::new(&data), GameVersion::Default, hints)
+        .expect("Failed to parse gvas file");
+    match &mut file.header {
+        GvasHeader::Version2 {
+            package_file_version: version,
+            ..
+        } => *version = package_file_version,
+        GvasHeader::Version3 { .. } => panic!("{path} is a UE5 save"),
+    }
+
+    let written = write(&file);
+    let read_back =
+        GvasFile::read_with_hints(&mut Cursor::new(&written), GameVersion::Default, hints)
+            .expect("Failed to parse downgraded file");
+    assert_eq!(read_back, file);
+    assert_eq!(write(&read_back), written);
+    (data.len(), written.len())
+}
+
+#[test]
+fn files_without_property_guid_flags() {
+    let version = EUnrealEngineObjectUE4Version::PropertyGuidInPropertyTag as u32 - 1;
+    for (path, hints) in [
+        (SLOT1_PATH, HashMap::new()),
+        (PROFILE_0_PATH, profile0::hints()),
+    ] {
+        let (original_len, written_len) = downgrade(path, &hints, version);
+        assert!(written_len < original_len, "{path}");
+    }
+}
+
+#[test]
+fn files_without_struct_guids() {
+    let version = EUnrealEngineObjectUE4Version::StructGuidInPropertyTag as u32 - 1;
+    let (_, flagless_len) = downgrade(
+        SLOT1_PATH,
+        &HashMap::new(),
+        EUnrealEngineObjectUE4Version::PropertyGuidInPropertyTag as u32 - 1,
+    );
+    let (_, written_len) = downgrade(SLOT1_PATH, &HashMap::new(), version);
+    // Each struct tag loses its GUID
+    assert!(written_len < flagless_len);
+    assert_eq!((flagless_len - written_len) % 16, 0);
+}
+
+#[test]
+fn too_old_package_version() {
+    let mut file = GvasFile::read(
+        &mut Cursor::new(
+            fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join(SLOT1_PATH))
+                .expect("Failed to read test asset"),
+        ),
+        GameVersion::Default,
+    )
+    .expect("Failed to parse gvas file");
+    if let GvasHeader::Version2 {
+        package_file_version,
+        ..
+    } = &mut file.header
+    {
+        *package_file_version = EUnrealEngineObjectUE4Version::OldestLoadablePackage as u32 - 1;
+    }
+    let written = write(&file);
+    assert!(GvasFile::read(&mut Cursor::new(written), GameVersion::Default).is_err());
+}
+
+#[test]
+fn wide_bool_arrays() {
+    let mut data = Vec::new();
+    data.extend([14, 0, 0, 0]);
+    data.extend(b"ArrayProperty\0");
+    data.extend([16, 0, 0, 0]); // length
+    data.extend([0, 0, 0, 0]); // array_index
+    data.extend([13, 0, 0, 0]);
+    data.extend(b"BoolProperty\0");
+    data.extend([3, 0, 0, 0]); // count
+    data.extend([1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]);
+
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion {
+            ue4: EUnrealEngineObjectUE4Version::ByteBoolArrays as u32 - 1,
+            ue5: 0,
+        },
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let mut reader = Cursor::new(data.clone());
+    let property_type = reader.read_string().expect("Failed to read type");
+    let property = Property::new(&mut reader, &property_type, true, &mut options, None)
+        .expect("Failed to read property");
+    assert_eq!(reader.position(), data.len() as u64);
+    assert_eq!(
+        property,
+        Property::ArrayProperty(ArrayProperty::WideBools {
+            wide_bools: vec![true, false, true]
+        })
+    );
+
+    let mut writer = Cursor::new(Vec::new());
+    let len = property
+        .write(&mut writer, true, &mut options)
+        .expect("Failed to write property");
+    assert_eq!(writer.into_inner(), data);
+    assert_eq!(len, data.len());
+}
+
+#[test]
+fn byte_bool_arrays() {
+    let mut data = Vec::new();
+    data.extend([14, 0, 0, 0]);
+    data.extend(b"ArrayProperty\0");
+    data.extend([8, 0, 0, 0]); // length
+    data.extend([0, 0, 0, 0]); // array_index
+    data.extend([13, 0, 0, 0]);
+    data.extend(b"BoolProperty\0");
+    data.extend([4, 0, 0, 0]); // count
+    data.extend([1, 0, 0, 1]);
+
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion {
+            ue4: EUnrealEngineObjectUE4Version::ByteBoolArrays as u32,
+            ue5: 0,
+        },
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let mut reader = Cursor::new(data.clone());
+    let property_type = reader.read_string().expect("Failed to read type");
+    let property = Property::new(&mut reader, &property_type, true, &mut options, None)
+        .expect("Failed to read property");
+    assert_eq!(reader.position(), data.len() as u64);
+    assert_eq!(
+        property,
+        Property::ArrayProperty(ArrayProperty::Bools {
+            bools: vec![true, false, false, true]
+        })
+    );
+}
@@ -0,0 +1,180 @@
+use std::{collections::HashMap, io::Cursor};
+
+use gvas::{
+    cursor_ext::ReadExt,
+    error::DeserializeError,
+    object_version::PackageVersion,
+    options::{ReadOptions, WriteOptions},
+    properties::{
+        delegate_property::{
+            Delegate, DelegateProperty, MulticastInlineDelegateProperty, MulticastScriptDelegate,
+            MulticastSparseDelegateProperty,
+        },
+        field_path_property::{FieldPath, FieldPathProperty},
+        int_property::IntProperty,
+        name_property::NameProperty,
+        object_property::ObjectProperty,
+        str_property::StrProperty,
+        text_property::{FText, TextProperty},
+        Property, PropertyOptions, PropertyTrait,
+    },
+    types::map::HashableIndexMap,
+};
+
+fn write(property: &Property) -> Vec<u8> {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let mut writer = Cursor::new(Vec::new());
+    property
+        .write(&mut writer, true, &mut options)
+        .expect("Failed to write property");
+    writer.into_inner()
+}
+
+fn read(data: Vec<u8>) -> Result<Property, gvas::error::Error> {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let len = data.len() as u64;
+    let mut reader = Cursor::new(data);
+    let property_type = reader.read_string().expect("Failed to read type");
+    let property = Property::new(&mut reader, &property_type, true, &mut options, None)?;
+    assert_eq!(reader.position(), len);
+    Ok(property)
+}
+
+/// Returns the length written in the header of a property.
+fn body_length(data: &[u8]) -> u32 {
+    let type_len = u32::from_le_bytes(data[..4].try_into().unwrap()) as usize;
+    let length = &data[4 + type_len..8 + type_len];
+    u32::from_le_bytes(length.try_into().unwrap())
+}
+
+macro_rules! empty_body {
+    ($function_name:ident, $empty:expr, $value:expr) => {
+        #[test]
+        fn $function_name() {
+            let mut empty = $empty;
+            empty.empty_body = true;
+            let empty = Property::from(empty);
+
+            let data = write(&empty);
+            assert_eq!(body_length(&data), 0);
+            assert_eq!(
+                read(data.clone()).expect("Failed to read empty body"),
+                empty
+            );
+            assert_eq!(write(&read(data).unwrap()), write(&empty));
+
+            // Empty values read from a regular body keep it
+            let empty = Property::from($empty);
+            let data = write(&empty);
+            assert_ne!(body_length(&data), 0);
+            assert_eq!(read(data).expect("Failed to read body"), empty);
+
+            // Values which aren't empty always have a body
+            let mut value = $value;
+            value.empty_body = true;
+            let data = write(&Property::from(value));
+            assert_ne!(body_length(&data), 0);
+            assert_eq!(
+                read(data).expect("Failed to read body"),
+                Property::from($value)
+            );
+        }
+    };
+}
+
+empty_body!(str_property, StrProperty::new(None), StrProperty::from(""));
+empty_body!(
+    name_property,
+    NameProperty::from(None),
+    NameProperty::from("Name")
+);
+empty_body!(
+    object_property,
+    ObjectProperty::from(""),
+    ObjectProperty::from("/Game/Object")
+);
+empty_body!(
+    text_property,
+    TextProperty::new(FText::new_none(0, None)),
+    TextProperty::new(FText::new_base(
+        0,
+        Some(String::new()),
+        Some(String::from("Key")),
+        Some(String::from("Text"))
+    ))
+);
+empty_body!(
+    field_path_property,
+    FieldPathProperty::new(FieldPath::new(Vec::new(), String::new())),
+    FieldPathProperty::new(FieldPath::new(
+        vec![String::from("Field")],
+        String::from("/Game/Owner")
+    ))
+);
+empty_body!(
+    delegate_property,
+    DelegateProperty::new(Delegate::new(String::new(), String::new())),
+    DelegateProperty::new(Delegate::new(
+        String::from("/Game/Object"),
+        String::from("Function")
+    ))
+);
+empty_body!(
+    multicast_inline_delegate_property,
+    MulticastInlineDelegateProperty::new(MulticastScriptDelegate::new(Vec::new())),
+    MulticastInlineDelegateProperty::new(MulticastScriptDelegate::new(vec![Delegate::new(
+        String::from("/Game/Object"),
+        String::from("Function")
+    )]))
+);
+empty_body!(
+    multicast_sparse_delegate_property,
+    MulticastSparseDelegateProperty::new(MulticastScriptDelegate::new(Vec::new())),
+    MulticastSparseDelegateProperty::new(MulticastScriptDelegate::new(vec![Delegate::new(
+        String::from("/Game/Object"),
+        String::from("Function")
+    )]))
+);
+
+#[test]
+fn name_array_index() {
+    let name = NameProperty {
+        array_index: 3,
+        value: None,
+        empty_body: true,
+    };
+    let data = write(&Property::from(name.clone()));
+    assert_eq!(body_length(&data), 0);
+    assert_eq!(read(data).expect("Failed to read empty body"), name.into());
+}
+
+#[test]
+fn int_property_needs_body() {
+    let data = write(&Property::from(IntProperty::new(0)));
+    assert_eq!(body_length(&data), 4);
+
+    // A zero-length body is still an error for properties which can't be empty
+    let mut data = data;
+    let type_len = data[0] as usize;
+    data[4 + type_len] = 0;
+    data.truncate(data.len() - 4);
+    let err = read(data).expect_err("IntProperty can't be empty");
+    assert!(matches!(
+        err.as_deserialize(),
+        Some(DeserializeError::InvalidValueSize(4, 0, _))
+    ));
+}
@@ -0,0 +1,49 @@
+use crate::common::{PACKAGE_VERSION_524_PATH, SLOT3_PATH};
+use gvas::{engine_version::EngineBranch, game_version::GameVersion, GvasFile};
+use std::{fs::File, path::Path};
+
+fn read_branch(path: &str) -> EngineBranch {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    let file = GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file");
+    file.header.get_engine_version().get_branch()
+}
+
+#[test]
+fn release_branch() {
+    let branch = read_branch(SLOT3_PATH);
+    assert_eq!(
+        branch,
+        EngineBranch {
+            depot: String::from("UE5"),
+            stream: String::from("Release-5.0"),
+        }
+    );
+    assert!(branch.is_release());
+    assert!(!branch.is_custom());
+    assert_eq!(branch.release_version(), Some((5, 0)));
+}
+
+#[test]
+fn custom_branch() {
+    let branch = read_branch(PACKAGE_VERSION_524_PATH);
+    assert_eq!(branch.depot, "stream");
+    assert_eq!(branch.stream, "Main_TeamCity_Code");
+    assert!(!branch.is_release());
+    assert!(branch.is_custom());
+    assert_eq!(branch.release_version(), None);
+}
+
+#[test]
+fn normalize_branch() {
+    let branch = EngineBranch::parse("//Fortnite/Release-28.10-CL-1234");
+    assert_eq!(branch.to_string(), "++Fortnite+Release-28.10-CL-1234");
+    assert!(branch.is_release());
+    assert!(branch.is_custom());
+    assert_eq!(branch.release_version(), Some((28, 10)));
+
+    let branch = EngineBranch::parse("UE4");
+    assert_eq!(branch.depot, "UE4");
+    assert_eq!(branch.stream, "");
+    assert_eq!(branch.to_string(), "++UE4");
+}
@@ -0,0 +1,58 @@
+use crate::common::{PACKAGE_VERSION_524_PATH, SLOT1_PATH, SLOT3_PATH};
+use gvas::{
+    engine_version::{EngineFamily, FEngineVersion},
+    game_version::GameVersion,
+    GvasFile,
+};
+use std::{cmp::Ordering, fs::File, path::Path};
+
+fn read_engine_version(path: &str) -> FEngineVersion {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    let file = GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file");
+    file.header.get_engine_version().clone()
+}
+
+#[test]
+fn family() {
+    assert_eq!(read_engine_version(SLOT1_PATH).family(), EngineFamily::UE4);
+    assert_eq!(read_engine_version(SLOT3_PATH).family(), EngineFamily::UE5);
+
+    // A custom branch, the family comes from the major version
+    let custom = read_engine_version(PACKAGE_VERSION_524_PATH);
+    assert_eq!(custom.family(), EngineFamily::UE4);
+    assert!(custom.get_branch().is_custom());
+    assert_eq!(
+        FEngineVersion::new(3, 0, 0, 0, String::new()).family(),
+        EngineFamily::Other(3)
+    );
+    assert_eq!(
+        FEngineVersion::new(0, 0, 0, 0, String::from("++Game+Main")).family(),
+        EngineFamily::Other(0)
+    );
+}
+
+#[test]
+fn compare_ignoring_build() {
+    let release = FEngineVersion::new(5, 0, 3, 20979098, String::from("++UE5+Release-5.0"));
+    let editor = FEngineVersion::new(5, 0, 3, 0, String::from("++UE5+Release-5.0"));
+    assert_ne!(release, editor);
+    assert_eq!(release.normalized(), editor);
+    assert_eq!(editor.normalized(), editor);
+    assert!(release.same_version(&editor));
+
+    let older = FEngineVersion::new(4, 27, 2, 0, String::from("++UE4+Release-4.27"));
+    assert_eq!(older.cmp_version(&release), Ordering::Less);
+    assert_eq!(release.cmp_version(&older), Ordering::Greater);
+    assert!(!older.same_version(&release));
+    assert_eq!(release.version(), (5, 0, 3));
+}
+
+#[test]
+fn is_at_least() {
+    let version = FEngineVersion::new(5, 1, 1, 0, String::from("++UE5+Release-5.1"));
+    assert!(version.is_at_least(4, 27));
+    assert!(version.is_at_least(5, 1));
+    assert!(!version.is_at_least(5, 2));
+    assert!(!version.is_at_least(6, 0));
+}
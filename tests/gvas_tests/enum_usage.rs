@@ -0,0 +1,92 @@
+use std::{fs::File, path::Path};
+
+use crate::common::ENUM_ARRAY_PATH;
+use gvas::{
+    enum_usage,
+    game_version::GameVersion,
+    properties::{
+        enum_property::EnumProperty,
+        int_property::ByteProperty,
+        map_property::MapProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        Property,
+    },
+    types::{map::HashableIndexMap, Guid},
+    GvasFile,
+};
+
+#[test]
+fn enum_array_usage() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(ENUM_ARRAY_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    let file = GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file");
+    let usage = file.enum_usage();
+
+    assert_eq!(usage.enum_types().collect::<Vec<_>>(), ["EFreightType"]);
+    let values = usage.values("EFreightType").collect::<Vec<_>>();
+    assert!(values.contains(&(
+        "EFreightType::Lumber",
+        usage.count("EFreightType", "EFreightType::Lumber")
+    )));
+    for (value, count) in values {
+        assert!(value.starts_with("EFreightType::"), "{value}");
+        assert!(count > 0, "{value}");
+        for path in usage.paths("EFreightType", value) {
+            assert!(path.ends_with(']'), "{path}");
+        }
+    }
+}
+
+#[test]
+fn nested_enum_usage() {
+    let property = Property::from(StructProperty::new(
+        Guid::default(),
+        String::from("Settings"),
+        StructPropertyValue::CustomStruct(HashableIndexMap::from([
+            (
+                String::from("Quality"),
+                vec![Property::from(EnumProperty::new(
+                    Some(String::from("EQuality")),
+                    String::from("EQuality::High"),
+                ))],
+            ),
+            (
+                String::from("Mode"),
+                vec![Property::from(ByteProperty::new_namespaced(
+                    Some(String::from("EMode")),
+                    String::from("EMode::Windowed"),
+                ))],
+            ),
+            (
+                String::from("Volume"),
+                vec![Property::from(ByteProperty::new_byte(None, 80))],
+            ),
+            (
+                String::from("Unlocked"),
+                vec![Property::from(MapProperty::EnumBool {
+                    enum_bools: HashableIndexMap::from([
+                        (String::from("EQuality::Low"), true),
+                        (String::from("EQuality::High"), false),
+                    ]),
+                })],
+            ),
+        ])),
+    ));
+
+    let usage = enum_usage::collect("Settings", &property);
+    assert_eq!(
+        usage.enum_types().collect::<Vec<_>>(),
+        ["EQuality", "EMode"]
+    );
+    assert_eq!(
+        usage.values("EQuality").collect::<Vec<_>>(),
+        [("EQuality::High", 2), ("EQuality::Low", 1)]
+    );
+    assert_eq!(
+        usage.paths("EQuality", "EQuality::High"),
+        ["Settings.Quality", "Settings.Unlocked[1]"]
+    );
+    assert_eq!(usage.paths("EMode", "EMode::Windowed"), ["Settings.Mode"]);
+    assert_eq!(usage.count("EMode", "EMode::Fullscreen"), 0);
+    assert_eq!(usage.values("EMissing").count(), 0);
+}
@@ -1,11 +1,24 @@
+use byteorder::{LittleEndian, WriteBytesExt};
 use gvas::{
-    error::{DeserializeError, Error},
+    cursor_ext::{ReadExt, WriteExt},
+    error::{DeserializeError, Error, ErrorKind, SerializeError},
     game_version::GameVersion,
+    hints::Hints,
+    object_version::PackageVersion,
+    options::{PresetHints, ReadOptions, WriteOptions},
     properties::{
-        array_property::ArrayProperty, enum_property::EnumProperty, map_property::MapProperty,
-        set_property::SetProperty, str_property::StrProperty, PropertyOptions,
+        array_property::ArrayProperty,
+        enum_property::EnumProperty,
+        int_property::IntProperty,
+        map_property::MapProperty,
+        name_property::NameProperty,
+        set_property::SetProperty,
+        str_property::StrProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        text_property::FormatArgumentValue,
+        Property, PropertyOptions, PropertyTrait,
     },
-    types::map::HashableIndexMap,
+    types::{map::HashableIndexMap, Guid},
     GvasFile,
 };
 use std::{collections::HashMap, io::Cursor};
@@ -31,7 +44,7 @@ fn test_invalid_header() {
     let mut reader = Cursor::new(INVALID_HEADER);
     let result = GvasFile::read(&mut reader, GameVersion::Default);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidHeader(reason))) => {
+        Err(Error::Header(DeserializeError::InvalidHeader(reason))) => {
             assert_eq!(reason.into_string(), "File type 1514231367 not recognized");
         }
         _ => panic!("Unexpected result {result:?}"),
@@ -45,11 +58,23 @@ const INVALID_ARRAY_INDEX: [u8; 8] = [
 
 #[test]
 fn test_invalid_array_index() {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+
     // StrProperty
     let mut reader = Cursor::new(INVALID_ARRAY_INDEX);
-    let result = StrProperty::read_header(&mut reader);
+    let result = StrProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidArrayIndex(value, position))) => {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::InvalidArrayIndex(value, position)),
+            ..
+        }) => {
             assert_eq!(value, 1);
             assert_eq!(position, 4);
         }
@@ -58,26 +83,26 @@ fn test_invalid_array_index() {
 
     // EnumProperty
     let mut reader = Cursor::new(INVALID_ARRAY_INDEX);
-    let result = EnumProperty::read_header(&mut reader);
+    let result = EnumProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidArrayIndex(value, position))) => {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::InvalidArrayIndex(value, position)),
+            ..
+        }) => {
             assert_eq!(value, 1);
             assert_eq!(position, 4);
         }
         _ => panic!("Unexpected result {result:?}"),
     };
 
-    let mut options = PropertyOptions {
-        hints: &HashMap::new(),
-        properties_stack: &mut Vec::new(),
-        custom_versions: &HashableIndexMap::new(),
-    };
-
     // ArrayProperty
     let mut reader = Cursor::new(INVALID_ARRAY_INDEX);
     let result = ArrayProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidArrayIndex(value, position))) => {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::InvalidArrayIndex(value, position)),
+            ..
+        }) => {
             assert_eq!(value, 1);
             assert_eq!(position, 4);
         }
@@ -88,7 +113,10 @@ fn test_invalid_array_index() {
     let mut reader = Cursor::new(INVALID_ARRAY_INDEX);
     let result = SetProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidArrayIndex(value, position))) => {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::InvalidArrayIndex(value, position)),
+            ..
+        }) => {
             assert_eq!(value, 1);
             assert_eq!(position, 4);
         }
@@ -99,12 +127,143 @@ fn test_invalid_array_index() {
     let mut reader = Cursor::new(INVALID_ARRAY_INDEX);
     let result = MapProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidArrayIndex(value, position))) => {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::InvalidArrayIndex(value, position)),
+            ..
+        }) => {
             assert_eq!(value, 1);
             assert_eq!(position, 4);
         }
         _ => panic!("Unexpected result {result:?}"),
     };
+
+    // ByteProperty and unknown properties
+    for property_type in ["ByteProperty", "CustomProperty"] {
+        let mut reader = Cursor::new(INVALID_ARRAY_INDEX);
+        let result = Property::new(&mut reader, property_type, true, &mut options, None);
+        match result {
+            Err(Error::Property {
+                kind: ErrorKind::Deserialize(DeserializeError::InvalidArrayIndex(value, position)),
+                ..
+            }) => {
+                assert_eq!(value, 1);
+                assert_eq!(position, 4);
+            }
+            _ => panic!("Unexpected result {result:?}"),
+        };
+    }
+}
+
+#[test]
+fn test_struct_name_array_index() {
+    let property = StructProperty::new(
+        Guid::default(),
+        "TestStruct",
+        StructPropertyValue::CustomStruct(HashableIndexMap::from([(
+            String::from("Id"),
+            vec![Property::from(NameProperty {
+                array_index: 1,
+                ..NameProperty::from("Skipped")
+            })],
+        )])),
+    );
+
+    let mut writer = Cursor::new(Vec::new());
+    property
+        .write(
+            &mut writer,
+            true,
+            &mut PropertyOptions {
+                hints: &HashMap::new(),
+                properties_stack: &mut Vec::new(),
+                custom_versions: &HashableIndexMap::new(),
+                package_version: PackageVersion::default(),
+                read_options: &ReadOptions::default(),
+                write_options: &WriteOptions::default(),
+            },
+        )
+        .expect("Write StructProperty");
+
+    // The first entry of a field can't have a non-zero array index
+    let mut reader = Cursor::new(writer.into_inner());
+    assert_eq!(reader.read_string().expect("Read type"), "StructProperty");
+    let result = Property::new(
+        &mut reader,
+        "StructProperty",
+        true,
+        &mut PropertyOptions {
+            hints: &HashMap::new(),
+            properties_stack: &mut Vec::new(),
+            custom_versions: &HashableIndexMap::new(),
+            package_version: PackageVersion::default(),
+            read_options: &ReadOptions::default(),
+            write_options: &WriteOptions::default(),
+        },
+        None,
+    );
+    match result {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::InvalidArrayIndex(value, _)),
+            ..
+        }) => assert_eq!(value, 1),
+        _ => panic!("Unexpected result {result:?}"),
+    };
+}
+
+#[test]
+fn test_invalid_struct_array_type() {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+
+    let mut data = Cursor::new(Vec::new());
+    data.write_u32::<LittleEndian>(0).expect("length");
+    data.write_u32::<LittleEndian>(0).expect("array_index");
+    data.write_string("StructProperty").expect("property_type");
+    data.write_u8(0).expect("separator");
+    data.write_u32::<LittleEndian>(1).expect("property_count");
+    data.write_string("Points").expect("field_name");
+    data.write_string("IntProperty").expect("element type");
+    data.set_position(0);
+
+    let result = ArrayProperty::read_header(&mut data, &mut options);
+    match result {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::InvalidProperty(reason, _)),
+            ..
+        }) => {
+            assert_eq!(
+                reason.as_ref(),
+                "Expected StructProperty array elements, got IntProperty"
+            );
+        }
+        _ => panic!("Unexpected result {result:?}"),
+    };
+}
+
+#[test]
+fn test_incompatible_format_argument() {
+    let options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+
+    // 64 bit format arguments require a UE5 custom version
+    let result = FormatArgumentValue::Int64(1).write(&mut Cursor::new(Vec::new()), &options);
+    let error = result.expect_err("Int64 written without 64 bit support");
+    assert!(
+        matches!(error.as_serialize(), Some(SerializeError::InvalidValue(_))),
+        "{error:?}"
+    );
 }
 
 const INVALID_TERMINATOR: [u8; 9] = [
@@ -129,12 +288,24 @@ const INVALID_TERMINATOR_MAP: [u8; 19] = [
 ];
 
 #[test]
-fn test_invalid_terminator() {
+fn test_invalid_separator() {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+
     // StrProperty
     let mut reader = Cursor::new(INVALID_TERMINATOR);
-    let result = StrProperty::read_header(&mut reader);
+    let result = StrProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidTerminator(value, position))) => {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::InvalidSeparator(value, position)),
+            ..
+        }) => {
             assert_eq!(value, 1);
             assert_eq!(position, 8);
         }
@@ -143,26 +314,26 @@ fn test_invalid_terminator() {
 
     // EnumProperty
     let mut reader = Cursor::new(INVALID_TERMINATOR_ENUM);
-    let result = EnumProperty::read_header(&mut reader);
+    let result = EnumProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidTerminator(value, position))) => {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::InvalidSeparator(value, position)),
+            ..
+        }) => {
             assert_eq!(value, 1);
             assert_eq!(position, 13);
         }
         _ => panic!("Unexpected result {result:?}"),
     };
 
-    let mut options = PropertyOptions {
-        hints: &HashMap::new(),
-        properties_stack: &mut Vec::new(),
-        custom_versions: &HashableIndexMap::new(),
-    };
-
     // ArrayProperty
     let mut reader = Cursor::new(INVALID_TERMINATOR_ENUM);
     let result = ArrayProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidTerminator(value, position))) => {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::InvalidSeparator(value, position)),
+            ..
+        }) => {
             assert_eq!(value, 1);
             assert_eq!(position, 13);
         }
@@ -173,7 +344,10 @@ fn test_invalid_terminator() {
     let mut reader = Cursor::new(INVALID_TERMINATOR_ENUM);
     let result = SetProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidTerminator(value, position))) => {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::InvalidSeparator(value, position)),
+            ..
+        }) => {
             assert_eq!(value, 1);
             assert_eq!(position, 13);
         }
@@ -184,7 +358,10 @@ fn test_invalid_terminator() {
     let mut reader = Cursor::new(INVALID_TERMINATOR_MAP);
     let result = MapProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidTerminator(value, position))) => {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::InvalidSeparator(value, position)),
+            ..
+        }) => {
             assert_eq!(value, 1);
             assert_eq!(position, 18);
         }
@@ -193,7 +370,7 @@ fn test_invalid_terminator() {
 }
 
 const INVALID_LENGTH_STR: [u8; 13] = [
-    0, 0, 0, 0, // length
+    1, 0, 0, 0, // length, zero-length bodies are read as `None`
     0, 0, 0, 0, // array_index
     0, // terminator
     0, 0, 0, 0, // string
@@ -236,12 +413,29 @@ const INVALID_LENGTH_MAP: [u8; 27] = [
 
 #[test]
 fn test_invalid_length() {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+
     // StrProperty
     let mut reader = Cursor::new(INVALID_LENGTH_STR);
-    let result = StrProperty::read_header(&mut reader);
+    let result = StrProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidValueSize(expected, read, position))) => {
-            assert_eq!(expected, 0);
+        Err(Error::Property {
+            kind:
+                ErrorKind::Deserialize(DeserializeError::LengthMismatch {
+                    expected,
+                    actual: read,
+                    position,
+                }),
+            ..
+        }) => {
+            assert_eq!(expected, 1);
             assert_eq!(read, 4);
             assert_eq!(position, 9);
         }
@@ -250,9 +444,17 @@ fn test_invalid_length() {
 
     // EnumProperty
     let mut reader = Cursor::new(INVALID_LENGTH_ENUM);
-    let result = EnumProperty::read_header(&mut reader);
+    let result = EnumProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidValueSize(expected, read, position))) => {
+        Err(Error::Property {
+            kind:
+                ErrorKind::Deserialize(DeserializeError::LengthMismatch {
+                    expected,
+                    actual: read,
+                    position,
+                }),
+            ..
+        }) => {
             assert_eq!(expected, 0);
             assert_eq!(read, 5);
             assert_eq!(position, 14);
@@ -260,17 +462,19 @@ fn test_invalid_length() {
         _ => panic!("Unexpected result {result:?}"),
     }
 
-    let mut options = PropertyOptions {
-        hints: &HashMap::new(),
-        properties_stack: &mut Vec::new(),
-        custom_versions: &HashableIndexMap::new(),
-    };
-
     // ArrayProperty
     let mut reader = Cursor::new(INVALID_LENGTH_ARRAY);
     let result = ArrayProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidValueSize(expected, read, position))) => {
+        Err(Error::Property {
+            kind:
+                ErrorKind::Deserialize(DeserializeError::LengthMismatch {
+                    expected,
+                    actual: read,
+                    position,
+                }),
+            ..
+        }) => {
             assert_eq!(expected, 0);
             assert_eq!(read, 4);
             assert_eq!(position, 14);
@@ -282,7 +486,15 @@ fn test_invalid_length() {
     let mut reader = Cursor::new(INVALID_LENGTH_SET);
     let result = SetProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidValueSize(expected, read, position))) => {
+        Err(Error::Property {
+            kind:
+                ErrorKind::Deserialize(DeserializeError::LengthMismatch {
+                    expected,
+                    actual: read,
+                    position,
+                }),
+            ..
+        }) => {
             assert_eq!(expected, 0);
             assert_eq!(read, 8);
             assert_eq!(position, 14);
@@ -294,7 +506,15 @@ fn test_invalid_length() {
     let mut reader = Cursor::new(INVALID_LENGTH_MAP);
     let result = MapProperty::read_header(&mut reader, &mut options);
     match result {
-        Err(Error::Deserialize(DeserializeError::InvalidValueSize(expected, read, position))) => {
+        Err(Error::Property {
+            kind:
+                ErrorKind::Deserialize(DeserializeError::LengthMismatch {
+                    expected,
+                    actual: read,
+                    position,
+                }),
+            ..
+        }) => {
             assert_eq!(expected, 0);
             assert_eq!(read, 8);
             assert_eq!(position, 19);
@@ -302,3 +522,223 @@ fn test_invalid_length() {
         _ => panic!("Unexpected result {result:?}"),
     };
 }
+
+#[test]
+fn test_misaligned_array_element() {
+    let element = |name: &str, property: Property| {
+        Property::from(StructPropertyValue::CustomStruct(HashableIndexMap::from([
+            (name.to_string(), vec![property]),
+        ])))
+    };
+    let array = ArrayProperty::new(
+        String::from("StructProperty"),
+        Some((
+            String::from("Elements"),
+            String::from("TestStruct"),
+            Guid::default(),
+        )),
+        vec![
+            element("Value", Property::from(IntProperty::new(1))),
+            element("Value", Property::from(IntProperty::new(2))),
+            element("Name", Property::from(StrProperty::from("misaligned"))),
+        ],
+    )
+    .expect("ArrayProperty::new");
+
+    let mut writer = Cursor::new(Vec::new());
+    array
+        .write(
+            &mut writer,
+            true,
+            &mut PropertyOptions {
+                hints: &HashMap::new(),
+                properties_stack: &mut Vec::new(),
+                custom_versions: &HashableIndexMap::new(),
+                package_version: PackageVersion::default(),
+                read_options: &ReadOptions::default(),
+                write_options: &WriteOptions::default(),
+            },
+        )
+        .expect("Write ArrayProperty");
+    let bytes = writer.into_inner();
+
+    // Lenient reading doesn't check element sizes
+    let mut reader = Cursor::new(bytes.clone());
+    assert_eq!(reader.read_string().expect("Read type"), "ArrayProperty");
+    let result = ArrayProperty::read_header(
+        &mut reader,
+        &mut PropertyOptions {
+            hints: &HashMap::new(),
+            properties_stack: &mut Vec::new(),
+            custom_versions: &HashableIndexMap::new(),
+            package_version: PackageVersion::default(),
+            read_options: &ReadOptions::default(),
+            write_options: &WriteOptions::default(),
+        },
+    );
+    assert_eq!(result.expect("Read ArrayProperty"), array);
+
+    let read_options = ReadOptions {
+        strict_struct_arrays: true,
+        ..Default::default()
+    };
+    let mut reader = Cursor::new(bytes);
+    assert_eq!(reader.read_string().expect("Read type"), "ArrayProperty");
+    let result = ArrayProperty::read_header(
+        &mut reader,
+        &mut PropertyOptions {
+            hints: &HashMap::new(),
+            properties_stack: &mut Vec::new(),
+            custom_versions: &HashableIndexMap::new(),
+            package_version: PackageVersion::default(),
+            read_options: &read_options,
+            write_options: &WriteOptions::default(),
+        },
+    );
+    match result {
+        Err(Error::Property {
+            kind:
+                ErrorKind::Deserialize(DeserializeError::MisalignedArrayElement(
+                    index,
+                    expected,
+                    actual,
+                    _,
+                )),
+            ..
+        }) => {
+            assert_eq!(index, 0);
+            assert_ne!(expected, actual);
+        }
+        _ => panic!("Unexpected result {result:?}"),
+    }
+}
+
+#[test]
+fn test_property_error_path() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/resources/test/features_01.bin"
+    );
+    let read_options = ReadOptions {
+        preset_hints: PresetHints::Disabled,
+        ..Default::default()
+    };
+    let result = GvasFile::open(path, GameVersion::Default, Hints::new(), &read_options);
+    let Err(err) = result else {
+        panic!("Unexpected result {result:?}")
+    };
+
+    let hint_path = "FSDEventRewardsSave.StructProperty.EventsSeen.SetProperty.StructProperty";
+    match &err {
+        Error::Property {
+            path: Some(path),
+            kind: ErrorKind::Deserialize(DeserializeError::MissingHint(_, missing, _)),
+        } => {
+            assert_eq!(path.as_ref(), hint_path);
+            assert_eq!(missing.as_ref(), hint_path);
+        }
+        _ => panic!("Unexpected error {err:?}"),
+    }
+    assert_eq!(err.path(), Some(hint_path));
+    assert!(err
+        .to_string()
+        .ends_with(&format!(" in property {hint_path}")));
+    // The message already includes the cause, which has no cause of its own
+    assert!(err.to_string().starts_with("Missing hint"));
+    assert!(std::error::Error::source(&err).is_none());
+}
+
+#[test]
+fn test_error_source() {
+    let utf8_error = String::from_utf8(vec![0xff]).expect_err("Invalid UTF-8");
+    let err = Error::Property {
+        path: Some("name".into()),
+        kind: DeserializeError::FromUtf8Error(utf8_error, 0x10).into(),
+    };
+    assert_eq!(
+        err.to_string(),
+        "Invalid UTF-8 string at position 0x10 in property name"
+    );
+    // The source skips the cause already included in the message
+    let source = std::error::Error::source(&err).expect("Missing source");
+    assert!(source.is::<std::string::FromUtf8Error>());
+}
+
+#[test]
+fn test_unsupported_version() {
+    let mut data = Vec::new();
+    data.extend(u32::from_le_bytes(*b"GVAS").to_le_bytes());
+    data.extend(1u32.to_le_bytes());
+    let result = GvasFile::read(&mut Cursor::new(data), GameVersion::Default);
+    match result {
+        Err(Error::Unsupported(ErrorKind::Deserialize(DeserializeError::InvalidHeader(
+            reason,
+        )))) => {
+            assert_eq!(reason.as_ref(), "GVAS version 1 not supported");
+        }
+        _ => panic!("Unexpected result {result:?}"),
+    }
+}
+
+#[test]
+fn test_max_string_len() {
+    let file = GvasFile::builder(gvas::GvasHeader::new_ue4_27())
+        .save_game_class_name("/Script/Game.SaveGame")
+        .property("Name", StrProperty::from("a".repeat(100)))
+        .build()
+        .expect("Failed to build file");
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("Failed to write file");
+    let mut data = writer.into_inner();
+    let position = data
+        .windows(101)
+        .position(|window| window[..100].iter().all(|&b| b == b'a') && window[100] == 0)
+        .expect("Missing string value")
+        - 4;
+
+    let read = |data: &[u8], max_string_len| {
+        let read_options = ReadOptions {
+            max_string_len,
+            ..Default::default()
+        };
+        GvasFile::read_with_options(
+            &mut Cursor::new(data),
+            GameVersion::Default,
+            Hints::new(),
+            &read_options,
+        )
+    };
+    assert_eq!(read(&data, Some(101)).expect("Failed to read"), file);
+    let result = read(&data, Some(100));
+    match result {
+        Err(Error::Limit(DeserializeError::StringTooLong(101, 100, offset))) => {
+            assert_eq!(offset, position as u64);
+        }
+        _ => panic!("Unexpected result {result:?}"),
+    }
+
+    // The limit also applies to the save game class name in the header
+    let result = read(&data, Some(21));
+    assert!(
+        matches!(
+            result,
+            Err(Error::Limit(DeserializeError::StringTooLong(22, 21, _)))
+        ),
+        "Unexpected result {result:?}"
+    );
+
+    // Corrupted lengths fail before allocating with the default limit
+    data[position..position + 4].copy_from_slice(&i32::MIN.to_le_bytes());
+    let result = read(&data, None);
+    assert!(
+        matches!(
+            result,
+            Err(Error::Limit(DeserializeError::StringTooLong(
+                0x8000_0000,
+                131072,
+                _
+            )))
+        ),
+        "Unexpected result {result:?}"
+    );
+}
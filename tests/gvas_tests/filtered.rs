@@ -0,0 +1,248 @@
+use std::{fs, io::Cursor, path::Path};
+
+use crate::common::{
+    palworld, profile0, DELEGATE_PATH, PALWORLD_ZLIB_PATH, PALWORLD_ZLIB_TWICE_PATH,
+    PROFILE_0_PATH, SLOT1_PATH, VECTOR2D_PATH,
+};
+use gvas::{
+    game_version::GameVersion,
+    hints::Hints,
+    options::ReadOptions,
+    properties::{unknown_property::UnknownProperty, Property},
+    GvasFile,
+};
+
+fn read(path: &str) -> Vec<u8> {
+    fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join(path)).expect("Failed to read test asset")
+}
+
+fn write(file: &GvasFile) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("Failed to write gvas file");
+    writer.into_inner()
+}
+
+fn compare(path: &str, game_version: GameVersion, hints: Hints) {
+    let data = read(path);
+    let file = GvasFile::read_with_hints(&mut Cursor::new(&data), game_version, &hints)
+        .expect("Failed to parse gvas file");
+    let expected = write(&file);
+
+    // Each root property on its own
+    for (name, property) in file.properties.iter() {
+        let filtered = GvasFile::read_properties_filtered(
+            &mut Cursor::new(&data),
+            game_version,
+            hints.clone(),
+            &ReadOptions::default(),
+            &[name],
+        )
+        .expect("Failed to read filtered properties");
+        assert_eq!(filtered.header, file.header);
+        assert_eq!(filtered.trailing_padding, file.trailing_padding);
+        assert_eq!(filtered.properties.len(), file.properties.len());
+        assert_eq!(&filtered.properties[name.as_str()], property);
+        assert_eq!(write(&filtered), expected, "{name}");
+    }
+
+    let names = file
+        .properties
+        .keys()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    let filtered = GvasFile::read_properties_filtered(
+        &mut Cursor::new(&data),
+        game_version,
+        hints.clone(),
+        &ReadOptions::default(),
+        &names,
+    )
+    .expect("Failed to read filtered properties");
+    assert_eq!(filtered, file);
+
+    let filtered = GvasFile::read_properties_filtered(
+        &mut Cursor::new(&data),
+        game_version,
+        hints,
+        &ReadOptions::default(),
+        &[],
+    )
+    .expect("Failed to read filtered properties");
+    assert!(filtered
+        .properties
+        .values()
+        .all(|property| matches!(property, Property::UnknownProperty(_))));
+    assert_eq!(write(&filtered), expected);
+}
+
+#[test]
+fn filtered_slot1() {
+    compare(SLOT1_PATH, GameVersion::Default, Hints::new());
+}
+
+#[test]
+fn filtered_delegate() {
+    compare(DELEGATE_PATH, GameVersion::Default, Hints::new());
+}
+
+#[test]
+fn filtered_vector2d() {
+    compare(VECTOR2D_PATH, GameVersion::Default, Hints::new());
+}
+
+#[test]
+fn filtered_with_hints() {
+    compare(
+        PROFILE_0_PATH,
+        GameVersion::Default,
+        profile0::hints().into(),
+    );
+}
+
+#[test]
+fn filtered_palworld() {
+    compare(
+        PALWORLD_ZLIB_PATH,
+        GameVersion::Palworld,
+        palworld::hints().into(),
+    );
+}
+
+#[test]
+fn filtered_skips_invalid_bodies() {
+    // An IntProperty tag with a 3 byte body fails to parse
+    let mut file = GvasFile::read(&mut Cursor::new(read(SLOT1_PATH)), GameVersion::Default)
+        .expect("Failed to parse gvas file");
+    file.properties.insert(
+        String::from("invalid"),
+        UnknownProperty::new(String::from("IntProperty"), vec![1, 2, 3]).into(),
+    );
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("Failed to write gvas file");
+    let data = writer.into_inner();
+    assert!(GvasFile::read(&mut Cursor::new(&data), GameVersion::Default).is_err());
+
+    let filtered = GvasFile::read_properties_filtered(
+        &mut Cursor::new(&data),
+        GameVersion::Default,
+        Hints::new(),
+        &ReadOptions::default(),
+        &["str_property"],
+    )
+    .expect("Failed to read filtered properties");
+    assert_eq!(
+        filtered.properties["str_property"],
+        file.properties["str_property"]
+    );
+    assert_eq!(filtered.trailing_padding, file.trailing_padding);
+}
+
+#[test]
+fn filtered_nested_path() {
+    let data = read(PALWORLD_ZLIB_TWICE_PATH);
+    let hints = Hints::from(palworld::hints());
+    let file = GvasFile::read_with_hints(&mut Cursor::new(&data), GameVersion::Palworld, &hints)
+        .expect("Failed to parse gvas file");
+
+    let filtered = GvasFile::read_properties_filtered(
+        &mut Cursor::new(&data),
+        GameVersion::Palworld,
+        hints,
+        &ReadOptions::default(),
+        &["worldSaveData.CharacterSaveParameterMap"],
+    )
+    .expect("Failed to read filtered properties");
+    let fields = filtered
+        .get_property_by_path("worldSaveData")
+        .and_then(|property| match property {
+            Property::StructProperty(property) => property.value.get_custom_struct(),
+            _ => None,
+        })
+        .expect("worldSaveData isn't a struct");
+    for (name, values) in fields {
+        for value in values {
+            match name.as_str() {
+                "CharacterSaveParameterMap" => assert!(
+                    matches!(value, Property::MapProperty(_)),
+                    "{name}: {value:?}"
+                ),
+                _ => assert!(
+                    matches!(value, Property::UnknownProperty(_)),
+                    "{name}: {value:?}"
+                ),
+            }
+        }
+    }
+    assert_eq!(
+        filtered.get_property_by_path("worldSaveData.CharacterSaveParameterMap"),
+        file.get_property_by_path("worldSaveData.CharacterSaveParameterMap")
+    );
+    assert_eq!(write(&filtered), write(&file));
+}
+
+#[test]
+fn filtered_iter() {
+    let data = read(SLOT1_PATH);
+    let read_options = ReadOptions {
+        root_properties: Some(vec![String::from("str_property")]),
+        ..Default::default()
+    };
+    let properties = GvasFile::read_properties_iter(
+        &mut Cursor::new(&data),
+        GameVersion::Default,
+        Hints::new(),
+        &read_options,
+    )
+    .expect("Failed to read header")
+    .map(|property| property.map(|(name, _)| name))
+    .collect::<Result<Vec<_>, _>>()
+    .expect("Failed to read properties");
+    assert_eq!(properties, ["str_property"]);
+}
+
+fn compare_only_properties(path: &str, game_version: GameVersion, hints: Hints) {
+    let data = read(path);
+    let file = GvasFile::read_with_hints(&mut Cursor::new(&data), game_version, &hints)
+        .expect("Failed to parse gvas file");
+    let expected = write(&file);
+
+    for name in file.properties.keys() {
+        let only = GvasFile::read_with_options(
+            &mut Cursor::new(&data),
+            game_version,
+            hints.clone(),
+            &ReadOptions::only_properties(&[name]),
+        )
+        .expect("Failed to read selected properties");
+        assert_eq!(
+            only.properties.keys().collect::<Vec<_>>(),
+            file.properties.keys().collect::<Vec<_>>()
+        );
+        for (other, property) in &only.properties {
+            if other == name {
+                assert_eq!(property, &file.properties[other]);
+            } else {
+                assert!(
+                    matches!(property, Property::UnknownProperty(_)),
+                    "{other}: {property:?}"
+                );
+            }
+        }
+
+        assert_eq!(write(&only), expected, "{name}");
+    }
+}
+
+#[test]
+fn only_properties_slot1() {
+    compare_only_properties(SLOT1_PATH, GameVersion::Default, Hints::new());
+}
+
+#[test]
+fn only_properties_palworld() {
+    compare_only_properties(
+        PALWORLD_ZLIB_PATH,
+        GameVersion::Palworld,
+        palworld::hints().into(),
+    );
+}
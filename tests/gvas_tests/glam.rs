@@ -0,0 +1,61 @@
+#![cfg(feature = "glam")]
+
+use glam::{DQuat, DVec3, Quat, Vec3, Vec4};
+use gvas::properties::{
+    struct_property::StructPropertyValue,
+    struct_types::{LinearColor, QuatF, RotatorD, RotatorF, VectorD, VectorF},
+};
+
+#[test]
+fn vector_conversions() {
+    let vector = VectorF::new(1.0, 2.0, 3.0);
+    assert_eq!(Vec3::from(vector), Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(VectorF::from(Vec3::new(1.0, 2.0, 3.0)), vector);
+    assert_eq!(DVec3::from(vector), DVec3::new(1.0, 2.0, 3.0));
+    assert_eq!(
+        VectorD::from(Vec3::new(1.0, 2.0, 3.0)),
+        VectorD::new(1.0, 2.0, 3.0)
+    );
+
+    let color = LinearColor::new(0.1, 0.2, 0.3, 1.0);
+    assert_eq!(Vec4::from(color), Vec4::new(0.1, 0.2, 0.3, 1.0));
+    assert_eq!(QuatF::from(Quat::IDENTITY), QuatF::new(0.0, 0.0, 0.0, 1.0));
+}
+
+#[test]
+fn rotator_conversions() {
+    // Yaw turns left around Z, pitch and roll follow Unreal's left handed convention
+    let yaw = DQuat::from(RotatorD::new(0.0, 90.0, 0.0));
+    assert!(yaw.abs_diff_eq(DQuat::from_rotation_z(90f64.to_radians()), 1e-12));
+    let pitch = DQuat::from(RotatorD::new(90.0, 0.0, 0.0));
+    assert!(pitch.abs_diff_eq(DQuat::from_rotation_y(-90f64.to_radians()), 1e-12));
+    let roll = DQuat::from(RotatorD::new(0.0, 0.0, 90.0));
+    assert!(roll.abs_diff_eq(DQuat::from_rotation_x(-90f64.to_radians()), 1e-12));
+
+    let rotator = RotatorF::from(Quat::from(RotatorF::new(30.0, 50.0, -70.0)));
+    assert!((rotator.pitch.0 - 30.0).abs() < 1e-4);
+    assert!((rotator.yaw.0 - 50.0).abs() < 1e-4);
+    assert!((rotator.roll.0 + 70.0).abs() < 1e-4);
+}
+
+#[test]
+fn struct_value_precision() {
+    let mut value = StructPropertyValue::VectorF(VectorF::new(1.0, 2.0, 3.0));
+    assert_eq!(value.get_dvec3(), Some(DVec3::new(1.0, 2.0, 3.0)));
+    assert!(value.set_dvec3(DVec3::new(4.0, 5.0, 6.0)));
+    assert_eq!(
+        value,
+        StructPropertyValue::VectorF(VectorF::new(4.0, 5.0, 6.0))
+    );
+
+    let mut value = StructPropertyValue::RotatorD(RotatorD::new(0.0, 0.0, 0.0));
+    assert!(value.set_dquat(DQuat::from_rotation_z(45f64.to_radians())));
+    let StructPropertyValue::RotatorD(rotator) = value else {
+        panic!("Unexpected value {value:?}");
+    };
+    assert!((rotator.yaw.0 - 45.0).abs() < 1e-9);
+
+    let mut value = StructPropertyValue::CustomStruct(Default::default());
+    assert_eq!(value.get_dvec3(), None);
+    assert!(!value.set_dvec3(DVec3::ZERO));
+}
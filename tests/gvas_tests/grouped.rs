@@ -0,0 +1,52 @@
+use std::io::Cursor;
+
+use gvas::{
+    error::Error,
+    game_version::GameVersion,
+    properties::{int_property::IntProperty, Property},
+    GvasFile,
+};
+
+fn group(name: &str) -> &'static str {
+    ["SplineTrack", "Industry", "Frame", "player"]
+        .into_iter()
+        .find(|prefix| name.starts_with(prefix))
+        .unwrap_or("Other")
+}
+
+#[test]
+fn group_by() -> Result<(), Error> {
+    let data = std::fs::read("resources/test/component8.sav")?;
+    let mut file = GvasFile::read(&mut Cursor::new(data), GameVersion::Default)?;
+
+    let view = file.group_by(group);
+    assert_eq!(
+        view.keys().copied().collect::<Vec<_>>(),
+        ["Other", "player", "SplineTrack", "Industry", "Frame"]
+    );
+    let players = view.get(&"player").expect("player group");
+    assert_eq!(players.len(), 5);
+    assert!(players.contains_key("playernamearray"));
+    assert!(view
+        .get_property(&"Industry", "IndustryTypeArray")
+        .is_some());
+    assert_eq!(
+        view.iter().map(|(_, group)| group.len()).sum::<usize>(),
+        file.properties.len()
+    );
+
+    let order = file.properties.keys().cloned().collect::<Vec<_>>();
+    let mut view = file.group_by_mut(group);
+    let property = view
+        .get_property_mut(&"Other", "SaveGameVersion")
+        .expect("SaveGameVersion");
+    *property = Property::from(IntProperty::new(1234));
+
+    assert_eq!(
+        file.properties.get("SaveGameVersion"),
+        Some(&Property::from(IntProperty::new(1234)))
+    );
+    assert_eq!(file.properties.keys().cloned().collect::<Vec<_>>(), order);
+
+    Ok(())
+}
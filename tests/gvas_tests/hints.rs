@@ -0,0 +1,193 @@
+use crate::common::{features, FEATURES_01_PATH};
+use gvas::{
+    error::{DeserializeError, Error, ErrorKind},
+    game_version::GameVersion,
+    hints::{HintValue, Hints},
+    object_version::PackageVersion,
+    options::{PresetHints, ReadOptions, WriteOptions},
+    properties::{
+        int_property::{ByteProperty, BytePropertyValue},
+        map_property::MapProperty,
+        Property, PropertyOptions,
+    },
+    property_file::PropertyFile,
+    types::map::HashableIndexMap,
+    GvasFile,
+};
+use std::{fs, io::Cursor, path::Path};
+
+const MISSIONS_KEY: &str = "UnLockedMissionParameters.MapProperty.Key.StructProperty";
+
+fn read_features(hints: Hints) -> Result<(Vec<u8>, GvasFile), Error> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(FEATURES_01_PATH);
+    let data = fs::read(path)?;
+    let file = GvasFile::read_with_hints(&mut Cursor::new(&data), GameVersion::Default, hints)?;
+    Ok((data, file))
+}
+
+fn read_body(data: &[u8], value_type: &str, hints: &Hints) -> Result<Property, Error> {
+    let mut options = PropertyOptions {
+        hints,
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    Property::new(
+        &mut Cursor::new(data),
+        value_type,
+        false,
+        &mut options,
+        None,
+    )
+}
+
+#[test]
+fn string_hints() {
+    let hints = Hints::from(features::hints());
+    assert_eq!(
+        hints.get(MISSIONS_KEY),
+        Some(&HintValue::Struct(String::from("Guid")))
+    );
+    read_features(hints).expect("Read with string hints");
+}
+
+#[test]
+fn raw_map_keys() -> Result<(), Error> {
+    let mut hints = Hints::from(features::hints());
+    hints.insert(MISSIONS_KEY.to_string(), HintValue::Raw(Some(16)));
+    let (data, file) = read_features(hints)?;
+
+    let Some(Property::MapProperty(MapProperty::Properties { value, .. })) =
+        file.properties.get("UnLockedMissionParameters")
+    else {
+        panic!("Expected a map of properties")
+    };
+    assert!(!value.is_empty());
+    for key in value.keys() {
+        assert!(
+            matches!(key, Property::UnknownProperty(_)),
+            "Expected a raw key, got {key:?}"
+        );
+    }
+
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer)?;
+    assert_eq!(writer.into_inner(), data);
+    Ok(())
+}
+
+#[test]
+fn raw_without_width() {
+    let mut hints = Hints::from(features::hints());
+    hints.insert(MISSIONS_KEY.to_string(), HintValue::Raw(None));
+    let err = read_features(hints).expect_err("Expected a missing hint");
+    match err {
+        Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::MissingHint(value_type, path, _)),
+            ..
+        } => {
+            assert_eq!(value_type.as_ref(), "StructProperty");
+            assert_eq!(path.as_ref(), MISSIONS_KEY);
+        }
+        _ => panic!("Unexpected error {err:?}"),
+    }
+}
+
+#[test]
+fn element_width() -> Result<(), Error> {
+    let mut data = 10u32.to_le_bytes().to_vec();
+    data.extend(b"E::Value1\0");
+
+    let property = read_body(&data, "ByteProperty", &Hints::new())?;
+    assert_eq!(
+        property,
+        Property::from(ByteProperty::new(None, BytePropertyValue::Byte(10)))
+    );
+
+    let hints = Hints::from_iter([("ByteProperty", HintValue::ElementWidth(15))]);
+    let property = read_body(&data, "ByteProperty", &hints)?;
+    assert_eq!(
+        property,
+        Property::from(ByteProperty::new(
+            None,
+            BytePropertyValue::Namespaced(String::from("E::Value1"))
+        ))
+    );
+    Ok(())
+}
+
+#[test]
+fn property_file_hints() -> Result<(), Error> {
+    let (_, file) = read_features(Hints::from(features::hints()))?;
+    let mut hints = Hints::from(features::hints()).into_inner();
+    hints.extend([
+        (
+            String::from("UnLockedMissionParameters.MapProperty.Value.IntProperty"),
+            HintValue::ElementWidth(4),
+        ),
+        (
+            String::from("UnLockedMissionParameters.MapProperty.Value.FooProperty"),
+            HintValue::Raw(None),
+        ),
+        (
+            String::from("UnLockedMissionParameters.MapProperty.Value.BarProperty"),
+            HintValue::Raw(Some(8)),
+        ),
+    ]);
+    let exported = file.export_property("UnLockedMissionParameters", &hints)?;
+    assert!(exported.hints.len() > 3);
+    assert!(exported.hints.contains_key(MISSIONS_KEY));
+
+    let mut writer = Cursor::new(Vec::new());
+    exported.write(&mut writer)?;
+    let imported = PropertyFile::read(&mut Cursor::new(writer.into_inner()))?;
+    assert_eq!(imported, exported);
+    Ok(())
+}
+
+#[test]
+fn deep_rock_galactic_game_version() -> Result<(), Error> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(FEATURES_01_PATH);
+    let data = fs::read(path)?;
+    let read_with = |hints: Hints, read_options: &ReadOptions| {
+        GvasFile::read_with_options(
+            &mut Cursor::new(&data),
+            GameVersion::DeepRockGalactic,
+            hints,
+            read_options,
+        )
+    };
+    let read = |hints: Hints| read_with(hints, &ReadOptions::default());
+
+    let file = read(Hints::new())?;
+    assert_eq!(file, read_features(Hints::from(features::hints()))?.1);
+
+    // Hints passed by the caller take precedence
+    let mut hints = Hints::new();
+    hints.insert(MISSIONS_KEY.to_string(), HintValue::Raw(Some(16)));
+    let file = read(hints)?;
+    let Some(Property::MapProperty(MapProperty::Properties { value, .. })) =
+        file.properties.get("UnLockedMissionParameters")
+    else {
+        panic!("Expected a map of properties")
+    };
+    assert!(value
+        .keys()
+        .all(|key| matches!(key, Property::UnknownProperty(_))));
+
+    // Unless presets are turned off
+    let read_options = ReadOptions {
+        preset_hints: PresetHints::Disabled,
+        ..Default::default()
+    };
+    assert!(matches!(
+        read_with(Hints::new(), &read_options),
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::MissingHint(..)),
+            ..
+        })
+    ));
+    Ok(())
+}
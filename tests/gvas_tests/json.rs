@@ -0,0 +1,137 @@
+#![cfg(feature = "json")]
+
+use gvas::{
+    json::{to_value, PreviewOptions},
+    properties::{array_property::ArrayProperty, name_property::NameProperty, Property},
+};
+use indexmap::IndexMap;
+use serde::{Serialize, Serializer};
+use serde_json::json;
+use std::cell::Cell;
+
+#[test]
+fn unlimited() {
+    let value = json!({"a": [1, 2, 3], "b": {"c": {"d": [4]}}});
+    assert_eq!(to_value(&value, &PreviewOptions::default()).unwrap(), value);
+}
+
+#[test]
+fn max_elements() {
+    let options = PreviewOptions {
+        max_depth: None,
+        max_elements: Some(2),
+        split_names: false,
+    };
+    let value = json!({"a": [1, 2, 3, 4, 5], "b": [1, 2], "c": 3});
+    assert_eq!(
+        to_value(&value, &options).unwrap(),
+        json!({"a": [1, 2, "... 3 more"], "b": [1, 2], "...": "1 more"})
+    );
+}
+
+#[test]
+fn max_depth() {
+    let options = PreviewOptions {
+        max_depth: Some(1),
+        max_elements: None,
+        split_names: false,
+    };
+    let value = json!({"a": [1, [2, 3], {"b": 4}], "c": {"d": {"e": 5, "f": 6}}});
+    assert_eq!(
+        to_value(&value, &options).unwrap(),
+        json!({"a": [1, "[2 elements]", "{1 fields}"], "c": {"d": "{2 fields}"}})
+    );
+
+    let options = PreviewOptions {
+        max_depth: Some(0),
+        max_elements: None,
+        split_names: false,
+    };
+    assert_eq!(
+        to_value(&value, &options).unwrap(),
+        json!({"a": "[3 elements]", "c": "{1 fields}"})
+    );
+}
+
+#[test]
+fn large_array() {
+    let property = Property::from(ArrayProperty::Ints {
+        ints: (0..12_003).collect(),
+    });
+    let properties = IndexMap::from([(String::from("ints"), property)]);
+    let options = PreviewOptions {
+        max_depth: None,
+        max_elements: Some(3),
+        split_names: false,
+    };
+    assert_eq!(
+        to_value(&properties, &options).unwrap(),
+        json!({"ints": {"type": "ArrayProperty", "ints": [0, 1, 2, "... 12000 more"]}})
+    );
+}
+
+#[test]
+fn split_names() {
+    let properties = IndexMap::from([
+        (
+            String::from("name"),
+            Property::from(NameProperty::from("Chest_12")),
+        ),
+        (
+            String::from("names"),
+            Property::from(ArrayProperty::Names {
+                names: vec![Some(String::from("Crate")), None],
+            }),
+        ),
+    ]);
+    let options = PreviewOptions {
+        split_names: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        to_value(&properties, &options).unwrap(),
+        json!({
+            "name": {"type": "NameProperty", "value": {"base": "Chest", "number": 12}},
+            "names": {
+                "type": "ArrayProperty",
+                "names": [{"base": "Crate", "number": null}, null]
+            }
+        })
+    );
+}
+
+/// Counts how often it is serialized.
+struct Counted<'a>(&'a Cell<usize>);
+
+impl Serialize for Counted<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.set(self.0.get() + 1);
+        serializer.serialize_u8(0)
+    }
+}
+
+#[test]
+fn truncated_while_serializing() {
+    let count = Cell::new(0);
+    let elements: Vec<_> = (0..1000).map(|_| Counted(&count)).collect();
+    let options = PreviewOptions {
+        max_elements: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(
+        to_value(&elements, &options).unwrap(),
+        json!([0, 0, 0, "... 997 more"])
+    );
+    assert_eq!(count.get(), 3);
+
+    count.set(0);
+    let options = PreviewOptions {
+        max_depth: Some(0),
+        ..Default::default()
+    };
+    assert_eq!(
+        to_value(&IndexMap::from([("a", &elements)]), &options).unwrap(),
+        json!({"a": "[1000 elements]"})
+    );
+    assert_eq!(count.get(), 0);
+}
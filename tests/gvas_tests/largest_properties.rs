@@ -0,0 +1,40 @@
+use std::{fs::File, io::Cursor};
+
+use gvas::{error::Error, game_version::GameVersion, GvasFile};
+
+use crate::common::SLOT1_PATH;
+
+#[test]
+fn largest_properties() -> Result<(), Error> {
+    let mut file = File::open(SLOT1_PATH)?;
+    let gvas_file = GvasFile::read(&mut file, GameVersion::Default)?;
+
+    let sizes = gvas_file.largest_properties(usize::MAX)?;
+    assert!(sizes.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    assert!(sizes
+        .iter()
+        .any(|(path, _)| path == "struct_property.test_field"));
+
+    // Structs include their fields
+    let size = |path: &str| sizes.iter().find(|(p, _)| p == path).map(|(_, size)| *size);
+    let parent = size("struct_property").expect("struct_property");
+    let field = size("struct_property.test_field").expect("struct_property.test_field");
+    assert!(parent > field);
+
+    // The top-level sizes add up to the serialized properties
+    let mut header = Cursor::new(Vec::new());
+    gvas_file.header.write(&mut header)?;
+    let mut writer = Cursor::new(Vec::new());
+    gvas_file.write(&mut writer)?;
+    let properties_len = writer.get_ref().len() - header.get_ref().len() - "None".len() - 5 - 4;
+    let top_level = sizes
+        .iter()
+        .filter(|(path, _)| !path.contains('.'))
+        .map(|(_, size)| size)
+        .sum::<usize>();
+    assert_eq!(top_level, properties_len);
+
+    let largest = gvas_file.largest_properties(2)?;
+    assert_eq!(largest, sizes[..2]);
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use std::{collections::HashMap, io::Cursor};
+
+use gvas::{
+    cursor_ext::ReadExt,
+    error::{DeserializeError, Error, ErrorKind},
+    object_version::PackageVersion,
+    options::{DuplicateKeyPolicy, ReadOptions, WriteOptions},
+    properties::{
+        int_property::IntProperty,
+        map_property::{MapDuplicate, MapProperty},
+        str_property::StrProperty,
+        Property, PropertyOptions, PropertyTrait,
+    },
+    types::map::HashableIndexMap,
+};
+
+fn map_with_duplicates() -> MapProperty {
+    MapProperty::Properties {
+        key_type: String::from("StrProperty"),
+        value_type: String::from("IntProperty"),
+        allocation_flags: 0,
+        value: HashableIndexMap::from([
+            (
+                Property::from(StrProperty::from("a")),
+                Property::from(IntProperty::new(1)),
+            ),
+            (
+                Property::from(StrProperty::from("b")),
+                Property::from(IntProperty::new(2)),
+            ),
+        ]),
+        duplicates: vec![MapDuplicate {
+            position: 1,
+            key: Property::from(StrProperty::from("a")),
+            value: Property::from(IntProperty::new(3)),
+        }],
+    }
+}
+
+fn read_map(data: &[u8], read_options: &ReadOptions) -> Result<Property, Error> {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut vec!["Map".into()],
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options,
+        write_options: &WriteOptions::default(),
+    };
+    let mut reader = Cursor::new(data);
+    let property_type = reader.read_string()?;
+    Property::new(&mut reader, &property_type, true, &mut options, None)
+}
+
+#[test]
+fn map_duplicates_tolerant() -> Result<(), Error> {
+    let property = map_with_duplicates();
+
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let mut writer = Cursor::new(Vec::new());
+    property.write(&mut writer, true, &mut options)?;
+
+    // The duplicate is written at its position, before "b"
+    let data = writer.get_ref();
+    let find = |needle: &[u8]| {
+        data.windows(needle.len())
+            .position(|window| window == needle)
+    };
+    let duplicate = find(b"\x02\0\0\0a\0\x03\0\0\0").expect("duplicate");
+    assert!(duplicate < find(b"\x02\0\0\0b\0").expect("b"));
+
+    let imported = read_map(writer.get_ref(), &ReadOptions::default())?;
+    assert_eq!(imported, Property::from(property));
+
+    let mut rewritten = Cursor::new(Vec::new());
+    imported.write(&mut rewritten, true, &mut options)?;
+    assert_eq!(rewritten.get_ref(), writer.get_ref());
+
+    Ok(())
+}
+
+#[test]
+fn map_duplicates_strict() -> Result<(), Error> {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let mut writer = Cursor::new(Vec::new());
+    map_with_duplicates().write(&mut writer, true, &mut options)?;
+
+    let read_options = ReadOptions {
+        duplicate_map_keys: DuplicateKeyPolicy::Strict,
+        ..Default::default()
+    };
+    match read_map(writer.get_ref(), &read_options) {
+        Err(Error::Property {
+            kind: ErrorKind::Deserialize(DeserializeError::DuplicateMapKey(key, path, _)),
+            ..
+        }) => {
+            assert!(key.contains("\"a\""), "{key}");
+            assert_eq!(path.as_ref(), "Map.MapProperty");
+        }
+        result => panic!("Unexpected result {result:?}"),
+    }
+
+    Ok(())
+}
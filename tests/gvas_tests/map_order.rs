@@ -0,0 +1,159 @@
+use std::{collections::HashMap, io::Cursor};
+
+use gvas::{
+    cursor_ext::ReadExt,
+    error::Error,
+    object_version::PackageVersion,
+    options::{ReadOptions, WriteOptions},
+    properties::{
+        int_property::IntProperty,
+        map_property::{MapDuplicate, MapProperty},
+        Property, PropertyOptions, PropertyTrait,
+    },
+    types::map::HashableIndexMap,
+};
+
+fn int_map(keys: &[i32]) -> MapProperty {
+    MapProperty::Properties {
+        key_type: String::from("IntProperty"),
+        value_type: String::from("IntProperty"),
+        allocation_flags: 0,
+        value: HashableIndexMap(
+            keys.iter()
+                .map(|key| {
+                    (
+                        Property::from(IntProperty::new(*key)),
+                        Property::from(IntProperty::new(key * 10)),
+                    )
+                })
+                .collect(),
+        ),
+        duplicates: Vec::new(),
+    }
+}
+
+fn str_map(keys: &[&str]) -> MapProperty {
+    MapProperty::StrInt {
+        str_ints: HashableIndexMap(
+            keys.iter()
+                .enumerate()
+                .map(|(index, key)| (String::from(*key), index as i32))
+                .collect(),
+        ),
+    }
+}
+
+fn write(property: &MapProperty, write_options: &WriteOptions) -> Result<Vec<u8>, Error> {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options,
+    };
+    let mut writer = Cursor::new(Vec::new());
+    Property::from(property.clone()).write(&mut writer, true, &mut options)?;
+    Ok(writer.into_inner())
+}
+
+fn read(data: &[u8]) -> Result<Property, Error> {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let mut reader = Cursor::new(data);
+    let property_type = reader.read_string()?;
+    Property::new(&mut reader, &property_type, true, &mut options, None)
+}
+
+fn keys(property: &Property) -> Vec<String> {
+    match property {
+        Property::MapProperty(MapProperty::Properties { value, .. }) => value
+            .keys()
+            .map(|key| match key {
+                Property::IntProperty(key) => key.value.to_string(),
+                key => panic!("Unexpected key {key:?}"),
+            })
+            .collect(),
+        Property::MapProperty(MapProperty::StrInt { str_ints }) => {
+            str_ints.keys().cloned().collect()
+        }
+        property => panic!("Unexpected property {property:?}"),
+    }
+}
+
+#[test]
+fn map_order_preserved() -> Result<(), Error> {
+    let map = int_map(&[3, -1, 20, 2]);
+    let property = read(&write(&map, &WriteOptions::default())?)?;
+    assert_eq!(keys(&property), ["3", "-1", "20", "2"]);
+    assert_eq!(property, Property::from(map));
+
+    let map = str_map(&["b", "c", "a"]);
+    let property = read(&write(&map, &WriteOptions::default())?)?;
+    assert_eq!(keys(&property), ["b", "c", "a"]);
+    Ok(())
+}
+
+#[test]
+fn map_sorted_by_key() {
+    let map = int_map(&[3, -1, 20, 2]);
+    let sorted = map.sorted_by_key();
+    assert_eq!(keys(&sorted.into()), ["-1", "2", "3", "20"]);
+    assert_eq!(keys(&map.clone().into()), ["3", "-1", "20", "2"]);
+
+    let mut map = str_map(&["b", "c", "a"]);
+    map.sort_keys();
+    assert_eq!(keys(&map.into()), ["a", "b", "c"]);
+}
+
+#[test]
+fn map_sort_on_write() -> Result<(), Error> {
+    let map = int_map(&[3, -1, 20, 2]);
+    let write_options = WriteOptions {
+        sort_maps: true,
+        ..Default::default()
+    };
+    let data = write(&map, &write_options)?;
+    assert_eq!(data, write(&map.sorted_by_key(), &WriteOptions::default())?);
+    assert_eq!(keys(&read(&data)?), ["-1", "2", "3", "20"]);
+    Ok(())
+}
+
+#[test]
+fn map_sort_duplicates() -> Result<(), Error> {
+    let mut map = int_map(&[3, 1, 2]);
+    if let MapProperty::Properties { duplicates, .. } = &mut map {
+        duplicates.push(MapDuplicate {
+            position: 1,
+            key: Property::from(IntProperty::new(3)),
+            value: Property::from(IntProperty::new(-3)),
+        });
+    }
+    let write_options = WriteOptions {
+        sort_maps: true,
+        ..Default::default()
+    };
+    let data = write(&map, &write_options)?;
+    assert_eq!(data, write(&map.sorted_by_key(), &WriteOptions::default())?);
+
+    map.sort_keys();
+    assert_eq!(keys(&map.clone().into()), ["1", "2", "3"]);
+    match map {
+        MapProperty::Properties { duplicates, .. } => assert_eq!(
+            duplicates,
+            [MapDuplicate {
+                position: 3,
+                key: Property::from(IntProperty::new(3)),
+                value: Property::from(IntProperty::new(-3)),
+            }]
+        ),
+        map => panic!("Unexpected map {map:?}"),
+    }
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use std::io::Cursor;
+
+use gvas::{
+    error::{Error, ErrorKind, SerializeError},
+    game_version::GameVersion,
+    properties::{int_property::UInt64Property, Property},
+    types::map::KeyComparison,
+    GvasFile,
+};
+
+fn read(path: &str) -> Result<GvasFile, Error> {
+    let data = std::fs::read(path)?;
+    GvasFile::read(&mut Cursor::new(data), GameVersion::Default)
+}
+
+#[test]
+fn minimal_repro() -> Result<(), Error> {
+    let source = read("resources/test/Slot1.sav")?;
+    let repro = source.minimal_repro("struct_property.test_field")?;
+    assert_eq!(repro.header, source.header);
+    assert_eq!(
+        repro.properties.keys().collect::<Vec<_>>(),
+        ["struct_property"]
+    );
+
+    // The repro must survive a round trip with freshly computed lengths
+    let mut writer = Cursor::new(Vec::new());
+    repro.write(&mut writer)?;
+    let read_back = GvasFile::read(&mut Cursor::new(writer.into_inner()), GameVersion::Default)?;
+    assert_eq!(read_back, repro);
+
+    let Some(Property::StructProperty(struct_property)) =
+        read_back.properties.get("struct_property")
+    else {
+        panic!("Missing struct_property");
+    };
+    let fields = struct_property
+        .value
+        .get_custom_struct()
+        .expect("Custom struct");
+    assert_eq!(fields.keys().collect::<Vec<_>>(), ["test_field"]);
+    assert_eq!(
+        read_back.get_property_by_path("struct_property.test_field"),
+        Some(&Property::from(UInt64Property::new(12345)))
+    );
+    Ok(())
+}
+
+#[test]
+fn minimal_repro_first_value() -> Result<(), Error> {
+    let mut source = read("resources/test/Slot1.sav")?;
+    let Some(Property::StructProperty(struct_property)) =
+        source.properties.get_mut("struct_property")
+    else {
+        panic!("Missing struct_property");
+    };
+    struct_property
+        .value
+        .get_custom_struct_mut()
+        .expect("Custom struct")
+        .get_mut("test_field")
+        .expect("Missing test_field")
+        .push(Property::from(UInt64Property::new(6789)));
+
+    // Extracting keeps every value of a static array, a repro only the first one
+    let path = "struct_property.test_field";
+    let count = |file: &GvasFile| match file.properties.get("struct_property") {
+        Some(Property::StructProperty(struct_property)) => struct_property
+            .value
+            .get_custom_struct()
+            .and_then(|fields| fields.get("test_field"))
+            .map_or(0, Vec::len),
+        _ => 0,
+    };
+    assert_eq!(count(&source.extract(&[path])), 2);
+    let repro = source.minimal_repro(path)?;
+    assert_eq!(count(&repro), 1);
+    assert_eq!(
+        repro.get_property_by_path(path),
+        Some(&Property::from(UInt64Property::new(12345)))
+    );
+    Ok(())
+}
+
+#[test]
+fn minimal_repro_missing_property() -> Result<(), Error> {
+    let source = read("resources/test/Slot1.sav")?;
+    let result = source.minimal_repro("struct_property.missing");
+    match result {
+        Err(Error::Property {
+            kind: ErrorKind::Serialize(SerializeError::PropertyNotFound(path)),
+            ..
+        }) => {
+            assert_eq!(path.as_ref(), "struct_property.missing");
+        }
+        _ => panic!("Unexpected result {result:?}"),
+    }
+    Ok(())
+}
+
+#[test]
+fn extract() -> Result<(), Error> {
+    let source = read("resources/test/Slot1.sav")?;
+    let extract = source.extract(&[
+        "struct_property.test_field",
+        "u8_test",
+        "struct_property.missing",
+        "missing",
+    ]);
+    assert_eq!(extract.header, source.header);
+    // Properties keep the order of the source file
+    assert_eq!(
+        extract.properties.keys().collect::<Vec<_>>(),
+        ["u8_test", "struct_property"]
+    );
+    assert_eq!(extract.properties["u8_test"], source.properties["u8_test"]);
+
+    let mut writer = Cursor::new(Vec::new());
+    extract.write(&mut writer)?;
+    let read_back = GvasFile::read(&mut Cursor::new(writer.into_inner()), GameVersion::Default)?;
+    assert_eq!(read_back, extract);
+    assert_eq!(
+        read_back.get_property_by_path("struct_property.test_field"),
+        Some(&Property::from(UInt64Property::new(12345)))
+    );
+
+    // A whole struct is selected by its own path
+    let extract = source.extract(&["struct_property", "struct_property.test_field"]);
+    assert_eq!(
+        extract.properties["struct_property"],
+        source.properties["struct_property"]
+    );
+    assert!(source.extract(&["missing"]).properties.is_empty());
+    Ok(())
+}
+
+#[test]
+fn extract_by() -> Result<(), Error> {
+    let source = read("resources/test/Slot1.sav")?;
+    let path = "STRUCT_PROPERTY.Test_Field";
+    assert!(source.extract(&[path]).properties.is_empty());
+
+    // Names are matched like get_property_by_path_by
+    let comparison = KeyComparison::CaseInsensitive;
+    let expected = source.extract(&["struct_property.test_field"]);
+    assert_eq!(source.extract_by(&[path], comparison), expected);
+    let repro = source.minimal_repro_by(path, comparison)?;
+    assert_eq!(repro, expected);
+    assert_eq!(
+        repro.get_property_by_path_by(path, comparison),
+        source.get_property_by_path_by(path, comparison)
+    );
+    Ok(())
+}
@@ -1,9 +1,74 @@
+mod array_parity;
+mod async_io;
+mod batch;
+mod bool_property;
+mod borrowed;
+mod builder;
+mod bundled_hints;
+mod case_insensitive;
+mod codepage;
+mod containers;
+mod coordinates;
+mod corpus;
+mod custom_version;
+mod deep_size;
+mod deep_tree;
+mod delegate_limits;
+mod diff;
+mod digest;
+mod early_ue4;
+mod empty_bodies;
+mod engine_branch;
+mod engine_version;
+mod enum_usage;
 mod errors;
+mod filtered;
+mod glam;
+mod grouped;
+mod hints;
+mod json;
+mod largest_properties;
+mod map_duplicates;
+mod map_order;
+mod minimal_repro;
 mod name_arrayindex;
+mod name_number;
+mod name_table;
 mod package_version_524;
 mod package_version_525;
+mod palworld;
+mod parse_report;
+mod precision;
+mod presets;
+mod property_file;
+mod property_iter;
+mod property_kind;
+mod query;
+mod quick;
+mod raw_passthrough;
+mod references;
 mod regression_01;
+mod replace_strings;
+mod retarget;
+mod savegame_version;
+mod seek_adapter;
+mod shared_bytes;
+mod shared_context;
+mod shared_structs;
+mod signature;
+mod snapshot;
+mod sqlite;
+mod stable_hash;
+mod statistics;
+mod strip_ue5;
+mod table;
 mod test_cursor;
 mod test_file;
 mod test_guid;
 mod test_property;
+mod testutil;
+mod text_format;
+mod tolerant;
+mod type_names;
+mod uesave;
+mod visitor;
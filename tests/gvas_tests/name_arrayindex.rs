@@ -1,4 +1,6 @@
 use gvas::cursor_ext::ReadExt;
+use gvas::object_version::PackageVersion;
+use gvas::options::{ReadOptions, WriteOptions};
 use gvas::properties::{name_property::NameProperty, PropertyOptions, PropertyTrait};
 use gvas::types::map::HashableIndexMap;
 use std::collections::HashMap;
@@ -13,27 +15,33 @@ fn name_property_with_array_index() {
         0x65, 0x54, 0x6f, 0x77, 0x65, 0x72, 0x5f, 0x42, 0x32, 0x00,
     ];
 
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+
     // Convert the Vec<u8> to a NameProperty
     let mut cursor = Cursor::new(data);
     let property_type = cursor.read_fstring().expect("Failed to read property type");
     assert_eq!(Some(String::from("NameProperty")), property_type);
-    let prop = NameProperty::read(&mut cursor, true).expect("Failed to read NameProperty");
+    let prop =
+        NameProperty::read(&mut cursor, true, &mut options).expect("Failed to read NameProperty");
 
     // Compare the parsed value to its expected value
     assert_eq!(
         NameProperty {
             array_index: 1,
             value: Some("QU91_InvestigateTower_B2".into()),
+            empty_body: false,
         },
         prop
     );
 
     // Convert the NameProperty back to a Vec<u8>
-    let mut options = PropertyOptions {
-        hints: &HashMap::new(),
-        properties_stack: &mut Vec::new(),
-        custom_versions: &HashableIndexMap::new(),
-    };
     let mut writer = Cursor::new(Vec::new());
     prop.write(&mut writer, true, &mut options)
         .expect("Failed to serialize gvas file");
@@ -0,0 +1,38 @@
+use gvas::properties::name_property::{join_name, split_name, NameProperty};
+
+#[test]
+fn split() {
+    assert_eq!(split_name("Chest_12"), ("Chest", Some(12)));
+    assert_eq!(split_name("Chest_0"), ("Chest", Some(0)));
+    assert_eq!(
+        split_name("QU91_InvestigateTower_B2"),
+        ("QU91_InvestigateTower_B2", None)
+    );
+    assert_eq!(split_name("Chest_01"), ("Chest_01", None));
+    assert_eq!(split_name("Chest_"), ("Chest_", None));
+    assert_eq!(split_name("Chest_-1"), ("Chest_-1", None));
+    assert_eq!(split_name("Chest_+1"), ("Chest_+1", None));
+    assert_eq!(split_name("Chest_2147483647"), ("Chest", Some(2147483647)));
+    assert_eq!(split_name("Chest_2147483648"), ("Chest_2147483648", None));
+    assert_eq!(split_name("_7"), ("", Some(7)));
+}
+
+#[test]
+fn join() {
+    for name in ["Chest_12", "Chest", "Chest_01", "A_B_3"] {
+        let (base, number) = split_name(name);
+        assert_eq!(join_name(base, number), name);
+    }
+}
+
+#[test]
+fn name_property() {
+    let name = NameProperty::from("Spawner_4");
+    assert_eq!(name.split(), Some(("Spawner", Some(4))));
+    assert_eq!(NameProperty::from(None).split(), None);
+
+    assert!(name.eq_ignore_number(&NameProperty::from("Spawner_10")));
+    assert!(!name.eq_ignore_number(&NameProperty::from("Spawners_4")));
+    assert!(!name.eq_ignore_number(&NameProperty::from(None)));
+    assert!(NameProperty::from(None).eq_ignore_number(&NameProperty::from(None)));
+}
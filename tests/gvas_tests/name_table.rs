@@ -0,0 +1,155 @@
+use std::{collections::HashMap, fs::File, io::Cursor, path::Path, sync::Arc};
+
+use gvas::{
+    game_version::GameVersion,
+    hints::Hints,
+    name_table::NameTable,
+    object_version::PackageVersion,
+    options::{ReadOptions, WriteOptions},
+    properties::{
+        name_property::NameProperty, object_property::ObjectProperty,
+        type_names::STRUCT_TYPE_NAMES, Property, PropertyOptions, PropertyTrait,
+    },
+    types::map::HashableIndexMap,
+    visitor::{walk, Visitor},
+    GvasFile,
+};
+
+use crate::common::{SLOT1_PATH, VECTOR2D_PATH};
+
+fn read_file(path: &str, read_options: &ReadOptions) -> GvasFile {
+    let mut file = File::open(Path::new(env!("CARGO_MANIFEST_DIR")).join(path)).expect(path);
+    GvasFile::read_with_options(&mut file, GameVersion::Default, Hints::new(), read_options)
+        .expect(path)
+}
+
+/// Collects the type names of every `StructProperty` in a file.
+#[derive(Default)]
+struct TypeNames(Vec<Arc<str>>);
+
+impl Visitor for TypeNames {
+    fn visit_property(&mut self, _path: &str, property: &Property) -> bool {
+        if let Property::StructProperty(property) = property {
+            self.0.push(Arc::clone(&property.type_name));
+        }
+        true
+    }
+}
+
+fn type_names(file: &GvasFile) -> Vec<Arc<str>> {
+    let mut type_names = TypeNames::default();
+    for (name, property) in &file.properties {
+        walk(&mut type_names, name, property);
+    }
+    type_names.0
+}
+
+#[test]
+fn struct_type_names_shared() {
+    for path in [SLOT1_PATH, VECTOR2D_PATH] {
+        let name_table = NameTable::new();
+        let read_options = ReadOptions {
+            name_table: Some(name_table.clone()),
+            ..Default::default()
+        };
+        let file = read_file(path, &read_options);
+        assert_eq!(file, read_file(path, &ReadOptions::default()), "{path}");
+
+        let type_names = type_names(&file);
+        assert!(!type_names.is_empty(), "{path}");
+        for type_name in &type_names {
+            // Well-known type names are shared without the name table
+            if STRUCT_TYPE_NAMES.contains(&&**type_name) {
+                continue;
+            }
+            let interned = name_table.intern(type_name);
+            assert!(Arc::ptr_eq(type_name, &interned), "{path}: {type_name}");
+        }
+    }
+}
+
+#[test]
+fn known_type_names_shared() {
+    let first = type_names(&read_file(VECTOR2D_PATH, &ReadOptions::default()));
+    let second = type_names(&read_file(VECTOR2D_PATH, &ReadOptions::default()));
+    let known = first
+        .iter()
+        .zip(&second)
+        .filter(|(type_name, _)| STRUCT_TYPE_NAMES.contains(&&***type_name))
+        .inspect(|(first, second)| assert!(Arc::ptr_eq(first, second), "{first}"))
+        .count();
+    assert!(known > 0);
+}
+
+fn read_property(property_type: &str, data: &[u8], read_options: &ReadOptions) -> Property {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options,
+        write_options: &WriteOptions::default(),
+    };
+    let mut reader = Cursor::new(data.to_vec());
+    Property::new(&mut reader, property_type, false, &mut options, None).expect(property_type)
+}
+
+fn write_property(property: &Property) -> Vec<u8> {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let mut writer = Cursor::new(Vec::new());
+    property.write(&mut writer, false, &mut options).unwrap();
+    writer.into_inner()
+}
+
+#[test]
+fn names_and_objects_shared() {
+    let name_table = NameTable::new();
+    let read_options = ReadOptions {
+        name_table: Some(name_table.clone()),
+        ..Default::default()
+    };
+
+    let name = Property::from(NameProperty::from("/Game/Items/Sword"));
+    let object = Property::from(ObjectProperty::new("/Game/Items/Sword"));
+    let first_name = read_property("NameProperty", &write_property(&name), &read_options);
+    let second_name = read_property("NameProperty", &write_property(&name), &read_options);
+    let first_object = read_property("ObjectProperty", &write_property(&object), &read_options);
+    assert_eq!(first_name, name);
+    assert_eq!(first_object, object);
+    assert_eq!(name_table.len(), 1);
+
+    let (
+        Property::NameProperty(NameProperty {
+            value: Some(first_name),
+            ..
+        }),
+        Property::NameProperty(NameProperty {
+            value: Some(second_name),
+            ..
+        }),
+        Property::ObjectProperty(ObjectProperty {
+            value: first_object,
+            ..
+        }),
+    ) = (first_name, second_name, first_object)
+    else {
+        panic!("unexpected property types");
+    };
+    assert!(Arc::ptr_eq(&first_name, &second_name));
+    assert!(Arc::ptr_eq(&first_name, &first_object));
+}
+
+#[test]
+fn table_equality() {
+    let name_table = NameTable::new();
+    assert!(name_table.is_empty());
+    assert_eq!(name_table, name_table.clone());
+    assert_ne!(name_table, NameTable::new());
+}
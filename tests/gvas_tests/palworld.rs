@@ -0,0 +1,124 @@
+#![cfg(feature = "palworld")]
+
+use std::{io::Cursor, path::Path};
+
+use crate::common::{palworld::hints, PALWORLD_ZLIB_TWICE_PATH};
+use gvas::{
+    error::{Error, SerializeError},
+    game_version::GameVersion,
+    options::ReadOptions,
+    palworld::{self, CharacterSlot, CHARACTER_CONTAINERS_PATH},
+    properties::{array_property::ArrayProperty, map_property::MapProperty, Property},
+    types::Guid,
+    GvasFile,
+};
+
+fn read_level() -> GvasFile {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(PALWORLD_ZLIB_TWICE_PATH);
+    GvasFile::open(
+        path,
+        GameVersion::Palworld,
+        hints(),
+        &ReadOptions::default(),
+    )
+    .expect("Failed to parse gvas file")
+}
+
+/// Puts a character into the slot at `position` of the first container.
+fn occupy(file: &mut GvasFile, position: usize, player_uid: Guid, instance_id: Guid) {
+    let Some(Property::MapProperty(map)) = file.get_property_by_path_mut(CHARACTER_CONTAINERS_PATH)
+    else {
+        panic!("Expected a character container map");
+    };
+    let MapProperty::Properties { value, .. } = map else {
+        panic!("Expected a struct map");
+    };
+    let (_, container) = value.iter_mut().next().expect("container");
+    let slots = container
+        .get_struct_value_mut()
+        .and_then(|value| value.get_custom_struct_mut())
+        .and_then(|fields| fields.get_mut("Slots"))
+        .and_then(|slots| slots.first_mut());
+    let Some(Property::ArrayProperty(ArrayProperty::Structs { structs, .. })) = slots else {
+        panic!("Expected an array of slots");
+    };
+    let raw = structs[position]
+        .get_custom_struct_mut()
+        .and_then(|fields| fields.get_mut("RawData"))
+        .and_then(|raw| raw.first_mut()?.get_array_mut()?.bytes_mut())
+        .expect("raw data");
+    raw[..16].copy_from_slice(&player_uid.0);
+    raw[16..32].copy_from_slice(&instance_id.0);
+    raw[32] = 7;
+}
+
+#[test]
+fn list_slots() -> Result<(), Error> {
+    let file = read_level();
+    let containers = palworld::container_ids(&file);
+    assert_eq!(containers.len(), 71);
+
+    let slots = palworld::slots(&file, containers[0])?;
+    assert_eq!(slots.len(), 5);
+    assert!(slots.iter().all(CharacterSlot::is_empty));
+    assert_eq!(
+        slots.iter().map(|slot| slot.index).collect::<Vec<_>>(),
+        [0, 1, 2, 3, 4]
+    );
+    Ok(())
+}
+
+#[test]
+fn move_and_delete() -> Result<(), Error> {
+    let mut file = read_level();
+    let container = palworld::container_ids(&file)[0];
+    let player_uid = Guid::from_u128(1);
+    let instance_id = Guid::from_u128(2);
+    occupy(&mut file, 1, player_uid, instance_id);
+
+    let pal = CharacterSlot {
+        index: 1,
+        player_uid,
+        instance_id,
+        permission_tribe_id: 7,
+    };
+    assert_eq!(palworld::slots(&file, container)?[1], pal);
+
+    palworld::move_slot(&mut file, container, 1, 3)?;
+    let slots = palworld::slots(&file, container)?;
+    assert!(slots[1].is_empty());
+    assert_eq!(slots[3], CharacterSlot { index: 3, ..pal });
+
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("Failed to write file");
+    writer.set_position(0);
+    let reread = GvasFile::read_with_hints(&mut writer, GameVersion::Palworld, hints())?;
+    assert_eq!(palworld::slots(&reread, container)?, slots);
+
+    assert_eq!(
+        palworld::delete_slot(&mut file, container, 3)?,
+        CharacterSlot { index: 3, ..pal }
+    );
+    assert!(palworld::slots(&file, container)?
+        .iter()
+        .all(CharacterSlot::is_empty));
+    Ok(())
+}
+
+#[test]
+fn missing_slot() {
+    let mut file = read_level();
+    let container = palworld::container_ids(&file)[0];
+
+    let err = palworld::move_slot(&mut file, container, 0, 5).expect_err("slot 5 doesn't exist");
+    assert!(matches!(
+        err.as_serialize(),
+        Some(SerializeError::PropertyNotFound(path)) if path.ends_with(".Slots[5]")
+    ));
+
+    let err = palworld::slots(&file, Guid::from_u128(1)).expect_err("unknown container");
+    assert!(matches!(
+        err.as_serialize(),
+        Some(SerializeError::PropertyNotFound(_))
+    ));
+}
@@ -0,0 +1,155 @@
+use std::{collections::HashMap, fs::File, io::Cursor, path::Path};
+
+use gvas::{
+    cursor_ext::ReadExt,
+    error::Error,
+    game_version::GameVersion,
+    hints::Hints,
+    object_version::PackageVersion,
+    options::{DelegateLimit, ReadOptions, WriteOptions},
+    parse_report::{ReportCollector, ReportReason},
+    properties::{
+        int_property::IntProperty,
+        map_property::{MapDuplicate, MapProperty},
+        str_property::StrProperty,
+        unknown_property::UnknownProperty,
+        Property, PropertyOptions, PropertyTrait,
+    },
+    types::map::HashableIndexMap,
+    GvasFile,
+};
+
+use crate::common::{DELEGATE_PATH, SLOT1_PATH};
+
+fn read_with_report(
+    path: &str,
+    read_options: &ReadOptions,
+) -> Result<(GvasFile, gvas::parse_report::ParseReport), Error> {
+    let mut file = File::open(Path::new(env!("CARGO_MANIFEST_DIR")).join(path))?;
+    GvasFile::read_with_report(&mut file, GameVersion::Default, Hints::new(), read_options)
+}
+
+fn write_property(property: &Property) -> Result<Vec<u8>, Error> {
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let mut writer = Cursor::new(Vec::new());
+    property.write(&mut writer, true, &mut options)?;
+    Ok(writer.into_inner())
+}
+
+fn read_property(data: &[u8], collector: &ReportCollector) -> Result<Property, Error> {
+    let read_options = ReadOptions {
+        report: Some(collector.clone()),
+        ..Default::default()
+    };
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut vec!["Root".into()],
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &read_options,
+        write_options: &WriteOptions::default(),
+    };
+    let mut reader = Cursor::new(data);
+    let property_type = reader.read_string()?;
+    Property::new(&mut reader, &property_type, true, &mut options, None)
+}
+
+#[test]
+fn report_empty() -> Result<(), Error> {
+    let (file, report) = read_with_report(SLOT1_PATH, &ReadOptions::default())?;
+    assert!(report.is_empty());
+    assert_eq!(
+        file,
+        GvasFile::open(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join(SLOT1_PATH),
+            GameVersion::Default,
+            Hints::new(),
+            &ReadOptions::default(),
+        )?
+    );
+    Ok(())
+}
+
+#[test]
+fn report_truncated_delegates() -> Result<(), Error> {
+    let read_options = ReadOptions {
+        delegate_limit: DelegateLimit::Truncate(1),
+        ..Default::default()
+    };
+    let (_, report) = read_with_report(DELEGATE_PATH, &read_options)?;
+
+    // Only the inline delegate has more than one delegate
+    let lost = report
+        .lost()
+        .map(|entry| (entry.path.as_str(), entry.reason.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        lost,
+        vec![(
+            "MulticastDelegate.MulticastInlineDelegateProperty",
+            ReportReason::TruncatedDelegates(1)
+        )]
+    );
+    assert!(report.entries.iter().all(|entry| entry.length > 0));
+    Ok(())
+}
+
+#[test]
+fn report_unknown_property() -> Result<(), Error> {
+    let data =
+        write_property(&UnknownProperty::new(String::from("Blob"), vec![1, 2, 3, 4]).into())?;
+
+    let collector = ReportCollector::new();
+    read_property(&data, &collector)?;
+
+    let report = collector.take();
+    assert_eq!(report.entries.len(), 1);
+    let entry = &report.entries[0];
+    assert_eq!(entry.path, "Root.Blob");
+    assert_eq!(
+        entry.reason,
+        ReportReason::UnknownProperty(String::from("Blob"))
+    );
+    assert_eq!(entry.offset + entry.length, data.len() as u64);
+    assert_eq!(report.lost().count(), 0);
+    assert!(collector.take().is_empty());
+    Ok(())
+}
+
+#[test]
+fn report_duplicate_map_key() -> Result<(), Error> {
+    let property = MapProperty::Properties {
+        key_type: String::from("StrProperty"),
+        value_type: String::from("IntProperty"),
+        allocation_flags: 0,
+        value: HashableIndexMap::from([(
+            Property::from(StrProperty::from("a")),
+            Property::from(IntProperty::new(1)),
+        )]),
+        duplicates: vec![MapDuplicate {
+            position: 1,
+            key: Property::from(StrProperty::from("a")),
+            value: Property::from(IntProperty::new(2)),
+        }],
+    };
+    let data = write_property(&property.into())?;
+
+    let collector = ReportCollector::new();
+    read_property(&data, &collector)?;
+
+    let report = collector.take();
+    assert_eq!(report.entries.len(), 1);
+    let entry = &report.entries[0];
+    assert_eq!(entry.reason, ReportReason::DuplicateMapKey);
+    // The key string "a" and the value
+    assert_eq!(entry.length, 4 + 2 + 4);
+    assert_eq!(entry.offset + entry.length, data.len() as u64);
+    Ok(())
+}
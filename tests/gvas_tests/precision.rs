@@ -0,0 +1,61 @@
+use std::{fs::File, io::Cursor, path::Path};
+
+use crate::common::TRANSFORM_PATH;
+use gvas::{
+    game_version::GameVersion,
+    options::{PrecisionMismatch, WriteOptions},
+    properties::struct_property::StructPropertyValue,
+    visitor::VisitorMut,
+    GvasFile,
+};
+
+fn read(path: &str) -> GvasFile {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file")
+}
+
+fn write_converted(file: &GvasFile) -> GvasFile {
+    let write_options = WriteOptions {
+        precision_mismatch: PrecisionMismatch::Convert,
+        ..Default::default()
+    };
+    let mut writer = Cursor::new(Vec::new());
+    file.write_with_options(&mut writer, &write_options)
+        .expect("Failed to write file");
+    GvasFile::read(&mut Cursor::new(writer.into_inner()), GameVersion::Default)
+        .expect("Failed to read written file")
+}
+
+#[test]
+fn precision_convert_to_single() {
+    // Large world coordinate values written to a file without the UE5 custom versions
+    let mut file = read(TRANSFORM_PATH);
+    file.header.strip_ue5_fields();
+
+    let mut expected = read(TRANSFORM_PATH);
+    expected.strip_ue5_fields();
+    assert_eq!(write_converted(&file), expected);
+}
+
+#[test]
+fn precision_convert_to_double() {
+    struct DoublePrecision;
+    impl VisitorMut for DoublePrecision {
+        fn visit_struct(&mut self, _path: &str, value: &mut StructPropertyValue) -> bool {
+            value.to_double_precision();
+            true
+        }
+    }
+
+    // Single precision values written to a file with large world coordinates
+    let original = read(TRANSFORM_PATH);
+    let mut file = original.clone();
+    file.strip_ue5_fields();
+    file.header = original.header.clone();
+
+    let mut expected = file.clone();
+    expected.walk_mut(&mut DoublePrecision);
+    assert_ne!(expected, file);
+    assert_eq!(write_converted(&file), expected);
+}
@@ -0,0 +1,89 @@
+use crate::common::{FEATURES_01_PATH, SLOT1_PATH};
+use gvas::{
+    error::Error,
+    game_version::GameVersion,
+    hints::{presets, HintValue, Hints},
+    options::{PresetHints, ReadOptions},
+    properties::{map_property::MapProperty, Property},
+    GvasFile,
+};
+use std::{fs, io::Cursor};
+
+const MISSIONS_KEY: &str = "UnLockedMissionParameters.MapProperty.Key.StructProperty";
+
+#[test]
+fn registry() {
+    assert_eq!(
+        presets::class_names().collect::<Vec<_>>(),
+        ["/Script/FSD.FSDSaveGame", "/Script/Pal.PalWorldSaveGame"]
+    );
+    for preset in presets::PRESETS {
+        assert_eq!(
+            presets::get(preset.class_name).map(|hints| hints.len()),
+            Some(preset.hints.len())
+        );
+    }
+    assert_eq!(presets::get("/Script/Game.SaveGame"), None);
+
+    let preset = presets::for_game_version(GameVersion::DeepRockGalactic).expect("Preset");
+    assert_eq!(preset.class_name, "/Script/FSD.FSDSaveGame");
+    assert_eq!(presets::for_game_version(GameVersion::Default), None);
+}
+
+#[test]
+fn read_with_preset_hints() -> Result<(), Error> {
+    let data = fs::read(FEATURES_01_PATH)?;
+    let file = GvasFile::read_with_preset_hints(
+        &mut Cursor::new(&data),
+        GameVersion::Default,
+        Hints::new(),
+    )?;
+    assert_eq!(
+        file.header.get_save_game_class_name(),
+        "/Script/FSD.FSDSaveGame"
+    );
+    assert!(file.properties.contains_key("UnLockedMissionParameters"));
+
+    // The same as enabling presets in the read options
+    let read_options = ReadOptions {
+        preset_hints: PresetHints::Enabled,
+        ..Default::default()
+    };
+    let read = GvasFile::read_with_options(
+        &mut Cursor::new(&data),
+        GameVersion::Default,
+        Hints::new(),
+        &read_options,
+    )?;
+    assert_eq!(read, file);
+
+    // Hints passed in take precedence over the preset
+    let mut hints = Hints::new();
+    hints.insert(MISSIONS_KEY.to_string(), HintValue::Raw(Some(16)));
+    let file =
+        GvasFile::read_with_preset_hints(&mut Cursor::new(&data), GameVersion::Default, hints)?;
+    let Some(Property::MapProperty(MapProperty::Properties { value, .. })) =
+        file.properties.get("UnLockedMissionParameters")
+    else {
+        panic!("Expected a map of properties")
+    };
+    assert!(value
+        .keys()
+        .all(|key| matches!(key, Property::UnknownProperty(_))));
+    Ok(())
+}
+
+#[test]
+fn read_without_preset() -> Result<(), Error> {
+    let data = fs::read(SLOT1_PATH)?;
+    let file = GvasFile::read_with_preset_hints(
+        &mut Cursor::new(&data),
+        GameVersion::Default,
+        Hints::new(),
+    )?;
+    assert_eq!(
+        file,
+        GvasFile::read(&mut Cursor::new(&data), GameVersion::Default)?
+    );
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::{collections::HashMap, io::Cursor};
+
+use crate::common::{palworld::hints, PALWORLD_ZLIB_TWICE_PATH};
+use gvas::{
+    error::{Error, ErrorKind, SerializeError},
+    game_version::GameVersion,
+    hints::Hints,
+    options::ReadOptions,
+    properties::{int_property::UInt64Property, Property},
+    property_file::PropertyFile,
+    GvasFile,
+};
+
+fn read(path: &str) -> Result<GvasFile, Error> {
+    let data = std::fs::read(path)?;
+    GvasFile::read(&mut Cursor::new(data), GameVersion::Default)
+}
+
+#[test]
+fn export_import_property() -> Result<(), Error> {
+    let source = read("resources/test/Slot1.sav")?;
+    let exported = source.export_property("struct_property.test_field", &HashMap::new())?;
+    assert_eq!(
+        exported.property,
+        Property::from(UInt64Property::new(12345))
+    );
+
+    let mut writer = Cursor::new(Vec::new());
+    exported.write(&mut writer)?;
+    let imported = PropertyFile::read(&mut Cursor::new(writer.into_inner()))?;
+    assert_eq!(imported, exported);
+
+    let mut target = read("resources/test/Slot1.sav")?;
+    target.import_property("struct_property.other_field", imported.clone())?;
+    assert_eq!(
+        target.get_property_by_path("struct_property.other_field"),
+        Some(&exported.property)
+    );
+    target.import_property("copied", imported)?;
+    assert_eq!(target.properties.get("copied"), Some(&exported.property));
+    Ok(())
+}
+
+#[test]
+fn export_missing_property() -> Result<(), Error> {
+    let source = read("resources/test/Slot1.sav")?;
+    let result = source.export_property("struct_property.missing", &HashMap::new());
+    match result {
+        Err(Error::Property {
+            kind: ErrorKind::Serialize(SerializeError::PropertyNotFound(path)),
+            ..
+        }) => {
+            assert_eq!(path.as_ref(), "struct_property.missing");
+        }
+        _ => panic!("Unexpected result {result:?}"),
+    }
+    Ok(())
+}
+
+#[test]
+fn export_nested_property_with_hints() -> Result<(), Error> {
+    let hints = Hints::from(hints()).into_inner();
+    let source = GvasFile::open(
+        PALWORLD_ZLIB_TWICE_PATH,
+        GameVersion::Palworld,
+        hints.clone(),
+        &ReadOptions::default(),
+    )?;
+    let exported = source.export_property("worldSaveData.CharacterSaveParameterMap", &hints)?;
+    let mut keys = exported.hints.keys().collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(
+        keys,
+        [
+            "worldSaveData.StructProperty.CharacterSaveParameterMap.MapProperty.Key.StructProperty",
+            "worldSaveData.StructProperty.CharacterSaveParameterMap.MapProperty.Value.StructProperty",
+        ]
+    );
+
+    let mut writer = Cursor::new(Vec::new());
+    exported.write(&mut writer)?;
+    let imported = PropertyFile::read(&mut Cursor::new(writer.into_inner()))?;
+    assert_eq!(imported, exported);
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use std::{fs, io::Cursor, path::Path};
+
+use crate::common::{palworld, profile0, PALWORLD_ZLIB_PATH, PROFILE_0_PATH, SLOT1_PATH};
+use gvas::{game_version::GameVersion, hints::Hints, options::ReadOptions, GvasFile};
+
+fn read(path: &str) -> Vec<u8> {
+    fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join(path)).expect("Failed to read test asset")
+}
+
+fn compare(path: &str, game_version: GameVersion, hints: Hints) {
+    let data = read(path);
+    let file = GvasFile::read_with_hints(&mut Cursor::new(&data), game_version, &hints)
+        .expect("Failed to parse gvas file");
+
+    let mut cursor = Cursor::new(&data);
+    let mut properties =
+        GvasFile::read_properties_iter(&mut cursor, game_version, hints, &ReadOptions::default())
+            .expect("Failed to read header");
+    assert_eq!(properties.header(), &file.header);
+    assert_eq!(
+        properties.deserialized_game_version(),
+        &file.deserialized_game_version
+    );
+    assert_eq!(properties.trailing_padding(), None);
+
+    let read = properties
+        .by_ref()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Failed to read properties");
+    let expected = file
+        .properties
+        .iter()
+        .map(|(name, property)| (name.clone(), property.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(read, expected);
+    assert_eq!(properties.trailing_padding(), file.trailing_padding);
+    assert!(properties.next().is_none());
+}
+
+#[test]
+fn iterate_slot1() {
+    compare(SLOT1_PATH, GameVersion::Default, Hints::new());
+}
+
+#[test]
+fn iterate_with_hints() {
+    compare(
+        PROFILE_0_PATH,
+        GameVersion::Default,
+        profile0::hints().into(),
+    );
+}
+
+#[test]
+fn iterate_palworld() {
+    compare(
+        PALWORLD_ZLIB_PATH,
+        GameVersion::Palworld,
+        palworld::hints().into(),
+    );
+}
+
+#[test]
+fn iterate_with_prefix() {
+    let mut data = [&9u32.to_le_bytes()[..], b"thumbnail"].concat();
+    data.extend(read(SLOT1_PATH));
+    let mut cursor = Cursor::new(data);
+    let properties = GvasFile::read_properties_iter(
+        &mut cursor,
+        GameVersion::Default,
+        Hints::new(),
+        &ReadOptions::default(),
+    )
+    .expect("Failed to read header");
+    assert_eq!(properties.prefix(), b"\x09\x00\x00\x00thumbnail");
+    assert_eq!(properties.count(), 16);
+}
+
+#[test]
+fn stop_at_first_error() {
+    let mut data = read(SLOT1_PATH);
+    data.truncate(data.len() - 20);
+    let mut cursor = Cursor::new(data);
+    let properties = GvasFile::read_properties_iter(
+        &mut cursor,
+        GameVersion::Default,
+        Hints::new(),
+        &ReadOptions::default(),
+    )
+    .expect("Failed to read header");
+
+    let results = properties.collect::<Vec<_>>();
+    let (last, read) = results.split_last().expect("Expected a result");
+    assert!(last.is_err());
+    assert!(!read.is_empty());
+    assert!(read.iter().all(Result::is_ok));
+}
@@ -0,0 +1,24 @@
+use gvas::properties::property_kind::PropertyKind;
+
+#[test]
+fn property_kind_roundtrip() {
+    for kind in PropertyKind::ALL {
+        assert_eq!(PropertyKind::from_type_name(kind.as_str()), Some(*kind));
+        assert_eq!(kind.to_string(), kind.as_str());
+    }
+}
+
+#[test]
+fn property_kind_rejects_unknown() {
+    for name in [
+        "",
+        "Vector",
+        "IntPropertx",
+        "Int32Property",
+        "UInt8Property",
+        "SoftObjectProperty",
+        "MulticastOtherxDelegateProperty",
+    ] {
+        assert_eq!(PropertyKind::from_type_name(name), None, "{name}");
+    }
+}
@@ -0,0 +1,87 @@
+use std::{fs::File, path::Path};
+
+use crate::common::SLOT1_PATH;
+use gvas::{
+    game_version::GameVersion,
+    properties::{map_property::MapProperty, Property},
+    query::{ParseQueryError, Query},
+    types::map::HashableIndexMap,
+    GvasFile,
+};
+
+fn read_slot1() -> GvasFile {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SLOT1_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file")
+}
+
+fn paths(file: &GvasFile, query: &str) -> Vec<String> {
+    file.select(query)
+        .expect("Failed to parse query")
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect()
+}
+
+#[test]
+fn query_paths() {
+    let file = read_slot1();
+    assert_eq!(paths(&file, "int32_test"), ["int32_test"]);
+    assert_eq!(
+        paths(&file, "u*_test"),
+        ["u8_test", "ushort_test", "uint32_test", "ulong_test"]
+    );
+    assert_eq!(
+        paths(&file, "array_of_structs[1].test_field"),
+        ["array_of_structs[1].test_field"]
+    );
+    assert_eq!(
+        paths(&file, "struct_property.test_field"),
+        ["struct_property.test_field"]
+    );
+    let nested = paths(&file, "**.test_field");
+    assert!(nested.contains(&String::from("struct_property.test_field")));
+    assert!(nested.contains(&String::from("array_of_structs[1].test_field")));
+    assert!(paths(&file, "missing").is_empty());
+}
+
+#[test]
+fn query_map_keys() {
+    let mut file = read_slot1();
+    file.properties.insert(
+        String::from("dbNumbers"),
+        Property::from(MapProperty::StrProperty {
+            value_type: String::from("IntProperty"),
+            str_props: HashableIndexMap::from([
+                (String::from("playtime_a"), Property::from(1)),
+                (String::from("kills"), Property::from(2)),
+                (String::from("playtime_b"), Property::from(3)),
+            ]),
+        }),
+    );
+
+    let matches = file
+        .select("dbNumbers['playtime_*']")
+        .expect("Failed to parse query");
+    assert_eq!(
+        matches,
+        [
+            (String::from("dbNumbers[0]"), &Property::from(1)),
+            (String::from("dbNumbers[2]"), &Property::from(3)),
+        ]
+    );
+    assert_eq!(paths(&file, "dbNumbers[\"kills\"]"), ["dbNumbers[1]"]);
+    assert_eq!(paths(&file, "db*[*]").len(), 3);
+    assert!(paths(&file, "dbNumbers['missing_*']").is_empty());
+}
+
+#[test]
+fn query_syntax_errors() {
+    for (query, position) in [("", 0), ("a..b", 1), ("a[1", 1), ("a[b]", 1), ("a]", 0)] {
+        assert_eq!(
+            query.parse::<Query>(),
+            Err(ParseQueryError { position }),
+            "{query}"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use gvas::{error::Error, game_version::GameVersion, quick};
+
+use crate::common::SLOT1_PATH;
+
+#[test]
+fn update() -> Result<(), Error> {
+    let dir = std::env::temp_dir().join(format!("gvas_test_quick_{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("Slot1.sav");
+    let original = std::fs::read(SLOT1_PATH)?;
+    std::fs::write(&path, &original)?;
+
+    quick::update(&path, GameVersion::Default, |file| {
+        file.set_int("int32_test", 9999)?;
+        file.set_float("f_property", 0.5)?;
+        file.set_str("str_property", "Hallo Welt")?;
+        Ok(())
+    })?;
+
+    let file = quick::read(&path, GameVersion::Default)?;
+    let property = |path| file.get_property_by_path(path);
+    assert_eq!(
+        property("int32_test")
+            .and_then(|p| p.get_int())
+            .map(|p| p.value),
+        Some(9999)
+    );
+    assert_eq!(
+        property("f_property")
+            .and_then(|p| p.get_f32())
+            .map(|p| p.value.0),
+        Some(0.5)
+    );
+    assert_eq!(
+        property("str_property")
+            .and_then(|p| p.get_str())
+            .and_then(|p| p.value.as_deref()),
+        Some("Hallo Welt")
+    );
+    assert_eq!(std::fs::read(dir.join("Slot1.sav.bak"))?, original);
+
+    // A failed edit leaves the file untouched
+    let edited = std::fs::read(&path)?;
+    let result = quick::update(&path, GameVersion::Default, |file| {
+        file.set_int("int32_test", 1)?;
+        file.set_int("str_property", 1)
+    });
+    assert!(
+        matches!(result, Err(ref e) if e.as_serialize().is_some()),
+        "{result:?}"
+    );
+    let result = quick::update(&path, GameVersion::Default, |file| {
+        file.set_bool("missing", true)
+    });
+    assert!(
+        matches!(result, Err(ref e) if e.as_serialize().is_some()),
+        "{result:?}"
+    );
+    assert_eq!(std::fs::read(&path)?, edited);
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
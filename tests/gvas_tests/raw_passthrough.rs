@@ -0,0 +1,224 @@
+use std::{fs, io::Cursor, path::Path};
+
+use crate::common::{
+    palworld, profile0, DELEGATE_PATH, PALWORLD_ZLIB_PATH, PROFILE_0_PATH, SLOT1_PATH,
+    VECTOR2D_PATH,
+};
+use gvas::{
+    game_version::GameVersion,
+    hints::Hints,
+    options::ReadOptions,
+    parse_report::{ReportCollector, ReportReason},
+    properties::{unknown_property::UnknownProperty, Property},
+    GvasFile,
+};
+
+fn read(path: &str) -> Vec<u8> {
+    fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join(path)).expect("Failed to read test asset")
+}
+
+fn write(file: &GvasFile) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("Failed to write gvas file");
+    writer.into_inner()
+}
+
+fn compare(path: &str, game_version: GameVersion, hints: Hints) {
+    let data = read(path);
+    let file = GvasFile::read_with_hints(&mut Cursor::new(&data), game_version, &hints)
+        .expect("Failed to parse gvas file");
+    let expected = write(&file);
+
+    let read_options = ReadOptions {
+        raw_passthrough: true,
+        ..Default::default()
+    };
+    for name in file.properties.keys() {
+        let (filtered, report) = GvasFile::read_with_report(
+            &mut Cursor::new(&data),
+            game_version,
+            hints.clone(),
+            &ReadOptions {
+                root_properties: Some(vec![name.clone()]),
+                ..read_options.clone()
+            },
+        )
+        .expect("Failed to read filtered properties");
+        assert_eq!(
+            &filtered.properties[name.as_str()],
+            &file.properties[name.as_str()]
+        );
+        assert_eq!(filtered.properties.len(), file.properties.len());
+        for (other, property) in filtered
+            .properties
+            .iter()
+            .filter(|(other, _)| *other != name)
+        {
+            match property {
+                Property::UnknownProperty(property) => assert!(property.tag().is_some()),
+                property => panic!("Unexpected property {other}: {property:?}"),
+            }
+        }
+        assert_eq!(report.lost().count(), 0);
+        assert_eq!(
+            report
+                .entries
+                .iter()
+                .filter(|entry| entry.reason == ReportReason::RawProperty)
+                .count(),
+            file.properties.len() - 1
+        );
+        assert_eq!(write(&filtered), expected);
+    }
+}
+
+#[test]
+fn raw_passthrough_slot1() {
+    compare(SLOT1_PATH, GameVersion::Default, Hints::new());
+}
+
+#[test]
+fn raw_passthrough_delegate() {
+    compare(DELEGATE_PATH, GameVersion::Default, Hints::new());
+}
+
+#[test]
+fn raw_passthrough_vector2d() {
+    compare(VECTOR2D_PATH, GameVersion::Default, Hints::new());
+}
+
+#[test]
+fn raw_passthrough_with_hints() {
+    compare(
+        PROFILE_0_PATH,
+        GameVersion::Default,
+        profile0::hints().into(),
+    );
+}
+
+#[test]
+fn raw_passthrough_palworld() {
+    compare(
+        PALWORLD_ZLIB_PATH,
+        GameVersion::Palworld,
+        palworld::hints().into(),
+    );
+}
+
+#[test]
+fn raw_passthrough_skipped_report() {
+    // Filtered out properties are kept as raw bytes without the option
+    let data = read(SLOT1_PATH);
+    let read_options = ReadOptions {
+        root_properties: Some(vec![String::from("str_property")]),
+        ..Default::default()
+    };
+    let (file, report) = GvasFile::read_with_report(
+        &mut Cursor::new(&data),
+        GameVersion::Default,
+        Hints::new(),
+        &read_options,
+    )
+    .expect("Failed to read filtered properties");
+    assert_eq!(report.lost().count(), 0);
+    assert!(report
+        .entries
+        .iter()
+        .all(|entry| entry.reason == ReportReason::RawProperty && entry.path != "str_property"));
+    assert_eq!(write(&file), data);
+
+    // Property iterators leave them out
+    let report = ReportCollector::new();
+    let read_options = ReadOptions {
+        report: Some(report.clone()),
+        ..read_options
+    };
+    let names = GvasFile::read_properties_iter(
+        &mut Cursor::new(&data),
+        GameVersion::Default,
+        Hints::new(),
+        &read_options,
+    )
+    .expect("Failed to read header")
+    .map(|property| property.map(|(name, _)| name))
+    .collect::<Result<Vec<_>, _>>()
+    .expect("Failed to read properties");
+    assert_eq!(names, ["str_property"]);
+    let report = report.take();
+    assert!(!report.entries.is_empty());
+    assert!(report
+        .entries
+        .iter()
+        .all(|entry| entry.reason == ReportReason::SkippedProperty));
+}
+
+#[test]
+fn raw_passthrough_unknown_tag() {
+    // A property of an unknown type with an array index, which isn't written by generated tags
+    let mut tag = Vec::new();
+    tag.extend_from_slice(&3u32.to_le_bytes());
+    tag.extend_from_slice(&1u32.to_le_bytes());
+    tag.push(0);
+    let property = UnknownProperty::with_tag(String::from("LazyObjectProperty"), tag, [1, 2, 3]);
+
+    let mut file = GvasFile::read(&mut Cursor::new(read(SLOT1_PATH)), GameVersion::Default)
+        .expect("Failed to parse gvas file");
+    file.properties
+        .insert(String::from("Lazy"), property.clone().into());
+    let data = write(&file);
+
+    let read_options = ReadOptions {
+        raw_passthrough: true,
+        ..Default::default()
+    };
+    let read = GvasFile::read_with_options(
+        &mut Cursor::new(&data),
+        GameVersion::Default,
+        Hints::new(),
+        &read_options,
+    )
+    .expect("Failed to parse gvas file");
+    assert_eq!(read.properties["Lazy"], Property::from(property));
+    assert_eq!(write(&read), data);
+}
+
+#[test]
+fn raw_passthrough_tag_length() {
+    // The tag declares a shorter body than the one stored
+    let mut tag = Vec::new();
+    tag.extend_from_slice(&3u32.to_le_bytes());
+    tag.extend_from_slice(&0u32.to_le_bytes());
+    tag.push(0);
+    let property = UnknownProperty::with_tag(
+        String::from("LazyObjectProperty"),
+        tag.clone(),
+        [1, 2, 3, 4, 5],
+    );
+
+    let mut file = GvasFile::read(&mut Cursor::new(read(SLOT1_PATH)), GameVersion::Default)
+        .expect("Failed to parse gvas file");
+    file.properties
+        .insert(String::from("Lazy"), property.into());
+    let data = write(&file);
+
+    let read_options = ReadOptions {
+        raw_passthrough: true,
+        ..Default::default()
+    };
+    let read = GvasFile::read_with_options(
+        &mut Cursor::new(&data),
+        GameVersion::Default,
+        Hints::new(),
+        &read_options,
+    )
+    .expect("Failed to parse gvas file");
+    tag[..4].copy_from_slice(&5u32.to_le_bytes());
+    assert_eq!(
+        read.properties["Lazy"],
+        Property::from(UnknownProperty::with_tag(
+            String::from("LazyObjectProperty"),
+            tag,
+            [1, 2, 3, 4, 5],
+        ))
+    );
+}
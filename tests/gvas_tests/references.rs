@@ -0,0 +1,109 @@
+use std::io::Cursor;
+
+use gvas::{
+    cursor_ext::WriteExt,
+    error::Error,
+    properties::{
+        array_property::ArrayProperty, object_property::ObjectProperty,
+        unknown_property::UnknownProperty, Property,
+    },
+    references::{self, Reference, ReferenceKind},
+};
+
+use crate::common::delegate;
+
+const DELEGATE_STR: &str =
+    "/Temp/UEDPIE_0_Untitled_1.Untitled_1:PersistentLevel.Saver1_Blueprint_2";
+
+fn reference(path: &str, kind: ReferenceKind) -> Reference {
+    Reference {
+        path: String::from(path),
+        kind,
+    }
+}
+
+fn soft_object(strings: &[&str]) -> Result<Property, Error> {
+    let mut raw = Cursor::new(Vec::new());
+    for string in strings {
+        raw.write_string(string)?;
+    }
+    Ok(UnknownProperty::new(String::from("SoftObjectProperty"), raw.into_inner()).into())
+}
+
+#[test]
+fn references_delegates() {
+    let graph = delegate::expected().references();
+    assert_eq!(graph.objects.len(), 1);
+    assert_eq!(graph.reference_count(), 4);
+    assert_eq!(
+        graph.objects[DELEGATE_STR],
+        vec![
+            reference(
+                "DynamicDelegate",
+                ReferenceKind::Delegate(String::from("FirstBinding"))
+            ),
+            reference(
+                "MulticastDelegate[0]",
+                ReferenceKind::Delegate(String::from("FirstBinding"))
+            ),
+            reference(
+                "MulticastDelegate[1]",
+                ReferenceKind::Delegate(String::from("SecondBinding"))
+            ),
+            reference(
+                "MulticastSparseDelegate[0]",
+                ReferenceKind::Delegate(String::from("FirstBinding"))
+            ),
+        ]
+    );
+}
+
+#[test]
+fn references_objects() -> Result<(), Error> {
+    let property = Property::from(ArrayProperty::new(
+        String::from("ObjectProperty"),
+        None,
+        vec![
+            Property::from(ObjectProperty::from("/Game/A.A")),
+            Property::from(ObjectProperty::from("None")),
+            soft_object(&["/Game/B.B", ""])?,
+            soft_object(&["/Game/C", "C", "Component"])?,
+            Property::from(ObjectProperty::from("/Game/A.A")),
+        ],
+    )?);
+    let graph = references::collect("Objects", &property);
+
+    let objects = graph.objects.keys().map(String::as_str).collect::<Vec<_>>();
+    assert_eq!(objects, ["/Game/A.A", "/Game/B.B", "/Game/C.C:Component"]);
+    assert_eq!(
+        graph.objects["/Game/A.A"],
+        vec![
+            reference("Objects[0]", ReferenceKind::Object),
+            reference("Objects[4]", ReferenceKind::Object),
+        ]
+    );
+    assert_eq!(
+        graph.objects["/Game/C.C:Component"],
+        vec![reference("Objects[3]", ReferenceKind::SoftObject)]
+    );
+    Ok(())
+}
+
+#[test]
+fn references_dot() -> Result<(), Error> {
+    let property = Property::from(ObjectProperty::from("/Game/\"Quoted\".A"));
+    let mut dot = Vec::new();
+    references::collect("Target", &property).write_dot(&mut dot)?;
+    assert_eq!(
+        String::from_utf8(dot).expect("DOT is valid UTF-8"),
+        concat!(
+            "digraph references {\n",
+            "    \"Target\" [shape=ellipse];\n",
+            "    node [shape=box];\n",
+            "    \"/Game/\\\"Quoted\\\".A\";\n",
+            "    \"Target\" -> \"/Game/\\\"Quoted\\\".A\" [label=\"Target\"];\n",
+            "}\n",
+        )
+    );
+    Ok(())
+}
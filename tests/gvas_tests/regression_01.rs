@@ -5,7 +5,14 @@ use std::{
     path::Path,
 };
 
-use gvas::GvasFile;
+use gvas::{
+    properties::{
+        int_property::IntProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        Property,
+    },
+    GvasFile,
+};
 
 #[test]
 fn regression_01_guid() {
@@ -50,12 +57,19 @@ fn regression_01_guid() {
     let written_guid = read_back
         .properties
         .get("Thing")
-        .expect("Failed to get test property in written asset")
-        .get_struct()
-        .expect("Failed to cast property from written asset to the correct type")
-        .value
-        .get_guid()
+        .and_then(Property::get_guid)
         .expect("Failed to get property from written asset as Guid");
 
-    assert_eq!(original_guid, written_guid);
+    assert_eq!(*original_guid, written_guid);
+    assert_eq!(
+        file.properties["Thing"]
+            .get_struct()
+            .and_then(StructProperty::as_guid),
+        Some(written_guid)
+    );
+    assert_eq!(
+        Property::from(StructPropertyValue::Guid(written_guid)).get_guid(),
+        Some(written_guid)
+    );
+    assert_eq!(Property::from(IntProperty::new(0)).get_guid(), None);
 }
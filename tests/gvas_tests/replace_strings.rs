@@ -0,0 +1,44 @@
+use std::{fs::File, io::Cursor, path::Path};
+
+use crate::common::SLOT1_PATH;
+use gvas::{
+    game_version::GameVersion,
+    properties::{array_property::ArrayProperty, str_property::StrProperty, Property},
+    GvasFile,
+};
+
+#[test]
+fn replace_strings() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SLOT1_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    let mut file =
+        GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file");
+
+    let mut paths = Vec::new();
+    let replaced = file.replace_strings(|path, value| {
+        paths.push(path.to_string());
+        // Non-ASCII strings are written as UTF-16, changing their length
+        value
+            .contains("world")
+            .then(|| value.replace("world", "w\u{f6}rld \u{1F30D}"))
+    });
+    assert_eq!(replaced, 4);
+    assert!(paths.contains(&String::from("str_property")));
+    assert!(paths.contains(&String::from("array_of_strings[2]")));
+    assert_eq!(
+        file.properties["str_property"],
+        Property::from(StrProperty::from("Hello w\u{f6}rld \u{1F30D}"))
+    );
+
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("Failed to write file");
+    let read_back = GvasFile::read(&mut Cursor::new(writer.into_inner()), GameVersion::Default)
+        .expect("Failed to read written file");
+    assert_eq!(read_back, file);
+    assert_eq!(
+        read_back.properties["array_of_strings"],
+        Property::from(ArrayProperty::Strings {
+            strings: vec![Some(String::from("Hello w\u{f6}rld \u{1F30D} from array")); 3],
+        })
+    );
+}
@@ -0,0 +1,236 @@
+use std::{fs::File, io::Cursor, path::Path};
+
+use crate::common::{SLOT1_PATH, SLOT2_PATH, TRANSFORM_PATH};
+use gvas::{
+    custom_version::{CustomVersionTrait, FUE5ReleaseStreamObjectVersion},
+    engine_version::FEngineVersion,
+    error::SerializeError,
+    game_version::GameVersion,
+    properties::{
+        int_property::IntProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        struct_types::{VectorD, VectorF},
+        text_property::{FText, FTextHistory, FormatArgumentValue, TextProperty},
+        Property,
+    },
+    types::Guid,
+    GvasFile, GvasHeader,
+};
+
+fn read(path: &str) -> GvasFile {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file")
+}
+
+fn write_and_read(file: &GvasFile) -> GvasFile {
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("Failed to write file");
+    GvasFile::read(&mut Cursor::new(writer.into_inner()), GameVersion::Default)
+        .expect("Failed to read written file")
+}
+
+fn ordered_format(arguments: Vec<FormatArgumentValue>) -> Property {
+    Property::from(TextProperty::new(FText {
+        flags: 0,
+        history: FTextHistory::OrderedFormat {
+            source_format: Box::new(FText::new_base(0, None, None, Some(String::from("{0}")))),
+            arguments,
+        },
+    }))
+}
+
+fn arguments(file: &GvasFile) -> &[FormatArgumentValue] {
+    match file.properties.get("text") {
+        Some(Property::TextProperty(TextProperty {
+            value:
+                FText {
+                    history: FTextHistory::OrderedFormat { arguments, .. },
+                    ..
+                },
+            ..
+        })) => arguments,
+        property => panic!("Unexpected property {property:?}"),
+    }
+}
+
+#[test]
+fn retarget_to_ue4() {
+    let file = read(TRANSFORM_PATH);
+    let mut header = file.header.clone();
+    header.strip_ue5_fields();
+
+    let retargeted = file.retarget(header).expect("Failed to retarget");
+    let mut expected = read(TRANSFORM_PATH);
+    expected.strip_ue5_fields();
+    assert_eq!(retargeted, expected);
+    assert_eq!(write_and_read(&retargeted), retargeted);
+
+    // Converting back restores double precision values
+    let restored = retargeted
+        .retarget(file.header.clone())
+        .expect("Failed to retarget");
+    assert_eq!(restored.header, file.header);
+    assert_eq!(write_and_read(&restored), restored);
+}
+
+#[test]
+fn retarget_text_arguments() {
+    let mut file = read(SLOT1_PATH);
+    let narrow_header = file.header.clone();
+    assert!(!narrow_header
+        .supports_version(FUE5ReleaseStreamObjectVersion::TextFormatArgumentData64bitSupport));
+    let mut wide_header = narrow_header.clone();
+    match &mut wide_header {
+        GvasHeader::Version2 {
+            custom_versions, ..
+        }
+        | GvasHeader::Version3 {
+            custom_versions, ..
+        } => {
+            custom_versions.insert(
+                FUE5ReleaseStreamObjectVersion::GUID,
+                FUE5ReleaseStreamObjectVersion::TextFormatArgumentData64bitSupport.into(),
+            );
+        }
+    }
+
+    file.properties.insert(
+        String::from("text"),
+        ordered_format(vec![
+            FormatArgumentValue::Int(-5),
+            FormatArgumentValue::UInt(7),
+        ]),
+    );
+    let wide = file.retarget(wide_header).expect("Failed to retarget");
+    assert_eq!(
+        arguments(&wide),
+        [
+            FormatArgumentValue::Int64(-5),
+            FormatArgumentValue::UInt64(7)
+        ]
+    );
+    assert_eq!(write_and_read(&wide), wide);
+
+    let narrow = wide
+        .retarget(narrow_header.clone())
+        .expect("Failed to retarget");
+    assert_eq!(narrow, file);
+
+    file.properties.insert(
+        String::from("text"),
+        ordered_format(vec![FormatArgumentValue::Int64(1 << 40)]),
+    );
+    let result = file.retarget(narrow_header);
+    let err = result.expect_err("Expected an overflowing argument");
+    assert!(
+        matches!(err.as_serialize(), Some(SerializeError::InvalidValue(_))),
+        "Unexpected error {err:?}"
+    );
+}
+
+fn vector(value: StructPropertyValue) -> StructProperty {
+    StructProperty::new(Guid::default(), String::from("Vector"), value)
+}
+
+#[test]
+fn transplant() {
+    let source = read(SLOT1_PATH);
+    let mut target = read(SLOT2_PATH);
+    target
+        .transplant_from(&source, "struct_property")
+        .expect("Failed to transplant");
+    assert_eq!(
+        target.properties["struct_property"],
+        source.properties["struct_property"]
+    );
+    assert_eq!(write_and_read(&target), target);
+
+    // Nested properties replace the field of an existing struct
+    let mut target = read(SLOT1_PATH);
+    target.properties.insert(
+        String::from("int32_test"),
+        Property::from(IntProperty::new(1)),
+    );
+    target
+        .transplant_from(&source, "int32_test")
+        .expect("Failed to transplant");
+    target
+        .transplant_from(&source, "struct_property.test_field")
+        .expect("Failed to transplant");
+    assert_eq!(target, source);
+
+    let err = target
+        .transplant_from(&source, "missing")
+        .expect_err("Expected a missing property");
+    assert!(
+        matches!(
+            err.as_serialize(),
+            Some(SerializeError::PropertyNotFound(_))
+        ),
+        "Unexpected error {err:?}"
+    );
+}
+
+#[test]
+fn transplant_precision() {
+    let source = GvasFile::builder(GvasHeader::new_ue5_3())
+        .save_game_class_name("/Script/Game.SaveGame")
+        .property(
+            "Location",
+            vector(StructPropertyValue::from(VectorD::new(1.0, 2.0, 3.0))),
+        )
+        .build()
+        .expect("Failed to build source");
+    // A UE5 save written before large world coordinates
+    let mut target = GvasFile::builder(
+        GvasHeader::new_ue5_0().custom_version(FUE5ReleaseStreamObjectVersion::GUID, 0),
+    )
+    .save_game_class_name("/Script/Game.SaveGame")
+    .build()
+    .expect("Failed to build target");
+
+    target
+        .transplant_from(&source, "Location")
+        .expect("Failed to transplant");
+    assert_eq!(
+        target.properties["Location"],
+        Property::from(vector(StructPropertyValue::from(VectorF::new(
+            1.0, 2.0, 3.0
+        ))))
+    );
+    assert_eq!(write_and_read(&target), target);
+
+    // UE5 values are narrowed for UE4 saves
+    let mut ue4 = read(SLOT1_PATH);
+    ue4.transplant_from(&source, "Location")
+        .expect("Failed to transplant");
+    assert_eq!(ue4.properties["Location"], target.properties["Location"]);
+    assert_eq!(write_and_read(&ue4), ue4);
+
+    // And UE4 values widened for UE5 saves
+    let mut ue5 = source.clone();
+    ue5.properties.clear();
+    ue5.transplant_from(&ue4, "Location")
+        .expect("Failed to transplant");
+    assert_eq!(ue5, source);
+
+    // Other engines are not compatible
+    let mut other = GvasFile::builder(GvasHeader::new_ue5_3().engine_version(FEngineVersion::new(
+        3,
+        0,
+        0,
+        0,
+        String::new(),
+    )))
+    .save_game_class_name("/Script/Game.SaveGame")
+    .build()
+    .expect("Failed to build target");
+    let err = other
+        .transplant_from(&source, "Location")
+        .expect_err("Expected incompatible engine versions");
+    assert!(
+        matches!(err.as_serialize(), Some(SerializeError::InvalidValue(_))),
+        "Unexpected error {err:?}"
+    );
+}
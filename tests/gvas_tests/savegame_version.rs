@@ -0,0 +1,69 @@
+use std::{fs, io::Cursor, path::Path};
+
+use crate::common::{vector2d, VECTOR2D_PATH};
+use gvas::{
+    error::Error, game_version::GameVersion, hints::Hints, options::ReadOptions, GvasFile,
+    GvasHeader,
+};
+
+/// The vector2d save with the save game file version set to `version`.
+fn with_version(version: u32) -> Vec<u8> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(VECTOR2D_PATH);
+    let mut data = fs::read(path).expect("Failed to read test asset");
+    data[4..8].copy_from_slice(&version.to_le_bytes());
+    data
+}
+
+fn read(data: Vec<u8>, read_options: &ReadOptions) -> Result<GvasFile, Error> {
+    GvasFile::read_with_options(
+        &mut Cursor::new(data),
+        GameVersion::Default,
+        Hints::new(),
+        read_options,
+    )
+}
+
+#[test]
+fn save_game_version_default_range() {
+    let result = read(with_version(4), &ReadOptions::default());
+    assert!(
+        matches!(result, Err(Error::Unsupported(_))),
+        "Unexpected result {result:?}"
+    );
+}
+
+#[test]
+fn save_game_version_custom_range() -> Result<(), Error> {
+    let read_options = ReadOptions {
+        save_game_versions: Some(2..=4),
+        ..Default::default()
+    };
+    let data = with_version(4);
+    let file = read(data.clone(), &read_options)?;
+    let mut expected = vector2d::expected();
+    if let GvasHeader::Version3 {
+        save_game_file_version,
+        ..
+    } = &mut expected.header
+    {
+        *save_game_file_version = 4;
+    }
+    assert_eq!(file, expected);
+
+    // The version is written back unchanged
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer)?;
+    assert_eq!(writer.into_inner(), data);
+
+    // Versions without custom versions can't be read
+    let read_options = ReadOptions {
+        save_game_versions: Some(0..=4),
+        ..Default::default()
+    };
+    let result = read(with_version(1), &read_options);
+    assert!(
+        matches!(result, Err(Error::Unsupported(_))),
+        "Unexpected result {result:?}"
+    );
+    Ok(())
+}
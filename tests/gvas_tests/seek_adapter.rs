@@ -0,0 +1,159 @@
+use std::{
+    fs,
+    io::{Cursor, ErrorKind, Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+use crate::common::{
+    palworld, DELEGATE_PATH, PALWORLD_ZLIB_PATH, PROFILE_0_PATH, SLOT1_PATH, VECTOR2D_PATH,
+};
+use gvas::{
+    game_version::GameVersion, hints::Hints, options::ReadOptions,
+    seek_adapter::BufferedSeekAdapter, GvasFile,
+};
+
+/// A reader which can't seek, like stdin.
+struct Unseekable(Cursor<Vec<u8>>);
+
+impl Read for Unseekable {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+/// A writer which can't seek, like stdout.
+struct UnseekableWriter<'a>(&'a mut Vec<u8>);
+
+impl Write for UnseekableWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn read_data(path: &str) -> Vec<u8> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    fs::read(path).expect("Failed to read test asset")
+}
+
+fn assert_same_file(path: &str, game_version: GameVersion, hints: Hints) {
+    let data = read_data(path);
+    let options = ReadOptions::default();
+    let expected = GvasFile::read_with_options(
+        &mut Cursor::new(data.clone()),
+        game_version,
+        hints.clone(),
+        &options,
+    )
+    .expect("Failed to parse gvas file");
+
+    let mut reader = BufferedSeekAdapter::with_lookback(Unseekable(Cursor::new(data)), 16);
+    let file = GvasFile::read_with_options(&mut reader, GameVersion::Auto, hints, &options)
+        .expect("Failed to parse gvas file through the adapter");
+    assert_eq!(file, expected);
+}
+
+#[test]
+fn read_unseekable() {
+    assert_same_file(SLOT1_PATH, GameVersion::Default, Hints::new());
+    assert_same_file(VECTOR2D_PATH, GameVersion::Default, Hints::new());
+    assert_same_file(DELEGATE_PATH, GameVersion::Default, Hints::new());
+    assert_same_file(
+        PROFILE_0_PATH,
+        GameVersion::Default,
+        crate::common::profile0::hints().into(),
+    );
+    assert_same_file(
+        PALWORLD_ZLIB_PATH,
+        GameVersion::Palworld,
+        palworld::hints().into(),
+    );
+}
+
+#[test]
+fn read_unseekable_file() {
+    let data = read_data(SLOT1_PATH);
+    let expected = GvasFile::read(&mut Cursor::new(data.clone()), GameVersion::Default)
+        .expect("Failed to parse gvas file");
+    let file = GvasFile::read_unseekable(
+        Unseekable(Cursor::new(data)),
+        GameVersion::Auto,
+        Hints::new(),
+        &ReadOptions::default(),
+    )
+    .expect("Failed to parse unseekable gvas file");
+    assert_eq!(file, expected);
+}
+
+#[test]
+fn write_unseekable() {
+    for (path, game_version, hints) in [
+        (SLOT1_PATH, GameVersion::Default, Hints::new()),
+        (
+            PALWORLD_ZLIB_PATH,
+            GameVersion::Palworld,
+            palworld::hints().into(),
+        ),
+    ] {
+        let data = read_data(path);
+        let file = GvasFile::read_with_options(
+            &mut Cursor::new(data),
+            game_version,
+            hints,
+            &ReadOptions::default(),
+        )
+        .expect("Failed to parse gvas file");
+
+        let mut expected = Cursor::new(Vec::new());
+        file.write(&mut expected)
+            .expect("Failed to write gvas file");
+        let mut writer = Vec::new();
+        file.write(&mut UnseekableWriter(&mut writer))
+            .expect("Failed to write unseekable gvas file");
+        assert_eq!(writer, expected.into_inner(), "{path}");
+    }
+}
+
+#[test]
+fn seek_within_lookback() {
+    let data = (0..64u8).collect::<Vec<_>>();
+    let mut reader = BufferedSeekAdapter::with_lookback(Unseekable(Cursor::new(data)), 8);
+
+    let mut buf = [0u8; 10];
+    reader.read_exact(&mut buf).expect("read");
+    assert_eq!(reader.window_start(), 2);
+    assert_eq!(reader.seek(SeekFrom::Current(-4)).expect("seek"), 6);
+
+    let mut buf = [0u8; 6];
+    reader.read_exact(&mut buf).expect("read");
+    assert_eq!(buf, [6, 7, 8, 9, 10, 11]);
+    assert_eq!(reader.stream_position().expect("position"), 12);
+
+    assert_eq!(reader.seek(SeekFrom::Start(40)).expect("seek"), 40);
+    let mut buf = [0u8; 1];
+    reader.read_exact(&mut buf).expect("read");
+    assert_eq!(buf, [40]);
+
+    let error = reader
+        .seek(SeekFrom::Start(20))
+        .expect_err("outside of lookback");
+    assert_eq!(error.kind(), ErrorKind::Unsupported);
+    let error = reader.seek(SeekFrom::End(0)).expect_err("end is unknown");
+    assert_eq!(error.kind(), ErrorKind::Unsupported);
+    let error = reader.seek(SeekFrom::Start(100)).expect_err("past the end");
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn read_leaves_reader_after_save() {
+    let mut data = read_data(SLOT1_PATH);
+    let len = data.len() as u64;
+    data.extend_from_slice(b"trailing data");
+
+    let mut reader = Cursor::new(data);
+    GvasFile::read(&mut reader, GameVersion::Default).expect("Failed to parse gvas file");
+    assert_eq!(reader.position(), len);
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use gvas::properties::{
+    array_property::ArrayProperty, unknown_property::UnknownProperty, Property,
+};
+
+#[test]
+fn array_copy_on_write() {
+    let array = ArrayProperty::Bytes {
+        bytes: Arc::from(vec![1u8, 2, 3]),
+    };
+    let mut copy = array.clone();
+    let (ArrayProperty::Bytes { bytes }, ArrayProperty::Bytes { bytes: copy_bytes }) =
+        (&array, &copy)
+    else {
+        panic!("Expected byte arrays");
+    };
+    assert!(Arc::ptr_eq(bytes, copy_bytes));
+
+    copy.bytes_mut().expect("byte array")[1] = 7;
+    assert_eq!(
+        array,
+        ArrayProperty::Bytes {
+            bytes: Arc::from(vec![1u8, 2, 3])
+        }
+    );
+    assert_eq!(
+        Property::ArrayProperty(copy),
+        Property::from(vec![1u8, 7, 3])
+    );
+    assert_eq!(ArrayProperty::Ints { ints: vec![1] }.bytes_mut(), None);
+}
+
+#[test]
+fn unknown_property_copy_on_write() {
+    let property = UnknownProperty::new(String::from("Blob"), vec![0u8; 16]);
+    let mut copy = property.clone();
+    assert!(Arc::ptr_eq(property.raw(), copy.raw()));
+
+    copy.raw_mut()[0] = 1;
+    assert!(!Arc::ptr_eq(property.raw(), copy.raw()));
+    assert_eq!(property.raw()[0], 0);
+    assert_eq!(copy.raw()[0], 1);
+    assert_eq!(copy.property_name(), "Blob");
+
+    // An unshared body is edited in place.
+    let before = Arc::as_ptr(copy.raw());
+    copy.raw_mut()[1] = 2;
+    assert_eq!(Arc::as_ptr(copy.raw()), before);
+}
@@ -0,0 +1,52 @@
+use std::{collections::HashMap, io::Cursor, sync::Arc, thread};
+
+use gvas::{
+    cursor_ext::ReadExt,
+    properties::{
+        int_property::IntProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        struct_types::VectorF,
+        Property, PropertyTrait, SharedParseContext,
+    },
+    types::{map::HashableIndexMap, Guid},
+};
+
+#[test]
+fn parse_on_multiple_threads() {
+    let context =
+        SharedParseContext::new(Arc::new(HashMap::new()), Arc::new(HashableIndexMap::new()));
+
+    let mut writer = Cursor::new(Vec::new());
+    Property::from(IntProperty::new(42))
+        .write(&mut writer, true, &mut context.options(&mut Vec::new()))
+        .expect("Write IntProperty");
+    let data = Arc::new(writer.into_inner());
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let context = context.clone();
+            let data = Arc::clone(&data);
+            thread::spawn(move || {
+                let mut stack = Vec::new();
+                let mut options = context.options(&mut stack);
+                let mut reader = Cursor::new(data.as_slice());
+                let property_type = reader.read_string().expect("Read property type");
+                Property::new(&mut reader, &property_type, true, &mut options, None)
+                    .expect("Read IntProperty")
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let property = handle.join().expect("Join thread");
+        assert_eq!(property, Property::from(IntProperty::new(42)));
+    }
+
+    // The parsed tree can be moved across threads.
+    let property = Property::from(StructProperty::new(
+        Guid::default(),
+        "Vector".to_string(),
+        StructPropertyValue::from(VectorF::new(0f32, 1f32, 2f32)),
+    ));
+    let _ = thread::spawn(move || property).join();
+}
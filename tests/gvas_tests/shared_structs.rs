@@ -0,0 +1,73 @@
+use std::io::Cursor;
+
+use gvas::{
+    error::Error,
+    game_version::GameVersion,
+    hints::Hints,
+    options::ReadOptions,
+    properties::{
+        array_property::ArrayProperty, int_property::IntProperty,
+        struct_property::StructPropertyValue, Property,
+    },
+    types::{map::HashableIndexMap, Guid},
+    GvasFile,
+};
+
+fn read(data: &[u8], share_struct_schemas: bool) -> Result<GvasFile, Error> {
+    GvasFile::read_with_options(
+        &mut Cursor::new(data),
+        GameVersion::Default,
+        Hints::new(),
+        &ReadOptions {
+            share_struct_schemas,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn shared_struct_schema() -> Result<(), Error> {
+    let data = std::fs::read("resources/test/Slot1.sav")?;
+    let file = read(&data, false)?;
+    let shared = read(&data, true)?;
+
+    let Some(Property::ArrayProperty(array @ ArrayProperty::SharedStructs { fields, values, .. })) =
+        shared.properties.get("array_of_structs")
+    else {
+        panic!("Expected SharedStructs");
+    };
+    assert_eq!(fields, &["test_field"]);
+    assert_eq!(values.len(), 2);
+    assert_eq!(
+        Some(&Property::from(array.clone().unshare_struct_schema())),
+        file.properties.get("array_of_structs")
+    );
+
+    let mut writer = Cursor::new(Vec::new());
+    shared.write(&mut writer)?;
+    assert_eq!(writer.into_inner(), data);
+
+    #[cfg(feature = "serde")]
+    assert_eq!(
+        serde_json::to_string(&shared).expect("Serialize"),
+        serde_json::to_string(&file).expect("Serialize")
+    );
+    Ok(())
+}
+
+#[test]
+fn shared_struct_schema_mismatch() {
+    let element = |name: &str| {
+        StructPropertyValue::CustomStruct(HashableIndexMap::from([(
+            name.to_string(),
+            vec![Property::from(IntProperty::new(0))],
+        )]))
+    };
+    let array = ArrayProperty::Structs {
+        field_name: String::from("Elements"),
+        type_name: String::from("TestStruct"),
+        guid: Guid::default(),
+        structs: vec![element("A"), element("B")],
+    };
+    assert_eq!(array.clone().share_struct_schema(), array);
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "hmac")]
+
+use std::io::Cursor;
+
+use crate::common::{PALWORLD_ZLIB_TWICE_PATH, SLOT1_PATH};
+use gvas::{
+    error::{DeserializeError, Error},
+    game_version::GameVersion,
+    hints::Hints,
+    options::{ReadOptions, WriteOptions},
+    properties::{array_property::ArrayProperty, Property},
+    signature::{SignatureLocation, Signer, SIGNATURE_LEN},
+    GvasFile,
+};
+
+fn read_file(path: &str, game_version: GameVersion) -> GvasFile {
+    let data = std::fs::read(path).expect("Failed to read test asset");
+    GvasFile::read(&mut Cursor::new(data), game_version).expect("Failed to parse gvas file")
+}
+
+fn write(signer: &Signer, file: &GvasFile) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+    signer
+        .write(file, &mut writer, &WriteOptions::default())
+        .expect("Failed to write signed file");
+    writer.into_inner()
+}
+
+fn read(signer: &Signer, data: &[u8], game_version: GameVersion) -> Result<GvasFile, Error> {
+    signer.read(
+        &mut Cursor::new(data),
+        game_version,
+        Hints::new(),
+        &ReadOptions::default(),
+    )
+}
+
+fn assert_invalid(result: Result<GvasFile, Error>) {
+    let error = result.expect_err("Invalid signature accepted");
+    assert!(
+        matches!(
+            error.as_deserialize(),
+            Some(DeserializeError::InvalidSignature(_))
+        ),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn property_signature() -> Result<(), Error> {
+    let file = read_file(SLOT1_PATH, GameVersion::Default);
+    let location = SignatureLocation::Property(String::from("Signature"));
+    let signer = Signer::new("secret", location.clone());
+    let data = write(&signer, &file);
+
+    let signed = read(&signer, &data, GameVersion::Default)?;
+    let Some(Property::ArrayProperty(ArrayProperty::Bytes { bytes })) =
+        signed.properties.get("Signature")
+    else {
+        panic!("Missing signature property")
+    };
+    assert_eq!(bytes.len(), SIGNATURE_LEN);
+    let mut unsigned = signed.clone();
+    unsigned.properties.shift_remove("Signature");
+    assert_eq!(unsigned, file);
+
+    // Signing again replaces the signature
+    assert_eq!(write(&signer, &signed), data);
+
+    assert_invalid(read(
+        &Signer::new("other", location),
+        &data,
+        GameVersion::Default,
+    ));
+    let mut unsigned_data = Cursor::new(Vec::new());
+    file.write(&mut unsigned_data)?;
+    assert_invalid(read(
+        &signer,
+        &unsigned_data.into_inner(),
+        GameVersion::Default,
+    ));
+    Ok(())
+}
+
+#[test]
+fn trailer_signature() -> Result<(), Error> {
+    let file = read_file(PALWORLD_ZLIB_TWICE_PATH, GameVersion::Palworld);
+    let signer = Signer::new(b"secret".to_vec(), SignatureLocation::Trailer);
+    let mut data = write(&signer, &file);
+
+    let mut unsigned = Cursor::new(Vec::new());
+    file.write(&mut unsigned)?;
+    let unsigned = unsigned.into_inner();
+    assert_eq!(data.len(), unsigned.len() + SIGNATURE_LEN);
+    assert_eq!(data[unsigned.len()..], signer.sign(&unsigned)?);
+    assert_eq!(read(&signer, &data, GameVersion::Palworld)?, file);
+
+    let last = data.len() - 1;
+    data[last] ^= 1;
+    assert_invalid(read(&signer, &data, GameVersion::Palworld));
+    assert_invalid(read(&signer, &data[..16], GameVersion::Palworld));
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use std::{io::Cursor, sync::Arc};
+
+use gvas::{
+    error::Error,
+    game_version::GameVersion,
+    properties::{int_property::IntProperty, Property},
+    snapshot::History,
+    GvasFile,
+};
+
+fn read() -> Result<GvasFile, Error> {
+    let data = std::fs::read("resources/test/component8.sav")?;
+    GvasFile::read(&mut Cursor::new(data), GameVersion::Default)
+}
+
+#[test]
+fn snapshot_shares_unchanged_properties() -> Result<(), Error> {
+    let mut file = read()?;
+    let first = file.snapshot();
+
+    let (name, _) = file.properties.get_index(0).expect("property");
+    let name = name.clone();
+    file.properties
+        .insert(name.clone(), Property::from(IntProperty::new(42)));
+    let second = file.snapshot_since(&first);
+
+    assert_eq!(second.shared_with(&first), file.properties.len() - 1);
+    assert!(!Arc::ptr_eq(
+        first.get(&name).expect("first"),
+        second.get(&name).expect("second")
+    ));
+
+    file.restore(&first);
+    assert_eq!(file, read()?);
+    Ok(())
+}
+
+#[test]
+fn history_undo_redo() -> Result<(), Error> {
+    let original = read()?;
+    let mut file = original.clone();
+    let mut history = History::new(&file);
+    assert!(!history.undo(&mut file));
+
+    file.properties
+        .insert(String::from("First"), Property::from(IntProperty::new(1)));
+    history.commit(&file);
+    let first = file.clone();
+    file.properties.shift_remove("First");
+    file.properties
+        .insert(String::from("Second"), Property::from(IntProperty::new(2)));
+    history.commit(&file);
+    let second = file.clone();
+
+    assert!(history.undo(&mut file));
+    assert_eq!(file, first);
+    assert!(history.undo(&mut file));
+    assert_eq!(file, original);
+    assert!(!history.can_undo());
+    assert!(history.redo(&mut file));
+    assert!(history.redo(&mut file));
+    assert_eq!(file, second);
+    assert!(!history.redo(&mut file));
+
+    // Committing after an undo discards the redo steps
+    history.undo(&mut file);
+    history.commit(&file);
+    assert!(!history.can_redo());
+
+    let mut limited = History::new(&file).with_limit(1);
+    limited.commit(&first);
+    limited.commit(&second);
+    assert!(limited.undo(&mut file));
+    assert_eq!(file, first);
+    assert!(!limited.undo(&mut file));
+    Ok(())
+}
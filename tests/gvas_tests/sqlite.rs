@@ -0,0 +1,136 @@
+#![cfg(feature = "sqlite")]
+
+use std::{fs::File, path::Path};
+
+use crate::common::{profile0, PROFILE_0_PATH, SLOT1_PATH};
+use gvas::{
+    game_version::GameVersion,
+    properties::{
+        array_property::ArrayProperty, int_property::IntProperty,
+        struct_property::StructPropertyValue, Property,
+    },
+    sqlite,
+    types::{map::HashableIndexMap, Guid},
+    GvasFile,
+};
+use rusqlite::Connection;
+
+fn read(path: &str) -> GvasFile {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    GvasFile::read_with_hints(&mut file, GameVersion::Default, profile0::hints())
+        .expect("Failed to parse gvas file")
+}
+
+#[test]
+fn profile0_metrics() {
+    let file = read(PROFILE_0_PATH);
+    let mut connection = Connection::open_in_memory().expect("open_in_memory");
+    let tables = sqlite::export(&file, &mut connection).expect("export");
+    assert!(tables.contains(&String::from("dbNumbers")));
+    assert!(tables.contains(&String::from(
+        "achievementHistoryScope.metrics.valueByFilter"
+    )));
+
+    let playtime: i64 = connection
+        .query_row(
+            "SELECT value FROM dbNumbers WHERE key = ?",
+            ["playtime_418AD60A45FCE310E9D3DEB5B9894EF2"],
+            |row| row.get(0),
+        )
+        .expect("query_row");
+    assert_eq!(playtime, 38);
+
+    let value: i64 = connection
+        .query_row(
+            "SELECT value FROM \"achievementHistoryScope.metrics.valueByFilter\"
+             WHERE parent = ? AND key = ?",
+            ["achievementHistoryScope.metrics[0].valueByFilter", "None"],
+            |row| row.get(0),
+        )
+        .expect("query_row");
+    assert_eq!(value, 737);
+}
+
+#[test]
+fn slot1_structs() {
+    let mut file = read(SLOT1_PATH);
+    let mut connection = Connection::open_in_memory().expect("open_in_memory");
+    let tables = sqlite::export(&file, &mut connection).expect("export");
+    assert_eq!(
+        tables,
+        ["array_of_structs", "array_of_ints", "array_of_strings"]
+    );
+
+    let rows = connection
+        .prepare("SELECT parent, position, test_field FROM array_of_structs ORDER BY position")
+        .expect("prepare")
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })
+        .expect("query_map")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("rows");
+    assert_eq!(rows.len(), 2);
+    assert!(rows
+        .iter()
+        .all(|(parent, _, _)| parent == "array_of_structs"));
+
+    // Exporting again replaces the tables
+    file.properties.insert(
+        String::from("array_of_ints"),
+        Property::from(ArrayProperty::Ints { ints: vec![7] }),
+    );
+    sqlite::export(&file, &mut connection).expect("export");
+    let ints: i64 = connection
+        .query_row("SELECT COUNT(*) FROM array_of_ints", [], |row| row.get(0))
+        .expect("query_row");
+    assert_eq!(ints, 1);
+}
+
+#[test]
+fn fixed_column_fields() {
+    let mut file = read(SLOT1_PATH);
+    let field = |name: &str, value: i32| {
+        (
+            name.to_string(),
+            vec![Property::from(IntProperty::new(value))],
+        )
+    };
+    file.properties.insert(
+        String::from("collisions"),
+        Property::from(ArrayProperty::Structs {
+            field_name: String::from("collisions"),
+            type_name: String::from("Collision"),
+            guid: Guid::default(),
+            structs: vec![StructPropertyValue::CustomStruct(HashableIndexMap::from([
+                field("parent", 1),
+                field("Position", 2),
+                field("_parent", 3),
+            ]))],
+        }),
+    );
+    let mut connection = Connection::open_in_memory().expect("open_in_memory");
+    sqlite::export(&file, &mut connection).expect("export");
+
+    let row = connection
+        .query_row(
+            "SELECT parent, position, _parent, _Position, __parent FROM collisions",
+            [],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, i64>(3)?,
+                    row.get::<_, i64>(4)?,
+                ))
+            },
+        )
+        .expect("query_row");
+    assert_eq!(row, (String::from("collisions"), 0, 1, 2, 3));
+}
@@ -0,0 +1,95 @@
+use gvas::{
+    game_version::GameVersion,
+    properties::{map_property::MapProperty, str_property::StrProperty, Property},
+    stable_hash::{StableHash, StableHasher},
+    types::map::HashableIndexMap,
+    GvasFile,
+};
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs::File,
+    hash::{Hash, Hasher},
+    io::Cursor,
+    path::Path,
+};
+
+use crate::common::SLOT1_PATH;
+
+fn read_slot1() -> GvasFile {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SLOT1_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file")
+}
+
+fn std_hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn known_values() {
+    // These values must not change unless STABLE_HASH_VERSION does
+    assert_eq!(StableHasher::new().finish(), 0xad2aca7747985764);
+    assert_eq!(Property::from(5).stable_hash(), 0x8fc234ce05e879cc);
+    assert_eq!(read_slot1().stable_hash(), 0xfeb0dd18f9153d7b);
+}
+
+#[test]
+fn round_trip() {
+    let file = read_slot1();
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer)
+        .expect("Failed to serialize gvas file");
+    writer.set_position(0);
+    let read =
+        GvasFile::read(&mut writer, GameVersion::Default).expect("Failed to parse gvas file");
+    assert_eq!(read.stable_hash(), file.stable_hash());
+}
+
+#[test]
+fn changes() {
+    let file = read_slot1();
+    let hash = file.stable_hash();
+
+    let mut changed = file.clone();
+    *changed
+        .get_property_by_path_mut("struct_property.test_field")
+        .expect("Missing struct field") = Property::from(1u64);
+    assert_ne!(changed.stable_hash(), hash);
+
+    let mut changed = file.clone();
+    changed.prefix = vec![0];
+    assert_ne!(changed.stable_hash(), hash);
+
+    // Empty and missing strings are different values
+    assert_ne!(
+        Property::from(StrProperty::new(None)).stable_hash(),
+        Property::from(StrProperty::from("")).stable_hash()
+    );
+}
+
+#[test]
+fn map_order() {
+    let map = HashableIndexMap::from([
+        (String::from("a"), Property::from(1)),
+        (String::from("b"), Property::from(2)),
+    ]);
+    let mut reversed = map.clone();
+    reversed.reverse();
+    let map = Property::from(MapProperty::StrProperty {
+        value_type: String::from("IntProperty"),
+        str_props: map,
+    });
+    let reversed = Property::from(MapProperty::StrProperty {
+        value_type: String::from("IntProperty"),
+        str_props: reversed,
+    });
+
+    // Maps compare equal regardless of the order, so their std hash must match too
+    assert_eq!(map, reversed);
+    assert_eq!(std_hash(&map), std_hash(&reversed));
+
+    // The order is part of the save, so the stable hash keeps it
+    assert_ne!(map.stable_hash(), reversed.stable_hash());
+}
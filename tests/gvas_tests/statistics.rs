@@ -0,0 +1,130 @@
+use std::{fs::File, path::Path};
+
+use crate::common::{profile0, PROFILE_0_PATH};
+use gvas::{
+    game_version::GameVersion,
+    properties::{
+        map_property::MapProperty,
+        str_property::StrProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        Property,
+    },
+    statistics::{self, Statistic},
+    types::{map::HashableIndexMap, Guid},
+    GvasFile,
+};
+
+fn profile0() -> GvasFile {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(PROFILE_0_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    GvasFile::read_with_hints(&mut file, GameVersion::Default, profile0::hints())
+        .expect("Failed to parse gvas file")
+}
+
+#[test]
+fn profile0_statistics() {
+    let file = profile0();
+    let statistics = file.statistics();
+
+    let playtime = statistics.with_prefix("playtime").collect::<Vec<_>>();
+    assert_eq!(playtime[0], ("418AD60A45FCE310E9D3DEB5B9894EF2", 38));
+    assert_eq!(playtime[1], ("2AB6B5764521494B8DE77891EFD0A8AE", 68));
+    assert_eq!(
+        playtime.len(),
+        statistics.with_prefix("bestrank").count(),
+        "every level has a playtime and a best rank"
+    );
+    for (id, _) in &playtime {
+        assert!(statistics.get("bestrank", id).is_some(), "{id}");
+    }
+    assert_eq!(
+        statistics.get("bestrank", "976B945848D92B929BC9318144D2AFD9"),
+        Some(2)
+    );
+
+    // Keys without a `_` have an empty id
+    assert_eq!(statistics.get("money", ""), Some(14));
+    assert_eq!(statistics.get("roamPlayerPosition", "x"), Some(-1366));
+    assert!(statistics.prefixes().contains(&"didNotify"));
+
+    // Maps of names nested in structs
+    let energy = statistics
+        .iter()
+        .find(|statistic| statistic.prefix == "Energy")
+        .expect("Energy statistic");
+    assert!(
+        energy.path.starts_with("achievementHistoryScope."),
+        "{}",
+        energy.path
+    );
+    assert_eq!(statistics.paths().next(), Some("dbNumbers"));
+}
+
+#[test]
+fn statistics_nested() {
+    let map = |entries: &[(&str, i32)]| {
+        Property::from(MapProperty::NameInt {
+            name_ints: HashableIndexMap(
+                entries
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), *value))
+                    .collect(),
+            ),
+        })
+    };
+    let property = Property::from(StructProperty::new(
+        Guid::default(),
+        String::from("Scores"),
+        StructPropertyValue::CustomStruct(HashableIndexMap::from([
+            (
+                String::from("Kills"),
+                vec![map(&[("kills_Wolf", 3), ("kills_Bear", 1)])],
+            ),
+            (
+                String::from("Deaths"),
+                vec![map(&[("deaths_Wolf", 2), ("deaths", 5)])],
+            ),
+        ])),
+    ));
+
+    let statistics = statistics::collect("Scores", &property);
+    assert_eq!(
+        statistics.iter().collect::<Vec<_>>(),
+        [
+            Statistic {
+                path: "Scores.Kills",
+                prefix: "kills",
+                id: "Wolf",
+                value: 3
+            },
+            Statistic {
+                path: "Scores.Kills",
+                prefix: "kills",
+                id: "Bear",
+                value: 1
+            },
+            Statistic {
+                path: "Scores.Deaths",
+                prefix: "deaths",
+                id: "Wolf",
+                value: 2
+            },
+            Statistic {
+                path: "Scores.Deaths",
+                prefix: "deaths",
+                id: "",
+                value: 5
+            },
+        ]
+    );
+    assert_eq!(statistics.prefixes(), ["kills", "deaths"]);
+    assert_eq!(statistics.get("deaths", "Bear"), None);
+
+    // Empty maps are found, other properties aren't
+    let empty = map(&[]);
+    let empty = statistics::collect("Empty", &empty);
+    assert!(!empty.is_empty());
+    assert_eq!(empty.iter().count(), 0);
+    let name = Property::from(StrProperty::from("kills_Wolf"));
+    assert!(statistics::collect("Name", &name).is_empty());
+}
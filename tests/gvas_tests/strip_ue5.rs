@@ -0,0 +1,56 @@
+use std::{fs::File, io::Cursor, path::Path};
+
+use crate::common::{TRANSFORM_PATH, VECTOR2D_PATH};
+use gvas::{
+    custom_version::UE5_CUSTOM_VERSIONS,
+    error::{Error, ErrorKind, SerializeError},
+    game_version::GameVersion,
+    GvasFile, GvasHeader,
+};
+
+fn read(path: &str) -> GvasFile {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file")
+}
+
+#[test]
+fn strip_ue5_fields() {
+    for path in [TRANSFORM_PATH, VECTOR2D_PATH] {
+        let mut file = read(path);
+        let package_version = file.header.get_package_version();
+        file.strip_ue5_fields();
+
+        assert!(matches!(file.header, GvasHeader::Version2 { .. }));
+        assert_eq!(file.header.get_package_version().ue4, package_version.ue4);
+        assert_eq!(file.header.get_package_version().ue5, 0);
+        for guid in UE5_CUSTOM_VERSIONS {
+            assert!(!file.header.get_custom_versions().contains_key(&guid));
+        }
+
+        let mut writer = Cursor::new(Vec::new());
+        file.write(&mut writer)
+            .expect("Failed to write stripped file");
+        let read_back = GvasFile::read(&mut Cursor::new(writer.into_inner()), GameVersion::Default)
+            .expect("Failed to read stripped file");
+        assert_eq!(read_back, file);
+    }
+}
+
+#[test]
+fn strip_ue5_header_only() {
+    // Large world coordinates can't be written without the UE5 custom versions
+    let mut file = read(TRANSFORM_PATH);
+    file.header.strip_ue5_fields();
+    let result = file.write(&mut Cursor::new(Vec::new()));
+    assert!(
+        matches!(
+            result,
+            Err(Error::Property {
+                kind: ErrorKind::Serialize(SerializeError::InvalidValue(_)),
+                ..
+            })
+        ),
+        "Unexpected result {result:?}"
+    );
+}
@@ -0,0 +1,98 @@
+use std::{fs::File, path::Path};
+
+use crate::common::{profile0, PROFILE_0_PATH};
+use gvas::{
+    game_version::GameVersion,
+    properties::{
+        array_property::ArrayProperty, int_property::Int64Property, map_property::MapProperty,
+        str_property::StrProperty, Property,
+    },
+    table::{self, TableRow},
+    types::map::HashableIndexMap,
+    GvasFile,
+};
+use ordered_float::OrderedFloat;
+
+fn row(path: &str, key: &str, value: &str, value_type: &'static str) -> TableRow {
+    TableRow {
+        path: path.to_string(),
+        key: key.to_string(),
+        value: value.to_string(),
+        value_type,
+    }
+}
+
+#[test]
+fn profile0_numbers() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(PROFILE_0_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    let file = GvasFile::read_with_hints(&mut file, GameVersion::Default, profile0::hints())
+        .expect("Failed to parse gvas file");
+
+    let rows = file.numeric_table();
+    assert!(rows.contains(&row(
+        "dbNumbers",
+        "playtime_418AD60A45FCE310E9D3DEB5B9894EF2",
+        "38",
+        "IntProperty"
+    )));
+    assert!(rows.contains(&row(
+        "dbNumbers",
+        "bestrank_976B945848D92B929BC9318144D2AFD9",
+        "2",
+        "IntProperty"
+    )));
+    assert!(rows.contains(&row(
+        "achievementHistoryScope.metrics[0].valueByFilter",
+        "None",
+        "737",
+        "IntProperty"
+    )));
+    assert!(!rows.iter().any(|row| row.path == "dbStrings"));
+}
+
+#[test]
+fn arrays_and_property_maps() {
+    let array = Property::from(ArrayProperty::Floats {
+        floats: vec![OrderedFloat(0.5), OrderedFloat(2.0)],
+    });
+    assert_eq!(
+        table::rows("Ratios", &array),
+        [
+            row("Ratios", "0", "0.5", "FloatProperty"),
+            row("Ratios", "1", "2", "FloatProperty"),
+        ]
+    );
+
+    let map = Property::from(MapProperty::Properties {
+        key_type: String::from("StrProperty"),
+        value_type: String::from("Int64Property"),
+        allocation_flags: 0,
+        value: HashableIndexMap::from([
+            (
+                Property::from(StrProperty::from("a")),
+                Property::from(Int64Property::new(-1)),
+            ),
+            (
+                Property::from(StrProperty::from("b")),
+                Property::from(StrProperty::from("not a number")),
+            ),
+        ]),
+        duplicates: vec![],
+    });
+    assert_eq!(
+        table::rows("Scores", &map),
+        [row("Scores", "a", "-1", "Int64Property")]
+    );
+}
+
+#[test]
+fn csv_quoting() {
+    let mut csv = Vec::new();
+    table::write_csv(&mut csv, &[row("Root", "a,\"b\"", "1", "IntProperty")])
+        .expect("Failed to write CSV");
+    assert_eq!(
+        String::from_utf8(csv).expect("CSV is valid UTF-8"),
+        "path,key,value,type\r\nRoot,\"a,\"\"b\"\"\",1,IntProperty\r\n"
+    );
+}
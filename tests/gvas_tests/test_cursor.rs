@@ -1,4 +1,4 @@
-use std::io::Cursor;
+use std::{borrow::Cow, io::Cursor};
 
 use gvas::{
     cursor_ext::{ReadExt, WriteExt},
@@ -9,7 +9,7 @@ use gvas::{
 fn test_write_string() -> Result<(), Error> {
     // ASCII
     let mut cursor = Cursor::new(Vec::new());
-    cursor.write_string("test")?;
+    assert_eq!(cursor.write_string("test")?, 9);
     assert_eq!(
         cursor.get_ref(),
         &[5u8, 0u8, 0u8, 0u8, b't', b'e', b's', b't', 0u8],
@@ -17,7 +17,7 @@ fn test_write_string() -> Result<(), Error> {
 
     // Non-ASCII
     let mut cursor = Cursor::new(Vec::new());
-    cursor.write_string("\u{A7}")?;
+    assert_eq!(cursor.write_string("\u{A7}")?, 8);
     assert_eq!(
         cursor.get_ref(),
         &[0xfeu8, 0xffu8, 0xffu8, 0xffu8, 0xa7u8, 0u8, 0u8, 0u8],
@@ -111,3 +111,21 @@ fn test_read_fstring() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn test_read_type_name() -> Result<(), Error> {
+    let mut cursor = Cursor::new(Vec::new());
+    cursor.write_string("IntProperty")?;
+    cursor.write_string("MyCustomStruct")?;
+    cursor.write_string("\u{A7}Property")?;
+    cursor.set_position(0);
+
+    assert!(matches!(
+        cursor.read_type_name()?,
+        Cow::Borrowed("IntProperty")
+    ));
+    assert!(matches!(cursor.read_type_name()?, Cow::Owned(name) if name == "MyCustomStruct"));
+    assert!(matches!(cursor.read_type_name()?, Cow::Owned(name) if name == "\u{A7}Property"));
+
+    Ok(())
+}
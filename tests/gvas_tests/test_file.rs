@@ -2,8 +2,13 @@ use std::io::Cursor;
 
 use byteorder::{LittleEndian, WriteBytesExt};
 
-use gvas::game_version::GameVersion;
-use gvas::{error::Error, GvasFile, GvasHeader, FILE_TYPE_GVAS};
+use gvas::game_version::{DeserializedGameVersion, GameVersion, PalworldCompressionType};
+use gvas::options::{ReadOptions, TrailingPadding, WriteOptions};
+use gvas::{
+    error::{DeserializeError, Error},
+    hints::Hints,
+    GvasFile, GvasHeader, FILE_TYPE_GVAS,
+};
 
 #[test]
 fn test_file_err() {
@@ -56,3 +61,185 @@ fn test_version_err() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn test_trailing_padding() -> Result<(), Error> {
+    let original = std::fs::read("resources/test/Slot1.sav")?;
+    let body = &original[..original.len() - 4];
+
+    // Omitted padding
+    let file = GvasFile::read(&mut Cursor::new(body), GameVersion::Default)?;
+    assert_eq!(file.trailing_padding, None);
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer)?;
+    assert_eq!(writer.get_ref().as_slice(), body);
+
+    // Nonzero padding
+    let mut modified = body.to_vec();
+    modified.extend_from_slice(&7i32.to_le_bytes());
+    let file = GvasFile::read(&mut Cursor::new(&modified), GameVersion::Default)?;
+    assert_eq!(file.trailing_padding, Some(7));
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer)?;
+    assert_eq!(writer.get_ref(), &modified);
+
+    // Override on write
+    let options = WriteOptions {
+        trailing_padding: TrailingPadding::Value(0),
+        ..Default::default()
+    };
+    let mut writer = Cursor::new(Vec::new());
+    file.write_with_options(&mut writer, &options)?;
+    assert_eq!(writer.get_ref(), &original);
+
+    Ok(())
+}
+
+#[test]
+fn test_partial_trailing_padding() -> Result<(), Error> {
+    let original = std::fs::read("resources/test/Slot1.sav")?;
+    let body = &original[..original.len() - 4];
+
+    for partial in [&[1u8][..], &[1, 2], &[1, 2, 3]] {
+        let mut modified = body.to_vec();
+        modified.extend_from_slice(partial);
+        let file = GvasFile::read(&mut Cursor::new(&modified), GameVersion::Default)?;
+        assert_eq!(file.trailing_padding, None);
+        assert_eq!(file.trailing_bytes, partial);
+
+        let mut writer = Cursor::new(Vec::new());
+        file.write(&mut writer)?;
+        assert_eq!(writer.get_ref(), &modified);
+
+        // Replaced by an explicit padding value
+        let options = WriteOptions {
+            trailing_padding: TrailingPadding::Value(0),
+            ..Default::default()
+        };
+        let mut writer = Cursor::new(Vec::new());
+        file.write_with_options(&mut writer, &options)?;
+        assert_eq!(writer.get_ref(), &original);
+    }
+
+    Ok(())
+}
+
+/// A PNG image with a text chunk, up to and including the IEND chunk.
+const PNG_PREFIX: &[u8] = b"\x89PNG\r\n\x1a\n\
+    \x00\x00\x00\x09tEXtthumbnail\x00\x00\x00\x00\
+    \x00\x00\x00\x00IEND\xae\x42\x60\x82";
+
+#[test]
+fn test_prefix() -> Result<(), Error> {
+    let original = std::fs::read("resources/test/Slot1.sav")?;
+    let block = [&9u32.to_le_bytes()[..], b"thumbnail"].concat();
+    for prefix in [PNG_PREFIX.to_vec(), block] {
+        let mut data = prefix.clone();
+        data.extend_from_slice(&original);
+
+        let file = GvasFile::read(&mut Cursor::new(&data), GameVersion::Default)?;
+        assert_eq!(file.prefix, prefix);
+
+        let mut writer = Cursor::new(Vec::new());
+        file.write(&mut writer)?;
+        assert_eq!(writer.get_ref(), &data);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_prefix() -> Result<(), Error> {
+    let original = std::fs::read("resources/test/Slot1.sav")?;
+    // Neither a PNG image nor a metadata block of the right length
+    for prefix in [&b"thumbnail"[..], &PNG_PREFIX[..PNG_PREFIX.len() - 12]] {
+        let mut data = prefix.to_vec();
+        data.extend_from_slice(&original);
+
+        let err = GvasFile::read(&mut Cursor::new(&data), GameVersion::Default)
+            .expect_err("Unknown prefix read");
+        assert!(matches!(
+            err.as_deserialize(),
+            Some(DeserializeError::InvalidHeader(_))
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_save() -> Result<(), Error> {
+    let original = std::fs::read("resources/test/Slot1.sav")?;
+    let dir = std::env::temp_dir().join(format!("gvas_test_save_{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("Slot1.sav");
+    std::fs::write(&path, b"previous")?;
+
+    let file = GvasFile::open(
+        "resources/test/Slot1.sav",
+        GameVersion::Default,
+        Hints::new(),
+        &ReadOptions::default(),
+    )?;
+    let options = WriteOptions {
+        backup: true,
+        ..Default::default()
+    };
+    file.save_with_options(&path, &options)?;
+
+    assert_eq!(std::fs::read(&path)?, original);
+    assert_eq!(std::fs::read(dir.join("Slot1.sav.bak"))?, b"previous");
+    assert!(!dir.join("Slot1.sav.tmp").exists());
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_palworld_declared_length() -> Result<(), Error> {
+    let mut data = std::fs::read("resources/test/palworld_zlib.sav")?;
+    let declared = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+    let compressed = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+
+    let file = GvasFile::read(&mut Cursor::new(&data), GameVersion::Palworld)?;
+    let DeserializedGameVersion::Palworld(PalworldCompressionType::Zlib, lengths) =
+        file.deserialized_game_version
+    else {
+        panic!(
+            "Unexpected game version {:?}",
+            file.deserialized_game_version
+        );
+    };
+    assert_eq!(lengths.decompressed, declared);
+    assert_eq!(lengths.compressed, compressed);
+
+    data[..4].copy_from_slice(&(declared + 1).to_le_bytes());
+    let err = GvasFile::read(&mut Cursor::new(&data), GameVersion::Palworld)
+        .expect_err("Expected length mismatch");
+    assert!(matches!(
+        err,
+        Error::Limit(DeserializeError::DecompressedLengthMismatch(..))
+    ));
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Declared decompressed length {} doesn't match actual length {}",
+            declared + 1,
+            declared
+        )
+    );
+
+    let read_options = ReadOptions {
+        tolerate_length_mismatch: true,
+        ..Default::default()
+    };
+    let tolerant = GvasFile::read_with_options(
+        &mut Cursor::new(&data),
+        GameVersion::Palworld,
+        Hints::new(),
+        &read_options,
+    )?;
+    assert_eq!(tolerant, file);
+
+    Ok(())
+}
@@ -2,9 +2,12 @@ use std::{collections::HashMap, io::Cursor};
 
 use gvas::{
     cursor_ext::ReadExt,
+    object_version::PackageVersion,
+    options::{ReadOptions, StringTableResolver, WriteOptions},
     properties::{
         array_property::ArrayProperty,
         enum_property::EnumProperty,
+        group::{ContainerProperty, PropertyGroup, ScalarProperty, StructLikeProperty},
         int_property::{
             BoolProperty, ByteProperty, BytePropertyValue, DoubleProperty, FloatProperty,
             Int16Property, Int64Property, Int8Property, IntProperty, UInt16Property,
@@ -14,14 +17,19 @@ use gvas::{
         set_property::SetProperty,
         str_property::StrProperty,
         struct_property::{StructProperty, StructPropertyValue},
-        struct_types::VectorF,
+        struct_types::{
+            DateTime, Int64Vector, IntVector, IntVector4, LinearColor, Timespan, UintVector,
+            Vector2F, VectorF,
+        },
         text_property::TextProperty,
         Property, PropertyOptions, PropertyTrait,
     },
     types::{map::HashableIndexMap, Guid},
 };
 
-use gvas::properties::text_property::FText;
+use gvas::properties::text_property::{
+    DateTimeStyle, FText, FTextHistory, ResolvedString, TransformType,
+};
 
 macro_rules! test_property {
     ($function_name:ident, $type:ident, $property_value:expr) => {
@@ -33,6 +41,9 @@ macro_rules! test_property {
                 hints: &HashMap::new(),
                 properties_stack: &mut Vec::new(),
                 custom_versions: &HashableIndexMap::new(),
+                package_version: PackageVersion::default(),
+                read_options: &ReadOptions::default(),
+                write_options: &WriteOptions::default(),
             };
 
             // Export the property to a byte array
@@ -101,6 +112,42 @@ test_property!(
         StructPropertyValue::from(VectorF::new(0f32, 1f32, 2f32))
     )
 );
+test_property!(
+    test_struct_int_vector,
+    StructProperty,
+    StructProperty::new(
+        Guid::default(),
+        "IntVector".to_string(),
+        StructPropertyValue::from(IntVector::new(-1, 0, i32::MAX))
+    )
+);
+test_property!(
+    test_struct_int_vector4,
+    StructProperty,
+    StructProperty::new(
+        Guid::default(),
+        "IntVector4".to_string(),
+        StructPropertyValue::from(IntVector4::new(1, 2, 3, i32::MIN))
+    )
+);
+test_property!(
+    test_struct_uint_vector,
+    StructProperty,
+    StructProperty::new(
+        Guid::default(),
+        "UintVector".to_string(),
+        StructPropertyValue::from(UintVector::new(0, 1, u32::MAX))
+    )
+);
+test_property!(
+    test_struct_int64_vector,
+    StructProperty,
+    StructProperty::new(
+        Guid::default(),
+        "Int64Vector".to_string(),
+        StructPropertyValue::from(Int64Vector::new(i64::MIN, 0, i64::MAX))
+    )
+);
 
 // ArrayProperty
 test_property!(
@@ -171,6 +218,23 @@ test_property!(
         vec![Property::from(FloatProperty::new(4321f32))]
     )
 );
+test_property!(
+    test_set_bytes,
+    SetProperty,
+    SetProperty::new_bytes(0, &[0, 1, 2, 255])
+);
+test_property!(
+    test_set_byte_enums,
+    SetProperty,
+    SetProperty::new(
+        String::from("ByteProperty"),
+        0,
+        vec![
+            Property::from(ByteProperty::new_namespaced(None, String::from("E::A"))),
+            Property::from(ByteProperty::new_namespaced(None, String::from("E::B"))),
+        ]
+    )
+);
 
 // MapProperty
 test_property!(
@@ -192,3 +256,267 @@ test_property!(
         ]),
     )
 );
+
+// Enum values added by newer engine versions
+test_property!(
+    test_text_unknown_transform,
+    TextProperty,
+    TextProperty::new(FText {
+        flags: 0,
+        history: FTextHistory::Transform {
+            source_text: Box::new(FText::new_base(
+                0,
+                Some(String::from("namespace")),
+                Some(String::from("key")),
+                Some(String::from("text")),
+            )),
+            transform_type: TransformType::Other(7),
+        },
+    })
+);
+test_property!(
+    test_text_unknown_date_style,
+    TextProperty,
+    TextProperty::new(FText {
+        flags: 0,
+        history: FTextHistory::AsDate {
+            date_time: DateTime { ticks: 1 },
+            date_style: DateTimeStyle::Other(0x80),
+            target_culture: String::from("culture"),
+        },
+    })
+);
+
+#[test]
+fn test_set_bytes_layout() {
+    let set = SetProperty::new_bytes(0, &[7, 8, 9]);
+    assert_eq!(set.get_bytes(), Some(vec![7, 8, 9]));
+
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    let mut writer = Cursor::new(Vec::new());
+    set.write_body(&mut writer, &mut options)
+        .expect("Failed to write SetProperty");
+    // The values follow the element count without any per-element data
+    assert_eq!(writer.into_inner(), [0, 0, 0, 0, 3, 0, 0, 0, 7, 8, 9]);
+
+    let floats = SetProperty::new(String::from("FloatProperty"), 0, vec![]);
+    assert_eq!(floats.get_bytes(), None);
+}
+
+#[test]
+fn test_struct_old_package_version() {
+    let property = StructProperty::new(
+        Guid::default(),
+        "Vector".to_string(),
+        StructPropertyValue::from(VectorF::new(0f32, 1f32, 2f32)),
+    );
+
+    let write = |package_version| {
+        let mut options = PropertyOptions {
+            hints: &HashMap::new(),
+            properties_stack: &mut Vec::new(),
+            custom_versions: &HashableIndexMap::new(),
+            package_version,
+            read_options: &ReadOptions::default(),
+            write_options: &WriteOptions::default(),
+        };
+        let mut writer = Cursor::new(Vec::new());
+        property
+            .write(&mut writer, true, &mut options)
+            .expect("Write StructProperty");
+
+        let mut reader = Cursor::new(writer.into_inner());
+        let property_type = reader.read_string().expect("Read type");
+        let imported = Property::new(&mut reader, &property_type, true, &mut options, None)
+            .expect("Read StructProperty");
+        assert_eq!(Property::from(property.clone()), imported);
+        reader.into_inner().len()
+    };
+
+    // Struct GUIDs were added in 441 and property GUID flags in 503
+    let current = write(PackageVersion::default());
+    let without_property_guid = write(PackageVersion { ue4: 502, ue5: 0 });
+    let without_struct_guid = write(PackageVersion { ue4: 440, ue5: 0 });
+    assert_eq!(current - without_property_guid, 1);
+    assert_eq!(without_property_guid - without_struct_guid, 16);
+}
+
+#[test]
+fn test_string_table_resolver() {
+    let property = TextProperty::new(FText {
+        flags: 0,
+        history: FTextHistory::StringTableEntry {
+            table_id: Box::new(FText::new_base(
+                0,
+                None,
+                None,
+                Some(String::from("/Game/Items")),
+            )),
+            key: String::from("Sword"),
+            display_string: ResolvedString::default(),
+        },
+    });
+
+    let read = |read_options: &ReadOptions| {
+        let mut options = PropertyOptions {
+            hints: &HashMap::new(),
+            properties_stack: &mut Vec::new(),
+            custom_versions: &HashableIndexMap::new(),
+            package_version: PackageVersion::default(),
+            read_options,
+            write_options: &WriteOptions::default(),
+        };
+        let mut writer = Cursor::new(Vec::new());
+        property
+            .write(&mut writer, true, &mut options)
+            .expect("Write TextProperty");
+        let mut reader = Cursor::new(writer.into_inner());
+        let property_type = reader.read_string().expect("Read type");
+        Property::new(&mut reader, &property_type, true, &mut options, None)
+            .expect("Read TextProperty")
+    };
+    let display_string = |property: &Property| {
+        property
+            .get_text()
+            .and_then(|text| text.value.display_string())
+            .map(String::from)
+    };
+
+    assert_eq!(display_string(&read(&ReadOptions::default())), None);
+    let resolver = StringTableResolver::new(|table_id, key| {
+        (table_id == "/Game/Items" && key == "Sword").then(|| String::from("Iron Sword"))
+    });
+    let read_options = ReadOptions {
+        string_table_resolver: Some(resolver),
+        ..Default::default()
+    };
+    let resolved = read(&read_options);
+    assert_eq!(display_string(&resolved), Some(String::from("Iron Sword")));
+
+    // The resolved string is a cache, so it doesn't affect equality
+    assert_eq!(resolved, Property::TextProperty(property));
+}
+
+#[test]
+fn test_from_literals() {
+    assert_eq!(
+        Property::from(true),
+        Property::from(BoolProperty::new(true))
+    );
+    assert_eq!(Property::from(-1i8), Property::from(Int8Property::new(-1)));
+    assert_eq!(
+        Property::from(2u8),
+        Property::from(ByteProperty::new_byte(None, 2))
+    );
+    assert_eq!(
+        Property::from(-3i16),
+        Property::from(Int16Property::new(-3))
+    );
+    assert_eq!(Property::from(2649), Property::from(IntProperty::new(2649)));
+    assert_eq!(
+        Property::from(-5i64),
+        Property::from(Int64Property::new(-5))
+    );
+    assert_eq!(Property::from(6u16), Property::from(UInt16Property::new(6)));
+    assert_eq!(Property::from(7u32), Property::from(UInt32Property::new(7)));
+    assert_eq!(Property::from(8u64), Property::from(UInt64Property::new(8)));
+    assert_eq!(
+        Property::from(0.5f32),
+        Property::from(FloatProperty::new(0.5))
+    );
+    assert_eq!(
+        Property::from(0.25f64),
+        Property::from(DoubleProperty::new(0.25))
+    );
+    assert_eq!(Property::from("a"), Property::from(StrProperty::from("a")));
+    assert_eq!(
+        Property::from(String::from("b")),
+        Property::from(StrProperty::from("b"))
+    );
+
+    let mut options = PropertyOptions {
+        hints: &HashMap::new(),
+        properties_stack: &mut Vec::new(),
+        custom_versions: &HashableIndexMap::new(),
+        package_version: PackageVersion::default(),
+        read_options: &ReadOptions::default(),
+        write_options: &WriteOptions::default(),
+    };
+    for property in [
+        Property::from(vec![true, false]),
+        Property::from(vec![1u8, 2]),
+        Property::from(vec![1, 2, 3]),
+        Property::from(vec![0.5f32]),
+        Property::from(vec!["a", "b"]),
+        Property::from(vec![String::from("c")]),
+    ] {
+        let mut writer = Cursor::new(Vec::new());
+        property
+            .write(&mut writer, true, &mut options)
+            .expect("Write ArrayProperty");
+        let mut reader = Cursor::new(writer.into_inner());
+        let property_type = reader.read_string().expect("Read type");
+        let imported = Property::new(&mut reader, &property_type, true, &mut options, None)
+            .expect("Read ArrayProperty");
+        assert_eq!(imported, property);
+    }
+}
+
+#[test]
+fn test_property_groups() {
+    let int = Property::from(5);
+    assert_eq!(
+        int.as_scalar(),
+        Some(ScalarProperty::IntProperty(&IntProperty::new(5)))
+    );
+    assert_eq!(int.as_container(), None);
+    assert!(matches!(int.group(), PropertyGroup::Scalar(_)));
+
+    let array = Property::from(vec![1, 2]);
+    assert!(matches!(
+        array.as_container(),
+        Some(ContainerProperty::ArrayProperty(_))
+    ));
+    assert!(matches!(array.group(), PropertyGroup::Container(_)));
+
+    let value = Property::from(StructPropertyValue::VectorF(VectorF::new(1.0, 2.0, 3.0)));
+    assert!(matches!(
+        value.as_struct(),
+        Some(StructLikeProperty::StructPropertyValue(_))
+    ));
+    assert_eq!(value.as_scalar(), None);
+
+    let text = Property::from(TextProperty::new(FText::new_none(0, None)));
+    assert!(matches!(text.group(), PropertyGroup::Text(_)));
+
+    for property in [int, array, value, text] {
+        assert_eq!(Property::from(property.group()), property);
+    }
+}
+
+#[test]
+fn test_struct_value_accessors() {
+    let mut property = Property::from(StructPropertyValue::Vector2F(Vector2F::new(1.0, 2.0)));
+    let value = property.get_struct_value().expect("StructPropertyValue");
+    assert_eq!(value.get_vector2_f(), Some(&Vector2F::new(1.0, 2.0)));
+    assert_eq!(value.get_vector2_d(), None);
+
+    let value = property
+        .get_struct_value_mut()
+        .expect("StructPropertyValue");
+    *value = StructPropertyValue::Timespan(Timespan::new(10));
+    assert_eq!(value.get_timespan(), Some(&Timespan::new(10)));
+    *value = StructPropertyValue::LinearColor(LinearColor::new(0.0, 0.5, 1.0, 1.0));
+    assert_eq!(
+        value.get_linear_color(),
+        Some(&LinearColor::new(0.0, 0.5, 1.0, 1.0))
+    );
+    assert_eq!(property.get_struct(), None);
+}
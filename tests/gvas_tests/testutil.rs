@@ -0,0 +1,46 @@
+#![cfg(feature = "testutil")]
+
+use gvas::{assert_gvas_eq, game_version::GameVersion, properties::Property, testutil, GvasFile};
+use std::{fs::File, path::Path};
+
+use crate::common::SLOT1_PATH;
+
+fn read_slot1() -> GvasFile {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SLOT1_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file")
+}
+
+#[test]
+fn equal_files() {
+    let file = read_slot1();
+    assert_gvas_eq!(file, file.clone());
+    assert_eq!(testutil::describe_differences(&file, &file.clone()), "");
+}
+
+#[test]
+fn describe_differences() {
+    let left = read_slot1();
+    let mut right = left.clone();
+    right
+        .properties
+        .insert(String::from("int32_test"), Property::from(5));
+    right.properties.shift_remove("u8_test");
+
+    let description = testutil::describe_differences(&left, &right);
+    assert!(description.starts_with("--- u8_test\n-ByteProperty("));
+    assert!(description.contains(
+        "--- int32_test\n-IntProperty(\n-    -2147483647i32,\n-)\n+IntProperty(\n+    5i32,\n+)\n"
+    ));
+}
+
+#[test]
+#[should_panic(expected = "--- int32_test")]
+fn assert_gvas_eq_panics() {
+    let left = read_slot1();
+    let mut right = left.clone();
+    right
+        .properties
+        .insert(String::from("int32_test"), Property::from(5));
+    assert_gvas_eq!(left, right, "files differ");
+}
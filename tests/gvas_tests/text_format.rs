@@ -0,0 +1,171 @@
+use gvas::{
+    options::StringTableResolver,
+    properties::{
+        struct_types::DateTime,
+        text_format::ResolveContext,
+        text_property::{
+            DateTimeStyle, FText, FTextHistory, FormatArgumentValue, NumberFormattingOptions,
+            ResolvedString, RoundingMode, TransformType,
+        },
+    },
+    types::map::HashableIndexMap,
+};
+use ordered_float::OrderedFloat;
+
+fn text(value: &str) -> FText {
+    FText::new_none(0, Some(Some(String::from(value))))
+}
+
+fn history(history: FTextHistory) -> FText {
+    FText { flags: 0, history }
+}
+
+fn resolve(text: &FText) -> String {
+    text.resolve(&ResolveContext::default())
+}
+
+#[test]
+fn ordered_format() {
+    let formatted = history(FTextHistory::OrderedFormat {
+        source_format: Box::new(text("{0} found {1} gold in `{the cave`}{2}")),
+        arguments: vec![
+            FormatArgumentValue::Text(text("Alex")),
+            FormatArgumentValue::Int64(12000),
+        ],
+    });
+    assert_eq!(
+        resolve(&formatted),
+        "Alex found 12000 gold in {the cave}{2}"
+    );
+}
+
+#[test]
+fn named_format() {
+    let formatted = history(FTextHistory::ArgumentFormat {
+        source_format: Box::new(text(
+            "{count} {Count}|plural(one=life,other=\"lives, left\") at {Ratio}",
+        )),
+        arguments: HashableIndexMap::from([
+            (String::from("Count"), FormatArgumentValue::UInt(3)),
+            (
+                String::from("Ratio"),
+                FormatArgumentValue::Double(OrderedFloat(0.12345)),
+            ),
+        ]),
+    });
+    assert_eq!(resolve(&formatted), "3 lives, left at 0.123");
+}
+
+#[test]
+fn numbers() {
+    let number = |value, format_options| {
+        resolve(&history(FTextHistory::AsNumber {
+            source_value: Box::new(value),
+            format_options,
+            target_culture: None,
+        }))
+    };
+    assert_eq!(
+        number(FormatArgumentValue::Int(-1234567), None),
+        "-1,234,567"
+    );
+    assert_eq!(
+        number(FormatArgumentValue::Double(OrderedFloat(2.5)), None),
+        "2.5"
+    );
+
+    let options = NumberFormattingOptions {
+        always_include_sign: true,
+        use_grouping: false,
+        rounding_mode: RoundingMode::ToZero,
+        minimum_integral_digits: 3,
+        maximum_integral_digits: 100,
+        minimum_fractional_digits: 2,
+        maximum_fractional_digits: 2,
+    };
+    assert_eq!(
+        number(
+            FormatArgumentValue::Float(OrderedFloat(7.999)),
+            Some(options)
+        ),
+        "+007.99"
+    );
+
+    let percent = history(FTextHistory::AsPercent {
+        source_value: Box::new(FormatArgumentValue::Float(OrderedFloat(0.25))),
+        format_options: None,
+        target_culture: None,
+    });
+    assert_eq!(resolve(&percent), "25%");
+
+    let currency = history(FTextHistory::AsCurrency {
+        currency_code: Some(String::from("USD")),
+        source_value: Box::new(FormatArgumentValue::Int(1500)),
+        format_options: None,
+        target_culture: None,
+    });
+    assert_eq!(resolve(&currency), "$1,500.00");
+}
+
+#[test]
+fn dates() {
+    // 2024-03-05 14:07:09 UTC, a Tuesday
+    let date_time = DateTime {
+        ticks: 638_452_444_290_000_000,
+    };
+    let date = |date_style| {
+        resolve(&history(FTextHistory::AsDate {
+            date_time,
+            date_style,
+            target_culture: String::new(),
+        }))
+    };
+    assert_eq!(date(DateTimeStyle::Short), "3/5/24");
+    assert_eq!(date(DateTimeStyle::Medium), "Mar 5, 2024");
+    assert_eq!(date(DateTimeStyle::Long), "March 5, 2024");
+    assert_eq!(date(DateTimeStyle::Full), "Tuesday, March 5, 2024");
+
+    let date_time = history(FTextHistory::AsDateTime {
+        source_date_time: date_time,
+        date_style: DateTimeStyle::Short,
+        time_style: DateTimeStyle::Short,
+        time_zone: String::new(),
+        target_culture: String::new(),
+    });
+    assert_eq!(resolve(&date_time), "3/5/24 2:07 PM");
+}
+
+#[test]
+fn transforms_and_lookups() {
+    let upper = history(FTextHistory::Transform {
+        source_text: Box::new(text("Game Over")),
+        transform_type: TransformType::ToUpper,
+    });
+    assert_eq!(resolve(&upper), "GAME OVER");
+
+    let entry = history(FTextHistory::StringTableEntry {
+        table_id: Box::new(text("/Game/Items")),
+        key: String::from("Sword"),
+        display_string: ResolvedString::default(),
+    });
+    assert_eq!(resolve(&entry), "Sword");
+
+    let base = FText::new_base(
+        0,
+        Some(String::from("Menu")),
+        Some(String::from("Start")),
+        Some(String::from("Start")),
+    );
+    assert_eq!(resolve(&base), "Start");
+
+    let context = ResolveContext {
+        string_table_resolver: Some(StringTableResolver::new(|table_id, key| {
+            (table_id == "/Game/Items" && key == "Sword").then(|| String::from("Iron Sword"))
+        })),
+        localization_resolver: Some(StringTableResolver::new(|namespace, key| {
+            (namespace == "Menu" && key == "Start").then(|| String::from("Commencer"))
+        })),
+    };
+    assert_eq!(entry.resolve(&context), "Iron Sword");
+    assert_eq!(base.resolve(&context), "Commencer");
+}
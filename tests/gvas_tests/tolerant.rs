@@ -0,0 +1,134 @@
+use std::io::Cursor;
+
+use gvas::{
+    error::Error,
+    game_version::GameVersion,
+    hints::Hints,
+    options::ReadOptions,
+    parse_report::{ReportCollector, ReportReason},
+    properties::{int_property::IntProperty, str_property::StrProperty, Property},
+    seek_adapter::BufferedSeekAdapter,
+    GvasFile, GvasHeader,
+};
+
+/// A save whose `Name` property has the string length `length`.
+fn save(length: i32) -> Vec<u8> {
+    let file = GvasFile::builder(GvasHeader::new_ue4_27())
+        .save_game_class_name("/Script/Game.SaveGame")
+        .property("Level", IntProperty::new(3))
+        .property("Name", StrProperty::from("Player"))
+        .property("Gold", IntProperty::new(250))
+        .build()
+        .expect("Failed to build file");
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("Failed to write file");
+    let mut data = writer.into_inner();
+
+    let position = data
+        .windows(7)
+        .position(|window| window == b"Player\0")
+        .expect("Missing string value");
+    data[position - 4..position].copy_from_slice(&length.to_le_bytes());
+    data
+}
+
+fn read(
+    data: &[u8],
+    read_options: &ReadOptions,
+) -> Result<(GvasFile, Vec<(String, Error)>), Error> {
+    GvasFile::read_tolerant(
+        &mut Cursor::new(data),
+        GameVersion::Default,
+        Hints::new(),
+        read_options,
+    )
+}
+
+#[test]
+fn read_tolerant() -> Result<(), Error> {
+    // The string length runs past the end of the file
+    let data = save(i32::MAX);
+    assert!(GvasFile::read(&mut Cursor::new(&data), GameVersion::Default).is_err());
+
+    let (file, errors) = read(&data, &ReadOptions::default())?;
+    assert_eq!(
+        file.properties.keys().collect::<Vec<_>>(),
+        ["Level", "Name", "Gold"]
+    );
+    assert_eq!(
+        file.properties["Level"],
+        Property::from(IntProperty::new(3))
+    );
+    assert_eq!(
+        file.properties["Gold"],
+        Property::from(IntProperty::new(250))
+    );
+    assert!(matches!(
+        file.properties["Name"],
+        Property::UnknownProperty(_)
+    ));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, "Name");
+
+    // Failed properties are written back unchanged
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer)?;
+    assert_eq!(writer.into_inner(), data);
+
+    // Valid saves read the same with and without recovery
+    let (valid, errors) = read(&save(7), &ReadOptions::default())?;
+    assert!(errors.is_empty());
+    assert_eq!(
+        valid.properties["Name"],
+        Property::from(StrProperty::from("Player"))
+    );
+    Ok(())
+}
+
+#[test]
+fn recover_errors_report() -> Result<(), Error> {
+    // The string length runs past the end of the file
+    let data = save(i32::MAX);
+    let report = ReportCollector::new();
+    let read_options = ReadOptions {
+        recover_errors: true,
+        report: Some(report.clone()),
+        ..Default::default()
+    };
+    GvasFile::read_with_options(
+        &mut Cursor::new(&data),
+        GameVersion::Default,
+        Hints::new(),
+        &read_options,
+    )?;
+    assert_eq!(report.take_errors().len(), 1);
+    let report = report.take();
+    let entry = report
+        .entries
+        .iter()
+        .find(|entry| entry.reason == ReportReason::FailedProperty)
+        .expect("Missing report entry");
+    assert_eq!(entry.path, "Name");
+    assert_eq!(report.lost().count(), 0);
+    Ok(())
+}
+
+#[test]
+fn read_tolerant_without_seeking() -> Result<(), Error> {
+    // The string length runs past the end of the file
+    let data = save(i32::MAX);
+    let mut reader = BufferedSeekAdapter::with_lookback(Cursor::new(&data), 8);
+    let (file, errors) = GvasFile::read_tolerant(
+        &mut reader,
+        GameVersion::Default,
+        Hints::new(),
+        &ReadOptions::default(),
+    )?;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, "Name");
+
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer)?;
+    assert_eq!(writer.into_inner(), data);
+    Ok(())
+}
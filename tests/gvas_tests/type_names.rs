@@ -0,0 +1,48 @@
+use gvas::properties::{
+    property_kind::PropertyKind,
+    type_names::{self, PROPERTY_TYPE_NAMES, STRUCT_TYPE_NAMES},
+};
+
+#[test]
+fn type_names_sorted() {
+    assert!(PROPERTY_TYPE_NAMES.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(STRUCT_TYPE_NAMES.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(PROPERTY_TYPE_NAMES
+        .iter()
+        .all(|name| !STRUCT_TYPE_NAMES.contains(name)));
+}
+
+#[test]
+fn type_names_cover_property_kinds() {
+    for kind in PropertyKind::ALL {
+        assert!(PROPERTY_TYPE_NAMES.contains(&kind.as_str()), "{kind}");
+    }
+    for name in PROPERTY_TYPE_NAMES {
+        assert!(name.ends_with("Property"), "{name}");
+        if let Some(kind) = PropertyKind::from_type_name(name) {
+            assert_eq!(kind.as_str(), name);
+        }
+    }
+}
+
+#[test]
+fn type_names_match() {
+    let kind = |name: &str| match name {
+        type_names::INT_PROPERTY => Some(PropertyKind::IntProperty),
+        type_names::MULTICAST_SPARSE_DELEGATE_PROPERTY => {
+            Some(PropertyKind::MulticastSparseDelegateProperty)
+        }
+        type_names::UINT64_PROPERTY => Some(PropertyKind::UInt64Property),
+        _ => None,
+    };
+    assert_eq!(kind("IntProperty"), Some(PropertyKind::IntProperty));
+    assert_eq!(
+        kind("MulticastSparseDelegateProperty"),
+        Some(PropertyKind::MulticastSparseDelegateProperty)
+    );
+    assert_eq!(kind("UInt64Property"), Some(PropertyKind::UInt64Property));
+    assert_eq!(kind(type_names::VECTOR2D), None);
+    assert_eq!(type_names::VECTOR2D, "Vector2D");
+    assert_eq!(type_names::INT_VECTOR4, "IntVector4");
+    assert_eq!(type_names::GAMEPLAY_TAG_CONTAINER, "GameplayTagContainer");
+}
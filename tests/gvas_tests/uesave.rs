@@ -0,0 +1,287 @@
+#![cfg(feature = "uesave")]
+
+use std::{fs::File, io::Cursor};
+
+use crate::common::{DELEGATE_PATH, PACKAGE_VERSION_525_PATH, SLOT1_PATH, SLOT2_PATH};
+use gvas::{
+    error::{Error, SerializeError},
+    game_version::GameVersion,
+    properties::{
+        array_property::ArrayProperty,
+        int_property::{ByteProperty, IntProperty},
+        map_property::MapProperty,
+        set_property::SetProperty,
+        str_property::StrProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        struct_types::{VectorD, VectorF},
+        Property,
+    },
+    types::{map::HashableIndexMap, Guid},
+    uesave, GvasFile, GvasHeader,
+};
+use serde_json::json;
+
+fn read(path: &str) -> GvasFile {
+    let mut file = File::open(path).expect("Failed to open test asset");
+    GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file")
+}
+
+fn write(file: &GvasFile) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+    file.write(&mut writer).expect("Failed to write file");
+    writer.into_inner()
+}
+
+fn assert_invalid(result: Result<impl std::fmt::Debug, Error>) {
+    let error = result.expect_err("Invalid value converted");
+    assert!(
+        matches!(error.as_serialize(), Some(SerializeError::InvalidValue(_))),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn round_trip_saves() {
+    for path in [SLOT1_PATH, PACKAGE_VERSION_525_PATH] {
+        let file = read(path);
+        let value = uesave::to_value(&file).expect("Failed to convert to uesave");
+        let read = uesave::from_value(&value).expect("Failed to convert from uesave");
+        assert_eq!(read, file, "{path}");
+        assert_eq!(
+            write(&read),
+            std::fs::read(path).expect("Read asset"),
+            "{path}"
+        );
+    }
+}
+
+#[test]
+fn slot1_document() {
+    let value = uesave::to_value(&read(SLOT1_PATH)).expect("Failed to convert to uesave");
+    let header = &value["header"];
+    assert_eq!(header["magic"], 0x53415647);
+    assert_eq!(header["save_game_version"], 2);
+    assert_eq!(
+        header["package_version"],
+        json!({ "ue4": 522, "ue5": null })
+    );
+    assert_eq!(header["engine_version"], "++UE4+Release-4.27");
+    assert_eq!(value["extra"], json!([0, 0, 0, 0]));
+
+    let properties = &value["root"]["properties"];
+    assert_eq!(
+        value["root"]["save_game_type"],
+        "/Script/UE4SaveFile.TestSaveGame"
+    );
+    assert_eq!(
+        properties["u8_test"],
+        json!({ "Byte": { "value": { "Byte": 129 }, "enum_type": "None" } })
+    );
+    assert_eq!(
+        properties["int32_test"],
+        json!({ "Int": { "value": -2147483647 } })
+    );
+    assert_eq!(
+        properties["struct_property"],
+        json!({
+            "Struct": {
+                "value": { "Struct": { "test_field": { "UInt64": { "value": 12345 } } } },
+                "struct_type": { "Struct": "CustomStruct" },
+                "struct_id": "00000000-0000-0000-0000-000000000000",
+            }
+        })
+    );
+    assert_eq!(
+        properties["date_time_property"]["Struct"]["value"],
+        json!({ "DateTime": 637864237380020000u64 })
+    );
+}
+
+#[test]
+fn read_document() {
+    let header = GvasHeader::new_ue5_3()
+        .save_game_class_name("/Script/Game.SaveGame")
+        .build()
+        .expect("Failed to build header");
+    let mut value = uesave::to_value(&GvasFile {
+        deserialized_game_version: Default::default(),
+        prefix: Vec::new(),
+        header,
+        custom_version_extra: Vec::new(),
+        properties: HashableIndexMap::new(),
+        trailing_padding: None,
+        trailing_bytes: Vec::new(),
+    })
+    .expect("Failed to convert header");
+    value["root"]["properties"] = json!({
+        "Level": { "Int": { "value": 3 } },
+        "Location": {
+            "Struct": {
+                "value": { "Vector": { "x": 1.0, "y": 2.5, "z": -3.0 } },
+                "struct_type": "Vector",
+                "struct_id": "00000000-0000-0000-0000-000000000000",
+            }
+        },
+        "Items": {
+            "Array": {
+                "array_type": "StrProperty",
+                "value": { "Base": { "Str": ["Sword", null] } },
+            }
+        },
+        "Flags": {
+            "Array": {
+                "array_type": "ByteProperty",
+                "value": { "Base": { "Byte": { "Label": ["EFlag::A", "EFlag::B"] } } },
+            }
+        },
+        "Visited": {
+            "Set": {
+                "set_type": "IntProperty",
+                "value": { "Base": { "Int": [1, 5] } },
+            }
+        },
+        "Scores": {
+            "Map": {
+                "key_type": "StrProperty",
+                "value_type": "IntProperty",
+                "value": [
+                    { "key": { "Str": "Alice" }, "value": { "Int": 10 } },
+                    { "key": { "Str": "Bob" }, "value": { "Int": 7 } },
+                ],
+            }
+        },
+        "Points": {
+            "Array": {
+                "array_type": "StructProperty",
+                "value": {
+                    "Struct": {
+                        "_type": "StructProperty",
+                        "name": "Points",
+                        "struct_type": "Vector",
+                        "id": "00000000-0000-0000-0000-000000000000",
+                        "value": [{ "Vector": { "x": 0.0, "y": 0.0, "z": 1.0 } }],
+                    }
+                },
+            }
+        },
+    });
+
+    let file = uesave::from_value(&value).expect("Failed to convert from uesave");
+    let properties = &file.properties;
+    assert_eq!(properties["Level"], Property::from(IntProperty::new(3)));
+    // Vectors have double components with large world coordinates
+    assert_eq!(
+        properties["Location"],
+        Property::from(StructProperty::new(
+            Guid::default(),
+            String::from("Vector"),
+            StructPropertyValue::from(VectorD::new(1.0, 2.5, -3.0)),
+        ))
+    );
+    assert_eq!(
+        properties["Items"],
+        Property::from(ArrayProperty::Strings {
+            strings: vec![Some(String::from("Sword")), None],
+        })
+    );
+    assert_eq!(
+        properties["Flags"],
+        Property::from(ArrayProperty::Properties {
+            property_type: String::from("ByteProperty"),
+            properties: vec![
+                Property::from(ByteProperty::new_namespaced(None, String::from("EFlag::A"))),
+                Property::from(ByteProperty::new_namespaced(None, String::from("EFlag::B"))),
+            ],
+        })
+    );
+    assert_eq!(
+        properties["Visited"],
+        Property::from(SetProperty::new(
+            String::from("IntProperty"),
+            0,
+            vec![Property::from(1), Property::from(5)],
+        ))
+    );
+    assert_eq!(
+        properties["Scores"],
+        Property::from(MapProperty::StrInt {
+            str_ints: HashableIndexMap::from([
+                (String::from("Alice"), 10),
+                (String::from("Bob"), 7),
+            ]),
+        })
+    );
+    assert_eq!(
+        properties["Points"],
+        Property::from(ArrayProperty::Structs {
+            field_name: String::from("Points"),
+            type_name: String::from("Vector"),
+            guid: Guid::default(),
+            structs: vec![StructPropertyValue::from(VectorD::new(0.0, 0.0, 1.0))],
+        })
+    );
+
+    // Written and read back unchanged
+    let bytes = write(&file);
+    let read = GvasFile::read(&mut Cursor::new(bytes), GameVersion::Default)
+        .expect("Failed to read converted file");
+    assert_eq!(read, file);
+    assert_eq!(uesave::to_value(&read).expect("Failed to convert"), value);
+}
+
+#[test]
+fn single_precision_vectors() {
+    let file = GvasFile::builder(GvasHeader::new_ue4_27())
+        .save_game_class_name("/Script/Game.SaveGame")
+        .property(
+            "Location",
+            StructProperty::new(
+                Guid::default(),
+                String::from("Vector"),
+                StructPropertyValue::from(VectorF::new(1.0, 2.0, 3.0)),
+            ),
+        )
+        .property("Name", StrProperty::from("Player"))
+        .build()
+        .expect("Failed to build file");
+    let value = uesave::to_value(&file).expect("Failed to convert to uesave");
+    assert_eq!(
+        uesave::from_value(&value).expect("Failed to convert from uesave"),
+        file
+    );
+}
+
+#[test]
+fn unsupported_properties() {
+    // Text and delegate properties have no uesave representation
+    assert_invalid(uesave::to_value(&read(SLOT2_PATH)));
+    assert_invalid(uesave::to_value(&read(DELEGATE_PATH)));
+
+    let mut file = read(SLOT1_PATH);
+    file.prefix = vec![1, 2, 3];
+    assert_invalid(uesave::to_value(&file));
+}
+
+#[test]
+fn invalid_documents() {
+    let value = uesave::to_value(&read(SLOT1_PATH)).expect("Failed to convert to uesave");
+
+    let mut missing_root = value.clone();
+    missing_root
+        .as_object_mut()
+        .expect("document is an object")
+        .remove("root");
+    assert_invalid(uesave::from_value(&missing_root));
+
+    let mut bad_magic = value.clone();
+    bad_magic["header"]["magic"] = json!(1);
+    assert_invalid(uesave::from_value(&bad_magic));
+
+    let mut out_of_range = value.clone();
+    out_of_range["root"]["properties"]["i8_test"] = json!({ "Int8": { "value": 300 } });
+    assert_invalid(uesave::from_value(&out_of_range));
+
+    let mut unknown = value;
+    unknown["root"]["properties"]["i8_test"] = json!({ "Unknown": { "value": 1 } });
+    assert_invalid(uesave::from_value(&unknown));
+}
@@ -0,0 +1,125 @@
+use std::{collections::HashMap, fs::File, path::Path};
+
+use crate::common::{SLOT1_PATH, TRANSFORM_PATH};
+use gvas::{
+    game_version::GameVersion,
+    properties::{
+        int_property::{IntProperty, UInt64Property},
+        struct_property::{StructProperty, StructPropertyValue},
+        struct_types::VectorD,
+        Property,
+    },
+    types::{map::HashableIndexMap, Guid},
+    visitor::{walk, walk_mut, Visitor, VisitorMut},
+    GvasFile,
+};
+
+#[derive(Default)]
+struct Paths {
+    properties: Vec<String>,
+    structs: Vec<String>,
+}
+
+impl Visitor for Paths {
+    fn visit_property(&mut self, path: &str, _property: &Property) -> bool {
+        self.properties.push(path.to_string());
+        true
+    }
+
+    fn visit_struct(&mut self, path: &str, _value: &StructPropertyValue) -> bool {
+        self.structs.push(path.to_string());
+        path != "Root.Skipped"
+    }
+}
+
+fn custom_struct(fields: Vec<(&str, Property)>) -> Property {
+    let fields = fields
+        .into_iter()
+        .map(|(name, property)| (name.to_string(), vec![property]))
+        .collect::<Vec<_>>();
+    Property::from(StructProperty::new(
+        Guid::default(),
+        String::from("Custom"),
+        StructPropertyValue::CustomStruct(HashableIndexMap(fields.into_iter().collect())),
+    ))
+}
+
+#[test]
+fn visit_paths() {
+    let property = custom_struct(vec![
+        ("Value", Property::from(IntProperty::new(1))),
+        (
+            "Nested",
+            custom_struct(vec![("Value", Property::from(IntProperty::new(2)))]),
+        ),
+        (
+            "Skipped",
+            custom_struct(vec![("Value", Property::from(IntProperty::new(3)))]),
+        ),
+    ]);
+
+    let mut paths = Paths::default();
+    walk(&mut paths, "Root", &property);
+    assert_eq!(
+        paths.properties,
+        [
+            "Root",
+            "Root.Value",
+            "Root.Nested",
+            "Root.Nested.Value",
+            "Root.Skipped"
+        ]
+    );
+    assert_eq!(paths.structs, ["Root", "Root.Nested", "Root.Skipped"]);
+}
+
+struct Offset(f64);
+
+impl VisitorMut for Offset {
+    fn visit_struct(&mut self, _path: &str, value: &mut StructPropertyValue) -> bool {
+        if let StructPropertyValue::VectorD(vector) = value {
+            *vector = VectorD::new(vector.x.0 + self.0, vector.y.0, vector.z.0);
+        }
+        true
+    }
+}
+
+#[test]
+fn visit_file() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(TRANSFORM_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    let mut file =
+        GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file");
+
+    let mut paths = Paths::default();
+    file.walk(&mut paths);
+    assert!(paths
+        .structs
+        .contains(&String::from("playerlocationarray[2]")));
+
+    let before = file.properties["playerlocationarray"].clone();
+    file.walk_mut(&mut Offset(10.0));
+    let mut property = before;
+    walk_mut(&mut Offset(10.0), "playerlocationarray", &mut property);
+    assert_eq!(file.properties["playerlocationarray"], property);
+}
+
+#[test]
+fn iter_leaves() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SLOT1_PATH);
+    let mut file = File::open(path).expect("Failed to open test asset");
+    let file = GvasFile::read(&mut file, GameVersion::Default).expect("Failed to parse gvas file");
+
+    let leaves = file.iter_leaves().collect::<HashMap<_, _>>();
+    assert_eq!(
+        leaves.get("struct_property.test_field"),
+        Some(&&Property::from(UInt64Property::new(12345)))
+    );
+    assert_eq!(
+        leaves.get("array_of_structs[1].test_field"),
+        Some(&&Property::from(UInt64Property::new(10)))
+    );
+    assert!(leaves.contains_key("date_time_property"));
+    assert!(!leaves.contains_key("struct_property"));
+    assert!(!leaves.contains_key("array_of_structs"));
+}
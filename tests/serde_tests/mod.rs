@@ -1,2 +1,3 @@
+mod serde_guid_policy;
 mod serde_json_round_trip;
 mod serde_json_template;
@@ -0,0 +1,134 @@
+use crate::common::SLOT1_PATH;
+use gvas::{
+    game_version::GameVersion,
+    properties::{
+        array_property::ArrayProperty,
+        struct_property::{StructProperty, StructPropertyValue},
+        Property,
+    },
+    types::{Guid, GuidPolicy, WithGuidPolicy},
+    GvasFile,
+};
+use serde_json::{json, Value};
+use std::{fs::File, path::Path};
+
+fn struct_property(guid: Guid) -> Property {
+    Property::from(StructProperty::new(
+        guid,
+        "Guid",
+        StructPropertyValue::Guid(Guid::from_u128(1)),
+    ))
+}
+
+fn struct_array(guid: Guid) -> Property {
+    Property::ArrayProperty(ArrayProperty::Structs {
+        field_name: String::from("fn"),
+        type_name: String::from("tn"),
+        guid,
+        structs: vec![],
+    })
+}
+
+fn guid_json(property: &Property, policy: GuidPolicy) -> Option<Value> {
+    let json = serde_json::to_value(WithGuidPolicy::new(property, policy)).expect("to_value");
+    json.get("guid").cloned()
+}
+
+#[test]
+fn zero_guid_output() {
+    for property in [
+        struct_property(Guid::default()),
+        struct_array(Guid::default()),
+    ] {
+        assert_eq!(guid_json(&property, GuidPolicy::SkipZero), None);
+        assert_eq!(guid_json(&property, GuidPolicy::Always), Some(json!("0")));
+        assert_eq!(guid_json(&property, GuidPolicy::Null), Some(Value::Null));
+    }
+}
+
+#[test]
+fn nonzero_guid_output() {
+    let guid = Guid::from_u128(2);
+    for property in [struct_property(guid), struct_array(guid)] {
+        for policy in [GuidPolicy::SkipZero, GuidPolicy::Always, GuidPolicy::Null] {
+            assert_eq!(
+                guid_json(&property, policy),
+                Some(json!(guid.to_string())),
+                "{policy:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn zero_guid_input() {
+    for property in [
+        struct_property(Guid::default()),
+        struct_array(Guid::default()),
+    ] {
+        let mut json = serde_json::to_value(&property).expect("to_value");
+        for guid in [Value::Null, json!("0")] {
+            json["guid"] = guid;
+            assert_eq!(
+                serde_json::from_value::<Property>(json.clone()).expect("from_value"),
+                property
+            );
+        }
+    }
+}
+
+#[test]
+fn guid_after_type() {
+    let property = struct_property(Guid::default());
+    let json =
+        serde_json::to_value(WithGuidPolicy::new(&property, GuidPolicy::Always)).expect("to_value");
+    let keys = json
+        .as_object()
+        .expect("object")
+        .keys()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    assert_eq!(&keys[..2], ["type", "guid"]);
+
+    // Only the wrapped value uses the policy
+    assert_eq!(
+        serde_json::to_value(&property)
+            .expect("to_value")
+            .get("guid"),
+        None
+    );
+}
+
+/// Returns the number of struct properties in `value`, checking that each has a guid.
+fn count_struct_guids(value: &Value) -> usize {
+    match value {
+        Value::Object(object) => {
+            let own = match object.get("type") == Some(&json!("StructProperty")) {
+                true => {
+                    assert!(object.contains_key("guid"), "{object:?}");
+                    1
+                }
+                false => 0,
+            };
+            own + object.values().map(count_struct_guids).sum::<usize>()
+        }
+        Value::Array(array) => array.iter().map(count_struct_guids).sum(),
+        _ => 0,
+    }
+}
+
+#[test]
+fn file_round_trip() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SLOT1_PATH);
+    let mut file = File::open(path).expect("Open test asset");
+    let file = GvasFile::read(&mut file, GameVersion::Default).expect("Parse gvas file");
+    for policy in [GuidPolicy::SkipZero, GuidPolicy::Always, GuidPolicy::Null] {
+        let json = serde_json::to_string(&WithGuidPolicy::new(&file, policy)).expect("Serialize");
+        if policy != GuidPolicy::SkipZero {
+            let value = serde_json::from_str::<Value>(&json).expect("Parse");
+            assert!(count_struct_guids(&value) > 0);
+        }
+        let from_json = serde_json::from_str::<GvasFile>(&json).expect("Deserialize");
+        assert_eq!(file, from_json, "{policy:?}");
+    }
+}
@@ -3,7 +3,9 @@ use gvas::{
     game_version::GameVersion,
     properties::{
         array_property::ArrayProperty,
-        delegate_property::{Delegate, DelegateProperty},
+        delegate_property::{
+            Delegate, DelegateProperty, MulticastInlineDelegateProperty, MulticastScriptDelegate,
+        },
         enum_property::EnumProperty,
         field_path_property::{FieldPath, FieldPathProperty},
         int_property::{
@@ -16,14 +18,14 @@ use gvas::{
         object_property::ObjectProperty,
         set_property::SetProperty,
         str_property::StrProperty,
-        struct_property::StructPropertyValue,
+        struct_property::{StructProperty, StructPropertyValue},
         struct_types::{
-            DateTime, IntPoint, LinearColor, QuatD, QuatF, RotatorD, RotatorF, Timespan, VectorD,
-            VectorF,
+            DateTime, Int64Vector, IntPoint, IntVector, LinearColor, QuatD, QuatF, RotatorD,
+            RotatorF, Timespan, VectorD, VectorF,
         },
         text_property::{
             DateTimeStyle, FText, FTextHistory, FormatArgumentValue, NumberFormattingOptions,
-            RoundingMode, TextProperty, TransformType,
+            ResolvedString, RoundingMode, TextProperty, TransformType,
         },
         unknown_property::UnknownProperty,
         Property,
@@ -56,6 +58,17 @@ where
     );
 }
 
+/// Deserialize hand-written JSON that leaves out fields with defaults.
+fn minimal_json<T>(json: &str, value: &T)
+where
+    T: Debug + for<'a> Deserialize<'a> + PartialEq,
+{
+    assert_eq!(
+        &serde_json::from_str::<T>(json).expect("serde_json::from_str"),
+        value
+    );
+}
+
 fn file<P: AsRef<Path>>(path: P, json: &str) {
     file_with_hints(path, &HashMap::new(), json)
 }
@@ -1155,6 +1168,7 @@ fn name_array_index() {
         &Property::NameProperty(NameProperty {
             array_index: 1,
             value: None,
+            empty_body: false,
         }),
         r#"{
   "type": "NameProperty",
@@ -1169,6 +1183,7 @@ fn name_none() {
         &Property::NameProperty(NameProperty {
             array_index: 0,
             value: None,
+            empty_body: false,
         }),
         r#"{
   "type": "NameProperty"
@@ -1355,13 +1370,27 @@ fn set_int() {
 #[test]
 fn str_none() {
     serde_json(
-        &Property::StrProperty(StrProperty { value: None }),
+        &Property::StrProperty(StrProperty::new(None)),
         r#"{
   "type": "StrProperty"
 }"#,
     )
 }
 
+#[test]
+fn str_empty_body() {
+    serde_json(
+        &Property::StrProperty(StrProperty {
+            value: None,
+            empty_body: true,
+        }),
+        r#"{
+  "type": "StrProperty",
+  "empty_body": true
+}"#,
+    )
+}
+
 #[test]
 fn str_some() {
     serde_json(
@@ -1519,6 +1548,36 @@ fn struct_intpoint() {
     )
 }
 
+#[test]
+fn struct_intvector() {
+    serde_json(
+        &Property::from(StructPropertyValue::from(IntVector::new(0, 1, 2))),
+        r#"{
+  "type": "StructPropertyValue",
+  "IntVector": {
+    "x": 0,
+    "y": 1,
+    "z": 2
+  }
+}"#,
+    )
+}
+
+#[test]
+fn struct_int64vector() {
+    serde_json(
+        &Property::from(StructPropertyValue::from(Int64Vector::new(-1, 0, i64::MAX))),
+        r#"{
+  "type": "StructPropertyValue",
+  "Int64Vector": {
+    "x": -1,
+    "y": 0,
+    "z": 9223372036854775807
+  }
+}"#,
+    )
+}
+
 #[test]
 fn struct_custom() {
     serde_json(
@@ -1551,11 +1610,13 @@ fn struct_array_index() {
                 vec![
                     Property::NameProperty(NameProperty {
                         array_index: 0,
-                        value: Some(String::from("QU91_InvestigateTower_B2")),
+                        value: Some("QU91_InvestigateTower_B2".into()),
+                        empty_body: false,
                     }),
                     Property::NameProperty(NameProperty {
                         array_index: 1,
-                        value: Some(String::from("QU91_InvestigateTower_B2")),
+                        value: Some("QU91_InvestigateTower_B2".into()),
+                        empty_body: false,
                     }),
                 ],
             ),
@@ -1944,6 +2005,34 @@ fn text_transform() {
     );
 }
 
+#[test]
+fn text_transform_other() {
+    serde_json(
+        &Property::TextProperty(TextProperty::new(FText {
+            flags: 0,
+            history: FTextHistory::Transform {
+                source_text: Box::new(FText {
+                    flags: 1,
+                    history: FTextHistory::None {
+                        culture_invariant_string: None,
+                    },
+                }),
+                transform_type: TransformType::Other(7),
+            },
+        })),
+        r#"{
+  "type": "TextProperty",
+  "history": "Transform",
+  "source_text": {
+    "flags": 1,
+    "history": "None"
+  },
+  "transform": "Other",
+  "value": 7
+}"#,
+    );
+}
+
 #[test]
 fn unknown() {
     serde_json(
@@ -1959,3 +2048,104 @@ fn unknown() {
 }"#,
     )
 }
+
+#[test]
+fn minimal_array_struct() {
+    minimal_json(
+        r#"{
+  "type": "ArrayProperty",
+  "field_name": "fn",
+  "type_name": "tn",
+  "structs": []
+}"#,
+        &Property::ArrayProperty(ArrayProperty::Structs {
+            field_name: String::from("fn"),
+            type_name: String::from("tn"),
+            guid: Guid::default(),
+            structs: vec![],
+        }),
+    )
+}
+
+#[test]
+fn minimal_map() {
+    minimal_json(
+        r#"{
+  "type": "MapProperty",
+  "key_type": "IntProperty",
+  "value_type": "IntProperty",
+  "value": []
+}"#,
+        &Property::MapProperty(MapProperty::new(
+            String::from("IntProperty"),
+            String::from("IntProperty"),
+            0,
+            HashableIndexMap::new(),
+        )),
+    )
+}
+
+#[test]
+fn minimal_multicast_delegate() {
+    minimal_json(
+        r#"{
+  "type": "MulticastInlineDelegateProperty",
+  "value": {}
+}"#,
+        &Property::MulticastInlineDelegateProperty(MulticastInlineDelegateProperty::new(
+            MulticastScriptDelegate::new(vec![]),
+        )),
+    )
+}
+
+#[test]
+fn minimal_set() {
+    minimal_json(
+        r#"{
+  "type": "SetProperty",
+  "property_type": "IntProperty",
+  "properties": []
+}"#,
+        &Property::SetProperty(SetProperty::new(String::from("IntProperty"), 0, vec![])),
+    )
+}
+
+#[test]
+fn minimal_struct() {
+    minimal_json(
+        r#"{
+  "type": "StructProperty",
+  "type_name": "DateTime",
+  "DateTime": {
+    "ticks": 1
+  }
+}"#,
+        &Property::StructProperty(StructProperty::new(
+            Guid::default(),
+            String::from("DateTime"),
+            StructPropertyValue::from(DateTime { ticks: 1 }),
+        )),
+    )
+}
+
+#[test]
+fn minimal_text() {
+    minimal_json(
+        r#"{
+  "type": "TextProperty",
+  "history": "StringTableEntry",
+  "table_id": {
+    "history": "None"
+  },
+  "key": "k"
+}"#,
+        &Property::TextProperty(TextProperty::new(FText {
+            flags: 0,
+            history: FTextHistory::StringTableEntry {
+                table_id: Box::new(FText::new_none(0, Some(None))),
+                key: String::from("k"),
+                display_string: ResolvedString::default(),
+            },
+        })),
+    )
+}